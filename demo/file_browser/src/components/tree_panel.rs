@@ -8,7 +8,8 @@ use std::sync::Arc;
 use gpui::{AnyElement, IntoElement, ParentElement, Styled, Window, div, px};
 
 use yororen_ui::component::{
-    SelectionMode, TreeNode, TreeState, button, context_menu_trigger, empty_state, label, tree,
+    SelectionMode, TreeNode, TreeState, button, context_menu_trigger, empty_state, label,
+    linear_progress, tree,
 };
 use yororen_ui::theme::{ActionVariantKind, Theme};
 use yororen_ui::widget::virtual_list_state;
@@ -62,11 +63,12 @@ impl FileBrowserTreePanel {
         } else {
             let list_state =
                 virtual_list_state(tree_nodes.len(), gpui::ListAlignment::Top, px(32.));
-            tree(TreeState::new(), &tree_nodes)
+            tree(TreeState::new(), tree_nodes)
                 .id("file-browser:tree")
                 .virtualized(true)
                 .list_state(list_state)
                 .selection_mode(SelectionMode::Single)
+                .row_tooltip_on_overflow(true)
                 .on_item_click(|id, _ev, _window, cx| {
                     let path = PathBuf::from(id.to_string());
                     let state = cx.global::<FileBrowserState>();
@@ -117,6 +119,11 @@ impl FileBrowserTreePanel {
                     cx.notify(id);
                 }
             })
+            .child(
+                linear_progress("file-browser:scan-progress")
+                    .active(is_scanning)
+                    .mb_2(),
+            )
             .child(tree_view)
             .into_any_element()
     }
@@ -15,7 +15,7 @@ use std::time::Duration;
 
 use gpui::Window;
 
-use yororen_ui::component::{ArcTreeNode, TreeCheckedState, TreeNode};
+use yororen_ui::component::{ArcTreeNode, TreeCheckedState, TreeNode, folders_first};
 
 use crate::state::{notify_file_browser, FileBrowserState};
 
@@ -42,18 +42,13 @@ fn set_children_by_id(nodes: &mut [TreeNode], parent_id: &str, children: Vec<Tre
 /// - TreeNode: The UI representation of the file/directory
 /// - Option<PathBuf>: Some(path) if it's a directory (for further scanning), None otherwise
 ///
-/// Directories are sorted first, then files, both alphabetically.
+/// Directories are sorted first, then files, both alphabetically ignoring case.
 fn read_dir_nodes(dir: &Path) -> Vec<(TreeNode, Option<PathBuf>)> {
     let Ok(read_dir) = fs::read_dir(dir) else {
         return Vec::new();
     };
 
-    let mut entries: Vec<_> = read_dir.filter_map(|e| e.ok()).collect();
-    entries.sort_by_key(|e| {
-        let ty = e.file_type().ok();
-        let is_dir = ty.map(|t| t.is_dir()).unwrap_or(false);
-        (if is_dir { 0 } else { 1 }, e.file_name())
-    });
+    let entries: Vec<_> = read_dir.filter_map(|e| e.ok()).collect();
 
     let mut out = Vec::with_capacity(entries.len());
     for entry in entries {
@@ -83,10 +78,12 @@ fn read_dir_nodes(dir: &Path) -> Vec<(TreeNode, Option<PathBuf>)> {
             checked: TreeCheckedState::Unchecked,
             depth: 0,
             has_children: is_dir,
+            loading: false,
         };
         out.push((node, is_dir.then_some(path)));
     }
 
+    out.sort_by(|(a, _), (b, _)| folders_first(a, b));
     out
 }
 
@@ -12,6 +12,15 @@ pub fn load_demo_i18n(locale: Locale) -> Result<I18n, LoadError> {
     Ok(i18n)
 }
 
+/// Reload `locales/<locale>.json` into the global `I18n` whenever it is saved.
+#[cfg(feature = "hot-reload")]
+pub fn watch_demo_locales(cx: &mut gpui::App) {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("locales");
+    yororen_ui::i18n::FileLoader::new(dir.to_string_lossy())
+        .watch(cx)
+        .detach();
+}
+
 fn load_demo_translation_map(locale: &Locale) -> Result<TranslationMap, LoadError> {
     let filename = if let Some(region) = locale.region() {
         format!("{}-{}.json", locale.language(), region)
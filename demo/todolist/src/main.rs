@@ -103,6 +103,9 @@ fn main() {
         // out of the core library locales.
         // Try to change this to zh-CN or ar
         cx.set_global(i18n::load_demo_i18n(Locale::new("en").unwrap()).unwrap());
+        // Run with `--features hot-reload` to pick up edits to the demo locale files live.
+        #[cfg(feature = "hot-reload")]
+        i18n::watch_demo_locales(cx);
 
         // RECOMMENDED: Set up global application state
         // Use Global trait + Arc<Mutex<T>> for shared state
@@ -120,7 +123,9 @@ fn main() {
 
         // Open window and render root component
         // cx.new() creates a new entity with the given closure as its impl
-        cx.open_window(options, |_, cx| {
+        cx.open_window(options, |window, cx| {
+            // Follow OS light/dark changes while the theme mode is `ThemeMode::System`
+            GlobalTheme::observe_system_appearance(window).detach();
             cx.new(|cx| todo_app::TodoApp::new(cx))
         }).unwrap();
     });
@@ -72,6 +72,8 @@ pub enum Role {
     Slider,
     /// Identifies a scrollbar element.
     Scrollbar,
+    /// Identifies a divider between sections, focusable when it can be moved.
+    Separator,
     /// Identifies generic content.
     Group,
     /// Identifies a generic presentation (no semantics).
@@ -112,6 +114,7 @@ impl Role {
             Role::Progressbar => "progressbar",
             Role::Slider => "slider",
             Role::Scrollbar => "scrollbar",
+            Role::Separator => "separator",
             Role::Group => "group",
             Role::Presentation => "presentation",
         }
@@ -497,6 +500,23 @@ pub mod combobox {
     }
 }
 
+/// Attributes for a text field.
+pub mod textbox {
+    /// Creates aria attributes for a text field, optionally described by help text.
+    pub fn attrs(multiline: bool, describedby: Option<&str>) -> Vec<(String, String)> {
+        let mut attrs = vec![
+            ("role".to_string(), "textbox".to_string()),
+            ("aria-multiline".to_string(), multiline.to_string()),
+        ];
+
+        if let Some(id) = describedby {
+            attrs.push(("aria-describedby".to_string(), id.to_string()));
+        }
+
+        attrs
+    }
+}
+
 /// Attributes for a listbox option.
 pub mod option {
     /// Creates aria attributes for a listbox option.
@@ -509,6 +529,22 @@ pub mod option {
     }
 }
 
+/// Attributes for a menu.
+pub mod menu {
+    /// Creates aria attributes for a menu panel.
+    pub fn attrs() -> Vec<(String, String)> {
+        vec![
+            ("role".to_string(), "menu".to_string()),
+            ("aria-orientation".to_string(), "vertical".to_string()),
+        ]
+    }
+
+    /// Creates aria attributes for a line between groups of menu items.
+    pub fn separator_attrs() -> Vec<(String, String)> {
+        vec![("role".to_string(), "separator".to_string())]
+    }
+}
+
 /// Attributes for a menu item.
 pub mod menuitem {
     /// Creates aria attributes for a menu item.
@@ -518,6 +554,229 @@ pub mod menuitem {
             ("aria-disabled".to_string(), disabled.to_string()),
         ]
     }
+
+    /// Creates aria attributes for a menu item opening a submenu.
+    pub fn submenu_attrs(disabled: bool, expanded: bool) -> Vec<(String, String)> {
+        let mut attrs = attrs(disabled);
+        attrs.push(("aria-haspopup".to_string(), "menu".to_string()));
+        attrs.push(("aria-expanded".to_string(), expanded.to_string()));
+        attrs
+    }
+}
+
+/// Attributes for tabs.
+pub mod tabs {
+    /// Creates aria attributes for the strip holding the tabs.
+    pub fn tablist_attrs() -> Vec<(String, String)> {
+        vec![
+            ("role".to_string(), "tablist".to_string()),
+            ("aria-orientation".to_string(), "horizontal".to_string()),
+        ]
+    }
+
+    /// Creates aria attributes for a tab, optionally controlling a panel.
+    pub fn tab_attrs(
+        selected: bool,
+        disabled: bool,
+        controls: Option<&str>,
+    ) -> Vec<(String, String)> {
+        let mut attrs = vec![
+            ("role".to_string(), "tab".to_string()),
+            ("aria-selected".to_string(), selected.to_string()),
+            ("aria-disabled".to_string(), disabled.to_string()),
+        ];
+
+        if let Some(id) = controls {
+            attrs.push(("aria-controls".to_string(), id.to_string()));
+        }
+
+        attrs
+    }
+
+    /// Creates aria attributes for the panel of the selected tab.
+    pub fn tabpanel_attrs(labelledby: Option<&str>) -> Vec<(String, String)> {
+        let mut attrs = vec![("role".to_string(), "tabpanel".to_string())];
+
+        if let Some(id) = labelledby {
+            attrs.push(("aria-labelledby".to_string(), id.to_string()));
+        }
+
+        attrs
+    }
+}
+
+/// Attributes for a data table.
+pub mod table {
+    use super::{Role, aria};
+
+    /// Creates aria attributes for a table presented as a grid. `rows` counts
+    /// the header row.
+    pub fn grid_attrs(rows: usize, columns: usize, multiselectable: bool) -> Vec<(String, String)> {
+        let mut attrs = aria().role(Role::Grid).build();
+        attrs.push(("aria-rowcount".to_string(), rows.to_string()));
+        attrs.push(("aria-colcount".to_string(), columns.to_string()));
+        attrs.push((
+            "aria-multiselectable".to_string(),
+            multiselectable.to_string(),
+        ));
+        attrs
+    }
+
+    /// Creates aria attributes for a column header, with its `aria-sort`
+    /// value if the column is sortable.
+    pub fn columnheader_attrs(sort: Option<&str>) -> Vec<(String, String)> {
+        let mut attrs = vec![("role".to_string(), "columnheader".to_string())];
+
+        if let Some(sort) = sort {
+            attrs.push(("aria-sort".to_string(), sort.to_string()));
+        }
+
+        attrs
+    }
+
+    /// Creates aria attributes for a body row. `index` is 1-based and counts
+    /// the header row.
+    pub fn row_attrs(index: usize, selected: Option<bool>) -> Vec<(String, String)> {
+        let mut attrs = aria().role(Role::Row).build();
+        attrs.push(("aria-rowindex".to_string(), index.to_string()));
+
+        if let Some(selected) = selected {
+            attrs.push(("aria-selected".to_string(), selected.to_string()));
+        }
+
+        attrs
+    }
+
+    /// Creates aria attributes for a body cell.
+    pub fn cell_attrs() -> Vec<(String, String)> {
+        vec![("role".to_string(), "gridcell".to_string())]
+    }
+}
+
+/// Attributes for accordion sections.
+pub mod accordion {
+    /// Creates aria attributes for a section header, optionally controlling its panel.
+    pub fn header_attrs(
+        expanded: bool,
+        disabled: bool,
+        controls: Option<&str>,
+    ) -> Vec<(String, String)> {
+        let mut attrs = vec![
+            ("role".to_string(), "button".to_string()),
+            ("aria-expanded".to_string(), expanded.to_string()),
+            ("aria-disabled".to_string(), disabled.to_string()),
+        ];
+
+        if let Some(id) = controls {
+            attrs.push(("aria-controls".to_string(), id.to_string()));
+        }
+
+        attrs
+    }
+
+    /// Creates aria attributes for the panel of an open section.
+    pub fn panel_attrs(labelledby: Option<&str>) -> Vec<(String, String)> {
+        let mut attrs = vec![("role".to_string(), "region".to_string())];
+
+        if let Some(id) = labelledby {
+            attrs.push(("aria-labelledby".to_string(), id.to_string()));
+        }
+
+        attrs
+    }
+}
+
+/// Attributes for a date picker.
+pub mod date_picker {
+    use super::{Role, aria};
+
+    /// Creates aria attributes for the button opening the calendar.
+    pub fn trigger_attrs(expanded: bool, disabled: bool) -> Vec<(String, String)> {
+        aria()
+            .role(Role::Button)
+            .aria_haspopup("dialog")
+            .aria_expanded(expanded)
+            .aria_disabled(disabled)
+            .build()
+    }
+
+    /// Creates aria attributes for the month grid, labelled by its heading.
+    pub fn grid_attrs(month_label: &str) -> Vec<(String, String)> {
+        aria().role(Role::Grid).aria_label(month_label).build()
+    }
+
+    /// Creates aria attributes for a day in the grid.
+    pub fn day_attrs(label: &str, selected: bool, disabled: bool) -> Vec<(String, String)> {
+        aria()
+            .role(Role::Cell)
+            .aria_label(label)
+            .aria_selected(selected)
+            .aria_disabled(disabled)
+            .build()
+    }
+
+    /// Creates aria attributes for a month or year navigation button.
+    pub fn nav_attrs(label: &str, disabled: bool) -> Vec<(String, String)> {
+        aria()
+            .role(Role::Button)
+            .aria_label(label)
+            .aria_disabled(disabled)
+            .build()
+    }
+}
+
+/// Attributes for a split pane.
+pub mod split_pane {
+    use super::{Role, aria};
+
+    /// Creates aria attributes for a draggable divider between panes.
+    /// `vertical` is true for the dividers of side-by-side panes, and `position`
+    /// is the percentage of the space before the divider.
+    pub fn separator_attrs(vertical: bool, position: f64) -> Vec<(String, String)> {
+        aria()
+            .role(Role::Separator)
+            .aria_orientation(if vertical { "vertical" } else { "horizontal" })
+            .aria_valuemin(0.)
+            .aria_valuemax(100.)
+            .aria_valuenow(position.round())
+            .build()
+    }
+}
+
+/// Attributes for a slider.
+pub mod slider {
+    use super::{Role, aria};
+
+    /// Creates aria attributes for a horizontal slider.
+    pub fn attrs(min: f64, max: f64, now: f64, disabled: bool) -> Vec<(String, String)> {
+        aria()
+            .role(Role::Slider)
+            .aria_valuemin(min)
+            .aria_valuemax(max)
+            .aria_valuenow(now)
+            .aria_orientation("horizontal")
+            .aria_disabled(disabled)
+            .build()
+    }
+}
+
+/// Attributes for a toast notification announced by screen readers.
+pub mod notification {
+    /// Creates aria attributes for a notification.
+    ///
+    /// Urgent notifications are alerts that interrupt; others are polite status updates.
+    pub fn attrs(urgent: bool) -> Vec<(String, String)> {
+        let (role, live) = if urgent {
+            ("alert", "assertive")
+        } else {
+            ("status", "polite")
+        };
+        vec![
+            ("role".to_string(), role.to_string()),
+            ("aria-live".to_string(), live.to_string()),
+            ("aria-atomic".to_string(), "true".to_string()),
+        ]
+    }
 }
 
 /// Extension trait for adding role directly to elements.
@@ -6,8 +6,9 @@
 
 use gpui::{
     App, ElementId, FocusHandle, InteractiveElement, IntoElement, ParentElement, RenderOnce,
-    StatefulInteractiveElement, Styled, Window, actions, div,
+    StatefulInteractiveElement, Styled, WeakFocusHandle, Window, actions, div,
 };
+use std::cell::Cell;
 use std::sync::Arc;
 
 actions!(
@@ -22,6 +23,13 @@ actions!(
     ]
 );
 
+pub(crate) fn init(cx: &mut App) {
+    cx.bind_keys([gpui::KeyBinding::new("escape", Close, Some("FocusTrap"))]);
+}
+
+/// Upper bound on tab stops visited while moving focus within a trap.
+const MAX_TAB_STOPS: usize = 256;
+
 /// Callback type for window and app event handlers.
 type WindowAppCallback = Arc<dyn Fn(&mut Window, &mut App)>;
 
@@ -30,6 +38,10 @@ type WindowAppCallback = Arc<dyn Fn(&mut Window, &mut App)>;
 /// FocusTrap is essential for modal dialogs and other overlays where
 /// keyboard focus should be contained within the component.
 ///
+/// The element focused when the trap first renders is restored when Escape is
+/// pressed inside the trap or when the trap stops rendering. Turn this off with
+/// [`FocusTrap::restore_focus`] for flows that move focus elsewhere on close.
+///
 /// # Usage
 ///
 /// ```ignore
@@ -57,6 +69,10 @@ pub struct FocusTrap {
     trap_focus: bool,
     /// Initial focus element ID.
     initial_focus: Option<ElementId>,
+    /// Focus target when the previously focused element is gone.
+    fallback_focus: Option<FocusHandle>,
+    /// Whether to restore focus on teardown (default: true).
+    restore_focus: bool,
 }
 
 impl Default for FocusTrap {
@@ -76,6 +92,8 @@ impl FocusTrap {
             on_focus_prev: None,
             trap_focus: true,
             initial_focus: None,
+            fallback_focus: None,
+            restore_focus: true,
         }
     }
 
@@ -128,6 +146,22 @@ impl FocusTrap {
         self.initial_focus = Some(id.into());
         self
     }
+
+    /// Sets the handle to focus on teardown if the previously focused element is gone.
+    ///
+    /// Without one, focus is cleared back to the window root.
+    pub fn fallback_focus(mut self, handle: &FocusHandle) -> Self {
+        self.fallback_focus = Some(handle.clone());
+        self
+    }
+
+    /// Sets whether focus returns to the previously focused element on teardown.
+    ///
+    /// Disable this when closing the trap intentionally moves focus elsewhere.
+    pub fn restore_focus(mut self, restore: bool) -> Self {
+        self.restore_focus = restore;
+        self
+    }
 }
 
 impl ParentElement for FocusTrap {
@@ -151,22 +185,99 @@ impl InteractiveElement for FocusTrap {
 impl StatefulInteractiveElement for FocusTrap {}
 
 impl RenderOnce for FocusTrap {
-    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
-        let element_id = self.element_id;
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let element_id = self.element_id.unwrap_or_else(|| "focus-trap".into());
+
+        // Record the focus to restore when the trap first renders, and restore it
+        // when the keyed state is released because the trap is no longer rendered.
+        let created = Cell::new(false);
+        let fallback_focus = self.fallback_focus;
+        let restore_focus = self.restore_focus;
+        let state = window.use_keyed_state(
+            (element_id.clone(), "ui:focus-trap:state"),
+            cx,
+            |window, cx| {
+                created.set(true);
+                let mut state = FocusTrapState::new();
+                state.fallback_focus = fallback_focus;
+                state.restore_focus = restore_focus;
+                state.activate(window, cx);
+                state
+            },
+        );
+        if created.get() {
+            window
+                .observe_release(&state, cx, |state, window, cx| state.deactivate(window, cx))
+                .detach();
+        } else if state.read(cx).restore_focus != restore_focus {
+            state.update(cx, |state, _| state.restore_focus = restore_focus);
+        }
 
-        // Return the base element with optional ID
-        // Note: Full keyboard trap functionality requires integration at the app/overlay level
+        // Note: Tab containment requires integration at the app/overlay level.
+        let on_escape = self.on_escape;
         self.base
-            .id(element_id.unwrap_or_else(|| "focus-trap".into()))
+            .id(element_id)
+            .key_context("FocusTrap")
+            .on_action(move |_: &Close, window, cx| {
+                if let Some(on_escape) = &on_escape {
+                    on_escape(window, cx);
+                }
+                state.update(cx, |state, cx| state.deactivate(window, cx));
+            })
     }
 }
 
+/// Where focus goes when a focus trap is deactivated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RestoreTarget<H> {
+    /// The element that was focused when the trap was activated.
+    Previous(H),
+    /// The previously focused element no longer exists; use the fallback.
+    Fallback(H),
+    /// Nothing to restore; focus returns to the window root.
+    Root,
+}
+
+/// Picks the focus restore target from the still-alive previous focus and fallback.
+pub fn resolve_restore_target<H>(previous: Option<H>, fallback: Option<H>) -> RestoreTarget<H> {
+    match (previous, fallback) {
+        (Some(previous), _) => RestoreTarget::Previous(previous),
+        (None, Some(fallback)) => RestoreTarget::Fallback(fallback),
+        (None, None) => RestoreTarget::Root,
+    }
+}
+
+/// Moves focus to the next (or previous) tab stop inside `panel`, or to the
+/// panel itself when it has none.
+///
+/// Call it from a `capture_key_down` handler for Tab on the trap so focus
+/// wraps around inside the panel instead of leaving it.
+pub(crate) fn cycle_focus(panel: &FocusHandle, backwards: bool, window: &mut Window, cx: &App) {
+    for _ in 0..MAX_TAB_STOPS {
+        if backwards {
+            window.focus_prev();
+        } else {
+            window.focus_next();
+        }
+        if panel.contains_focused(window, cx) {
+            return;
+        }
+    }
+    panel.focus(window);
+}
+
 /// Focus trap state for managing focus programmatically.
 pub struct FocusTrapState {
     /// The previously focused element handle (to restore later).
-    pub previous_focus: Option<FocusHandle>,
+    ///
+    /// Held weakly so a trap never keeps a removed element's focus handle alive.
+    pub previous_focus: Option<WeakFocusHandle>,
+    /// Focused instead when the previous element no longer exists.
+    pub fallback_focus: Option<FocusHandle>,
     /// Whether the trap is currently active.
     pub is_active: bool,
+    /// Whether deactivating moves focus back; when `false` focus is left alone.
+    pub restore_focus: bool,
 }
 
 impl FocusTrapState {
@@ -174,22 +285,51 @@ impl FocusTrapState {
     pub fn new() -> Self {
         Self {
             previous_focus: None,
+            fallback_focus: None,
             is_active: false,
+            restore_focus: true,
         }
     }
 
+    /// Sets the handle focused when the previously focused element is gone.
+    pub fn fallback_focus(mut self, handle: FocusHandle) -> Self {
+        self.fallback_focus = Some(handle);
+        self
+    }
+
     /// Activates the focus trap, storing the current focus.
+    ///
+    /// Does nothing if the trap is already active, so the original focus is kept.
     pub fn activate(&mut self, window: &mut Window, cx: &App) {
-        self.previous_focus = window.focused(cx);
+        if self.is_active {
+            return;
+        }
+        self.previous_focus = window.focused(cx).map(|handle| handle.downgrade());
         self.is_active = true;
     }
 
     /// Deactivates the focus trap and restores previous focus.
+    ///
+    /// Falls back to [`Self::fallback_focus`], or clears focus to the window root,
+    /// when the previously focused element no longer exists. Focus is left
+    /// untouched when [`Self::restore_focus`] is `false`.
     pub fn deactivate(&mut self, window: &mut Window, _cx: &mut App) {
-        if let Some(handle) = &self.previous_focus {
-            handle.focus(window);
+        if !self.is_active {
+            return;
         }
         self.is_active = false;
+
+        let previous = self.previous_focus.take();
+        if !self.restore_focus {
+            return;
+        }
+        let previous = previous.and_then(|handle| handle.upgrade());
+        match resolve_restore_target(previous, self.fallback_focus.clone()) {
+            RestoreTarget::Previous(handle) | RestoreTarget::Fallback(handle) => {
+                handle.focus(window)
+            }
+            RestoreTarget::Root => window.blur(),
+        }
     }
 }
 
@@ -242,3 +382,88 @@ pub mod focus_nav {
         false
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use gpui::{Context, FocusHandle, Render, TestAppContext, prelude::FluentBuilder};
+
+    use super::*;
+
+    #[test]
+    fn restores_previous_focus_when_alive() {
+        assert_eq!(
+            resolve_restore_target(Some("search"), Some("root")),
+            RestoreTarget::Previous("search")
+        );
+    }
+
+    #[test]
+    fn missing_previous_focus_falls_back() {
+        assert_eq!(
+            resolve_restore_target(None, Some("root")),
+            RestoreTarget::Fallback("root")
+        );
+        assert_eq!(
+            resolve_restore_target::<&str>(None, None),
+            RestoreTarget::Root
+        );
+    }
+
+    struct Trapped {
+        before: FocusHandle,
+        inside: FocusHandle,
+        restore: bool,
+        open: bool,
+    }
+
+    impl Render for Trapped {
+        fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+            div()
+                .child(div().track_focus(&self.before))
+                .when(self.open, |this| {
+                    this.child(
+                        focus_trap()
+                            .restore_focus(self.restore)
+                            .child(div().track_focus(&self.inside)),
+                    )
+                })
+        }
+    }
+
+    /// Opens a trap over a focused element, focuses inside it, then closes it,
+    /// returning whether focus went back to the element.
+    fn focus_returns_on_close(restore: bool, cx: &mut TestAppContext) -> bool {
+        let (view, cx) = cx.add_window_view(|_, cx| Trapped {
+            before: cx.focus_handle(),
+            inside: cx.focus_handle(),
+            restore,
+            open: false,
+        });
+        cx.update(|window, cx| view.read(cx).before.focus(window));
+        cx.run_until_parked();
+
+        view.update(cx, |view, cx| {
+            view.open = true;
+            cx.notify();
+        });
+        cx.run_until_parked();
+        cx.update(|window, cx| view.read(cx).inside.focus(window));
+        cx.run_until_parked();
+
+        view.update(cx, |view, cx| {
+            view.open = false;
+            cx.notify();
+        });
+        cx.run_until_parked();
+        // The trap's state is released once the frame without it is done.
+        cx.update(|window, _| window.refresh());
+        cx.run_until_parked();
+        cx.update(|window, cx| view.read(cx).before.is_focused(window))
+    }
+
+    #[gpui::test]
+    fn unmounting_restores_focus_unless_disabled(cx: &mut TestAppContext) {
+        assert!(focus_returns_on_close(true, cx));
+        assert!(!focus_returns_on_close(false, cx));
+    }
+}
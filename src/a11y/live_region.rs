@@ -0,0 +1,245 @@
+//! Live-region announcements for screen readers.
+//!
+//! Transient messages ("Copied to clipboard", form errors, toast text) are
+//! pushed with [`announce`] into a global [`LiveAnnouncer`], and a single
+//! [`live_region`] host rendered near the window root presents them one frame
+//! at a time with the matching `aria-live` politeness, so announcements made
+//! in the same frame are all read.
+//!
+//! gpui does not expose a platform accessibility tree yet, so the host renders
+//! the message as visually hidden text and carries its ARIA attributes in
+//! [`Announcement::aria_attrs`] for when it does.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use gpui::{
+    App, Global, InteractiveElement, IntoElement, ParentElement, RenderOnce, SharedString, Styled,
+    Window, div, px,
+};
+
+/// Identical announcements within this interval are dropped.
+const REPEAT_DEBOUNCE: Duration = Duration::from_millis(1000);
+
+/// How urgently a screen reader should read an announcement.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Politeness {
+    /// Read once the user is idle (`aria-live="polite"`).
+    #[default]
+    Polite,
+    /// Interrupt whatever is being read (`aria-live="assertive"`).
+    Assertive,
+}
+
+impl Politeness {
+    /// The `aria-live` value.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Politeness::Polite => "polite",
+            Politeness::Assertive => "assertive",
+        }
+    }
+}
+
+/// A message for assistive technology.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Announcement {
+    /// Increases with every announcement, so repeating a message still
+    /// changes the live region.
+    pub id: u64,
+    pub message: SharedString,
+    pub politeness: Politeness,
+}
+
+impl Announcement {
+    /// ARIA attributes for the live region presenting this announcement.
+    pub fn aria_attrs(&self) -> Vec<(String, String)> {
+        let role = match self.politeness {
+            Politeness::Polite => "status",
+            Politeness::Assertive => "alert",
+        };
+        vec![
+            ("role".to_string(), role.to_string()),
+            (
+                "aria-live".to_string(),
+                self.politeness.as_str().to_string(),
+            ),
+            ("aria-atomic".to_string(), "true".to_string()),
+        ]
+    }
+}
+
+/// Global holding the announcements the [`live_region`] host presents.
+#[derive(Default)]
+pub struct LiveAnnouncer {
+    current: Option<Announcement>,
+    pending: VecDeque<Announcement>,
+    announced_at: Option<Instant>,
+    next_id: u64,
+}
+
+impl Global for LiveAnnouncer {}
+
+impl LiveAnnouncer {
+    /// The announcement the host presents this frame.
+    pub fn current(&self) -> Option<&Announcement> {
+        self.current.as_ref()
+    }
+
+    /// Whether announcements are still queued behind [`Self::current`].
+    pub fn has_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Moves the next queued announcement into [`Self::current`], if any.
+    fn advance(&mut self) -> Option<&Announcement> {
+        if let Some(next) = self.pending.pop_front() {
+            self.current = Some(next);
+        }
+        self.current.as_ref()
+    }
+
+    /// Queue an announcement. Returns `false` if `message` repeats the latest
+    /// one within the debounce interval.
+    fn push(&mut self, message: SharedString, politeness: Politeness, now: Instant) -> bool {
+        let latest = self.pending.back().or(self.current.as_ref());
+        let repeated = latest
+            .is_some_and(|latest| latest.message == message && latest.politeness == politeness)
+            && self
+                .announced_at
+                .is_some_and(|at| now.duration_since(at) < REPEAT_DEBOUNCE);
+        if repeated || message.is_empty() {
+            return false;
+        }
+
+        self.next_id += 1;
+        self.pending.push_back(Announcement {
+            id: self.next_id,
+            message,
+            politeness,
+        });
+        self.announced_at = Some(now);
+        true
+    }
+}
+
+/// Announce `message` to screen readers through the [`live_region`] host.
+///
+/// Repeating the same message within a second is ignored.
+pub fn announce(cx: &mut App, message: impl Into<SharedString>, politeness: Politeness) {
+    let announced =
+        cx.default_global::<LiveAnnouncer>()
+            .push(message.into(), politeness, Instant::now());
+    if announced {
+        // Announcements may come from render; refresh once the frame is done.
+        cx.defer(|cx| cx.refresh_windows());
+    }
+}
+
+/// The host element presenting [`announce`]d messages.
+///
+/// Render this once near the root of your window. It takes no space and is
+/// not visible.
+pub fn live_region() -> LiveRegion {
+    LiveRegion::new()
+}
+
+#[derive(IntoElement)]
+pub struct LiveRegion {
+    base: gpui::Div,
+}
+
+impl Default for LiveRegion {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LiveRegion {
+    pub fn new() -> Self {
+        Self { base: div() }
+    }
+}
+
+impl RenderOnce for LiveRegion {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let announcer = cx.default_global::<LiveAnnouncer>();
+        let current = announcer.advance().cloned();
+        // Present the rest of the queue over the following frames.
+        if announcer.has_pending() {
+            window.request_animation_frame();
+        }
+
+        self.base
+            .id("ui:live-region")
+            .absolute()
+            .size(px(1.))
+            .overflow_hidden()
+            .opacity(0.)
+            .children(current.map(|announcement| {
+                div()
+                    .id(("ui:live-region:message", announcement.id as usize))
+                    .child(announcement.message)
+            }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeated_announcements_are_debounced() {
+        let mut announcer = LiveAnnouncer::default();
+        let start = Instant::now();
+
+        assert!(announcer.push("Copied".into(), Politeness::Polite, start));
+        assert!(!announcer.push("Copied".into(), Politeness::Polite, start));
+        assert!(announcer.push("Copied".into(), Politeness::Assertive, start));
+        assert!(announcer.push("Saved".into(), Politeness::Polite, start));
+        assert!(!announcer.push("".into(), Politeness::Polite, start));
+
+        let later = start + REPEAT_DEBOUNCE;
+        assert!(announcer.push("Saved".into(), Politeness::Polite, later));
+        let ids: Vec<_> = std::iter::from_fn(|| {
+            announcer
+                .has_pending()
+                .then(|| announcer.advance().unwrap().id)
+        })
+        .collect();
+        assert_eq!(ids, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn announcements_in_one_frame_are_presented_in_turn() {
+        let mut announcer = LiveAnnouncer::default();
+        let now = Instant::now();
+        announcer.push("Saved".into(), Politeness::Polite, now);
+        announcer.push("Name is required".into(), Politeness::Assertive, now);
+
+        assert_eq!(announcer.current(), None);
+        assert_eq!(announcer.advance().unwrap().message, "Saved");
+        assert!(announcer.has_pending());
+        assert_eq!(announcer.advance().unwrap().message, "Name is required");
+        assert!(!announcer.has_pending());
+
+        // The last announcement stays presented until the next one.
+        assert_eq!(announcer.advance().unwrap().message, "Name is required");
+        assert!(announcer.push("Saved".into(), Politeness::Polite, now));
+        assert!(!announcer.push("Saved".into(), Politeness::Polite, now));
+    }
+
+    #[test]
+    fn politeness_maps_to_aria_live() {
+        let announcement = Announcement {
+            id: 1,
+            message: "Name is required".into(),
+            politeness: Politeness::Assertive,
+        };
+        assert!(
+            announcement
+                .aria_attrs()
+                .contains(&("aria-live".to_string(), "assertive".to_string()))
+        );
+    }
+}
@@ -4,9 +4,15 @@
 //! - ARIA role and attribute definitions
 //! - Focus management components (FocusTrap)
 //! - Keyboard navigation helpers
+//! - Live-region announcements for screen readers
+//! - Roving tabindex for composite widgets
 
 mod aria;
 mod focus_trap;
+mod live_region;
+mod roving_focus;
 
 pub use aria::*;
 pub use focus_trap::*;
+pub use live_region::*;
+pub use roving_focus::*;
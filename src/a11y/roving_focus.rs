@@ -0,0 +1,224 @@
+//! Roving tabindex for composite widgets.
+//!
+//! A composite widget (tree, radio group, segmented control) is a single tab
+//! stop: only its active item is tabbable, and arrow keys move which item is
+//! active. [`RovingFocus`] tracks that item for a list of child ids; the widget
+//! renders each child with [`RovingFocus::tab_index`] and focuses
+//! [`RovingFocus::active_id`] after a move.
+
+use gpui::ElementId;
+
+use super::FocusDirection;
+
+/// Which arrow keys move the active item.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Orientation {
+    /// Left and right.
+    Horizontal,
+    /// Up and down.
+    #[default]
+    Vertical,
+    /// All four arrows, e.g. for wrapping toolbars.
+    Both,
+}
+
+impl Orientation {
+    /// The `aria-orientation` value. `Both` has none.
+    pub fn as_str(self) -> Option<&'static str> {
+        match self {
+            Orientation::Horizontal => Some("horizontal"),
+            Orientation::Vertical => Some("vertical"),
+            Orientation::Both => None,
+        }
+    }
+}
+
+/// Tracks the active item of a composite widget.
+#[derive(Debug, Clone, Default)]
+pub struct RovingFocus {
+    items: Vec<ElementId>,
+    active: usize,
+    orientation: Orientation,
+    wrap: bool,
+    rtl: bool,
+}
+
+impl RovingFocus {
+    /// Creates a roving focus over no items, moving with the arrows of `orientation`.
+    pub fn new(orientation: Orientation) -> Self {
+        Self {
+            orientation,
+            ..Self::default()
+        }
+    }
+
+    /// Sets whether moving past either end wraps around (default: false).
+    pub fn wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Sets whether left and right are swapped for right-to-left layouts.
+    pub fn rtl(mut self, rtl: bool) -> Self {
+        self.rtl = rtl;
+        self
+    }
+
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    /// Replaces the items, in visual order.
+    ///
+    /// The active item stays active if it is still present; otherwise the item
+    /// now at its position (or the last one) takes over.
+    pub fn set_items(&mut self, items: impl IntoIterator<Item = impl Into<ElementId>>) {
+        let active = self.active_id().cloned();
+        self.items = items.into_iter().map(Into::into).collect();
+        self.active = active
+            .and_then(|id| self.position(&id))
+            .unwrap_or_else(|| self.active.min(self.items.len().saturating_sub(1)));
+    }
+
+    pub fn items(&self) -> &[ElementId] {
+        &self.items
+    }
+
+    pub fn active_index(&self) -> Option<usize> {
+        (!self.items.is_empty()).then_some(self.active)
+    }
+
+    /// The item that currently owns the tab stop.
+    pub fn active_id(&self) -> Option<&ElementId> {
+        self.items.get(self.active)
+    }
+
+    /// Makes `id` the active item, e.g. when it is clicked. Returns `false` if
+    /// it isn't one of the items.
+    pub fn set_active(&mut self, id: &ElementId) -> bool {
+        match self.position(id) {
+            Some(index) => {
+                self.active = index;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Whether `id` is the item that is tabbable.
+    pub fn is_tab_stop(&self, id: &ElementId) -> bool {
+        self.active_id() == Some(id)
+    }
+
+    /// The tab index to render `id` with: `0` for the active item, `-1` otherwise.
+    pub fn tab_index(&self, id: &ElementId) -> isize {
+        if self.is_tab_stop(id) { 0 } else { -1 }
+    }
+
+    /// Maps a key name (`"up"`, `"left"`, `"home"`, ...) to a move, honoring the
+    /// orientation and right-to-left layout.
+    pub fn direction_for_key(&self, key: &str) -> Option<FocusDirection> {
+        let vertical = self.orientation != Orientation::Horizontal;
+        let horizontal = self.orientation != Orientation::Vertical;
+        let (left, right) = if self.rtl {
+            (FocusDirection::Next, FocusDirection::Previous)
+        } else {
+            (FocusDirection::Previous, FocusDirection::Next)
+        };
+        match key {
+            "up" if vertical => Some(FocusDirection::Previous),
+            "down" if vertical => Some(FocusDirection::Next),
+            "left" if horizontal => Some(left),
+            "right" if horizontal => Some(right),
+            "home" => Some(FocusDirection::First),
+            "end" => Some(FocusDirection::Last),
+            _ => None,
+        }
+    }
+
+    /// Moves the active item. Returns the new active id if it changed.
+    pub fn move_focus(&mut self, direction: FocusDirection) -> Option<&ElementId> {
+        let last = self.items.len().checked_sub(1)?;
+        let next = match direction {
+            FocusDirection::First => 0,
+            FocusDirection::Last => last,
+            FocusDirection::Next if self.active < last => self.active + 1,
+            FocusDirection::Next if self.wrap => 0,
+            FocusDirection::Previous if self.active > 0 => self.active - 1,
+            FocusDirection::Previous if self.wrap => last,
+            FocusDirection::Next | FocusDirection::Previous => self.active,
+        };
+        if next == self.active {
+            return None;
+        }
+        self.active = next;
+        self.active_id()
+    }
+
+    /// Handles a key press: [`direction_for_key`](Self::direction_for_key) then
+    /// [`move_focus`](Self::move_focus).
+    pub fn handle_key(&mut self, key: &str) -> Option<&ElementId> {
+        let direction = self.direction_for_key(key)?;
+        self.move_focus(direction)
+    }
+
+    fn position(&self, id: &ElementId) -> Option<usize> {
+        self.items.iter().position(|item| item == id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roving(orientation: Orientation) -> RovingFocus {
+        let mut focus = RovingFocus::new(orientation);
+        focus.set_items(["a", "b", "c"]);
+        focus
+    }
+
+    fn id(name: &'static str) -> ElementId {
+        name.into()
+    }
+
+    #[test]
+    fn arrows_follow_orientation() {
+        let mut focus = roving(Orientation::Vertical);
+        assert_eq!(focus.handle_key("down"), Some(&id("b")));
+        assert_eq!(focus.handle_key("right"), None);
+        assert_eq!(focus.handle_key("end"), Some(&id("c")));
+        assert_eq!(focus.handle_key("down"), None);
+        assert_eq!(focus.handle_key("home"), Some(&id("a")));
+
+        let mut focus = roving(Orientation::Horizontal).rtl(true);
+        assert_eq!(focus.handle_key("down"), None);
+        assert_eq!(focus.handle_key("left"), Some(&id("b")));
+    }
+
+    #[test]
+    fn wrapping_is_configurable() {
+        let mut focus = roving(Orientation::Vertical);
+        assert_eq!(focus.handle_key("up"), None);
+
+        let mut focus = roving(Orientation::Vertical).wrap(true);
+        assert_eq!(focus.handle_key("up"), Some(&id("c")));
+        assert_eq!(focus.handle_key("down"), Some(&id("a")));
+    }
+
+    #[test]
+    fn only_active_item_is_tabbable() {
+        let mut focus = roving(Orientation::Both);
+        assert!(focus.set_active(&id("b")));
+        assert_eq!(focus.tab_index(&id("b")), 0);
+        assert_eq!(focus.tab_index(&id("a")), -1);
+        assert!(!focus.set_active(&id("z")));
+
+        // The active item survives reordering; a removed one hands over.
+        focus.set_items(["c", "b"]);
+        assert_eq!(focus.active_index(), Some(1));
+        focus.set_items(["c"]);
+        assert_eq!(focus.active_id(), Some(&id("c")));
+        focus.set_items(Vec::<ElementId>::new());
+        assert_eq!(focus.active_id(), None);
+    }
+}
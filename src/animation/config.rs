@@ -1,12 +1,57 @@
 //! Animation configuration module.
 
+use std::fmt;
+use std::rc::Rc;
 use std::time::Duration;
 
+use gpui::{App, ElementId, Task, Window};
+
 use super::easing::EasingFn;
 use super::easing::ease_out_quad;
+use super::motion::reduce_motion;
+use super::timing::progress_from_elapsed;
+
+/// How many times an animation plays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Repeat {
+    /// Play this many times in a row (`Count(1)` plays once).
+    Count(u32),
+    /// Play until the element goes away.
+    Forever,
+}
+
+impl Default for Repeat {
+    fn default() -> Self {
+        Self::Count(1)
+    }
+}
+
+/// `true` repeats forever and `false` plays once, as `AnimationConfig::repeat`
+/// did when it took a `bool`.
+impl From<bool> for Repeat {
+    fn from(forever: bool) -> Self {
+        if forever {
+            Self::Forever
+        } else {
+            Self::Count(1)
+        }
+    }
+}
+
+impl Repeat {
+    /// Number of iterations, or `None` for [`Repeat::Forever`].
+    pub fn count(self) -> Option<u32> {
+        match self {
+            Self::Count(count) => Some(count.max(1)),
+            Self::Forever => None,
+        }
+    }
+}
+
+type CompleteHandler = Rc<dyn Fn(&mut Window, &mut App)>;
 
 /// Configuration for animations.
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct AnimationConfig {
     /// Duration of the animation.
     pub duration: Duration,
@@ -14,10 +59,23 @@ pub struct AnimationConfig {
     pub easing: EasingFn,
     /// Delay before starting the animation.
     pub delay: Duration,
-    /// Whether the animation should repeat.
-    pub repeat: bool,
-    /// Whether the animation should reverse (yoyo effect).
+    /// How many times the animation plays.
+    pub repeat: Repeat,
+    /// Whether every other iteration plays backwards (yoyo effect).
     pub reverse: bool,
+    on_complete: Option<CompleteHandler>,
+}
+
+impl fmt::Debug for AnimationConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AnimationConfig")
+            .field("duration", &self.duration)
+            .field("delay", &self.delay)
+            .field("repeat", &self.repeat)
+            .field("reverse", &self.reverse)
+            .field("on_complete", &self.on_complete.is_some())
+            .finish_non_exhaustive()
+    }
 }
 
 impl Default for AnimationConfig {
@@ -26,8 +84,9 @@ impl Default for AnimationConfig {
             duration: Duration::from_millis(200),
             easing: ease_out_quad,
             delay: Duration::ZERO,
-            repeat: false,
+            repeat: Repeat::default(),
             reverse: false,
+            on_complete: None,
         }
     }
 }
@@ -56,23 +115,52 @@ impl AnimationConfig {
         self
     }
 
-    /// Enable repeat.
-    pub fn with_repeat(mut self) -> Self {
-        self.repeat = true;
+    /// Enable repeat. Same as `.repeat(Repeat::Forever)`.
+    pub fn with_repeat(self) -> Self {
+        self.repeat(Repeat::Forever)
+    }
+
+    /// Set how many times the animation plays. A `bool` is accepted too:
+    /// `true` repeats forever and `false` plays once.
+    pub fn repeat(mut self, repeat: impl Into<Repeat>) -> Self {
+        self.repeat = repeat.into();
+        self
+    }
+
+    /// Call `handler` once, after the final iteration finishes.
+    ///
+    /// Never called for [`Repeat::Forever`], or if the element is removed first.
+    /// When [motion is reduced](super::reduce_motion) the animation shows its end
+    /// state right away, so `handler` runs right away too.
+    pub fn on_complete(mut self, handler: impl Fn(&mut Window, &mut App) + 'static) -> Self {
+        self.on_complete = Some(Rc::new(handler));
         self
     }
 
-    /// Enable reverse (yoyo).
+    /// Time from the start until the final iteration ends, or `None` if the
+    /// animation repeats forever.
+    pub fn total_duration(&self) -> Option<Duration> {
+        let count = self.repeat.count()?;
+        Some(self.delay + self.duration * count)
+    }
+
+    /// Enable reverse (yoyo): every other iteration plays backwards, so
+    /// `.repeat(Repeat::Count(2))` goes there and back.
     pub fn with_reverse(mut self) -> Self {
         self.reverse = true;
         self
     }
 
     /// Convert to gpui Animation.
+    ///
+    /// A single gpui animation can only play once or forever, so a
+    /// [`Repeat::Count`] plays once here and the delay and reverse are not
+    /// applied; use [`to_gpui_animations`](Self::to_gpui_animations) to honour
+    /// them.
     pub fn to_gpui_animation(self) -> gpui::Animation {
         let mut animation = gpui::Animation::new(self.duration);
 
-        if self.repeat {
+        if self.repeat == Repeat::Forever {
             animation = animation.repeat();
         }
 
@@ -81,9 +169,84 @@ impl AnimationConfig {
         // for custom easing in the preset module.
         animation
     }
+
+    /// Convert to a chain of gpui animations for `with_animations`: the delay,
+    /// if any, then one animation per iteration. Map the animator's index and
+    /// delta to the iteration's progress with
+    /// [`iteration_progress`](Self::iteration_progress).
+    pub fn to_gpui_animations(&self) -> Vec<gpui::Animation> {
+        let delay = (!self.delay.is_zero()).then(|| gpui::Animation::new(self.delay));
+        let iterations = match self.repeat.count() {
+            Some(count) => (0..count)
+                .map(|_| gpui::Animation::new(self.duration))
+                .collect(),
+            // One loop covers a there-and-back pair, so the yoyo keeps alternating.
+            None if self.reverse => vec![gpui::Animation::new(self.duration * 2).repeat()],
+            None => vec![gpui::Animation::new(self.duration).repeat()],
+        };
+        delay.into_iter().chain(iterations).collect()
+    }
+
+    /// Progress of the animation at `animation_ix` in
+    /// [`to_gpui_animations`](Self::to_gpui_animations), `delta` of the way
+    /// through it: `0.0` during the delay, and running backwards on reversed
+    /// iterations.
+    pub fn iteration_progress(&self, animation_ix: usize, delta: f32) -> f32 {
+        let delayed = !self.delay.is_zero();
+        if delayed && animation_ix == 0 {
+            return 0.0;
+        }
+        if !self.reverse {
+            return delta;
+        }
+        if self.repeat == Repeat::Forever {
+            // A there-and-back pair per loop.
+            return 1.0 - (1.0 - 2.0 * delta).abs();
+        }
+        let iteration = animation_ix - usize::from(delayed);
+        if iteration % 2 == 1 {
+            1.0 - delta
+        } else {
+            delta
+        }
+    }
+
+    /// Schedule `on_complete` for the animation with `id`.
+    ///
+    /// The timer lives in the element's keyed state, so it is scheduled once per
+    /// mount and cancelled if the element is removed before it fires.
+    pub(crate) fn schedule_on_complete(&self, id: &ElementId, window: &mut Window, cx: &mut App) {
+        let (Some(handler), Some(wait)) = (
+            self.on_complete.clone(),
+            self.complete_after(reduce_motion()),
+        ) else {
+            return;
+        };
+        window.use_keyed_state(
+            (id.clone(), "ui:animation-complete"),
+            cx,
+            |window, cx| -> Task<()> {
+                window.spawn(cx, async move |cx| {
+                    cx.background_executor().timer(wait).await;
+                    cx.update(|window, cx| handler(window, cx)).ok();
+                })
+            },
+        );
+    }
+
+    /// Time until `on_complete` runs: the total duration, or none at all when
+    /// motion is `reduced` and the animation shows its end state at once.
+    fn complete_after(&self, reduced: bool) -> Option<Duration> {
+        let total = self.total_duration()?;
+        Some(if reduced { Duration::ZERO } else { total })
+    }
 }
 
 /// State tracking for complex animations.
+///
+/// Drive it per frame with [`advance`](Self::advance). Progress is derived from
+/// the elapsed time, so pausing, resuming and reversing all continue from the
+/// current position without a jump.
 #[derive(Debug, Clone, Default)]
 pub struct AnimationState {
     /// Current progress (0.0 to 1.0).
@@ -92,6 +255,12 @@ pub struct AnimationState {
     pub is_running: bool,
     /// Whether the animation is paused.
     pub is_paused: bool,
+    /// Whether time runs backwards, from the end towards the start.
+    pub is_reversed: bool,
+    /// Position on the timeline, from zero to `duration`.
+    pub elapsed: Duration,
+    /// Length of the timeline.
+    pub duration: Duration,
 }
 
 impl AnimationState {
@@ -101,6 +270,9 @@ impl AnimationState {
             progress: 0.0,
             is_running: false,
             is_paused: false,
+            is_reversed: false,
+            elapsed: Duration::ZERO,
+            duration: Duration::ZERO,
         }
     }
 
@@ -109,10 +281,189 @@ impl AnimationState {
         self.progress = 0.0;
         self.is_running = false;
         self.is_paused = false;
+        self.is_reversed = false;
+        self.elapsed = Duration::ZERO;
+    }
+
+    /// Start playing a `duration`-long animation from the beginning.
+    pub fn start(&mut self, duration: Duration) {
+        self.reset();
+        self.duration = duration;
+        self.is_running = true;
     }
 
     /// Update progress value.
+    ///
+    /// The timeline moves to match, so a later [`advance`](Self::advance)
+    /// continues from here. Useful when a gesture takes over the animation.
     pub fn update(&mut self, progress: f32) {
         self.progress = progress.clamp(0.0, 1.0);
+        self.elapsed = self.duration.mul_f32(self.progress);
+    }
+
+    /// Move the timeline forward (or back, when reversed) by `dt` and return the
+    /// new progress. Does nothing while paused or stopped.
+    ///
+    /// The animation stops when it reaches the end it is heading for.
+    pub fn advance(&mut self, dt: Duration) -> f32 {
+        if !self.is_running || self.is_paused {
+            return self.progress;
+        }
+
+        self.elapsed = if self.is_reversed {
+            self.elapsed.saturating_sub(dt)
+        } else {
+            (self.elapsed + dt).min(self.duration)
+        };
+        self.progress = progress_from_elapsed(self.elapsed, self.duration);
+        if self.at_end() {
+            self.is_running = false;
+        }
+        self.progress
+    }
+
+    /// Freeze the animation where it is.
+    pub fn pause(&mut self) {
+        if self.is_running {
+            self.is_paused = true;
+        }
+    }
+
+    /// Continue a paused animation from where it stopped.
+    pub fn resume(&mut self) {
+        self.is_paused = false;
+    }
+
+    /// Flip the direction of travel.
+    ///
+    /// A running animation heads back the way it came from its current position.
+    /// A finished one plays back from the end it stopped at.
+    pub fn reverse(&mut self) {
+        self.is_reversed = !self.is_reversed;
+        if !self.is_running && !self.duration.is_zero() {
+            self.is_running = !self.at_end();
+        }
+    }
+
+    /// Whether the timeline sits at the end it is heading for.
+    fn at_end(&self) -> bool {
+        if self.is_reversed {
+            self.elapsed.is_zero()
+        } else {
+            self.elapsed >= self.duration
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MS: Duration = Duration::from_millis(1);
+
+    fn assert_approx(actual: f32, expected: f32) {
+        let diff = (actual - expected).abs();
+        assert!(
+            diff <= 1e-4,
+            "expected {expected} but got {actual} (diff={diff})"
+        );
+    }
+
+    #[test]
+    fn repeat_count_sets_iterations_and_total() {
+        let config = AnimationConfig::new()
+            .with_duration(MS * 100)
+            .with_delay(MS * 50)
+            .repeat(Repeat::Count(3));
+        // The delay plays first, then one animation per iteration.
+        assert_eq!(config.to_gpui_animations().len(), 4);
+        assert_eq!(config.total_duration(), Some(MS * 350));
+
+        let config = config.repeat(Repeat::Forever);
+        assert_eq!(config.to_gpui_animations().len(), 2);
+        assert_eq!(config.total_duration(), None);
+
+        assert_eq!(Repeat::Count(0).count(), Some(1));
+    }
+
+    #[test]
+    fn repeat_still_accepts_a_bool() {
+        let config = AnimationConfig::new().repeat(true);
+        assert_eq!(config.repeat, Repeat::Forever);
+
+        let config = config.repeat(false);
+        assert_eq!(config.repeat, Repeat::Count(1));
+    }
+
+    #[test]
+    fn on_complete_does_not_wait_when_motion_is_reduced() {
+        let config = AnimationConfig::new()
+            .with_duration(MS * 100)
+            .repeat(Repeat::Count(2));
+        assert_eq!(config.complete_after(false), Some(MS * 200));
+        assert_eq!(config.complete_after(true), Some(Duration::ZERO));
+
+        let config = config.repeat(Repeat::Forever);
+        assert_eq!(config.complete_after(true), None);
+    }
+
+    #[test]
+    fn delay_holds_the_start_and_reverse_plays_back() {
+        let config = AnimationConfig::new()
+            .with_delay(MS * 50)
+            .repeat(Repeat::Count(2))
+            .with_reverse();
+        assert_approx(config.iteration_progress(0, 0.7), 0.0);
+        assert_approx(config.iteration_progress(1, 0.25), 0.25);
+        assert_approx(config.iteration_progress(2, 0.25), 0.75);
+
+        // Forever loops a there-and-back pair.
+        let config = AnimationConfig::new().with_repeat().with_reverse();
+        assert_eq!(config.to_gpui_animations().len(), 1);
+        assert_approx(config.iteration_progress(0, 0.25), 0.5);
+        assert_approx(config.iteration_progress(0, 0.75), 0.5);
+        assert_approx(config.iteration_progress(0, 1.0), 0.0);
+    }
+
+    #[test]
+    fn pause_holds_progress_until_resumed() {
+        let mut state = AnimationState::new();
+        state.start(MS * 100);
+
+        assert_approx(state.advance(MS * 25), 0.25);
+        state.pause();
+        assert_approx(state.advance(MS * 50), 0.25);
+        state.resume();
+        assert_approx(state.advance(MS * 25), 0.5);
+        assert!(state.is_running);
+
+        assert_approx(state.advance(MS * 80), 1.0);
+        assert!(!state.is_running);
+    }
+
+    #[test]
+    fn reverse_continues_from_the_current_position() {
+        let mut state = AnimationState::new();
+        state.start(MS * 100);
+        state.advance(MS * 60);
+
+        state.reverse();
+        assert_approx(state.advance(MS * 20), 0.4);
+        assert_approx(state.advance(MS * 100), 0.0);
+        assert!(!state.is_running);
+
+        // A finished animation plays back from where it stopped.
+        state.reverse();
+        assert!(state.is_running);
+        assert_approx(state.advance(MS * 50), 0.5);
+    }
+
+    #[test]
+    fn update_moves_the_timeline() {
+        let mut state = AnimationState::new();
+        state.start(MS * 200);
+        state.update(0.75);
+        assert_approx(state.elapsed.as_secs_f32(), 0.15);
+        assert_approx(state.advance(MS * 20), 0.85);
     }
 }
@@ -12,6 +12,7 @@
 /// passing an easing function to gpui.
 pub type EasingFn = fn(f32) -> f32;
 
+/// Clamp eased progress to `[0.0, 1.0]`.
 #[inline]
 fn clamp01(t: f32) -> f32 {
     t.clamp(0.0, 1.0)
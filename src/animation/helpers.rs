@@ -2,7 +2,13 @@
 
 use std::time::Duration;
 
-use gpui::{Div, ElementId, Hsla, InteractiveElement, Pixels, Stateful, Styled};
+use gpui::{
+    AnimationElement, AnimationExt, App, Bounds, Div, ElementId, Hsla, InteractiveElement, Pixels,
+    Point, Size, Stateful, Styled, Window, point, px, size,
+};
+
+use super::config::AnimationConfig;
+use super::motion::settle;
 
 /// Extension trait for animating gpui elements.
 pub trait AnimateExt {
@@ -37,6 +43,20 @@ pub trait AnimateExt {
     ) -> Stateful<Self>
     where
         Self: Sized;
+
+    /// Run `animator` with the timing, delay, repeat count, reverse and
+    /// completion handler of `config`. `animator` receives the progress of the
+    /// current iteration.
+    fn animate_with(
+        self,
+        id: impl Into<ElementId>,
+        config: AnimationConfig,
+        animator: impl Fn(Self, f32) -> Self + 'static,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> AnimationElement<Self>
+    where
+        Self: Sized;
 }
 
 /// Direction for slide animations.
@@ -55,7 +75,7 @@ impl AnimateExt for Div {
         _duration: Duration,
         progress: f32,
     ) -> Stateful<Self> {
-        self.id(id).opacity(progress)
+        self.id(id).opacity(settle(progress))
     }
 
     fn animate_slide(
@@ -66,6 +86,7 @@ impl AnimateExt for Div {
         _duration: Duration,
         progress: f32,
     ) -> Stateful<Self> {
+        let progress = settle(progress);
         let distance_f: f32 = distance.into();
         let (ml, mt) = match direction {
             SlideDirection::Left => (gpui::px(distance_f * (progress - 1.0)), gpui::px(0.0)),
@@ -82,26 +103,128 @@ impl AnimateExt for Div {
         _duration: Duration,
         progress: f32,
     ) -> Stateful<Self> {
-        self.id(id).opacity(progress)
+        self.id(id).opacity(settle(progress))
+    }
+
+    fn animate_with(
+        self,
+        id: impl Into<ElementId>,
+        config: AnimationConfig,
+        animator: impl Fn(Self, f32) -> Self + 'static,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> AnimationElement<Self> {
+        let id = id.into();
+        config.schedule_on_complete(&id, window, cx);
+        let animations = config.to_gpui_animations();
+        self.with_animations(id, animations, move |element, ix, delta| {
+            animator(element, settle(config.iteration_progress(ix, delta)))
+        })
     }
 }
 
+/// A value that can be interpolated linearly, so any animatable property can
+/// be driven by the progress an animator receives.
+pub trait Lerp: Sized {
+    /// The value `t` of the way from `self` to `end`. `t` may leave
+    /// `0.0..=1.0` for easings that overshoot.
+    fn lerp(&self, end: &Self, t: f32) -> Self;
+}
+
+impl Lerp for f32 {
+    fn lerp(&self, end: &Self, t: f32) -> Self {
+        self + (end - self) * t
+    }
+}
+
+impl Lerp for Pixels {
+    fn lerp(&self, end: &Self, t: f32) -> Self {
+        px(f32::from(*self).lerp(&f32::from(*end), t))
+    }
+}
+
+impl Lerp for Hsla {
+    fn lerp(&self, end: &Self, t: f32) -> Self {
+        Hsla {
+            h: self.h.lerp(&end.h, t),
+            s: self.s.lerp(&end.s, t),
+            l: self.l.lerp(&end.l, t),
+            a: self.a.lerp(&end.a, t),
+        }
+    }
+}
+
+impl Lerp for Point<Pixels> {
+    fn lerp(&self, end: &Self, t: f32) -> Self {
+        point(self.x.lerp(&end.x, t), self.y.lerp(&end.y, t))
+    }
+}
+
+impl Lerp for Size<Pixels> {
+    fn lerp(&self, end: &Self, t: f32) -> Self {
+        size(
+            self.width.lerp(&end.width, t),
+            self.height.lerp(&end.height, t),
+        )
+    }
+}
+
+impl Lerp for Bounds<Pixels> {
+    fn lerp(&self, end: &Self, t: f32) -> Self {
+        Bounds {
+            origin: self.origin.lerp(&end.origin, t),
+            size: self.size.lerp(&end.size, t),
+        }
+    }
+}
+
+/// Interpolate any [`Lerp`] value, e.g. inside an animator:
+///
+/// ```ignore
+/// el.with_animation("grow", Animation::new(duration), move |el, delta| {
+///     let size = animate_value(collapsed, expanded, delta);
+///     el.w(size.width).h(size.height)
+/// })
+/// ```
+pub fn animate_value<T: Lerp>(from: T, to: T, progress: f32) -> T {
+    from.lerp(&to, progress)
+}
+
 /// Helper to interpolate between two values.
 pub fn lerp(start: f32, end: f32, t: f32) -> f32 {
-    start + (end - start) * t
+    animate_value(start, end, t)
 }
 
 /// Helper to interpolate between two colors.
 pub fn lerp_color(start: Hsla, end: Hsla, t: f32) -> Hsla {
-    Hsla {
-        h: lerp(start.h, end.h, t),
-        s: lerp(start.s, end.s, t),
-        l: lerp(start.l, end.l, t),
-        a: lerp(start.a, end.a, t),
-    }
+    animate_value(start, end, t)
 }
 
 /// Create a simple animation id from a prefix and state.
 pub fn animation_id(prefix: &str, state: impl std::fmt::Debug) -> String {
     format!("{}:{:?}", prefix, state)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lerps_geometry() {
+        let from = Bounds::new(point(px(0.0), px(10.0)), size(px(100.0), px(20.0)));
+        let to = Bounds::new(point(px(50.0), px(30.0)), size(px(200.0), px(40.0)));
+        let mid = animate_value(from, to, 0.5);
+        assert_eq!(mid.origin, point(px(25.0), px(20.0)));
+        assert_eq!(mid.size, size(px(150.0), px(30.0)));
+        assert_eq!(animate_value(from, to, 0.0), from);
+        assert_eq!(animate_value(from, to, 1.0), to);
+    }
+
+    #[test]
+    fn free_functions_match_trait() {
+        assert_eq!(lerp(2.0, 4.0, 0.25), 2.5);
+        let black = gpui::black();
+        let white = gpui::white();
+        assert_eq!(lerp_color(black, white, 0.5), black.lerp(&white, 0.5));
+    }
+}
@@ -6,8 +6,10 @@
 mod config;
 mod easing;
 mod helpers;
+mod motion;
 mod orchestrator;
 mod preset;
+mod spring;
 mod timing;
 
 pub mod constants;
@@ -15,7 +17,7 @@ pub mod constants;
 /// Convenience re-export of global duration constants.
 pub use constants::duration;
 
-pub use config::{AnimationConfig, AnimationState};
+pub use config::{AnimationConfig, AnimationState, Repeat};
 pub use easing::{
     EasingFn, clamp_easing, ease_in, ease_in_back, ease_in_back_clamped, ease_in_bounce,
     ease_in_bounce_clamped, ease_in_circ, ease_in_circ_clamped, ease_in_cubic,
@@ -35,8 +37,11 @@ pub use easing::{
     ease_out_quint_clamped, ease_out_sine, ease_out_sine_clamped,
 };
 pub use helpers::{
-    AnimateExt, SlideDirection as HelpersSlideDirection, animation_id, lerp, lerp_color,
+    AnimateExt, Lerp, SlideDirection as HelpersSlideDirection, animate_value, animation_id, lerp,
+    lerp_color,
 };
+pub(crate) use motion::{detect_system_reduce_motion, settle};
+pub use motion::{follow_system_reduce_motion, reduce_motion, set_reduce_motion};
 pub use orchestrator::{AnimationParallel, AnimationSequence, Staggered, parallel, sequence};
 pub use orchestrator::{Orchestration, TrackId};
 pub use preset::{
@@ -47,5 +52,6 @@ pub use preset::{
     fade_slide_in_left, fade_slide_in_right, fade_slide_in_up, fade_slide_out, fade_slide_out_to,
     preset_duration, pulse,
 };
+pub use spring::{Spring, SpringConfig, spring};
 
 pub use timing::{clamp01, parallel_progress, progress_from_elapsed, sequence_progress};
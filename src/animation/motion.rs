@@ -0,0 +1,132 @@
+//! Reduced-motion preference.
+//!
+//! When motion is reduced, the preset animators and
+//! [`AnimateExt`](super::AnimateExt) report finished progress, so animations
+//! built from them show their end state right away. Elements still appear and
+//! disappear; they just don't move or fade on the way. Easing functions are left
+//! alone: looping indicators check [`reduce_motion`] themselves and show a static
+//! state instead, since their end state is not a useful resting one.
+//!
+//! The preference follows the OS setting where it can be read (macOS and GNOME)
+//! until the app pins it with [`set_reduce_motion`].
+
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+const FOLLOW_SYSTEM: u8 = 0;
+const PINNED_OFF: u8 = 1;
+const PINNED_ON: u8 = 2;
+
+static PREFERENCE: AtomicU8 = AtomicU8::new(FOLLOW_SYSTEM);
+static SYSTEM_REDUCE_MOTION: AtomicBool = AtomicBool::new(false);
+
+/// Whether animations should skip straight to their end state.
+pub fn reduce_motion() -> bool {
+    match PREFERENCE.load(Ordering::Relaxed) {
+        PINNED_ON => true,
+        PINNED_OFF => false,
+        _ => SYSTEM_REDUCE_MOTION.load(Ordering::Relaxed),
+    }
+}
+
+/// Pin the reduced-motion preference, ignoring the OS setting.
+///
+/// Running animations pick it up on their next frame; call
+/// `cx.refresh_windows()` to update idle windows too.
+pub fn set_reduce_motion(reduce: bool) {
+    let preference = if reduce { PINNED_ON } else { PINNED_OFF };
+    PREFERENCE.store(preference, Ordering::Relaxed);
+}
+
+/// Go back to following the OS setting after [`set_reduce_motion`].
+pub fn follow_system_reduce_motion() {
+    PREFERENCE.store(FOLLOW_SYSTEM, Ordering::Relaxed);
+}
+
+/// Progress to show for `t`: `t` itself, or the end when motion is reduced.
+#[inline]
+pub(crate) fn settle(t: f32) -> f32 {
+    settle_with(reduce_motion(), t)
+}
+
+#[inline]
+fn settle_with(reduce: bool, t: f32) -> f32 {
+    if reduce { 1.0 } else { t }
+}
+
+/// Read the OS preference in the background. Called from `component::init`.
+pub(crate) fn detect_system_reduce_motion() {
+    std::thread::spawn(|| {
+        if let Some(reduce) = query_system_reduce_motion() {
+            SYSTEM_REDUCE_MOTION.store(reduce, Ordering::Relaxed);
+        }
+    });
+}
+
+fn query_system_reduce_motion() -> Option<bool> {
+    if cfg!(target_os = "macos") {
+        run(
+            "defaults",
+            &["read", "com.apple.universalaccess", "reduceMotion"],
+        )
+        .and_then(|out| parse_macos(&out))
+    } else if cfg!(any(target_os = "linux", target_os = "freebsd")) {
+        run(
+            "gsettings",
+            &["get", "org.gnome.desktop.interface", "enable-animations"],
+        )
+        .and_then(|out| parse_gnome(&out))
+    } else {
+        None
+    }
+}
+
+fn run(program: &str, args: &[&str]) -> Option<String> {
+    let output = std::process::Command::new(program)
+        .args(args)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()
+}
+
+/// `defaults read com.apple.universalaccess reduceMotion` prints `1` or `0`.
+fn parse_macos(output: &str) -> Option<bool> {
+    match output.trim() {
+        "1" => Some(true),
+        "0" => Some(false),
+        _ => None,
+    }
+}
+
+/// `gsettings get org.gnome.desktop.interface enable-animations` prints `true`
+/// or `false`; disabled animations mean reduced motion.
+fn parse_gnome(output: &str) -> Option<bool> {
+    match output.trim() {
+        "false" => Some(true),
+        "true" => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reduced_motion_jumps_to_the_end() {
+        assert_eq!(settle_with(false, 0.25), 0.25);
+        assert_eq!(settle_with(true, 0.25), 1.0);
+        assert_eq!(settle_with(true, 0.0), 1.0);
+    }
+
+    #[test]
+    fn os_settings_are_parsed() {
+        assert_eq!(parse_macos("1\n"), Some(true));
+        assert_eq!(parse_macos("0\n"), Some(false));
+        assert_eq!(parse_macos(""), None);
+        assert_eq!(parse_gnome("false\n"), Some(true));
+        assert_eq!(parse_gnome("true\n"), Some(false));
+    }
+}
@@ -11,6 +11,8 @@ use std::time::Duration;
 
 use gpui::Animation;
 
+use super::config::{AnimationState, Repeat};
+use super::spring::Spring;
 use super::timing::{clamp01, parallel_progress, sequence_progress};
 
 /// A named handle for a track inside an orchestration.
@@ -21,6 +23,23 @@ pub struct TrackId(pub usize);
 struct TrackSpec {
     duration: Duration,
     delay: Duration,
+    repeat: Repeat,
+}
+
+impl TrackSpec {
+    fn new(duration: Duration, delay: Duration) -> Self {
+        Self {
+            duration,
+            delay,
+            repeat: Repeat::default(),
+        }
+    }
+
+    /// Time from the step start until the last iteration ends. A track that
+    /// repeats forever counts one iteration; its step loops instead.
+    fn span(&self) -> Duration {
+        self.delay + self.duration * self.repeat.count().unwrap_or(1)
+    }
 }
 
 /// High-level orchestration builder.
@@ -56,11 +75,32 @@ impl Orchestration {
     pub fn then_delayed(mut self, duration: Duration, delay: Duration) -> (Self, TrackId) {
         let track_id = TrackId(self.total_tracks());
         self.steps.push(Step {
-            tracks: vec![TrackSpec { duration, delay }],
+            tracks: vec![TrackSpec::new(duration, delay)],
+        });
+        (self, track_id)
+    }
+
+    /// Add a sequential step that plays `duration` as many times as `repeat` says.
+    ///
+    /// With [`Repeat::Forever`] the step loops and later steps never start.
+    pub fn then_repeated(mut self, duration: Duration, repeat: Repeat) -> (Self, TrackId) {
+        let track_id = TrackId(self.total_tracks());
+        self.steps.push(Step {
+            tracks: vec![TrackSpec {
+                repeat,
+                ..TrackSpec::new(duration, Duration::ZERO)
+            }],
         });
         (self, track_id)
     }
 
+    /// Add a sequential step that lasts as long as `spring` takes to settle.
+    ///
+    /// Sample the spring with the track's progress to get its value.
+    pub fn then_spring(self, spring: &Spring) -> (Self, TrackId) {
+        self.then(spring.duration())
+    }
+
     /// Add a parallel step containing multiple tracks.
     ///
     /// Each track progresses relative to the longest track within the step.
@@ -86,7 +126,7 @@ impl Orchestration {
         for (duration, delay) in tracks {
             let track_id = TrackId(self.total_tracks() + specs.len());
             ids.push(track_id);
-            specs.push(TrackSpec { duration, delay });
+            specs.push(TrackSpec::new(duration, delay));
         }
         self.steps.push(Step { tracks: specs });
         (self, ids)
@@ -99,10 +139,7 @@ impl Orchestration {
         }
 
         self.steps.push(Step {
-            tracks: vec![TrackSpec {
-                duration,
-                delay: Duration::ZERO,
-            }],
+            tracks: vec![TrackSpec::new(duration, Duration::ZERO)],
         });
         self
     }
@@ -121,13 +158,30 @@ impl Orchestration {
         self.steps.iter().map(|s| s.tracks.len()).sum()
     }
 
+    /// Time until every track has finished all of its iterations, or `None` if
+    /// a track repeats forever.
+    pub fn total_duration(&self) -> Option<Duration> {
+        self.steps
+            .iter()
+            .map(|step| (!step_loops(step)).then(|| step_duration(step)))
+            .sum()
+    }
+
     /// Compile into gpui animations for use with `with_animations`.
     ///
-    /// Each orchestration step maps to one gpui `Animation`.
+    /// Each orchestration step maps to one gpui `Animation`. Steps with a track
+    /// that repeats forever loop.
     pub fn compile(&self) -> Vec<Animation> {
         self.steps
             .iter()
-            .map(|step| Animation::new(step_duration(step)))
+            .map(|step| {
+                let animation = Animation::new(step_duration(step));
+                if step_loops(step) {
+                    animation.repeat()
+                } else {
+                    animation
+                }
+            })
             .collect()
     }
 
@@ -140,8 +194,8 @@ impl Orchestration {
     /// you may call it for any track on any step, and it will return:
     ///
     /// - `0.0` before the track starts
-    /// - `1.0` after it finishes
-    /// - a normalized value while active
+    /// - `1.0` after its last iteration finishes
+    /// - the progress of the current iteration while active
     pub fn track_progress(&self, animation_index: usize, delta: f32, track_id: TrackId) -> f32 {
         let delta = clamp01(delta);
 
@@ -172,7 +226,7 @@ impl Orchestration {
         }
         let track_start = track_step_start + track.delay;
 
-        track_progress_from_elapsed(elapsed, track_start, track.duration)
+        track_progress_from_elapsed(elapsed, track_start, track.duration, track.repeat)
     }
 
     fn locate_track(&self, track_id: TrackId) -> Option<(usize, &TrackSpec)> {
@@ -194,12 +248,21 @@ impl Orchestration {
 fn step_duration(step: &Step) -> Duration {
     step.tracks
         .iter()
-        .map(|t| t.delay + t.duration)
+        .map(TrackSpec::span)
         .max()
         .unwrap_or(Duration::ZERO)
 }
 
-fn track_progress_from_elapsed(elapsed: Duration, start: Duration, duration: Duration) -> f32 {
+fn step_loops(step: &Step) -> bool {
+    step.tracks.iter().any(|t| t.repeat == Repeat::Forever)
+}
+
+fn track_progress_from_elapsed(
+    elapsed: Duration,
+    start: Duration,
+    duration: Duration,
+    repeat: Repeat,
+) -> f32 {
     if elapsed < start {
         return 0.0;
     }
@@ -208,8 +271,12 @@ fn track_progress_from_elapsed(elapsed: Duration, start: Duration, duration: Dur
         return 1.0;
     }
 
-    let active_elapsed = elapsed - start;
-    clamp01(active_elapsed.as_secs_f32() / duration.as_secs_f32())
+    let iterations = (elapsed - start).as_secs_f32() / duration.as_secs_f32();
+    let count = repeat.count().unwrap_or(1) as f32;
+    if iterations >= count {
+        return 1.0;
+    }
+    clamp01(iterations.fract())
 }
 
 // ============================================================================
@@ -220,28 +287,56 @@ fn track_progress_from_elapsed(elapsed: Duration, start: Duration, duration: Dur
 ///
 /// This is retained for compatibility with earlier docs, but prefer [`Orchestration`]
 /// when integrating with gpui.
+///
+/// It also carries a playhead: [`play`](Self::play) it and call
+/// [`advance`](Self::advance) per frame. Pausing, resuming or reversing the
+/// sequence applies to every step at once.
 #[derive(Debug, Default, Clone)]
 pub struct AnimationSequence {
     durations: Vec<Duration>,
+    springs: SpringTracks,
+    playhead: AnimationState,
 }
 
 impl AnimationSequence {
     pub fn new() -> Self {
         Self {
             durations: Vec::new(),
+            springs: SpringTracks::default(),
+            playhead: AnimationState::new(),
         }
     }
 
     pub fn then(mut self, duration: Duration) -> Self {
         self.durations.push(duration);
+        self.springs.push(None);
         self
     }
 
     pub fn then_all(mut self, durations: impl IntoIterator<Item = Duration>) -> Self {
-        self.durations.extend(durations);
+        for duration in durations {
+            self = self.then(duration);
+        }
+        self
+    }
+
+    /// Append a step driven by `spring`.
+    ///
+    /// The step is planned to last as long as the spring takes to settle. While
+    /// playing forward, the playhead steps the spring and the step ends when
+    /// [`Spring::is_settled`] says so. Read the value with
+    /// [`spring`](Self::spring).
+    pub fn then_spring(mut self, spring: &Spring) -> Self {
+        self.durations.push(spring.duration());
+        self.springs.push(Some(spring.clone()));
         self
     }
 
+    /// The live spring driving the step at `index`, if it is a spring step.
+    pub fn spring(&self, index: usize) -> Option<&Spring> {
+        self.springs.live(index)
+    }
+
     pub fn total_duration(&self) -> Duration {
         self.durations.iter().copied().sum()
     }
@@ -257,34 +352,105 @@ impl AnimationSequence {
     pub fn calculate_progress(&self, total_progress: f32) -> (usize, f32) {
         sequence_progress(&self.durations, total_progress)
     }
+
+    /// Start the playhead from the first step.
+    pub fn play(&mut self) {
+        self.springs.restart(&mut self.durations);
+        self.playhead.start(self.total_duration());
+    }
+
+    /// Move the playhead by `dt` and return the active step and its progress.
+    pub fn advance(&mut self, dt: Duration) -> (usize, f32) {
+        if self.springs.is_running(&self.playhead) {
+            let from = self.playhead.elapsed;
+            let mut start = Duration::ZERO;
+            for (index, duration) in self.durations.iter_mut().enumerate() {
+                self.springs.run(index, duration, start, from, from + dt);
+                start += *duration;
+            }
+            self.playhead.duration = start;
+        }
+        let progress = self.playhead.advance(dt);
+        self.calculate_progress(progress)
+    }
+
+    /// The active step and its progress at the playhead.
+    pub fn current(&self) -> (usize, f32) {
+        self.calculate_progress(self.playhead.progress)
+    }
+
+    /// Pause every step.
+    pub fn pause(&mut self) {
+        self.playhead.pause();
+    }
+
+    /// Resume every step from where it was paused.
+    pub fn resume(&mut self) {
+        self.playhead.resume();
+    }
+
+    /// Play the sequence back towards the first step from the current position.
+    pub fn reverse(&mut self) {
+        self.playhead.reverse();
+    }
+
+    /// The playhead state.
+    pub fn state(&self) -> &AnimationState {
+        &self.playhead
+    }
 }
 
 /// A builder for parallel animations by duration.
 ///
 /// This is retained for compatibility with earlier docs, but prefer [`Orchestration`]
 /// when integrating with gpui.
+///
+/// Like [`AnimationSequence`], it carries a playhead shared by all tracks.
 #[derive(Debug, Default, Clone)]
 pub struct AnimationParallel {
     durations: Vec<Duration>,
+    springs: SpringTracks,
+    playhead: AnimationState,
 }
 
 impl AnimationParallel {
     pub fn new() -> Self {
         Self {
             durations: Vec::new(),
+            springs: SpringTracks::default(),
+            playhead: AnimationState::new(),
         }
     }
 
     pub fn with(mut self, duration: Duration) -> Self {
         self.durations.push(duration);
+        self.springs.push(None);
         self
     }
 
     pub fn with_all(mut self, durations: impl IntoIterator<Item = Duration>) -> Self {
-        self.durations.extend(durations);
+        for duration in durations {
+            self = self.with(duration);
+        }
+        self
+    }
+
+    /// Add a track driven by `spring`.
+    ///
+    /// Like a spring step in an [`AnimationSequence`], the track ends when the
+    /// spring settles while playing forward. Read the value with
+    /// [`spring`](Self::spring).
+    pub fn with_spring(mut self, spring: &Spring) -> Self {
+        self.durations.push(spring.duration());
+        self.springs.push(Some(spring.clone()));
         self
     }
 
+    /// The live spring driving the track at `index`, if it is a spring track.
+    pub fn spring(&self, index: usize) -> Option<&Spring> {
+        self.springs.live(index)
+    }
+
     pub fn max_duration(&self) -> Duration {
         self.durations
             .iter()
@@ -304,6 +470,118 @@ impl AnimationParallel {
     pub fn calculate_progress(&self, total_progress: f32, animation_index: usize) -> f32 {
         parallel_progress(&self.durations, total_progress, animation_index)
     }
+
+    /// Start the playhead from the beginning.
+    pub fn play(&mut self) {
+        self.springs.restart(&mut self.durations);
+        self.playhead.start(self.max_duration());
+    }
+
+    /// Move the playhead by `dt` and return the overall progress.
+    pub fn advance(&mut self, dt: Duration) -> f32 {
+        if self.springs.is_running(&self.playhead) {
+            let from = self.playhead.elapsed;
+            for (index, duration) in self.durations.iter_mut().enumerate() {
+                self.springs
+                    .run(index, duration, Duration::ZERO, from, from + dt);
+            }
+            self.playhead.duration = self.max_duration();
+        }
+        self.playhead.advance(dt)
+    }
+
+    /// Progress of the track at `index` at the playhead.
+    pub fn track_progress(&self, index: usize) -> f32 {
+        self.calculate_progress(self.playhead.progress, index)
+    }
+
+    /// Pause every track.
+    pub fn pause(&mut self) {
+        self.playhead.pause();
+    }
+
+    /// Resume every track from where it was paused.
+    pub fn resume(&mut self) {
+        self.playhead.resume();
+    }
+
+    /// Play every track back towards its start from the current position.
+    pub fn reverse(&mut self) {
+        self.playhead.reverse();
+    }
+
+    /// The playhead state.
+    pub fn state(&self) -> &AnimationState {
+        &self.playhead
+    }
+}
+
+/// Springs behind the spring steps of an [`AnimationSequence`] or tracks of an
+/// [`AnimationParallel`], indexed like its durations.
+#[derive(Debug, Default, Clone)]
+struct SpringTracks {
+    /// The springs as added, replayed from on every `play`.
+    initial: Vec<Option<Spring>>,
+    live: Vec<Option<Spring>>,
+}
+
+impl SpringTracks {
+    fn push(&mut self, spring: Option<Spring>) {
+        self.initial.push(spring.clone());
+        self.live.push(spring);
+    }
+
+    fn live(&self, index: usize) -> Option<&Spring> {
+        self.live.get(index)?.as_ref()
+    }
+
+    /// Rewind every spring and plan its step by its simulated settle time again.
+    fn restart(&mut self, durations: &mut [Duration]) {
+        for (duration, spring) in durations.iter_mut().zip(&self.initial) {
+            if let Some(spring) = spring {
+                *duration = spring.duration();
+            }
+        }
+        self.live.clone_from(&self.initial);
+    }
+
+    /// Springs only run while the playhead moves forward.
+    fn is_running(&self, playhead: &AnimationState) -> bool {
+        playhead.is_running
+            && !playhead.is_paused
+            && !playhead.is_reversed
+            && self
+                .live
+                .iter()
+                .flatten()
+                .any(|spring| !spring.is_settled())
+    }
+
+    /// Step the spring at `index`, if any, for the part of the playhead's move
+    /// from `from` to `to` that falls in its step starting at `start`, and end
+    /// the step where the spring settles.
+    fn run(
+        &mut self,
+        index: usize,
+        duration: &mut Duration,
+        start: Duration,
+        from: Duration,
+        to: Duration,
+    ) {
+        let Some(spring) = self.live[index].as_mut() else {
+            return;
+        };
+        if spring.is_settled() || to <= start {
+            return;
+        }
+        let ran = to - start;
+        if spring.step(to - from.max(start)) {
+            *duration = ran;
+        } else {
+            // Still moving: keep the step going for at least another `to - from`.
+            *duration = (*duration).max(ran + (to - from));
+        }
+    }
 }
 
 // Convenience functions
@@ -386,6 +664,119 @@ mod tests {
         assert_approx(orch.track_progress(0, delta, b), 0.5);
     }
 
+    #[test]
+    fn repeated_tracks_extend_their_step() {
+        let (orch, a) =
+            Orchestration::new().then_repeated(Duration::from_millis(100), Repeat::Count(3));
+        let (orch, b) = orch.then(Duration::from_millis(100));
+
+        assert_eq!(orch.total_duration(), Some(Duration::from_millis(400)));
+        assert_approx(orch.track_progress(0, 0.5, a), 0.5);
+        assert_approx(orch.track_progress(0, 0.9, a), 0.7);
+        assert_approx(orch.track_progress(0, 1.0, a), 1.0);
+        assert_approx(orch.track_progress(0, 1.0, b), 0.0);
+
+        let (orch, _) = orch.then_repeated(Duration::from_millis(100), Repeat::Forever);
+        assert_eq!(orch.total_duration(), None);
+        assert_eq!(orch.compile().len(), 3);
+    }
+
+    #[test]
+    fn spring_steps_last_until_settled() {
+        let spring = crate::animation::spring(1.0);
+        let settle = spring.duration();
+
+        let seq = AnimationSequence::new()
+            .then(Duration::from_millis(100))
+            .then_spring(&spring);
+        assert_eq!(seq.total_duration(), Duration::from_millis(100) + settle);
+
+        let par = AnimationParallel::new()
+            .with(Duration::from_millis(10))
+            .with_spring(&spring);
+        assert_eq!(par.max_duration(), settle);
+        assert_approx(par.calculate_progress(1.0, 1), 1.0);
+    }
+
+    #[test]
+    fn spring_steps_end_when_the_spring_settles() {
+        let frame = Duration::from_millis(16);
+        let spring =
+            crate::animation::Spring::new(0.0, 1.0, crate::animation::SpringConfig::WOBBLY);
+
+        let mut seq = AnimationSequence::new()
+            .then(Duration::from_millis(100))
+            .then_spring(&spring);
+        seq.play();
+        let mut frames = 0;
+        while seq.state().is_running {
+            let (index, _) = seq.advance(frame);
+            frames += 1;
+            let live = seq.spring(1).unwrap();
+            if index == 0 {
+                assert_eq!(live.value(), 0.0);
+            }
+            assert_eq!(seq.state().is_running, !live.is_settled());
+            assert!(frames < 1000);
+        }
+        assert_approx(seq.state().progress, 1.0);
+        assert_eq!(seq.spring(1).unwrap().value(), 1.0);
+        assert!(seq.spring(0).is_none());
+
+        // Playing again replays the spring from the start.
+        seq.play();
+        seq.advance(frame);
+        assert_eq!(seq.spring(1).unwrap().value(), 0.0);
+
+        let mut par = AnimationParallel::new()
+            .with(Duration::from_millis(10))
+            .with_spring(&spring);
+        par.play();
+        while par.state().is_running {
+            par.advance(frame);
+            assert_eq!(par.state().is_running, !par.spring(1).unwrap().is_settled());
+        }
+        assert_approx(par.track_progress(0), 1.0);
+        assert_approx(par.track_progress(1), 1.0);
+    }
+
+    #[test]
+    fn pausing_a_sequence_pauses_its_steps() {
+        let mut seq = sequence(&[Duration::from_millis(100), Duration::from_millis(100)]);
+        seq.play();
+
+        let (index, progress) = seq.advance(Duration::from_millis(150));
+        assert_eq!(index, 1);
+        assert_approx(progress, 0.5);
+
+        seq.pause();
+        assert_eq!(seq.advance(Duration::from_millis(40)), (index, progress));
+
+        seq.resume();
+        seq.reverse();
+        let (index, progress) = seq.advance(Duration::from_millis(100));
+        assert_eq!(index, 0);
+        assert_approx(progress, 0.5);
+    }
+
+    #[test]
+    fn pausing_a_parallel_pauses_its_tracks() {
+        let mut par = parallel(&[Duration::from_millis(50), Duration::from_millis(100)]);
+        par.play();
+        par.advance(Duration::from_millis(25));
+        assert_approx(par.track_progress(0), 0.5);
+        assert_approx(par.track_progress(1), 0.25);
+
+        par.pause();
+        par.advance(Duration::from_millis(25));
+        assert_approx(par.track_progress(0), 0.5);
+
+        par.resume();
+        par.advance(Duration::from_millis(25));
+        assert_approx(par.track_progress(0), 1.0);
+        assert_approx(par.track_progress(1), 0.5);
+    }
+
     #[test]
     fn track_progress_is_global_across_steps() {
         let (orch, a) = Orchestration::new().then(Duration::from_millis(100));
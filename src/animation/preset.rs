@@ -9,6 +9,7 @@ use gpui::{Pixels, Styled};
 use super::easing::{
     ease_in_bounce, ease_in_out, ease_out_bounce, ease_out_cubic, ease_out_elastic, ease_out_quint,
 };
+use super::motion::settle;
 
 /// Preset animation durations.
 ///
@@ -94,6 +95,8 @@ pub enum AnimationType {
     FadeSlideIn(SlideDirection),
     /// Combined fade and scale.
     FadeScaleIn,
+    /// Physically based spring; see [`Spring`](super::Spring).
+    Spring(super::SpringConfig),
 }
 
 /// Slide direction.
@@ -137,6 +140,7 @@ impl FadeIn {
     ) -> impl FnOnce(gpui::Div, f32) -> gpui::Div + 'static {
         let _ = duration;
         move |element: gpui::Div, progress: f32| {
+            let progress = settle(progress);
             let eased_progress = easing(progress);
             element.opacity(eased_progress)
         }
@@ -144,6 +148,7 @@ impl FadeIn {
 
     /// Apply with default ease_out_cubic.
     pub fn apply_default(self, element: gpui::Div, progress: f32) -> gpui::Div {
+        let progress = settle(progress);
         element.opacity(progress)
     }
 }
@@ -165,6 +170,7 @@ impl FadeOut {
 
     /// Apply to a gpui element.
     pub fn apply(self, element: gpui::Div, progress: f32) -> gpui::Div {
+        let progress = settle(progress);
         element.opacity(1.0 - progress)
     }
 }
@@ -183,6 +189,7 @@ impl Default for FadeOut {
 pub fn fade_slide_in(duration: Duration) -> impl Fn(gpui::Div, f32) -> gpui::Div {
     let _ = duration;
     move |element: gpui::Div, progress: f32| {
+        let progress = settle(progress);
         let eased = ease_out_quint(progress);
         element.opacity(eased).mt(gpui::px(10.0 - 6.0 * eased))
     }
@@ -192,6 +199,7 @@ pub fn fade_slide_in(duration: Duration) -> impl Fn(gpui::Div, f32) -> gpui::Div
 pub fn fade_slide_out(duration: Duration) -> impl Fn(gpui::Div, f32) -> gpui::Div {
     let _ = duration;
     move |element: gpui::Div, progress: f32| {
+        let progress = settle(progress);
         let eased = ease_out_quint(progress);
         element.opacity(1.0 - eased).mt(gpui::px(4.0 + 6.0 * eased))
     }
@@ -204,6 +212,7 @@ pub fn fade_slide_in_from(
 ) -> impl Fn(gpui::Div, f32) -> gpui::Div {
     let distance_f: f32 = distance.into();
     move |element: gpui::Div, progress: f32| {
+        let progress = settle(progress);
         let eased = ease_out_cubic(progress);
         let translate = distance_f * (1.0 - eased);
 
@@ -223,6 +232,7 @@ pub fn fade_slide_out_to(
 ) -> impl Fn(gpui::Div, f32) -> gpui::Div {
     let distance_f: f32 = distance.into();
     move |element: gpui::Div, progress: f32| {
+        let progress = settle(progress);
         let eased = ease_out_cubic(progress);
         let translate = distance_f * eased;
         let opacity = 1.0 - eased;
@@ -240,6 +250,7 @@ pub fn fade_slide_out_to(
 pub fn pulse(duration: Duration) -> impl Fn(gpui::Div, f32) -> gpui::Div {
     let _ = duration;
     move |element: gpui::Div, progress: f32| {
+        let progress = settle(progress);
         let eased = ease_in_out(progress);
         let opacity = defaults::PULSE_MIN_OPACITY
             + (defaults::PULSE_MAX_OPACITY - defaults::PULSE_MIN_OPACITY) * eased;
@@ -251,6 +262,7 @@ pub fn pulse(duration: Duration) -> impl Fn(gpui::Div, f32) -> gpui::Div {
 pub fn fade_slide_in_left(distance: Pixels) -> impl Fn(gpui::Div, f32) -> gpui::Div {
     let distance_f: f32 = distance.into();
     move |element: gpui::Div, progress: f32| {
+        let progress = settle(progress);
         let eased = ease_out_cubic(progress);
         let translate = -distance_f * (1.0 - eased);
         element.opacity(eased).ml(gpui::px(translate))
@@ -261,6 +273,7 @@ pub fn fade_slide_in_left(distance: Pixels) -> impl Fn(gpui::Div, f32) -> gpui::
 pub fn fade_slide_in_right(distance: Pixels) -> impl Fn(gpui::Div, f32) -> gpui::Div {
     let distance_f: f32 = distance.into();
     move |element: gpui::Div, progress: f32| {
+        let progress = settle(progress);
         let eased = ease_out_cubic(progress);
         let translate = distance_f * (1.0 - eased);
         element.opacity(eased).ml(gpui::px(translate))
@@ -271,6 +284,7 @@ pub fn fade_slide_in_right(distance: Pixels) -> impl Fn(gpui::Div, f32) -> gpui:
 pub fn fade_slide_in_up(distance: Pixels) -> impl Fn(gpui::Div, f32) -> gpui::Div {
     let distance_f: f32 = distance.into();
     move |element: gpui::Div, progress: f32| {
+        let progress = settle(progress);
         let eased = ease_out_cubic(progress);
         let translate = -distance_f * (1.0 - eased);
         element.opacity(eased).mt(gpui::px(translate))
@@ -281,6 +295,7 @@ pub fn fade_slide_in_up(distance: Pixels) -> impl Fn(gpui::Div, f32) -> gpui::Di
 pub fn fade_slide_in_down(distance: Pixels) -> impl Fn(gpui::Div, f32) -> gpui::Div {
     let distance_f: f32 = distance.into();
     move |element: gpui::Div, progress: f32| {
+        let progress = settle(progress);
         let eased = ease_out_cubic(progress);
         let translate = distance_f * (1.0 - eased);
         element.opacity(eased).mt(gpui::px(translate))
@@ -309,6 +324,7 @@ impl ScaleIn {
     ) -> impl FnOnce(gpui::Div, f32) -> gpui::Div + 'static {
         let _ = duration;
         move |element: gpui::Div, progress: f32| {
+            let progress = settle(progress);
             let eased_progress = easing(progress);
             // Scale from 0.8 to 1.0 with opacity fade in
             // Note: Full scale requires CSS transform, using opacity as visual cue
@@ -318,6 +334,7 @@ impl ScaleIn {
 
     /// Apply with default ease_out_cubic.
     pub fn apply_default(self, element: gpui::Div, progress: f32) -> gpui::Div {
+        let progress = settle(progress);
         let eased = ease_out_cubic(progress);
         element.opacity(eased)
     }
@@ -346,6 +363,7 @@ impl ScaleOut {
     ) -> impl FnOnce(gpui::Div, f32) -> gpui::Div + 'static {
         let _ = duration;
         move |element: gpui::Div, progress: f32| {
+            let progress = settle(progress);
             let eased_progress = easing(progress);
             // Scale from 1.0 to 0.8 with opacity fade out
             element.opacity(1.0 - eased_progress)
@@ -354,6 +372,7 @@ impl ScaleOut {
 
     /// Apply with default ease_out_cubic (reversed).
     pub fn apply_default(self, element: gpui::Div, progress: f32) -> gpui::Div {
+        let progress = settle(progress);
         let eased = ease_out_cubic(progress);
         element.opacity(1.0 - eased)
     }
@@ -369,6 +388,7 @@ impl Default for ScaleOut {
 pub fn fade_scale_in(duration: Duration) -> impl Fn(gpui::Div, f32) -> gpui::Div {
     let _ = duration;
     move |element: gpui::Div, progress: f32| {
+        let progress = settle(progress);
         let eased = ease_out_cubic(progress);
         element.opacity(eased)
     }
@@ -378,6 +398,7 @@ pub fn fade_scale_in(duration: Duration) -> impl Fn(gpui::Div, f32) -> gpui::Div
 pub fn fade_scale_out(duration: Duration) -> impl Fn(gpui::Div, f32) -> gpui::Div {
     let _ = duration;
     move |element: gpui::Div, progress: f32| {
+        let progress = settle(progress);
         let eased = ease_out_cubic(progress);
         element.opacity(1.0 - eased)
     }
@@ -404,6 +425,7 @@ impl BounceIn {
     ) -> impl FnOnce(gpui::Div, f32) -> gpui::Div + 'static {
         let _ = duration;
         move |element: gpui::Div, progress: f32| {
+            let progress = settle(progress);
             let eased_progress = easing(progress);
             // Start from above and bounce down
             let translate = -30.0 * (1.0 - eased_progress);
@@ -413,6 +435,7 @@ impl BounceIn {
 
     /// Apply with default ease_out_bounce.
     pub fn apply_default(self, element: gpui::Div, progress: f32) -> gpui::Div {
+        let progress = settle(progress);
         let eased = ease_out_bounce(progress);
         let translate = -30.0 * (1.0 - eased);
         element.opacity(eased).mt(gpui::px(translate))
@@ -442,6 +465,7 @@ impl BounceOut {
     ) -> impl FnOnce(gpui::Div, f32) -> gpui::Div + 'static {
         let _ = duration;
         move |element: gpui::Div, progress: f32| {
+            let progress = settle(progress);
             let eased_progress = easing(progress);
             // Bounce down and away
             let translate = 30.0 * eased_progress;
@@ -453,6 +477,7 @@ impl BounceOut {
 
     /// Apply with default ease_in_bounce.
     pub fn apply_default(self, element: gpui::Div, progress: f32) -> gpui::Div {
+        let progress = settle(progress);
         let eased = ease_in_bounce(progress);
         let translate = 30.0 * eased;
         element.opacity(1.0 - eased).mt(gpui::px(translate))
@@ -469,6 +494,7 @@ impl Default for BounceOut {
 pub fn bounce_in_left(distance: Pixels) -> impl Fn(gpui::Div, f32) -> gpui::Div {
     let distance_f: f32 = distance.into();
     move |element: gpui::Div, progress: f32| {
+        let progress = settle(progress);
         let eased = ease_out_bounce(progress);
         let translate = -distance_f * (1.0 - eased);
         element.opacity(eased).ml(gpui::px(translate))
@@ -479,6 +505,7 @@ pub fn bounce_in_left(distance: Pixels) -> impl Fn(gpui::Div, f32) -> gpui::Div
 pub fn bounce_in_right(distance: Pixels) -> impl Fn(gpui::Div, f32) -> gpui::Div {
     let distance_f: f32 = distance.into();
     move |element: gpui::Div, progress: f32| {
+        let progress = settle(progress);
         let eased = ease_out_bounce(progress);
         let translate = distance_f * (1.0 - eased);
         element.opacity(eased).ml(gpui::px(translate))
@@ -489,6 +516,7 @@ pub fn bounce_in_right(distance: Pixels) -> impl Fn(gpui::Div, f32) -> gpui::Div
 pub fn bounce_in_up(distance: Pixels) -> impl Fn(gpui::Div, f32) -> gpui::Div {
     let distance_f: f32 = distance.into();
     move |element: gpui::Div, progress: f32| {
+        let progress = settle(progress);
         let eased = ease_out_bounce(progress);
         let translate = -distance_f * (1.0 - eased);
         element.opacity(eased).mt(gpui::px(translate))
@@ -499,6 +527,7 @@ pub fn bounce_in_up(distance: Pixels) -> impl Fn(gpui::Div, f32) -> gpui::Div {
 pub fn bounce_in_down(distance: Pixels) -> impl Fn(gpui::Div, f32) -> gpui::Div {
     let distance_f: f32 = distance.into();
     move |element: gpui::Div, progress: f32| {
+        let progress = settle(progress);
         let eased = ease_out_bounce(progress);
         let translate = distance_f * (1.0 - eased);
         element.opacity(eased).mt(gpui::px(translate))
@@ -512,6 +541,7 @@ pub fn bounce_out_to(
 ) -> impl Fn(gpui::Div, f32) -> gpui::Div {
     let distance_f: f32 = distance.into();
     move |element: gpui::Div, progress: f32| {
+        let progress = settle(progress);
         let eased = ease_in_bounce(progress);
         let translate = distance_f * eased;
         let opacity = 1.0 - eased;
@@ -546,6 +576,7 @@ impl ElasticIn {
     ) -> impl FnOnce(gpui::Div, f32) -> gpui::Div + 'static {
         let _ = duration;
         move |element: gpui::Div, progress: f32| {
+            let progress = settle(progress);
             let eased_progress = easing(progress);
             // Elastic effect via position overshoot
             let overshoot = if eased_progress < 0.5 {
@@ -559,6 +590,7 @@ impl ElasticIn {
 
     /// Apply with default ease_out_elastic.
     pub fn apply_default(self, element: gpui::Div, progress: f32) -> gpui::Div {
+        let progress = settle(progress);
         let eased = ease_out_elastic(progress);
         element.opacity(eased)
     }
@@ -587,6 +619,7 @@ impl ElasticOut {
     ) -> impl FnOnce(gpui::Div, f32) -> gpui::Div + 'static {
         let _ = duration;
         move |element: gpui::Div, progress: f32| {
+            let progress = settle(progress);
             let eased_progress = easing(progress);
             // Elastic effect via position overshoot
             let overshoot = if eased_progress > 0.5 {
@@ -602,6 +635,7 @@ impl ElasticOut {
 
     /// Apply with default ease_out_elastic (reversed).
     pub fn apply_default(self, element: gpui::Div, progress: f32) -> gpui::Div {
+        let progress = settle(progress);
         let eased = ease_out_elastic(progress);
         element.opacity(1.0 - eased)
     }
@@ -617,6 +651,7 @@ impl Default for ElasticOut {
 pub fn elastic_scale_in(duration: Duration) -> impl Fn(gpui::Div, f32) -> gpui::Div {
     let _ = duration;
     move |element: gpui::Div, progress: f32| {
+        let progress = settle(progress);
         let eased = ease_out_elastic(progress);
         element.opacity(eased)
     }
@@ -626,6 +661,7 @@ pub fn elastic_scale_in(duration: Duration) -> impl Fn(gpui::Div, f32) -> gpui::
 pub fn elastic_scale_out(duration: Duration) -> impl Fn(gpui::Div, f32) -> gpui::Div {
     let _ = duration;
     move |element: gpui::Div, progress: f32| {
+        let progress = settle(progress);
         let eased = ease_out_elastic(progress);
         element.opacity(1.0 - eased)
     }
@@ -0,0 +1,396 @@
+//! Physically based spring animation.
+//!
+//! Unlike the preset/easing animations, a spring has no fixed duration: it is
+//! integrated frame by frame from its stiffness, damping and mass, and is done
+//! once it comes to rest. [`Spring::duration`] simulates that ahead of time so a
+//! spring can still be placed on a duration-based timeline
+//! ([`AnimationSequence`](super::AnimationSequence), [`parallel`](super::parallel)
+//! or [`Orchestration`](super::Orchestration)).
+
+use std::cell::Cell;
+use std::time::Duration;
+
+use gpui::Animation;
+
+use super::motion::settle;
+
+/// Largest integration step. Smaller steps keep stiff springs stable.
+const MAX_STEP_SECS: f32 = 1.0 / 240.0;
+
+/// Springs that haven't settled by then are treated as settled.
+const MAX_SETTLE_TIME: Duration = Duration::from_secs(10);
+
+/// Physical parameters of a spring.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpringConfig {
+    /// Spring stiffness (force per unit of displacement).
+    pub stiffness: f32,
+    /// Damping coefficient (force per unit of velocity).
+    pub damping: f32,
+    /// Mass of the animated value.
+    pub mass: f32,
+    /// Speed below which the spring may rest, as a fraction of the travel
+    /// distance per second.
+    pub rest_velocity: f32,
+    /// Distance from the target below which the spring may rest, as a fraction
+    /// of the travel distance.
+    pub rest_displacement: f32,
+}
+
+impl Default for SpringConfig {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl SpringConfig {
+    /// Slow, soft motion with a slight overshoot.
+    pub const GENTLE: Self = Self {
+        stiffness: 120.0,
+        damping: 14.0,
+        ..Self::DEFAULT
+    };
+
+    /// Bouncy motion that overshoots noticeably.
+    pub const WOBBLY: Self = Self {
+        stiffness: 180.0,
+        damping: 12.0,
+        ..Self::DEFAULT
+    };
+
+    /// Quick, firm motion for small UI elements like popovers.
+    pub const STIFF: Self = Self {
+        stiffness: 210.0,
+        damping: 20.0,
+        ..Self::DEFAULT
+    };
+
+    const DEFAULT: Self = Self {
+        stiffness: 170.0,
+        damping: 26.0,
+        mass: 1.0,
+        rest_velocity: 0.01,
+        rest_displacement: 0.001,
+    };
+
+    /// Create a new spring config with default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the stiffness.
+    pub fn with_stiffness(mut self, stiffness: f32) -> Self {
+        self.stiffness = stiffness;
+        self
+    }
+
+    /// Set the damping.
+    pub fn with_damping(mut self, damping: f32) -> Self {
+        self.damping = damping;
+        self
+    }
+
+    /// Set the mass.
+    pub fn with_mass(mut self, mass: f32) -> Self {
+        self.mass = mass;
+        self
+    }
+
+    /// How long a spring released from rest takes to settle.
+    pub fn settle_duration(&self) -> Duration {
+        Spring::new(0.0, 1.0, *self).duration()
+    }
+}
+
+/// A value driven towards a target by a damped spring.
+#[derive(Debug, Clone)]
+pub struct Spring {
+    config: SpringConfig,
+    target: f32,
+    value: f32,
+    velocity: f32,
+    /// Travel distance the rest thresholds are relative to.
+    span: f32,
+    settled: bool,
+    /// [`Spring::duration`] from the current state, cleared when it changes.
+    settle_time: Cell<Option<Duration>>,
+    /// Where the last [`Spring::sample`] left off, cleared when the state changes.
+    cursor: Cell<Option<SampleCursor>>,
+}
+
+/// Simulation state at some point after the spring's current state.
+#[derive(Debug, Clone, Copy)]
+struct SampleCursor {
+    elapsed: Duration,
+    value: f32,
+    velocity: f32,
+    settled: bool,
+}
+
+impl Spring {
+    /// Create a spring at rest at `from`, heading for `target`.
+    pub fn new(from: f32, target: f32, config: SpringConfig) -> Self {
+        let mut spring = Self {
+            config,
+            target,
+            value: from,
+            velocity: 0.0,
+            span: 1.0,
+            settled: false,
+            settle_time: Cell::new(None),
+            cursor: Cell::new(None),
+        };
+        spring.set_target(target);
+        spring
+    }
+
+    /// Use a different spring config from now on.
+    pub fn with_config(mut self, config: SpringConfig) -> Self {
+        self.config = config;
+        self.invalidate();
+        self
+    }
+
+    /// The spring config.
+    pub fn config(&self) -> SpringConfig {
+        self.config
+    }
+
+    /// The current value.
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    /// The current velocity, in units per second.
+    pub fn velocity(&self) -> f32 {
+        self.velocity
+    }
+
+    /// The value the spring is heading for.
+    pub fn target(&self) -> f32 {
+        self.target
+    }
+
+    /// Whether the spring has come to rest at its target.
+    pub fn is_settled(&self) -> bool {
+        self.settled
+    }
+
+    /// Head for a new target, keeping the current velocity so motion stays smooth.
+    pub fn set_target(&mut self, target: f32) {
+        self.target = target;
+        let distance = (target - self.value).abs();
+        self.span = if distance > f32::EPSILON {
+            distance
+        } else {
+            1.0
+        };
+        self.settled = self.at_rest();
+        self.invalidate();
+    }
+
+    /// Advance the simulation by `dt`. Returns `true` once the spring has settled.
+    pub fn step(&mut self, dt: Duration) -> bool {
+        if !self.settled && !dt.is_zero() {
+            self.invalidate();
+        }
+        let mut remaining = dt.as_secs_f32();
+        while !self.settled && remaining > 0.0 {
+            let h = remaining.min(MAX_STEP_SECS);
+            self.integrate(h);
+            remaining -= h;
+            if self.at_rest() {
+                self.value = self.target;
+                self.velocity = 0.0;
+                self.settled = true;
+            }
+        }
+        self.settled
+    }
+
+    /// How long the spring takes to settle from its current state.
+    ///
+    /// Simulated once and cached until the spring is stepped or retargeted.
+    pub fn duration(&self) -> Duration {
+        if let Some(duration) = self.settle_time.get() {
+            return duration;
+        }
+        let mut spring = self.clone();
+        let step = Duration::from_secs_f32(MAX_STEP_SECS);
+        let mut elapsed = Duration::ZERO;
+        while !spring.settled && elapsed < MAX_SETTLE_TIME {
+            spring.step(step);
+            elapsed += step;
+        }
+        self.settle_time.set(Some(elapsed));
+        elapsed
+    }
+
+    /// The value `progress` (`0.0..=1.0`) of the way through [`duration`](Self::duration).
+    ///
+    /// This lets a spring drive a fixed-length gpui animation: use
+    /// [`to_gpui_animation`](Self::to_gpui_animation) and sample with the delta
+    /// the animator receives. The result may overshoot the target. When
+    /// [motion is reduced](super::reduce_motion) this is always the target.
+    ///
+    /// Sampling with increasing progress, as an animator does frame by frame,
+    /// continues the simulation from the previous sample. Going back restarts
+    /// it from the spring's current state.
+    pub fn sample(&self, progress: f32) -> f32 {
+        let progress = settle(progress);
+        if progress >= 1.0 {
+            return self.target;
+        }
+        let elapsed = self.duration().mul_f32(progress.max(0.0));
+        let cursor = self
+            .cursor
+            .get()
+            .filter(|cursor| cursor.elapsed <= elapsed)
+            .unwrap_or(SampleCursor {
+                elapsed: Duration::ZERO,
+                value: self.value,
+                velocity: self.velocity,
+                settled: self.settled,
+            });
+        let mut spring = Self {
+            value: cursor.value,
+            velocity: cursor.velocity,
+            settled: cursor.settled,
+            settle_time: Cell::new(None),
+            cursor: Cell::new(None),
+            ..*self
+        };
+        spring.step(elapsed - cursor.elapsed);
+        self.cursor.set(Some(SampleCursor {
+            elapsed,
+            value: spring.value,
+            velocity: spring.velocity,
+            settled: spring.settled,
+        }));
+        spring.value
+    }
+
+    /// A linear gpui animation lasting as long as the spring takes to settle.
+    pub fn to_gpui_animation(&self) -> Animation {
+        Animation::new(self.duration())
+    }
+
+    /// Drop the cached simulation results after the state changes.
+    fn invalidate(&self) {
+        self.settle_time.set(None);
+        self.cursor.set(None);
+    }
+
+    fn integrate(&mut self, h: f32) {
+        let SpringConfig {
+            stiffness,
+            damping,
+            mass,
+            ..
+        } = self.config;
+        let force = -stiffness * (self.value - self.target) - damping * self.velocity;
+        // Semi-implicit Euler: update velocity first, then position.
+        self.velocity += force / mass.max(f32::EPSILON) * h;
+        self.value += self.velocity * h;
+    }
+
+    fn at_rest(&self) -> bool {
+        self.velocity.abs() <= self.config.rest_velocity * self.span
+            && (self.target - self.value).abs() <= self.config.rest_displacement * self.span
+    }
+}
+
+/// A spring from `0.0` to `target` with the default config.
+///
+/// Step it per frame with [`Spring::step`], or sample it inside a gpui animator:
+///
+/// ```ignore
+/// let slide = spring(24.0);
+/// div().with_animation("toast", slide.to_gpui_animation(), move |el, delta| {
+///     el.mt(px(24.0 - slide.sample(delta)))
+/// })
+/// ```
+pub fn spring(target: f32) -> Spring {
+    Spring::new(0.0, target, SpringConfig::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(mut spring: Spring) -> (Spring, f32) {
+        let mut peak = spring.value();
+        for _ in 0..1000 {
+            if spring.step(Duration::from_millis(16)) {
+                break;
+            }
+            peak = peak.max(spring.value());
+        }
+        (spring, peak)
+    }
+
+    #[test]
+    fn settles_on_target() {
+        let (spring, _) = run(spring(100.0));
+        assert!(spring.is_settled());
+        assert_eq!(spring.value(), 100.0);
+        assert_eq!(spring.velocity(), 0.0);
+    }
+
+    #[test]
+    fn damping_controls_overshoot() {
+        let (_, wobbly_peak) = run(Spring::new(0.0, 1.0, SpringConfig::WOBBLY));
+        assert!(wobbly_peak > 1.05, "peak {wobbly_peak}");
+
+        // Critically damped: damping = 2 * sqrt(stiffness * mass).
+        let critical = SpringConfig::new().with_stiffness(100.0).with_damping(20.0);
+        let (_, critical_peak) = run(Spring::new(0.0, 1.0, critical));
+        assert!(critical_peak <= 1.0, "peak {critical_peak}");
+    }
+
+    #[test]
+    fn duration_matches_stepping() {
+        let spring = spring(1.0);
+        let duration = spring.duration();
+        assert!(duration > Duration::from_millis(100) && duration < Duration::from_secs(2));
+        assert_eq!(SpringConfig::default().settle_duration(), duration);
+
+        assert_eq!(spring.sample(0.0), 0.0);
+        assert_eq!(spring.sample(1.0), 1.0);
+
+        let mut stepped = spring.clone();
+        assert!(!stepped.step(duration.mul_f32(0.5)));
+        assert!(stepped.duration() < duration);
+        assert!(stepped.step(duration));
+    }
+
+    #[test]
+    fn sampling_continues_from_the_previous_sample() {
+        let spring = Spring::new(0.0, 1.0, SpringConfig::WOBBLY);
+        let fresh = |progress: f32| Spring::new(0.0, 1.0, SpringConfig::WOBBLY).sample(progress);
+
+        for i in 0..=60 {
+            let progress = i as f32 / 60.0;
+            let diff = (spring.sample(progress) - fresh(progress)).abs();
+            assert!(diff < 1e-3, "progress {progress} off by {diff}");
+        }
+
+        // Going back restarts from the spring's own state.
+        assert!((spring.sample(0.25) - fresh(0.25)).abs() < 1e-3);
+        assert_eq!(spring.sample(0.0), 0.0);
+    }
+
+    #[test]
+    fn retargeting_keeps_velocity() {
+        let mut spring = spring(1.0);
+        spring.step(Duration::from_millis(50));
+        let velocity = spring.velocity();
+        assert!(velocity > 0.0);
+
+        spring.set_target(0.0);
+        assert_eq!(spring.velocity(), velocity);
+        assert!(!spring.is_settled());
+        let (spring, _) = run(spring);
+        assert_eq!(spring.value(), 0.0);
+    }
+}
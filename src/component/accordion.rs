@@ -0,0 +1,468 @@
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use gpui::{
+    AnyElement, App, Bounds, Div, ElementId, FocusHandle, InteractiveElement, IntoElement,
+    KeyDownEvent, ParentElement, RenderOnce, SharedString, StatefulInteractiveElement, Styled,
+    Transformation, Window, div, percentage, prelude::FluentBuilder, px, svg,
+};
+
+use crate::{
+    animation::{animate_value, duration, ease_in_out_cubic_clamped},
+    component::{ArrowDirection, BoundsTrackerElement, IconName, IconPath},
+    rtl,
+    theme::ActiveTheme,
+};
+
+/// Creates a new accordion element.
+///
+/// An accordion stacks sections whose content expands below their header. By
+/// default opening one section closes the others; use `.multiple(true)` to
+/// let several stay open. Headers toggle on click, or with Enter/Space when
+/// focused. Expanding animates the content's measured height, and shows the
+/// end state right away when [motion is reduced](crate::animation::reduce_motion).
+///
+/// # Example
+/// ```rust,ignore
+/// use yororen_ui::component::{accordion, AccordionItem};
+///
+/// accordion("faq")
+///     .item(AccordionItem::new("shipping", "Shipping").content(|_window, _cx| {
+///         label("Orders ship within two days.").into_any_element()
+///     }))
+///     .default_open(["shipping"]);
+/// ```
+pub fn accordion(id: impl Into<ElementId>) -> Accordion {
+    Accordion::new().id(id)
+}
+
+type ToggleFn = Arc<dyn Fn(&ElementId, bool, &mut Window, &mut App)>;
+type ContentFn = Rc<dyn Fn(&mut Window, &mut App) -> AnyElement>;
+
+/// How long a section takes to expand or collapse.
+const EXPAND_DURATION: Duration = duration::NORMAL;
+
+/// One section of an [`Accordion`].
+#[derive(Clone)]
+pub struct AccordionItem {
+    pub id: ElementId,
+    pub header: SharedString,
+    pub disabled: bool,
+    content: Option<ContentFn>,
+}
+
+impl AccordionItem {
+    pub fn new(id: impl Into<ElementId>, header: impl Into<SharedString>) -> Self {
+        Self {
+            id: id.into(),
+            header: header.into(),
+            disabled: false,
+            content: None,
+        }
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Renders the content shown while the section is open.
+    pub fn content<F>(mut self, render: F) -> Self
+    where
+        F: 'static + Fn(&mut Window, &mut App) -> AnyElement,
+    {
+        self.content = Some(Rc::new(render));
+        self
+    }
+
+    /// ARIA attributes for this section's header.
+    pub fn aria_attrs(&self, expanded: bool) -> Vec<(String, String)> {
+        let panel = self.content.as_ref().map(|_| self.id.to_string());
+        crate::a11y::accordion::header_attrs(expanded, self.disabled, panel.as_deref())
+    }
+}
+
+#[derive(IntoElement)]
+pub struct Accordion {
+    element_id: ElementId,
+    base: Div,
+    items: Vec<AccordionItem>,
+    multiple: bool,
+    default_open: Vec<ElementId>,
+    on_toggle: Option<ToggleFn>,
+}
+
+impl Default for Accordion {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Accordion {
+    pub fn new() -> Self {
+        Self {
+            element_id: "ui:accordion".into(),
+            base: div(),
+            items: Vec::new(),
+            multiple: false,
+            default_open: Vec::new(),
+            on_toggle: None,
+        }
+    }
+
+    pub fn id(mut self, id: impl Into<ElementId>) -> Self {
+        self.element_id = id.into();
+        self
+    }
+
+    /// Alias for `id(...)`. Use `key(...)` when you want to emphasize state identity.
+    pub fn key(self, key: impl Into<ElementId>) -> Self {
+        self.id(key)
+    }
+
+    pub fn item(mut self, item: AccordionItem) -> Self {
+        self.items.push(item);
+        self
+    }
+
+    pub fn items(mut self, items: impl IntoIterator<Item = AccordionItem>) -> Self {
+        self.items.extend(items);
+        self
+    }
+
+    /// Sets whether several sections can be open at once (default: false).
+    pub fn multiple(mut self, multiple: bool) -> Self {
+        self.multiple = multiple;
+        self
+    }
+
+    /// Sections open on first render.
+    pub fn default_open(mut self, ids: impl IntoIterator<Item = impl Into<ElementId>>) -> Self {
+        self.default_open = ids.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Called with a section's id and whether it is now open, for every
+    /// section that opens or closes.
+    pub fn on_toggle<F>(mut self, handler: F) -> Self
+    where
+        F: 'static + Fn(&ElementId, bool, &mut Window, &mut App),
+    {
+        self.on_toggle = Some(Arc::new(handler));
+        self
+    }
+}
+
+impl ParentElement for Accordion {
+    fn extend(&mut self, elements: impl IntoIterator<Item = AnyElement>) {
+        self.base.extend(elements);
+    }
+}
+
+impl Styled for Accordion {
+    fn style(&mut self) -> &mut gpui::StyleRefinement {
+        self.base.style()
+    }
+}
+
+impl InteractiveElement for Accordion {
+    fn interactivity(&mut self) -> &mut gpui::Interactivity {
+        self.base.interactivity()
+    }
+}
+
+impl StatefulInteractiveElement for Accordion {}
+
+impl RenderOnce for Accordion {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let id = self.element_id;
+        let multiple = self.multiple;
+        let theme = cx.theme().clone();
+        let chevron: SharedString = IconPath::from(IconName::Arrow(rtl::flip_left_right(
+            cx.text_direction(),
+            ArrowDirection::Right,
+            ArrowDirection::Left,
+        )))
+        .into();
+        // Rotating the collapsed arrow by a quarter turn points it down.
+        let quarter_turn = rtl::flip_left_right(cx.text_direction(), 0.25, -0.25);
+
+        let state = window.use_keyed_state((id.clone(), "ui:accordion:state"), cx, {
+            let mut initial = AccordionState::default();
+            for open in &self.default_open {
+                initial.toggle(open, multiple, None);
+            }
+            move |_, _| initial
+        });
+        let focus_handles = window
+            .use_keyed_state((id.clone(), "ui:accordion:focus"), cx, |_, _| {
+                HashMap::<ElementId, FocusHandle>::new()
+            })
+            .update(cx, |handles, cx| {
+                handles.retain(|section, _| self.items.iter().any(|item| &item.id == section));
+                for item in &self.items {
+                    handles
+                        .entry(item.id.clone())
+                        .or_insert_with(|| cx.focus_handle().tab_stop(true));
+                }
+                handles.clone()
+            });
+
+        let now = Instant::now();
+        let openness = state.update(cx, |state, _| {
+            state.finish(now);
+            self.items
+                .iter()
+                .map(|item| state.openness(&item.id, now))
+                .collect::<Vec<_>>()
+        });
+        if openness.iter().any(|t| *t > 0.0 && *t < 1.0) {
+            window.request_animation_frame();
+        }
+
+        let toggle = {
+            let on_toggle = self.on_toggle.clone();
+            Rc::new(
+                move |section: &ElementId, window: &mut Window, cx: &mut App| {
+                    let changed = state.update(cx, |state, cx| {
+                        cx.notify();
+                        state.toggle(section, multiple, Some(Instant::now()))
+                    });
+                    if let Some(handler) = &on_toggle {
+                        for (section, open) in &changed {
+                            handler(section, *open, window, cx);
+                        }
+                    }
+                    window.refresh();
+                },
+            )
+        };
+
+        let sections = self
+            .items
+            .into_iter()
+            .zip(openness)
+            .map(|(item, openness)| {
+                let focus = &focus_handles[&item.id];
+                let focused = focus.is_focused(window);
+                let disabled = item.disabled;
+                let expanded = openness > 0.0;
+
+                let header = div()
+                    .id(item.id.clone())
+                    .track_focus(focus)
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .gap_2()
+                    .px_3()
+                    .py_2()
+                    .rounded_sm()
+                    .border_1()
+                    .border_color(if focused {
+                        theme.border.focus
+                    } else {
+                        gpui::transparent_black()
+                    })
+                    .text_color(if disabled {
+                        theme.content.disabled
+                    } else {
+                        theme.content.primary
+                    })
+                    .when(disabled, |this| this.cursor_not_allowed())
+                    .when(!disabled, |this| {
+                        let toggle_on_click = toggle.clone();
+                        let toggle_on_key = toggle.clone();
+                        let section = item.id.clone();
+                        let section_on_key = item.id.clone();
+                        this.cursor_pointer()
+                            .hover(|this| this.bg(theme.surface.hover))
+                            .on_click(move |_ev, window, cx| toggle_on_click(&section, window, cx))
+                            .on_key_down(move |ev: &KeyDownEvent, window, cx| {
+                                if matches!(ev.keystroke.key.as_str(), "enter" | "space") {
+                                    cx.stop_propagation();
+                                    toggle_on_key(&section_on_key, window, cx);
+                                }
+                            })
+                    })
+                    .child(item.header.clone())
+                    .child(
+                        svg()
+                            .path(chevron.clone())
+                            .size(px(12.))
+                            .flex_none()
+                            .text_color(theme.content.tertiary)
+                            .with_transformation(Transformation::rotate(percentage(
+                                quarter_turn * openness,
+                            ))),
+                    );
+
+                let body = item.content.filter(|_| expanded).map(|render| {
+                    let height = window.use_keyed_state(
+                        (id.clone(), format!("ui:accordion:height:{}", item.id)),
+                        cx,
+                        |_, _| Bounds::default(),
+                    );
+                    let measured = height.read(cx).size.height;
+                    let content = BoundsTrackerElement {
+                        bounds_state: height,
+                        inner: div()
+                            .px_3()
+                            .py_2()
+                            .child(render(window, cx))
+                            .into_any_element(),
+                    };
+                    // Clip to a fraction of the measured height while moving.
+                    div()
+                        .overflow_hidden()
+                        .when(openness < 1.0, |this| {
+                            this.h(animate_value(px(0.), measured, openness))
+                        })
+                        .child(content)
+                });
+
+                div()
+                    .flex()
+                    .flex_col()
+                    .border_b_1()
+                    .border_color(theme.border.divider)
+                    .child(header)
+                    .children(body)
+            })
+            .collect::<Vec<_>>();
+
+        self.base.id(id).flex().flex_col().children(sections)
+    }
+}
+
+/// A section opening or closing.
+#[derive(Clone, Copy, Debug)]
+struct Motion {
+    opening: bool,
+    started: Instant,
+}
+
+/// Open sections and the ones still moving.
+#[derive(Clone, Debug, Default)]
+struct AccordionState {
+    open: HashSet<ElementId>,
+    motion: HashMap<ElementId, Motion>,
+}
+
+impl AccordionState {
+    /// Opens or closes `section`, closing the others unless `multiple`.
+    /// Animates from `now` if given. Returns the sections that changed and
+    /// whether each is now open.
+    fn toggle(
+        &mut self,
+        section: &ElementId,
+        multiple: bool,
+        now: Option<Instant>,
+    ) -> Vec<(ElementId, bool)> {
+        let mut changed = Vec::new();
+        let opening = !self.open.contains(section);
+        if opening && !multiple {
+            for other in self.open.drain().collect::<Vec<_>>() {
+                changed.push((other, false));
+            }
+        }
+        changed.push((section.clone(), opening));
+
+        for (section, open) in &changed {
+            if *open {
+                self.open.insert(section.clone());
+            } else {
+                self.open.remove(section);
+            }
+            let Some(now) = now else {
+                continue;
+            };
+            // Reversing mid-way continues from the current height.
+            let done = self
+                .motion
+                .get(section)
+                .map(|motion| 1.0 - progress(motion, now))
+                .unwrap_or(0.0);
+            self.motion.insert(
+                section.clone(),
+                Motion {
+                    opening: *open,
+                    started: now - EXPAND_DURATION.mul_f32(done),
+                },
+            );
+        }
+        changed
+    }
+
+    /// How far `section` is open, eased, from `0.0` (closed) to `1.0` (open).
+    fn openness(&self, section: &ElementId, now: Instant) -> f32 {
+        match self.motion.get(section) {
+            Some(motion) => {
+                let t = ease_in_out_cubic_clamped(progress(motion, now));
+                if motion.opening { t } else { 1.0 - t }
+            }
+            None if self.open.contains(section) => 1.0,
+            None => 0.0,
+        }
+    }
+
+    /// Forgets motions that have finished.
+    fn finish(&mut self, now: Instant) {
+        self.motion
+            .retain(|_, motion| ease_in_out_cubic_clamped(progress(motion, now)) < 1.0);
+    }
+}
+
+fn progress(motion: &Motion, now: Instant) -> f32 {
+    crate::animation::settle(crate::animation::progress_from_elapsed(
+        now.saturating_duration_since(motion.started),
+        EXPAND_DURATION,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id(name: &'static str) -> ElementId {
+        name.into()
+    }
+
+    #[test]
+    fn single_mode_closes_the_others() {
+        let mut state = AccordionState::default();
+        state.toggle(&id("a"), false, None);
+        let changed = state.toggle(&id("b"), false, None);
+        assert_eq!(changed, vec![(id("a"), false), (id("b"), true)]);
+        assert!(!state.open.contains(&id("a")));
+
+        let mut state = AccordionState::default();
+        state.toggle(&id("a"), true, None);
+        state.toggle(&id("b"), true, None);
+        assert_eq!(state.open.len(), 2);
+        assert_eq!(state.toggle(&id("b"), true, None), vec![(id("b"), false)]);
+    }
+
+    #[test]
+    fn sections_expand_over_time() {
+        let start = Instant::now();
+        let mut state = AccordionState::default();
+        state.toggle(&id("a"), false, Some(start));
+        assert_eq!(state.openness(&id("a"), start), 0.0);
+
+        let half = start + EXPAND_DURATION / 2;
+        let midway = state.openness(&id("a"), half);
+        assert!(midway > 0.0 && midway < 1.0);
+
+        // Closing half-way starts from the current height.
+        state.toggle(&id("a"), false, Some(half));
+        assert!((state.openness(&id("a"), half) - midway).abs() < 1e-3);
+
+        let end = half + EXPAND_DURATION;
+        state.finish(end);
+        assert!(state.motion.is_empty());
+        assert_eq!(state.openness(&id("a"), end), 0.0);
+    }
+}
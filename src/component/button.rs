@@ -1,11 +1,11 @@
 use std::sync::Arc;
 
 use gpui::{
-    ClickEvent, Div, ElementId, Hsla, InteractiveElement, IntoElement, ParentElement, RenderOnce,
-    StatefulInteractiveElement, Styled, div, prelude::FluentBuilder, px,
+    AnyElement, ClickEvent, Div, ElementId, Hsla, InteractiveElement, IntoElement, ParentElement,
+    RenderOnce, StatefulInteractiveElement, Styled, div, prelude::FluentBuilder, px,
 };
 
-use crate::component::{ClickCallback, HoverCallback, compute_action_style};
+use crate::component::{ClickCallback, HoverCallback, SpinnerSize, compute_action_style, spinner};
 use crate::theme::{ActionVariantKind, ActiveTheme};
 
 /// Creates a new button element.
@@ -34,11 +34,13 @@ pub fn button(id: impl Into<ElementId>) -> Button {
 pub struct Button {
     element_id: ElementId,
     base: Div,
+    children: Vec<AnyElement>,
 
     click_fn: Option<ClickCallback>,
     hover_fn: Option<HoverCallback>,
     clickable: bool,
     disabled: bool,
+    loading: bool,
     variant: ActionVariantKind,
 
     bg: Option<Hsla>,
@@ -69,10 +71,12 @@ impl Button {
         Self {
             element_id: "ui:button".into(),
             base: div().h(px(36.)).px_4().py_2(),
+            children: Vec::new(),
             click_fn: None,
             hover_fn: None,
             clickable: true,
             disabled: false,
+            loading: false,
             variant: ActionVariantKind::Neutral,
             bg: None,
             hover_bg: None,
@@ -99,6 +103,14 @@ impl Button {
         self
     }
 
+    /// Shows a spinner in place of the label and ignores clicks, for actions
+    /// still in progress. The label keeps its space, so the button doesn't
+    /// change size, and the variant colors stay as they are.
+    pub fn loading(mut self, loading: bool) -> Self {
+        self.loading = loading;
+        self
+    }
+
     pub fn variant(mut self, variant: ActionVariantKind) -> Self {
         self.variant = variant;
         self
@@ -139,7 +151,7 @@ impl Styled for Button {
 
 impl ParentElement for Button {
     fn extend(&mut self, elements: impl IntoIterator<Item = gpui::AnyElement>) {
-        self.base.extend(elements);
+        self.children.extend(elements);
     }
 }
 
@@ -155,6 +167,7 @@ impl RenderOnce for Button {
     fn render(self, _window: &mut gpui::Window, cx: &mut gpui::App) -> impl IntoElement {
         let clickable = self.clickable;
         let disabled = self.disabled;
+        let loading = self.loading;
         let click_fn = self.click_fn;
         let hover_fn = self.hover_fn;
         let bg = self.bg;
@@ -163,6 +176,15 @@ impl RenderOnce for Button {
 
         let action_style = compute_action_style(cx.theme(), variant, disabled, bg, hover_bg);
 
+        // While loading, the label stays in the layout but is hidden under the spinner.
+        let children = self.children.into_iter().map(|child| {
+            if loading {
+                div().invisible().child(child).into_any_element()
+            } else {
+                child
+            }
+        });
+
         self.base
             .id(self.element_id)
             .rounded_md()
@@ -171,10 +193,24 @@ impl RenderOnce for Button {
             .justify_center()
             .bg(action_style.bg)
             .text_color(action_style.fg)
-            .when(clickable && !disabled, |this| this.cursor_pointer())
+            .children(children)
+            .when(loading, |this| {
+                this.relative().child(
+                    div()
+                        .absolute()
+                        .inset_0()
+                        .flex()
+                        .items_center()
+                        .justify_center()
+                        .child(spinner().size(SpinnerSize::Sm).color(action_style.fg)),
+                )
+            })
+            .when(clickable && !disabled && !loading, |this| {
+                this.cursor_pointer()
+            })
             .when(disabled, |this| this.cursor_not_allowed())
             .on_click(move |ev, window, cx| {
-                if disabled {
+                if disabled || loading {
                     return;
                 }
                 if clickable && let Some(f) = &click_fn {
@@ -189,6 +225,8 @@ impl RenderOnce for Button {
                     }
                 })
             })
-            .hover(move |this| this.bg(action_style.hover_bg))
+            .when(!loading, |this| {
+                this.hover(move |this| this.bg(action_style.hover_bg))
+            })
     }
 }
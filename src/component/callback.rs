@@ -6,7 +6,7 @@
 
 use std::sync::Arc;
 
-use gpui::{App, ClickEvent, ElementId, MouseDownEvent, Window};
+use gpui::{App, ClickEvent, ElementId, MouseDownEvent, SharedString, Window};
 
 /// Callback for click events.
 ///
@@ -55,6 +55,14 @@ pub type ToggleCallback = Arc<dyn Fn(bool, Option<&ClickEvent>, &mut Window, &mu
 /// - `&mut App` - The application context
 pub type ChangeCallback<T> = Arc<dyn Fn(T, &mut Window, &mut App)>;
 
+/// Callback validating a text value.
+///
+/// # Parameters
+/// - `&str` - The value to validate
+///
+/// Returns `Err(message)` when the value is invalid.
+pub type ValidateCallback = Arc<dyn Fn(&str) -> Result<(), SharedString>>;
+
 /// Callback for value changes with event information.
 ///
 /// # Parameters
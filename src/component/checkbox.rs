@@ -39,6 +39,7 @@ pub struct Checkbox {
     element_id: ElementId,
     base: Div,
     checked: bool,
+    indeterminate: bool,
     disabled: bool,
     on_toggle: Option<ToggleCallback>,
     tone: Option<Hsla>,
@@ -56,6 +57,7 @@ impl Checkbox {
             element_id: "ui:checkbox".into(),
             base: div().w(px(18.)).h(px(18.)),
             checked: false,
+            indeterminate: false,
             disabled: false,
             on_toggle: None,
             tone: None,
@@ -77,6 +79,14 @@ impl Checkbox {
         self
     }
 
+    /// Show a dash instead of a check mark, e.g. for a parent whose children
+    /// are only partly checked. Takes effect while the checkbox is unchecked;
+    /// toggling it reports `true`.
+    pub fn indeterminate(mut self, indeterminate: bool) -> Self {
+        self.indeterminate = indeterminate;
+        self
+    }
+
     pub fn disabled(mut self, disabled: bool) -> Self {
         self.disabled = disabled;
         self
@@ -120,6 +130,7 @@ impl RenderOnce for Checkbox {
     fn render(self, window: &mut gpui::Window, cx: &mut gpui::App) -> impl IntoElement {
         let disabled = self.disabled;
         let explicit_checked = self.checked;
+        let indeterminate = self.indeterminate;
         let on_toggle = self.on_toggle;
         let tone = self.tone;
 
@@ -140,8 +151,10 @@ impl RenderOnce for Checkbox {
         let checked =
             resolve_state_value_simple(explicit_checked, &internal_checked, cx, use_internal);
 
+        let indeterminate = indeterminate && !checked;
+
         let theme = cx.theme();
-        let toggle_style = compute_toggle_style(theme, checked, disabled, tone);
+        let toggle_style = compute_toggle_style(theme, checked || indeterminate, disabled, tone);
 
         let mut base = self
             .base
@@ -174,7 +187,11 @@ impl RenderOnce for Checkbox {
             move |this, value| this.opacity(if checked { value } else { 1.0 - value * 0.3 }),
         );
 
-        base = base.when(checked, |this| this.child(animated_check));
+        base = base
+            .when(checked, |this| this.child(animated_check))
+            .when(indeterminate, |this| {
+                this.child(div().w(px(8.)).h(px(2.)).rounded_sm().bg(toggle_style.fg))
+            });
 
         base.on_click(move |ev, window, cx| {
             if disabled {
@@ -1,15 +1,21 @@
+use std::rc::Rc;
 use std::sync::Arc;
+use std::time::Duration;
 
 use gpui::{
-    Animation, AnimationExt, ClickEvent, Div, ElementId, Hsla, InteractiveElement, IntoElement,
-    ParentElement, Pixels, Bounds, RenderOnce, SharedString, StatefulInteractiveElement, Styled, div,
-    prelude::FluentBuilder, px,
+    Animation, AnimationExt, AnyElement, App, Bounds, ClickEvent, Div, ElementId, FontWeight, Hsla,
+    InteractiveElement, IntoElement, KeyboardButton, KeyboardClickEvent, ListAlignment, ListState,
+    ParentElement, Pixels, RenderOnce, SharedString, StatefulInteractiveElement, Styled, Window,
+    actions, div, list, prelude::FluentBuilder, px,
 };
 
 use crate::{
     animation::constants::duration,
-    component::{ArrowDirection, BoundsTrackerElement, IconName, compute_input_style, icon, text_input},
-    i18n::{I18n, I18nContext, TextDirection, defaults::DefaultPlaceholders},
+    component::{
+        ArrowDirection, BoundsTrackerElement, IconName, SpinnerSize, compute_input_style, icon,
+        spinner, tag, text_input,
+    },
+    i18n::{I18n, I18nContext, Locale, TextDirection, defaults::DefaultPlaceholders},
     theme::ActiveTheme,
 };
 
@@ -17,6 +23,34 @@ use crate::rtl;
 
 use crate::animation::ease_out_quint_clamped;
 
+/// Height of an option row; the virtualized menu sizes itself in these.
+const OPTION_ROW_HEIGHT: Pixels = px(36.);
+
+/// Menus with more rows than this render through a virtualized list.
+///
+/// `max_results` defaults to 12, so only combos that raise it past this
+/// (e.g. `.max_results(usize::MAX)` for long option sets) are virtualized.
+const VIRTUALIZE_THRESHOLD: usize = 50;
+
+/// Rows of a virtualized menu visible at once.
+const VISIBLE_ROWS: usize = 8;
+
+/// Default delay between the last keystroke and an `on_search` call.
+const SEARCH_DEBOUNCE: Duration = Duration::from_millis(250);
+
+actions!(
+    ui_combo_box,
+    [SelectPrevious, SelectNext, ToggleHighlighted]
+);
+
+pub(crate) fn init(cx: &mut App) {
+    cx.bind_keys([
+        gpui::KeyBinding::new("up", SelectPrevious, Some("UIComboBox")),
+        gpui::KeyBinding::new("down", SelectNext, Some("UIComboBox")),
+        gpui::KeyBinding::new("space", ToggleHighlighted, Some("UIComboBox")),
+    ]);
+}
+
 #[derive(Clone, Debug)]
 pub struct ComboBoxOption {
     pub value: String,
@@ -39,6 +73,35 @@ impl ComboBoxOption {
     }
 }
 
+/// An entry of the combo box menu: a selectable option or a section header.
+///
+/// A header titles the options that follow it, up to the next header. It is
+/// hidden while none of those options match the search.
+#[derive(Clone, Debug)]
+pub enum ComboBoxItem {
+    Option(ComboBoxOption),
+    Header(SharedString),
+}
+
+impl ComboBoxItem {
+    pub fn header(label: impl Into<SharedString>) -> Self {
+        Self::Header(label.into())
+    }
+
+    fn option(&self) -> Option<&ComboBoxOption> {
+        match self {
+            Self::Option(option) => Some(option),
+            Self::Header(_) => None,
+        }
+    }
+}
+
+impl From<ComboBoxOption> for ComboBoxItem {
+    fn from(option: ComboBoxOption) -> Self {
+        Self::Option(option)
+    }
+}
+
 /// Creates a new combo box element.
 /// Requires an id to be set via `.id()` for internal state management.
 ///
@@ -84,19 +147,234 @@ fn menu_width_px(menu_width: Option<Pixels>, default: Pixels) -> Pixels {
 
 type ChangeFn = Arc<dyn Fn(String, &ClickEvent, &mut gpui::Window, &mut gpui::App)>;
 type SimpleChangeFn = Arc<dyn Fn(String)>;
+type FilterFn = Arc<dyn Fn(usize)>;
+type ValuesChangeFn = Arc<dyn Fn(Vec<String>, &mut Window, &mut App)>;
+type CreateFn = Arc<dyn Fn(String)>;
+type SearchFn = Arc<dyn Fn(String, &mut Window, &mut App)>;
+type CommitFn = Rc<dyn Fn(String, &ClickEvent, &mut Window, &mut App)>;
+
+/// The query a "Create" row would add, if `query` is not blank and matches no
+/// option's label or value exactly.
+fn create_candidate(options: &[ComboBoxOption], query: &str) -> Option<String> {
+    let trimmed = query.trim();
+    if trimmed.is_empty()
+        || options
+            .iter()
+            .any(|opt| opt.value == trimmed || opt.label.as_ref() == trimmed)
+    {
+        return None;
+    }
+    Some(query.to_string())
+}
+
+/// Adds `value` to the selection, or removes it if it is already selected.
+fn toggle_value(values: &mut Vec<String>, value: &str) {
+    match values.iter().position(|selected| selected == value) {
+        Some(index) => {
+            values.remove(index);
+        }
+        None => values.push(value.to_string()),
+    }
+}
+
+/// Moves the keyboard highlight one enabled option forward or back, wrapping
+/// around the ends. Stays put when no option is enabled.
+fn move_highlight(options: &[ComboBoxOption], current: usize, forward: bool) -> usize {
+    let len = options.len();
+    (1..=len)
+        .map(|step| {
+            if forward {
+                (current + step) % len
+            } else {
+                (current + len * 2 - step) % len
+            }
+        })
+        .find(|&index| !options[index].disabled)
+        .unwrap_or(current)
+}
+
+/// Whether `opt`'s label or value contains `query_lower` (case-insensitive).
+fn matches_query(opt: &ComboBoxOption, query_lower: &str) -> bool {
+    query_lower.is_empty()
+        || opt.label.to_lowercase().contains(query_lower)
+        || opt.value.to_lowercase().contains(query_lower)
+}
+
+/// What the open menu lists for the current search.
+#[derive(Debug, Default)]
+struct MenuItems {
+    /// Matching options, at most `limit` of them.
+    options: Vec<ComboBoxOption>,
+    /// Headers to draw, each before the option at its index in `options`.
+    headers: Vec<(usize, SharedString)>,
+    /// Number of matching options before truncation.
+    match_count: usize,
+}
+
+/// A row of the open menu.
+#[derive(Clone, Debug, PartialEq)]
+enum MenuRow {
+    Header(SharedString),
+    /// Index into the listed options.
+    Option(usize),
+}
+
+/// Whether a menu of `row_count` rows renders through a virtualized list.
+fn virtualizes(row_count: usize) -> bool {
+    row_count > VIRTUALIZE_THRESHOLD
+}
+
+/// Interleaves `headers` with `option_count` options, in display order.
+fn menu_rows(option_count: usize, headers: Vec<(usize, SharedString)>) -> Vec<MenuRow> {
+    let mut headers = headers.into_iter().peekable();
+    let mut rows = Vec::with_capacity(option_count + headers.len());
+    for index in 0..option_count {
+        while let Some((_, label)) = headers.next_if(|(at, _)| *at == index) {
+            rows.push(MenuRow::Header(label));
+        }
+        rows.push(MenuRow::Option(index));
+    }
+    rows
+}
+
+fn header_row(label: SharedString, color: Hsla) -> AnyElement {
+    div()
+        .px_3()
+        .pt_2()
+        .pb_1()
+        .text_xs()
+        .font_weight(FontWeight::SEMIBOLD)
+        .text_color(color)
+        .child(label)
+        .into_any_element()
+}
+
+/// Lays out `items` for `query`, keeping a header only when one of its options is shown.
+fn menu_items(items: &[ComboBoxItem], query: &str, filter: bool, limit: usize) -> MenuItems {
+    let query_lower = query.to_lowercase();
+    let mut menu = MenuItems::default();
+    let mut header = None;
+    for item in items {
+        match item {
+            ComboBoxItem::Header(label) => header = Some(label.clone()),
+            ComboBoxItem::Option(opt) => {
+                if filter && !matches_query(opt, &query_lower) {
+                    continue;
+                }
+                menu.match_count += 1;
+                if menu.options.len() < limit {
+                    if let Some(label) = header.take() {
+                        menu.headers.push((menu.options.len(), label));
+                    }
+                    menu.options.push(opt.clone());
+                }
+            }
+        }
+    }
+    menu
+}
+
+/// Which placeholder row the open menu shows instead of options.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum EmptyRow {
+    /// The combo box has no options at all.
+    NoOptions,
+    /// Options exist but none match the query.
+    NoResults,
+}
+
+fn empty_row_kind(option_count: usize, match_count: usize) -> Option<EmptyRow> {
+    if option_count == 0 {
+        Some(EmptyRow::NoOptions)
+    } else if match_count == 0 {
+        Some(EmptyRow::NoResults)
+    } else {
+        None
+    }
+}
+
+/// Whether the combo box should ignore interaction.
+fn effectively_disabled(disabled: bool, disable_when_empty: bool, option_count: usize) -> bool {
+    disabled || (disable_when_empty && option_count == 0)
+}
+
+/// Looks up `key` in the active translations, falling back to a built-in default.
+fn localized_text(
+    key: &str,
+    i18n: Option<&I18n>,
+    default: fn(&Locale) -> &'static str,
+) -> SharedString {
+    i18n.and_then(|i18n| i18n.t(key))
+        .map(str::to_string)
+        .unwrap_or_else(|| {
+            let locale = i18n.map(|i18n| i18n.locale().clone()).unwrap_or_default();
+            default(&locale).to_string()
+        })
+        .into()
+}
+
+/// Resolves the text shown when no option matches the query.
+///
+/// A translation key takes precedence; otherwise the localized default is used.
+fn empty_message_text(key: Option<&str>, i18n: Option<&I18n>) -> SharedString {
+    if let Some(key) = key {
+        return i18n
+            .and_then(|i18n| i18n.t(key))
+            .map(|text| SharedString::from(text.to_string()))
+            .unwrap_or_else(|| SharedString::from(key.to_string()));
+    }
+
+    localized_text(
+        "combobox.no_results",
+        i18n,
+        DefaultPlaceholders::combobox_no_results,
+    )
+}
+
+/// Resolves the label of the row that creates `query` as a new option.
+fn create_row_text(query: &str, i18n: Option<&I18n>) -> SharedString {
+    localized_text(
+        "combobox.create",
+        i18n,
+        DefaultPlaceholders::combobox_create,
+    )
+    .replace("{query}", query)
+    .into()
+}
+
+/// Resolves the text of the row shown while options are loading.
+fn loading_text(i18n: Option<&I18n>) -> SharedString {
+    localized_text("common.loading", i18n, DefaultPlaceholders::loading)
+}
+
+/// Resolves the text shown when the combo box has no options.
+fn no_options_text(i18n: Option<&I18n>) -> SharedString {
+    localized_text(
+        "combobox.no_options",
+        i18n,
+        DefaultPlaceholders::combobox_no_options,
+    )
+}
 
 #[derive(IntoElement)]
 pub struct ComboBox {
     element_id: ElementId,
     base: Div,
-    options: Vec<ComboBoxOption>,
+    items: Vec<ComboBoxItem>,
 
     value: Option<String>,
+    multiple: bool,
+    values: Option<Vec<String>>,
+    creatable: bool,
+    loading: bool,
+    filter_locally: bool,
+    search_debounce: Duration,
     placeholder: SharedString,
     search_placeholder: SharedString,
     /// Whether to use localized placeholders from i18n
     localized: bool,
     disabled: bool,
+    disable_when_empty: bool,
 
     bg: Option<Hsla>,
     border: Option<Hsla>,
@@ -106,8 +384,14 @@ pub struct ComboBox {
 
     menu_width: Option<gpui::Pixels>,
     max_results: usize,
+    empty_message: Option<gpui::AnyElement>,
+    empty_message_key: Option<SharedString>,
     on_change: Option<ChangeFn>,
     on_change_simple: Option<SimpleChangeFn>,
+    on_filter: Option<FilterFn>,
+    on_change_values: Option<ValuesChangeFn>,
+    on_create: Option<CreateFn>,
+    on_search: Option<SearchFn>,
 }
 
 impl Default for ComboBox {
@@ -121,12 +405,19 @@ impl ComboBox {
         Self {
             element_id: "ui:combo-box".into(),
             base: div(),
-            options: Vec::new(),
+            items: Vec::new(),
             value: None,
+            multiple: false,
+            values: None,
+            creatable: false,
+            loading: false,
+            filter_locally: true,
+            search_debounce: SEARCH_DEBOUNCE,
             placeholder: "Select…".into(),
             search_placeholder: "Search…".into(),
             localized: false,
             disabled: false,
+            disable_when_empty: false,
             bg: None,
             border: None,
             focus_border: None,
@@ -134,8 +425,14 @@ impl ComboBox {
             height: None,
             menu_width: None,
             max_results: 12,
+            empty_message: None,
+            empty_message_key: None,
             on_change: None,
             on_change_simple: None,
+            on_filter: None,
+            on_change_values: None,
+            on_create: None,
+            on_search: None,
         }
     }
 
@@ -157,12 +454,36 @@ impl ComboBox {
     }
 
     pub fn option(mut self, option: ComboBoxOption) -> Self {
-        self.options.push(option);
+        self.items.push(option.into());
         self
     }
 
     pub fn options(mut self, options: impl IntoIterator<Item = ComboBoxOption>) -> Self {
-        self.options.extend(options);
+        self.items
+            .extend(options.into_iter().map(ComboBoxItem::from));
+        self
+    }
+
+    /// Starts a section of the menu titled `label`; options added after it belong to it.
+    pub fn header(mut self, label: impl Into<SharedString>) -> Self {
+        self.items.push(ComboBoxItem::header(label));
+        self
+    }
+
+    /// Adds options and section headers in menu order.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// combo_box("font").items([
+    ///     ComboBoxItem::header("Recent"),
+    ///     ComboBoxOption::new("inter", "Inter").into(),
+    ///     ComboBoxItem::header("All"),
+    ///     ComboBoxOption::new("arial", "Arial").into(),
+    /// ])
+    /// ```
+    pub fn items(mut self, items: impl IntoIterator<Item = ComboBoxItem>) -> Self {
+        self.items.extend(items);
         self
     }
 
@@ -171,6 +492,52 @@ impl ComboBox {
         self
     }
 
+    /// Let the user pick any number of options (default: false).
+    ///
+    /// Picking an option toggles it and keeps the menu open; the selection is
+    /// shown as removable chips in the trigger and reported through
+    /// [`on_change_values`](Self::on_change_values).
+    pub fn multiple(mut self, multiple: bool) -> Self {
+        self.multiple = multiple;
+        self
+    }
+
+    /// The selected values in `multiple` mode.
+    pub fn values(mut self, values: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.values = Some(values.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Offer a "Create" row for search text that matches no option (default: false).
+    ///
+    /// Picking it selects the raw search text (as a chip in `multiple` mode) and
+    /// calls [`on_create`](Self::on_create) so the host can persist the new option.
+    pub fn creatable(mut self, creatable: bool) -> Self {
+        self.creatable = creatable;
+        self
+    }
+
+    /// Show a loading row in the menu, e.g. while `on_search` results are in flight.
+    pub fn loading(mut self, loading: bool) -> Self {
+        self.loading = loading;
+        self
+    }
+
+    /// Filter options by the search text (default: true).
+    ///
+    /// Turn this off when `on_search` returns server-side matches, so they are
+    /// shown as given.
+    pub fn filter_locally(mut self, filter_locally: bool) -> Self {
+        self.filter_locally = filter_locally;
+        self
+    }
+
+    /// Delay after the last keystroke before `on_search` is called (default: 250ms).
+    pub fn search_debounce(mut self, delay: Duration) -> Self {
+        self.search_debounce = delay;
+        self
+    }
+
     pub fn placeholder(mut self, placeholder: impl Into<SharedString>) -> Self {
         self.placeholder = placeholder.into();
         self
@@ -186,11 +553,51 @@ impl ComboBox {
         self
     }
 
+    /// Render as disabled while the option list is empty (default: false).
+    ///
+    /// Useful for dynamically-populated combos whose options are not loaded yet.
+    pub fn disable_when_empty(mut self, disable: bool) -> Self {
+        self.disable_when_empty = disable;
+        self
+    }
+
+    /// Caps how many matching options the menu shows (12 by default).
+    ///
+    /// Raise it above 50 to let long lists render virtualized instead of
+    /// truncating them.
     pub fn max_results(mut self, max_results: usize) -> Self {
         self.max_results = max_results.max(1);
         self
     }
 
+    /// Custom content shown in the menu when no option matches the search.
+    ///
+    /// A combo box without any options shows the localized `combobox.no_options` instead.
+    pub fn empty_message(mut self, message: impl IntoElement) -> Self {
+        self.empty_message = Some(message.into_any_element());
+        self
+    }
+
+    /// Translation key for the message shown when no option matches the search.
+    ///
+    /// Defaults to the localized `combobox.no_results`.
+    pub fn empty_message_key(mut self, key: impl Into<SharedString>) -> Self {
+        self.empty_message_key = Some(key.into());
+        self
+    }
+
+    /// Called with the number of options matching the current search.
+    ///
+    /// The count is taken before `max_results` truncation, so it can back a
+    /// "12 results" header. It is reported when the menu opens and whenever it changes.
+    pub fn on_filter<F>(mut self, handler: F) -> Self
+    where
+        F: 'static + Fn(usize),
+    {
+        self.on_filter = Some(Arc::new(handler));
+        self
+    }
+
     pub fn on_change<F>(mut self, handler: F) -> Self
     where
         F: 'static + Fn(String, &ClickEvent, &mut gpui::Window, &mut gpui::App),
@@ -218,6 +625,49 @@ impl ComboBox {
         self
     }
 
+    /// Called with the full selection whenever it changes in `multiple` mode.
+    pub fn on_change_values<F>(mut self, handler: F) -> Self
+    where
+        F: 'static + Fn(Vec<String>, &mut Window, &mut App),
+    {
+        self.on_change_values = Some(Arc::new(handler));
+        self
+    }
+
+    /// Called with the search text when the user picks the "Create" row.
+    pub fn on_create<F>(mut self, handler: F) -> Self
+    where
+        F: 'static + Fn(String),
+    {
+        self.on_create = Some(Arc::new(handler));
+        self
+    }
+
+    /// Called with the search text once typing pauses, so the host can fetch
+    /// matching options.
+    ///
+    /// Results are shown by re-rendering the combo box with the new `options(...)`;
+    /// set `loading(true)` meanwhile. A keystroke cancels any call still waiting
+    /// on the debounce, so only the latest query is searched.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let view = cx.entity();
+    /// combo_box("city")
+    ///     .options(self.cities.clone())
+    ///     .loading(self.searching)
+    ///     .filter_locally(false)
+    ///     .on_search(move |query, _window, cx| view.update(cx, |this, cx| this.search(query, cx)))
+    /// ```
+    pub fn on_search<F>(mut self, handler: F) -> Self
+    where
+        F: 'static + Fn(String, &mut Window, &mut App),
+    {
+        self.on_search = Some(Arc::new(handler));
+        self
+    }
+
     pub fn bg(mut self, color: impl Into<Hsla>) -> Self {
         self.bg = Some(color.into());
         self
@@ -293,10 +743,15 @@ fn call_on_change(
 
 impl RenderOnce for ComboBox {
     fn render(self, window: &mut gpui::Window, cx: &mut gpui::App) -> impl IntoElement {
-        let disabled = self.disabled;
+        let items = self.items;
+        let options: Vec<ComboBoxOption> = items
+            .iter()
+            .filter_map(ComboBoxItem::option)
+            .cloned()
+            .collect();
+        let disabled = effectively_disabled(self.disabled, self.disable_when_empty, options.len());
         let height = self.height.unwrap_or_else(|| px(36.).into());
         let menu_width = self.menu_width;
-        let options = self.options;
         let localized = self.localized;
         let placeholder = if localized {
             DefaultPlaceholders::select_placeholder(cx.i18n().locale()).into()
@@ -310,7 +765,18 @@ impl RenderOnce for ComboBox {
         };
         let on_change = self.on_change;
         let on_change_simple = self.on_change_simple;
+        let on_filter = self.on_filter;
         let max_results = self.max_results;
+        let empty_message = self.empty_message;
+        let empty_message_key = self.empty_message_key;
+        let multiple = self.multiple;
+        let on_change_values = self.on_change_values;
+        let creatable = self.creatable;
+        let on_create = self.on_create;
+        let on_search = self.on_search;
+        let loading = self.loading;
+        let filter_locally = self.filter_locally;
+        let search_debounce = self.search_debounce;
 
         // ComboBox requires an element ID for keyed state management.
         // Use `.id()` to provide a stable ID, or a unique ID will be generated automatically.
@@ -340,8 +806,18 @@ impl RenderOnce for ComboBox {
                 SharedString::new_static("")
             });
 
+        let highlight =
+            window.use_keyed_state((id.clone(), format!("{}:highlight", id)), cx, |_, _| 0usize);
+
+        // Bumped on every keystroke; a debounced search only runs if it still matches.
+        let search_epoch = on_search.is_some().then(|| {
+            window.use_keyed_state((id.clone(), format!("{}:search-epoch", id)), cx, |_, _| {
+                0usize
+            })
+        });
+
         let use_internal_value =
-            on_change.is_none() && on_change_simple.is_none() && self.value.is_none();
+            !multiple && on_change.is_none() && on_change_simple.is_none() && self.value.is_none();
         let internal_value = use_internal_value.then(|| {
             window.use_keyed_state((id.clone(), format!("{}:value", id)), cx, |_, _| {
                 options
@@ -364,10 +840,126 @@ impl RenderOnce for ComboBox {
                 .unwrap_or_default()
         };
 
+        let use_internal_values = multiple && on_change_values.is_none() && self.values.is_none();
+        let internal_values = use_internal_values.then(|| {
+            window.use_keyed_state((id.clone(), format!("{}:values", id)), cx, |_, _| {
+                Vec::<String>::new()
+            })
+        });
+        let values = match &internal_values {
+            Some(internal_values) => internal_values.read(cx).clone(),
+            None => self.values.clone().unwrap_or_default(),
+        };
+
         let selected_label = options
             .iter()
             .find(|opt| opt.value == value)
-            .map(|opt| opt.label.clone());
+            .map(|opt| opt.label.clone())
+            .or_else(|| (creatable && !value.is_empty()).then(|| value.clone().into()));
+
+        let menu_open_for_select = menu_open.clone();
+        // Applies a pick: toggles it in `multiple` mode, otherwise selects it and closes the menu.
+        let commit: CommitFn = Rc::new({
+            let values = values.clone();
+            let internal_value = internal_value.clone();
+            let internal_values = internal_values.clone();
+            let on_change = on_change.clone();
+            let on_change_simple = on_change_simple.clone();
+            move |option_value, ev, window, cx| {
+                if multiple {
+                    let mut next = internal_values
+                        .as_ref()
+                        .map(|state| state.read(cx).clone())
+                        .unwrap_or_else(|| values.clone());
+                    toggle_value(&mut next, &option_value);
+                    if let Some(internal_values) = &internal_values {
+                        internal_values.update(cx, |state, _| *state = next.clone());
+                    }
+                    if let Some(handler) = &on_change_values {
+                        handler(next, window, cx);
+                    }
+                    return;
+                }
+
+                if let Some(internal_value) = &internal_value {
+                    internal_value.update(cx, |state, _| {
+                        *state = option_value.clone();
+                    });
+                }
+
+                call_on_change(
+                    option_value,
+                    on_change.as_ref(),
+                    on_change_simple.as_ref(),
+                    ev,
+                    window,
+                    cx,
+                );
+
+                menu_open_for_select.update(cx, |open, _| *open = false);
+            }
+        });
+
+        // Applies a pick of the "Create" row.
+        let create: CommitFn = Rc::new({
+            let values = values.clone();
+            let internal_values = internal_values.clone();
+            let commit = commit.clone();
+            let search_text = search_text.clone();
+            let needs_content_init = needs_content_init.clone();
+            let highlight = highlight.clone();
+            move |query, ev, window, cx| {
+                if let Some(on_create) = &on_create {
+                    on_create(query.clone());
+                }
+
+                if !multiple {
+                    commit(query, ev, window, cx);
+                    return;
+                }
+
+                let already_selected = internal_values
+                    .as_ref()
+                    .map(|state| state.read(cx).contains(&query))
+                    .unwrap_or_else(|| values.contains(&query));
+                if !already_selected {
+                    commit(query, ev, window, cx);
+                }
+                // Clear the search so the next tag can be typed right away.
+                search_text.update(cx, |text, _| *text = SharedString::new_static(""));
+                needs_content_init.update(cx, |init, _| *init = true);
+                highlight.update(cx, |index, _| *index = 0);
+            }
+        });
+
+        let chips = (multiple && !values.is_empty()).then(|| {
+            div()
+                .id(format!("{}:chips", id))
+                .flex_1()
+                .min_w(px(0.))
+                .max_h(px(88.))
+                .overflow_y_scroll()
+                .flex()
+                .flex_wrap()
+                .gap_1()
+                .children(values.iter().map(|selected| {
+                    let label = options
+                        .iter()
+                        .find(|opt| &opt.value == selected)
+                        .map(|opt| opt.label.to_string())
+                        .unwrap_or_else(|| selected.clone());
+                    let commit = commit.clone();
+                    let selected = selected.clone();
+                    div()
+                        .id((ElementId::from("ui:combo-box:chip"), selected.clone()))
+                        .child(tag(label).h(px(22.)).closable(!disabled).on_close(
+                            move |ev, window, cx| {
+                                cx.stop_propagation();
+                                commit(selected.clone(), ev, window, cx);
+                            },
+                        ))
+                }))
+        });
 
         let theme = cx.theme().clone();
         let hint = theme.content.tertiary;
@@ -381,14 +973,16 @@ impl RenderOnce for ComboBox {
             self.text_color,
         );
 
+        let last_filter_count =
+            window.use_keyed_state((id.clone(), format!("{}:filter-count", id)), cx, |_, _| {
+                None::<usize>
+            });
+
         let menu_open_for_button = menu_open.clone();
+        let last_filter_count_for_button = last_filter_count.clone();
         let menu_open_for_outside = menu_open.clone();
-        let menu_open_for_select = menu_open.clone();
-
-        let internal_value_for_select = internal_value.clone();
-        let on_change_for_select = on_change.clone();
-        let on_change_simple_for_select = on_change_simple.clone();
 
+        let direction = cx.text_direction();
         let trigger = self
             .base
             .id(id.clone())
@@ -397,7 +991,14 @@ impl RenderOnce for ComboBox {
             .items_center()
             .justify_between()
             .gap_2()
-            .h(height)
+            .when(direction.is_rtl(), |this| this.flex_row_reverse())
+            .map(|this| {
+                if multiple {
+                    this.min_h(height).max_h(px(96.)).py_1()
+                } else {
+                    this.h(height)
+                }
+            })
             .px_3()
             .rounded_md()
             .bg(input_style.bg)
@@ -414,20 +1015,26 @@ impl RenderOnce for ComboBox {
                     return;
                 }
                 menu_open_for_button.update(cx, |open, _| *open = !*open);
+                last_filter_count_for_button.update(cx, |count, _| *count = None);
+            })
+            .map(|this| match chips {
+                Some(chips) => this.child(chips),
+                None => this.child(
+                    div()
+                        .flex_1()
+                        .min_w(px(0.))
+                        .truncate()
+                        .text_align(rtl::text_align_start(direction))
+                        .text_color(
+                            selected_label
+                                .as_ref()
+                                .filter(|_| !multiple)
+                                .map(|_| input_style.text_color)
+                                .unwrap_or(hint),
+                        )
+                        .child(selected_label.filter(|_| !multiple).unwrap_or(placeholder)),
+                ),
             })
-            .child(
-                div()
-                    .flex_1()
-                    .min_w(px(0.))
-                    .truncate()
-                    .text_color(
-                        selected_label
-                            .as_ref()
-                            .map(|_| input_style.text_color)
-                            .unwrap_or(hint),
-                    )
-                    .child(selected_label.unwrap_or(placeholder)),
-            )
             .child(
                 icon(IconName::Arrow(ArrowDirection::Down))
                     .size(px(14.))
@@ -439,17 +1046,12 @@ impl RenderOnce for ComboBox {
                 let text_color = input_style.text_color;
                 let value = value.clone();
                 let options = options.clone();
-                let on_change = on_change_for_select.clone();
-                let on_change_simple = on_change_simple_for_select.clone();
-                let internal_value = internal_value_for_select.clone();
+                let items = items.clone();
                 let search_text = search_text.clone();
                 let needs_content_init = needs_content_init.clone();
                 let max_results = max_results;
 
-                let direction = cx
-                    .try_global::<I18n>()
-                    .map(|i18n| i18n.text_direction())
-                    .unwrap_or(TextDirection::Ltr);
+                let direction = cx.text_direction();
 
                 let trigger_bounds = *trigger_bounds_state_for_menu.read(cx);
                 let menu_width_px = menu_width_px(menu_width, px(420.));
@@ -464,82 +1066,175 @@ impl RenderOnce for ComboBox {
 
                 // Read search text for filtering
                 let query = search_text.read(cx).clone();
-                let query_lower = query.to_lowercase();
 
-                let filtered = options
-                    .into_iter()
-                    .filter(move |opt| {
-                        if query_lower.is_empty() {
-                            return true;
+                let create_query = creatable
+                    .then(|| create_candidate(&options, &query))
+                    .flatten();
+                let has_create = create_query.is_some();
+                // The "Create" row takes one of the `max_results` slots.
+                let MenuItems {
+                    options: mut filtered,
+                    mut headers,
+                    match_count,
+                } = menu_items(
+                    &items,
+                    &query,
+                    filter_locally,
+                    max_results - usize::from(has_create),
+                );
+                if let Some(create_query) = create_query {
+                    let label = create_row_text(&create_query, cx.try_global::<I18n>());
+                    filtered.insert(0, ComboBoxOption::new(create_query, label));
+                    for (index, _) in &mut headers {
+                        *index += 1;
+                    }
+                }
+                let highlighted = (*highlight.read(cx)).min(filtered.len().saturating_sub(1));
+                let rows = Rc::new(menu_rows(filtered.len(), headers));
+                let filtered = Rc::new(filtered);
+
+                let virtualized = virtualizes(rows.len());
+                // The list state and the query its row heights were measured for.
+                let virtual_list = window.use_keyed_state(
+                    (id.clone(), format!("{}:list-state", id)),
+                    cx,
+                    |_, _| {
+                        let state = ListState::new(0, ListAlignment::Top, OPTION_ROW_HEIGHT * 4.);
+                        (state, SharedString::default())
+                    },
+                );
+                let list_state = virtual_list.read(cx).0.clone();
+                if virtualized
+                    && (list_state.item_count() != rows.len() || virtual_list.read(cx).1 != query)
+                {
+                    list_state.reset(rows.len());
+                    virtual_list.update(cx, |(_, measured), _| *measured = query.clone());
+                }
+
+                // Keeps the highlighted option in view of the virtualized list.
+                let reveal = Rc::new({
+                    let rows = rows.clone();
+                    let list_state = list_state.clone();
+                    move |index: usize| {
+                        if let Some(ix) = virtualized
+                            .then(|| rows.iter().position(|row| *row == MenuRow::Option(index)))
+                            .flatten()
+                        {
+                            list_state.scroll_to_reveal_item(ix);
                         }
-                        opt.label.to_string().to_lowercase().contains(&query_lower)
-                            || opt.value.to_lowercase().contains(&query_lower)
-                    })
-                    .take(max_results)
-                    .collect::<Vec<_>>();
+                    }
+                });
+
+                // Picks the highlighted option from the keyboard.
+                let commit_highlighted = Rc::new({
+                    let filtered = filtered.clone();
+                    let highlight = highlight.clone();
+                    let commit = commit.clone();
+                    let create = create.clone();
+                    move |button: KeyboardButton, window: &mut Window, cx: &mut App| {
+                        let index = *highlight.read(cx);
+                        let Some(opt) = filtered.get(index) else {
+                            return;
+                        };
+                        if disabled || opt.disabled {
+                            return;
+                        }
+                        let ev = ClickEvent::Keyboard(KeyboardClickEvent {
+                            button,
+                            ..Default::default()
+                        });
+                        if has_create && index == 0 {
+                            create(opt.value.clone(), &ev, window, cx);
+                        } else {
+                            commit(opt.value.clone(), &ev, window, cx);
+                        }
+                    }
+                });
+
+                if let Some(on_filter) = &on_filter
+                    && *last_filter_count.read(cx) != Some(match_count)
+                {
+                    last_filter_count.update(cx, |count, _| *count = Some(match_count));
+                    on_filter(match_count);
+                }
 
-                let menu = div()
-                    .id(format!("{}:menu", id))
-                    .absolute()
-                    .top_full()
-                    .left_0()
-                    // Horizontal overflow protection: shift within window bounds.
-                    .when(relative_left != Pixels::ZERO, |this| this.left(relative_left))
-                    .mt(px(10.))
-                    .rounded_md()
-                    .border_1()
-                    .border_color(theme.border.default)
-                    .bg(theme.surface.raised)
-                    .shadow_md()
-                    .py_1()
-                    .w(menu_width_px)
-                    .occlude()
-                    .text_align(rtl::text_align_start(direction))
-                    .on_mouse_down_out({
-                        let needs_content_init = needs_content_init.clone();
-                        move |_ev, _window, cx| {
-                            menu_open_for_outside.update(cx, |open, _cx| *open = false);
-                            needs_content_init.update(cx, |v, _| *v = true);
+                let has_options = !options.is_empty() || creatable || on_search.is_some();
+                let empty_kind = empty_row_kind(options.len(), match_count)
+                    .filter(|_| !has_create && !loading);
+                let loading_row = loading.then(|| {
+                    div()
+                        .px_3()
+                        .py_2()
+                        .flex()
+                        .items_center()
+                        .gap_2()
+                        .text_color(hint)
+                        .child(
+                            spinner()
+                                .id(format!("{}:loading", id))
+                                .size(SpinnerSize::Sm)
+                                .color(hint),
+                        )
+                        .child(loading_text(cx.try_global::<I18n>()))
+                });
+                let empty_row = empty_kind.map(|kind| {
+                    let message = match kind {
+                        EmptyRow::NoOptions => {
+                            no_options_text(cx.try_global::<I18n>()).into_any_element()
                         }
-                    })
-                    .child(
-                        div().px_2().pb_2().child(
-                            text_input(format!("{}:query", id))
-                                .placeholder(search_placeholder)
-                                .bg(theme.surface.base)
-                                .border(theme.border.default)
-                                .focus_border(theme.border.focus)
-                                .text_color(theme.content.primary)
-                                .when(should_init_content, |this| this.content(query.clone()))
-                                .on_change({
-                                    let search_text = search_text.clone();
-                                    move |value, _window, cx| {
-                                        search_text.update(cx, |text, _| {
-                                            *text = value;
-                                        });
-                                    }
-                                }),
-                        ),
-                    )
-                    .children(filtered.into_iter().map(move |opt| {
-                        let is_selected = opt.value == value;
+                        EmptyRow::NoResults => empty_message.unwrap_or_else(|| {
+                            empty_message_text(
+                                empty_message_key.as_ref().map(|key| key.as_ref()),
+                                cx.try_global::<I18n>(),
+                            )
+                            .into_any_element()
+                        }),
+                    };
+                    div()
+                        .px_3()
+                        .py_2()
+                        .text_color(hint)
+                        .child(message)
+                });
+
+                let render_option = Rc::new({
+                    let create = create.clone();
+                    let commit = commit.clone();
+                    let values = values.clone();
+                    let value = value.clone();
+                    let theme = theme.clone();
+                    move |index: usize, opt: ComboBoxOption| -> AnyElement {
+                        let is_create = has_create && index == 0;
+                        let is_selected = if is_create {
+                            false
+                        } else if multiple {
+                            values.contains(&opt.value)
+                        } else {
+                            opt.value == value
+                        };
                         let is_disabled = disabled || opt.disabled;
                         let option_value = opt.value.clone();
-                        let menu_open_for_select = menu_open_for_select.clone();
-                        let on_change = on_change.clone();
-                        let on_change_simple = on_change_simple.clone();
-                        let internal_value = internal_value.clone();
+                        let pick = if is_create { create.clone() } else { commit.clone() };
 
                         let row_fg = if is_disabled {
                             theme.content.disabled
+                        } else if is_create {
+                            theme.action.primary.bg
                         } else {
                             text_color
                         };
 
                         div()
-                            .id((ElementId::from("ui:combo-box:option"), option_value.clone()))
+                            .id((
+                                ElementId::from(if is_create {
+                                    "ui:combo-box:create"
+                                } else {
+                                    "ui:combo-box:option"
+                                }),
+                                option_value.clone(),
+                            ))
+                            .h(OPTION_ROW_HEIGHT)
                             .px_3()
-                            .py_2()
                             .flex()
                             .items_center()
                             .justify_between()
@@ -550,6 +1245,9 @@ impl RenderOnce for ComboBox {
                                     .hover(|this| this.bg(theme.surface.hover))
                             })
                             .when(is_disabled, |this| this.cursor_not_allowed().opacity(0.6))
+                            .when(index == highlighted && !is_disabled, |this| {
+                                this.bg(theme.surface.hover)
+                            })
                             .child(opt.label)
                             .when(is_selected, |this| {
                                 this.child(
@@ -562,25 +1260,180 @@ impl RenderOnce for ComboBox {
                                 if is_disabled {
                                     return;
                                 }
+                                pick(option_value.clone(), ev, window, cx);
+                            })
+                            .into_any_element()
+                    }
+                });
 
-                                if let Some(internal_value) = &internal_value {
-                                    internal_value.update(cx, |state, _| {
-                                        *state = option_value.clone();
-                                    });
+                let menu = div()
+                    .id(format!("{}:menu", id))
+                    .absolute()
+                    .top_full()
+                    .left_0()
+                    // Horizontal overflow protection: shift within window bounds.
+                    .when(relative_left != Pixels::ZERO, |this| this.left(relative_left))
+                    .mt(px(10.))
+                    .rounded_md()
+                    .border_1()
+                    .border_color(theme.border.default)
+                    .bg(theme.surface.raised)
+                    .shadow_md()
+                    .py_1()
+                    .w(menu_width_px)
+                    .occlude()
+                    .text_align(rtl::text_align_start(direction))
+                    .on_mouse_down_out({
+                        let needs_content_init = needs_content_init.clone();
+                        let last_filter_count = last_filter_count.clone();
+                        move |_ev, _window, cx| {
+                            menu_open_for_outside.update(cx, |open, _cx| *open = false);
+                            needs_content_init.update(cx, |v, _| *v = true);
+                            last_filter_count.update(cx, |count, _| *count = None);
+                        }
+                    })
+                    .when(has_options, |this| {
+                        this.child(
+                            div()
+                                .px_2()
+                                .pb_2()
+                                .key_context("UIComboBox")
+                                .on_action({
+                                    let filtered = filtered.clone();
+                                    let highlight = highlight.clone();
+                                    let reveal = reveal.clone();
+                                    move |_: &SelectNext, _window, cx| {
+                                        let index = highlight.update(cx, |index, cx| {
+                                            *index = move_highlight(&filtered, *index, true);
+                                            cx.notify();
+                                            *index
+                                        });
+                                        reveal(index);
+                                    }
+                                })
+                                .on_action({
+                                    let filtered = filtered.clone();
+                                    let highlight = highlight.clone();
+                                    let reveal = reveal.clone();
+                                    move |_: &SelectPrevious, _window, cx| {
+                                        let index = highlight.update(cx, |index, cx| {
+                                            *index = move_highlight(&filtered, *index, false);
+                                            cx.notify();
+                                            *index
+                                        });
+                                        reveal(index);
+                                    }
+                                })
+                                .on_action({
+                                    let search_text = search_text.clone();
+                                    let commit_highlighted = commit_highlighted.clone();
+                                    move |_: &ToggleHighlighted, window, cx| {
+                                        // Space is part of the query once the user has typed one.
+                                        if !search_text.read(cx).is_empty() {
+                                            cx.propagate();
+                                            return;
+                                        }
+                                        commit_highlighted(KeyboardButton::Space, window, cx);
+                                    }
+                                })
+                                .when(multiple, |this| {
+                                    let search_text = search_text.clone();
+                                    let commit = commit.clone();
+                                    let values = values.clone();
+                                    this.capture_action(
+                                        move |_: &super::text_input::Backspace, window, cx| {
+                                            if !search_text.read(cx).is_empty() {
+                                                return;
+                                            }
+                                            if let Some(last) = values.last() {
+                                                cx.stop_propagation();
+                                                let event = ClickEvent::default();
+                                                commit(last.clone(), &event, window, cx);
+                                            }
+                                        },
+                                    )
+                                })
+                                .child(
+                                text_input(format!("{}:query", id))
+                                    .placeholder(search_placeholder)
+                                    .bg(theme.surface.base)
+                                    .border(theme.border.default)
+                                    .focus_border(theme.border.focus)
+                                    .text_color(theme.content.primary)
+                                    .when(should_init_content, |this| this.content(query.clone()))
+                                    .on_change({
+                                        let search_text = search_text.clone();
+                                        let highlight = highlight.clone();
+                                        let on_search = on_search.clone();
+                                        let search_epoch = search_epoch.clone();
+                                        move |value, window, cx| {
+                                            search_text.update(cx, |text, _| {
+                                                *text = value.clone();
+                                            });
+                                            highlight.update(cx, |index, _| *index = 0);
+
+                                            let (Some(on_search), Some(search_epoch)) =
+                                                (on_search.clone(), search_epoch.clone())
+                                            else {
+                                                return;
+                                            };
+                                            let epoch = search_epoch.update(cx, |epoch, _| {
+                                                *epoch = epoch.wrapping_add(1);
+                                                *epoch
+                                            });
+                                            window
+                                                .spawn(cx, async move |cx| {
+                                                    cx.background_executor()
+                                                        .timer(search_debounce)
+                                                        .await;
+                                                    cx.update(|window, cx| {
+                                                        if *search_epoch.read(cx) == epoch {
+                                                            on_search(
+                                                                value.to_string(),
+                                                                window,
+                                                                cx,
+                                                            );
+                                                        }
+                                                    })
+                                                    .ok();
+                                                })
+                                                .detach();
+                                        }
+                                    })
+                                    .on_submit({
+                                        let commit_highlighted = commit_highlighted.clone();
+                                        move |_, window, cx| {
+                                            commit_highlighted(KeyboardButton::Enter, window, cx)
+                                        }
+                                    }),
+                            ),
+                        )
+                    })
+                    .children(loading_row)
+                    .children(empty_row)
+                    .map(|this| {
+                        if virtualized {
+                            let rows = rows.clone();
+                            let filtered = filtered.clone();
+                            let list_height = OPTION_ROW_HEIGHT * VISIBLE_ROWS as f32;
+                            this.child(
+                                list(list_state.clone(), move |ix, _window, _cx| match &rows[ix] {
+                                    MenuRow::Header(label) => header_row(label.clone(), hint),
+                                    MenuRow::Option(index) => {
+                                        render_option(*index, filtered[*index].clone())
+                                    }
+                                })
+                                .h(list_height),
+                            )
+                        } else {
+                            this.children(rows.iter().map(|row| match row {
+                                MenuRow::Header(label) => header_row(label.clone(), hint),
+                                MenuRow::Option(index) => {
+                                    render_option(*index, filtered[*index].clone())
                                 }
-
-                                call_on_change(
-                                    option_value.clone(),
-                                    on_change.as_ref(),
-                                    on_change_simple.as_ref(),
-                                    ev,
-                                    window,
-                                    cx,
-                                );
-
-                                menu_open_for_select.update(cx, |open, _| *open = false);
-                            })
-                    }));
+                            }))
+                        }
+                    });
 
                 let animated_menu = menu.with_animation(
                     format!("combo-box-menu-{}", is_open),
@@ -597,3 +1450,194 @@ impl RenderOnce for ComboBox {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n::TranslationMap;
+
+    fn fruit_options() -> Vec<ComboBoxOption> {
+        vec![
+            ComboBoxOption::new("apple", "Apple"),
+            ComboBoxOption::new("apricot", "Apricot"),
+            ComboBoxOption::new("banana", "Banana"),
+        ]
+    }
+
+    #[test]
+    fn filter_count_reflects_query() {
+        let items: Vec<ComboBoxItem> = fruit_options().into_iter().map(Into::into).collect();
+        let count = |query| menu_items(&items, query, true, 12).match_count;
+        assert_eq!(count(""), 3);
+        assert_eq!(count("AP"), 2);
+        assert_eq!(count("nan"), 1);
+        assert_eq!(count("cherry"), 0);
+    }
+
+    #[test]
+    fn headers_follow_their_visible_options() {
+        let items = vec![
+            ComboBoxItem::header("Recent"),
+            ComboBoxOption::new("banana", "Banana").into(),
+            ComboBoxItem::header("All"),
+            ComboBoxOption::new("apple", "Apple").into(),
+            ComboBoxOption::new("apricot", "Apricot").into(),
+        ];
+
+        let all = menu_items(&items, "", true, 10);
+        assert_eq!(all.options.len(), 3);
+        assert_eq!(
+            all.headers,
+            [
+                (0, SharedString::from("Recent")),
+                (1, SharedString::from("All"))
+            ]
+        );
+
+        // "Recent" has no match, so only "All" is drawn.
+        let ap = menu_items(&items, "ap", true, 10);
+        assert_eq!(ap.match_count, 2);
+        assert_eq!(ap.headers, [(0, SharedString::from("All"))]);
+
+        // Headers don't count towards the limit.
+        let limited = menu_items(&items, "", true, 2);
+        assert_eq!(limited.options.len(), 2);
+        assert_eq!(limited.match_count, 3);
+        assert_eq!(limited.headers.len(), 2);
+
+        let unfiltered = menu_items(&items, "zzz", false, 10);
+        assert_eq!(unfiltered.options.len(), 3);
+    }
+
+    #[test]
+    fn long_lists_virtualize_only_with_raised_max_results() {
+        let items: Vec<ComboBoxItem> = (0..100)
+            .map(|i| ComboBoxOption::new(format!("item-{i}"), format!("Item {i}")).into())
+            .collect();
+        let virtualized = |combo: ComboBox| {
+            let menu = menu_items(&items, "", true, combo.max_results);
+            virtualizes(menu_rows(menu.options.len(), menu.headers).len())
+        };
+
+        assert!(!virtualized(ComboBox::new()));
+        assert!(!virtualized(ComboBox::new().max_results(VIRTUALIZE_THRESHOLD)));
+        assert!(virtualized(ComboBox::new().max_results(usize::MAX)));
+    }
+
+    #[test]
+    fn menu_rows_interleave_headers() {
+        let rows = menu_rows(3, vec![(0, "Recent".into()), (1, "All".into())]);
+        assert_eq!(
+            rows,
+            [
+                MenuRow::Header("Recent".into()),
+                MenuRow::Option(0),
+                MenuRow::Header("All".into()),
+                MenuRow::Option(1),
+                MenuRow::Option(2),
+            ]
+        );
+        assert!(menu_rows(0, Vec::new()).is_empty());
+    }
+
+    #[test]
+    fn empty_options_show_no_options_row() {
+        assert_eq!(empty_row_kind(0, 0), Some(EmptyRow::NoOptions));
+        assert_eq!(empty_row_kind(3, 0), Some(EmptyRow::NoResults));
+        assert_eq!(empty_row_kind(3, 2), None);
+
+        let en = I18n::with_locale(Locale::new("en").unwrap());
+        assert_eq!(
+            no_options_text(Some(&en)),
+            SharedString::from("No options available")
+        );
+    }
+
+    #[test]
+    fn disable_when_empty_only_applies_without_options() {
+        assert!(effectively_disabled(false, true, 0));
+        assert!(!effectively_disabled(false, true, 2));
+        assert!(!effectively_disabled(false, false, 0));
+        assert!(effectively_disabled(true, false, 2));
+    }
+
+    #[test]
+    fn empty_message_uses_custom_key() {
+        let locale = Locale::new("en").unwrap();
+        let mut map = TranslationMap::new();
+        map.insert("fruit.none", "No fruit found");
+        let mut i18n = I18n::with_locale(locale.clone());
+        i18n.load_translations(locale, map);
+
+        assert_eq!(
+            empty_message_text(Some("fruit.none"), Some(&i18n)),
+            SharedString::from("No fruit found")
+        );
+    }
+
+    #[test]
+    fn toggling_adds_and_removes_values() {
+        let mut values = vec!["apple".to_string()];
+        toggle_value(&mut values, "banana");
+        assert_eq!(values, ["apple", "banana"]);
+        toggle_value(&mut values, "apple");
+        assert_eq!(values, ["banana"]);
+    }
+
+    #[test]
+    fn highlight_wraps_and_skips_disabled_options() {
+        let mut options = fruit_options();
+        options[1] = options[1].clone().disabled(true);
+        assert_eq!(move_highlight(&options, 0, true), 2);
+        assert_eq!(move_highlight(&options, 2, true), 0);
+        assert_eq!(move_highlight(&options, 0, false), 2);
+        assert_eq!(move_highlight(&[], 0, true), 0);
+    }
+
+    #[test]
+    fn create_row_only_for_new_non_blank_queries() {
+        let options = fruit_options();
+        assert_eq!(
+            create_candidate(&options, "cherry"),
+            Some("cherry".to_string())
+        );
+        assert_eq!(create_candidate(&options, "apple"), None);
+        assert_eq!(create_candidate(&options, "Apricot"), None);
+        assert_eq!(create_candidate(&options, "app"), Some("app".to_string()));
+        assert_eq!(create_candidate(&options, "   "), None);
+        assert_eq!(create_candidate(&options, ""), None);
+    }
+
+    #[test]
+    fn create_row_text_inserts_query() {
+        let en = I18n::with_locale(Locale::new("en").unwrap());
+        assert_eq!(
+            create_row_text("cherry", Some(&en)),
+            SharedString::from("Create \"cherry\"")
+        );
+        assert_eq!(
+            create_row_text("樱桃", None),
+            SharedString::from("Create \"樱桃\"")
+        );
+    }
+
+    #[test]
+    fn loading_text_is_localized() {
+        let zh = I18n::with_locale(Locale::new("zh-CN").unwrap());
+        assert_eq!(loading_text(Some(&zh)), SharedString::from("加载中…"));
+        assert_eq!(loading_text(None), SharedString::from("Loading…"));
+    }
+
+    #[test]
+    fn empty_message_defaults_to_localized_text() {
+        let zh = I18n::with_locale(Locale::new("zh-CN").unwrap());
+        assert_eq!(
+            empty_message_text(None, Some(&zh)),
+            SharedString::from("无匹配结果")
+        );
+        assert_eq!(
+            empty_message_text(None, None),
+            SharedString::from("No results")
+        );
+    }
+}
@@ -0,0 +1,203 @@
+use std::time::Duration;
+
+use gpui::{
+    ClipboardItem, ElementId, IntoElement, Pixels, RenderOnce, SharedString, Styled, Window,
+    prelude::FluentBuilder,
+};
+
+use crate::{
+    a11y::{Politeness, announce},
+    component::{Icon, IconName, icon, icon_button},
+    i18n::{I18nContext, defaults::DefaultPlaceholders},
+    theme::ActionVariantKind,
+};
+
+/// Creates a new copy button element.
+///
+/// Clicking the button writes its text to the clipboard, announces "Copied"
+/// through the live region and shows a checkmark for a moment before the
+/// copy icon returns.
+///
+/// # Example
+/// ```rust,ignore
+/// use yororen_ui::component::copy_button;
+///
+/// copy_button("copy-token").text(self.token.clone());
+/// ```
+pub fn copy_button(id: impl Into<ElementId>) -> CopyButton {
+    CopyButton::new().id(id)
+}
+
+#[derive(IntoElement)]
+pub struct CopyButton {
+    element_id: ElementId,
+    text: SharedString,
+    duration: Duration,
+    icon: Option<Icon>,
+    copied_icon: Option<Icon>,
+    icon_size: Option<Pixels>,
+    variant: ActionVariantKind,
+    disabled: bool,
+}
+
+impl Default for CopyButton {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CopyButton {
+    pub fn new() -> Self {
+        Self {
+            element_id: "ui:copy-button".into(),
+            text: SharedString::default(),
+            duration: Duration::from_millis(1500),
+            icon: None,
+            copied_icon: None,
+            icon_size: None,
+            variant: ActionVariantKind::Neutral,
+            disabled: false,
+        }
+    }
+
+    pub fn id(mut self, id: impl Into<ElementId>) -> Self {
+        self.element_id = id.into();
+        self
+    }
+
+    /// Alias for `id(...)`. Use `key(...)` when you want to emphasize state identity.
+    pub fn key(self, key: impl Into<ElementId>) -> Self {
+        self.id(key)
+    }
+
+    /// The text written to the clipboard on click.
+    pub fn text(mut self, text: impl Into<SharedString>) -> Self {
+        self.text = text.into();
+        self
+    }
+
+    /// How long the confirmation icon is shown (default: 1.5s).
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// The icon shown at rest (default: [`IconName::Copy`]).
+    pub fn icon(mut self, icon: impl Into<Icon>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+
+    /// The icon shown after copying (default: [`IconName::Check`]).
+    pub fn copied_icon(mut self, icon: impl Into<Icon>) -> Self {
+        self.copied_icon = Some(icon.into());
+        self
+    }
+
+    pub fn icon_size(mut self, size: Pixels) -> Self {
+        self.icon_size = Some(size);
+        self
+    }
+
+    pub fn variant(mut self, variant: ActionVariantKind) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+}
+
+/// Whether a copy button is showing its confirmation.
+///
+/// Each copy bumps `epoch`, so the timer of an earlier copy does not cut a
+/// later confirmation short.
+#[derive(Default)]
+struct CopyFeedback {
+    epoch: u64,
+    copied: bool,
+}
+
+impl CopyFeedback {
+    /// Shows the confirmation and returns the epoch its timer should expire.
+    fn copy(&mut self) -> u64 {
+        self.epoch += 1;
+        self.copied = true;
+        self.epoch
+    }
+
+    /// Hides the confirmation if no copy happened since `epoch`.
+    fn expire(&mut self, epoch: u64) {
+        if self.epoch == epoch {
+            self.copied = false;
+        }
+    }
+}
+
+impl RenderOnce for CopyButton {
+    fn render(self, window: &mut Window, cx: &mut gpui::App) -> impl IntoElement {
+        let id = self.element_id;
+        let feedback =
+            window.use_keyed_state((id.clone(), "ui:copy-button:feedback"), cx, |_, _| {
+                CopyFeedback::default()
+            });
+        let copied = feedback.read(cx).copied;
+
+        let shown = if copied {
+            self.copied_icon.unwrap_or_else(|| icon(IconName::Check))
+        } else {
+            self.icon.unwrap_or_else(|| icon(IconName::Copy))
+        };
+
+        let text = self.text;
+        let duration = self.duration;
+        icon_button(id)
+            .icon(shown)
+            .variant(self.variant)
+            .disabled(self.disabled)
+            .when_some(self.icon_size, |this, size| this.icon_size(size))
+            .flex_none()
+            .on_click(move |_ev, window, cx| {
+                cx.write_to_clipboard(ClipboardItem::new_string(text.to_string()));
+                let epoch = feedback.update(cx, |feedback, _| feedback.copy());
+                announce(
+                    cx,
+                    DefaultPlaceholders::copied(cx.i18n().locale()),
+                    Politeness::Polite,
+                );
+                window.refresh();
+
+                let feedback = feedback.clone();
+                window
+                    .spawn(cx, async move |cx| {
+                        cx.background_executor().timer(duration).await;
+                        cx.update(|window, cx| {
+                            feedback.update(cx, |feedback, _| feedback.expire(epoch));
+                            window.refresh();
+                        })
+                        .ok();
+                    })
+                    .detach();
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_the_latest_copy_expires_the_confirmation() {
+        let mut feedback = CopyFeedback::default();
+        let first = feedback.copy();
+        let second = feedback.copy();
+
+        feedback.expire(first);
+        assert!(feedback.copied);
+
+        feedback.expire(second);
+        assert!(!feedback.copied);
+    }
+}
@@ -0,0 +1,611 @@
+use std::rc::Rc;
+use std::sync::Arc;
+
+use chrono::{DateTime, Datelike, Days, Months, NaiveDate, Utc, Weekday};
+use gpui::{
+    App, Div, ElementId, InteractiveElement, IntoElement, KeyDownEvent, ParentElement, RenderOnce,
+    SharedString, StatefulInteractiveElement, Styled, Window, div, prelude::FluentBuilder, px,
+};
+
+use crate::{
+    component::{
+        ArrowDirection, IconName, button, create_internal_state, icon, icon_button, popover,
+        tooltip, update_internal_state, use_internal_state,
+    },
+    i18n::{I18nContext, I18nFormatter, defaults::DefaultPlaceholders},
+    rtl,
+    theme::{ActionVariantKind, ActiveTheme},
+};
+
+/// Creates a new date picker element.
+///
+/// The trigger shows the selected date formatted for the active locale, and
+/// opens a month calendar in a popover. Weeks start on the locale's first
+/// weekday and the grid mirrors for right-to-left locales.
+///
+/// In the calendar, arrow keys move by day and week, Home/End go to the start
+/// and end of the week, PageUp/PageDown change the month (with Shift, the
+/// year), and Enter or Space picks the focused day.
+///
+/// Dates are Unix timestamps in seconds at midnight UTC, matching
+/// [`DateTimeFormatter::format_date`](crate::i18n::DateTimeFormatter::format_date).
+///
+/// # Example
+/// ```rust,ignore
+/// use yororen_ui::component::date_picker;
+///
+/// date_picker("due-date")
+///     .min(1704067200)
+///     .on_change(|timestamp, _window, _cx| println!("{timestamp}"));
+/// ```
+pub fn date_picker(id: impl Into<ElementId>) -> DatePicker {
+    DatePicker::new().id(id)
+}
+
+type ChangeFn = Arc<dyn Fn(i64, &mut Window, &mut App)>;
+
+#[derive(IntoElement)]
+pub struct DatePicker {
+    element_id: ElementId,
+    base: Div,
+    value: Option<i64>,
+    min: Option<i64>,
+    max: Option<i64>,
+    placeholder: Option<SharedString>,
+    disabled: bool,
+    on_change: Option<ChangeFn>,
+}
+
+impl Default for DatePicker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DatePicker {
+    pub fn new() -> Self {
+        Self {
+            element_id: "ui:date-picker".into(),
+            base: div(),
+            value: None,
+            min: None,
+            max: None,
+            placeholder: None,
+            disabled: false,
+            on_change: None,
+        }
+    }
+
+    pub fn id(mut self, id: impl Into<ElementId>) -> Self {
+        self.element_id = id.into();
+        self
+    }
+
+    /// Alias for `id(...)`. Use `key(...)` when you want to emphasize state identity.
+    pub fn key(self, key: impl Into<ElementId>) -> Self {
+        self.id(key)
+    }
+
+    /// The selected date, as a timestamp in seconds.
+    pub fn value(mut self, timestamp: i64) -> Self {
+        self.value = Some(timestamp);
+        self
+    }
+
+    /// The earliest date that can be picked.
+    pub fn min(mut self, timestamp: i64) -> Self {
+        self.min = Some(timestamp);
+        self
+    }
+
+    /// The latest date that can be picked.
+    pub fn max(mut self, timestamp: i64) -> Self {
+        self.max = Some(timestamp);
+        self
+    }
+
+    /// Text shown while no date is selected. Defaults to a localized "Select date".
+    pub fn placeholder(mut self, placeholder: impl Into<SharedString>) -> Self {
+        self.placeholder = Some(placeholder.into());
+        self
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Called with the picked date as a timestamp in seconds (midnight UTC).
+    pub fn on_change<F>(mut self, handler: F) -> Self
+    where
+        F: 'static + Fn(i64, &mut Window, &mut App),
+    {
+        self.on_change = Some(Arc::new(handler));
+        self
+    }
+
+    /// ARIA attributes for the trigger.
+    pub fn aria_attrs(&self, expanded: bool) -> Vec<(String, String)> {
+        crate::a11y::date_picker::trigger_attrs(expanded, self.disabled)
+    }
+}
+
+impl Styled for DatePicker {
+    fn style(&mut self) -> &mut gpui::StyleRefinement {
+        self.base.style()
+    }
+}
+
+/// Whether the calendar is open, and which month and day it shows.
+#[derive(Clone, Copy)]
+struct Calendar {
+    open: bool,
+    /// The day keyboard navigation starts from.
+    cursor: NaiveDate,
+}
+
+impl RenderOnce for DatePicker {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let id = self.element_id;
+        let disabled = self.disabled;
+        let theme = cx.theme().clone();
+        let locale = cx.i18n().locale().clone();
+        let formatter = cx.i18n().formatter();
+        let is_rtl = cx.text_direction().is_rtl();
+        let first_weekday = formatter.datetime().first_weekday();
+        let bounds = DateRange {
+            min: self.min.and_then(date_from_timestamp),
+            max: self.max.and_then(date_from_timestamp),
+        };
+
+        let use_internal = use_internal_state(self.value.is_some(), self.on_change.is_some());
+        let internal_value = create_internal_state(
+            window,
+            cx,
+            &id,
+            format!("{}:value", id),
+            None::<i64>,
+            use_internal,
+        );
+        let value = match &internal_value {
+            Some(state) => *state.read(cx),
+            None => self.value,
+        };
+        let selected = value.and_then(date_from_timestamp);
+        let today = Utc::now().date_naive();
+
+        let calendar =
+            window.use_keyed_state((id.clone(), "ui:date-picker:calendar"), cx, |_, _| {
+                Calendar {
+                    open: false,
+                    cursor: today,
+                }
+            });
+        let focus = window
+            .use_keyed_state((id.clone(), "ui:date-picker:focus"), cx, |_, cx| {
+                cx.focus_handle()
+            })
+            .read(cx)
+            .clone();
+        let Calendar { open, cursor } = *calendar.read(cx);
+
+        let label: SharedString = match value {
+            Some(timestamp) => formatter.format_date(timestamp).into(),
+            None => self
+                .placeholder
+                .unwrap_or_else(|| DefaultPlaceholders::date_picker_placeholder(&locale).into()),
+        };
+
+        // Picks `date`, closes the calendar and reports the change.
+        let pick = {
+            let calendar = calendar.clone();
+            let on_change = self.on_change.clone();
+            Rc::new(move |date: NaiveDate, window: &mut Window, cx: &mut App| {
+                if !bounds.contains(date) {
+                    return;
+                }
+                let timestamp = timestamp_of(date);
+                calendar.update(cx, |calendar, _| calendar.open = false);
+                update_internal_state(&internal_value, cx, Some(timestamp));
+                if let Some(handler) = &on_change {
+                    handler(timestamp, window, cx);
+                }
+                window.refresh();
+            })
+        };
+
+        let trigger = button((id.clone(), "ui:date-picker:trigger"))
+            .variant(ActionVariantKind::Neutral)
+            .disabled(disabled)
+            .flex()
+            .items_center()
+            .gap_2()
+            .when(value.is_none(), |this| {
+                this.text_color(theme.content.tertiary)
+            })
+            .when(!disabled, |this| {
+                let calendar = calendar.clone();
+                let focus = focus.clone();
+                this.on_click(move |_ev, window, cx| {
+                    let opening = calendar.update(cx, |calendar, _| {
+                        calendar.open = !calendar.open;
+                        calendar.cursor = bounds.clamp(selected.unwrap_or(today));
+                        calendar.open
+                    });
+                    if opening {
+                        window.focus(&focus);
+                    }
+                    window.refresh();
+                })
+            })
+            .child(label)
+            .child(icon(IconName::Arrow(ArrowDirection::Down)).size(px(12.)));
+
+        let content = open.then(|| {
+            let month = first_of_month(cursor);
+            let month_label = formatter
+                .datetime()
+                .format_month(month.year(), month.month());
+
+            // Moves the cursor, keeping it within the bounds.
+            let move_to = {
+                let calendar = calendar.clone();
+                move |date: NaiveDate, window: &mut Window, cx: &mut App| {
+                    calendar.update(cx, |calendar, _| calendar.cursor = bounds.clamp(date));
+                    window.refresh();
+                }
+            };
+            let nav = |suffix: &'static str,
+                       label: &'static str,
+                       target: Option<NaiveDate>|
+             -> (ElementId, SharedString, Option<NaiveDate>) {
+                let target = target.filter(|date| bounds.overlaps_month(*date));
+                ((id.clone(), suffix).into(), label.into(), target)
+            };
+            let previous_year = nav(
+                "ui:date-picker:previous-year",
+                DefaultPlaceholders::date_picker_previous_year(&locale),
+                cursor.checked_sub_months(Months::new(12)),
+            );
+            let previous_month = nav(
+                "ui:date-picker:previous-month",
+                DefaultPlaceholders::date_picker_previous_month(&locale),
+                cursor.checked_sub_months(Months::new(1)),
+            );
+            let next_month = nav(
+                "ui:date-picker:next-month",
+                DefaultPlaceholders::date_picker_next_month(&locale),
+                cursor.checked_add_months(Months::new(1)),
+            );
+            let next_year = nav(
+                "ui:date-picker:next-year",
+                DefaultPlaceholders::date_picker_next_year(&locale),
+                cursor.checked_add_months(Months::new(12)),
+            );
+
+            let year_button =
+                |(id, label, target): (ElementId, SharedString, Option<NaiveDate>),
+                 glyph: &'static str| {
+                    let move_to = move_to.clone();
+                    button(id)
+                        .variant(ActionVariantKind::Neutral)
+                        .bg(theme.action.neutral.bg.alpha(0.0))
+                        .px_2()
+                        .disabled(target.is_none())
+                        .tooltip(tooltip(label).build())
+                        .when_some(target, |this, target| {
+                            this.on_click(move |_ev, window, cx| move_to(target, window, cx))
+                        })
+                        .child(glyph)
+                };
+            let month_button =
+                |(id, label, target): (ElementId, SharedString, Option<NaiveDate>),
+                 direction: ArrowDirection| {
+                    let move_to = move_to.clone();
+                    icon_button(id)
+                        .icon(icon(IconName::Arrow(direction)).size(px(12.)))
+                        .variant(ActionVariantKind::Neutral)
+                        .bg(theme.action.neutral.bg.alpha(0.0))
+                        .size(px(28.))
+                        .disabled(target.is_none())
+                        .tooltip(tooltip(label).build())
+                        .when_some(target, |this, target| {
+                            this.on_click(move |_ev, window, cx| move_to(target, window, cx))
+                        })
+                };
+            // Previous points towards the start of the line.
+            let (back, forward) = rtl::flip_left_right(
+                cx.text_direction(),
+                (ArrowDirection::Left, ArrowDirection::Right),
+                (ArrowDirection::Right, ArrowDirection::Left),
+            );
+            let (back_glyph, forward_glyph) =
+                rtl::flip_left_right(cx.text_direction(), ("«", "»"), ("»", "«"));
+
+            let header = div()
+                .flex()
+                .when(is_rtl, |this| this.flex_row_reverse())
+                .items_center()
+                .gap_1()
+                .child(year_button(previous_year, back_glyph))
+                .child(month_button(previous_month, back))
+                .child(
+                    div()
+                        .flex_1()
+                        .text_center()
+                        .text_sm()
+                        .text_color(theme.content.primary)
+                        .child(month_label),
+                )
+                .child(month_button(next_month, forward))
+                .child(year_button(next_year, forward_glyph));
+
+            let weekdays = div()
+                .flex()
+                .when(is_rtl, |this| this.flex_row_reverse())
+                .children(week_order(first_weekday).map(|weekday| {
+                    div()
+                        .w(CELL_SIZE)
+                        .text_center()
+                        .text_xs()
+                        .text_color(theme.content.tertiary)
+                        .child(formatter.datetime().weekday_short(weekday))
+                }));
+
+            let focused = focus.is_focused(window);
+            let days = month_grid(month, first_weekday);
+            let rows = days
+                .chunks(7)
+                .map(|week| {
+                    div()
+                        .flex()
+                        .when(is_rtl, |this| this.flex_row_reverse())
+                        .children(week.iter().map(|&date| {
+                            let in_month = date.month() == month.month();
+                            let enabled = bounds.contains(date);
+                            let is_selected = selected == Some(date);
+                            let is_cursor = date == cursor;
+                            let pick = pick.clone();
+                            div()
+                                .id(("ui:date-picker:day", date.num_days_from_ce() as usize))
+                                .w(CELL_SIZE)
+                                .h(CELL_SIZE)
+                                .flex()
+                                .items_center()
+                                .justify_center()
+                                .rounded_md()
+                                .border_1()
+                                .text_sm()
+                                .border_color(if is_cursor && focused {
+                                    theme.border.focus
+                                } else if date == today {
+                                    theme.border.default
+                                } else {
+                                    gpui::transparent_black()
+                                })
+                                .when(is_selected, |this| {
+                                    this.bg(theme.action.primary.bg)
+                                        .text_color(theme.action.primary.fg)
+                                })
+                                .when(!is_selected, |this| {
+                                    this.text_color(if !enabled {
+                                        theme.content.disabled
+                                    } else if in_month {
+                                        theme.content.primary
+                                    } else {
+                                        theme.content.tertiary
+                                    })
+                                })
+                                .when(enabled, |this| {
+                                    this.cursor_pointer()
+                                        .when(!is_selected, |this| {
+                                            this.hover(|this| this.bg(theme.surface.hover))
+                                        })
+                                        .on_click(move |_ev, window, cx| pick(date, window, cx))
+                                })
+                                .when(!enabled, |this| this.cursor_not_allowed())
+                                .child(date.day().to_string())
+                        }))
+                })
+                .collect::<Vec<_>>();
+
+            let on_key = {
+                let calendar = calendar.clone();
+                let pick = pick.clone();
+                move |ev: &KeyDownEvent, window: &mut Window, cx: &mut App| {
+                    let key = ev.keystroke.key.as_str();
+                    match key {
+                        "enter" | "space" => pick(cursor, window, cx),
+                        "escape" => {
+                            calendar.update(cx, |calendar, _| calendar.open = false);
+                            window.refresh();
+                        }
+                        _ => {
+                            let shift = ev.keystroke.modifiers.shift;
+                            let Some(date) =
+                                date_for_key(key, shift, cursor, first_weekday, is_rtl)
+                            else {
+                                return;
+                            };
+                            move_to(date, window, cx);
+                        }
+                    }
+                    cx.stop_propagation();
+                }
+            };
+
+            div()
+                .id((id.clone(), "ui:date-picker:calendar"))
+                .track_focus(&focus)
+                .on_key_down(on_key)
+                .flex()
+                .flex_col()
+                .gap_1()
+                .px_2()
+                .py_1()
+                .child(header)
+                .child(weekdays)
+                .children(rows)
+        });
+
+        let calendar_for_close = calendar.clone();
+        self.base.child(
+            popover((id.clone(), "ui:date-picker:popover"))
+                .open(open)
+                .width(CELL_SIZE * 7. + px(18.))
+                .on_close(move |window, cx| {
+                    calendar_for_close.update(cx, |calendar, _| calendar.open = false);
+                    window.refresh();
+                })
+                .trigger(trigger)
+                .when_some(content, |this, content| this.content(content)),
+        )
+    }
+}
+
+/// The width and height of a day in the grid.
+const CELL_SIZE: gpui::Pixels = px(32.);
+
+/// The inclusive range of dates that can be picked.
+#[derive(Clone, Copy, Debug, Default)]
+struct DateRange {
+    min: Option<NaiveDate>,
+    max: Option<NaiveDate>,
+}
+
+impl DateRange {
+    fn contains(&self, date: NaiveDate) -> bool {
+        self.min.is_none_or(|min| date >= min) && self.max.is_none_or(|max| date <= max)
+    }
+
+    fn clamp(&self, date: NaiveDate) -> NaiveDate {
+        let date = self.min.map_or(date, |min| date.max(min));
+        self.max.map_or(date, |max| date.min(max))
+    }
+
+    /// Whether any day of `date`'s month can be picked.
+    fn overlaps_month(&self, date: NaiveDate) -> bool {
+        let first = first_of_month(date);
+        let last = first
+            .checked_add_months(Months::new(1))
+            .and_then(|next| next.pred_opt())
+            .unwrap_or(first);
+        self.min.is_none_or(|min| last >= min) && self.max.is_none_or(|max| first <= max)
+    }
+}
+
+fn date_from_timestamp(timestamp: i64) -> Option<NaiveDate> {
+    DateTime::from_timestamp(timestamp, 0).map(|datetime| datetime.date_naive())
+}
+
+/// The timestamp of midnight UTC on `date`.
+fn timestamp_of(date: NaiveDate) -> i64 {
+    date.and_time(chrono::NaiveTime::MIN).and_utc().timestamp()
+}
+
+fn first_of_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+/// The seven weekdays, starting with `first`.
+fn week_order(first: Weekday) -> impl Iterator<Item = Weekday> {
+    std::iter::successors(Some(first), |day| Some(day.succ())).take(7)
+}
+
+/// The six weeks shown for `month`, starting on the week holding its first day.
+fn month_grid(month: NaiveDate, first: Weekday) -> Vec<NaiveDate> {
+    let first_of_month = first_of_month(month);
+    let lead =
+        (7 + first_of_month.weekday().num_days_from_monday() - first.num_days_from_monday()) % 7;
+    let start = first_of_month - Days::new(lead as u64);
+    start.iter_days().take(42).collect()
+}
+
+/// Where a key press moves the calendar's cursor from `date`.
+fn date_for_key(
+    key: &str,
+    shift: bool,
+    date: NaiveDate,
+    first: Weekday,
+    rtl: bool,
+) -> Option<NaiveDate> {
+    let (left, right) = if rtl { (1, -1) } else { (-1, 1) };
+    let from_week_start =
+        (7 + date.weekday().num_days_from_monday() - first.num_days_from_monday()) % 7;
+    let months = if shift { 12 } else { 1 };
+    match key {
+        "left" => shift_days(date, left),
+        "right" => shift_days(date, right),
+        "up" => shift_days(date, -7),
+        "down" => shift_days(date, 7),
+        "home" => shift_days(date, -(from_week_start as i64)),
+        "end" => shift_days(date, 6 - from_week_start as i64),
+        "pageup" => date.checked_sub_months(Months::new(months)),
+        "pagedown" => date.checked_add_months(Months::new(months)),
+        _ => None,
+    }
+}
+
+fn shift_days(date: NaiveDate, days: i64) -> Option<NaiveDate> {
+    if days < 0 {
+        date.checked_sub_days(Days::new(days.unsigned_abs()))
+    } else {
+        date.checked_add_days(Days::new(days as u64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn grid_starts_on_the_locale_weekday() {
+        // March 2024 starts on a Friday.
+        let sunday_first = month_grid(date(2024, 3, 15), Weekday::Sun);
+        assert_eq!(sunday_first.len(), 42);
+        assert_eq!(sunday_first[0], date(2024, 2, 25));
+        assert_eq!(sunday_first[5], date(2024, 3, 1));
+
+        let monday_first = month_grid(date(2024, 3, 15), Weekday::Mon);
+        assert_eq!(monday_first[0], date(2024, 2, 26));
+        assert_eq!(monday_first[4], date(2024, 3, 1));
+
+        let saturday_first = month_grid(date(2024, 6, 1), Weekday::Sat);
+        assert_eq!(saturday_first[0], date(2024, 6, 1));
+    }
+
+    #[test]
+    fn keys_move_the_cursor() {
+        let day = date(2024, 3, 15);
+        let key = |key: &str, shift, rtl| date_for_key(key, shift, day, Weekday::Mon, rtl);
+
+        assert_eq!(key("right", false, false), Some(date(2024, 3, 16)));
+        assert_eq!(key("right", false, true), Some(date(2024, 3, 14)));
+        assert_eq!(key("up", false, false), Some(date(2024, 3, 8)));
+        assert_eq!(key("home", false, false), Some(date(2024, 3, 11)));
+        assert_eq!(key("end", false, false), Some(date(2024, 3, 17)));
+        assert_eq!(key("pagedown", false, false), Some(date(2024, 4, 15)));
+        assert_eq!(key("pageup", true, false), Some(date(2023, 3, 15)));
+        assert_eq!(key("tab", false, false), None);
+    }
+
+    #[test]
+    fn bounds_limit_the_cursor() {
+        let bounds = DateRange {
+            min: Some(date(2024, 3, 10)),
+            max: Some(date(2024, 4, 5)),
+        };
+        assert!(!bounds.contains(date(2024, 3, 9)));
+        assert_eq!(bounds.clamp(date(2024, 5, 1)), date(2024, 4, 5));
+        assert!(bounds.overlaps_month(date(2024, 4, 30)));
+        assert!(!bounds.overlaps_month(date(2024, 2, 28)));
+
+        let midnight = timestamp_of(date(2024, 1, 1));
+        assert_eq!(midnight, 1704067200);
+        assert_eq!(date_from_timestamp(midnight + 3600), Some(date(2024, 1, 1)));
+    }
+}
@@ -3,6 +3,7 @@ use gpui::{
 };
 
 use crate::component::{ArrowDirection, IconName, icon};
+use crate::rtl;
 use crate::theme::ActiveTheme;
 
 /// A disclosure arrow with expanded/collapsed state.
@@ -93,7 +94,11 @@ impl RenderOnce for Disclosure {
                 icon(IconName::Arrow(if expanded {
                     ArrowDirection::Down
                 } else {
-                    ArrowDirection::Right
+                    rtl::flip_left_right(
+                        cx.text_direction(),
+                        ArrowDirection::Right,
+                        ArrowDirection::Left,
+                    )
                 }))
                 .size(size),
             )
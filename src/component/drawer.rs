@@ -0,0 +1,384 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+use gpui::{
+    AnyElement, App, Div, ElementId, Hsla, InteractiveElement, IntoElement, MouseButton,
+    ParentElement, Pixels, RenderOnce, StatefulInteractiveElement, Styled, Window, anchored,
+    deferred, div, point, prelude::FluentBuilder, px,
+};
+
+use crate::{
+    a11y::{Close, cycle_focus, dialog, focus_trap},
+    animation::{animate_value, constants::duration, ease_out_quint_clamped},
+    theme::{ActiveTheme, ElevationLevel},
+};
+
+/// Creates a new drawer element.
+///
+/// A drawer is a panel that slides in from an edge of the window over a
+/// scrim. While it is open the scrim blocks clicks and scrolling on the view
+/// underneath, focus moves into the panel and is restored when the drawer
+/// closes. Escape and clicks on the scrim call `on_close` unless turned off
+/// with [`Drawer::close_on_escape`] and [`Drawer::close_on_scrim_click`].
+///
+/// The drawer is positioned against the window but painted in tree order, so
+/// render it as the last child of the window's root element. Popovers and
+/// menus inside the panel work as usual.
+///
+/// # Example
+/// ```rust,ignore
+/// use yororen_ui::component::{DrawerSide, drawer};
+///
+/// drawer("settings")
+///     .open(self.settings_open)
+///     .side(DrawerSide::Right)
+///     .size(px(420.))
+///     .on_close(cx.listener(|this, _, _, cx| {
+///         this.settings_open = false;
+///         cx.notify();
+///     }))
+///     .child(settings_panel);
+/// ```
+pub fn drawer(id: impl Into<ElementId>) -> Drawer {
+    Drawer::new().id(id)
+}
+
+/// The window edge a drawer slides in from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DrawerSide {
+    Left,
+    #[default]
+    Right,
+    Top,
+    Bottom,
+}
+
+impl DrawerSide {
+    fn is_horizontal(self) -> bool {
+        matches!(self, DrawerSide::Left | DrawerSide::Right)
+    }
+}
+
+type CloseFn = Arc<dyn Fn(&mut Window, &mut App)>;
+
+#[derive(IntoElement)]
+pub struct Drawer {
+    element_id: ElementId,
+    base: Div,
+    open: bool,
+    side: DrawerSide,
+    size: Pixels,
+    scrim: Option<Hsla>,
+    bg: Option<Hsla>,
+    close_on_escape: bool,
+    close_on_scrim_click: bool,
+    on_close: Option<CloseFn>,
+}
+
+impl Default for Drawer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drawer {
+    pub fn new() -> Self {
+        Self {
+            element_id: "ui:drawer".into(),
+            base: div(),
+            open: false,
+            side: DrawerSide::default(),
+            size: px(360.),
+            scrim: None,
+            bg: None,
+            close_on_escape: true,
+            close_on_scrim_click: true,
+            on_close: None,
+        }
+    }
+
+    pub fn id(mut self, id: impl Into<ElementId>) -> Self {
+        self.element_id = id.into();
+        self
+    }
+
+    /// Alias for `id(...)`. Use `key(...)` when you want to emphasize state identity.
+    pub fn key(self, key: impl Into<ElementId>) -> Self {
+        self.id(key)
+    }
+
+    /// Whether the drawer is shown. Changing it slides the drawer in or out.
+    pub fn open(mut self, open: bool) -> Self {
+        self.open = open;
+        self
+    }
+
+    pub fn side(mut self, side: DrawerSide) -> Self {
+        self.side = side;
+        self
+    }
+
+    /// The width of a left or right drawer, or the height of a top or bottom one
+    /// (default: 360px).
+    pub fn size(mut self, size: Pixels) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// The scrim color, overriding the theme's `shadow.scrim`.
+    pub fn scrim(mut self, color: impl Into<Hsla>) -> Self {
+        self.scrim = Some(color.into());
+        self
+    }
+
+    pub fn bg(mut self, color: impl Into<Hsla>) -> Self {
+        self.bg = Some(color.into());
+        self
+    }
+
+    /// Whether Escape inside the drawer calls `on_close` (default: true).
+    pub fn close_on_escape(mut self, close: bool) -> Self {
+        self.close_on_escape = close;
+        self
+    }
+
+    /// Whether clicking the scrim calls `on_close` (default: true).
+    pub fn close_on_scrim_click(mut self, close: bool) -> Self {
+        self.close_on_scrim_click = close;
+        self
+    }
+
+    /// Called when the drawer asks to be closed. Set `.open(false)` in response.
+    pub fn on_close<F>(mut self, handler: F) -> Self
+    where
+        F: 'static + Fn(&mut Window, &mut App),
+    {
+        self.on_close = Some(Arc::new(handler));
+        self
+    }
+
+    /// Returns the aria attributes of the drawer panel.
+    pub fn aria_attrs(&self) -> Vec<(String, String)> {
+        dialog::attrs(None, None)
+    }
+}
+
+impl ParentElement for Drawer {
+    fn extend(&mut self, elements: impl IntoIterator<Item = AnyElement>) {
+        self.base.extend(elements);
+    }
+}
+
+impl Styled for Drawer {
+    fn style(&mut self) -> &mut gpui::StyleRefinement {
+        self.base.style()
+    }
+}
+
+impl InteractiveElement for Drawer {
+    fn interactivity(&mut self) -> &mut gpui::Interactivity {
+        self.base.interactivity()
+    }
+}
+
+impl StatefulInteractiveElement for Drawer {}
+
+/// When a drawer last opened or closed.
+#[derive(Clone, Copy)]
+struct DrawerMotion {
+    open: bool,
+    changed: Option<Instant>,
+}
+
+impl DrawerMotion {
+    /// How far the drawer is shown, eased, from `0.0` (hidden) to `1.0` (open).
+    fn shown(&self, now: Instant) -> f32 {
+        let t = match self.changed {
+            Some(changed) => ease_out_quint_clamped(crate::animation::settle(
+                crate::animation::progress_from_elapsed(
+                    now.saturating_duration_since(changed),
+                    duration::SLOW,
+                ),
+            )),
+            None => 1.0,
+        };
+        if self.open { t } else { 1.0 - t }
+    }
+
+    /// Whether the slide is still running and needs another frame.
+    ///
+    /// True on the frame `open` changes too, where [`shown`](Self::shown) is
+    /// still at the starting end.
+    fn animating(&self, now: Instant) -> bool {
+        let target = if self.open { 1.0 } else { 0.0 };
+        self.changed
+            .is_some_and(|changed| now.saturating_duration_since(changed) < duration::SLOW)
+            && self.shown(now) != target
+    }
+}
+
+impl RenderOnce for Drawer {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let id = self.element_id.clone();
+        let theme = cx.theme().clone();
+        let open = self.open;
+        let side = self.side;
+        let size = self.size;
+
+        let now = Instant::now();
+        let motion =
+            window.use_keyed_state((id.clone(), "ui:drawer:motion"), cx, |_, _| DrawerMotion {
+                open: false,
+                changed: None,
+            });
+        let focus = window
+            .use_keyed_state((id.clone(), "ui:drawer:focus"), cx, |_, cx| {
+                cx.focus_handle()
+            })
+            .read(cx)
+            .clone();
+        if motion.read(cx).open != open {
+            motion.update(cx, |motion, _| {
+                motion.open = open;
+                motion.changed = Some(now);
+            });
+            if open {
+                // Focus once the focus trap has rendered and recorded the focus to restore.
+                let focus = focus.clone();
+                window.on_next_frame(move |window, _cx| window.focus(&focus));
+            }
+        }
+        let shown = motion.read(cx).shown(now);
+        if motion.read(cx).animating(now) {
+            window.request_animation_frame();
+        }
+        if shown <= 0.0 {
+            return div().id(id);
+        }
+
+        let viewport = window.viewport_size();
+        let scrim = self.scrim.unwrap_or(theme.shadow.scrim);
+        let offset = animate_value(-size, Pixels::ZERO, shown);
+        let on_close = self.on_close;
+        let close_on_scrim_click = self.close_on_scrim_click;
+
+        let panel = focus_trap()
+            .id((id.clone(), "ui:drawer:panel"))
+            .absolute()
+            .map(|this| match side {
+                DrawerSide::Left => this.top_0().bottom_0().left(offset).w(size).border_r_1(),
+                DrawerSide::Right => this.top_0().bottom_0().right(offset).w(size).border_l_1(),
+                DrawerSide::Top => this.left_0().right_0().top(offset).h(size).border_b_1(),
+                DrawerSide::Bottom => this.left_0().right_0().bottom(offset).h(size).border_t_1(),
+            })
+            .map(|this| {
+                if side.is_horizontal() {
+                    this.max_w(viewport.width)
+                } else {
+                    this.max_h(viewport.height)
+                }
+            })
+            .bg(self.bg.unwrap_or(theme.surface.raised))
+            .border_color(theme.border.default)
+            .shadow(theme.elevation(ElevationLevel::Overlay))
+            .overflow_hidden()
+            .occlude()
+            .track_focus(&focus)
+            .capture_key_down({
+                let focus = focus.clone();
+                move |ev, window, cx| {
+                    if ev.keystroke.key != "tab" {
+                        return;
+                    }
+                    cx.stop_propagation();
+                    cycle_focus(&focus, ev.keystroke.modifiers.shift, window, cx);
+                }
+            })
+            .when_some(
+                on_close.clone().filter(|_| self.close_on_escape),
+                |this, on_close| this.on_escape(move |window, cx| on_close(window, cx)),
+            )
+            .when(!self.close_on_escape, |this| {
+                this.capture_action(|_: &Close, _window, cx| cx.stop_propagation())
+            })
+            .child(self.base.size_full());
+
+        let scrim_el = div()
+            .id((id.clone(), "ui:drawer:scrim"))
+            .absolute()
+            .inset_0()
+            .bg(scrim.opacity(shown))
+            .occlude()
+            .on_mouse_down(MouseButton::Left, move |_ev, window, cx| {
+                cx.stop_propagation();
+                if !open || !close_on_scrim_click {
+                    return;
+                }
+                if let Some(on_close) = &on_close {
+                    on_close(window, cx);
+                }
+            });
+
+        // Deferred so the drawer paints above content rendered after it, at a
+        // priority below the menus (100) of selects and popovers inside it.
+        div().id(id).child(
+            deferred(
+                anchored()
+                    .position(point(Pixels::ZERO, Pixels::ZERO))
+                    .child(
+                        div()
+                            .relative()
+                            .w(viewport.width)
+                            .h(viewport.height)
+                            .child(scrim_el)
+                            .child(panel),
+                    ),
+            )
+            .with_priority(1),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn motion_slides_in_and_out() {
+        let start = Instant::now();
+        let opening = DrawerMotion {
+            open: true,
+            changed: Some(start),
+        };
+        assert_eq!(opening.shown(start), 0.0);
+        assert_eq!(opening.shown(start + duration::SLOW), 1.0);
+
+        let closing = DrawerMotion {
+            open: false,
+            changed: Some(start),
+        };
+        assert_eq!(closing.shown(start), 1.0);
+        assert_eq!(closing.shown(start + duration::SLOW), 0.0);
+    }
+
+    #[test]
+    fn toggling_keeps_requesting_frames_until_the_slide_ends() {
+        let start = Instant::now();
+        for open in [true, false] {
+            let motion = DrawerMotion {
+                open,
+                changed: Some(start),
+            };
+            // The frame `open` changes on sits at the starting end.
+            assert!(motion.animating(start));
+            assert!(motion.animating(start + duration::SLOW / 2));
+            assert!(!motion.animating(start + duration::SLOW));
+        }
+
+        let settled = DrawerMotion {
+            open: true,
+            changed: None,
+        };
+        assert!(!settled.animating(start));
+    }
+}
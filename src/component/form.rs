@@ -1,3 +1,5 @@
+use std::cell::Cell;
+
 use gpui::prelude::FluentBuilder;
 use gpui::{
     Div, ElementId, Hsla, InteractiveElement, IntoElement, ParentElement, RenderOnce, SharedString,
@@ -5,6 +7,7 @@ use gpui::{
 };
 
 use crate::{
+    a11y::{Politeness, announce},
     component::{IconName, icon, label},
     theme::ActiveTheme,
 };
@@ -227,7 +230,18 @@ impl Styled for InlineError {
 }
 
 impl RenderOnce for InlineError {
-    fn render(self, _window: &mut gpui::Window, cx: &mut gpui::App) -> impl IntoElement {
+    fn render(self, window: &mut gpui::Window, cx: &mut gpui::App) -> impl IntoElement {
+        // Announce each error once, when it first appears.
+        let announced = Cell::new(false);
+        window.use_keyed_state(
+            SharedString::from(format!("{}:announced:{}", self.element_id, self.text)),
+            cx,
+            |_, _| announced.set(true),
+        );
+        if announced.get() {
+            announce(cx, self.text.clone(), Politeness::Assertive);
+        }
+
         let bg = cx.theme().status.error.bg;
         let fg = cx.theme().status.error.fg;
 
@@ -0,0 +1,436 @@
+//! Field registration and aggregate validation for forms.
+//!
+//! [`FormController`] holds the values of a form's fields by key, runs each
+//! field's validators and tracks their errors. It is glue between existing
+//! controls rather than a container element: bind each control's change handler
+//! to the controller, drive the control from the controller's values, and call
+//! [`FormController::submit`] from the submit button.
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use gpui::{
+    App, AppContext, ClickEvent, Context, ElementId, Entity, FocusHandle, SharedString, Window,
+};
+
+use crate::component::focus_input;
+
+/// Validates a field value, returning the message to show when it is invalid.
+type FormValidator = Rc<dyn Fn(&FormValue) -> Result<(), SharedString>>;
+
+/// The value of a form field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormValue {
+    /// Text from a `TextInput`, `TextArea` or `PasswordInput`, or the value picked
+    /// in a `ComboBox`.
+    Text(SharedString),
+    /// Whether a `Checkbox` or `Switch` is on.
+    Bool(bool),
+}
+
+impl FormValue {
+    /// The text of a text value, or `""` for a boolean.
+    pub fn as_str(&self) -> &str {
+        match self {
+            FormValue::Text(text) => text,
+            FormValue::Bool(_) => "",
+        }
+    }
+
+    /// Whether a boolean value is on; text values are never on.
+    pub fn as_bool(&self) -> bool {
+        matches!(self, FormValue::Bool(true))
+    }
+
+    /// Whether the value counts as filled in: non-blank text, or `true`.
+    pub fn is_filled(&self) -> bool {
+        match self {
+            FormValue::Text(text) => !text.trim().is_empty(),
+            FormValue::Bool(on) => *on,
+        }
+    }
+}
+
+/// Where focus goes when a field is the first invalid one on submit.
+#[derive(Clone)]
+enum FieldFocus {
+    None,
+    /// A text input, text area or password input, focused by element id.
+    Input(ElementId),
+    /// A handle the control tracks with `.track_focus(...)`.
+    Handle(FocusHandle),
+}
+
+/// Describes a field registered with [`FormController::register`].
+///
+/// # Example
+/// ```rust,ignore
+/// FormField::text("email")
+///     .input("signup-email")
+///     .required("Enter your email")
+///     .validate(|value| {
+///         if value.as_str().contains('@') {
+///             Ok(())
+///         } else {
+///             Err("Enter a valid email".into())
+///         }
+///     });
+/// ```
+#[derive(Clone)]
+pub struct FormField {
+    key: SharedString,
+    value: FormValue,
+    validators: Vec<FormValidator>,
+    focus: FieldFocus,
+    error: Option<SharedString>,
+}
+
+impl FormField {
+    /// A text field, starting empty.
+    pub fn text(key: impl Into<SharedString>) -> Self {
+        Self::new(key, FormValue::Text(SharedString::default()))
+    }
+
+    /// A checkbox or switch field, starting off.
+    pub fn toggle(key: impl Into<SharedString>) -> Self {
+        Self::new(key, FormValue::Bool(false))
+    }
+
+    fn new(key: impl Into<SharedString>, value: FormValue) -> Self {
+        Self {
+            key: key.into(),
+            value,
+            validators: Vec::new(),
+            focus: FieldFocus::None,
+            error: None,
+        }
+    }
+
+    /// Sets the starting value.
+    pub fn initial(mut self, value: FormValue) -> Self {
+        self.value = value;
+        self
+    }
+
+    /// The element id of the `TextInput`, `TextArea` or `PasswordInput` showing
+    /// this field, focused when the field is the first invalid one on submit.
+    pub fn input(mut self, id: impl Into<ElementId>) -> Self {
+        self.focus = FieldFocus::Input(id.into());
+        self
+    }
+
+    /// A focus handle tracked by the control showing this field, for controls
+    /// other than text inputs.
+    pub fn focus_handle(mut self, handle: &FocusHandle) -> Self {
+        self.focus = FieldFocus::Handle(handle.clone());
+        self
+    }
+
+    /// Rejects blank text or an unchecked toggle with `message`.
+    pub fn required(self, message: impl Into<SharedString>) -> Self {
+        let message = message.into();
+        self.validate(move |value| {
+            if value.is_filled() {
+                Ok(())
+            } else {
+                Err(message.clone())
+            }
+        })
+    }
+
+    /// Adds a validator. Validators run in the order they were added and the
+    /// first error is reported.
+    pub fn validate<F>(mut self, validate: F) -> Self
+    where
+        F: 'static + Fn(&FormValue) -> Result<(), SharedString>,
+    {
+        self.validators.push(Rc::new(validate));
+        self
+    }
+
+    fn check(&self) -> Result<(), SharedString> {
+        self.validators
+            .iter()
+            .try_for_each(|validate| validate(&self.value))
+    }
+}
+
+/// The fields of a form, in registration order.
+#[derive(Default)]
+struct FormFields {
+    fields: Vec<FormField>,
+}
+
+impl FormFields {
+    fn register(&mut self, field: FormField) {
+        match self.fields.iter_mut().find(|f| f.key == field.key) {
+            Some(existing) => *existing = field,
+            None => self.fields.push(field),
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<&FormField> {
+        self.fields.iter().find(|field| field.key == key)
+    }
+
+    /// Stores a new value. A field already showing an error is validated again,
+    /// so the error clears as soon as the value is fixed.
+    fn set(&mut self, key: &str, value: FormValue) -> bool {
+        let Some(field) = self.fields.iter_mut().find(|field| field.key == key) else {
+            return false;
+        };
+        if field.value == value {
+            return false;
+        }
+        field.value = value;
+        if field.error.is_some() {
+            field.error = field.check().err();
+        }
+        true
+    }
+
+    fn is_valid(&self) -> bool {
+        self.fields.iter().all(|field| field.check().is_ok())
+    }
+
+    fn values(&self) -> HashMap<SharedString, FormValue> {
+        self.fields
+            .iter()
+            .map(|field| (field.key.clone(), field.value.clone()))
+            .collect()
+    }
+
+    /// Validates every field, recording errors, and returns the index of the
+    /// first invalid field.
+    fn validate_all(&mut self) -> Option<usize> {
+        let mut first_invalid = None;
+        for (index, field) in self.fields.iter_mut().enumerate() {
+            field.error = field.check().err();
+            if field.error.is_some() && first_invalid.is_none() {
+                first_invalid = Some(index);
+            }
+        }
+        first_invalid
+    }
+}
+
+/// A handle to a form's fields, values and validation errors.
+///
+/// Cloning the controller shares the same fields. Register fields once, bind
+/// each control's change handler with [`on_text`](Self::on_text),
+/// [`on_toggle`](Self::on_toggle) or [`on_choice`](Self::on_choice), and drive
+/// the control from [`text`](Self::text) or [`checked`](Self::checked).
+///
+/// # Example
+/// ```rust,ignore
+/// use yororen_ui::component::{FormController, FormField, button, checkbox, text_input};
+///
+/// // In the view's constructor:
+/// let form = FormController::new(cx);
+/// form.register(FormField::text("email").input("signup-email").required("Enter your email"), cx);
+/// form.register(FormField::toggle("terms").required("Accept the terms"), cx);
+///
+/// // In render:
+/// text_input("signup-email").on_change(self.form.on_text("email"));
+/// checkbox("signup-terms")
+///     .checked(self.form.checked("terms", cx))
+///     .on_toggle(self.form.on_toggle("terms"));
+/// button("signup-submit").on_click({
+///     let form = self.form.clone();
+///     move |_, window, cx| {
+///         if let Some(values) = form.submit(window, cx) {
+///             println!("{values:?}");
+///         }
+///     }
+/// });
+/// ```
+#[derive(Clone)]
+pub struct FormController {
+    fields: Entity<FormFields>,
+}
+
+impl FormController {
+    /// Creates a controller that re-renders the view creating it whenever a
+    /// value or error changes.
+    pub fn new<V: 'static>(cx: &mut Context<V>) -> Self {
+        let fields = cx.new(|_| FormFields::default());
+        cx.observe(&fields, |_, _, cx| cx.notify()).detach();
+        Self { fields }
+    }
+
+    /// Adds a field, replacing any field registered under the same key.
+    pub fn register(&self, field: FormField, cx: &mut App) {
+        self.fields.update(cx, |fields, cx| {
+            fields.register(field);
+            cx.notify();
+        });
+    }
+
+    /// Sets the value of the field with `key`.
+    pub fn set_value(&self, key: &str, value: FormValue, cx: &mut App) {
+        self.fields.update(cx, |fields, cx| {
+            if fields.set(key, value) {
+                cx.notify();
+            }
+        });
+    }
+
+    /// The value of the field with `key`.
+    pub fn value(&self, key: &str, cx: &App) -> Option<FormValue> {
+        self.fields
+            .read(cx)
+            .get(key)
+            .map(|field| field.value.clone())
+    }
+
+    /// The text of the field with `key`, or `""` when it isn't a text field.
+    pub fn text(&self, key: &str, cx: &App) -> SharedString {
+        match self.value(key, cx) {
+            Some(FormValue::Text(text)) => text,
+            _ => SharedString::default(),
+        }
+    }
+
+    /// Whether the toggle field with `key` is on.
+    pub fn checked(&self, key: &str, cx: &App) -> bool {
+        self.value(key, cx).is_some_and(|value| value.as_bool())
+    }
+
+    /// The error shown for the field with `key` since the last
+    /// [`submit`](Self::submit), cleared once the value is fixed.
+    pub fn error(&self, key: &str, cx: &App) -> Option<SharedString> {
+        self.fields.read(cx).get(key)?.error.clone()
+    }
+
+    /// Whether every field passes its validators. Unlike
+    /// [`submit`](Self::submit), this doesn't show errors.
+    pub fn is_valid(&self, cx: &App) -> bool {
+        self.fields.read(cx).is_valid()
+    }
+
+    /// The value of every field, by key.
+    pub fn values(&self, cx: &App) -> HashMap<SharedString, FormValue> {
+        self.fields.read(cx).values()
+    }
+
+    /// Validates every field and returns the values when they are all valid.
+    ///
+    /// Otherwise the errors become visible through [`error`](Self::error), focus
+    /// moves to the first invalid field that has an input or focus handle, and
+    /// `None` is returned.
+    pub fn submit(
+        &self,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Option<HashMap<SharedString, FormValue>> {
+        let first_invalid = self.fields.update(cx, |fields, cx| {
+            let first_invalid = fields.validate_all();
+            cx.notify();
+            first_invalid.map(|index| fields.fields[index].focus.clone())
+        });
+        match first_invalid {
+            None => Some(self.values(cx)),
+            Some(focus) => {
+                match focus {
+                    FieldFocus::None => {}
+                    FieldFocus::Input(id) => {
+                        focus_input(window, cx, id);
+                    }
+                    FieldFocus::Handle(handle) => window.focus(&handle),
+                }
+                None
+            }
+        }
+    }
+
+    /// A change handler for a `TextInput`, `TextArea` or `PasswordInput` that
+    /// stores its text under `key`.
+    pub fn on_text(
+        &self,
+        key: impl Into<SharedString>,
+    ) -> impl Fn(SharedString, &mut Window, &mut App) {
+        let form = self.clone();
+        let key = key.into();
+        move |text, _window, cx| form.set_value(&key, FormValue::Text(text), cx)
+    }
+
+    /// A toggle handler for a `Checkbox` or `Switch` that stores its state under
+    /// `key`.
+    pub fn on_toggle(
+        &self,
+        key: impl Into<SharedString>,
+    ) -> impl Fn(bool, Option<&ClickEvent>, &mut Window, &mut App) {
+        let form = self.clone();
+        let key = key.into();
+        move |checked, _event, _window, cx| form.set_value(&key, FormValue::Bool(checked), cx)
+    }
+
+    /// A change handler for a `ComboBox` that stores the picked value under `key`.
+    pub fn on_choice(
+        &self,
+        key: impl Into<SharedString>,
+    ) -> impl Fn(String, &ClickEvent, &mut Window, &mut App) {
+        let form = self.clone();
+        let key = key.into();
+        move |value, _event, _window, cx| form.set_value(&key, FormValue::Text(value.into()), cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signup() -> FormFields {
+        let mut fields = FormFields::default();
+        fields.register(
+            FormField::text("email")
+                .required("Enter your email")
+                .validate(|value| {
+                    if value.as_str().contains('@') {
+                        Ok(())
+                    } else {
+                        Err("Enter a valid email".into())
+                    }
+                }),
+        );
+        fields.register(FormField::toggle("terms").required("Accept the terms"));
+        fields
+    }
+
+    #[test]
+    fn submit_reports_first_invalid_field_in_order() {
+        let mut fields = signup();
+        assert!(!fields.is_valid());
+        assert_eq!(fields.validate_all(), Some(0));
+        assert_eq!(
+            fields.get("email").and_then(|f| f.error.clone()),
+            Some("Enter your email".into())
+        );
+
+        fields.set("email", FormValue::Text("ada".into()));
+        assert_eq!(
+            fields.get("email").and_then(|f| f.error.clone()),
+            Some("Enter a valid email".into())
+        );
+        fields.set("email", FormValue::Text("ada@example.com".into()));
+        assert_eq!(fields.get("email").and_then(|f| f.error.clone()), None);
+        assert_eq!(fields.validate_all(), Some(1));
+
+        fields.set("terms", FormValue::Bool(true));
+        assert_eq!(fields.validate_all(), None);
+        assert!(fields.is_valid());
+    }
+
+    #[test]
+    fn values_are_collected_by_key() {
+        let mut fields = signup();
+        assert!(fields.set("email", FormValue::Text("ada@example.com".into())));
+        assert!(!fields.set("email", FormValue::Text("ada@example.com".into())));
+        assert!(!fields.set("missing", FormValue::Bool(true)));
+
+        let values = fields.values();
+        assert_eq!(values.len(), 2);
+        assert_eq!(values["email"], FormValue::Text("ada@example.com".into()));
+        assert_eq!(values["terms"], FormValue::Bool(false));
+    }
+}
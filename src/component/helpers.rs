@@ -3,10 +3,43 @@
 //! This module provides common utility functions used across multiple components
 //! to reduce code duplication.
 
-use gpui::{App, ElementId, Entity, Window};
+use std::ops::Range;
 
+use gpui::{App, ElementId, Entity, Pixels, SharedString, TextRun, Window};
+
+use crate::component::{ChangeCallback, WindowCallback};
 use crate::theme::{ActionVariantKind, Theme};
 
+/// Generates a fallback element ID from the caller's source location.
+///
+/// Stateful components use this when no `.id()` is provided, so un-ided instances
+/// created at different call sites keep independent keyed state. Instances created
+/// at the same call site (e.g. in a loop) share the ID; stable ids remain recommended.
+#[track_caller]
+pub fn generate_element_id(prefix: &'static str) -> ElementId {
+    let location = std::panic::Location::caller();
+    ElementId::Name(
+        format!(
+            "{prefix}@{}:{}:{}",
+            location.file(),
+            location.line(),
+            location.column()
+        )
+        .into(),
+    )
+}
+
+/// Emits a one-time debug warning for a component rendered with a generated ID.
+pub(crate) fn warn_generated_id(window: &mut Window, cx: &mut App, id: &ElementId) {
+    window.use_keyed_state((id.clone(), "ui:generated-id-warning"), cx, |_, _| {
+        #[cfg(debug_assertions)]
+        eprintln!(
+            "yororen_ui: component rendered without `.id()`, falling back to `{id}`; \
+             set a stable id to keep its state across call-site changes"
+        );
+    });
+}
+
 /// Input style configuration for input components.
 ///
 /// This struct holds the computed style values for input components
@@ -74,6 +107,59 @@ pub fn compute_input_style(
     }
 }
 
+impl InputStyle {
+    /// Switches both borders to the error color when `invalid`.
+    ///
+    /// Disabled inputs keep their muted border. `error_border` overrides the
+    /// theme's error color.
+    pub fn with_invalid(
+        mut self,
+        theme: &Theme,
+        disabled: bool,
+        invalid: bool,
+        error_border: Option<gpui::Hsla>,
+    ) -> Self {
+        if invalid && !disabled {
+            let color = error_border.unwrap_or(theme.status.error.bg);
+            self.border = color;
+            self.focus_border = color;
+        }
+        self
+    }
+}
+
+/// Everything a text field's shaped lines depend on.
+///
+/// Fields keep the key of their last layout and reuse it while the key is
+/// unchanged, so repaints that only blink the cursor skip shaping. The runs carry
+/// the font and color, so theme and font changes shape the text again.
+#[derive(Clone, PartialEq)]
+pub(crate) struct ShapeKey {
+    pub(crate) text: SharedString,
+    pub(crate) runs: Vec<TextRun>,
+    pub(crate) marked_range: Option<Range<usize>>,
+    pub(crate) font_size: Pixels,
+    pub(crate) line_height: Pixels,
+    pub(crate) wrap_width: Option<Pixels>,
+}
+
+/// Default opacity of the selection fill in text fields.
+pub const DEFAULT_SELECTION_OPACITY: f32 = 0.25;
+
+/// Returns the selection fill color for a text field.
+///
+/// Editable fields tint the selection with the focus color. Read-only fields use
+/// the tertiary content color instead, so their selection reads as inactive.
+/// `opacity` is clamped to `0.0..=1.0`.
+pub fn selection_color(theme: &Theme, opacity: f32, read_only: bool) -> gpui::Hsla {
+    let base = if read_only {
+        theme.content.tertiary
+    } else {
+        theme.border.focus
+    };
+    base.alpha(opacity.clamp(0.0, 1.0))
+}
+
 /// Resolves the controlled/uncontrolled state for a component.
 ///
 /// In "controlled" mode, the component's value is managed externally via the
@@ -105,6 +191,78 @@ pub fn resolve_controlled_state<T: Clone + Default + 'static>(
     default_value
 }
 
+/// Returns the content a controlled text `value` prop should write into an input.
+///
+/// `None` means uncontrolled and never touches the input. `Some(value)` is applied
+/// when it differs from the last value passed in, so `Some("")` force-clears the
+/// field (e.g. a form reset) while unchanged props leave user edits alone.
+pub fn controlled_content_update(
+    value: Option<&SharedString>,
+    last_value: Option<&SharedString>,
+    current: &SharedString,
+) -> Option<SharedString> {
+    let value = value?;
+    (last_value != Some(value) && current != value).then(|| value.clone())
+}
+
+/// Applies a controlled text `value` prop to a keyed input state.
+///
+/// Shared by `TextInput`, `TextArea` and `PasswordInput`; see
+/// [`controlled_content_update`] for the semantics.
+pub(crate) fn sync_controlled_value<S: 'static>(
+    window: &mut Window,
+    cx: &mut App,
+    id: &ElementId,
+    value: Option<SharedString>,
+    state: &Entity<S>,
+    content: impl Fn(&S) -> &SharedString,
+    set_content: impl FnOnce(&mut S, SharedString),
+) {
+    let last_value = window.use_keyed_state((id.clone(), "ui:controlled-value"), cx, |_, _| {
+        None::<SharedString>
+    });
+
+    let update = controlled_content_update(
+        value.as_ref(),
+        last_value.read(cx).as_ref(),
+        content(state.read(cx)),
+    );
+    if let Some(new_content) = update {
+        state.update(cx, |state, _| set_content(state, new_content));
+    }
+    if last_value.read(cx).as_ref() != value.as_ref() {
+        last_value.update(cx, |last, _| *last = value);
+    }
+}
+
+/// Records whether an input is focused and fires `on_focus` / `on_blur` when that
+/// changed since the last call.
+///
+/// Shared by `TextInput`, `TextArea` and `PasswordInput`. They call it from their
+/// focus and blur handlers and again on render, which catches keyboard focus
+/// moves; `was_focused` keeps each transition to a single callback.
+pub(crate) fn notify_focus_change(
+    was_focused: &Entity<bool>,
+    focused: bool,
+    content: impl FnOnce(&App) -> SharedString,
+    on_focus: Option<&WindowCallback>,
+    on_blur: Option<&ChangeCallback<SharedString>>,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    if *was_focused.read(cx) == focused {
+        return;
+    }
+    was_focused.update(cx, |value, _| *value = focused);
+    if focused {
+        if let Some(on_focus) = on_focus {
+            on_focus(window, cx);
+        }
+    } else if let Some(on_blur) = on_blur {
+        on_blur(content(cx), window, cx);
+    }
+}
+
 /// Determines whether a component should use internal state management.
 ///
 /// A component is "uncontrolled" (uses internal state) when:
@@ -360,3 +518,87 @@ pub fn compute_toggle_style(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn controlled_empty_value_clears_content() {
+        let current = SharedString::from("draft");
+        let last = SharedString::from("draft");
+        let empty = SharedString::from("");
+
+        assert_eq!(
+            controlled_content_update(Some(&empty), Some(&last), &current),
+            Some(empty)
+        );
+    }
+
+    #[test]
+    fn uncontrolled_value_never_resets() {
+        let current = SharedString::from("typed");
+        let last = SharedString::from("");
+
+        assert_eq!(controlled_content_update(None, Some(&last), &current), None);
+        assert_eq!(controlled_content_update(None, None, &current), None);
+    }
+
+    #[test]
+    fn unchanged_controlled_value_keeps_edits() {
+        let value = SharedString::from("");
+        let current = SharedString::from("typed");
+
+        assert_eq!(
+            controlled_content_update(Some(&value), Some(&value), &current),
+            None
+        );
+    }
+
+    #[test]
+    fn invalid_input_uses_error_border_unless_disabled() {
+        let theme = Theme::default_light();
+        let style = |disabled, invalid, error_border| {
+            compute_input_style(&theme, disabled, None, None, None, None).with_invalid(
+                &theme,
+                disabled,
+                invalid,
+                error_border,
+            )
+        };
+
+        let invalid = style(false, true, None);
+        assert_eq!(invalid.border, theme.status.error.bg);
+        assert_eq!(invalid.focus_border, theme.status.error.bg);
+
+        let custom = gpui::hsla(0.5, 0.5, 0.5, 1.0);
+        assert_eq!(style(false, true, Some(custom)).border, custom);
+
+        assert_eq!(style(false, false, None).border, theme.border.default);
+        assert_eq!(style(true, true, None).border, theme.border.muted);
+    }
+
+    #[test]
+    fn selection_color_uses_configured_opacity() {
+        let theme = Theme::default_light();
+
+        assert_eq!(
+            selection_color(&theme, DEFAULT_SELECTION_OPACITY, false).a,
+            0.25
+        );
+        assert_eq!(selection_color(&theme, 0.6, false).a, 0.6);
+        assert_eq!(selection_color(&theme, 0.6, true).a, 0.6);
+        assert_eq!(selection_color(&theme, 1.5, false).a, 1.0);
+    }
+
+    #[test]
+    fn read_only_selection_uses_muted_color() {
+        let theme = Theme::default_dark();
+        let editable = selection_color(&theme, 0.4, false);
+        let read_only = selection_color(&theme, 0.4, true);
+
+        assert_eq!(editable, theme.border.focus.alpha(0.4));
+        assert_eq!(read_only, theme.content.tertiary.alpha(0.4));
+        assert_ne!(editable, read_only);
+    }
+}
@@ -0,0 +1,321 @@
+use std::time::Duration;
+
+use gpui::{
+    AnyElement, App, ElementId, Entity, Hsla, InteractiveElement, IntoElement, ParentElement,
+    Pixels, RenderOnce, StatefulInteractiveElement, Window, div, prelude::FluentBuilder,
+};
+
+use crate::component::{popover, tooltip::TooltipSuppressor};
+
+/// How long the pointer rests on a trigger before its hover card opens.
+const DEFAULT_OPEN_DELAY: Duration = Duration::from_millis(500);
+
+/// How long a hover card stays after the pointer leaves both it and its trigger.
+const DEFAULT_CLOSE_DELAY: Duration = Duration::from_millis(300);
+
+/// Creates a new hover card element.
+///
+/// A hover card is a popover that opens while the pointer rests on its
+/// trigger. Unlike a tooltip it stays open while the pointer is over the card,
+/// so its content can be interactive, and it closes a short grace period after
+/// the pointer leaves both. It opens and flips like a [`popover`], and hides any
+/// `.with_tooltip()` tooltip on the trigger while it is open. Tooltips attached
+/// through gpui's `.tooltip(tooltip(..).build())` are not hidden.
+///
+/// # Example
+/// ```rust,ignore
+/// use yororen_ui::component::{avatar, hover_card, label};
+///
+/// hover_card("author")
+///     .trigger(label(author.name.clone()))
+///     .content(profile_card(&author))
+///     .delay(400, 200);
+/// ```
+pub fn hover_card(id: impl Into<ElementId>) -> HoverCard {
+    HoverCard::new().id(id)
+}
+
+#[derive(IntoElement)]
+pub struct HoverCard {
+    element_id: ElementId,
+    trigger: Option<AnyElement>,
+    content: Option<AnyElement>,
+    open_delay: Duration,
+    close_delay: Duration,
+    width: Option<Pixels>,
+    caret: bool,
+    bg: Option<Hsla>,
+    border: Option<Hsla>,
+}
+
+impl Default for HoverCard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HoverCard {
+    pub fn new() -> Self {
+        Self {
+            element_id: "ui:hover-card".into(),
+            trigger: None,
+            content: None,
+            open_delay: DEFAULT_OPEN_DELAY,
+            close_delay: DEFAULT_CLOSE_DELAY,
+            width: None,
+            caret: false,
+            bg: None,
+            border: None,
+        }
+    }
+
+    pub fn id(mut self, id: impl Into<ElementId>) -> Self {
+        self.element_id = id.into();
+        self
+    }
+
+    /// Alias for `id(...)`. Use `key(...)` when you want to emphasize state identity.
+    pub fn key(self, key: impl Into<ElementId>) -> Self {
+        self.id(key)
+    }
+
+    pub fn trigger(mut self, trigger: impl IntoElement) -> Self {
+        self.trigger = Some(trigger.into_any_element());
+        self
+    }
+
+    pub fn content(mut self, content: impl IntoElement) -> Self {
+        self.content = Some(content.into_any_element());
+        self
+    }
+
+    /// How long, in milliseconds, the pointer rests on the trigger before the
+    /// card opens (default: 500), and how long the card stays after the pointer
+    /// leaves both the trigger and the card (default: 300).
+    pub fn delay(mut self, open_ms: u64, close_ms: u64) -> Self {
+        self.open_delay = Duration::from_millis(open_ms);
+        self.close_delay = Duration::from_millis(close_ms);
+        self
+    }
+
+    pub fn width(mut self, width: Pixels) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Shows an arrow on the card's edge pointing at the trigger (default: false).
+    pub fn caret(mut self, caret: bool) -> Self {
+        self.caret = caret;
+        self
+    }
+
+    pub fn bg(mut self, color: impl Into<Hsla>) -> Self {
+        self.bg = Some(color.into());
+        self
+    }
+
+    pub fn border(mut self, color: impl Into<Hsla>) -> Self {
+        self.border = Some(color.into());
+        self
+    }
+}
+
+/// The part of a hover card the pointer is over.
+#[derive(Clone, Copy)]
+enum HoverRegion {
+    Trigger,
+    Card,
+}
+
+/// Whether a hover card is open, and the pending change to it.
+#[derive(Default)]
+struct HoverCardState {
+    open: bool,
+    over_trigger: bool,
+    over_card: bool,
+    /// Bumped on every hover change, so a timer from an older one is ignored.
+    epoch: usize,
+}
+
+impl HoverCardState {
+    /// Records the pointer entering or leaving `region`, returning the epoch
+    /// and the open state to settle on once its delay passes, if it changes.
+    fn hover(&mut self, region: HoverRegion, hovered: bool) -> Option<(usize, bool)> {
+        match region {
+            HoverRegion::Trigger => self.over_trigger = hovered,
+            HoverRegion::Card => self.over_card = hovered,
+        }
+        self.epoch = self.epoch.wrapping_add(1);
+        let open = self.over_trigger || self.over_card;
+        (open != self.open).then_some((self.epoch, open))
+    }
+
+    /// Applies a pending change unless the pointer moved since it was scheduled.
+    fn settle(&mut self, epoch: usize, open: bool) -> bool {
+        let changed = self.epoch == epoch && self.open != open;
+        if changed {
+            self.open = open;
+        }
+        changed
+    }
+
+    fn close(&mut self) {
+        *self = Self {
+            epoch: self.epoch.wrapping_add(1),
+            ..Self::default()
+        };
+    }
+}
+
+/// Returns a hover listener for `region` that opens or closes the card once
+/// the matching delay passes.
+fn on_hover(
+    state: &Entity<HoverCardState>,
+    region: HoverRegion,
+    open_delay: Duration,
+    close_delay: Duration,
+) -> impl Fn(bool, &mut Window, &mut App) + 'static {
+    let state = state.clone();
+    move |hovered, window, cx| {
+        let Some((epoch, open)) = state.update(cx, |state, _| state.hover(region, hovered)) else {
+            return;
+        };
+        let delay = if open { open_delay } else { close_delay };
+        let state = state.clone();
+        window
+            .spawn(cx, async move |cx| {
+                cx.background_executor().timer(delay).await;
+                cx.update(|_window, cx| {
+                    state.update(cx, |state, cx| {
+                        if state.settle(epoch, open) {
+                            cx.notify();
+                        }
+                    });
+                })
+                .ok();
+            })
+            .detach();
+    }
+}
+
+impl RenderOnce for HoverCard {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let id = self.element_id;
+        let state = window.use_keyed_state((id.clone(), "ui:hover-card:state"), cx, |_, _| {
+            HoverCardState::default()
+        });
+        let open = state.read(cx).open;
+
+        let trigger = self.trigger.unwrap_or_else(|| div().into_any_element());
+        let on_trigger_hover = on_hover(
+            &state,
+            HoverRegion::Trigger,
+            self.open_delay,
+            self.close_delay,
+        );
+
+        popover((id.clone(), "ui:hover-card:popover"))
+            .open(open)
+            .flip(true)
+            .caret(self.caret)
+            .when_some(self.width, |this, width| this.width(width))
+            .when_some(self.bg, |this, bg| this.bg(bg))
+            .when_some(self.border, |this, border| this.border(border))
+            .trigger(
+                div()
+                    .id((id, "ui:hover-card:trigger"))
+                    .on_hover(move |hovered, window, cx| on_trigger_hover(*hovered, window, cx))
+                    .child(TooltipSuppressor {
+                        active: open,
+                        inner: trigger,
+                    }),
+            )
+            .when_some(self.content, |this, content| this.content(content))
+            .on_menu_hover(on_hover(
+                &state,
+                HoverRegion::Card,
+                self.open_delay,
+                self.close_delay,
+            ))
+            .on_close(move |_window, cx| {
+                state.update(cx, |state, cx| {
+                    state.close();
+                    cx.notify();
+                });
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{
+        Modifiers, Render, Styled, TestAppContext, VisualTestContext, WindowAppearance, px,
+    };
+
+    use super::*;
+    use crate::theme::GlobalTheme;
+
+    #[test]
+    fn card_stays_open_while_the_pointer_moves_onto_it() {
+        let mut state = HoverCardState::default();
+        let (epoch, open) = state.hover(HoverRegion::Trigger, true).unwrap();
+        assert!(state.settle(epoch, open));
+
+        // Leaving the trigger schedules a close, which entering the card cancels.
+        let (epoch, open) = state.hover(HoverRegion::Trigger, false).unwrap();
+        assert_eq!(state.hover(HoverRegion::Card, true), None);
+        assert!(!state.settle(epoch, open));
+        assert!(state.open);
+
+        let (epoch, open) = state.hover(HoverRegion::Card, false).unwrap();
+        assert!(state.settle(epoch, open));
+        assert!(!state.open);
+    }
+
+    /// A trigger at the bottom edge of the window, with a tall card.
+    struct BottomTrigger;
+
+    impl Render for BottomTrigger {
+        fn render(
+            &mut self,
+            _window: &mut Window,
+            _cx: &mut gpui::Context<Self>,
+        ) -> impl IntoElement {
+            div().size_full().flex().flex_col().justify_end().child(
+                hover_card("card")
+                    .trigger(
+                        div()
+                            .id("trigger")
+                            .debug_selector(|| "trigger".into())
+                            .size(px(20.)),
+                    )
+                    .content(div().debug_selector(|| "card".into()).h(px(200.))),
+            )
+        }
+    }
+
+    #[gpui::test]
+    fn card_flips_above_a_trigger_at_the_bottom_edge(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            cx.set_global(GlobalTheme::new(WindowAppearance::Light));
+            crate::component::init(cx);
+        });
+        let (_, cx) = cx.add_window_view(|_, _| BottomTrigger);
+        let cx: &mut VisualTestContext = cx;
+        cx.run_until_parked();
+
+        let trigger = cx.debug_bounds("trigger").unwrap();
+        cx.simulate_mouse_move(trigger.center(), None, Modifiers::none());
+        cx.executor().advance_clock(DEFAULT_OPEN_DELAY);
+        cx.run_until_parked();
+        // The first open frame measures the card; the next one places it.
+        cx.update(|window, _| window.refresh());
+        cx.run_until_parked();
+
+        let card = cx.debug_bounds("card").unwrap();
+        assert!(
+            card.bottom() <= trigger.top(),
+            "card {card:?} should open above trigger {trigger:?}"
+        );
+    }
+}
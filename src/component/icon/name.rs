@@ -32,9 +32,12 @@ pub enum IconName {
 
     Arrow(ArrowDirection),
     Check,
+    Copy,
     Warning,
     Info,
     Close,
+    /// Open (`true`) or crossed-out (`false`) eye, e.g. for show/hide toggles.
+    Eye(bool),
     Maximize(bool),
     Minimize,
     Modpack,
@@ -55,9 +58,11 @@ impl From<IconName> for SharedString {
 
             IconName::Arrow(direction) => format!("arrow-{direction}").into(),
             IconName::Check => "check".into(),
+            IconName::Copy => "copy".into(),
             IconName::Warning => "warning".into(),
             IconName::Info => "info".into(),
             IconName::Close => "close".into(),
+            IconName::Eye(open) => format!("eye-{}", if open { "on" } else { "off" }).into(),
             IconName::Maximize(i) => format!("maximize-{}", if i { "on" } else { "off" }).into(),
             IconName::Minimize => "minimize".into(),
             IconName::Modpack => "modpack".into(),
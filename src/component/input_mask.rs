@@ -0,0 +1,190 @@
+//! Format masks for text inputs, e.g. `(###) ###-####` for phone numbers.
+//!
+//! In a pattern, `#` accepts a digit, `A` a letter and `*` any character; every
+//! other character is a literal that is inserted as the user types. Prefix a
+//! placeholder character with `\` to use it as a literal.
+
+use std::ops::Range;
+
+use gpui::SharedString;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Slot {
+    Digit,
+    Letter,
+    Any,
+    Literal(char),
+}
+
+impl Slot {
+    fn accepts(self, c: char) -> bool {
+        match self {
+            Slot::Digit => c.is_ascii_digit(),
+            Slot::Letter => c.is_alphabetic(),
+            Slot::Any => !c.is_control(),
+            Slot::Literal(_) => false,
+        }
+    }
+}
+
+/// A parsed format mask.
+///
+/// Masked text always holds one character per slot, from the start of the
+/// pattern up to the last entered character.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InputMask {
+    pattern: SharedString,
+    slots: Vec<Slot>,
+}
+
+impl InputMask {
+    pub fn new(pattern: impl Into<SharedString>) -> Self {
+        let pattern = pattern.into();
+        let mut slots = Vec::new();
+        let mut chars = pattern.chars();
+        while let Some(c) = chars.next() {
+            slots.push(match c {
+                '#' => Slot::Digit,
+                'A' => Slot::Letter,
+                '*' => Slot::Any,
+                '\\' => Slot::Literal(chars.next().unwrap_or('\\')),
+                c => Slot::Literal(c),
+            });
+        }
+        Self { pattern, slots }
+    }
+
+    pub fn pattern(&self) -> &SharedString {
+        &self.pattern
+    }
+
+    /// Lays `input` out over the mask.
+    ///
+    /// Characters that don't fit their slot are dropped, and literals in `input`
+    /// that line up with the mask's own are absorbed, so both `5551234567` and
+    /// `(555) 123-4567` format the same.
+    pub fn apply(&self, input: &str) -> String {
+        let mut input = input.chars().peekable();
+        let mut out = String::new();
+        // Literals only show once a character follows them.
+        let mut filled = 0;
+        for slot in &self.slots {
+            if input.peek().is_none() {
+                break;
+            }
+            match *slot {
+                Slot::Literal(literal) => {
+                    out.push(literal);
+                    input.next_if_eq(&literal);
+                }
+                slot => match input.by_ref().find(|&c| slot.accepts(c)) {
+                    Some(c) => {
+                        out.push(c);
+                        filled = out.len();
+                    }
+                    None => break,
+                },
+            }
+        }
+        out.truncate(filled);
+        out
+    }
+
+    /// The user-entered characters of masked `text`, without literals.
+    pub fn unmask(&self, text: &str) -> String {
+        self.entered(text, 0).collect()
+    }
+
+    /// Whether the character at `char_index` of masked text is a literal.
+    pub fn is_literal_at(&self, char_index: usize) -> bool {
+        matches!(self.slots.get(char_index), Some(Slot::Literal(_)))
+    }
+
+    /// Replaces the byte `range` of masked `text` with `inserted` and re-applies
+    /// the mask, returning the new text and the byte offset for the cursor.
+    ///
+    /// The cursor lands after the inserted characters, past any literals that
+    /// were filled in before them.
+    pub fn edit(&self, text: &str, range: Range<usize>, inserted: &str) -> (String, usize) {
+        let mut head: String = self.entered(&text[..range.start], 0).collect();
+        head.push_str(inserted);
+        let cursor = self.apply(&head).len();
+
+        let tail_slot = text[..range.end].chars().count();
+        head.extend(self.entered(&text[range.end..], tail_slot));
+        (self.apply(&head), cursor)
+    }
+
+    /// Characters of `text` sitting in input slots, where `text` starts at slot
+    /// `first_slot`.
+    fn entered<'a>(&'a self, text: &'a str, first_slot: usize) -> impl Iterator<Item = char> + 'a {
+        text.chars()
+            .zip(self.slots.iter().skip(first_slot))
+            .filter(|(_, slot)| !matches!(slot, Slot::Literal(_)))
+            .map(|(c, _)| c)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn phone() -> InputMask {
+        InputMask::new("(###) ###-####")
+    }
+
+    #[test]
+    fn literals_are_inserted_as_input_arrives() {
+        let mask = phone();
+        assert_eq!(mask.apply(""), "");
+        assert_eq!(mask.apply("5"), "(5");
+        assert_eq!(mask.apply("555"), "(555");
+        assert_eq!(mask.apply("5551"), "(555) 1");
+        assert_eq!(mask.apply("55512345678"), "(555) 123-4567");
+    }
+
+    #[test]
+    fn formatted_and_noisy_input_normalize() {
+        let mask = phone();
+        assert_eq!(mask.apply("(555) 123-4567"), "(555) 123-4567");
+        assert_eq!(mask.apply("555-123-4567"), "(555) 123-4567");
+        assert_eq!(mask.apply("5a5b5"), "(555");
+        assert_eq!(InputMask::new("AA-##").apply("ab12"), "ab-12");
+        assert_eq!(InputMask::new("\\##").apply("7"), "#7");
+    }
+
+    #[test]
+    fn unmask_keeps_only_entered_characters() {
+        let mask = phone();
+        assert_eq!(mask.unmask("(555) 123-4567"), "5551234567");
+        assert_eq!(mask.unmask("(555"), "555");
+        assert!(mask.is_literal_at(0));
+        assert!(!mask.is_literal_at(1));
+        assert!(mask.is_literal_at(4));
+    }
+
+    #[test]
+    fn typing_skips_over_literals() {
+        let mask = phone();
+        assert_eq!(mask.edit("(555", 4..4, "1"), ("(555) 1".to_string(), 7));
+        // Typing a rejected character leaves text and cursor alone.
+        assert_eq!(mask.edit("(555", 4..4, "x"), ("(555".to_string(), 4));
+    }
+
+    #[test]
+    fn edits_in_the_middle_reflow_the_tail() {
+        let mask = phone();
+        // Delete the "5" before ") 1".
+        assert_eq!(
+            mask.edit("(555) 123", 3..4, ""),
+            ("(551) 23".to_string(), 3)
+        );
+        // Insert at the start.
+        assert_eq!(mask.edit("(555) 1", 1..1, "9"), ("(955) 51".to_string(), 2));
+        // Paste a formatted number over everything.
+        assert_eq!(
+            mask.edit("(12", 0..3, "(555) 123-4567"),
+            ("(555) 123-4567".to_string(), 14)
+        );
+    }
+}
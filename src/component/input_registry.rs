@@ -0,0 +1,290 @@
+//! Lookup of text input state by element id.
+//!
+//! `TextInput`, `TextArea` and `PasswordInput` keep their content in keyed state,
+//! which is only reachable while the component renders. Each input registers its
+//! state here on render so app code (and tests) can read or replace a field's
+//! content, focus it, or fetch its state entity, by id.
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use gpui::{
+    AnyEntity, App, ElementId, Entity, EntityId, Focusable, Global, SharedString, Window, WindowId,
+};
+
+type ReadContent = Box<dyn Fn(&App) -> Option<SharedString>>;
+type WriteContent = Rc<dyn Fn(SharedString, &mut App) -> bool>;
+type FocusInput = Rc<dyn Fn(&mut Window, &mut App) -> bool>;
+
+struct InputSlot {
+    entity_id: EntityId,
+    alive: Box<dyn Fn() -> bool>,
+    state: Box<dyn Fn() -> Option<AnyEntity>>,
+    read: ReadContent,
+    write: WriteContent,
+    focus: FocusInput,
+}
+
+#[derive(Default)]
+struct InputRegistry {
+    inputs: HashMap<(WindowId, ElementId), InputSlot>,
+}
+
+impl Global for InputRegistry {}
+
+impl InputRegistry {
+    /// Records `slot` under `key` unless the same entity is already registered.
+    ///
+    /// Returns `true` when the registry changed. Registering a new key also drops
+    /// slots whose inputs are gone, so the map doesn't grow with transient ids.
+    fn register(&mut self, key: (WindowId, ElementId), slot: impl FnOnce() -> InputSlot) -> bool {
+        if let Some(existing) = self.inputs.get(&key) {
+            let slot = slot();
+            if existing.entity_id == slot.entity_id {
+                return false;
+            }
+            self.inputs.insert(key, slot);
+            return true;
+        }
+
+        self.inputs.retain(|_, slot| (slot.alive)());
+        self.inputs.insert(key, slot());
+        true
+    }
+
+    fn get(&self, window_id: WindowId, id: &ElementId) -> Option<&InputSlot> {
+        self.inputs.get(&(window_id, id.clone()))
+    }
+}
+
+/// Registers an input's keyed state so it can be reached through [`input_content`],
+/// [`set_input_content`] and [`focus_input`].
+pub(crate) fn register_input<S: 'static + Focusable>(
+    window: &Window,
+    cx: &mut App,
+    id: &ElementId,
+    state: &Entity<S>,
+    content: fn(&S) -> &SharedString,
+    set_content: fn(&mut S, SharedString),
+) {
+    let key = (window.window_handle().window_id(), id.clone());
+    let entity_id = state.entity_id();
+    if cx
+        .try_global::<InputRegistry>()
+        .and_then(|registry| registry.inputs.get(&key))
+        .is_some_and(|slot| slot.entity_id == entity_id)
+    {
+        return;
+    }
+
+    let weak = state.downgrade();
+    cx.default_global::<InputRegistry>()
+        .register(key, || InputSlot {
+            entity_id,
+            alive: Box::new({
+                let weak = weak.clone();
+                move || weak.upgrade().is_some()
+            }),
+            state: Box::new({
+                let weak = weak.clone();
+                move || weak.upgrade().map(Entity::into_any)
+            }),
+            read: Box::new({
+                let weak = weak.clone();
+                move |cx| weak.upgrade().map(|state| content(state.read(cx)).clone())
+            }),
+            write: Rc::new({
+                let weak = weak.clone();
+                move |text, cx| {
+                    let Some(state) = weak.upgrade() else {
+                        return false;
+                    };
+                    state.update(cx, |state, cx| {
+                        set_content(state, text);
+                        cx.notify();
+                    });
+                    true
+                }
+            }),
+            focus: Rc::new(move |window, cx| {
+                let Some(state) = weak.upgrade() else {
+                    return false;
+                };
+                window.focus(&state.read(cx).focus_handle(cx));
+                true
+            }),
+        });
+}
+
+/// Returns the keyed state of the input with `id` in `window`, if one of type `S`
+/// has rendered.
+pub(crate) fn registered_state<S: 'static>(
+    window: &Window,
+    cx: &App,
+    id: &ElementId,
+) -> Option<Entity<S>> {
+    let registry = cx.try_global::<InputRegistry>()?;
+    let slot = registry.get(window.window_handle().window_id(), id)?;
+    (slot.state)()?.downcast().ok()
+}
+
+/// Returns the content of the text input, text area or password input with `id`
+/// in `window`, or `None` if no such input has rendered.
+pub fn input_content(window: &Window, cx: &App, id: impl Into<ElementId>) -> Option<SharedString> {
+    let registry = cx.try_global::<InputRegistry>()?;
+    let slot = registry.get(window.window_handle().window_id(), &id.into())?;
+    (slot.read)(cx)
+}
+
+/// Replaces the content of the input with `id` in `window` and re-renders it.
+///
+/// Returns `false` if no such input has rendered. Like `.set_content(...)` on the
+/// builder, this fires the input's `on_change` on its next render.
+pub fn set_input_content(
+    window: &Window,
+    cx: &mut App,
+    id: impl Into<ElementId>,
+    text: impl Into<SharedString>,
+) -> bool {
+    let write = cx
+        .try_global::<InputRegistry>()
+        .and_then(|registry| registry.get(window.window_handle().window_id(), &id.into()))
+        .map(|slot| slot.write.clone());
+    match write {
+        Some(write) => write(text.into(), cx),
+        None => false,
+    }
+}
+
+/// Moves keyboard focus to the input with `id` in `window`.
+///
+/// Returns `false` if no such input has rendered.
+pub fn focus_input(window: &mut Window, cx: &mut App, id: impl Into<ElementId>) -> bool {
+    let focus = cx
+        .try_global::<InputRegistry>()
+        .and_then(|registry| registry.get(window.window_handle().window_id(), &id.into()))
+        .map(|slot| slot.focus.clone());
+    match focus {
+        Some(focus) => focus(window, cx),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use gpui::{IntoElement, ParentElement, Render, TestAppContext, WindowAppearance, div};
+
+    use super::*;
+    use crate::component::text_input;
+    use crate::theme::GlobalTheme;
+
+    fn slot(entity_id: u64, alive: bool) -> InputSlot {
+        InputSlot {
+            entity_id: EntityId::from(entity_id),
+            alive: Box::new(move || alive),
+            state: Box::new(|| None),
+            read: Box::new(|_| None),
+            write: Rc::new(|_, _| false),
+            focus: Rc::new(|_, _| false),
+        }
+    }
+
+    fn key(window: u64, id: &'static str) -> (WindowId, ElementId) {
+        (WindowId::from(window), ElementId::from(id))
+    }
+
+    #[test]
+    fn same_entity_registers_once() {
+        let mut registry = InputRegistry::default();
+
+        assert!(registry.register(key(1, "name"), || slot(7, true)));
+        assert!(!registry.register(key(1, "name"), || slot(7, true)));
+        assert!(registry.register(key(1, "name"), || slot(8, true)));
+        assert_eq!(
+            registry
+                .get(WindowId::from(1), &ElementId::from("name"))
+                .map(|slot| slot.entity_id),
+            Some(EntityId::from(8))
+        );
+    }
+
+    #[test]
+    fn ids_are_scoped_per_window() {
+        let mut registry = InputRegistry::default();
+        registry.register(key(1, "name"), || slot(7, true));
+        registry.register(key(2, "name"), || slot(9, true));
+
+        let id = ElementId::from("name");
+        assert_eq!(
+            registry.get(WindowId::from(1), &id).map(|s| s.entity_id),
+            Some(EntityId::from(7))
+        );
+        assert_eq!(
+            registry.get(WindowId::from(2), &id).map(|s| s.entity_id),
+            Some(EntityId::from(9))
+        );
+        assert!(registry.get(WindowId::from(3), &id).is_none());
+    }
+
+    #[test]
+    fn dropped_inputs_are_pruned() {
+        let mut registry = InputRegistry::default();
+        registry.register(key(1, "gone"), || slot(7, false));
+        registry.register(key(1, "live"), || slot(8, true));
+
+        assert!(
+            registry
+                .get(WindowId::from(1), &ElementId::from("gone"))
+                .is_none()
+        );
+        assert!(
+            registry
+                .get(WindowId::from(1), &ElementId::from("live"))
+                .is_some()
+        );
+    }
+
+    struct Form {
+        rendered: Rc<RefCell<Vec<SharedString>>>,
+    }
+
+    impl Render for Form {
+        fn render(
+            &mut self,
+            _window: &mut Window,
+            _cx: &mut gpui::Context<Self>,
+        ) -> impl IntoElement {
+            let rendered = self.rendered.clone();
+            div().child(
+                text_input("name")
+                    .on_change(move |content, _, _| rendered.borrow_mut().push(content)),
+            )
+        }
+    }
+
+    #[gpui::test]
+    fn set_input_content_rerenders_the_input(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            cx.set_global(GlobalTheme::new(WindowAppearance::Light));
+            crate::component::init(cx);
+        });
+        let rendered = Rc::new(RefCell::new(Vec::new()));
+        let (_, cx) = cx.add_window_view({
+            let rendered = rendered.clone();
+            |_, _| Form { rendered }
+        });
+        cx.run_until_parked();
+
+        assert!(cx.update(|window, cx| set_input_content(window, cx, "name", "Ada")));
+        cx.run_until_parked();
+
+        assert_eq!(rendered.borrow().last(), Some(&SharedString::from("Ada")));
+        assert_eq!(
+            cx.update(|window, cx| input_content(window, cx, "name")),
+            Some(SharedString::from("Ada"))
+        );
+        assert!(!cx.update(|window, cx| set_input_content(window, cx, "missing", "x")));
+    }
+}
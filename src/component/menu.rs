@@ -0,0 +1,791 @@
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use gpui::{
+    AnyElement, App, Bounds, Div, ElementId, Entity, FocusHandle, InteractiveElement, IntoElement,
+    KeyDownEvent, ParentElement, Pixels, RenderOnce, SharedString, StatefulInteractiveElement,
+    Styled, Window, anchored, deferred, div, point, prelude::FluentBuilder, px,
+};
+
+use crate::{
+    component::{
+        ArrowDirection, BoundsTrackerElement, IconName, IconPath, PopoverPlacement, divider, icon,
+        popover,
+        popover::{desired_submenu_left, desired_submenu_top},
+        shortcut_hint,
+    },
+    i18n::TextDirection,
+    theme::{ActiveTheme, Theme},
+};
+
+/// Creates a new menu element.
+///
+/// A menu opens a list of [`MenuItem`]s in a popover below its trigger. Items
+/// can carry an icon, a shortcut hint and a submenu, which opens on hover or
+/// with the arrow key pointing into it.
+///
+/// While open, Up/Down move the highlight, Home/End jump to the first and last
+/// item, Enter or Space selects, Escape closes the innermost panel, and typing
+/// jumps to the next item whose label starts with the typed text. The menu
+/// closes on selection or a click outside it.
+///
+/// # Example
+/// ```rust,ignore
+/// use yororen_ui::component::{button, menu, MenuItem};
+///
+/// menu("file-menu")
+///     .trigger(button("file-menu-trigger").child("File"))
+///     .item(MenuItem::new("open", "Open").shortcut("Ctrl+O"))
+///     .item(MenuItem::separator())
+///     .item(MenuItem::new("recent", "Open Recent").submenu([
+///         MenuItem::new("recent-1", "notes.md"),
+///     ]))
+///     .on_select(|id, _window, _cx| println!("{id}"));
+/// ```
+pub fn menu(id: impl Into<ElementId>) -> Menu {
+    Menu::new().id(id)
+}
+
+type ItemSelectFn = Rc<dyn Fn(&mut Window, &mut App)>;
+type MenuSelectFn = Rc<dyn Fn(&SharedString, &mut Window, &mut App)>;
+
+/// Typed characters within this interval extend the type-ahead search.
+const TYPEAHEAD_TIMEOUT: Duration = Duration::from_millis(500);
+
+const MENU_WIDTH: Pixels = px(220.);
+const ROW_HEIGHT: Pixels = px(30.);
+
+/// An entry of a [`Menu`].
+#[derive(Clone)]
+pub struct MenuItem {
+    pub id: SharedString,
+    pub label: SharedString,
+    pub icon: Option<SharedString>,
+    pub shortcut: Option<SharedString>,
+    pub disabled: bool,
+    pub submenu: Vec<MenuItem>,
+    separator: bool,
+    on_select: Option<ItemSelectFn>,
+}
+
+impl MenuItem {
+    pub fn new(id: impl Into<SharedString>, label: impl Into<SharedString>) -> Self {
+        Self {
+            id: id.into(),
+            label: label.into(),
+            icon: None,
+            shortcut: None,
+            disabled: false,
+            submenu: Vec::new(),
+            separator: false,
+            on_select: None,
+        }
+    }
+
+    /// A line between groups of items.
+    pub fn separator() -> Self {
+        Self {
+            separator: true,
+            ..Self::new("", "")
+        }
+    }
+
+    pub fn icon(mut self, icon: impl Into<IconPath>) -> Self {
+        self.icon = Some(icon.into().into());
+        self
+    }
+
+    /// Shortcut text shown at the end of the row, e.g. "Ctrl+S".
+    pub fn shortcut(mut self, shortcut: impl Into<SharedString>) -> Self {
+        self.shortcut = Some(shortcut.into());
+        self
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Items of a nested menu opened from this one.
+    pub fn submenu(mut self, items: impl IntoIterator<Item = MenuItem>) -> Self {
+        self.submenu = items.into_iter().collect();
+        self
+    }
+
+    /// Called when this item is selected.
+    pub fn on_select<F>(mut self, handler: F) -> Self
+    where
+        F: 'static + Fn(&mut Window, &mut App),
+    {
+        self.on_select = Some(Rc::new(handler));
+        self
+    }
+
+    pub fn is_separator(&self) -> bool {
+        self.separator
+    }
+
+    /// Whether the item can be highlighted and selected.
+    fn is_enabled(&self) -> bool {
+        !self.separator && !self.disabled
+    }
+
+    fn has_submenu(&self) -> bool {
+        !self.submenu.is_empty()
+    }
+
+    /// ARIA attributes for this item.
+    pub fn aria_attrs(&self, expanded: bool) -> Vec<(String, String)> {
+        if self.separator {
+            crate::a11y::menu::separator_attrs()
+        } else if self.has_submenu() {
+            crate::a11y::menuitem::submenu_attrs(self.disabled, expanded)
+        } else {
+            crate::a11y::menuitem::attrs(self.disabled)
+        }
+    }
+}
+
+#[derive(IntoElement)]
+pub struct Menu {
+    element_id: ElementId,
+    base: Div,
+    trigger: Option<AnyElement>,
+    items: Vec<MenuItem>,
+    width: Pixels,
+    placement: PopoverPlacement,
+    on_select: Option<MenuSelectFn>,
+}
+
+impl Default for Menu {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Menu {
+    pub fn new() -> Self {
+        Self {
+            element_id: "ui:menu".into(),
+            base: div(),
+            trigger: None,
+            items: Vec::new(),
+            width: MENU_WIDTH,
+            placement: PopoverPlacement::BottomStart,
+            on_select: None,
+        }
+    }
+
+    pub fn id(mut self, id: impl Into<ElementId>) -> Self {
+        self.element_id = id.into();
+        self
+    }
+
+    /// Alias for `id(...)`. Use `key(...)` when you want to emphasize state identity.
+    pub fn key(self, key: impl Into<ElementId>) -> Self {
+        self.id(key)
+    }
+
+    /// The element that opens the menu when clicked.
+    pub fn trigger(mut self, trigger: impl IntoElement) -> Self {
+        self.trigger = Some(trigger.into_any_element());
+        self
+    }
+
+    pub fn item(mut self, item: MenuItem) -> Self {
+        self.items.push(item);
+        self
+    }
+
+    pub fn items(mut self, items: impl IntoIterator<Item = MenuItem>) -> Self {
+        self.items.extend(items);
+        self
+    }
+
+    /// Width of the menu and its submenus.
+    pub fn width(mut self, width: Pixels) -> Self {
+        self.width = width;
+        self
+    }
+
+    pub fn placement(mut self, placement: PopoverPlacement) -> Self {
+        self.placement = placement;
+        self
+    }
+
+    /// Called with the id of any selected item, after the item's own handler.
+    pub fn on_select<F>(mut self, handler: F) -> Self
+    where
+        F: 'static + Fn(&SharedString, &mut Window, &mut App),
+    {
+        self.on_select = Some(Rc::new(handler));
+        self
+    }
+
+    /// ARIA attributes for the menu panel.
+    pub fn aria_attrs(&self) -> Vec<(String, String)> {
+        crate::a11y::menu::attrs()
+    }
+}
+
+impl Styled for Menu {
+    fn style(&mut self) -> &mut gpui::StyleRefinement {
+        self.base.style()
+    }
+}
+
+/// What a menu needs to render its panels.
+#[derive(Clone)]
+struct MenuContext {
+    id: ElementId,
+    items: Rc<Vec<MenuItem>>,
+    nav: Entity<MenuNav>,
+    levels: Vec<Option<usize>>,
+    on_select: Option<MenuSelectFn>,
+    theme: Arc<Theme>,
+    direction: TextDirection,
+    /// Width of the root menu and its submenus.
+    width: Pixels,
+}
+
+impl MenuContext {
+    /// Selects the highlighted item of the innermost panel, or opens its submenu.
+    fn activate(&self, window: &mut Window, cx: &mut App) {
+        let Some(item) = self.nav.read(cx).highlighted(&self.items).cloned() else {
+            return;
+        };
+        if item.has_submenu() {
+            self.nav.update(cx, |nav, _| nav.expand(&self.items));
+        } else {
+            self.nav.update(cx, |nav, _| nav.close());
+            if let Some(handler) = &item.on_select {
+                handler(window, cx);
+            }
+            if let Some(handler) = &self.on_select {
+                handler(&item.id, window, cx);
+            }
+        }
+        window.refresh();
+    }
+
+    fn anchor_state(
+        &self,
+        depth: usize,
+        index: usize,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Entity<Bounds<Pixels>> {
+        window.use_keyed_state(
+            (self.id.clone(), format!("ui:menu:anchor:{depth}:{index}")),
+            cx,
+            |_, _| Bounds::default(),
+        )
+    }
+
+    fn panel_state(
+        &self,
+        depth: usize,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Entity<Bounds<Pixels>> {
+        window.use_keyed_state(
+            (self.id.clone(), format!("ui:menu:panel:{depth}")),
+            cx,
+            |_, _| Bounds::default(),
+        )
+    }
+
+    /// The rows of the panel at `depth`.
+    fn panel(&self, depth: usize, window: &mut Window, cx: &mut App) -> Div {
+        let items = panel_items(&self.items, &self.levels, depth);
+        let highlighted = self.levels.get(depth).copied().flatten();
+        let theme = &self.theme;
+
+        let rows = items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                if item.separator {
+                    return div().py_1().child(divider()).into_any_element();
+                }
+                let enabled = item.is_enabled();
+                let row = div()
+                    .id((SharedString::from(format!("ui:menu:item:{depth}")), index))
+                    .flex()
+                    .items_center()
+                    .gap_2()
+                    .h(ROW_HEIGHT)
+                    .px_3()
+                    .mx_1()
+                    .rounded_md()
+                    .text_sm()
+                    .text_color(if enabled {
+                        theme.content.primary
+                    } else {
+                        theme.content.disabled
+                    })
+                    .when(highlighted == Some(index) && enabled, |this| {
+                        this.bg(theme.surface.hover)
+                    })
+                    .when(!enabled, |this| this.cursor_not_allowed())
+                    .when(enabled, |this| {
+                        let menu = self.clone();
+                        let nav = self.nav.clone();
+                        let items = self.items.clone();
+                        this.cursor_pointer()
+                            .on_hover(move |hovered, window, cx| {
+                                if *hovered {
+                                    nav.update(cx, |nav, _| nav.hover(&items, depth, index));
+                                    window.refresh();
+                                }
+                            })
+                            .on_click(move |_ev, window, cx| {
+                                menu.nav
+                                    .update(cx, |nav, _| nav.hover(&menu.items, depth, index));
+                                menu.activate(window, cx);
+                            })
+                    })
+                    .child(
+                        div()
+                            .size(px(14.))
+                            .flex_none()
+                            .when_some(item.icon.clone(), |this, path| {
+                                this.child(icon(path).size(px(14.)).inherit_color(true))
+                            }),
+                    )
+                    .child(div().flex_1().truncate().child(item.label.clone()))
+                    .when_some(item.shortcut.clone(), |this, shortcut| {
+                        this.child(shortcut_hint(shortcut))
+                    })
+                    .when(item.has_submenu(), |this| {
+                        let arrow = crate::rtl::flip_left_right(
+                            self.direction,
+                            ArrowDirection::Right,
+                            ArrowDirection::Left,
+                        );
+                        this.child(
+                            icon(IconName::Arrow(arrow))
+                                .size(px(12.))
+                                .color(theme.content.tertiary),
+                        )
+                    });
+
+                if item.has_submenu() {
+                    BoundsTrackerElement {
+                        bounds_state: self.anchor_state(depth, index, window, cx),
+                        inner: row.into_any_element(),
+                    }
+                    .into_any_element()
+                } else {
+                    row.into_any_element()
+                }
+            })
+            .collect::<Vec<_>>();
+
+        div().flex().flex_col().py_1().children(rows)
+    }
+
+    /// The open submenus, each placed beside the item that opened it.
+    fn submenus(&self, window: &mut Window, cx: &mut App) -> Vec<AnyElement> {
+        let viewport = Bounds::new(point(px(0.), px(0.)), window.viewport_size());
+        let mut submenus = Vec::new();
+        for depth in 1..self.levels.len() {
+            let Some(index) = self.levels[depth - 1] else {
+                break;
+            };
+            let anchor = *self.anchor_state(depth - 1, index, window, cx).read(cx);
+            let panel_bounds = self.panel_state(depth, window, cx);
+            let height = panel_bounds.read(cx).size.height;
+            if height == Pixels::ZERO {
+                // Measure the panel before placing it.
+                window.request_animation_frame();
+            }
+            let left = desired_submenu_left(anchor, self.width, self.direction, viewport);
+            let top = desired_submenu_top(anchor, height, viewport);
+
+            let panel = div()
+                .id((self.id.clone(), format!("ui:menu:submenu:{depth}")))
+                .w(self.width)
+                .rounded_md()
+                .border_1()
+                .border_color(self.theme.border.default)
+                .bg(self.theme.surface.raised)
+                .shadow_md()
+                .occlude()
+                .when(height == Pixels::ZERO, |this| this.opacity(0.))
+                .child(self.panel(depth, window, cx));
+            submenus.push(
+                anchored()
+                    .position(point(left, top - px(4.)))
+                    .child(BoundsTrackerElement {
+                        bounds_state: panel_bounds,
+                        inner: panel.into_any_element(),
+                    })
+                    .into_any_element(),
+            );
+        }
+        submenus
+    }
+
+    /// Whether `position` is over one of the open submenus.
+    fn over_submenu(
+        &self,
+        position: gpui::Point<Pixels>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> bool {
+        (1..self.levels.len()).any(|depth| {
+            self.panel_state(depth, window, cx)
+                .read(cx)
+                .contains(&position)
+        })
+    }
+}
+
+impl RenderOnce for Menu {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let id = self.element_id;
+        let nav =
+            window.use_keyed_state((id.clone(), "ui:menu:nav"), cx, |_, _| MenuNav::default());
+        let focus: FocusHandle = window
+            .use_keyed_state((id.clone(), "ui:menu:focus"), cx, |_, cx| cx.focus_handle())
+            .read(cx)
+            .clone();
+        let levels = nav.read(cx).levels.clone();
+        let open = !levels.is_empty();
+
+        let menu = MenuContext {
+            id: id.clone(),
+            items: Rc::new(self.items),
+            nav: nav.clone(),
+            levels,
+            on_select: self.on_select,
+            theme: cx.theme().clone(),
+            direction: cx.text_direction(),
+            width: self.width,
+        };
+
+        let trigger = div()
+            .id((id.clone(), "ui:menu:trigger"))
+            .on_click({
+                let nav = nav.clone();
+                let focus = focus.clone();
+                move |_ev, window, cx| {
+                    let opening = nav.update(cx, |nav, _| {
+                        if nav.is_open() {
+                            nav.close();
+                        } else {
+                            nav.open();
+                        }
+                        nav.is_open()
+                    });
+                    if opening {
+                        window.focus(&focus);
+                    }
+                    window.refresh();
+                }
+            })
+            .children(self.trigger);
+
+        let content = open.then(|| {
+            let on_key = {
+                let menu = menu.clone();
+                move |ev: &KeyDownEvent, window: &mut Window, cx: &mut App| {
+                    let key = ev.keystroke.key.as_str();
+                    let rtl = menu.direction == TextDirection::Rtl;
+                    let (into, out_of) = if rtl {
+                        ("left", "right")
+                    } else {
+                        ("right", "left")
+                    };
+                    let items = &menu.items;
+                    match key {
+                        "up" => menu.nav.update(cx, |nav, _| nav.step(items, -1)),
+                        "down" => menu.nav.update(cx, |nav, _| nav.step(items, 1)),
+                        "home" => menu.nav.update(cx, |nav, _| nav.jump(items, false)),
+                        "end" => menu.nav.update(cx, |nav, _| nav.jump(items, true)),
+                        "enter" | "space" => menu.activate(window, cx),
+                        "escape" => menu.nav.update(cx, |nav, _| {
+                            if !nav.collapse() {
+                                nav.close();
+                            }
+                        }),
+                        key if key == into => {
+                            menu.nav.update(cx, |nav, _| nav.expand(items));
+                        }
+                        key if key == out_of => {
+                            menu.nav.update(cx, |nav, _| nav.collapse());
+                        }
+                        _ => {
+                            let modifiers = &ev.keystroke.modifiers;
+                            let typed = ev
+                                .keystroke
+                                .key_char
+                                .as_deref()
+                                .filter(|_| !modifiers.control && !modifiers.alt)
+                                .filter(|_| !modifiers.platform)
+                                .and_then(|text| {
+                                    let mut chars = text.chars();
+                                    chars.next().filter(|_| chars.next().is_none())
+                                })
+                                .filter(|ch| !ch.is_control());
+                            let Some(ch) = typed else {
+                                return;
+                            };
+                            menu.nav
+                                .update(cx, |nav, _| nav.type_char(items, ch, Instant::now()));
+                        }
+                    }
+                    cx.stop_propagation();
+                    window.refresh();
+                }
+            };
+
+            let submenus = menu.submenus(window, cx);
+            menu.panel(0, window, cx)
+                .id((id.clone(), "ui:menu:panel"))
+                .track_focus(&focus)
+                .on_key_down(on_key)
+                .when(!submenus.is_empty(), |this| {
+                    this.child(deferred(div().children(submenus)).with_priority(101))
+                })
+        });
+
+        let on_close = {
+            let menu = menu.clone();
+            move |window: &mut Window, cx: &mut App| {
+                // Clicks in a submenu land outside the root panel.
+                if menu.over_submenu(window.mouse_position(), window, cx) {
+                    return;
+                }
+                menu.nav.update(cx, |nav, _| nav.close());
+                window.refresh();
+            }
+        };
+
+        self.base.child(
+            popover((id.clone(), "ui:menu:popover"))
+                .open(open)
+                .placement(self.placement)
+                .width(self.width)
+                .on_close(on_close)
+                .trigger(trigger)
+                .when_some(content, |this, content| this.content(content)),
+        )
+    }
+}
+
+/// The items of the panel at `depth`, following the highlighted items with
+/// open submenus.
+fn panel_items<'a>(
+    items: &'a [MenuItem],
+    levels: &[Option<usize>],
+    depth: usize,
+) -> &'a [MenuItem] {
+    let mut items = items;
+    for index in levels.iter().take(depth) {
+        match index.and_then(|index| items.get(index)) {
+            Some(item) => items = &item.submenu,
+            None => return &[],
+        }
+    }
+    items
+}
+
+/// Keyboard and hover state of an open menu.
+#[derive(Clone, Debug, Default)]
+struct MenuNav {
+    /// The highlighted item of each open panel, outermost first. Empty while
+    /// the menu is closed.
+    levels: Vec<Option<usize>>,
+    typed: String,
+    typed_at: Option<Instant>,
+}
+
+impl MenuNav {
+    fn is_open(&self) -> bool {
+        !self.levels.is_empty()
+    }
+
+    fn open(&mut self) {
+        self.levels = vec![None];
+    }
+
+    fn close(&mut self) {
+        self.levels.clear();
+        self.typed.clear();
+    }
+
+    fn depth(&self) -> usize {
+        self.levels.len().saturating_sub(1)
+    }
+
+    fn highlighted<'a>(&self, items: &'a [MenuItem]) -> Option<&'a MenuItem> {
+        let index = (*self.levels.last()?)?;
+        panel_items(items, &self.levels, self.depth()).get(index)
+    }
+
+    fn set_highlight(&mut self, index: Option<usize>) {
+        if let Some(last) = self.levels.last_mut() {
+            *last = index;
+        }
+    }
+
+    /// Moves the highlight of the innermost panel by `delta` enabled items, wrapping.
+    fn step(&mut self, items: &[MenuItem], delta: isize) {
+        let panel = panel_items(items, &self.levels, self.depth());
+        let len = panel.len() as isize;
+        if len == 0 {
+            return;
+        }
+        let mut index = match self.levels.last().copied().flatten() {
+            Some(index) => index as isize,
+            None if delta > 0 => -1,
+            None => len,
+        };
+        for _ in 0..len {
+            index = (index + delta.signum()).rem_euclid(len);
+            if panel[index as usize].is_enabled() {
+                self.set_highlight(Some(index as usize));
+                return;
+            }
+        }
+    }
+
+    /// Highlights the first or last enabled item of the innermost panel.
+    fn jump(&mut self, items: &[MenuItem], last: bool) {
+        self.set_highlight(None);
+        self.step(items, if last { -1 } else { 1 });
+    }
+
+    /// Opens the submenu of the highlighted item and highlights its first item.
+    fn expand(&mut self, items: &[MenuItem]) {
+        if self.highlighted(items).is_some_and(MenuItem::has_submenu) {
+            self.levels.push(None);
+            self.step(items, 1);
+        }
+    }
+
+    /// Closes the innermost submenu. Returns `false` if no submenu is open.
+    fn collapse(&mut self) -> bool {
+        if self.levels.len() > 1 {
+            self.levels.pop();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Highlights the item under the pointer, opening its submenu.
+    fn hover(&mut self, items: &[MenuItem], depth: usize, index: usize) {
+        if self.levels.len() <= depth || self.levels.get(depth) == Some(&Some(index)) {
+            return;
+        }
+        self.levels.truncate(depth + 1);
+        self.levels[depth] = Some(index);
+        if self.highlighted(items).is_some_and(MenuItem::has_submenu) {
+            self.levels.push(None);
+        }
+    }
+
+    /// Highlights the next item of the innermost panel whose label starts with
+    /// the characters typed so far.
+    fn type_char(&mut self, items: &[MenuItem], ch: char, now: Instant) {
+        if self
+            .typed_at
+            .is_none_or(|at| now.duration_since(at) > TYPEAHEAD_TIMEOUT)
+        {
+            self.typed.clear();
+        }
+        self.typed.extend(ch.to_lowercase());
+        self.typed_at = Some(now);
+
+        let panel = panel_items(items, &self.levels, self.depth());
+        let current = self.levels.last().copied().flatten();
+        // A new search starts after the highlighted item; a longer one may stay on it.
+        let start = match current {
+            Some(index) if self.typed.chars().count() == 1 => index + 1,
+            Some(index) => index,
+            None => 0,
+        };
+        let found = (0..panel.len())
+            .map(|offset| (start + offset) % panel.len())
+            .find(|&index| {
+                let item = &panel[index];
+                item.is_enabled() && item.label.to_lowercase().starts_with(&self.typed)
+            });
+        if found.is_some() {
+            self.set_highlight(found);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn items() -> Vec<MenuItem> {
+        vec![
+            MenuItem::new("new", "New"),
+            MenuItem::separator(),
+            MenuItem::new("open", "Open"),
+            MenuItem::new("print", "Print").disabled(true),
+            MenuItem::new("recent", "Recent")
+                .submenu([MenuItem::new("a", "a.txt"), MenuItem::new("b", "b.txt")]),
+        ]
+    }
+
+    #[test]
+    fn arrows_skip_separators_and_disabled_items() {
+        let items = items();
+        let mut nav = MenuNav::default();
+        nav.open();
+        nav.step(&items, 1);
+        assert_eq!(nav.levels, vec![Some(0)]);
+        nav.step(&items, 1);
+        assert_eq!(nav.levels, vec![Some(2)]);
+        nav.step(&items, 1);
+        assert_eq!(nav.levels, vec![Some(4)]);
+        nav.step(&items, 1);
+        assert_eq!(nav.levels, vec![Some(0)]);
+        nav.jump(&items, true);
+        assert_eq!(nav.levels, vec![Some(4)]);
+    }
+
+    #[test]
+    fn submenus_open_and_close() {
+        let items = items();
+        let mut nav = MenuNav::default();
+        nav.open();
+        nav.jump(&items, true);
+        nav.expand(&items);
+        assert_eq!(nav.levels, vec![Some(4), Some(0)]);
+        assert_eq!(nav.highlighted(&items).unwrap().id, "a");
+        assert!(nav.collapse());
+        assert!(!nav.collapse());
+
+        nav.hover(&items, 0, 2);
+        assert_eq!(nav.levels, vec![Some(2)]);
+        nav.hover(&items, 0, 4);
+        assert_eq!(nav.levels, vec![Some(4), None]);
+        nav.hover(&items, 0, 0);
+        assert_eq!(nav.levels, vec![Some(0)]);
+    }
+
+    #[test]
+    fn typing_jumps_to_matching_labels() {
+        let items = items();
+        let start = Instant::now();
+        let mut nav = MenuNav::default();
+        nav.open();
+        nav.type_char(&items, 'o', start);
+        assert_eq!(nav.levels, vec![Some(2)]);
+        // "Print" is disabled, so "p" finds nothing and the highlight stays.
+        nav.type_char(&items, 'p', start + TYPEAHEAD_TIMEOUT * 2);
+        assert_eq!(nav.levels, vec![Some(2)]);
+        nav.type_char(&items, 'R', start + TYPEAHEAD_TIMEOUT * 4);
+        nav.type_char(&items, 'e', start + TYPEAHEAD_TIMEOUT * 4);
+        assert_eq!(nav.highlighted(&items).unwrap().id, "recent");
+    }
+}
@@ -1,3 +1,4 @@
+mod accordion;
 mod avatar;
 mod badge;
 mod bounds_tracker;
@@ -8,23 +9,31 @@ mod checkbox;
 mod clickable_surface;
 mod combo_box;
 mod context_menu_trigger;
+mod copy_button;
+mod date_picker;
 mod disclosure;
 mod divider;
 mod drag_handle;
+mod drawer;
 mod dropdown_menu;
 mod empty_state;
 mod file_path_input;
 mod focus_ring;
 mod form;
+mod form_state;
 mod heading;
+mod hover_card;
 mod icon;
 mod icon_button;
 mod image;
 mod input;
+mod input_mask;
+mod input_registry;
 mod keybinding_display;
 mod keybinding_input;
 mod label;
 mod list_item;
+mod menu;
 mod modal;
 mod number_input;
 mod password_input;
@@ -32,6 +41,7 @@ mod popover;
 mod progress;
 mod radio;
 mod radio_group;
+mod scroll_area;
 mod search_input;
 mod select;
 mod shortcut_hint;
@@ -39,12 +49,17 @@ mod skeleton;
 mod slider;
 mod spacer;
 mod split_button;
+mod split_pane;
 mod switch;
+mod table;
+mod tabs;
 mod tag;
+mod tag_input;
 mod text;
 mod text_area;
 mod text_edit_state;
 mod text_input;
+mod theme_provider;
 mod toast;
 mod toggle_button;
 mod tooltip;
@@ -60,6 +75,7 @@ pub mod helpers;
 pub use callback::*;
 pub use helpers::*;
 
+pub use accordion::*;
 pub use avatar::*;
 pub use badge::*;
 
@@ -71,29 +87,39 @@ pub use checkbox::*;
 pub use clickable_surface::*;
 pub use combo_box::*;
 pub use context_menu_trigger::*;
+pub use copy_button::*;
+pub use date_picker::*;
 pub use disclosure::*;
 pub use divider::*;
 pub use drag_handle::*;
+pub use drawer::*;
 pub use dropdown_menu::*;
 pub use empty_state::*;
 pub use file_path_input::*;
 pub use focus_ring::*;
 pub use form::*;
+pub use form_state::*;
 pub use heading::*;
+pub use hover_card::*;
 pub use icon::*;
 pub use icon_button::*;
 pub use image::*;
+pub use input_mask::InputMask;
+pub use input_registry::{focus_input, input_content, set_input_content};
+pub(crate) use input_registry::{register_input, registered_state};
 pub use keybinding_display::*;
 pub use keybinding_input::*;
 pub use label::*;
 pub use list_item::*;
+pub use menu::*;
 pub use modal::*;
-pub use number_input::*;
+pub use number_input::{NumberInput, number_input};
 pub use password_input::{PasswordInput, PasswordInputState, password_input};
 pub use popover::*;
 pub use progress::*;
 pub use radio::*;
 pub use radio_group::*;
+pub use scroll_area::*;
 pub use search_input::{SearchInput, search_input};
 pub use select::*;
 pub use shortcut_hint::*;
@@ -101,12 +127,17 @@ pub use skeleton::*;
 pub use slider::*;
 pub use spacer::*;
 pub use split_button::*;
+pub use split_pane::*;
 pub use switch::*;
+pub use table::*;
+pub use tabs::*;
 pub use tag::*;
+pub use tag_input::*;
 pub use text::*;
-pub use text_area::{EnterBehavior, TextArea, TextAreaState, WrapMode, text_area};
+pub use text_area::{EnterBehavior, TabBehavior, TextArea, TextAreaState, WrapMode, text_area};
 pub use text_edit_state::*;
 pub use text_input::{TextInput, TextInputState, text_input};
+pub use theme_provider::*;
 pub use toast::*;
 pub use toggle_button::*;
 pub use tooltip::*;
@@ -121,4 +152,11 @@ pub fn init(cx: &mut gpui::App) {
     text_input::init(cx);
     text_area::init(cx);
     password_input::init(cx);
+    number_input::init(cx);
+    combo_box::init(cx);
+    tree::init(cx);
+    scroll_area::init(cx);
+    crate::notification::host::init(cx);
+    crate::a11y::init(cx);
+    crate::animation::detect_system_reduce_motion();
 }
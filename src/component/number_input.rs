@@ -1,15 +1,33 @@
+use std::rc::Rc;
 use std::sync::Arc;
 
 use gpui::{
-    ClickEvent, Div, ElementId, Hsla, InteractiveElement, IntoElement, ParentElement, RenderOnce,
-    SharedString, StatefulInteractiveElement, Styled, div, px,
+    App, ClickEvent, Div, ElementId, Focusable, Hsla, InteractiveElement, IntoElement, KeyBinding,
+    ParentElement, RenderOnce, ScrollWheelEvent, SharedString, StatefulInteractiveElement, Styled,
+    Window, actions, div, px,
 };
 
 use crate::{
-    component::{button, compute_input_style, text_input},
-    theme::{ActionVariantKind, ActiveTheme},
+    component::{
+        ArrowDirection, IconName, TextInputState, compute_input_style, icon, icon_button,
+        text_input,
+    },
+    i18n::{I18nContext, I18nFormatter, NumberFormatOptions},
+    theme::ActiveTheme,
 };
 
+actions!(ui_number_input, [Increment, Decrement]);
+
+pub(crate) fn init(cx: &mut App) {
+    cx.bind_keys([
+        KeyBinding::new("up", Increment, Some("UINumberInput")),
+        KeyBinding::new("down", Decrement, Some("UINumberInput")),
+    ]);
+}
+
+/// Scroll distance that counts as one wheel notch.
+const WHEEL_STEP: f32 = 20.;
+
 /// Creates a new number input element.
 /// Requires an id to be set via `.id()` for internal state management.
 pub fn number_input(id: impl Into<ElementId>) -> NumberInput {
@@ -18,6 +36,7 @@ pub fn number_input(id: impl Into<ElementId>) -> NumberInput {
 
 type ChangeFn = Arc<dyn Fn(f64, &mut gpui::Window, &mut gpui::App)>;
 type ValidateFn = Arc<dyn Fn(&str) -> bool>;
+type StepFn = Rc<dyn Fn(f64, &mut Window, &mut App)>;
 
 #[derive(IntoElement)]
 pub struct NumberInput {
@@ -28,6 +47,7 @@ pub struct NumberInput {
     min: Option<f64>,
     max: Option<f64>,
     step: f64,
+    precision: Option<usize>,
 
     placeholder: SharedString,
     disabled: bool,
@@ -57,6 +77,7 @@ impl NumberInput {
             min: None,
             max: None,
             step: 1.0,
+            precision: None,
             placeholder: "0".into(),
             disabled: false,
             bg: None,
@@ -100,6 +121,12 @@ impl NumberInput {
         self
     }
 
+    /// Number of decimal places values are rounded to and displayed with.
+    pub fn precision(mut self, precision: usize) -> Self {
+        self.precision = Some(precision);
+        self
+    }
+
     pub fn placeholder(mut self, placeholder: impl Into<SharedString>) -> Self {
         self.placeholder = placeholder.into();
         self
@@ -119,7 +146,8 @@ impl NumberInput {
     }
 
     /// Sets a custom validation function.
-    /// The function receives the raw input string and returns true if valid.
+    /// The function receives the typed text (always with a `.` decimal point)
+    /// and returns true if valid.
     /// Example: `.validate(|s| !s.contains('-'))` to disallow negative numbers.
     pub fn validate<F>(mut self, validator: F) -> Self
     where
@@ -175,6 +203,16 @@ impl InteractiveElement for NumberInput {
 
 impl StatefulInteractiveElement for NumberInput {}
 
+/// Focus and display bookkeeping kept across renders.
+#[derive(Default)]
+struct EditState {
+    focused: bool,
+    /// The value the field's text was last written from.
+    shown: Option<f64>,
+    /// Scroll distance not yet turned into a step.
+    wheel: f32,
+}
+
 impl RenderOnce for NumberInput {
     fn render(self, window: &mut gpui::Window, cx: &mut gpui::App) -> impl IntoElement {
         let id = self.element_id;
@@ -183,6 +221,7 @@ impl RenderOnce for NumberInput {
         let step = self.step;
         let min = self.min;
         let max = self.max;
+        let precision = self.precision;
         let on_change = self.on_change;
         let validate = self.validate;
 
@@ -219,14 +258,19 @@ impl RenderOnce for NumberInput {
             self.value.unwrap_or(0.0)
         };
 
-        let value_state = clamp_f64(value_state, min, max);
-        let _text = SharedString::from(format_number(value_state));
+        let value_state = normalize(value_state, min, max, precision);
 
         let set_value = {
             let internal_value = internal_value.clone();
             let on_change = on_change.clone();
             move |next: f64, window: &mut gpui::Window, cx: &mut gpui::App| {
-                let next = clamp_f64(next, min, max);
+                let next = normalize(next, min, max, precision);
+                let current = internal_value
+                    .as_ref()
+                    .map_or(value_state, |value| *value.read(cx));
+                if next == current {
+                    return;
+                }
                 if let Some(internal_value) = &internal_value {
                     internal_value.update(cx, |state, cx| {
                         *state = next;
@@ -239,123 +283,160 @@ impl RenderOnce for NumberInput {
             }
         };
 
-        let sanitize = move |raw: &str| -> Option<f64> {
+        let accepts = move |raw: &str| -> Option<f64> {
             // If custom validator is set, check it first
             if let Some(ref validator) = validate
                 && !validator(raw)
             {
                 return None;
             }
-            raw.parse::<f64>().ok()
+            parse_number(raw)
         };
 
-        // Keep the input "controlled": always reflect the current numeric value.
-        // This prevents non-numeric characters from staying visible in the text field.
-        let controlled_text = SharedString::from(format_number(value_state));
+        let input_id: ElementId = format!("{}:input", id).into();
+        let input_state =
+            window.use_keyed_state(input_id.clone(), cx, |_, cx| TextInputState::new(cx));
+        let edit = window.use_keyed_state((id.clone(), "ui:number-input:edit"), cx, |_, _| {
+            EditState::default()
+        });
+
+        let focused = input_state.read(cx).focus_handle(cx).is_focused(window);
+        let was_focused = edit.read(cx).focused;
+        let buffer = input_state.read(cx).content().clone();
+
+        // Half-typed text like "-" or "1e" is left alone while editing; leaving
+        // the field commits what parses (clamped) and reverts the rest.
+        let mut value_state = value_state;
+        if was_focused
+            && !focused
+            && let Some(parsed) = accepts(buffer.as_ref())
+        {
+            set_value(parsed, window, cx);
+            value_state = normalize(parsed, min, max, precision);
+        }
+
+        // While focused the field holds plain "." decimals so it stays editable;
+        // otherwise it shows the value formatted for the active locale.
+        let value_changed = edit.read(cx).shown != Some(value_state);
+        let buffer_matches = parse_number(buffer.as_ref()) == Some(value_state);
+        if focused != was_focused || (value_changed && !(focused && buffer_matches)) {
+            let text = if focused {
+                canonical_text(value_state, precision)
+            } else {
+                display_text(value_state, precision, cx)
+            };
+            input_state.update(cx, |state, cx| {
+                state.set_content(text);
+                cx.notify();
+            });
+        }
+        edit.update(cx, |edit, _cx| {
+            edit.focused = focused;
+            edit.shown = Some(value_state);
+        });
+
+        let step_by: StepFn = Rc::new({
+            let internal_value = internal_value.clone();
+            let set_value = set_value.clone();
+            move |steps, window, cx| {
+                if disabled {
+                    return;
+                }
+                let current = internal_value
+                    .as_ref()
+                    .map_or(value_state, |value| *value.read(cx));
+                set_value(current + steps * step, window, cx);
+            }
+        });
+
+        let at_min = min.is_some_and(|min| value_state <= min);
+        let at_max = max.is_some_and(|max| value_state >= max);
+        let stepper = |direction: ArrowDirection, key: &'static str, steps: f64, blocked: bool| {
+            let step_by = step_by.clone();
+            let input_state = input_state.clone();
+            icon_button((id.clone(), key))
+                .icon(icon(IconName::Arrow(direction)))
+                .icon_size(px(10.))
+                .w(px(20.))
+                .h(px(14.))
+                .rounded_sm()
+                .bg(theme.action.neutral.bg.alpha(0.0))
+                .hover_bg(theme.action.neutral.hover_bg)
+                .disabled(disabled || blocked)
+                .on_click(move |_ev: &ClickEvent, window, cx| {
+                    step_by(steps, window, cx);
+                    window.focus(&input_state.read(cx).focus_handle(cx));
+                })
+        };
+        let steppers = div()
+            .flex()
+            .flex_col()
+            .pr(px(2.))
+            .child(stepper(
+                ArrowDirection::Up,
+                "ui:number-input:increment",
+                1.0,
+                at_max,
+            ))
+            .child(stepper(
+                ArrowDirection::Down,
+                "ui:number-input:decrement",
+                -1.0,
+                at_min,
+            ));
 
         self.base
             .id(id.clone())
+            .key_context("UINumberInput")
             .h(height)
             .w_full()
-            .flex()
-            .items_center()
-            .gap_2()
-            .child(
-                div().flex_1().min_w(px(0.)).child(
-                    text_input(format!("{}:input", id))
-                        .placeholder(self.placeholder)
-                        .disabled(disabled)
-                        .height(height)
-                        .bg(input_style.bg)
-                        .border(input_style.border)
-                        .focus_border(input_style.focus_border)
-                        .text_color(input_style.text_color)
-                        .content(controlled_text)
-                        .on_change({
-                            let set_value = set_value.clone();
-                            move |value, window, cx| {
-                                if let Some(parsed) = sanitize(value.as_ref()) {
-                                    set_value(parsed, window, cx);
-                                }
-                            }
-                        }),
-                ),
-            )
+            .on_action({
+                let step_by = step_by.clone();
+                move |_: &Increment, window, cx| step_by(1.0, window, cx)
+            })
+            .on_action({
+                let step_by = step_by.clone();
+                move |_: &Decrement, window, cx| step_by(-1.0, window, cx)
+            })
+            .on_scroll_wheel({
+                let edit = edit.clone();
+                let step_by = step_by.clone();
+                // Only a focused field reacts, so scrolling past it doesn't change it.
+                move |event: &ScrollWheelEvent, window, cx| {
+                    if !focused || disabled {
+                        return;
+                    }
+                    cx.stop_propagation();
+                    let delta = f32::from(event.delta.pixel_delta(px(WHEEL_STEP)).y);
+                    let (steps, rest) = wheel_steps(edit.read(cx).wheel, delta);
+                    edit.update(cx, |edit, _cx| edit.wheel = rest);
+                    if steps != 0 {
+                        step_by(steps as f64, window, cx);
+                    }
+                }
+            })
             .child(
-                div()
-                    .flex()
-                    .items_center()
-                    .gap_1()
-                    .child(
-                        button(format!("{}:decrement", id))
-                            .h(px(36.))
-                            .px_3()
-                            .rounded_md()
-                            .variant(ActionVariantKind::Neutral)
-                            .disabled(disabled)
-                            .child("-")
-                            .on_click({
-                                let internal_value = internal_value.clone();
-                                let on_change = on_change.clone();
-                                move |_ev: &ClickEvent, window, cx| {
-                                    let current = if use_internal_value {
-                                        internal_value
-                                            .as_ref()
-                                            .expect("internal value should exist")
-                                            .read(cx)
-                                            .to_owned()
-                                    } else {
-                                        value_state
-                                    };
-
-                                    let next = clamp_f64(current - step, min, max);
-                                    if let Some(internal_value) = &internal_value {
-                                        internal_value.update(cx, |state, cx| {
-                                            *state = next;
-                                            cx.notify();
-                                        });
-                                    }
-                                    if let Some(handler) = &on_change {
-                                        handler(next, window, cx);
-                                    }
-                                }
-                            }),
-                    )
-                    .child(
-                        button(format!("{}:increment", id))
-                            .h(px(36.))
-                            .px_3()
-                            .rounded_md()
-                            .variant(ActionVariantKind::Neutral)
-                            .disabled(disabled)
-                            .child("+")
-                            .on_click({
-                                let internal_value = internal_value.clone();
-                                let on_change = on_change.clone();
-                                move |_ev: &ClickEvent, window, cx| {
-                                    let current = if use_internal_value {
-                                        internal_value
-                                            .as_ref()
-                                            .expect("internal value should exist")
-                                            .read(cx)
-                                            .to_owned()
-                                    } else {
-                                        value_state
-                                    };
-
-                                    let next = clamp_f64(current + step, min, max);
-                                    if let Some(internal_value) = &internal_value {
-                                        internal_value.update(cx, |state, cx| {
-                                            *state = next;
-                                            cx.notify();
-                                        });
-                                    }
-                                    if let Some(handler) = &on_change {
-                                        handler(next, window, cx);
-                                    }
-                                }
-                            }),
-                    ),
+                text_input(input_id)
+                    .placeholder(self.placeholder)
+                    .disabled(disabled)
+                    .height(height)
+                    .bg(input_style.bg)
+                    .border(input_style.border)
+                    .focus_border(input_style.focus_border)
+                    .text_color(input_style.text_color)
+                    .suffix(steppers)
+                    .on_change(move |value, window, cx| {
+                        // Only typing commits; rewrites of the display text don't.
+                        if !focused {
+                            return;
+                        }
+                        if let Some(parsed) = accepts(value.as_ref())
+                            && min.is_none_or(|min| parsed >= min)
+                            && max.is_none_or(|max| parsed <= max)
+                        {
+                            set_value(parsed, window, cx);
+                        }
+                    }),
             )
     }
 }
@@ -373,6 +454,62 @@ fn clamp_f64(value: f64, min: Option<f64>, max: Option<f64>) -> f64 {
     }
 }
 
+/// Rounds `value` to `precision` decimals, or drops float noise (`0.1 + 0.2`)
+/// when no precision is set.
+fn round_to_precision(value: f64, precision: Option<usize>) -> f64 {
+    let digits = precision.unwrap_or(10).min(15) as i32;
+    let scale = 10f64.powi(digits);
+    let rounded = (value * scale).round() / scale;
+    if rounded.is_finite() { rounded } else { value }
+}
+
+fn normalize(value: f64, min: Option<f64>, max: Option<f64>, precision: Option<usize>) -> f64 {
+    clamp_f64(round_to_precision(value, precision), min, max)
+}
+
+/// Parses the editable text, which always uses `.` as the decimal separator.
+fn parse_number(raw: &str) -> Option<f64> {
+    let raw = raw.trim();
+    if !raw
+        .chars()
+        .all(|c| c.is_ascii_digit() || matches!(c, '.' | '-' | '+'))
+    {
+        return None;
+    }
+    raw.parse::<f64>().ok().filter(|value| value.is_finite())
+}
+
+/// The editable form of `value`.
+fn canonical_text(value: f64, precision: Option<usize>) -> String {
+    match precision {
+        Some(precision) => format!("{value:.precision$}"),
+        None => format_number(value),
+    }
+}
+
+fn display_text(value: f64, precision: Option<usize>, cx: &App) -> String {
+    let formatter = cx.i18n().formatter();
+    match precision {
+        Some(precision) => formatter.number().format_with_options(
+            value,
+            &NumberFormatOptions {
+                min_fraction_digits: Some(precision),
+                max_fraction_digits: Some(precision),
+                ..NumberFormatOptions::default()
+            },
+        ),
+        None => formatter.format_number(value),
+    }
+}
+
+/// Adds `delta` pixels of scrolling to `pending` and returns the whole steps
+/// taken (scrolling up is positive) plus the leftover distance.
+fn wheel_steps(pending: f32, delta: f32) -> (i32, f32) {
+    let total = pending + delta;
+    let steps = (total / WHEEL_STEP).trunc();
+    (steps as i32, total - steps * WHEEL_STEP)
+}
+
 fn format_number(value: f64) -> String {
     if (value.fract()).abs() <= f64::EPSILON {
         format!("{}", value as i64)
@@ -380,3 +517,43 @@ fn format_number(value: f64) -> String {
         format!("{value}")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_accepts_only_plain_decimals() {
+        assert_eq!(parse_number(" 12.5 "), Some(12.5));
+        assert_eq!(parse_number("-3"), Some(-3.0));
+        assert_eq!(parse_number("1."), Some(1.0));
+        assert_eq!(parse_number("-"), None);
+        assert_eq!(parse_number(""), None);
+        assert_eq!(parse_number("1,5"), None);
+        assert_eq!(parse_number("1e3"), None);
+        assert_eq!(parse_number("inf"), None);
+    }
+
+    #[test]
+    fn normalize_rounds_then_clamps() {
+        assert_eq!(normalize(0.1 + 0.2, None, None, None), 0.3);
+        assert_eq!(normalize(1.256, None, None, Some(2)), 1.26);
+        assert_eq!(normalize(12.0, Some(0.0), Some(10.0), Some(0)), 10.0);
+        assert_eq!(normalize(-0.4, Some(0.0), None, Some(0)), 0.0);
+    }
+
+    #[test]
+    fn canonical_text_uses_precision() {
+        assert_eq!(canonical_text(1.5, Some(2)), "1.50");
+        assert_eq!(canonical_text(1.5, None), "1.5");
+        assert_eq!(canonical_text(3.0, None), "3");
+    }
+
+    #[test]
+    fn wheel_steps_carry_the_remainder() {
+        assert_eq!(wheel_steps(0.0, 45.0), (2, 5.0));
+        assert_eq!(wheel_steps(5.0, 15.0), (1, 0.0));
+        assert_eq!(wheel_steps(0.0, -25.0), (-1, -5.0));
+        assert_eq!(wheel_steps(10.0, 5.0), (0, 15.0));
+    }
+}
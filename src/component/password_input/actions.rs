@@ -16,5 +16,7 @@ gpui::actions!(
         Paste,
         Cut,
         Copy,
+        Undo,
+        Redo,
     ]
 );
@@ -3,26 +3,38 @@
 use std::sync::Arc;
 
 use gpui::{
-    App, CursorStyle, Div, ElementId, Hsla, InteractiveElement, MouseButton, ParentElement,
+    App, CursorStyle, Div, ElementId, Entity, Hsla, InteractiveElement, MouseButton, ParentElement,
     RenderOnce, SharedString, StatefulInteractiveElement, Styled, div, prelude::FluentBuilder,
 };
 
 use super::actions::*;
 use super::element::PasswordLineElement;
-use super::state::{PasswordInputHandler, PasswordInputState};
+use super::state::{DEFAULT_MASK_CHAR, PasswordInputHandler, PasswordInputState};
 use crate::action_handler;
+use crate::component::{
+    DEFAULT_SELECTION_OPACITY, IconName, WindowCallback, generate_element_id, icon, icon_button,
+    notify_focus_change, register_input, registered_state, sync_controlled_value, tooltip,
+    warn_generated_id,
+};
+use crate::i18n::{I18nContext, defaults::DefaultPlaceholders};
 use crate::theme::ActiveTheme;
 
 #[derive(gpui::IntoElement)]
 pub struct PasswordInput {
     element_id: ElementId,
+    generated_id: bool,
     base: Div,
     placeholder: SharedString,
 
     disabled: bool,
+    read_only: bool,
+    selection_opacity: f32,
+    undo_limit: Option<usize>,
 
     allow_copy: bool,
     allow_cut: bool,
+    revealable: bool,
+    mask_char: char,
 
     bg: Option<Hsla>,
     border: Option<Hsla>,
@@ -30,32 +42,63 @@ pub struct PasswordInput {
     text_color: Option<Hsla>,
     height: Option<gpui::AbsoluteLength>,
 
+    value: Option<SharedString>,
     on_change: Option<PasswordInputHandler>,
+    on_focus: Option<WindowCallback>,
+    on_blur: Option<PasswordInputHandler>,
 }
 
 impl PasswordInput {
+    /// Creates a new PasswordInput without an explicit id.
+    ///
+    /// The id falls back to one derived from the caller's source location; prefer `.id()`.
+    #[track_caller]
     pub fn new() -> Self {
         Self {
-            element_id: "ui:password-input".into(),
+            element_id: generate_element_id("ui:password-input"),
+            generated_id: true,
             base: div().h(gpui::px(36.)).px_3(),
             placeholder: "".into(),
 
             disabled: false,
+            read_only: false,
+            selection_opacity: DEFAULT_SELECTION_OPACITY,
+            undo_limit: None,
 
             allow_copy: false,
             allow_cut: false,
+            revealable: false,
+            mask_char: DEFAULT_MASK_CHAR,
 
             bg: None,
             border: None,
             focus_border: None,
             text_color: None,
             height: None,
+            value: None,
             on_change: None,
+            on_focus: None,
+            on_blur: None,
         }
     }
 
+    /// The state of the password input with `id`, e.g. to focus it again after a
+    /// failed sign-in.
+    ///
+    /// Looked up like [`TextInput::state_handle`](crate::component::TextInput::state_handle).
+    pub fn state_handle(
+        id: impl Into<ElementId>,
+        window: &mut gpui::Window,
+        cx: &mut App,
+    ) -> Entity<PasswordInputState> {
+        let id = id.into();
+        registered_state(window, cx, &id)
+            .unwrap_or_else(|| window.use_keyed_state(id, cx, |_, cx| PasswordInputState::new(cx)))
+    }
+
     pub fn id(mut self, id: impl Into<ElementId>) -> Self {
         self.element_id = id.into();
+        self.generated_id = false;
         self
     }
 
@@ -74,6 +117,40 @@ impl PasswordInput {
         self
     }
 
+    /// Keeps the field focusable and selectable but rejects edits.
+    ///
+    /// The selection is drawn in a muted color while read-only.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Opacity of the selection fill (default: 0.25).
+    pub fn selection_opacity(mut self, opacity: f32) -> Self {
+        self.selection_opacity = opacity;
+        self
+    }
+
+    /// Maximum number of undo steps kept (default: 200).
+    pub fn undo_limit(mut self, limit: usize) -> Self {
+        self.undo_limit = Some(limit);
+        self
+    }
+
+    /// Controlled content; shorthand for `.value(Some(content))`.
+    pub fn content(mut self, content: impl Into<SharedString>) -> Self {
+        self.value = Some(content.into());
+        self
+    }
+
+    /// Controlled value. `Some("")` forces the field empty; `None` leaves it uncontrolled.
+    ///
+    /// The value is written into the field whenever it changes between renders.
+    pub fn value(mut self, value: Option<SharedString>) -> Self {
+        self.value = value;
+        self
+    }
+
     /// Allow copy action to write selected text into clipboard.
     ///
     /// Default: `false`.
@@ -90,6 +167,23 @@ impl PasswordInput {
         self
     }
 
+    /// Shows an eye button that toggles between the masked and plain text.
+    ///
+    /// The field starts masked each time it mounts. Copy and cut still follow
+    /// `allow_copy` / `allow_cut` while revealed. Default: `false`.
+    pub fn revealable(mut self, revealable: bool) -> Self {
+        self.revealable = revealable;
+        self
+    }
+
+    /// Character drawn for each typed character (default: `•`).
+    ///
+    /// Pick one the font covers, e.g. `*`, if the bullet renders as a missing glyph.
+    pub fn mask_char(mut self, mask_char: char) -> Self {
+        self.mask_char = mask_char;
+        self
+    }
+
     pub fn on_change<F>(mut self, handler: F) -> Self
     where
         F: 'static + Fn(SharedString, &mut gpui::Window, &mut App),
@@ -98,6 +192,24 @@ impl PasswordInput {
         self
     }
 
+    /// Called once each time the field gains focus.
+    pub fn on_focus<F>(mut self, handler: F) -> Self
+    where
+        F: 'static + Fn(&mut gpui::Window, &mut App),
+    {
+        self.on_focus = Some(Arc::new(handler));
+        self
+    }
+
+    /// Called once each time the field loses focus, with its current content.
+    pub fn on_blur<F>(mut self, handler: F) -> Self
+    where
+        F: 'static + Fn(SharedString, &mut gpui::Window, &mut App),
+    {
+        self.on_blur = Some(Arc::new(handler));
+        self
+    }
+
     pub fn bg(mut self, color: impl Into<Hsla>) -> Self {
         self.bg = Some(color.into());
         self
@@ -125,6 +237,7 @@ impl PasswordInput {
 }
 
 impl Default for PasswordInput {
+    #[track_caller]
     fn default() -> Self {
         Self::new()
     }
@@ -153,20 +266,53 @@ impl StatefulInteractiveElement for PasswordInput {}
 impl RenderOnce for PasswordInput {
     fn render(self, window: &mut gpui::Window, cx: &mut App) -> impl gpui::IntoElement {
         // PasswordInput requires an element ID for keyed state management.
-        // Use `.id()` to provide a stable ID, or a unique ID will be generated automatically.
+        // Use `.id()` to provide a stable ID, or one is derived from the call site.
         let id = self.element_id;
+        if self.generated_id {
+            warn_generated_id(window, cx, &id);
+        }
 
         let disabled = self.disabled;
+        let read_only = disabled || self.read_only;
+        let selection_opacity = self.selection_opacity;
         let allow_copy = self.allow_copy;
         let allow_cut = self.allow_cut;
 
         let state = window.use_keyed_state(id.clone(), cx, |_, cx| PasswordInputState::new(cx));
         let focus_handle = state.read(cx).focus_handle.clone();
         let placeholder = self.placeholder;
+        let undo_limit = self.undo_limit;
+        let revealable = self.revealable;
+        let mask_char = self.mask_char;
         state.update(cx, |state, _cx| {
             state.placeholder = placeholder;
+            state.mask_char = mask_char;
+            if !revealable {
+                state.revealed = false;
+            }
+            if let Some(limit) = undo_limit {
+                state.history.set_limit(limit);
+            }
         });
 
+        sync_controlled_value(
+            window,
+            cx,
+            &id,
+            self.value,
+            &state,
+            |state| state.content(),
+            |state, content| state.set_content(content),
+        );
+        register_input(
+            window,
+            cx,
+            &id,
+            &state,
+            |state| state.content(),
+            |state, content| state.set_content(content),
+        );
+
         let on_change = self.on_change;
         let last_content = window.use_keyed_state(
             (id.clone(), format!("{}:last-content", id)),
@@ -174,7 +320,31 @@ impl RenderOnce for PasswordInput {
             |_, _cx| SharedString::new_static(""),
         );
 
+        let on_focus = self.on_focus;
+        let on_blur = self.on_blur;
+        let was_focused =
+            window.use_keyed_state((id.clone(), "ui:password-input:focused"), cx, |_, _| false);
+        let content_of = {
+            let state = state.clone();
+            move |cx: &App| state.read(cx).content.clone()
+        };
+        notify_focus_change(
+            &was_focused,
+            focus_handle.is_focused(window),
+            &content_of,
+            on_focus.as_ref(),
+            on_blur.as_ref(),
+            window,
+            cx,
+        );
+
+        let revealed = state.read(cx).revealed;
+        let caps_lock_warning =
+            (!disabled && focus_handle.is_focused(window) && state.read(cx).caps_lock_on)
+                .then(|| DefaultPlaceholders::caps_lock_on(cx.i18n().locale()));
         let theme = cx.theme();
+        let reveal_button = theme.action.neutral.clone();
+        let warning_color = theme.status.warning.bg;
 
         let bg = if disabled {
             theme.surface.sunken
@@ -214,8 +384,8 @@ impl RenderOnce for PasswordInput {
             .when(!disabled, |this| this.cursor(CursorStyle::IBeam))
             .when(disabled, |this| this.cursor_not_allowed().opacity(0.6))
             .key_context("UIPasswordInput")
-            .on_action(action_handler!(state, disabled, Backspace, backspace))
-            .on_action(action_handler!(state, disabled, Delete, delete))
+            .on_action(action_handler!(state, read_only, Backspace, backspace))
+            .on_action(action_handler!(state, read_only, Delete, delete))
             .on_action(action_handler!(state, disabled, Left, left))
             .on_action(action_handler!(state, disabled, Right, right))
             .on_action(action_handler!(state, disabled, SelectLeft, select_left))
@@ -225,15 +395,17 @@ impl RenderOnce for PasswordInput {
             .on_action(action_handler!(state, disabled, End, end))
             .on_action(action_handler!(
                 state,
-                disabled,
+                read_only,
                 ShowCharacterPalette,
                 show_character_palette
             ))
-            .on_action(action_handler!(state, disabled, Paste, paste))
+            .on_action(action_handler!(state, read_only, Paste, paste))
+            .on_action(action_handler!(state, read_only, Undo, undo))
+            .on_action(action_handler!(state, read_only, Redo, redo))
             .on_action({
                 let state = state.clone();
                 move |action: &Cut, window, cx| {
-                    if disabled || !allow_cut {
+                    if read_only || !allow_cut {
                         return;
                     }
                     state.update(cx, |state, cx| state.cut(action, window, cx));
@@ -250,6 +422,8 @@ impl RenderOnce for PasswordInput {
             })
             .on_mouse_down(MouseButton::Left, {
                 let state = state.clone();
+                let was_focused = was_focused.clone();
+                let content_of = content_of.clone();
                 move |event, window, cx| {
                     if disabled {
                         return;
@@ -258,6 +432,15 @@ impl RenderOnce for PasswordInput {
                         state.focus_in(window, cx);
                         state.on_mouse_down(event, window, cx);
                     });
+                    notify_focus_change(
+                        &was_focused,
+                        true,
+                        &content_of,
+                        on_focus.as_ref(),
+                        None,
+                        window,
+                        cx,
+                    );
                 }
             })
             .on_mouse_up(MouseButton::Left, {
@@ -286,6 +469,20 @@ impl RenderOnce for PasswordInput {
                     }
                     state.update(cx, |state, cx| state.on_mouse_move(event, window, cx));
                 }
+            })
+            .on_key_down({
+                let state = state.clone();
+                move |event, window, cx| {
+                    state.update(cx, |state, cx| state.on_key_down(event, window, cx));
+                }
+            })
+            .on_modifiers_changed({
+                let state = state.clone();
+                move |event, _window, cx| {
+                    state.update(cx, |state, cx| {
+                        state.set_caps_lock_on(event.capslock.on, cx)
+                    });
+                }
             });
 
         base = base
@@ -301,15 +498,69 @@ impl RenderOnce for PasswordInput {
                         PasswordLineElement {
                             input: state.clone(),
                             disabled,
+                            read_only,
+                            selection_opacity,
                         },
-                    )),
+                    ))
+                    .when_some(caps_lock_warning, |this, warning| {
+                        this.child(
+                            div()
+                                .id((id.clone(), "ui:password-input:caps-lock"))
+                                .flex_none()
+                                .ml_1()
+                                .child(
+                                    icon(IconName::Warning)
+                                        .size(gpui::px(14.))
+                                        .color(warning_color),
+                                )
+                                .tooltip(tooltip(warning).build()),
+                        )
+                    })
+                    .when(revealable, |this| {
+                        this.child(
+                            div()
+                                .flex_none()
+                                .ml_1()
+                                // Don't let the press reach the field and move the cursor.
+                                .on_mouse_down(MouseButton::Left, |_, _, cx| cx.stop_propagation())
+                                .child(
+                                    icon_button((id.clone(), "ui:password-input:reveal"))
+                                        .icon(icon(IconName::Eye(!revealed)))
+                                        .icon_size(gpui::px(14.))
+                                        .w(gpui::px(24.))
+                                        .h(gpui::px(24.))
+                                        .rounded_md()
+                                        .disabled(disabled)
+                                        .bg(reveal_button.bg.alpha(0.0))
+                                        .hover_bg(reveal_button.hover_bg)
+                                        .on_click({
+                                            let state = state.clone();
+                                            move |_ev, _window, cx| {
+                                                state.update(cx, |state, cx| {
+                                                    state.revealed = !state.revealed;
+                                                    cx.notify();
+                                                });
+                                            }
+                                        }),
+                                ),
+                        )
+                    }),
             )
-            .on_mouse_down_out(move |_event, window, _cx| {
+            .on_mouse_down_out(move |_event, window, cx| {
                 if disabled {
                     return;
                 }
                 if focus_handle.is_focused(window) {
                     window.blur();
+                    notify_focus_change(
+                        &was_focused,
+                        false,
+                        &content_of,
+                        None,
+                        on_blur.as_ref(),
+                        window,
+                        cx,
+                    );
                 }
             });
 
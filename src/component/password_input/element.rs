@@ -6,15 +6,19 @@ use gpui::{
 };
 
 use super::state::PasswordInputState;
+use crate::component::{ShapeKey, selection_color};
 use crate::theme::ActiveTheme;
 
 pub struct PasswordLineElement {
     pub input: Entity<PasswordInputState>,
     pub disabled: bool,
+    pub read_only: bool,
+    pub selection_opacity: f32,
 }
 
 pub struct PrepaintState {
     line: Option<ShapedLine>,
+    shape_key: Option<ShapeKey>,
     cursor: Option<PaintQuad>,
     selection: Option<PaintQuad>,
     scroll_x: Pixels,
@@ -120,9 +124,20 @@ impl Element for PasswordLineElement {
         };
 
         let font_size = style.font_size.to_pixels(window.rem_size());
-        let line = window
-            .text_system()
-            .shape_line(display_text, font_size, &runs, None);
+        let shape_key = ShapeKey {
+            text: display_text.clone(),
+            runs: runs.clone(),
+            marked_range: marked_display_range,
+            font_size,
+            line_height: window.line_height(),
+            wrap_width: None,
+        };
+        let line = match &input.last_layout {
+            Some(line) if input.shape_key.as_ref() == Some(&shape_key) => line.clone(),
+            _ => window
+                .text_system()
+                .shape_line(display_text, font_size, &runs, None),
+        };
 
         let cursor_pos = line.x_for_index(cursor_display_index);
 
@@ -166,7 +181,7 @@ impl Element for PasswordLineElement {
                             bounds.bottom(),
                         ),
                     ),
-                    cx.theme().border.focus.alpha(0.25),
+                    selection_color(cx.theme(), self.selection_opacity, self.read_only),
                 )),
                 None,
             )
@@ -174,6 +189,7 @@ impl Element for PasswordLineElement {
 
         PrepaintState {
             line: Some(line),
+            shape_key: Some(shape_key),
             cursor,
             selection,
             scroll_x,
@@ -191,7 +207,7 @@ impl Element for PasswordLineElement {
         cx: &mut App,
     ) {
         let focus_handle = self.input.read(cx).focus_handle.clone();
-        if !self.disabled {
+        if !self.disabled && !self.read_only {
             window.handle_input(
                 &focus_handle,
                 ElementInputHandler::new(bounds, self.input.clone()),
@@ -221,6 +237,7 @@ impl Element for PasswordLineElement {
 
         self.input.update(cx, |input, _cx| {
             input.last_layout = Some(line);
+            input.shape_key = prepaint.shape_key.take();
             input.last_bounds = Some(bounds);
             input.scroll_x = prepaint.scroll_x;
         });
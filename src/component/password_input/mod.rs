@@ -32,6 +32,8 @@ pub(crate) fn init(cx: &mut App) {
         gpui::KeyBinding::new("secondary-v", Paste, Some("UIPasswordInput")),
         gpui::KeyBinding::new("secondary-c", Copy, Some("UIPasswordInput")),
         gpui::KeyBinding::new("secondary-x", Cut, Some("UIPasswordInput")),
+        gpui::KeyBinding::new("secondary-z", Undo, Some("UIPasswordInput")),
+        gpui::KeyBinding::new("secondary-shift-z", Redo, Some("UIPasswordInput")),
         gpui::KeyBinding::new("home", Home, Some("UIPasswordInput")),
         gpui::KeyBinding::new("end", End, Some("UIPasswordInput")),
         gpui::KeyBinding::new(
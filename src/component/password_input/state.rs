@@ -9,11 +9,13 @@ use gpui::{
 };
 use unicode_segmentation::UnicodeSegmentation;
 
+use crate::component::{EditHistory, EditSnapshot, ShapeKey, floor_char_boundary};
 use crate::constants::CURSOR_BLINK_INTERVAL;
 
 pub type PasswordInputHandler = Arc<dyn Fn(SharedString, &mut gpui::Window, &mut App)>;
 
-const MASK_CHAR: char = '•';
+/// Default character drawn in place of each grapheme of a password.
+pub const DEFAULT_MASK_CHAR: char = '•';
 
 pub struct PasswordInputState {
     pub focus_handle: FocusHandle,
@@ -22,7 +24,10 @@ pub struct PasswordInputState {
     pub selected_range: Range<usize>,
     pub selection_reversed: bool,
     pub marked_range: Option<Range<usize>>,
+    pub history: EditHistory,
     pub last_layout: Option<ShapedLine>,
+    /// What `last_layout` was shaped from.
+    pub(crate) shape_key: Option<ShapeKey>,
     pub last_bounds: Option<gpui::Bounds<gpui::Pixels>>,
     pub is_selecting: bool,
 
@@ -31,6 +36,11 @@ pub struct PasswordInputState {
 
     pub focus_subscription: Option<gpui::Subscription>,
     pub scroll_x: gpui::Pixels,
+    /// Shows the plain content instead of the mask. Starts hidden.
+    pub revealed: bool,
+    pub mask_char: char,
+    /// Last known Caps Lock state, refreshed on focus-in and keystrokes.
+    pub caps_lock_on: bool,
 }
 
 impl PasswordInputState {
@@ -42,7 +52,9 @@ impl PasswordInputState {
             selected_range: 0..0,
             selection_reversed: false,
             marked_range: None,
+            history: EditHistory::default(),
             last_layout: None,
+            shape_key: None,
             last_bounds: None,
             is_selecting: false,
 
@@ -51,6 +63,9 @@ impl PasswordInputState {
 
             focus_subscription: None,
             scroll_x: gpui::Pixels::ZERO,
+            revealed: false,
+            mask_char: DEFAULT_MASK_CHAR,
+            caps_lock_on: false,
         }
     }
 
@@ -66,6 +81,32 @@ impl PasswordInputState {
         self.selection_reversed = false;
         self.marked_range = None;
         self.scroll_x = gpui::Pixels::ZERO;
+        self.history.clear();
+    }
+
+    fn snapshot(&self) -> EditSnapshot {
+        EditSnapshot {
+            content: self.content.clone(),
+            selected_range: self.selected_range.clone(),
+            selection_reversed: self.selection_reversed,
+        }
+    }
+
+    fn restore(&mut self, snapshot: EditSnapshot) {
+        let len = snapshot.content.len();
+        self.content = snapshot.content;
+        self.selected_range =
+            snapshot.selected_range.start.min(len)..snapshot.selected_range.end.min(len);
+        self.selection_reversed = snapshot.selection_reversed;
+        self.marked_range = None;
+    }
+
+    /// Records an undo step unless an IME composition is already in progress.
+    fn record_edit(&mut self, range: Range<usize>, new_text: &str) {
+        if self.marked_range.is_some() || (range.is_empty() && new_text.is_empty()) {
+            return;
+        }
+        self.history.record(self.snapshot(), &range, new_text);
     }
 
     pub fn show_cursor(&mut self, cx: &mut Context<Self>) {
@@ -116,21 +157,80 @@ impl PasswordInputState {
             .detach();
     }
 
+    /// Focuses the field, e.g. to jump to the first invalid field of a form.
+    pub fn focus(&mut self, window: &mut gpui::Window, cx: &mut Context<Self>) {
+        self.focus_in(window, cx);
+        cx.notify();
+    }
+
+    /// Removes focus from the field if it has it.
+    pub fn blur(&mut self, window: &mut gpui::Window, cx: &mut Context<Self>) {
+        if self.focus_handle.is_focused(window) {
+            window.blur();
+            cx.notify();
+        }
+    }
+
+    /// Selects the byte `range` of the content, clamped to its bounds.
+    pub fn select_range(&mut self, range: Range<usize>, cx: &mut Context<Self>) {
+        let start = floor_char_boundary(&self.content, range.start);
+        let end = floor_char_boundary(&self.content, range.end).max(start);
+        self.selected_range = start..end;
+        self.selection_reversed = false;
+        cx.notify();
+    }
+
+    /// Selects the whole content.
+    ///
+    /// This is the programmatic `select_all`; that name belongs to the handler
+    /// of the `SelectAll` action (Cmd/Ctrl+A), which needs a window.
+    pub fn select_all_content(&mut self, cx: &mut Context<Self>) {
+        self.select_range(0..self.content.len(), cx);
+    }
+
     pub fn focus_in(&mut self, window: &mut gpui::Window, cx: &mut Context<Self>) {
         if self.focus_subscription.is_none() {
             let focus_handle = self.focus_handle.clone();
             let this = cx.entity().downgrade();
             let subscription = window.on_focus_in(&focus_handle, cx, move |window, cx| {
-                this.update(cx, |this, cx| this.reset_cursor_blink(window, cx))
-                    .ok();
+                this.update(cx, |this, cx| {
+                    this.caps_lock_on = window.capslock().on;
+                    this.reset_cursor_blink(window, cx)
+                })
+                .ok();
             });
             self.focus_subscription = Some(subscription);
         }
 
         window.focus(&self.focus_handle);
+        self.caps_lock_on = window.capslock().on;
         self.reset_cursor_blink(window, cx);
     }
 
+    /// Refreshes `caps_lock_on` from a keystroke.
+    ///
+    /// A typed letter whose case disagrees with Shift settles it; other keys fall
+    /// back to the platform's reported state.
+    pub fn on_key_down(
+        &mut self,
+        event: &gpui::KeyDownEvent,
+        window: &mut gpui::Window,
+        cx: &mut Context<Self>,
+    ) {
+        let keystroke = &event.keystroke;
+        let caps_lock_on =
+            infer_caps_lock(keystroke.key_char.as_deref(), keystroke.modifiers.shift)
+                .unwrap_or_else(|| window.capslock().on);
+        self.set_caps_lock_on(caps_lock_on, cx);
+    }
+
+    pub fn set_caps_lock_on(&mut self, on: bool, cx: &mut Context<Self>) {
+        if self.caps_lock_on != on {
+            self.caps_lock_on = on;
+            cx.notify();
+        }
+    }
+
     pub fn left(
         &mut self,
         _: &super::actions::Left,
@@ -309,6 +409,32 @@ impl PasswordInputState {
         }
     }
 
+    pub fn undo(
+        &mut self,
+        _: &super::actions::Undo,
+        window: &mut gpui::Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(snapshot) = self.history.undo(self.snapshot()) {
+            self.restore(snapshot);
+            self.reset_cursor_blink(window, cx);
+            cx.notify();
+        }
+    }
+
+    pub fn redo(
+        &mut self,
+        _: &super::actions::Redo,
+        window: &mut gpui::Window,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(snapshot) = self.history.redo(self.snapshot()) {
+            self.restore(snapshot);
+            self.reset_cursor_blink(window, cx);
+            cx.notify();
+        }
+    }
+
     pub fn move_to(&mut self, offset: usize, window: &mut gpui::Window, cx: &mut Context<Self>) {
         self.selected_range = offset..offset;
         self.selection_reversed = false;
@@ -413,41 +539,80 @@ impl PasswordInputState {
     }
 
     pub fn grapheme_index_for_content_offset(&self, offset: usize) -> usize {
-        let mut index = 0;
-        for (byte_index, _) in self.content.grapheme_indices(true) {
-            if byte_index >= offset {
-                break;
-            }
-            index += 1;
-        }
-        index
+        grapheme_index_for_offset(&self.content, offset)
     }
 
     pub fn content_offset_for_grapheme_index(&self, grapheme_index: usize) -> usize {
-        for (current, (byte_index, _)) in self.content.grapheme_indices(true).enumerate() {
-            if current == grapheme_index {
-                return byte_index;
-            }
-        }
-        self.content.len()
+        offset_for_grapheme_index(&self.content, grapheme_index)
+    }
+
+    /// The mask drawn over the content, or `None` while revealed.
+    fn mask(&self) -> Option<char> {
+        (!self.revealed).then_some(self.mask_char)
     }
 
     pub fn display_index_for_content_offset(&self, offset: usize) -> usize {
-        self.grapheme_index_for_content_offset(offset) * MASK_CHAR.len_utf8()
+        display_index_for_offset(&self.content, offset, self.mask())
     }
 
     pub fn content_offset_for_display_index(&self, display_offset: usize) -> usize {
-        let grapheme_index = display_offset / MASK_CHAR.len_utf8();
-        self.content_offset_for_grapheme_index(grapheme_index)
+        offset_for_display_index(&self.content, display_offset, self.mask())
     }
 
     pub fn display_text(&self) -> SharedString {
         if self.content.is_empty() {
             return self.placeholder.clone();
         }
+        match self.mask() {
+            Some(mask) => {
+                let grapheme_count = self.content.graphemes(true).count();
+                SharedString::from(mask.to_string().repeat(grapheme_count))
+            }
+            None => self.content.clone(),
+        }
+    }
+}
+
+/// Infers Caps Lock from a typed character and the Shift modifier.
+///
+/// Returns `None` unless `key_char` is a single cased letter.
+fn infer_caps_lock(key_char: Option<&str>, shift: bool) -> Option<bool> {
+    let mut chars = key_char?.chars();
+    let ch = chars.next()?;
+    if chars.next().is_some() || ch.is_uppercase() == ch.is_lowercase() {
+        return None;
+    }
+    Some(ch.is_uppercase() != shift)
+}
+
+fn grapheme_index_for_offset(content: &str, offset: usize) -> usize {
+    content
+        .grapheme_indices(true)
+        .take_while(|(byte_index, _)| *byte_index < offset)
+        .count()
+}
+
+fn offset_for_grapheme_index(content: &str, grapheme_index: usize) -> usize {
+    content
+        .grapheme_indices(true)
+        .nth(grapheme_index)
+        .map_or(content.len(), |(byte_index, _)| byte_index)
+}
 
-        let grapheme_count = self.content.graphemes(true).count();
-        SharedString::from(MASK_CHAR.to_string().repeat(grapheme_count))
+/// Maps a content offset to a byte offset in the displayed text, where each
+/// grapheme is drawn as one `mask` character (or as itself when `mask` is `None`).
+fn display_index_for_offset(content: &str, offset: usize, mask: Option<char>) -> usize {
+    match mask {
+        Some(mask) => grapheme_index_for_offset(content, offset) * mask.len_utf8(),
+        None => offset.min(content.len()),
+    }
+}
+
+/// Inverse of [`display_index_for_offset`]; offsets inside a mask character round down.
+fn offset_for_display_index(content: &str, display_offset: usize, mask: Option<char>) -> usize {
+    match mask {
+        Some(mask) => offset_for_grapheme_index(content, display_offset / mask.len_utf8()),
+        None => display_offset.min(content.len()),
     }
 }
 
@@ -515,6 +680,7 @@ impl gpui::EntityInputHandler for PasswordInputState {
 
         let range_start = range.start.min(self.content.len());
         let range_end = range.end.min(self.content.len()).max(range_start);
+        self.record_edit(range_start..range_end, new_text);
         self.content =
             (self.content[0..range_start].to_owned() + new_text + &self.content[range_end..])
                 .into();
@@ -541,6 +707,7 @@ impl gpui::EntityInputHandler for PasswordInputState {
 
         let range_start = range.start.min(self.content.len());
         let range_end = range.end.min(self.content.len()).max(range_start);
+        self.record_edit(range_start..range_end, new_text);
         self.content =
             (self.content[0..range_start].to_owned() + new_text + &self.content[range_end..])
                 .into();
@@ -607,3 +774,60 @@ impl Focusable for PasswordInputState {
         self.focus_handle.clone()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Graphemes `a`, `é` (2 bytes), `👍` (4 bytes) and `b`.
+    const CONTENT: &str = "aé👍b";
+    const BOUNDARIES: [usize; 5] = [0, 1, 3, 7, 8];
+
+    #[test]
+    fn mask_width_scales_display_offsets() {
+        for (mask, width) in [('*', 1), ('●', 3), (DEFAULT_MASK_CHAR, 3)] {
+            for (grapheme, offset) in BOUNDARIES.into_iter().enumerate() {
+                let display = display_index_for_offset(CONTENT, offset, Some(mask));
+                assert_eq!(display, grapheme * width, "mask {mask:?}");
+                assert_eq!(
+                    offset_for_display_index(CONTENT, display, Some(mask)),
+                    offset
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn hit_testing_inside_a_mask_glyph_rounds_down() {
+        // A display offset between bytes of `●` lands on the grapheme it starts.
+        assert_eq!(offset_for_display_index(CONTENT, 4, Some('●')), 1);
+        assert_eq!(offset_for_display_index(CONTENT, 8, Some('●')), 3);
+        assert_eq!(
+            offset_for_display_index(CONTENT, 99, Some('*')),
+            CONTENT.len()
+        );
+    }
+
+    #[test]
+    fn caps_lock_is_inferred_from_letter_case() {
+        assert_eq!(infer_caps_lock(Some("A"), false), Some(true));
+        assert_eq!(infer_caps_lock(Some("a"), true), Some(true));
+        assert_eq!(infer_caps_lock(Some("a"), false), Some(false));
+        assert_eq!(infer_caps_lock(Some("A"), true), Some(false));
+        assert_eq!(infer_caps_lock(Some("Ж"), false), Some(true));
+
+        assert_eq!(infer_caps_lock(Some("1"), false), None);
+        assert_eq!(infer_caps_lock(Some("中"), false), None);
+        assert_eq!(infer_caps_lock(Some("ab"), false), None);
+        assert_eq!(infer_caps_lock(None, false), None);
+    }
+
+    #[test]
+    fn revealed_text_maps_one_to_one() {
+        for offset in BOUNDARIES {
+            assert_eq!(display_index_for_offset(CONTENT, offset, None), offset);
+            assert_eq!(offset_for_display_index(CONTENT, offset, None), offset);
+        }
+        assert_eq!(offset_for_display_index(CONTENT, 99, None), CONTENT.len());
+    }
+}
@@ -1,31 +1,86 @@
+use std::rc::Rc;
+
 use gpui::prelude::FluentBuilder;
-use gpui::AppContext;
 use gpui::{
-    Animation, AnimationExt, ClickEvent, ElementId, Hsla, InteractiveElement, IntoElement,
-    Bounds, ParentElement, Pixels, RenderOnce, Styled, div, px,
+    Animation, AnimationExt, Bounds, ClickEvent, ElementId, Hsla, InteractiveElement, IntoElement,
+    ParentElement, Pixels, RenderOnce, Size, StatefulInteractiveElement, Styled, div, px,
 };
 
 use crate::{animation::constants::duration, theme::ActiveTheme};
-use crate::i18n::{I18n, TextDirection};
+use crate::i18n::TextDirection;
 use crate::component::BoundsTrackerElement;
 
+use crate::a11y::{cycle_focus, focus_trap};
 use crate::animation::ease_out_quint_clamped;
+use crate::component::tooltip::{TooltipPlacement, caret};
+
+/// Closest the caret gets to the popover's corners.
+const CARET_INSET: Pixels = px(16.);
 
 fn desired_menu_left(
     trigger_bounds: Bounds<Pixels>,
     menu_width: Pixels,
     direction: TextDirection,
-    window: &gpui::Window,
+    viewport: Size<Pixels>,
 ) -> Pixels {
     let desired_left = match direction {
         TextDirection::Ltr => trigger_bounds.left(),
         TextDirection::Rtl => trigger_bounds.right() - menu_width,
     };
 
-    let window_bounds = window.bounds();
-    let min_left = window_bounds.left();
-    let max_left = (window_bounds.right() - menu_width).max(min_left);
-    desired_left.clamp(min_left, max_left)
+    let max_left = (viewport.width - menu_width).max(Pixels::ZERO);
+    desired_left.clamp(Pixels::ZERO, max_left)
+}
+
+/// Whether a popover `menu_height` tall opens above `trigger`: it does not fit
+/// below it in `viewport` and there is more room above.
+fn opens_above(
+    trigger: Bounds<Pixels>,
+    menu_height: Pixels,
+    gap: Pixels,
+    viewport: Size<Pixels>,
+) -> bool {
+    let below = viewport.height - trigger.bottom() - gap;
+    let above = trigger.top() - gap;
+    menu_height > below && above > below
+}
+
+/// Left edge of a submenu opening beside `anchor`: after it in reading order,
+/// or before it when only that side fits in `viewport`.
+pub(crate) fn desired_submenu_left(
+    anchor: Bounds<Pixels>,
+    width: Pixels,
+    direction: TextDirection,
+    viewport: Bounds<Pixels>,
+) -> Pixels {
+    let (after, before) = match direction {
+        TextDirection::Ltr => (anchor.right(), anchor.left() - width),
+        TextDirection::Rtl => (anchor.left() - width, anchor.right()),
+    };
+    let fits = |left: Pixels| left >= viewport.left() && left + width <= viewport.right();
+    let left = if fits(after) || !fits(before) {
+        after
+    } else {
+        before
+    };
+
+    let max_left = (viewport.right() - width).max(viewport.left());
+    left.clamp(viewport.left(), max_left)
+}
+
+/// Top edge of a submenu beside `anchor`: level with its top, or with its
+/// bottom when the submenu would run past the bottom of `viewport`.
+pub(crate) fn desired_submenu_top(
+    anchor: Bounds<Pixels>,
+    height: Pixels,
+    viewport: Bounds<Pixels>,
+) -> Pixels {
+    let top = if anchor.top() + height > viewport.bottom() {
+        anchor.bottom() - height
+    } else {
+        anchor.top()
+    };
+    top.max(viewport.top())
 }
 
 /// Defines the placement position of a popover relative to its trigger element.
@@ -42,6 +97,11 @@ pub enum PopoverPlacement {
 /// Popovers display floating content relative to a trigger element. Use `.trigger()` to set
 /// the clickable element and `.content()` to set the popover body.
 ///
+/// The popover opens below its trigger; `.flip(true)` lets it open above when there
+/// is not enough room below. `.caret(true)` adds an arrow pointing at the trigger, and
+/// `.trap_focus(true)` keeps Tab inside the open popover and returns focus to the
+/// trigger when it closes.
+///
 /// # Example
 /// ```rust,ignore
 /// use gpui::px;
@@ -56,7 +116,8 @@ pub fn popover(id: impl Into<ElementId>) -> Popover {
     Popover::new(id)
 }
 
-type CloseFn = Box<dyn Fn(&mut gpui::Window, &mut gpui::App)>;
+type CloseFn = Rc<dyn Fn(&mut gpui::Window, &mut gpui::App)>;
+type HoverFn = Rc<dyn Fn(bool, &mut gpui::Window, &mut gpui::App)>;
 
 #[derive(IntoElement)]
 pub struct Popover {
@@ -72,7 +133,11 @@ pub struct Popover {
 
     bg: Option<Hsla>,
     border: Option<Hsla>,
+    caret: bool,
+    flip: bool,
+    trap_focus: bool,
     on_close: Option<CloseFn>,
+    on_menu_hover: Option<HoverFn>,
 }
 
 impl Default for Popover {
@@ -96,7 +161,11 @@ impl Popover {
 
             bg: None,
             border: None,
+            caret: false,
+            flip: false,
+            trap_focus: false,
             on_close: None,
+            on_menu_hover: None,
         }
     }
 
@@ -154,6 +223,32 @@ impl Popover {
         self
     }
 
+    /// Shows an arrow on the popover's edge pointing at the trigger (default: false).
+    pub fn caret(mut self, caret: bool) -> Self {
+        self.caret = caret;
+        self
+    }
+
+    /// Opens the popover above its trigger when it does not fit below and there
+    /// is more room above (default: false).
+    ///
+    /// The popover has to be measured before a side is picked, so it stays
+    /// hidden for the frame it opens on.
+    pub fn flip(mut self, flip: bool) -> Self {
+        self.flip = flip;
+        self
+    }
+
+    /// Moves focus into the open popover and keeps Tab and Shift+Tab cycling
+    /// inside it (default: false).
+    ///
+    /// Escape then calls `on_close`, and focus returns to the trigger when the
+    /// popover closes.
+    pub fn trap_focus(mut self, trap: bool) -> Self {
+        self.trap_focus = trap;
+        self
+    }
+
     pub fn trigger(mut self, trigger: impl IntoElement) -> Self {
         self.trigger = Some(trigger.into_any_element());
         self
@@ -168,7 +263,16 @@ impl Popover {
     where
         F: 'static + Fn(&mut gpui::Window, &mut gpui::App),
     {
-        self.on_close = Some(Box::new(f));
+        self.on_close = Some(Rc::new(f));
+        self
+    }
+
+    /// Called when the pointer enters or leaves the open popover.
+    pub(crate) fn on_menu_hover<F>(mut self, f: F) -> Self
+    where
+        F: 'static + Fn(bool, &mut gpui::Window, &mut gpui::App),
+    {
+        self.on_menu_hover = Some(Rc::new(f));
         self
     }
 }
@@ -186,57 +290,130 @@ impl Styled for Popover {
 }
 
 impl RenderOnce for Popover {
-    fn render(self, _window: &mut gpui::Window, cx: &mut gpui::App) -> impl IntoElement {
+    fn render(self, window: &mut gpui::Window, cx: &mut gpui::App) -> impl IntoElement {
         let element_id = self.element_id;
         let id = element_id.clone();
 
-        // Track trigger bounds for overflow protection.
-        let trigger_bounds_state = cx.new(|_| Bounds::<Pixels>::default());
-
-        let theme = cx.theme();
-        let bg = self.bg.unwrap_or(theme.surface.raised);
-        let border = self.border.unwrap_or(theme.border.default);
+        // Track trigger bounds for overflow protection, and with `flip` the menu's
+        // bounds too, to pick a side.
+        let trigger_bounds_state =
+            window.use_keyed_state((id.clone(), "ui:popover:trigger-bounds"), cx, |_, _| {
+                Bounds::<Pixels>::default()
+            });
+        let menu_bounds_state = self.flip.then(|| {
+            window.use_keyed_state((id.clone(), "ui:popover:menu-bounds"), cx, |_, _| {
+                Bounds::<Pixels>::default()
+            })
+        });
 
         let is_open = self.open;
+        // Measure afresh on every open; the content may have changed since.
+        if !is_open
+            && let Some(menu_bounds) = &menu_bounds_state
+            && *menu_bounds.read(cx) != Bounds::default()
+        {
+            menu_bounds.update(cx, |bounds, _| *bounds = Bounds::default());
+        }
         let placement = self.placement;
         let width = self.width;
+        let show_caret = self.caret;
+        let trap_focus = self.trap_focus;
         let on_close = self.on_close;
+        let on_menu_hover = self.on_menu_hover;
+
+        // With `trap_focus`, the container takes focus back when the popover closes
+        // and the panel holds focus while it is open.
+        let focus = trap_focus.then(|| {
+            let trigger_focus = window
+                .use_keyed_state((id.clone(), "ui:popover:trigger-focus"), cx, |_, cx| {
+                    cx.focus_handle()
+                })
+                .read(cx)
+                .clone();
+            let panel_focus = window
+                .use_keyed_state((id.clone(), "ui:popover:panel-focus"), cx, |_, cx| {
+                    cx.focus_handle()
+                })
+                .read(cx)
+                .clone();
+            let was_open =
+                window.use_keyed_state((id.clone(), "ui:popover:was-open"), cx, |_, _| false);
+            if *was_open.read(cx) != is_open {
+                was_open.update(cx, |was_open, _| *was_open = is_open);
+                if is_open {
+                    // Wait for the panel's tab stops to be rendered.
+                    let panel_focus = panel_focus.clone();
+                    window.on_next_frame(move |window, cx| {
+                        cycle_focus(&panel_focus, false, window, cx)
+                    });
+                }
+            }
+            (trigger_focus, panel_focus)
+        });
+
+        let theme = cx.theme();
+        let bg = self.bg.unwrap_or(theme.surface.raised);
+        let border = self.border.unwrap_or(theme.border.default);
 
         let trigger = self.trigger.unwrap_or_else(|| div().into_any_element());
         let content = self.content.unwrap_or_else(|| div().into_any_element());
 
         // Like Select/ComboBox, Popover is a relative container and the menu is an absolute child
         // rendered via `gpui::deferred(...)` so it is painted above.
-        let trigger = self.base
+        self.base
             .id(element_id)
             .relative()
+            .when_some(focus.as_ref(), |this, (trigger_focus, _)| {
+                this.track_focus(trigger_focus)
+            })
             .child(BoundsTrackerElement {
                 bounds_state: trigger_bounds_state.clone(),
                 inner: trigger.into_any_element(),
             })
             .when(is_open, move |this| {
-                let direction = cx
-                    .try_global::<I18n>()
-                    .map(|i18n| i18n.text_direction())
-                    .unwrap_or(TextDirection::Ltr);
+                let direction = cx.text_direction();
+                let viewport = window.viewport_size();
 
                 // Resolve menu width for clamping.
                 let menu_width_px = width.unwrap_or(px(260.));
                 let trigger_bounds = *trigger_bounds_state.read(cx);
-                let menu_left = desired_menu_left(trigger_bounds, menu_width_px, direction, _window);
+                let menu_left =
+                    desired_menu_left(trigger_bounds, menu_width_px, direction, viewport);
                 let relative_left = menu_left - trigger_bounds.left();
 
-                let menu = div()
-                    .id((id.clone(), "ui:popover:menu"))
-                    .absolute()
-                    .when(placement == PopoverPlacement::BottomStart, |this| {
-                        this.top_full().left_0()
-                    })
-                    .when(placement == PopoverPlacement::BottomEnd, |this| {
-                        this.top_full().left_0()
-                    })
-                    .when(relative_left != Pixels::ZERO, |this| this.left(relative_left))
-                    .mt(px(10.))
+                // Leave room for the caret between the trigger and the menu.
+                let gap = if show_caret { px(8.) } else { px(4.) };
+                let menu_height = menu_bounds_state
+                    .as_ref()
+                    .map(|state| state.read(cx).size.height);
+                // With `flip`, the frame the menu opens on only measures it.
+                let measuring = menu_height == Some(Pixels::ZERO);
+                let above = menu_height
+                    .is_some_and(|height| opens_above(trigger_bounds, height, gap, viewport));
+
+                let content = match focus.clone() {
+                    Some((trigger_focus, panel_focus)) => focus_trap()
+                        .id((id.clone(), "ui:popover:trap"))
+                        .fallback_focus(&trigger_focus)
+                        .track_focus(&panel_focus)
+                        .capture_key_down(move |ev, window, cx| {
+                            if ev.keystroke.key != "tab" {
+                                return;
+                            }
+                            cx.stop_propagation();
+                            let backwards = ev.keystroke.modifiers.shift;
+                            cycle_focus(&panel_focus, backwards, window, cx);
+                        })
+                        .when_some(on_close.clone(), |this, on_close| {
+                            this.on_escape(move |window, cx| on_close(window, cx))
+                        })
+                        .child(content)
+                        .into_any_element(),
+                    None => content,
+                };
+
+                let panel = div()
+                    .w_full()
                     .rounded_md()
                     .overflow_hidden()
                     .border_1()
@@ -244,6 +421,46 @@ impl RenderOnce for Popover {
                     .bg(bg)
                     .shadow_md()
                     .py_1()
+                    .child(content);
+
+                // The caret sits outside the panel's clipped box: a border-colored
+                // triangle with a background-colored one 1px closer covering the
+                // panel border underneath.
+                let carets = show_caret.then(|| {
+                    let offset = (trigger_bounds.center().x - menu_left)
+                        .clamp(CARET_INSET, (menu_width_px - CARET_INSET).max(CARET_INSET));
+                    if above {
+                        [
+                            caret(TooltipPlacement::Top, offset, border),
+                            caret(TooltipPlacement::Top, offset, bg).mt(px(-1.)),
+                        ]
+                    } else {
+                        [
+                            caret(TooltipPlacement::Bottom, offset, border),
+                            caret(TooltipPlacement::Bottom, offset, bg).mb(px(-1.)),
+                        ]
+                    }
+                });
+
+                let menu = div()
+                    .id((id.clone(), "ui:popover:menu"))
+                    .absolute()
+                    .when(placement == PopoverPlacement::BottomStart, |this| {
+                        this.left_0()
+                    })
+                    .when(placement == PopoverPlacement::BottomEnd, |this| {
+                        this.left_0()
+                    })
+                    .map(|this| {
+                        if above {
+                            this.bottom_full()
+                        } else {
+                            this.top_full()
+                        }
+                    })
+                    .when(relative_left != Pixels::ZERO, |this| {
+                        this.left(relative_left)
+                    })
                     .w(menu_width_px)
                     .occlude()
                     .on_mouse_down_out(move |_ev, window, cx| {
@@ -251,21 +468,103 @@ impl RenderOnce for Popover {
                             on_close(window, cx);
                         }
                     })
-                    .child(content);
-
-                let animated = menu.with_animation(
-                    format!("ui:popover:menu:{}", is_open),
-                    Animation::new(duration::MENU_OPEN).with_easing(ease_out_quint_clamped),
-                    |this, value| this.opacity(value).mt(px(10.0 - 6.0 * value)),
-                );
-
-                this.child(gpui::deferred(animated).with_priority(100))
-            });
-
-        trigger
+                    .when_some(on_menu_hover, |this, on_menu_hover| {
+                        this.on_hover(move |hovered, window, cx| {
+                            on_menu_hover(*hovered, window, cx)
+                        })
+                    })
+                    .child(panel)
+                    .children(carets.into_iter().flatten());
+
+                let menu = if measuring {
+                    window.request_animation_frame();
+                    menu.opacity(0.).into_any_element()
+                } else {
+                    // Slide in from the side facing away from the trigger.
+                    menu.with_animation(
+                        format!("ui:popover:menu:{}", is_open),
+                        Animation::new(duration::MENU_OPEN).with_easing(ease_out_quint_clamped),
+                        move |this, value| {
+                            let offset = gap + px(6.0 * (1.0 - value));
+                            let this = this.opacity(value);
+                            if above {
+                                this.mb(offset)
+                            } else {
+                                this.mt(offset)
+                            }
+                        },
+                    )
+                    .into_any_element()
+                };
+                let menu = match menu_bounds_state {
+                    Some(bounds_state) => BoundsTrackerElement {
+                        bounds_state,
+                        inner: menu,
+                    }
+                    .into_any_element(),
+                    None => menu,
+                };
+
+                this.child(gpui::deferred(menu).with_priority(100))
+            })
     }
 }
 
 // Keep a stable signature for downstream; on_trigger click handling stays with caller.
 #[allow(dead_code)]
 fn _click_passthrough(_ev: &ClickEvent) {}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{point, size};
+
+    use super::*;
+
+    #[test]
+    fn submenus_flip_near_window_edges() {
+        let viewport = Bounds::new(point(px(0.), px(0.)), size(px(800.), px(600.)));
+        let anchor = |x: f32, y: f32| Bounds::new(point(px(x), px(y)), size(px(200.), px(30.)));
+        let width = px(220.);
+
+        let ltr = TextDirection::Ltr;
+        assert_eq!(
+            desired_submenu_left(anchor(100., 0.), width, ltr, viewport),
+            px(300.)
+        );
+        assert_eq!(
+            desired_submenu_left(anchor(500., 0.), width, ltr, viewport),
+            px(280.)
+        );
+
+        let rtl = TextDirection::Rtl;
+        assert_eq!(
+            desired_submenu_left(anchor(500., 0.), width, rtl, viewport),
+            px(280.)
+        );
+        assert_eq!(
+            desired_submenu_left(anchor(100., 0.), width, rtl, viewport),
+            px(300.)
+        );
+
+        assert_eq!(
+            desired_submenu_top(anchor(0., 100.), px(200.), viewport),
+            px(100.)
+        );
+        assert_eq!(
+            desired_submenu_top(anchor(0., 500.), px(200.), viewport),
+            px(330.)
+        );
+    }
+
+    #[test]
+    fn popovers_open_above_only_when_there_is_more_room() {
+        let viewport = size(px(800.), px(600.));
+        let trigger = |y: f32| Bounds::new(point(px(0.), px(y)), size(px(100.), px(30.)));
+        let gap = px(4.);
+
+        assert!(!opens_above(trigger(100.), px(200.), gap, viewport));
+        assert!(opens_above(trigger(500.), px(200.), gap, viewport));
+        // Too tall for either side: stay below when that side has more room.
+        assert!(!opens_above(trigger(200.), px(500.), gap, viewport));
+    }
+}
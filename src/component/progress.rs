@@ -7,7 +7,31 @@ use gpui::InteractiveElement;
 
 use crate::{animation::constants::duration, theme::ActiveTheme};
 
-use crate::animation::ease_in_out_clamped;
+use crate::animation::{ease_in_out_clamped, reduce_motion};
+
+/// Left edge and width, as fractions of the track, of the segment an
+/// indeterminate bar shows instead of its sweep when motion is reduced.
+const STILL_SEGMENT: (f32, f32) = (0.0, 0.4);
+
+/// Whether an indeterminate bar shows [`STILL_SEGMENT`]: when the bar asks for
+/// it or motion is reduced app-wide.
+fn motion_reduced(reduced_motion: bool) -> bool {
+    reduced_motion || reduce_motion()
+}
+
+/// Left edge and width of `ProgressBar`'s sweeping segment, `delta` of the way
+/// through a sweep. It grows and shrinks as it moves.
+fn progress_bar_sweep(delta: f32) -> (f32, f32) {
+    let width = 0.18 + 0.32 * (1.0 - (2.0 * delta - 1.0).abs());
+    (-width + (1.0 + width) * delta, width)
+}
+
+/// Left edge and width of `LinearProgress`'s sweeping segment, `delta` of the
+/// way through a sweep.
+fn linear_progress_sweep(delta: f32) -> (f32, f32) {
+    let width = 0.3;
+    (-width + (1.0 + width) * delta, width)
+}
 
 /// Creates a new spinner element.
 pub fn spinner() -> Spinner {
@@ -229,6 +253,7 @@ pub struct ProgressBar {
     base: Div,
     value: f32,
     indeterminate: bool,
+    reduced_motion: bool,
     height: Pixels,
     track_color: Option<Hsla>,
     fill_color: Option<Hsla>,
@@ -247,6 +272,7 @@ impl ProgressBar {
             base: div().w_full(),
             value: 0.0,
             indeterminate: false,
+            reduced_motion: false,
             height: px(10.),
             track_color: None,
             fill_color: None,
@@ -270,6 +296,13 @@ impl ProgressBar {
         self
     }
 
+    /// Show a static segment instead of the indeterminate animation. The
+    /// segment is also shown while motion is reduced app-wide.
+    pub fn reduced_motion(mut self, reduced: bool) -> Self {
+        self.reduced_motion = reduced;
+        self
+    }
+
     pub fn height(mut self, height: Pixels) -> Self {
         self.height = height;
         self
@@ -332,27 +365,28 @@ impl RenderOnce for ProgressBar {
             .overflow_hidden();
 
         if indeterminate {
+            let segment = div()
+                .id(indeterminate_id)
+                .absolute()
+                .top_0()
+                .h(height)
+                .rounded_full()
+                .bg(fill);
+            if motion_reduced(self.reduced_motion) {
+                let (x, width) = STILL_SEGMENT;
+                return base.child(segment.left(relative(x)).w(relative(width)).opacity(0.6));
+            }
             base.child(
-                div()
-                    .id(indeterminate_id)
-                    .absolute()
-                    .top_0()
-                    .h(height)
-                    .rounded_full()
-                    .bg(fill)
-                    .with_animation(
-                        "ui:progress-bar:indeterminate:anim",
-                        Animation::new(duration::PROGRESS_CIRCLE)
-                            .repeat()
-                            .with_easing(ease_in_out_clamped),
-                        move |this, delta| {
-                            // A more dynamic indeterminate animation: bar grows and shrinks as it
-                            // moves, similar to common loading indicators.
-                            let width = 0.18 + 0.32 * (1.0 - (2.0 * delta - 1.0).abs());
-                            let x = -width + (1.0 + width) * delta;
-                            this.left(relative(x)).w(relative(width))
-                        },
-                    ),
+                segment.with_animation(
+                    "ui:progress-bar:indeterminate:anim",
+                    Animation::new(duration::PROGRESS_CIRCLE)
+                        .repeat()
+                        .with_easing(ease_in_out_clamped),
+                    move |this, delta| {
+                        let (x, width) = progress_bar_sweep(delta);
+                        this.left(relative(x)).w(relative(width))
+                    },
+                ),
             )
         } else {
             base.child(
@@ -370,6 +404,177 @@ impl RenderOnce for ProgressBar {
     }
 }
 
+/// Tracks whether an indeterminate bar is running and which animation run is current.
+///
+/// Each start or stop bumps the epoch. The animation element is keyed by the epoch, so
+/// a stopped bar stops rendering its animation (and requesting frames), and a restarted
+/// bar begins a fresh run instead of resuming a stale one.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct IndeterminateProgressState {
+    epoch: usize,
+    running: bool,
+}
+
+impl IndeterminateProgressState {
+    /// Starts or stops the animation. Returns `true` when the state changed.
+    pub fn set_active(&mut self, active: bool) -> bool {
+        if self.running == active {
+            return false;
+        }
+        self.running = active;
+        self.epoch = self.epoch.wrapping_add(1);
+        true
+    }
+
+    pub fn epoch(&self) -> usize {
+        self.epoch
+    }
+
+    /// The epoch of the current run, or `None` when stopped.
+    pub fn active_epoch(&self) -> Option<usize> {
+        self.running.then_some(self.epoch)
+    }
+}
+
+/// Creates a thin indeterminate linear progress bar for background work.
+///
+/// # Example
+/// ```rust,ignore
+/// linear_progress("file-browser:scan").active(is_scanning)
+/// ```
+pub fn linear_progress(id: impl Into<ElementId>) -> LinearProgress {
+    LinearProgress::new().id(id)
+}
+
+#[derive(IntoElement)]
+pub struct LinearProgress {
+    element_id: ElementId,
+    base: Div,
+    active: bool,
+    reduced_motion: bool,
+    height: Pixels,
+    track_color: Option<Hsla>,
+    fill_color: Option<Hsla>,
+}
+
+impl Default for LinearProgress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LinearProgress {
+    pub fn new() -> Self {
+        Self {
+            element_id: "ui:linear-progress".into(),
+            base: div().w_full(),
+            active: true,
+            reduced_motion: false,
+            height: px(3.),
+            track_color: None,
+            fill_color: None,
+        }
+    }
+
+    pub fn id(mut self, id: impl Into<ElementId>) -> Self {
+        self.element_id = id.into();
+        self
+    }
+
+    /// Alias for `id(...)`. Use `key(...)` when you want to emphasize state identity.
+    pub fn key(self, key: impl Into<ElementId>) -> Self {
+        self.id(key)
+    }
+
+    /// Whether work is in progress (default: true).
+    ///
+    /// An inactive bar keeps its height, so toggling it doesn't shift the layout.
+    pub fn active(mut self, active: bool) -> Self {
+        self.active = active;
+        self
+    }
+
+    /// Show a static segment instead of the sweeping animation.
+    pub fn reduced_motion(mut self, reduced: bool) -> Self {
+        self.reduced_motion = reduced;
+        self
+    }
+
+    pub fn height(mut self, height: Pixels) -> Self {
+        self.height = height;
+        self
+    }
+
+    pub fn track_color(mut self, color: impl Into<Hsla>) -> Self {
+        self.track_color = Some(color.into());
+        self
+    }
+
+    pub fn fill_color(mut self, color: impl Into<Hsla>) -> Self {
+        self.fill_color = Some(color.into());
+        self
+    }
+}
+
+impl Styled for LinearProgress {
+    fn style(&mut self) -> &mut gpui::StyleRefinement {
+        self.base.style()
+    }
+}
+
+impl RenderOnce for LinearProgress {
+    fn render(self, window: &mut gpui::Window, cx: &mut gpui::App) -> impl IntoElement {
+        let id = self.element_id;
+        let state = window.use_keyed_state((id.clone(), "ui:linear-progress:state"), cx, |_, _| {
+            IndeterminateProgressState::default()
+        });
+        if state.read(cx).active_epoch().is_some() != self.active {
+            state.update(cx, |state, _| {
+                state.set_active(self.active);
+            });
+        }
+        let active_epoch = state.read(cx).active_epoch();
+
+        let theme = cx.theme();
+        let track = self.track_color.unwrap_or(theme.surface.hover);
+        let fill = self.fill_color.unwrap_or(theme.action.primary.bg);
+        let height = self.height;
+
+        let base = self
+            .base
+            .id(id.clone())
+            .relative()
+            .h(height)
+            .rounded_full()
+            .overflow_hidden();
+
+        let Some(epoch) = active_epoch else {
+            return base;
+        };
+        let base = base.bg(track);
+
+        let segment = div().absolute().top_0().h(height).rounded_full().bg(fill);
+
+        if motion_reduced(self.reduced_motion) {
+            let (x, width) = STILL_SEGMENT;
+            return base.child(segment.left(relative(x)).w(relative(width)).opacity(0.6));
+        }
+
+        base.child(
+            segment.with_animation(
+                (id, format!("ui:linear-progress:anim:{}", epoch)),
+                Animation::new(duration::PROGRESS_BAR)
+                    .repeat()
+                    .with_easing(ease_in_out_clamped),
+                move |this, delta| {
+                    let (x, width) = linear_progress_sweep(delta);
+                    this.left(relative(x)).w(relative(width))
+                },
+            ),
+        )
+    }
+}
+
 /// Creates a new progress circle element.
 pub fn progress_circle() -> ProgressCircle {
     ProgressCircle::new()
@@ -578,3 +783,56 @@ impl RenderOnce for ProgressCircle {
             )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stopping_cancels_the_animation_epoch() {
+        let mut state = IndeterminateProgressState::default();
+
+        assert!(state.set_active(true));
+        let running = state.active_epoch().expect("running after start");
+
+        assert!(state.set_active(false));
+        assert_eq!(state.active_epoch(), None);
+        assert_ne!(state.epoch(), running);
+    }
+
+    #[test]
+    fn restarting_begins_a_new_epoch() {
+        let mut state = IndeterminateProgressState::default();
+        state.set_active(true);
+        let first = state.active_epoch();
+        state.set_active(false);
+        state.set_active(true);
+
+        assert!(state.active_epoch().is_some());
+        assert_ne!(state.active_epoch(), first);
+    }
+
+    #[test]
+    fn reduced_motion_shows_a_still_segment_on_the_track() {
+        // The sweeps end past the track, which is why reduced motion can't just
+        // jump them to their end.
+        for sweep in [progress_bar_sweep, linear_progress_sweep] {
+            let (x, _) = sweep(1.0);
+            assert!(x > 0.99);
+        }
+
+        assert!(motion_reduced(true));
+        let (x, width) = STILL_SEGMENT;
+        assert!(x >= 0.0 && width > 0.0 && x + width <= 1.0);
+    }
+
+    #[test]
+    fn repeated_activation_keeps_the_epoch() {
+        let mut state = IndeterminateProgressState::default();
+        state.set_active(true);
+        let epoch = state.epoch();
+
+        assert!(!state.set_active(true));
+        assert_eq!(state.epoch(), epoch);
+    }
+}
@@ -0,0 +1,359 @@
+//! Scroll area component.
+//!
+//! A focusable, vertically scrollable region. When focused it responds to
+//! arrow keys, PageUp/PageDown and Home/End, which gpui's raw `overflow_y_scroll`
+//! does not provide. A thin scrollbar thumb is overlaid while the region is
+//! hovered or focused.
+
+use gpui::prelude::FluentBuilder;
+use gpui::{
+    AnyElement, App, Div, ElementId, FocusHandle, Hsla, InteractiveElement, IntoElement,
+    ParentElement, Pixels, RenderOnce, ScrollHandle, StatefulInteractiveElement, Styled, Window,
+    actions, div, point, px,
+};
+
+use crate::theme::ActiveTheme;
+
+actions!(
+    ui_scroll_area,
+    [
+        ScrollLineUp,
+        ScrollLineDown,
+        ScrollPageUp,
+        ScrollPageDown,
+        ScrollToTop,
+        ScrollToBottom,
+    ]
+);
+
+pub(crate) fn init(cx: &mut App) {
+    cx.bind_keys([
+        gpui::KeyBinding::new("up", ScrollLineUp, Some("UIScrollArea")),
+        gpui::KeyBinding::new("down", ScrollLineDown, Some("UIScrollArea")),
+        gpui::KeyBinding::new("pageup", ScrollPageUp, Some("UIScrollArea")),
+        gpui::KeyBinding::new("pagedown", ScrollPageDown, Some("UIScrollArea")),
+        gpui::KeyBinding::new("home", ScrollToTop, Some("UIScrollArea")),
+        gpui::KeyBinding::new("end", ScrollToBottom, Some("UIScrollArea")),
+    ]);
+}
+
+/// Minimum height of the scrollbar thumb, so it stays grabbable on long content.
+const MIN_THUMB_HEIGHT: Pixels = px(24.);
+
+/// A single keyboard scroll step.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ScrollStep {
+    LineUp,
+    LineDown,
+    PageUp,
+    PageDown,
+    Top,
+    Bottom,
+}
+
+/// Computes the new scroll position for a keyboard step.
+///
+/// `scroll_top` is the distance scrolled from the top (always `>= 0`), and
+/// `max_scroll` is the largest reachable distance. Page steps move by the viewport
+/// height minus one line so the last visible line stays on screen as context.
+pub fn scroll_step_target(
+    step: ScrollStep,
+    scroll_top: Pixels,
+    max_scroll: Pixels,
+    viewport_height: Pixels,
+    line_height: Pixels,
+) -> Pixels {
+    let page = (viewport_height - line_height).max(line_height);
+    let target = match step {
+        ScrollStep::LineUp => scroll_top - line_height,
+        ScrollStep::LineDown => scroll_top + line_height,
+        ScrollStep::PageUp => scroll_top - page,
+        ScrollStep::PageDown => scroll_top + page,
+        ScrollStep::Top => Pixels::ZERO,
+        ScrollStep::Bottom => max_scroll,
+    };
+    target.clamp(Pixels::ZERO, max_scroll.max(Pixels::ZERO))
+}
+
+/// Computes the scrollbar thumb as `(top, height)` within the viewport.
+///
+/// Returns `None` when the content fits and no scrollbar is needed.
+pub fn scrollbar_thumb(
+    scroll_top: Pixels,
+    max_scroll: Pixels,
+    viewport_height: Pixels,
+) -> Option<(Pixels, Pixels)> {
+    if max_scroll <= Pixels::ZERO || viewport_height <= Pixels::ZERO {
+        return None;
+    }
+
+    let content_height = viewport_height + max_scroll;
+    let height = (viewport_height * (viewport_height / content_height))
+        .max(MIN_THUMB_HEIGHT)
+        .min(viewport_height);
+    let progress = (scroll_top / max_scroll).clamp(0.0, 1.0);
+    let top = (viewport_height - height) * progress;
+    Some((top, height))
+}
+
+/// Creates a new scroll area.
+///
+/// # Example
+/// ```rust,ignore
+/// scroll_area("settings-body")
+///     .h(px(320.))
+///     .child(long_content)
+/// ```
+pub fn scroll_area(id: impl Into<ElementId>) -> ScrollArea {
+    ScrollArea::new().id(id)
+}
+
+#[derive(IntoElement)]
+pub struct ScrollArea {
+    element_id: ElementId,
+    base: Div,
+    children: Vec<AnyElement>,
+    line_height: Pixels,
+    show_scrollbar: bool,
+    scroll_handle: Option<ScrollHandle>,
+    thumb_color: Option<Hsla>,
+}
+
+impl Default for ScrollArea {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScrollArea {
+    pub fn new() -> Self {
+        Self {
+            element_id: "ui:scroll-area".into(),
+            base: div(),
+            children: Vec::new(),
+            line_height: px(40.),
+            show_scrollbar: true,
+            scroll_handle: None,
+            thumb_color: None,
+        }
+    }
+
+    pub fn id(mut self, id: impl Into<ElementId>) -> Self {
+        self.element_id = id.into();
+        self
+    }
+
+    /// Alias for `id(...)`. Use `key(...)` when you want to emphasize state identity.
+    pub fn key(self, key: impl Into<ElementId>) -> Self {
+        self.id(key)
+    }
+
+    /// Distance scrolled by the arrow keys.
+    pub fn line_height(mut self, line_height: Pixels) -> Self {
+        self.line_height = line_height;
+        self
+    }
+
+    /// Show the overlay scrollbar while hovered or focused (default: true).
+    pub fn show_scrollbar(mut self, show: bool) -> Self {
+        self.show_scrollbar = show;
+        self
+    }
+
+    /// Use an externally owned scroll handle, e.g. to scroll programmatically.
+    ///
+    /// When not set, the handle is kept in keyed state.
+    pub fn track_scroll(mut self, handle: &ScrollHandle) -> Self {
+        self.scroll_handle = Some(handle.clone());
+        self
+    }
+
+    pub fn thumb_color(mut self, color: impl Into<Hsla>) -> Self {
+        self.thumb_color = Some(color.into());
+        self
+    }
+}
+
+impl ParentElement for ScrollArea {
+    fn extend(&mut self, elements: impl IntoIterator<Item = AnyElement>) {
+        self.children.extend(elements);
+    }
+}
+
+impl Styled for ScrollArea {
+    fn style(&mut self) -> &mut gpui::StyleRefinement {
+        self.base.style()
+    }
+}
+
+fn apply_step(handle: &ScrollHandle, step: ScrollStep, line_height: Pixels) {
+    let offset = handle.offset();
+    let scroll_top = Pixels::ZERO - offset.y;
+    let target = scroll_step_target(
+        step,
+        scroll_top,
+        handle.max_offset().height,
+        handle.bounds().size.height,
+        line_height,
+    );
+    handle.set_offset(point(offset.x, Pixels::ZERO - target));
+}
+
+impl RenderOnce for ScrollArea {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let id = self.element_id;
+        let line_height = self.line_height;
+
+        let scroll_handle = match self.scroll_handle {
+            Some(handle) => handle,
+            None => window
+                .use_keyed_state((id.clone(), "ui:scroll-area:handle"), cx, |_, _| {
+                    ScrollHandle::new()
+                })
+                .read(cx)
+                .clone(),
+        };
+        let focus_handle: FocusHandle = window
+            .use_keyed_state((id.clone(), "ui:scroll-area:focus"), cx, |_, cx| {
+                cx.focus_handle()
+            })
+            .read(cx)
+            .clone();
+        let hovered =
+            window.use_keyed_state((id.clone(), "ui:scroll-area:hovered"), cx, |_, _| false);
+
+        let is_active = *hovered.read(cx) || focus_handle.contains_focused(window, cx);
+        let thumb = (self.show_scrollbar && is_active)
+            .then(|| {
+                scrollbar_thumb(
+                    Pixels::ZERO - scroll_handle.offset().y,
+                    scroll_handle.max_offset().height,
+                    scroll_handle.bounds().size.height,
+                )
+            })
+            .flatten();
+        let thumb_color = self
+            .thumb_color
+            .unwrap_or_else(|| cx.theme().content.tertiary.opacity(0.5));
+
+        let step_handler = |step: ScrollStep| {
+            let handle = scroll_handle.clone();
+            move |window: &mut Window| {
+                apply_step(&handle, step, line_height);
+                window.refresh();
+            }
+        };
+        let line_up = step_handler(ScrollStep::LineUp);
+        let line_down = step_handler(ScrollStep::LineDown);
+        let page_up = step_handler(ScrollStep::PageUp);
+        let page_down = step_handler(ScrollStep::PageDown);
+        let to_top = step_handler(ScrollStep::Top);
+        let to_bottom = step_handler(ScrollStep::Bottom);
+
+        let content = div()
+            .id((id.clone(), "ui:scroll-area:viewport"))
+            .size_full()
+            .overflow_y_scroll()
+            .track_scroll(&scroll_handle)
+            .children(self.children);
+
+        self.base
+            .id(id)
+            .relative()
+            .overflow_hidden()
+            .key_context("UIScrollArea")
+            .track_focus(&focus_handle)
+            .on_hover(move |is_hovered, window, cx| {
+                hovered.update(cx, |state, _| *state = *is_hovered);
+                window.refresh();
+            })
+            .on_action(move |_: &ScrollLineUp, window, _cx| line_up(window))
+            .on_action(move |_: &ScrollLineDown, window, _cx| line_down(window))
+            .on_action(move |_: &ScrollPageUp, window, _cx| page_up(window))
+            .on_action(move |_: &ScrollPageDown, window, _cx| page_down(window))
+            .on_action(move |_: &ScrollToTop, window, _cx| to_top(window))
+            .on_action(move |_: &ScrollToBottom, window, _cx| to_bottom(window))
+            .child(content)
+            .when_some(thumb, |this, (top, height)| {
+                this.child(
+                    div()
+                        .absolute()
+                        .top(top)
+                        .right(px(2.))
+                        .w(px(6.))
+                        .h(height)
+                        .rounded_full()
+                        .bg(thumb_color),
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn page_down_scrolls_by_viewport_minus_one_line() {
+        let line = px(40.);
+        let viewport = px(400.);
+        let max = px(2000.);
+
+        let arrow = scroll_step_target(ScrollStep::LineDown, Pixels::ZERO, max, viewport, line);
+        let page = scroll_step_target(ScrollStep::PageDown, Pixels::ZERO, max, viewport, line);
+
+        assert_eq!(arrow, px(40.));
+        assert_eq!(page, px(360.));
+    }
+
+    #[test]
+    fn steps_are_clamped_to_scroll_range() {
+        let line = px(40.);
+        let viewport = px(400.);
+        let max = px(500.);
+
+        assert_eq!(
+            scroll_step_target(ScrollStep::PageDown, px(300.), max, viewport, line),
+            max
+        );
+        assert_eq!(
+            scroll_step_target(ScrollStep::LineUp, px(10.), max, viewport, line),
+            Pixels::ZERO
+        );
+        assert_eq!(
+            scroll_step_target(ScrollStep::Bottom, Pixels::ZERO, max, viewport, line),
+            max
+        );
+        assert_eq!(
+            scroll_step_target(ScrollStep::Top, px(250.), max, viewport, line),
+            Pixels::ZERO
+        );
+    }
+
+    #[test]
+    fn page_step_never_smaller_than_a_line() {
+        let page = scroll_step_target(
+            ScrollStep::PageDown,
+            Pixels::ZERO,
+            px(500.),
+            px(20.),
+            px(40.),
+        );
+        assert_eq!(page, px(40.));
+    }
+
+    #[test]
+    fn thumb_hidden_when_content_fits() {
+        assert_eq!(scrollbar_thumb(Pixels::ZERO, Pixels::ZERO, px(300.)), None);
+    }
+
+    #[test]
+    fn thumb_tracks_scroll_position() {
+        let (top, height) = scrollbar_thumb(Pixels::ZERO, px(300.), px(300.)).unwrap();
+        assert_eq!(top, Pixels::ZERO);
+        assert_eq!(height, px(150.));
+
+        let (top, _) = scrollbar_thumb(px(300.), px(300.), px(300.)).unwrap();
+        assert_eq!(top, px(150.));
+    }
+}
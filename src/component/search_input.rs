@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use gpui::{
     App, Div, ElementId, Hsla, InteractiveElement, IntoElement, ParentElement, RenderOnce,
@@ -35,6 +36,7 @@ pub struct SearchInput {
 
     on_change: Option<ChangeFn>,
     on_submit: Option<SubmitFn>,
+    debounce: Option<Duration>,
 }
 
 impl Default for SearchInput {
@@ -60,6 +62,7 @@ impl SearchInput {
 
             on_change: None,
             on_submit: None,
+            debounce: None,
         }
     }
 
@@ -99,6 +102,13 @@ impl SearchInput {
         self
     }
 
+    /// Delays `on_change` until typing has paused for `delay`, delivering only the
+    /// latest text. Enter delivers a pending change right away.
+    pub fn debounce(mut self, delay: Duration) -> Self {
+        self.debounce = Some(delay);
+        self
+    }
+
     pub fn bg(mut self, color: impl Into<Hsla>) -> Self {
         self.bg = Some(color.into());
         self
@@ -164,6 +174,7 @@ impl RenderOnce for SearchInput {
         let text_color = self.text_color;
         let on_change = self.on_change;
         let on_submit = self.on_submit;
+        let debounce = self.debounce;
 
         let input_id: ElementId = (id.clone(), "ui:search-input:input").into();
         let clear_id: ElementId = (id.clone(), "ui:search-input:clear").into();
@@ -175,9 +186,15 @@ impl RenderOnce for SearchInput {
         let input_state =
             window.use_keyed_state(input_id.clone(), cx, |_, cx| TextInputState::new(cx));
 
+        let debouncer =
+            window.use_keyed_state((id.clone(), "ui:search-input:debounce"), cx, |_, _| {
+                Debouncer::default()
+            });
+
         let on_change_for_input = {
             let input_state = input_state.clone();
             let on_change = on_change.clone();
+            let debouncer = debouncer.clone();
             move |value: SharedString, window: &mut gpui::Window, cx: &mut App| {
                 // Sync to our input_state
                 input_state.update(cx, |state, cx| {
@@ -185,15 +202,34 @@ impl RenderOnce for SearchInput {
                     cx.notify();
                 });
                 // Call external handler
-                if let Some(handler) = &on_change {
+                let Some(handler) = on_change.clone() else {
+                    return;
+                };
+                let Some(delay) = debounce else {
                     handler(value, window, cx);
-                }
+                    return;
+                };
+
+                let epoch = debouncer.update(cx, |debouncer, _| debouncer.schedule(value));
+                let debouncer = debouncer.clone();
+                window
+                    .spawn(cx, async move |cx| {
+                        cx.background_executor().timer(delay).await;
+                        cx.update(|window, cx| {
+                            let value = debouncer.update(cx, |debouncer, _| debouncer.take(epoch));
+                            if let Some(value) = value {
+                                handler(value, window, cx);
+                            }
+                        })
+                        .ok();
+                    })
+                    .detach();
             }
         };
 
         let clear_visible = !input_state.read(cx).content().is_empty();
 
-        let on_change_for_clear = on_change;
+        let on_change_for_clear = on_change.clone();
 
         let on_submit_for_input = on_submit.clone();
 
@@ -228,7 +264,15 @@ impl RenderOnce for SearchInput {
                         .on_change(on_change_for_input)
                         .on_submit({
                             let on_submit = on_submit_for_input;
+                            let debouncer = debouncer.clone();
                             move |value, window, cx| {
+                                let pending =
+                                    debouncer.update(cx, |debouncer, _| debouncer.flush());
+                                if let Some(pending) = pending
+                                    && let Some(handler) = &on_change
+                                {
+                                    handler(pending, window, cx);
+                                }
                                 if let Some(handler) = &on_submit {
                                     handler(value, window, cx);
                                 }
@@ -263,6 +307,11 @@ impl RenderOnce for SearchInput {
                                         state.set_content(SharedString::new_static(""));
                                         cx.notify();
                                     });
+                                    // Clearing is delivered now; drop any typing still waiting.
+                                    debouncer.update(cx, |debouncer, _| {
+                                        debouncer.flush();
+                                        debouncer.delivered = Some(SharedString::new_static(""));
+                                    });
 
                                     if let Some(handler) = &on_change {
                                         handler(SharedString::new_static(""), window, cx);
@@ -276,3 +325,71 @@ impl RenderOnce for SearchInput {
         base
     }
 }
+
+/// Pending debounced change for a search input.
+///
+/// Each keystroke bumps `epoch`; a timer only delivers if no newer keystroke
+/// arrived while it waited.
+#[derive(Default)]
+struct Debouncer {
+    epoch: usize,
+    pending: Option<SharedString>,
+    /// Last value handed to `on_change`, so a change that lands back on it is dropped.
+    delivered: Option<SharedString>,
+}
+
+impl Debouncer {
+    /// Holds `value` back and returns the epoch its timer must match.
+    fn schedule(&mut self, value: SharedString) -> usize {
+        self.epoch = self.epoch.wrapping_add(1);
+        self.pending = Some(value);
+        self.epoch
+    }
+
+    /// The pending value if the timer for `epoch` is still the latest one.
+    fn take(&mut self, epoch: usize) -> Option<SharedString> {
+        if epoch != self.epoch {
+            return None;
+        }
+        self.flush()
+    }
+
+    /// The pending value right away, cancelling its timer.
+    fn flush(&mut self) -> Option<SharedString> {
+        self.epoch = self.epoch.wrapping_add(1);
+        let value = self.pending.take()?;
+        if self.delivered.as_ref() == Some(&value) {
+            return None;
+        }
+        self.delivered = Some(value.clone());
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_the_latest_timer_delivers() {
+        let mut debouncer = Debouncer::default();
+        let first = debouncer.schedule("a".into());
+        let second = debouncer.schedule("ab".into());
+
+        assert_eq!(debouncer.take(first), None);
+        assert_eq!(debouncer.take(second), Some("ab".into()));
+        assert_eq!(debouncer.take(second), None);
+    }
+
+    #[test]
+    fn flush_delivers_now_and_cancels_the_timer() {
+        let mut debouncer = Debouncer::default();
+        let epoch = debouncer.schedule("query".into());
+
+        assert_eq!(debouncer.flush(), Some("query".into()));
+        debouncer.schedule("query".into());
+        assert_eq!(debouncer.take(epoch), None);
+        // Typing back to the delivered text isn't a change.
+        assert_eq!(debouncer.flush(), None);
+    }
+}
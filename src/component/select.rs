@@ -12,7 +12,7 @@ use crate::{
         ArrowDirection, BoundsTrackerElement, ChangeCallback, ChangeWithEventCallback, IconName,
         compute_input_style, create_internal_state, icon, use_internal_state,
     },
-    i18n::{I18n, I18nContext, TextDirection, defaults::DefaultPlaceholders},
+    i18n::{I18nContext, TextDirection, defaults::DefaultPlaceholders},
     theme::ActiveTheme,
 };
 
@@ -460,10 +460,7 @@ impl RenderOnce for Select {
                 let on_change_with_event = on_change_with_event_for_select.clone();
                 let internal_value = internal_value_for_select.clone();
                 let text_color = input_style.text_color;
-                let direction = cx
-                    .try_global::<I18n>()
-                    .map(|i18n| i18n.text_direction())
-                    .unwrap_or(TextDirection::Ltr);
+                let direction = cx.text_direction();
 
                 let trigger_bounds = *trigger_bounds_state_for_menu.read(cx);
                 let menu_width_px = menu_width.unwrap_or_else(|| trigger_bounds.size.width);
@@ -2,19 +2,26 @@ use std::sync::Arc;
 
 use gpui::{
     AppContext, Bounds, Div, Element, ElementId, Empty, GlobalElementId, Hsla, InspectorElementId,
-    InteractiveElement, IntoElement, LayoutId, MouseButton, MouseDownEvent, ParentElement,
-    RenderOnce, StatefulInteractiveElement, Styled, px, relative,
+    InteractiveElement, IntoElement, KeyDownEvent, LayoutId, MouseButton, MouseDownEvent,
+    ParentElement, RenderOnce, StatefulInteractiveElement, Styled, px, relative,
 };
 
 use gpui::prelude::FluentBuilder;
 
-use crate::{component::create_internal_state, theme::ActiveTheme};
+use crate::{
+    component::create_internal_state,
+    i18n::{I18nContext, I18nFormatter},
+    theme::ActiveTheme,
+};
 
 /// Creates a new slider element.
 ///
 /// Sliders allow users to select a value from a range by dragging a thumb.
 /// Use `.range(min, max)` to set the value range, and `.on_change()` to receive value updates.
 ///
+/// Clicking the track seeks to that point. When focused, the arrow keys move by
+/// one step, Page Up/Down by ten steps, and Home/End jump to either end.
+///
 /// # Example
 /// ```rust,ignore
 /// use yororen_ui::component::slider;
@@ -106,6 +113,7 @@ pub struct Slider {
     step: Option<f32>,
     value: Option<f32>,
     default_value: Option<f32>,
+    marks: Vec<f32>,
 
     disabled: bool,
 
@@ -135,6 +143,7 @@ impl Slider {
             step: None,
             value: None,
             default_value: None,
+            marks: Vec::new(),
 
             disabled: false,
 
@@ -167,6 +176,16 @@ impl Slider {
         self
     }
 
+    pub fn min(mut self, min: f32) -> Self {
+        self.min = min;
+        self
+    }
+
+    pub fn max(mut self, max: f32) -> Self {
+        self.max = max;
+        self
+    }
+
     pub fn step(mut self, step: f32) -> Self {
         assert!(step > 0.0, "Slider step must be greater than 0");
         self.step = Some(step);
@@ -183,11 +202,25 @@ impl Slider {
         self
     }
 
+    /// Draws a tick with a label below the track at each value, formatted for
+    /// the current locale. Values outside the range are skipped.
+    pub fn marks(mut self, marks: impl IntoIterator<Item = f32>) -> Self {
+        self.marks = marks.into_iter().collect();
+        self
+    }
+
     pub fn disabled(mut self, disabled: bool) -> Self {
         self.disabled = disabled;
         self
     }
 
+    /// ARIA attributes for this slider.
+    pub fn aria_attrs(&self) -> Vec<(String, String)> {
+        let (min, max) = (self.min.min(self.max), self.max.max(self.min));
+        let now = clamp(self.value.or(self.default_value).unwrap_or(min), min, max);
+        crate::a11y::slider::attrs(min as f64, max as f64, now as f64, self.disabled)
+    }
+
     pub fn height(mut self, height: gpui::AbsoluteLength) -> Self {
         self.height = Some(height);
         self
@@ -296,11 +329,7 @@ impl RenderOnce for Slider {
         let mut value = external_value.unwrap_or(*internal_value.read(cx));
 
         value = clamp(value, min.min(max), max.max(min));
-        let t = if (max - min).abs() <= f32::EPSILON {
-            0.0
-        } else {
-            clamp((value - min) / (max - min), 0.0, 1.0)
-        };
+        let t = ratio(value, min, max);
 
         let knob_diameter = 16.0;
         let track_height = 6.0;
@@ -310,9 +339,34 @@ impl RenderOnce for Slider {
                 Bounds::default()
             });
 
-        let set_from_mouse_x = {
+        let focus_handle = window
+            .use_keyed_state((id.clone(), "ui:slider:focus"), cx, |_, cx| {
+                cx.focus_handle().tab_stop(true)
+            })
+            .read(cx)
+            .clone();
+        let focused = focus_handle.is_focused(window);
+
+        let set_value = {
             let internal_value = internal_value.clone();
             let on_change = on_change.clone();
+            move |new_value: f32, window: &mut gpui::Window, cx: &mut gpui::App| {
+                // Only update internal state in uncontrolled mode
+                // In controlled mode, external value controls the display
+                if !is_controlled {
+                    internal_value.update(cx, |state, cx| {
+                        *state = new_value;
+                        cx.notify();
+                    });
+                }
+                if let Some(handler) = &on_change {
+                    handler(new_value, window, cx);
+                }
+            }
+        };
+
+        let set_from_mouse_x = {
+            let set_value = set_value.clone();
             move |x: f32,
                   bounds: Bounds<gpui::Pixels>,
                   window: &mut gpui::Window,
@@ -330,18 +384,7 @@ impl RenderOnce for Slider {
                     new_value = quantize(new_value, min, step);
                 }
                 new_value = clamp(new_value, min.min(max), max.max(min));
-
-                // Only update internal state in uncontrolled mode
-                // In controlled mode, external value controls the display
-                if !is_controlled {
-                    internal_value.update(cx, |state, cx| {
-                        *state = new_value;
-                        cx.notify();
-                    });
-                }
-                if let Some(handler) = &on_change {
-                    handler(new_value, window, cx);
-                }
+                set_value(new_value, window, cx);
             }
         };
 
@@ -358,6 +401,17 @@ impl RenderOnce for Slider {
             base.opacity(0.6).cursor_not_allowed()
         } else {
             base.cursor_pointer()
+                .track_focus(&focus_handle)
+                .on_key_down(move |ev: &KeyDownEvent, window, cx| {
+                    if let Some(new_value) = value_for_key(&ev.keystroke.key, value, min, max, step)
+                    {
+                        cx.stop_propagation();
+                        if new_value != value {
+                            set_value(new_value, window, cx);
+                        }
+                        window.refresh();
+                    }
+                })
         };
 
         // Make the interaction hitbox more lenient: clicking or dragging anywhere in the slider's
@@ -367,10 +421,12 @@ impl RenderOnce for Slider {
             .on_mouse_down(MouseButton::Left, {
                 let track_bounds_state = track_bounds_state.clone();
                 let set_from_mouse_x = set_from_mouse_x.clone();
+                let focus_handle = focus_handle.clone();
                 move |ev: &MouseDownEvent, window, cx| {
                     if disabled {
                         return;
                     }
+                    focus_handle.focus(window);
 
                     let bounds = *track_bounds_state.read(cx);
                     if bounds.size.width > px(1.) {
@@ -397,7 +453,50 @@ impl RenderOnce for Slider {
                 }
             });
 
-        base.child(TrackBoundsElement {
+        let (lo, hi) = (min.min(max), max.max(min));
+        let marks = self
+            .marks
+            .iter()
+            .copied()
+            .filter(|mark| (lo..=hi).contains(mark))
+            .map(|mark| (mark, ratio(mark, min, max)))
+            .collect::<Vec<_>>();
+        let formatter = cx.i18n().formatter();
+        let mark_labels = marks
+            .iter()
+            .map(|(mark, t)| {
+                gpui::div()
+                    .absolute()
+                    .top_0()
+                    .left(relative(*t))
+                    .ml(px(-(MARK_LABEL_WIDTH / 2.0)))
+                    .w(px(MARK_LABEL_WIDTH))
+                    .flex()
+                    .justify_center()
+                    .text_xs()
+                    .text_color(theme.content.secondary)
+                    .child(formatter.format_number(*mark as f64))
+            })
+            .collect::<Vec<_>>();
+        let ticks = marks
+            .iter()
+            .map(|(_, t)| {
+                gpui::div()
+                    .absolute()
+                    .top_0()
+                    .left(relative(*t))
+                    .w(px(2.))
+                    .h(px(track_height))
+                    .bg(theme.surface.raised)
+            })
+            .collect::<Vec<_>>();
+        let knob_border = if focused {
+            self.focus_border.unwrap_or(theme.border.focus)
+        } else {
+            theme.surface.raised
+        };
+
+        let track = TrackBoundsElement {
             bounds_state: track_bounds_state.clone(),
             inner: gpui::div()
                 .id((id.clone(), "ui:slider:track"))
@@ -415,6 +514,7 @@ impl RenderOnce for Slider {
                         if disabled {
                             return;
                         }
+                        focus_handle.focus(window);
 
                         let bounds = *track_bounds_state.read(cx);
                         if bounds.size.width > px(1.) {
@@ -445,6 +545,7 @@ impl RenderOnce for Slider {
                         .bg(fill)
                         .w(gpui::relative(t)),
                 )
+                .children(ticks)
                 .child(
                     gpui::div()
                         .absolute()
@@ -462,15 +563,38 @@ impl RenderOnce for Slider {
                                 .rounded_full()
                                 .bg(theme.action.primary.bg)
                                 .hover(|this| this.bg(theme.action.primary.hover_bg))
-                                .border_1()
-                                .border_color(theme.surface.raised),
+                                .when(focused, |this| this.border_2())
+                                .when(!focused, |this| this.border_1())
+                                .border_color(knob_border),
                         ),
                 )
                 .into_any_element(),
-        })
+        };
+
+        if mark_labels.is_empty() {
+            return base.child(track);
+        }
+        base.child(
+            gpui::div()
+                .w_full()
+                .flex()
+                .flex_col()
+                .gap_1()
+                .child(track)
+                .child(
+                    gpui::div()
+                        .relative()
+                        .w_full()
+                        .h(px(14.))
+                        .children(mark_labels),
+                ),
+        )
     }
 }
 
+/// Width of the box a mark label is centered in below its tick.
+const MARK_LABEL_WIDTH: f32 = 48.0;
+
 fn clamp(v: f32, min: f32, max: f32) -> f32 {
     v.max(min).min(max)
 }
@@ -479,3 +603,58 @@ fn quantize(value: f32, origin: f32, step: f32) -> f32 {
     let n = ((value - origin) / step).round();
     origin + n * step
 }
+
+/// Where `value` sits between `min` and `max`, from `0.0` to `1.0`.
+fn ratio(value: f32, min: f32, max: f32) -> f32 {
+    if (max - min).abs() <= f32::EPSILON {
+        0.0
+    } else {
+        clamp((value - min) / (max - min), 0.0, 1.0)
+    }
+}
+
+/// The value a key press moves to, or `None` if the key doesn't adjust a slider.
+///
+/// Without a step, arrows move by a hundredth of the range.
+fn value_for_key(key: &str, value: f32, min: f32, max: f32, step: Option<f32>) -> Option<f32> {
+    let increment = step.unwrap_or((max - min).abs() / 100.0);
+    let next = match key {
+        "left" | "down" => value - increment,
+        "right" | "up" => value + increment,
+        "pagedown" => value - increment * 10.0,
+        "pageup" => value + increment * 10.0,
+        "home" => min,
+        "end" => max,
+        _ => return None,
+    };
+    let next = match step {
+        Some(step) => quantize(next, min, step),
+        None => next,
+    };
+    Some(clamp(next, min.min(max), max.max(min)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_move_by_step_within_range() {
+        let step = Some(5.0);
+        assert_eq!(value_for_key("right", 50.0, 0.0, 100.0, step), Some(55.0));
+        assert_eq!(value_for_key("down", 50.0, 0.0, 100.0, step), Some(45.0));
+        assert_eq!(value_for_key("pageup", 70.0, 0.0, 100.0, step), Some(100.0));
+        assert_eq!(value_for_key("home", 70.0, 0.0, 100.0, step), Some(0.0));
+        assert_eq!(value_for_key("left", 0.0, 0.0, 100.0, step), Some(0.0));
+        assert_eq!(value_for_key("right", 0.5, 0.0, 1.0, None), Some(0.51));
+        assert_eq!(value_for_key("a", 0.5, 0.0, 1.0, None), None);
+    }
+
+    #[test]
+    fn aria_attrs_report_the_value() {
+        let attrs = Slider::new().range(0.0, 10.0).value(12.0).aria_attrs();
+        assert!(attrs.contains(&("role".to_string(), "slider".to_string())));
+        assert!(attrs.contains(&("aria-valuemax".to_string(), "10".to_string())));
+        assert!(attrs.contains(&("aria-valuenow".to_string(), "10".to_string())));
+    }
+}
@@ -0,0 +1,622 @@
+use std::rc::Rc;
+
+use gpui::{
+    AnyElement, App, AppContext, Bounds, CursorStyle, Div, ElementId, Empty, InteractiveElement,
+    IntoElement, KeyDownEvent, MouseButton, MouseDownEvent, ParentElement, Pixels, Point,
+    RenderOnce, StatefulInteractiveElement, Styled, Window, div, prelude::FluentBuilder, px,
+};
+
+use crate::{
+    a11y::{Politeness, announce, split_pane as aria_split_pane},
+    component::BoundsTrackerElement,
+    theme::ActiveTheme,
+};
+
+/// Thickness of the draggable area between two panes.
+const DIVIDER_SIZE: Pixels = px(6.);
+
+/// How far an arrow key moves a focused divider; with Shift, four times as far.
+const KEYBOARD_STEP: f32 = 16.;
+
+/// Creates a new split pane element.
+///
+/// Panes sit side by side (or stacked, with [`SplitOrientation::Vertical`])
+/// and share the space between them. Dragging a divider resizes its two
+/// neighbours live, and double-clicking it restores the initial ratios.
+/// A focused divider moves with the arrow keys.
+///
+/// The ratios are kept in keyed state, so they survive re-renders. Split
+/// panes nest: a pane can hold another split pane with its own id.
+///
+/// # Example
+/// ```rust,ignore
+/// use yororen_ui::component::{SplitPaneItem, split_pane};
+///
+/// split_pane("mail")
+///     .initial_ratio(0.3)
+///     .item(SplitPaneItem::new(folders).min_size(px(160.)))
+///     .pane(messages);
+/// ```
+pub fn split_pane(id: impl Into<ElementId>) -> SplitPane {
+    SplitPane::new().id(id)
+}
+
+/// How the panes of a split pane are laid out.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SplitOrientation {
+    /// Panes side by side, separated by vertical dividers.
+    #[default]
+    Horizontal,
+    /// Panes stacked top to bottom, separated by horizontal dividers.
+    Vertical,
+}
+
+/// A pane of a split pane.
+pub struct SplitPaneItem {
+    content: AnyElement,
+    min_size: Option<Pixels>,
+}
+
+impl SplitPaneItem {
+    pub fn new(content: impl IntoElement) -> Self {
+        Self {
+            content: content.into_any_element(),
+            min_size: None,
+        }
+    }
+
+    /// The size dragging can't shrink this pane below, overriding the split
+    /// pane's `.min_size()`.
+    pub fn min_size(mut self, size: Pixels) -> Self {
+        self.min_size = Some(size);
+        self
+    }
+}
+
+#[derive(IntoElement)]
+pub struct SplitPane {
+    element_id: ElementId,
+    base: Div,
+    orientation: SplitOrientation,
+    items: Vec<SplitPaneItem>,
+    initial_ratios: Vec<f32>,
+    min_size: Pixels,
+}
+
+impl Default for SplitPane {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SplitPane {
+    pub fn new() -> Self {
+        Self {
+            element_id: "ui:split-pane".into(),
+            base: div(),
+            orientation: SplitOrientation::default(),
+            items: Vec::new(),
+            initial_ratios: Vec::new(),
+            min_size: px(48.),
+        }
+    }
+
+    pub fn id(mut self, id: impl Into<ElementId>) -> Self {
+        self.element_id = id.into();
+        self
+    }
+
+    /// Alias for `id(...)`. Use `key(...)` when you want to emphasize state identity.
+    pub fn key(self, key: impl Into<ElementId>) -> Self {
+        self.id(key)
+    }
+
+    pub fn orientation(mut self, orientation: SplitOrientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// Adds a pane with the split pane's minimum size.
+    pub fn pane(self, content: impl IntoElement) -> Self {
+        self.item(SplitPaneItem::new(content))
+    }
+
+    pub fn item(mut self, item: SplitPaneItem) -> Self {
+        self.items.push(item);
+        self
+    }
+
+    /// The share of the space the first pane starts with; the other panes split
+    /// the rest evenly.
+    pub fn initial_ratio(self, ratio: f32) -> Self {
+        self.initial_ratios([ratio])
+    }
+
+    /// The shares of the space the leading panes start with; panes without one
+    /// split the rest evenly.
+    pub fn initial_ratios(mut self, ratios: impl IntoIterator<Item = f32>) -> Self {
+        self.initial_ratios = ratios.into_iter().collect();
+        self
+    }
+
+    /// The size dragging can't shrink a pane below (default: 48px).
+    pub fn min_size(mut self, size: Pixels) -> Self {
+        self.min_size = size;
+        self
+    }
+
+    /// Returns the aria attributes of a divider, given the share of the space
+    /// before it (`0.0..=1.0`).
+    ///
+    /// Each rendered divider carries these for its live position; while it is
+    /// focused, its `aria-valuenow` is read out through the
+    /// [`live_region`](crate::a11y::live_region).
+    pub fn divider_aria_attrs(&self, position: f32) -> Vec<(String, String)> {
+        aria_split_pane::separator_attrs(
+            self.orientation == SplitOrientation::Horizontal,
+            f64::from(position * 100.),
+        )
+    }
+}
+
+impl ParentElement for SplitPane {
+    fn extend(&mut self, elements: impl IntoIterator<Item = AnyElement>) {
+        self.items
+            .extend(elements.into_iter().map(SplitPaneItem::new));
+    }
+}
+
+impl Styled for SplitPane {
+    fn style(&mut self) -> &mut gpui::StyleRefinement {
+        self.base.style()
+    }
+}
+
+impl InteractiveElement for SplitPane {
+    fn interactivity(&mut self) -> &mut gpui::Interactivity {
+        self.base.interactivity()
+    }
+}
+
+impl StatefulInteractiveElement for SplitPane {}
+
+/// Drag payload of a divider being dragged.
+struct DividerDrag {
+    split: ElementId,
+    divider: usize,
+}
+
+/// Where a divider drag started.
+#[derive(Clone)]
+struct DividerResize {
+    divider: usize,
+    origin: Pixels,
+    ratios: Vec<f32>,
+}
+
+/// Everything a divider needs to resize its neighbours.
+#[derive(Clone)]
+struct Layout {
+    vertical: bool,
+    rtl: bool,
+    defaults: Vec<f32>,
+    min_sizes: Rc<Vec<f32>>,
+    bounds: gpui::Entity<Bounds<Pixels>>,
+    ratios: gpui::Entity<Vec<f32>>,
+}
+
+impl Layout {
+    /// The space shared by the panes, without the dividers.
+    fn available(&self, cx: &App) -> f32 {
+        let size = self.bounds.read(cx).size;
+        let total = if self.vertical {
+            size.height
+        } else {
+            size.width
+        };
+        let dividers = DIVIDER_SIZE * self.defaults.len().saturating_sub(1) as f32;
+        f32::from(total - dividers).max(0.)
+    }
+
+    /// The mouse position along the split axis.
+    fn along(&self, position: Point<Pixels>) -> Pixels {
+        if self.vertical {
+            position.y
+        } else {
+            position.x
+        }
+    }
+
+    /// Moves `divider` by `delta` pixels from where `ratios` put it.
+    fn resize(
+        &self,
+        ratios: &[f32],
+        divider: usize,
+        delta: f32,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        // Horizontal splits run right to left in RTL layouts.
+        let delta = if self.rtl && !self.vertical {
+            -delta
+        } else {
+            delta
+        };
+        let available = self.available(cx);
+        let next = resize_panes(ratios, divider, delta, available, &self.min_sizes);
+        self.ratios.update(cx, |ratios, _| *ratios = next);
+        window.refresh();
+    }
+}
+
+impl RenderOnce for SplitPane {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let id = self.element_id.clone();
+        let theme = cx.theme().clone();
+        let vertical = self.orientation == SplitOrientation::Vertical;
+        let rtl = cx.text_direction().is_rtl();
+        let count = self.items.len();
+
+        let defaults = initial_ratios(count, &self.initial_ratios);
+        let bounds = window.use_keyed_state((id.clone(), "ui:split-pane:bounds"), cx, |_, _| {
+            Bounds::default()
+        });
+        let ratios = window.use_keyed_state((id.clone(), "ui:split-pane:ratios"), cx, {
+            let defaults = defaults.clone();
+            move |_, _| defaults
+        });
+        // Panes were added or removed since the ratios were kept.
+        if ratios.read(cx).len() != count {
+            ratios.update(cx, |ratios, _| *ratios = defaults.clone());
+        }
+        let resize = window.use_keyed_state((id.clone(), "ui:split-pane:resize"), cx, |_, _| {
+            None::<DividerResize>
+        });
+
+        let layout = Layout {
+            vertical,
+            rtl,
+            defaults,
+            min_sizes: Rc::new(
+                self.items
+                    .iter()
+                    .map(|item| f32::from(item.min_size.unwrap_or(self.min_size)))
+                    .collect(),
+            ),
+            bounds: bounds.clone(),
+            ratios: ratios.clone(),
+        };
+        let current = ratios.read(cx).clone();
+        let available = layout.available(cx);
+        let divider_attrs: Vec<_> = (0..count.saturating_sub(1))
+            .map(|index| self.divider_aria_attrs(divider_position(&current, index)))
+            .collect();
+
+        let mut children = Vec::with_capacity(count * 2);
+        for (index, item) in self.items.into_iter().enumerate() {
+            if index > 0 {
+                let aria = &divider_attrs[index - 1];
+                children.push(
+                    divider(&id, index - 1, &layout, &resize, aria, window, cx).into_any_element(),
+                );
+            }
+            let ratio = current.get(index).copied().unwrap_or(0.);
+            let min_size = px(layout.min_sizes[index].min(available));
+            children.push(
+                div()
+                    .relative()
+                    .overflow_hidden()
+                    .flex_basis(px(0.))
+                    .map(|mut this| {
+                        this.style().flex_grow = Some(ratio);
+                        this.style().flex_shrink = Some(1.);
+                        this
+                    })
+                    .map(|this| {
+                        if vertical {
+                            this.w_full().min_h(min_size)
+                        } else {
+                            this.h_full().min_w(min_size)
+                        }
+                    })
+                    .child(item.content)
+                    .into_any_element(),
+            );
+        }
+
+        self.base.id(id).size_full().child(BoundsTrackerElement {
+            bounds_state: bounds,
+            inner: div()
+                .size_full()
+                .flex()
+                .map(|this| match (vertical, rtl) {
+                    (true, _) => this.flex_col(),
+                    (false, false) => this.flex_row(),
+                    (false, true) => this.flex_row_reverse(),
+                })
+                .when(resize.read(cx).is_some(), |this| {
+                    this.cursor(resize_cursor(vertical))
+                })
+                .bg(theme.surface.base)
+                .children(children)
+                .into_any_element(),
+        })
+    }
+}
+
+fn resize_cursor(vertical: bool) -> CursorStyle {
+    if vertical {
+        CursorStyle::ResizeRow
+    } else {
+        CursorStyle::ResizeColumn
+    }
+}
+
+/// The divider between pane `index` and the pane after it.
+fn divider(
+    id: &ElementId,
+    index: usize,
+    layout: &Layout,
+    resize: &gpui::Entity<Option<DividerResize>>,
+    aria: &[(String, String)],
+    window: &mut Window,
+    cx: &mut App,
+) -> impl IntoElement {
+    let theme = cx.theme().clone();
+    let vertical = layout.vertical;
+    let focus = window
+        .use_keyed_state(
+            (id.clone(), format!("ui:split-pane:divider-focus:{index}")),
+            cx,
+            |_, cx| cx.focus_handle().tab_stop(true),
+        )
+        .read(cx)
+        .clone();
+
+    // gpui has no accessibility tree to hold `aria`, so a focused divider reads
+    // its position out whenever it gains focus or moves.
+    let announced = window.use_keyed_state(
+        (id.clone(), format!("ui:split-pane:divider-value:{index}")),
+        cx,
+        |_, _| None::<String>,
+    );
+    let value_now = focus
+        .is_focused(window)
+        .then(|| aria.iter().find(|(name, _)| name == "aria-valuenow"))
+        .flatten()
+        .map(|(_, value)| value.clone());
+    if *announced.read(cx) != value_now {
+        if let Some(value) = &value_now {
+            announce(cx, format!("{value}%"), Politeness::Polite);
+        }
+        announced.update(cx, |announced, _| *announced = value_now);
+    }
+    let dragging = resize
+        .read(cx)
+        .as_ref()
+        .is_some_and(|resize| resize.divider == index);
+
+    let line = div()
+        .bg(if dragging {
+            theme.border.focus
+        } else {
+            theme.border.divider
+        })
+        .map(|this| {
+            if vertical {
+                this.w_full().h(px(1.))
+            } else {
+                this.h_full().w(px(1.))
+            }
+        });
+
+    let split = id.clone();
+    div()
+        .id((id.clone(), format!("ui:split-pane:divider:{index}")))
+        .flex_none()
+        .flex()
+        .items_center()
+        .justify_center()
+        .map(|this| {
+            if vertical {
+                this.w_full().h(DIVIDER_SIZE)
+            } else {
+                this.h_full().w(DIVIDER_SIZE)
+            }
+        })
+        .cursor(resize_cursor(vertical))
+        .track_focus(&focus)
+        .focus_visible(|style| style.bg(theme.border.focus.alpha(0.35)))
+        .hover(|style| style.bg(theme.border.divider.alpha(0.5)))
+        .child(line)
+        .on_mouse_down(MouseButton::Left, {
+            let layout = layout.clone();
+            let resize = resize.clone();
+            move |ev: &MouseDownEvent, _window, cx| {
+                cx.stop_propagation();
+                let origin = layout.along(ev.position);
+                let ratios = layout.ratios.read(cx).clone();
+                resize.update(cx, |resize, _| {
+                    *resize = Some(DividerResize {
+                        divider: index,
+                        origin,
+                        ratios,
+                    });
+                });
+            }
+        })
+        .on_drag(
+            DividerDrag {
+                split: id.clone(),
+                divider: index,
+            },
+            |_drag: &DividerDrag, _pos, _window, cx| cx.new(|_| Empty),
+        )
+        .on_drag_move::<DividerDrag>({
+            let layout = layout.clone();
+            let resize = resize.clone();
+            move |ev, window, cx| {
+                let dragged = ev.drag(cx);
+                if dragged.split != split || dragged.divider != index {
+                    return;
+                }
+                let Some(drag) = resize.read(cx).clone() else {
+                    return;
+                };
+                let delta = f32::from(layout.along(ev.event.position) - drag.origin);
+                layout.resize(&drag.ratios, index, delta, window, cx);
+            }
+        })
+        .on_mouse_up_out(MouseButton::Left, {
+            let resize = resize.clone();
+            move |_ev, _window, cx| {
+                resize.update(cx, |resize, _| *resize = None);
+            }
+        })
+        .on_mouse_up(MouseButton::Left, {
+            let resize = resize.clone();
+            move |_ev, _window, cx| {
+                resize.update(cx, |resize, _| *resize = None);
+            }
+        })
+        .on_click({
+            let layout = layout.clone();
+            move |ev, window, cx| {
+                if ev.click_count() == 2 {
+                    let defaults = layout.defaults.clone();
+                    layout.ratios.update(cx, |ratios, _| *ratios = defaults);
+                    window.refresh();
+                }
+            }
+        })
+        .on_key_down({
+            let layout = layout.clone();
+            move |ev: &KeyDownEvent, window, cx| {
+                let step = if ev.keystroke.modifiers.shift {
+                    KEYBOARD_STEP * 4.
+                } else {
+                    KEYBOARD_STEP
+                };
+                let delta = match (ev.keystroke.key.as_str(), vertical) {
+                    ("left", false) | ("up", true) => -step,
+                    ("right", false) | ("down", true) => step,
+                    _ => return,
+                };
+                cx.stop_propagation();
+                let ratios = layout.ratios.read(cx).clone();
+                layout.resize(&ratios, index, delta, window, cx);
+            }
+        })
+}
+
+/// The share of the space before the divider after pane `index`.
+fn divider_position(ratios: &[f32], index: usize) -> f32 {
+    let total = ratios.iter().sum::<f32>();
+    if total <= f32::EPSILON {
+        return 0.;
+    }
+    ratios.iter().take(index + 1).sum::<f32>() / total
+}
+
+/// The starting share of each of `count` panes: the given `initial` shares
+/// for the leading panes, with the rest split evenly among the others.
+fn initial_ratios(count: usize, initial: &[f32]) -> Vec<f32> {
+    if count == 0 {
+        return Vec::new();
+    }
+    let given = initial.len().min(count);
+    let mut ratios: Vec<f32> = initial[..given]
+        .iter()
+        .map(|ratio| ratio.clamp(0., 1.))
+        .collect();
+    let taken = ratios.iter().sum::<f32>();
+    if given < count {
+        let rest = (1. - taken).max(0.) / (count - given) as f32;
+        ratios.resize(count, rest);
+    }
+    let total = ratios.iter().sum::<f32>();
+    if total <= f32::EPSILON {
+        return vec![1. / count as f32; count];
+    }
+    ratios.iter().map(|ratio| ratio / total).collect()
+}
+
+/// Moves the divider after pane `divider` by `delta` pixels, trading space
+/// between its two neighbours only. Neither neighbour shrinks below its
+/// minimum size, unless they can't both fit, in which case they keep their
+/// sizes.
+fn resize_panes(
+    ratios: &[f32],
+    divider: usize,
+    delta: f32,
+    available: f32,
+    min_sizes: &[f32],
+) -> Vec<f32> {
+    let mut next = ratios.to_vec();
+    if divider + 1 >= ratios.len() || available <= 0. {
+        return next;
+    }
+    let pair = (ratios[divider] + ratios[divider + 1]) * available;
+    let (min_before, min_after) = (min_sizes[divider], min_sizes[divider + 1]);
+    if min_before + min_after > pair {
+        return next;
+    }
+    let before = (ratios[divider] * available + delta).clamp(min_before, pair - min_after);
+    next[divider] = before / available;
+    next[divider + 1] = (pair - before) / available;
+    next
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_ratios(actual: &[f32], expected: &[f32]) {
+        assert_eq!(actual.len(), expected.len());
+        for (actual, expected) in actual.iter().zip(expected) {
+            assert!(
+                (actual - expected).abs() < 1e-4,
+                "{actual:?} != {expected:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn initial_ratios_split_the_rest_evenly() {
+        assert_ratios(&initial_ratios(2, &[]), &[0.5, 0.5]);
+        assert_ratios(&initial_ratios(3, &[0.4]), &[0.4, 0.3, 0.3]);
+        assert_ratios(&initial_ratios(2, &[3., 1.]), &[0.5, 0.5]);
+        assert!(initial_ratios(0, &[0.5]).is_empty());
+    }
+
+    #[test]
+    fn resize_panes_respects_min_sizes() {
+        let ratios = [0.25, 0.25, 0.5];
+        let mins = [50., 50., 50.];
+        assert_ratios(
+            &resize_panes(&ratios, 0, 50., 400., &mins),
+            &[0.375, 0.125, 0.5],
+        );
+        // Clamped so the second pane keeps its 50px.
+        assert_ratios(
+            &resize_panes(&ratios, 0, 500., 400., &mins),
+            &[0.375, 0.125, 0.5],
+        );
+        assert_ratios(
+            &resize_panes(&ratios, 1, -500., 400., &mins),
+            &[0.25, 0.125, 0.625],
+        );
+        // Neighbours that can't both fit are left alone.
+        assert_ratios(&resize_panes(&ratios, 0, 10., 100., &mins), &ratios);
+    }
+
+    #[test]
+    fn divider_attrs_report_the_share_before_the_divider() {
+        let ratios = [0.25, 0.25, 0.5];
+        let pane = split_pane("split");
+        let attrs = pane.divider_aria_attrs(divider_position(&ratios, 1));
+        assert!(attrs.contains(&("role".to_string(), "separator".to_string())));
+        assert!(attrs.contains(&("aria-orientation".to_string(), "vertical".to_string())));
+        assert!(attrs.contains(&("aria-valuenow".to_string(), "50".to_string())));
+    }
+}
@@ -0,0 +1,765 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+use gpui::{
+    AnyElement, App, AppContext, Bounds, CursorStyle, Div, ElementId, Empty, InteractiveElement,
+    IntoElement, ListAlignment, MouseButton, MouseDownEvent, ParentElement, Pixels, RenderOnce,
+    SharedString, StatefulInteractiveElement, Styled, Window, div, prelude::FluentBuilder, px,
+};
+
+use crate::{
+    component::{ArrowDirection, BoundsTrackerElement, IconName, icon},
+    theme::ActiveTheme,
+    widget::{virtual_list, virtual_list_state},
+};
+
+/// Creates a new table element.
+///
+/// A table lays out [`TableRow`]s under a header of [`TableColumn`]s. Clicking
+/// a sortable header sorts by that column, toggling between ascending and
+/// descending. Without [`on_sort`](Table::on_sort) the table sorts rows by
+/// their cell text itself; with it, the caller is expected to pass the rows in
+/// the new order.
+///
+/// Use `.virtualized(true)` with a fixed height for large datasets, so only
+/// the visible rows are rendered.
+///
+/// # Example
+/// ```rust,ignore
+/// use gpui::px;
+/// use yororen_ui::component::{table, ColumnAlign, TableColumn, TableRow};
+///
+/// table("files")
+///     .column(TableColumn::new("name", "Name").sortable(true))
+///     .column(TableColumn::new("size", "Size").width(px(96.)).align(ColumnAlign::End))
+///     .row(TableRow::new("a").cell("name", "a.txt").cell("size", "12"))
+///     .selectable(true);
+/// ```
+pub fn table(id: impl Into<ElementId>) -> Table {
+    Table::new().id(id)
+}
+
+type CellFn = Rc<dyn Fn(&TableRow, &mut Window, &mut App) -> AnyElement>;
+type SortFn = Rc<dyn Fn(&SharedString, SortDirection, &mut Window, &mut App)>;
+type SelectionFn = Rc<dyn Fn(&[SharedString], &mut Window, &mut App)>;
+type RowFn = Rc<dyn Fn(usize, &mut Window, &mut App) -> AnyElement>;
+
+const ROW_HEIGHT: Pixels = px(36.);
+const MIN_COLUMN_WIDTH: Pixels = px(48.);
+const RESIZE_HANDLE_WIDTH: Pixels = px(6.);
+
+/// Order of a sorted column.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SortDirection {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+impl SortDirection {
+    /// The `aria-sort` value.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SortDirection::Ascending => "ascending",
+            SortDirection::Descending => "descending",
+        }
+    }
+
+    pub fn reversed(self) -> Self {
+        match self {
+            SortDirection::Ascending => SortDirection::Descending,
+            SortDirection::Descending => SortDirection::Ascending,
+        }
+    }
+}
+
+/// Horizontal alignment of a column's cells.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColumnAlign {
+    /// Left in left-to-right layouts, right in right-to-left ones.
+    #[default]
+    Start,
+    Center,
+    End,
+}
+
+/// A column of a [`Table`].
+#[derive(Clone)]
+pub struct TableColumn {
+    pub key: SharedString,
+    pub header: SharedString,
+    /// Fixed width; columns without one share the remaining space.
+    pub width: Option<Pixels>,
+    pub sortable: bool,
+    pub resizable: bool,
+    pub align: ColumnAlign,
+    render: Option<CellFn>,
+}
+
+impl TableColumn {
+    pub fn new(key: impl Into<SharedString>, header: impl Into<SharedString>) -> Self {
+        Self {
+            key: key.into(),
+            header: header.into(),
+            width: None,
+            sortable: false,
+            resizable: false,
+            align: ColumnAlign::Start,
+            render: None,
+        }
+    }
+
+    pub fn width(mut self, width: Pixels) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    pub fn sortable(mut self, sortable: bool) -> Self {
+        self.sortable = sortable;
+        self
+    }
+
+    /// Lets the user drag the column's edge to change its width.
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+
+    pub fn align(mut self, align: ColumnAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Renders this column's cells instead of showing their text.
+    pub fn render<F>(mut self, render: F) -> Self
+    where
+        F: 'static + Fn(&TableRow, &mut Window, &mut App) -> AnyElement,
+    {
+        self.render = Some(Rc::new(render));
+        self
+    }
+
+    /// ARIA attributes for this column's header.
+    pub fn aria_attrs(&self, sort: Option<SortDirection>) -> Vec<(String, String)> {
+        crate::a11y::table::columnheader_attrs(
+            self.sortable
+                .then(|| sort.map_or("none", SortDirection::as_str)),
+        )
+    }
+}
+
+/// A row of a [`Table`]: its id and the text of each cell, by column key.
+#[derive(Clone, Debug)]
+pub struct TableRow {
+    pub id: SharedString,
+    cells: Vec<(SharedString, SharedString)>,
+}
+
+impl TableRow {
+    pub fn new(id: impl Into<SharedString>) -> Self {
+        Self {
+            id: id.into(),
+            cells: Vec::new(),
+        }
+    }
+
+    pub fn cell(mut self, key: impl Into<SharedString>, text: impl Into<SharedString>) -> Self {
+        self.cells.push((key.into(), text.into()));
+        self
+    }
+
+    /// The text of the cell in column `key`.
+    pub fn get(&self, key: &str) -> Option<&SharedString> {
+        self.cells
+            .iter()
+            .find(|(column, _)| column == key)
+            .map(|(_, text)| text)
+    }
+}
+
+#[derive(IntoElement)]
+pub struct Table {
+    element_id: ElementId,
+    base: Div,
+    columns: Vec<TableColumn>,
+    rows: Vec<TableRow>,
+    default_sort: Option<(SharedString, SortDirection)>,
+    striped: bool,
+    selectable: bool,
+    multiple: bool,
+    virtualized: bool,
+    on_sort: Option<SortFn>,
+    on_selection_change: Option<SelectionFn>,
+}
+
+impl Default for Table {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Table {
+    pub fn new() -> Self {
+        Self {
+            element_id: "ui:table".into(),
+            base: div(),
+            columns: Vec::new(),
+            rows: Vec::new(),
+            default_sort: None,
+            striped: false,
+            selectable: false,
+            multiple: false,
+            virtualized: false,
+            on_sort: None,
+            on_selection_change: None,
+        }
+    }
+
+    pub fn id(mut self, id: impl Into<ElementId>) -> Self {
+        self.element_id = id.into();
+        self
+    }
+
+    /// Alias for `id(...)`. Use `key(...)` when you want to emphasize state identity.
+    pub fn key(self, key: impl Into<ElementId>) -> Self {
+        self.id(key)
+    }
+
+    pub fn column(mut self, column: TableColumn) -> Self {
+        self.columns.push(column);
+        self
+    }
+
+    pub fn columns(mut self, columns: impl IntoIterator<Item = TableColumn>) -> Self {
+        self.columns.extend(columns);
+        self
+    }
+
+    pub fn row(mut self, row: TableRow) -> Self {
+        self.rows.push(row);
+        self
+    }
+
+    pub fn rows(mut self, rows: impl IntoIterator<Item = TableRow>) -> Self {
+        self.rows.extend(rows);
+        self
+    }
+
+    /// The column sorted by on first render.
+    pub fn default_sort(mut self, key: impl Into<SharedString>, direction: SortDirection) -> Self {
+        self.default_sort = Some((key.into(), direction));
+        self
+    }
+
+    /// Shades every other row.
+    pub fn striped(mut self, striped: bool) -> Self {
+        self.striped = striped;
+        self
+    }
+
+    /// Lets rows be selected by clicking them.
+    pub fn selectable(mut self, selectable: bool) -> Self {
+        self.selectable = selectable;
+        self
+    }
+
+    /// Lets Ctrl/Cmd-click add rows to the selection (default: false).
+    pub fn multiple(mut self, multiple: bool) -> Self {
+        self.multiple = multiple;
+        self
+    }
+
+    /// Renders only the rows in view. The table needs a definite height.
+    pub fn virtualized(mut self, virtualized: bool) -> Self {
+        self.virtualized = virtualized;
+        self
+    }
+
+    /// Called with the column key and direction when a sortable header is
+    /// clicked. The table then leaves the row order to the caller.
+    pub fn on_sort<F>(mut self, handler: F) -> Self
+    where
+        F: 'static + Fn(&SharedString, SortDirection, &mut Window, &mut App),
+    {
+        self.on_sort = Some(Rc::new(handler));
+        self
+    }
+
+    /// Called with the ids of the selected rows whenever the selection changes.
+    pub fn on_selection_change<F>(mut self, handler: F) -> Self
+    where
+        F: 'static + Fn(&[SharedString], &mut Window, &mut App),
+    {
+        self.on_selection_change = Some(Rc::new(handler));
+        self
+    }
+
+    /// ARIA attributes for the table.
+    pub fn aria_attrs(&self) -> Vec<(String, String)> {
+        crate::a11y::table::grid_attrs(
+            self.rows.len() + 1,
+            self.columns.len(),
+            self.selectable && self.multiple,
+        )
+    }
+}
+
+impl Styled for Table {
+    fn style(&mut self) -> &mut gpui::StyleRefinement {
+        self.base.style()
+    }
+}
+
+impl RenderOnce for Table {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let id = self.element_id;
+        let theme = cx.theme().clone();
+        let rtl = cx.text_direction().is_rtl();
+        let selectable = self.selectable;
+        let multiple = self.multiple;
+        let striped = self.striped;
+
+        let sort = window.use_keyed_state((id.clone(), "ui:table:sort"), cx, {
+            let default_sort = self.default_sort.clone();
+            move |_, _| default_sort
+        });
+        let selection = window.use_keyed_state((id.clone(), "ui:table:selection"), cx, |_, _| {
+            HashSet::<SharedString>::new()
+        });
+        let widths = window.use_keyed_state((id.clone(), "ui:table:widths"), cx, |_, _| {
+            HashMap::<SharedString, Pixels>::new()
+        });
+        let resize = window.use_keyed_state((id.clone(), "ui:table:resize"), cx, |_, _| {
+            None::<ColumnResize>
+        });
+
+        let current_sort = sort.read(cx).clone();
+        let mut rows = self.rows;
+        if self.on_sort.is_none()
+            && let Some((key, direction)) = &current_sort
+        {
+            sort_rows(&mut rows, key, *direction);
+        }
+        let rows = Rc::new(rows);
+
+        let columns = Rc::new(self.columns);
+        let resolved = {
+            let widths = widths.read(cx);
+            Rc::new(
+                columns
+                    .iter()
+                    .map(|column| widths.get(&column.key).copied().or(column.width))
+                    .collect::<Vec<_>>(),
+            )
+        };
+
+        let header_cells = columns
+            .iter()
+            .enumerate()
+            .map(|(index, column)| {
+                let sorted = current_sort
+                    .as_ref()
+                    .filter(|(key, _)| key == &column.key)
+                    .map(|(_, direction)| *direction);
+                let header_bounds = window.use_keyed_state(
+                    (id.clone(), format!("ui:table:header:{}", column.key)),
+                    cx,
+                    |_, _| Bounds::default(),
+                );
+
+                let cell = sized(div(), resolved[index])
+                    .id(("ui:table:header", index))
+                    .relative()
+                    .h_full()
+                    .px_3()
+                    .flex()
+                    .items_center()
+                    .gap_1()
+                    .when(rtl, |this| this.flex_row_reverse())
+                    .map(|this| justify(this, column.align, rtl))
+                    .text_sm()
+                    .text_color(theme.content.secondary)
+                    .child(div().truncate().child(column.header.clone()))
+                    .when_some(sorted, |this, direction| {
+                        let arrow = match direction {
+                            SortDirection::Ascending => ArrowDirection::Up,
+                            SortDirection::Descending => ArrowDirection::Down,
+                        };
+                        this.child(
+                            icon(IconName::Arrow(arrow))
+                                .size(px(12.))
+                                .color(theme.content.secondary),
+                        )
+                    })
+                    .when(column.sortable, |this| {
+                        let sort = sort.clone();
+                        let on_sort = self.on_sort.clone();
+                        let key = column.key.clone();
+                        this.cursor_pointer()
+                            .hover(|this| this.bg(theme.surface.hover))
+                            .on_click(move |_ev, window, cx| {
+                                let direction = sort.update(cx, |sort, cx| {
+                                    let direction = next_sort(sort.as_ref(), &key);
+                                    *sort = Some((key.clone(), direction));
+                                    cx.notify();
+                                    direction
+                                });
+                                if let Some(handler) = &on_sort {
+                                    handler(&key, direction, window, cx);
+                                }
+                                window.refresh();
+                            })
+                    })
+                    .when(column.resizable, |this| {
+                        this.child(resize_handle(
+                            &id,
+                            index,
+                            column.key.clone(),
+                            header_bounds.clone(),
+                            &widths,
+                            &resize,
+                            rtl,
+                        ))
+                    });
+
+                BoundsTrackerElement {
+                    bounds_state: header_bounds,
+                    inner: cell.into_any_element(),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let header = div()
+            .flex()
+            .when(rtl, |this| this.flex_row_reverse())
+            .flex_none()
+            .h(ROW_HEIGHT)
+            .bg(theme.surface.sunken)
+            .border_b_1()
+            .border_color(theme.border.divider)
+            .children(header_cells);
+
+        let render_row: RowFn = {
+            let rows = rows.clone();
+            let theme = theme.clone();
+            let selection = selection.clone();
+            let on_selection_change = self.on_selection_change.clone();
+            Rc::new(move |index, window, cx| {
+                let Some(row) = rows.get(index) else {
+                    return Empty.into_any_element();
+                };
+                let selected = selection.read(cx).contains(&row.id);
+                let cells = columns
+                    .iter()
+                    .zip(resolved.iter())
+                    .map(|(column, width)| {
+                        let content = match &column.render {
+                            Some(render) => render(row, window, cx),
+                            None => div()
+                                .truncate()
+                                .child(row.get(&column.key).cloned().unwrap_or_default())
+                                .into_any_element(),
+                        };
+                        sized(div(), *width)
+                            .h_full()
+                            .px_3()
+                            .flex()
+                            .items_center()
+                            .map(|this| justify(this, column.align, rtl))
+                            .child(content)
+                    })
+                    .collect::<Vec<_>>();
+
+                div()
+                    .id(("ui:table:row", index))
+                    .flex()
+                    .when(rtl, |this| this.flex_row_reverse())
+                    .h(ROW_HEIGHT)
+                    .text_sm()
+                    .text_color(theme.content.primary)
+                    .border_b_1()
+                    .border_color(theme.border.divider)
+                    .when(striped && index % 2 == 1, |this| {
+                        this.bg(theme.surface.raised)
+                    })
+                    .when(selected, |this| this.bg(theme.action.neutral.active_bg))
+                    .when(!selected, |this| {
+                        this.hover(|this| this.bg(theme.surface.hover))
+                    })
+                    .when(selectable, |this| {
+                        let selection = selection.clone();
+                        let on_selection_change = on_selection_change.clone();
+                        let rows = rows.clone();
+                        let row_id = row.id.clone();
+                        this.cursor_pointer().on_click(move |ev, window, cx| {
+                            let modifiers = ev.modifiers();
+                            let additive = multiple && (modifiers.control || modifiers.platform);
+                            let changed = selection.update(cx, |selection, cx| {
+                                cx.notify();
+                                select_row(selection, &row_id, additive)
+                            });
+                            if changed && let Some(handler) = &on_selection_change {
+                                let selection = selection.read(cx);
+                                let ids = rows
+                                    .iter()
+                                    .filter(|row| selection.contains(&row.id))
+                                    .map(|row| row.id.clone())
+                                    .collect::<Vec<_>>();
+                                handler(&ids, window, cx);
+                            }
+                            window.refresh();
+                        })
+                    })
+                    .children(cells)
+                    .into_any_element()
+            })
+        };
+
+        let body = if self.virtualized {
+            let list = window.use_keyed_state((id.clone(), "ui:table:list"), cx, |_, _| {
+                virtual_list_state(rows.len(), ListAlignment::Top, ROW_HEIGHT * 10.)
+            });
+            let state = list.read(cx).clone();
+            if state.item_count() != rows.len() {
+                state.reset(rows.len());
+            }
+            let render_row = render_row.clone();
+            virtual_list(state, move |index, window, cx| {
+                render_row(index, window, cx)
+            })
+            .id((id.clone(), "ui:table:body"))
+            .flex_1()
+            .into_any_element()
+        } else {
+            let rows = (0..rows.len())
+                .map(|index| render_row(index, window, cx))
+                .collect::<Vec<_>>();
+            div()
+                .id((id.clone(), "ui:table:body"))
+                .flex_1()
+                .overflow_y_scroll()
+                .children(rows)
+                .into_any_element()
+        };
+
+        self.base
+            .flex()
+            .flex_col()
+            .overflow_hidden()
+            .rounded_md()
+            .border_1()
+            .border_color(theme.border.default)
+            .bg(theme.surface.base)
+            .child(header)
+            .child(body)
+    }
+}
+
+/// Gives a cell its column's width, or a share of the remaining space.
+fn sized(cell: Div, width: Option<Pixels>) -> Div {
+    match width {
+        Some(width) => cell.w(width).flex_none(),
+        None => cell.flex_1().min_w(MIN_COLUMN_WIDTH),
+    }
+}
+
+fn justify<E: Styled>(cell: E, align: ColumnAlign, rtl: bool) -> E {
+    match (align, rtl) {
+        (ColumnAlign::Center, _) => cell.justify_center(),
+        (ColumnAlign::Start, false) | (ColumnAlign::End, true) => cell.justify_start(),
+        (ColumnAlign::End, false) | (ColumnAlign::Start, true) => cell.justify_end(),
+    }
+}
+
+/// Drag payload of a column edge being dragged.
+struct ColumnDrag {
+    table: ElementId,
+    column: usize,
+}
+
+/// Where a column resize started.
+#[derive(Clone, Copy)]
+struct ColumnResize {
+    origin_x: Pixels,
+    origin_width: Pixels,
+}
+
+/// The handle on a header cell's trailing edge that resizes its column.
+fn resize_handle(
+    id: &ElementId,
+    index: usize,
+    key: SharedString,
+    header_bounds: gpui::Entity<Bounds<Pixels>>,
+    widths: &gpui::Entity<HashMap<SharedString, Pixels>>,
+    resize: &gpui::Entity<Option<ColumnResize>>,
+    rtl: bool,
+) -> impl IntoElement {
+    let widths = widths.clone();
+    let resize_for_start = resize.clone();
+    let resize_for_end = resize.clone();
+    let resize = resize.clone();
+    let table = id.clone();
+    div()
+        .id((id.clone(), format!("ui:table:resize:{index}")))
+        .absolute()
+        .top_0()
+        .bottom_0()
+        .w(RESIZE_HANDLE_WIDTH)
+        .map(|this| {
+            if rtl {
+                this.left(-RESIZE_HANDLE_WIDTH / 2.)
+            } else {
+                this.right(-RESIZE_HANDLE_WIDTH / 2.)
+            }
+        })
+        .cursor(CursorStyle::ResizeColumn)
+        .on_mouse_down(
+            MouseButton::Left,
+            move |ev: &MouseDownEvent, _window, cx| {
+                // Keep the header from sorting.
+                cx.stop_propagation();
+                let origin_width = header_bounds.read(cx).size.width;
+                resize_for_start.update(cx, |resize, _| {
+                    *resize = Some(ColumnResize {
+                        origin_x: ev.position.x,
+                        origin_width,
+                    });
+                });
+            },
+        )
+        .on_drag(
+            ColumnDrag {
+                table: id.clone(),
+                column: index,
+            },
+            |_drag: &ColumnDrag, _pos, _window, cx| cx.new(|_| Empty),
+        )
+        .on_drag_move::<ColumnDrag>(move |ev, window, cx| {
+            let dragged = ev.drag(cx);
+            if dragged.table != table || dragged.column != index {
+                return;
+            }
+            let Some(drag) = *resize.read(cx) else {
+                return;
+            };
+            let delta = ev.event.position.x - drag.origin_x;
+            let width = resized_width(drag.origin_width, delta, rtl);
+            widths.update(cx, |widths, _| {
+                widths.insert(key.clone(), width);
+            });
+            window.refresh();
+        })
+        .on_mouse_up_out(MouseButton::Left, move |_ev, _window, cx| {
+            resize_for_end.update(cx, |resize, _| *resize = None);
+        })
+}
+
+/// A column's width after dragging its trailing edge by `delta`.
+fn resized_width(origin: Pixels, delta: Pixels, rtl: bool) -> Pixels {
+    let delta = if rtl { -delta } else { delta };
+    (origin + delta).max(MIN_COLUMN_WIDTH)
+}
+
+/// The direction of sorting by `key` after its header is clicked.
+fn next_sort(current: Option<&(SharedString, SortDirection)>, key: &SharedString) -> SortDirection {
+    match current {
+        Some((sorted, direction)) if sorted == key => direction.reversed(),
+        _ => SortDirection::Ascending,
+    }
+}
+
+/// Sorts rows by the text of column `key`: numerically when both cells are
+/// numbers, otherwise case-insensitively. Rows without the cell go last.
+fn sort_rows(rows: &mut [TableRow], key: &str, direction: SortDirection) {
+    rows.sort_by(|a, b| {
+        let ordering = match (a.get(key), b.get(key)) {
+            (Some(a), Some(b)) => compare_cells(a, b),
+            (Some(_), None) => return Ordering::Less,
+            (None, Some(_)) => return Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        };
+        match direction {
+            SortDirection::Ascending => ordering,
+            SortDirection::Descending => ordering.reverse(),
+        }
+    });
+}
+
+fn compare_cells(a: &str, b: &str) -> Ordering {
+    match (a.trim().parse::<f64>(), b.trim().parse::<f64>()) {
+        (Ok(a), Ok(b)) => a.total_cmp(&b),
+        _ => a.to_lowercase().cmp(&b.to_lowercase()),
+    }
+}
+
+/// Applies a click on row `id`. Returns whether the selection changed.
+///
+/// An additive click toggles the row; a plain one selects only it.
+fn select_row(selection: &mut HashSet<SharedString>, id: &SharedString, additive: bool) -> bool {
+    if additive {
+        if !selection.remove(id) {
+            selection.insert(id.clone());
+        }
+        return true;
+    }
+    if selection.len() == 1 && selection.contains(id) {
+        return false;
+    }
+    selection.clear();
+    selection.insert(id.clone());
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(rows: &[TableRow]) -> Vec<&str> {
+        rows.iter().map(|row| row.id.as_ref()).collect()
+    }
+
+    #[test]
+    fn rows_sort_by_cell_text() {
+        let mut rows = vec![
+            TableRow::new("a").cell("name", "beta").cell("size", "10"),
+            TableRow::new("b").cell("name", "Alpha").cell("size", "9"),
+            TableRow::new("c").cell("name", "gamma"),
+        ];
+        sort_rows(&mut rows, "name", SortDirection::Ascending);
+        assert_eq!(ids(&rows), ["b", "a", "c"]);
+        sort_rows(&mut rows, "size", SortDirection::Ascending);
+        assert_eq!(ids(&rows), ["b", "a", "c"]);
+        sort_rows(&mut rows, "size", SortDirection::Descending);
+        assert_eq!(ids(&rows), ["a", "b", "c"]);
+
+        let name: SharedString = "name".into();
+        let sorted = (name.clone(), SortDirection::Ascending);
+        assert_eq!(next_sort(Some(&sorted), &name), SortDirection::Descending);
+        assert_eq!(
+            next_sort(Some(&sorted), &"size".into()),
+            SortDirection::Ascending
+        );
+        assert_eq!(next_sort(None, &name), SortDirection::Ascending);
+    }
+
+    #[test]
+    fn clicks_select_rows() {
+        let mut selection = HashSet::new();
+        assert!(select_row(&mut selection, &"a".into(), false));
+        assert!(!select_row(&mut selection, &"a".into(), false));
+        assert!(select_row(&mut selection, &"b".into(), true));
+        assert_eq!(selection.len(), 2);
+        assert!(select_row(&mut selection, &"a".into(), true));
+        assert_eq!(selection, HashSet::from(["b".into()]));
+        assert!(select_row(&mut selection, &"c".into(), false));
+        assert_eq!(selection, HashSet::from(["c".into()]));
+    }
+
+    #[test]
+    fn column_resize_clamps_to_min_width() {
+        assert_eq!(resized_width(px(100.), px(30.), false), px(130.));
+        assert_eq!(resized_width(px(100.), px(30.), true), px(70.));
+        assert_eq!(resized_width(px(100.), px(-90.), false), MIN_COLUMN_WIDTH);
+    }
+}
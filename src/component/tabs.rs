@@ -0,0 +1,576 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
+
+use gpui::{
+    Animation, AnimationExt, AnyElement, App, Bounds, Div, ElementId, FocusHandle, Hsla,
+    InteractiveElement, IntoElement, KeyDownEvent, ParentElement, Pixels, RenderOnce, SharedString,
+    StatefulInteractiveElement, Styled, Window, div, prelude::FluentBuilder, px,
+};
+
+use crate::{
+    a11y::{Orientation, RovingFocus},
+    animation::{animate_value, ease_out_quint_clamped},
+    component::{
+        BoundsTrackerElement, DropdownItem, DropdownMenuItem, IconPath, dropdown_menu, icon,
+    },
+    i18n::{I18nContext, defaults::DefaultPlaceholders},
+    theme::ActiveTheme,
+};
+
+/// Creates a new tabs element.
+///
+/// Tabs switch between views that share one place on screen. The strip is a
+/// single tab stop: Left/Right move between enabled tabs (mirrored for
+/// right-to-left text) and Home/End jump to the first or last one, selecting
+/// as they go.
+///
+/// # Example
+/// ```rust,ignore
+/// use yororen_ui::component::{tabs, TabItem};
+///
+/// tabs("settings-tabs")
+///     .item(TabItem::new("general", "General").content(|_window, _cx| general().into_any_element()))
+///     .item(TabItem::new("advanced", "Advanced").disabled(true))
+///     .on_change(|id, _window, _cx| println!("selected {id}"));
+/// ```
+///
+/// # Accessibility
+///
+/// gpui doesn't expose an accessibility tree yet; [`Tabs::aria_attrs`],
+/// [`TabItem::aria_attrs`] and [`crate::a11y::tabs::tabpanel_attrs`] describe the
+/// `tablist`/`tab`/`tabpanel` roles for when it does.
+pub fn tabs(id: impl Into<ElementId>) -> Tabs {
+    Tabs::new().id(id)
+}
+
+type ChangeFn = Arc<dyn Fn(&ElementId, &mut Window, &mut App)>;
+type PanelFn = Rc<dyn Fn(&mut Window, &mut App) -> AnyElement>;
+type SelectFn = Rc<dyn Fn(&ElementId, &mut Window, &mut App)>;
+
+/// One tab of a [`Tabs`] strip.
+#[derive(Clone)]
+pub struct TabItem {
+    pub id: ElementId,
+    pub label: SharedString,
+    pub icon: Option<SharedString>,
+    pub disabled: bool,
+    content: Option<PanelFn>,
+}
+
+impl TabItem {
+    pub fn new(id: impl Into<ElementId>, label: impl Into<SharedString>) -> Self {
+        Self {
+            id: id.into(),
+            label: label.into(),
+            icon: None,
+            disabled: false,
+            content: None,
+        }
+    }
+
+    pub fn icon(mut self, icon: impl Into<IconPath>) -> Self {
+        self.icon = Some(icon.into().into());
+        self
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Renders the panel shown below the strip while this tab is selected.
+    pub fn content<F>(mut self, render: F) -> Self
+    where
+        F: 'static + Fn(&mut Window, &mut App) -> AnyElement,
+    {
+        self.content = Some(Rc::new(render));
+        self
+    }
+
+    /// ARIA attributes for this tab.
+    pub fn aria_attrs(&self, selected: bool) -> Vec<(String, String)> {
+        let panel = self.content.as_ref().map(|_| self.id.to_string());
+        crate::a11y::tabs::tab_attrs(selected, self.disabled, panel.as_deref())
+    }
+}
+
+/// What the strip does with tabs that don't fit.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TabsOverflow {
+    /// Scroll the strip horizontally.
+    #[default]
+    Scroll,
+    /// Show this many tabs and move the rest into a "More" menu. The selected
+    /// tab is always shown.
+    Menu(usize),
+}
+
+#[derive(IntoElement)]
+pub struct Tabs {
+    element_id: ElementId,
+    base: Div,
+    items: Vec<TabItem>,
+    active: Option<ElementId>,
+    default_active: Option<ElementId>,
+    overflow: TabsOverflow,
+    indicator_color: Option<Hsla>,
+    on_change: Option<ChangeFn>,
+}
+
+impl Default for Tabs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tabs {
+    pub fn new() -> Self {
+        Self {
+            element_id: "ui:tabs".into(),
+            base: div(),
+            items: Vec::new(),
+            active: None,
+            default_active: None,
+            overflow: TabsOverflow::default(),
+            indicator_color: None,
+            on_change: None,
+        }
+    }
+
+    pub fn id(mut self, id: impl Into<ElementId>) -> Self {
+        self.element_id = id.into();
+        self
+    }
+
+    /// Alias for `id(...)`. Use `key(...)` when you want to emphasize state identity.
+    pub fn key(self, key: impl Into<ElementId>) -> Self {
+        self.id(key)
+    }
+
+    pub fn item(mut self, item: TabItem) -> Self {
+        self.items.push(item);
+        self
+    }
+
+    pub fn items(mut self, items: impl IntoIterator<Item = TabItem>) -> Self {
+        self.items.extend(items);
+        self
+    }
+
+    /// Selects the tab with this id. The caller then owns the selection and
+    /// should update it from [`on_change`](Self::on_change).
+    pub fn active(mut self, id: impl Into<ElementId>) -> Self {
+        self.active = Some(id.into());
+        self
+    }
+
+    /// The tab selected initially when the selection is managed internally.
+    /// Defaults to the first enabled tab.
+    pub fn default_active(mut self, id: impl Into<ElementId>) -> Self {
+        self.default_active = Some(id.into());
+        self
+    }
+
+    pub fn overflow(mut self, overflow: TabsOverflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    pub fn indicator_color(mut self, color: impl Into<Hsla>) -> Self {
+        self.indicator_color = Some(color.into());
+        self
+    }
+
+    pub fn on_change<F>(mut self, handler: F) -> Self
+    where
+        F: 'static + Fn(&ElementId, &mut Window, &mut App),
+    {
+        self.on_change = Some(Arc::new(handler));
+        self
+    }
+
+    /// ARIA attributes for the tab strip.
+    pub fn aria_attrs(&self) -> Vec<(String, String)> {
+        crate::a11y::tabs::tablist_attrs()
+    }
+}
+
+impl ParentElement for Tabs {
+    fn extend(&mut self, elements: impl IntoIterator<Item = AnyElement>) {
+        self.base.extend(elements);
+    }
+}
+
+impl Styled for Tabs {
+    fn style(&mut self) -> &mut gpui::StyleRefinement {
+        self.base.style()
+    }
+}
+
+impl InteractiveElement for Tabs {
+    fn interactivity(&mut self) -> &mut gpui::Interactivity {
+        self.base.interactivity()
+    }
+}
+
+impl StatefulInteractiveElement for Tabs {}
+
+impl RenderOnce for Tabs {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let id = self.element_id;
+        let items = self.items;
+        let theme = cx.theme().clone();
+        let rtl = cx.text_direction().is_rtl();
+
+        let is_controlled = self.active.is_some();
+        let first_enabled = items
+            .iter()
+            .find(|item| !item.disabled)
+            .map(|item| item.id.clone());
+        let internal_active = window.use_keyed_state((id.clone(), "ui:tabs:active"), cx, {
+            let initial = self.default_active.clone().or(first_enabled.clone());
+            move |_, _| initial
+        });
+        let active = self
+            .active
+            .clone()
+            .or_else(|| internal_active.read(cx).clone())
+            .filter(|active| items.iter().any(|item| &item.id == active))
+            .or(first_enabled);
+
+        let focus_handles = window
+            .use_keyed_state((id.clone(), "ui:tabs:focus"), cx, |_, _| {
+                HashMap::<ElementId, FocusHandle>::new()
+            })
+            .update(cx, |handles, cx| {
+                handles.retain(|tab, _| items.iter().any(|item| &item.id == tab));
+                for item in &items {
+                    handles
+                        .entry(item.id.clone())
+                        .or_insert_with(|| cx.focus_handle());
+                }
+                handles.clone()
+            });
+
+        let mut roving = RovingFocus::new(Orientation::Horizontal).rtl(rtl);
+        roving.set_items(
+            items
+                .iter()
+                .filter(|item| !item.disabled)
+                .map(|item| item.id.clone()),
+        );
+        if let Some(active) = &active {
+            roving.set_active(active);
+        }
+
+        let select: SelectFn = {
+            let internal_active = internal_active.clone();
+            let on_change = self.on_change.clone();
+            let focus_handles = focus_handles.clone();
+            Rc::new(move |tab: &ElementId, window, cx| {
+                if let Some(handle) = focus_handles.get(tab) {
+                    handle.focus(window);
+                }
+                if !is_controlled {
+                    internal_active.update(cx, |active, cx| {
+                        *active = Some(tab.clone());
+                        cx.notify();
+                    });
+                }
+                if let Some(handler) = &on_change {
+                    handler(tab, window, cx);
+                }
+                window.refresh();
+            })
+        };
+
+        // The indicator follows the selected tab's measured bounds, which are
+        // only known after layout, so a new selection settles a frame later.
+        let strip_bounds =
+            window.use_keyed_state((id.clone(), "ui:tabs:strip-bounds"), cx, |_, _| {
+                Bounds::default()
+            });
+        let active_bounds =
+            window.use_keyed_state((id.clone(), "ui:tabs:active-bounds"), cx, |_, _| {
+                Bounds::default()
+            });
+        let indicator_state =
+            window.use_keyed_state((id.clone(), "ui:tabs:indicator"), cx, |_, _| {
+                IndicatorState::default()
+            });
+        if let Some(active) = &active {
+            let strip = *strip_bounds.read(cx);
+            let tab = *active_bounds.read(cx);
+            let measured = (tab.origin.x - strip.origin.x, tab.size.width);
+            let settled = indicator_state.update(cx, |state, _| state.update(active, measured));
+            if !settled {
+                window.request_animation_frame();
+            }
+        }
+        let indicator = indicator_state.read(cx).clone();
+
+        let active_ix = active
+            .as_ref()
+            .and_then(|active| items.iter().position(|item| &item.id == active));
+        let (shown, hidden) = match self.overflow {
+            TabsOverflow::Scroll => ((0..items.len()).collect(), Vec::new()),
+            TabsOverflow::Menu(max) => strip_indices(items.len(), active_ix, max),
+        };
+
+        let tab_elements = shown
+            .into_iter()
+            .map(|ix| {
+                let item = &items[ix];
+                let selected = Some(ix) == active_ix;
+                let focus = focus_handles[&item.id]
+                    .clone()
+                    .tab_stop(roving.is_tab_stop(&item.id));
+                let focused = focus.is_focused(window);
+                let disabled = item.disabled;
+                let select = select.clone();
+                let tab_id = item.id.clone();
+
+                let tab = div()
+                    .id(item.id.clone())
+                    .track_focus(&focus)
+                    .flex()
+                    .flex_none()
+                    .items_center()
+                    .gap_2()
+                    .px_3()
+                    .py_2()
+                    .rounded_sm()
+                    .border_1()
+                    .border_color(if focused {
+                        theme.border.focus
+                    } else {
+                        gpui::transparent_black()
+                    })
+                    .text_sm()
+                    .text_color(if disabled {
+                        theme.content.disabled
+                    } else if selected {
+                        theme.content.primary
+                    } else {
+                        theme.content.secondary
+                    })
+                    .when(disabled, |this| this.cursor_not_allowed())
+                    .when(!disabled, |this| {
+                        this.cursor_pointer()
+                            .hover(|this| this.bg(theme.surface.hover))
+                            .on_click(move |_ev, window, cx| select(&tab_id, window, cx))
+                    })
+                    .when_some(item.icon.clone(), |this, path| {
+                        this.child(icon(path).size(px(14.)).inherit_color(true))
+                    })
+                    .child(item.label.clone());
+
+                if selected {
+                    BoundsTrackerElement {
+                        bounds_state: active_bounds.clone(),
+                        inner: tab.into_any_element(),
+                    }
+                    .into_any_element()
+                } else {
+                    tab.into_any_element()
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let more = (!hidden.is_empty()).then(|| {
+            let select = select.clone();
+            let hidden_ids = hidden
+                .iter()
+                .map(|&ix| items[ix].id.clone())
+                .collect::<Vec<_>>();
+            dropdown_menu((id.clone(), "ui:tabs:more"))
+                .label(DefaultPlaceholders::tabs_more(cx.i18n().locale()))
+                .items(hidden.iter().map(|&ix| {
+                    let item = &items[ix];
+                    DropdownItem::Item(
+                        DropdownMenuItem::new(ix.to_string(), item.label.clone())
+                            .disabled(item.disabled),
+                    )
+                }))
+                .on_select(move |value, _ev, window, cx| {
+                    let tab = value
+                        .parse::<usize>()
+                        .ok()
+                        .and_then(|ix| hidden.iter().position(|&hidden| hidden == ix))
+                        .map(|pos| hidden_ids[pos].clone());
+                    if let Some(tab) = tab {
+                        select(&tab, window, cx);
+                    }
+                })
+        });
+
+        let strip = div()
+            .id((id.clone(), "ui:tabs:strip"))
+            .flex()
+            .items_center()
+            .gap_1()
+            .when(self.overflow == TabsOverflow::Scroll, |this| {
+                this.overflow_x_scroll()
+            })
+            .on_key_down({
+                let select = select.clone();
+                move |ev: &KeyDownEvent, window, cx| {
+                    let mut roving = roving.clone();
+                    if let Some(tab) = roving.handle_key(&ev.keystroke.key).cloned() {
+                        cx.stop_propagation();
+                        select(&tab, window, cx);
+                    }
+                }
+            })
+            .children(tab_elements)
+            .children(more);
+
+        let indicator_color = self.indicator_color.unwrap_or(theme.action.primary.bg);
+        let indicator_el = indicator.target.as_ref().map(|(_, left, width)| {
+            let (left, width) = (*left, *width);
+            let bar = div()
+                .absolute()
+                .bottom_0()
+                .left(left)
+                .w(width)
+                .h(px(2.))
+                .rounded_full()
+                .bg(indicator_color);
+            match indicator.from {
+                Some((from_left, from_width)) => bar
+                    .with_animation(
+                        (
+                            id.clone(),
+                            format!("ui:tabs:indicator:{}", indicator.generation),
+                        ),
+                        Animation::new(Duration::from_millis(200))
+                            .with_easing(ease_out_quint_clamped),
+                        move |this, delta| {
+                            this.left(animate_value(from_left, left, delta))
+                                .w(animate_value(from_width, width, delta))
+                        },
+                    )
+                    .into_any_element(),
+                None => bar.into_any_element(),
+            }
+        });
+
+        let panel = active_ix
+            .and_then(|ix| items[ix].content.clone())
+            .map(|render| {
+                div()
+                    .id((id.clone(), "ui:tabs:panel"))
+                    .pt_3()
+                    .child(render(window, cx))
+            });
+
+        self.base
+            .id(id.clone())
+            .flex()
+            .flex_col()
+            .child(BoundsTrackerElement {
+                bounds_state: strip_bounds,
+                inner: div()
+                    .relative()
+                    .overflow_hidden()
+                    .border_b_1()
+                    .border_color(theme.border.divider)
+                    .child(strip)
+                    .children(indicator_el)
+                    .into_any_element(),
+            })
+            .children(panel)
+    }
+}
+
+/// Where the selection indicator sits and where it is sliding from.
+#[derive(Clone, Debug, Default)]
+struct IndicatorState {
+    /// The tab the indicator points at, with its left edge (relative to the
+    /// strip) and width.
+    target: Option<(ElementId, Pixels, Pixels)>,
+    /// Left edge and width to slide from after the selection changed.
+    from: Option<(Pixels, Pixels)>,
+    /// Bumped on every slide so the animation restarts.
+    generation: usize,
+}
+
+impl IndicatorState {
+    /// Points the indicator at `active`, measured at `(left, width)` on the
+    /// last frame. Returns `false` while that measurement may still belong to
+    /// the previously selected tab.
+    fn update(&mut self, active: &ElementId, measured: (Pixels, Pixels)) -> bool {
+        let (left, width) = measured;
+        if width <= Pixels::ZERO {
+            return false;
+        }
+        match &self.target {
+            None => {}
+            // Same tab, e.g. after a resize: jump there.
+            Some((id, old_left, old_width)) if id == active => {
+                if (*old_left, *old_width) == measured {
+                    return true;
+                }
+                self.from = None;
+            }
+            // The tracker still measured the previous tab.
+            Some((_, old_left, old_width)) if (*old_left, *old_width) == measured => return false,
+            Some((_, old_left, old_width)) => {
+                self.from = Some((*old_left, *old_width));
+                self.generation += 1;
+            }
+        }
+        self.target = Some((active.clone(), left, width));
+        true
+    }
+}
+
+/// Indices of the tabs shown in a strip of at most `max` tabs, and of those
+/// moved into the overflow menu. The active tab always takes the last visible
+/// slot if it would otherwise be hidden.
+fn strip_indices(len: usize, active: Option<usize>, max: usize) -> (Vec<usize>, Vec<usize>) {
+    let max = max.max(1);
+    if len <= max {
+        return ((0..len).collect(), Vec::new());
+    }
+    let mut shown = (0..max).collect::<Vec<_>>();
+    if let Some(active) = active.filter(|&active| active >= max) {
+        shown[max - 1] = active;
+    }
+    let hidden = (0..len).filter(|ix| !shown.contains(ix)).collect();
+    (shown, hidden)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overflow_menu_keeps_the_active_tab_visible() {
+        assert_eq!(strip_indices(3, Some(0), 5), (vec![0, 1, 2], vec![]));
+        assert_eq!(strip_indices(5, Some(1), 3), (vec![0, 1, 2], vec![3, 4]));
+        assert_eq!(strip_indices(5, Some(4), 3), (vec![0, 1, 4], vec![2, 3]));
+    }
+
+    #[test]
+    fn indicator_slides_once_the_new_tab_is_measured() {
+        let (a, b): (ElementId, ElementId) = ("a".into(), "b".into());
+        let mut state = IndicatorState::default();
+        assert!(state.update(&a, (px(0.), px(40.))));
+        assert_eq!(state.from, None);
+
+        // The frame the selection changes still measures tab `a`.
+        assert!(!state.update(&b, (px(0.), px(40.))));
+        assert_eq!(state.target, Some((a.clone(), px(0.), px(40.))));
+        assert!(state.update(&b, (px(48.), px(60.))));
+        assert_eq!(state.generation, 1);
+        assert_eq!(state.from, Some((px(0.), px(40.))));
+        assert_eq!(state.target, Some((b.clone(), px(48.), px(60.))));
+
+        // Layout changes without a new selection jump straight there.
+        assert!(state.update(&b, (px(50.), px(60.))));
+        assert_eq!(state.from, None);
+    }
+}
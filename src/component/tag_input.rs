@@ -0,0 +1,413 @@
+use std::rc::Rc;
+
+use gpui::{
+    App, Div, ElementId, Focusable, Hsla, InteractiveElement, IntoElement, ParentElement,
+    RenderOnce, SharedString, StatefulInteractiveElement, Styled, Window, div,
+    prelude::FluentBuilder, px,
+};
+
+use crate::{
+    component::{
+        TextInput, create_internal_state, tag, text_input, update_internal_state,
+        use_internal_state,
+    },
+    theme::ActiveTheme,
+};
+
+use super::text_input::{Backspace, Paste};
+
+/// Creates a new tag input element.
+///
+/// Typing a comma or pressing Enter turns the typed text into a removable
+/// chip. Backspace in an empty field removes the last chip, and pasting
+/// comma- or newline-separated text adds one chip per entry.
+///
+/// Entries are trimmed; empty entries, duplicates and entries rejected by
+/// `.validate()` are not added.
+///
+/// # Example
+/// ```rust,ignore
+/// use yororen_ui::component::tag_input;
+///
+/// tag_input("labels")
+///     .placeholder("Add a label")
+///     .validate(|tag| tag.len() <= 24)
+///     .on_change(|tags, _window, _cx| println!("{tags:?}"));
+/// ```
+pub fn tag_input(id: impl Into<ElementId>) -> TagInput {
+    TagInput::new().id(id)
+}
+
+type ChangeFn = Rc<dyn Fn(Vec<String>, &mut Window, &mut App)>;
+type ValidateFn = Rc<dyn Fn(&str) -> bool>;
+
+#[derive(IntoElement)]
+pub struct TagInput {
+    element_id: ElementId,
+    base: Div,
+    placeholder: SharedString,
+    value: Option<Vec<String>>,
+    disabled: bool,
+
+    bg: Option<Hsla>,
+    border: Option<Hsla>,
+    focus_border: Option<Hsla>,
+
+    validate: Option<ValidateFn>,
+    on_change: Option<ChangeFn>,
+}
+
+impl Default for TagInput {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TagInput {
+    pub fn new() -> Self {
+        Self {
+            element_id: "ui:tag-input".into(),
+            base: div(),
+            placeholder: "".into(),
+            value: None,
+            disabled: false,
+            bg: None,
+            border: None,
+            focus_border: None,
+            validate: None,
+            on_change: None,
+        }
+    }
+
+    pub fn id(mut self, id: impl Into<ElementId>) -> Self {
+        self.element_id = id.into();
+        self
+    }
+
+    /// Alias for `id(...)`. Use `key(...)` when you want to emphasize state identity.
+    pub fn key(self, key: impl Into<ElementId>) -> Self {
+        self.id(key)
+    }
+
+    pub fn placeholder(mut self, text: impl Into<SharedString>) -> Self {
+        self.placeholder = text.into();
+        self
+    }
+
+    /// Sets the tags, making the input controlled.
+    pub fn value<I, S>(mut self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.value = Some(tags.into_iter().map(Into::into).collect());
+        self
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    pub fn bg(mut self, color: impl Into<Hsla>) -> Self {
+        self.bg = Some(color.into());
+        self
+    }
+
+    pub fn border(mut self, color: impl Into<Hsla>) -> Self {
+        self.border = Some(color.into());
+        self
+    }
+
+    pub fn focus_border(mut self, color: impl Into<Hsla>) -> Self {
+        self.focus_border = Some(color.into());
+        self
+    }
+
+    /// Accepts or rejects each trimmed entry before it becomes a tag.
+    pub fn validate<F>(mut self, validate: F) -> Self
+    where
+        F: 'static + Fn(&str) -> bool,
+    {
+        self.validate = Some(Rc::new(validate));
+        self
+    }
+
+    /// Called with the full list of tags whenever a tag is added or removed.
+    pub fn on_change<F>(mut self, handler: F) -> Self
+    where
+        F: 'static + Fn(Vec<String>, &mut Window, &mut App),
+    {
+        self.on_change = Some(Rc::new(handler));
+        self
+    }
+}
+
+impl ParentElement for TagInput {
+    fn extend(&mut self, elements: impl IntoIterator<Item = gpui::AnyElement>) {
+        self.base.extend(elements);
+    }
+}
+
+impl Styled for TagInput {
+    fn style(&mut self) -> &mut gpui::StyleRefinement {
+        self.base.style()
+    }
+}
+
+impl InteractiveElement for TagInput {
+    fn interactivity(&mut self) -> &mut gpui::Interactivity {
+        self.base.interactivity()
+    }
+}
+
+impl StatefulInteractiveElement for TagInput {}
+
+impl RenderOnce for TagInput {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let id = self.element_id.clone();
+        let disabled = self.disabled;
+        let theme = cx.theme().clone();
+
+        let use_internal = use_internal_state(self.value.is_some(), self.on_change.is_some());
+        let internal_tags = create_internal_state(
+            window,
+            cx,
+            &id,
+            format!("{}:tags", id),
+            Vec::<String>::new(),
+            use_internal,
+        );
+        let tags = match &internal_tags {
+            Some(state) => state.read(cx).clone(),
+            None => self.value.unwrap_or_default(),
+        };
+
+        let input_id: ElementId = (id.clone(), "ui:tag-input:input").into();
+        let input_state = TextInput::state_handle(input_id.clone(), window, cx);
+        let focused = input_state.read(cx).focus_handle(cx).is_focused(window);
+
+        // Replaces the tag list and reports the change.
+        let set_tags: ChangeFn = {
+            let on_change = self.on_change.clone();
+            Rc::new(
+                move |next: Vec<String>, window: &mut Window, cx: &mut App| {
+                    update_internal_state(&internal_tags, cx, next.clone());
+                    if let Some(handler) = &on_change {
+                        handler(next, window, cx);
+                    }
+                },
+            )
+        };
+
+        // Adds the accepted `entries`, returning whether any were added.
+        let add = {
+            let tags = tags.clone();
+            let validate = self.validate.clone();
+            let set_tags = set_tags.clone();
+            Rc::new(
+                move |entries: Vec<&str>, window: &mut Window, cx: &mut App| -> bool {
+                    let next = add_tags(&tags, entries, validate.as_deref());
+                    let added = next.len() > tags.len();
+                    if added {
+                        set_tags(next, window, cx);
+                    }
+                    added
+                },
+            )
+        };
+
+        let chips = tags
+            .iter()
+            .enumerate()
+            .map(|(index, text)| {
+                let tags = tags.clone();
+                let set_tags = set_tags.clone();
+                div().id(("ui:tag-input:tag", index)).child(
+                    tag(text.clone())
+                        .closable(!disabled)
+                        .on_close(move |_, window, cx| {
+                            let mut next = tags.clone();
+                            next.remove(index);
+                            set_tags(next, window, cx);
+                        }),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let focus_border = self.focus_border.unwrap_or(theme.border.focus);
+        self.base
+            .id(id.clone())
+            .flex()
+            .flex_wrap()
+            .items_center()
+            .gap_1()
+            .w_full()
+            .min_h(px(36.))
+            .px_2()
+            .py_1()
+            .rounded_md()
+            .bg(self.bg.unwrap_or(theme.surface.base))
+            .border_1()
+            .border_color(self.border.unwrap_or(theme.border.default))
+            .when(!disabled && focused, |this| {
+                this.border_2().border_color(focus_border)
+            })
+            .when(disabled, |this| this.opacity(0.6).cursor_not_allowed())
+            .when(!disabled, |this| {
+                this.on_click({
+                    let input_state = input_state.clone();
+                    move |_, window, cx| {
+                        input_state.update(cx, |state, cx| state.focus(window, cx));
+                    }
+                })
+                .capture_action({
+                    let input_state = input_state.clone();
+                    let tags = tags.clone();
+                    let set_tags = set_tags.clone();
+                    move |_: &Backspace, window, cx| {
+                        if !input_state.read(cx).content().is_empty() || tags.is_empty() {
+                            return;
+                        }
+                        cx.stop_propagation();
+                        set_tags(tags[..tags.len() - 1].to_vec(), window, cx);
+                    }
+                })
+                .capture_action({
+                    let add = add.clone();
+                    move |_: &Paste, window, cx| {
+                        // Single values paste into the field as usual.
+                        let Some(text) = cx.read_from_clipboard().and_then(|item| item.text())
+                        else {
+                            return;
+                        };
+                        if !text.contains(is_separator) {
+                            return;
+                        }
+                        cx.stop_propagation();
+                        add(text.split(is_separator).collect(), window, cx);
+                    }
+                })
+            })
+            .children(chips)
+            .child(
+                div().flex_1().min_w(px(80.)).child(
+                    text_input(input_id)
+                        .placeholder(if tags.is_empty() {
+                            self.placeholder
+                        } else {
+                            SharedString::default()
+                        })
+                        .disabled(disabled)
+                        .height(px(26.).into())
+                        .px_1()
+                        .bg(theme.surface.base.alpha(0.0))
+                        .border(theme.border.default.alpha(0.0))
+                        .focus_border(theme.border.default.alpha(0.0))
+                        .text_color(theme.content.primary)
+                        .on_change({
+                            let input_state = input_state.clone();
+                            let add = add.clone();
+                            move |value, window, cx| {
+                                let Some((entries, rest)) = split_draft(&value) else {
+                                    return;
+                                };
+                                // A rejected entry stays in the field to be corrected.
+                                let draft = if add(entries, window, cx) {
+                                    rest.to_string()
+                                } else {
+                                    without_last_separator(&value)
+                                };
+                                input_state.update(cx, |state, cx| {
+                                    state.set_content(SharedString::from(draft));
+                                    cx.notify();
+                                });
+                            }
+                        })
+                        .on_submit({
+                            let input_state = input_state.clone();
+                            move |value, window, cx| {
+                                if add(vec![value.as_ref()], window, cx) {
+                                    input_state.update(cx, |state, cx| {
+                                        state.set_content(SharedString::default());
+                                        cx.notify();
+                                    });
+                                }
+                            }
+                        }),
+                ),
+            )
+    }
+}
+
+fn is_separator(c: char) -> bool {
+    c == ',' || c == '\n'
+}
+
+/// Splits typed text at its last separator into finished entries and the
+/// text still being typed, or `None` when there is no separator yet.
+fn split_draft(text: &str) -> Option<(Vec<&str>, &str)> {
+    let end = text.rfind(is_separator)?;
+    Some((text[..end].split(is_separator).collect(), &text[end + 1..]))
+}
+
+/// `text` without its last separator.
+fn without_last_separator(text: &str) -> String {
+    match text.rfind(is_separator) {
+        Some(end) => format!("{}{}", &text[..end], &text[end + 1..]),
+        None => text.to_string(),
+    }
+}
+
+/// Returns `tags` with each trimmed entry appended, skipping empty entries,
+/// duplicates and entries rejected by `validate`.
+fn add_tags(
+    tags: &[String],
+    entries: Vec<&str>,
+    validate: Option<&dyn Fn(&str) -> bool>,
+) -> Vec<String> {
+    let mut next = tags.to_vec();
+    for entry in entries {
+        let entry = entry.trim();
+        if entry.is_empty()
+            || next.iter().any(|tag| tag == entry)
+            || validate.is_some_and(|validate| !validate(entry))
+        {
+            continue;
+        }
+        next.push(entry.to_string());
+    }
+    next
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_draft_keeps_text_after_last_separator() {
+        assert_eq!(split_draft("rust"), None);
+        assert_eq!(split_draft("rust,"), Some((vec!["rust"], "")));
+        assert_eq!(split_draft("a, b\nc"), Some((vec!["a", " b"], "c")));
+        assert_eq!(without_last_separator("rust,"), "rust");
+        assert_eq!(without_last_separator("a,b,"), "a,b");
+    }
+
+    #[test]
+    fn add_tags_skips_empty_duplicate_and_invalid_entries() {
+        let tags = vec!["rust".to_string()];
+        let short = |tag: &str| tag.len() <= 4;
+        assert_eq!(
+            add_tags(
+                &tags,
+                vec![" go ", "", "rust", "go", "python"],
+                Some(&short)
+            ),
+            vec!["rust", "go"]
+        );
+        assert_eq!(
+            add_tags(&tags, vec!["python"], None),
+            vec!["rust", "python"]
+        );
+    }
+}
@@ -25,5 +25,13 @@ actions!(
         Paste,
         Cut,
         Copy,
+        Undo,
+        Redo,
+        WordLeft,
+        WordRight,
+        BackspaceWord,
+        DeleteWord,
+        Tab,
+        Backtab,
     ]
 );
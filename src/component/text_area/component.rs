@@ -3,26 +3,41 @@
 use std::sync::Arc;
 
 use gpui::{
-    App, CursorStyle, Div, ElementId, Hsla, InteractiveElement, IntoElement, MouseButton,
-    ParentElement, RenderOnce, SharedString, StatefulInteractiveElement, Styled, div,
+    App, CursorStyle, Div, ElementId, Entity, Hsla, InteractiveElement, IntoElement, MouseButton,
+    ParentElement, Pixels, RenderOnce, SharedString, StatefulInteractiveElement, Styled, div,
     prelude::FluentBuilder,
 };
 
 use super::actions::*;
 use super::element::TextAreaElement;
-use super::state::{EnterBehavior, TextAreaHandler, TextAreaState, WrapMode};
+use super::state::{EnterBehavior, TabBehavior, TextAreaHandler, TextAreaState, WrapMode};
 use crate::action_handler;
-use crate::theme::ActiveTheme;
+use crate::component::{
+    DEFAULT_SELECTION_OPACITY, WindowCallback, generate_element_id, help_text, label,
+    notify_focus_change, register_input, registered_state, sync_controlled_value,
+    warn_generated_id,
+};
+use crate::i18n::{I18nContext, I18nFormatter};
+use crate::theme::{ActiveTheme, Scale, Theme};
 
 #[derive(IntoElement)]
 pub struct TextArea {
     element_id: ElementId,
+    generated_id: bool,
     base: Div,
     placeholder: SharedString,
+    help_text: Option<SharedString>,
 
     disabled: bool,
+    read_only: bool,
+    selection_opacity: f32,
+    undo_limit: Option<usize>,
+    max_length: Option<usize>,
+    show_counter: bool,
+    auto_grow: Option<(usize, usize)>,
     wrap: WrapMode,
     enter: EnterBehavior,
+    tab: TabBehavior,
 
     bg: Option<Hsla>,
     border: Option<Hsla>,
@@ -30,31 +45,64 @@ pub struct TextArea {
     text_color: Option<Hsla>,
     height: Option<gpui::AbsoluteLength>,
 
+    value: Option<SharedString>,
     on_change: Option<TextAreaHandler>,
+    on_focus: Option<WindowCallback>,
+    on_blur: Option<TextAreaHandler>,
 }
 
 impl TextArea {
+    /// Creates a new TextArea without an explicit id.
+    ///
+    /// The id falls back to one derived from the caller's source location; prefer `.id()`.
+    #[track_caller]
     pub fn new() -> Self {
         Self {
-            element_id: "ui:text-area".into(),
-            base: div().h(gpui::px(120.)).px_3(),
+            element_id: generate_element_id("ui:text-area"),
+            generated_id: true,
+            base: div().h(gpui::px(120.)),
             placeholder: "".into(),
+            help_text: None,
 
             disabled: false,
+            read_only: false,
+            selection_opacity: DEFAULT_SELECTION_OPACITY,
+            undo_limit: None,
+            max_length: None,
+            show_counter: false,
+            auto_grow: None,
             wrap: WrapMode::None,
             enter: EnterBehavior::Newline,
+            tab: TabBehavior::MoveFocus,
 
             bg: None,
             border: None,
             focus_border: None,
             text_color: None,
             height: None,
+            value: None,
             on_change: None,
+            on_focus: None,
+            on_blur: None,
         }
     }
 
+    /// The state of the text area with `id`, e.g. to focus it or select its text.
+    ///
+    /// Looked up like [`TextInput::state_handle`](crate::component::TextInput::state_handle).
+    pub fn state_handle(
+        id: impl Into<ElementId>,
+        window: &mut gpui::Window,
+        cx: &mut App,
+    ) -> Entity<TextAreaState> {
+        let id = id.into();
+        registered_state(window, cx, &id)
+            .unwrap_or_else(|| window.use_keyed_state(id, cx, |_, cx| TextAreaState::new(cx)))
+    }
+
     pub fn id(mut self, id: impl Into<ElementId>) -> Self {
         self.element_id = id.into();
+        self.generated_id = false;
         self
     }
 
@@ -62,16 +110,79 @@ impl TextArea {
         self.id(key)
     }
 
+    /// Placeholder shown while empty. Use `\n` to span several lines.
     pub fn placeholder(mut self, text: impl Into<SharedString>) -> Self {
         self.placeholder = text.into();
         self
     }
 
+    /// Muted help text rendered below the field.
+    pub fn help_text(mut self, text: impl Into<SharedString>) -> Self {
+        self.help_text = Some(text.into());
+        self
+    }
+
+    /// ARIA attributes for this field, describing it by its help text when set.
+    pub fn aria_attrs(&self) -> Vec<(String, String)> {
+        let help_id = self
+            .help_text
+            .as_ref()
+            .map(|_| help_text_id(&self.element_id).to_string());
+        crate::a11y::textbox::attrs(true, help_id.as_deref())
+    }
+
     pub fn disabled(mut self, disabled: bool) -> Self {
         self.disabled = disabled;
         self
     }
 
+    /// Keeps the field focusable and selectable but rejects edits.
+    ///
+    /// The selection is drawn in a muted color while read-only.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Opacity of the selection fill (default: 0.25).
+    pub fn selection_opacity(mut self, opacity: f32) -> Self {
+        self.selection_opacity = opacity;
+        self
+    }
+
+    /// Maximum number of undo steps kept (default: 200).
+    pub fn undo_limit(mut self, limit: usize) -> Self {
+        self.undo_limit = Some(limit);
+        self
+    }
+
+    /// Maximum number of characters (grapheme clusters) allowed.
+    ///
+    /// Typed or pasted text past the limit is dropped; a paste inserts as much as fits.
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    /// Shows a `current/limit` counter in the bottom-right corner.
+    ///
+    /// Without `max_length` only the current count is shown.
+    pub fn show_counter(mut self, show: bool) -> Self {
+        self.show_counter = show;
+        self
+    }
+
+    /// Sizes the field to its content, between `min_rows` and `max_rows` visual
+    /// rows; past `max_rows` it scrolls. Overrides `.height()`.
+    ///
+    /// Soft-wrapped rows count toward the total. The height follows the content
+    /// one frame after it changes.
+    pub fn auto_grow(mut self, min_rows: usize, max_rows: usize) -> Self {
+        let min_rows = min_rows.max(1);
+        self.auto_grow = Some((min_rows, max_rows.max(min_rows)));
+        self
+    }
+
     pub fn wrap(mut self, wrap: WrapMode) -> Self {
         self.wrap = wrap;
         self
@@ -82,6 +193,36 @@ impl TextArea {
         self
     }
 
+    /// What Tab and Shift+Tab do. By default Tab moves focus as usual.
+    pub fn tab_behavior(mut self, tab: TabBehavior) -> Self {
+        self.tab = tab;
+        self
+    }
+
+    /// Makes Tab indent with `width` spaces, e.g. for editing code.
+    pub fn tab_inserts_spaces(self, width: usize) -> Self {
+        self.tab_behavior(TabBehavior::Spaces(width))
+    }
+
+    /// Makes Tab indent with a tab character.
+    pub fn tab_inserts_tab(self) -> Self {
+        self.tab_behavior(TabBehavior::Tab)
+    }
+
+    /// Controlled content; shorthand for `.value(Some(content))`.
+    pub fn content(mut self, content: impl Into<SharedString>) -> Self {
+        self.value = Some(content.into());
+        self
+    }
+
+    /// Controlled value. `Some("")` forces the field empty; `None` leaves it uncontrolled.
+    ///
+    /// The value is written into the field whenever it changes between renders.
+    pub fn value(mut self, value: Option<SharedString>) -> Self {
+        self.value = value;
+        self
+    }
+
     pub fn on_change<F>(mut self, handler: F) -> Self
     where
         F: 'static + Fn(SharedString, &mut gpui::Window, &mut App),
@@ -90,6 +231,24 @@ impl TextArea {
         self
     }
 
+    /// Called once each time the text area gains focus.
+    pub fn on_focus<F>(mut self, handler: F) -> Self
+    where
+        F: 'static + Fn(&mut gpui::Window, &mut App),
+    {
+        self.on_focus = Some(Arc::new(handler));
+        self
+    }
+
+    /// Called once each time the text area loses focus, with its current content.
+    pub fn on_blur<F>(mut self, handler: F) -> Self
+    where
+        F: 'static + Fn(SharedString, &mut gpui::Window, &mut App),
+    {
+        self.on_blur = Some(Arc::new(handler));
+        self
+    }
+
     pub fn bg(mut self, color: impl Into<Hsla>) -> Self {
         self.bg = Some(color.into());
         self
@@ -116,7 +275,58 @@ impl TextArea {
     }
 }
 
+fn help_text_id(id: &ElementId) -> ElementId {
+    (id.clone(), "ui:text-area:help").into()
+}
+
+/// Height of an `auto_grow` field laid out in `rows` visual rows, clamped to
+/// `(min_rows, max_rows)`.
+fn auto_grow_height(
+    rows: usize,
+    (min_rows, max_rows): (usize, usize),
+    line_height: Pixels,
+    show_counter: bool,
+) -> Pixels {
+    let rows = rows.clamp(min_rows, max_rows);
+    // Room for the focused border and, when shown, the counter.
+    let chrome = gpui::px(4.)
+        + if show_counter {
+            gpui::px(18.)
+        } else {
+            gpui::px(0.)
+        };
+    line_height * rows as f32 + chrome
+}
+
+/// How close a character count is to its limit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CounterLevel {
+    Normal,
+    /// Within 10% of the limit.
+    Near,
+    AtLimit,
+}
+
+impl CounterLevel {
+    fn for_count(count: usize, max: Option<usize>) -> Self {
+        match max {
+            Some(max) if count >= max => Self::AtLimit,
+            Some(max) if count * 10 >= max * 9 => Self::Near,
+            _ => Self::Normal,
+        }
+    }
+
+    fn color(self, theme: &Theme) -> Hsla {
+        match self {
+            Self::Normal => theme.content.tertiary,
+            Self::Near => theme.status.warning.bg,
+            Self::AtLimit => theme.status.error.bg,
+        }
+    }
+}
+
 impl Default for TextArea {
+    #[track_caller]
     fn default() -> Self {
         Self::new()
     }
@@ -145,20 +355,54 @@ impl StatefulInteractiveElement for TextArea {}
 impl RenderOnce for TextArea {
     fn render(self, window: &mut gpui::Window, cx: &mut App) -> impl IntoElement {
         let id = self.element_id;
+        if self.generated_id {
+            warn_generated_id(window, cx, &id);
+        }
 
         let disabled = self.disabled;
+        let read_only = disabled || self.read_only;
+        let selection_opacity = self.selection_opacity;
         let state = window.use_keyed_state(id.clone(), cx, |_, cx| TextAreaState::new(cx));
         let focus_handle = state.read(cx).focus_handle.clone();
 
         let placeholder = self.placeholder;
+        let help = self.help_text;
         let wrap = self.wrap;
         let enter = self.enter;
+        let tab = self.tab;
+        let undo_limit = self.undo_limit;
+        let max_length = self.max_length;
         state.update(cx, |state, _cx| {
             state.placeholder = placeholder;
             state.wrap = wrap;
             state.enter = enter;
+            state.tab = tab;
+            if state.max_length() != max_length {
+                state.set_max_length(max_length);
+            }
+            if let Some(limit) = undo_limit {
+                state.edit.set_undo_limit(limit);
+            }
         });
 
+        sync_controlled_value(
+            window,
+            cx,
+            &id,
+            self.value,
+            &state,
+            |state| state.content(),
+            |state, content| state.set_content(content),
+        );
+        register_input(
+            window,
+            cx,
+            &id,
+            &state,
+            |state| state.content(),
+            |state, content| state.set_content(content),
+        );
+
         let on_change = self.on_change;
         let last_content = window.use_keyed_state(
             (id.clone(), format!("{}:last-content", id)),
@@ -166,6 +410,39 @@ impl RenderOnce for TextArea {
             |_, _cx| SharedString::new_static(""),
         );
 
+        let on_focus = self.on_focus;
+        let on_blur = self.on_blur;
+        let was_focused =
+            window.use_keyed_state((id.clone(), "ui:text-area:focused"), cx, |_, _| false);
+        let content_of = {
+            let state = state.clone();
+            move |cx: &App| state.read(cx).content().clone()
+        };
+        notify_focus_change(
+            &was_focused,
+            focus_handle.is_focused(window),
+            &content_of,
+            on_focus.as_ref(),
+            on_blur.as_ref(),
+            window,
+            cx,
+        );
+
+        let show_counter = self.show_counter;
+        let counter = show_counter.then(|| {
+            let count = state.read(cx).edit.grapheme_count();
+            let formatter = cx.i18n().formatter();
+            let text = match max_length {
+                Some(max) => format!(
+                    "{}/{}",
+                    formatter.format_number(count as f64),
+                    formatter.format_number(max as f64)
+                ),
+                None => formatter.format_number(count as f64),
+            };
+            (text, CounterLevel::for_count(count, max_length))
+        });
+
         let theme = cx.theme();
         let bg = if disabled {
             theme.surface.sunken
@@ -184,11 +461,25 @@ impl RenderOnce for TextArea {
         } else {
             self.text_color.unwrap_or_else(|| theme.content.primary)
         };
-        let height = self.height.unwrap_or_else(|| gpui::px(120.).into());
+        let height = match self.auto_grow {
+            Some(bounds) => {
+                let rows = state
+                    .read(cx)
+                    .last_layout
+                    .as_ref()
+                    .map_or(0, |l| l.lines.len());
+                auto_grow_height(rows, bounds, window.line_height(), show_counter).into()
+            }
+            None => self.height.unwrap_or_else(|| gpui::px(120.).into()),
+        };
         let inset = if disabled { gpui::px(6.) } else { gpui::px(5.) };
 
-        let mut base = self
-            .base
+        let mut base = self.base;
+        // The theme's padding, unless set through `Styled`.
+        if base.style().padding.left.is_none() {
+            base = base.px(theme.spacing(Scale::Md));
+        }
+        let mut base = base
             .id(id.clone())
             .flex()
             .items_start()
@@ -205,28 +496,60 @@ impl RenderOnce for TextArea {
             .when(!disabled, |this| this.cursor(CursorStyle::IBeam))
             .when(disabled, |this| this.cursor_not_allowed().opacity(0.6))
             .key_context("UITextArea")
-            .on_action(action_handler!(state, disabled, Backspace, backspace))
-            .on_action(action_handler!(state, disabled, Delete, delete))
+            .on_action(action_handler!(state, read_only, Backspace, backspace))
+            .on_action(action_handler!(state, read_only, Delete, delete))
+            .on_action(action_handler!(
+                state,
+                read_only,
+                BackspaceWord,
+                backspace_word
+            ))
+            .on_action(action_handler!(state, read_only, DeleteWord, delete_word))
             .on_action(action_handler!(state, disabled, Left, left))
             .on_action(action_handler!(state, disabled, Right, right))
             .on_action(action_handler!(state, disabled, Up, up))
             .on_action(action_handler!(state, disabled, Down, down))
             .on_action(action_handler!(state, disabled, SelectLeft, select_left))
             .on_action(action_handler!(state, disabled, SelectRight, select_right))
+            .on_action(action_handler!(state, disabled, WordLeft, word_left))
+            .on_action(action_handler!(state, disabled, WordRight, word_right))
             .on_action(action_handler!(state, disabled, SelectUp, select_up))
             .on_action(action_handler!(state, disabled, SelectDown, select_down))
             .on_action(action_handler!(state, disabled, SelectAll, select_all))
             .on_action(action_handler!(state, disabled, Home, home))
             .on_action(action_handler!(state, disabled, End, end))
-            .on_action(action_handler!(state, disabled, Enter, enter))
+            .on_action(action_handler!(state, read_only, Enter, enter))
+            .on_action({
+                let state = state.clone();
+                move |action: &Tab, window, cx| {
+                    // Without indentation Tab falls through to focus traversal.
+                    if read_only || tab == TabBehavior::MoveFocus {
+                        cx.propagate();
+                        return;
+                    }
+                    state.update(cx, |state, cx| state.tab(action, window, cx));
+                }
+            })
+            .on_action({
+                let state = state.clone();
+                move |action: &Backtab, window, cx| {
+                    if read_only || tab == TabBehavior::MoveFocus {
+                        cx.propagate();
+                        return;
+                    }
+                    state.update(cx, |state, cx| state.backtab(action, window, cx));
+                }
+            })
             .on_action(action_handler!(
                 state,
-                disabled,
+                read_only,
                 ShowCharacterPalette,
                 show_character_palette
             ))
-            .on_action(action_handler!(state, disabled, Paste, paste))
-            .on_action(action_handler!(state, disabled, Cut, cut))
+            .on_action(action_handler!(state, read_only, Paste, paste))
+            .on_action(action_handler!(state, read_only, Cut, cut))
+            .on_action(action_handler!(state, read_only, Undo, undo))
+            .on_action(action_handler!(state, read_only, Redo, redo))
             .on_action({
                 let state = state.clone();
                 move |action: &Copy, window, cx| {
@@ -238,6 +561,8 @@ impl RenderOnce for TextArea {
             })
             .on_mouse_down(MouseButton::Left, {
                 let state = state.clone();
+                let was_focused = was_focused.clone();
+                let content_of = content_of.clone();
                 move |event, window, cx| {
                     if disabled {
                         return;
@@ -246,6 +571,15 @@ impl RenderOnce for TextArea {
                         state.focus_in(window, cx);
                         state.on_mouse_down(event, window, cx);
                     });
+                    notify_focus_change(
+                        &was_focused,
+                        true,
+                        &content_of,
+                        on_focus.as_ref(),
+                        None,
+                        window,
+                        cx,
+                    );
                 }
             })
             .on_mouse_up(MouseButton::Left, {
@@ -279,29 +613,62 @@ impl RenderOnce for TextArea {
         base = base
             .text_color(text_color)
             .child(
-                div().w_full().h_full().flex().px(inset).child(
-                    div()
-                        .w_full()
-                        .h_full()
-                        .rounded_sm()
-                        .id(format!("{}:scroll", id))
-                        .overflow_scroll()
-                        .child(TextAreaElement {
-                            input: state.clone(),
-                            disabled,
-                        }),
-                ),
+                div()
+                    .w_full()
+                    .h_full()
+                    .flex()
+                    .px(inset)
+                    // Keep the last line clear of the counter.
+                    .when(show_counter, |this| this.pb(gpui::px(18.)))
+                    .child(
+                        div()
+                            .w_full()
+                            .h_full()
+                            .rounded_sm()
+                            .id(format!("{}:scroll", id))
+                            .overflow_scroll()
+                            .child(TextAreaElement {
+                                input: state.clone(),
+                                disabled,
+                                read_only,
+                                selection_opacity,
+                            }),
+                    ),
             )
-            .on_mouse_down_out(move |_event, window, _cx| {
+            .when_some(counter, |this, (text, level)| {
+                this.relative().child(
+                    div()
+                        .absolute()
+                        .bottom(gpui::px(4.))
+                        .right(gpui::px(8.))
+                        .text_xs()
+                        .text_color(level.color(theme))
+                        .child(
+                            label(text)
+                                .id((id.clone(), "ui:text-area:counter"))
+                                .inherit_color(true),
+                        ),
+                )
+            })
+            .on_mouse_down_out(move |_event, window, cx| {
                 if disabled {
                     return;
                 }
                 if focus_handle.is_focused(window) {
                     window.blur();
+                    notify_focus_change(
+                        &was_focused,
+                        false,
+                        &content_of,
+                        None,
+                        on_blur.as_ref(),
+                        window,
+                        cx,
+                    );
                 }
             });
 
-        base.map(move |this| {
+        let field = base.map(move |this| {
             if on_change.is_none() {
                 return this;
             }
@@ -313,6 +680,55 @@ impl RenderOnce for TextArea {
                 on_change(current, window, cx);
             }
             this
-        })
+        });
+
+        match help {
+            Some(text) => div()
+                .flex()
+                .flex_col()
+                .gap_1()
+                .w_full()
+                .child(field)
+                .child(help_text(text).id(help_text_id(&id)))
+                .into_any_element(),
+            None => field.into_any_element(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counter_warns_near_the_limit() {
+        assert_eq!(CounterLevel::for_count(0, Some(100)), CounterLevel::Normal);
+        assert_eq!(CounterLevel::for_count(89, Some(100)), CounterLevel::Normal);
+        assert_eq!(CounterLevel::for_count(90, Some(100)), CounterLevel::Near);
+        assert_eq!(
+            CounterLevel::for_count(100, Some(100)),
+            CounterLevel::AtLimit
+        );
+        assert_eq!(CounterLevel::for_count(5, Some(5)), CounterLevel::AtLimit);
+        assert_eq!(CounterLevel::for_count(500, None), CounterLevel::Normal);
+    }
+
+    #[test]
+    fn auto_grow_bounds_are_normalized() {
+        assert_eq!(TextArea::new().auto_grow(2, 6).auto_grow, Some((2, 6)));
+        assert_eq!(TextArea::new().auto_grow(0, 0).auto_grow, Some((1, 1)));
+        assert_eq!(TextArea::new().auto_grow(4, 2).auto_grow, Some((4, 4)));
+    }
+
+    #[test]
+    fn auto_grow_height_clamps_rows() {
+        let height =
+            |rows, show_counter| auto_grow_height(rows, (2, 4), gpui::px(20.), show_counter);
+
+        assert_eq!(height(0, false), gpui::px(44.));
+        assert_eq!(height(3, false), gpui::px(64.));
+        assert_eq!(height(4, false), gpui::px(84.));
+        assert_eq!(height(9, false), gpui::px(84.));
+        assert_eq!(height(3, true), gpui::px(82.));
     }
 }
@@ -7,17 +7,21 @@ use gpui::{
     LayoutId, PaintQuad, Pixels, Style, TextRun, fill, point, px, relative, size,
 };
 
-use super::layout::{LineLayout, TextAreaLayout};
+use super::layout::TextAreaLayout;
 use super::state::{TextAreaState, WrapMode};
+use crate::component::{ShapeKey, selection_color};
 use crate::theme::ActiveTheme;
 
 pub struct TextAreaElement {
     pub input: Entity<TextAreaState>,
     pub disabled: bool,
+    pub read_only: bool,
+    pub selection_opacity: f32,
 }
 
 pub struct PrepaintState {
     layout: TextAreaLayout,
+    shape_key: Option<ShapeKey>,
     cursor: Option<PaintQuad>,
     selection: Vec<PaintQuad>,
     scroll_x: Pixels,
@@ -101,26 +105,40 @@ impl Element for TextAreaElement {
             marked_range
         };
 
-        let (lines_result, max_width) = super::layout::layout_lines(
-            display_text.as_str(),
-            marked_range.as_ref(),
-            &base_run,
+        let wrap_width = (wrap == WrapMode::Soft).then_some(bounds.size.width);
+        let shape_key = ShapeKey {
+            text: display_text.clone(),
+            runs: vec![base_run.clone()],
+            marked_range: marked_range.clone(),
             font_size,
             line_height,
-            window,
-        );
-        let lines = lines_result;
-        let y = lines
-            .last()
-            .map(|l| l.y + line_height)
-            .unwrap_or(line_height);
+            wrap_width,
+        };
+        let layout = match &input.last_layout {
+            Some(layout) if input.shape_key.as_ref() == Some(&shape_key) => layout.clone(),
+            _ => {
+                let (lines, max_width) = super::layout::layout_lines(
+                    display_text.as_str(),
+                    marked_range.as_ref(),
+                    &base_run,
+                    font_size,
+                    line_height,
+                    wrap_width,
+                    window,
+                );
+                let y = lines
+                    .last()
+                    .map(|l| l.y + line_height)
+                    .unwrap_or(line_height);
 
-        let content_height = y.max(line_height);
-        let layout = TextAreaLayout {
-            lines,
-            line_height,
-            content_height,
-            content_width: max_width,
+                let content_height = y.max(line_height);
+                TextAreaLayout {
+                    lines,
+                    line_height,
+                    content_height,
+                    content_width: max_width,
+                }
+            }
         };
 
         let max_scroll_y = (layout.content_height - bounds.size.height).max(Pixels::ZERO);
@@ -178,7 +196,7 @@ impl Element for TextAreaElement {
                             bounds.top() + layout.lines[row].y + line_height - scroll_y,
                         ),
                     ),
-                    cx.theme().border.focus.alpha(0.25),
+                    selection_color(cx.theme(), self.selection_opacity, self.read_only),
                 ));
             }
         }
@@ -204,6 +222,7 @@ impl Element for TextAreaElement {
 
         PrepaintState {
             layout,
+            shape_key: Some(shape_key),
             cursor: cursor_quad,
             selection,
             scroll_x,
@@ -222,7 +241,7 @@ impl Element for TextAreaElement {
         cx: &mut App,
     ) {
         let focus_handle = self.input.read(cx).focus_handle.clone();
-        if !self.disabled {
+        if !self.disabled && !self.read_only {
             window.handle_input(
                 &focus_handle,
                 ElementInputHandler::new(bounds, self.input.clone()),
@@ -259,24 +278,17 @@ impl Element for TextAreaElement {
             window.paint_quad(cursor);
         }
 
-        let layout = TextAreaLayout {
-            lines: prepaint
-                .layout
-                .lines
-                .iter()
-                .map(|line| LineLayout {
-                    range: line.range.clone(),
-                    shaped: line.shaped.clone(),
-                    y: line.y,
-                })
-                .collect(),
-            line_height: prepaint.layout.line_height,
-            content_height: prepaint.layout.content_height,
-            content_width: prepaint.layout.content_width,
-        };
+        let layout = prepaint.layout.clone();
+        let shape_key = prepaint.shape_key.take();
 
-        self.input.update(cx, |input, _cx| {
+        self.input.update(cx, |input, cx| {
+            // Auto-growing fields size themselves from the row count on the next render.
+            let rows = layout.lines.len();
+            if input.last_layout.as_ref().map(|l| l.lines.len()) != Some(rows) {
+                cx.notify();
+            }
             input.last_layout = Some(layout);
+            input.shape_key = shape_key;
             input.last_bounds = Some(bounds);
             input.scroll_x = prepaint.scroll_x;
             input.scroll_y = prepaint.scroll_y;
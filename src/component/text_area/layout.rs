@@ -4,13 +4,16 @@
 
 use gpui::{Pixels, ShapedLine, SharedString, TextRun, UnderlineStyle, px};
 use std::ops::Range;
+use unicode_segmentation::UnicodeSegmentation;
 
+#[derive(Clone)]
 pub struct LineLayout {
     pub range: Range<usize>,
     pub shaped: ShapedLine,
     pub y: Pixels,
 }
 
+#[derive(Clone)]
 pub struct TextAreaLayout {
     pub lines: Vec<LineLayout>,
     pub line_height: Pixels,
@@ -27,14 +30,18 @@ impl TextAreaLayout {
         Some(row.min(self.lines.len().saturating_sub(1)))
     }
 
+    /// Returns the visual row containing `index`.
+    ///
+    /// Rows may be soft-wrapped pieces of one logical line. An offset sitting exactly on
+    /// a wrap boundary belongs to the start of the following row.
+    pub fn row_for_index(&self, index: usize) -> Option<usize> {
+        let ranges: Vec<Range<usize>> = self.lines.iter().map(|line| line.range.clone()).collect();
+        row_for_offset(&ranges, index)
+    }
+
     pub fn position_for_index(&self, index: usize) -> Option<(usize, Pixels)> {
-        for (row, line) in self.lines.iter().enumerate() {
-            if index < line.range.start {
-                continue;
-            }
-            if index > line.range.end {
-                continue;
-            }
+        if let Some(row) = self.row_for_index(index) {
+            let line = &self.lines[row];
             let idx_in_line = (index - line.range.start).min(line.shaped.len());
             return Some((row, line.shaped.x_for_index(idx_in_line)));
         }
@@ -42,56 +49,173 @@ impl TextAreaLayout {
             .last()
             .map(|line| (self.lines.len().saturating_sub(1), line.shaped.width))
     }
+
+    /// Returns the content offset closest to a point local to the text origin.
+    ///
+    /// `y` picks a visual row and `x` a position within it, so dragging through a
+    /// wrapped line selects inside it rather than jumping between logical lines.
+    pub fn index_for_point(&self, x: Pixels, y: Pixels) -> usize {
+        let Some(row) = self.row_for_y(y.max(Pixels::ZERO)) else {
+            return 0;
+        };
+        let line = &self.lines[row];
+        line.range.start + line.shaped.closest_index_for_x(x.max(Pixels::ZERO))
+    }
+
+    /// Returns the offset reached by moving `row_delta` visual rows from `index`,
+    /// aiming for the horizontal position `x`.
+    pub fn index_for_vertical_move(
+        &self,
+        index: usize,
+        row_delta: isize,
+        x: Pixels,
+    ) -> Option<usize> {
+        let (row, _) = self.position_for_index(index)?;
+        let target_row = (row as isize + row_delta)
+            .clamp(0, self.lines.len().saturating_sub(1) as isize)
+            as usize;
+        let line = &self.lines[target_row];
+        Some(line.range.start + line.shaped.closest_index_for_x(x))
+    }
 }
 
-/// Helper function to layout text lines based on wrap mode.
+/// Finds the row whose byte range contains `index`.
+///
+/// When a row ends where the next one starts (a soft wrap), the shared offset is
+/// attributed to the next row.
+pub fn row_for_offset(rows: &[Range<usize>], index: usize) -> Option<usize> {
+    for (row, range) in rows.iter().enumerate() {
+        if index < range.start || index > range.end {
+            continue;
+        }
+        let wraps_into_next = rows
+            .get(row + 1)
+            .is_some_and(|next| next.start == range.end);
+        if index == range.end && wraps_into_next {
+            continue;
+        }
+        return Some(row);
+    }
+    None
+}
+
+/// Lays out text as visual rows.
+///
+/// Each hard line is one row, or with `wrap_width` set, as many rows as it takes
+/// to fit that width (see [`soft_wrap_ranges`]).
 pub fn layout_lines(
     display_text: &str,
     marked_range: Option<&Range<usize>>,
     base_run: &TextRun,
     font_size: Pixels,
     line_height: Pixels,
+    wrap_width: Option<Pixels>,
     window: &mut gpui::Window,
 ) -> (Vec<LineLayout>, Pixels) {
     let mut lines = Vec::new();
     let mut y = Pixels::ZERO;
     let mut max_width = Pixels::ZERO;
 
-    let mut start = 0;
-    for (i, ch) in display_text.char_indices() {
-        if ch == '\n' {
-            let line_text = SharedString::new(display_text[start..i].to_string());
-            let runs = runs_for_line(start..i, marked_range, base_run);
-            let shaped = window
-                .text_system()
-                .shape_line(line_text, font_size, &runs, None);
+    let shape = |range: Range<usize>| {
+        let line_text = SharedString::new(display_text[range.clone()].to_string());
+        let runs = runs_for_line(range, marked_range, base_run);
+        window
+            .text_system()
+            .shape_line(line_text, font_size, &runs, None)
+    };
+
+    for range in line_ranges(display_text) {
+        let shaped = shape(range.clone());
+        let rows = match wrap_width {
+            Some(width) if shaped.width > width => {
+                soft_wrap_ranges(&display_text[range.clone()], width, |index| {
+                    shaped.x_for_index(index)
+                })
+                .into_iter()
+                .map(|row| range.start + row.start..range.start + row.end)
+                .map(|row| (row.clone(), shape(row)))
+                .collect()
+            }
+            _ => vec![(range, shaped)],
+        };
+
+        for (range, shaped) in rows {
             max_width = max_width.max(shaped.width);
-            lines.push(LineLayout {
-                range: start..i,
-                shaped,
-                y,
-            });
+            lines.push(LineLayout { range, shaped, y });
             y += line_height;
-            start = i + '\n'.len_utf8();
         }
     }
 
-    let end = display_text.len();
-    if start < end || lines.is_empty() {
-        let line_text = SharedString::new(display_text[start..end].to_string());
-        let runs = runs_for_line(start..end, marked_range, base_run);
-        let shaped = window
-            .text_system()
-            .shape_line(line_text, font_size, &runs, None);
-        max_width = max_width.max(shaped.width);
-        lines.push(LineLayout {
-            range: start..end,
-            shaped,
-            y,
-        });
+    (lines, max_width)
+}
+
+/// Splits one hard line into the byte ranges of rows no wider than `max_width`.
+///
+/// `x_for_index` measures the line at a byte offset. Rows break after the last
+/// whitespace that fits, or mid-word when a word is wider than a whole row.
+/// Whitespace may hang past the edge rather than start the next row. Every row
+/// holds at least one grapheme, and consecutive rows share their boundary offset.
+pub fn soft_wrap_ranges(
+    line: &str,
+    max_width: Pixels,
+    x_for_index: impl Fn(usize) -> Pixels,
+) -> Vec<Range<usize>> {
+    let mut rows = Vec::new();
+    let mut start = 0;
+
+    while x_for_index(line.len()) - x_for_index(start) > max_width {
+        let row_x = x_for_index(start);
+        let mut fit = None;
+        let mut after_space = None;
+        for (idx, grapheme) in line[start..].grapheme_indices(true) {
+            let end = start + idx + grapheme.len();
+            if grapheme.trim().is_empty() {
+                fit = Some(end);
+                after_space = Some(end);
+                continue;
+            }
+            if x_for_index(end) - row_x > max_width {
+                break;
+            }
+            fit = Some(end);
+        }
+
+        let first_grapheme = || {
+            start
+                + line[start..]
+                    .graphemes(true)
+                    .next()
+                    .map_or(0, |grapheme| grapheme.len())
+        };
+        let end = after_space.or(fit).unwrap_or_else(first_grapheme);
+        if end >= line.len() {
+            break;
+        }
+        rows.push(start..end);
+        start = end;
     }
 
-    (lines, max_width)
+    rows.push(start..line.len());
+    rows
+}
+
+/// Splits text into the byte ranges of its hard lines (separated by `\n`).
+///
+/// Always returns at least one range, so empty text still lays out a single line.
+/// Used for both content and the placeholder, which may span several lines.
+pub fn line_ranges(text: &str) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    for (i, ch) in text.char_indices() {
+        if ch == '\n' {
+            ranges.push(start..i);
+            start = i + '\n'.len_utf8();
+        }
+    }
+    if start < text.len() || ranges.is_empty() {
+        ranges.push(start..text.len());
+    }
+    ranges
 }
 
 fn runs_for_line(
@@ -141,3 +265,134 @@ fn runs_for_line(
     .filter(|run| run.len > 0)
     .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::ShapedLine;
+
+    /// Rows for "0123456789abcdefghij\nwxyz" soft-wrapped every 10 bytes.
+    fn wrapped_layout() -> TextAreaLayout {
+        let line_height = px(20.);
+        let lines = [0..10, 10..20, 21..25]
+            .into_iter()
+            .enumerate()
+            .map(|(row, range)| LineLayout {
+                range,
+                shaped: ShapedLine::default(),
+                y: line_height * row as f32,
+            })
+            .collect();
+        TextAreaLayout {
+            lines,
+            line_height,
+            content_height: px(60.),
+            content_width: px(100.),
+        }
+    }
+
+    #[test]
+    fn wrap_boundary_offset_belongs_to_next_row() {
+        let rows = [0..10, 10..20, 21..25];
+
+        assert_eq!(row_for_offset(&rows, 5), Some(0));
+        assert_eq!(row_for_offset(&rows, 10), Some(1));
+        // A hard newline keeps the line-end offset on its own row.
+        assert_eq!(row_for_offset(&rows, 20), Some(1));
+        assert_eq!(row_for_offset(&rows, 21), Some(2));
+    }
+
+    #[test]
+    fn dragging_across_wrap_boundary_selects_visual_rows() {
+        let layout = wrapped_layout();
+        let anchor = layout.index_for_point(Pixels::ZERO, px(5.));
+        assert_eq!(anchor, 0);
+
+        // Dragging into the second visual row stays within the first logical line.
+        let head = layout.index_for_point(Pixels::ZERO, px(25.));
+        assert_eq!(anchor..head, 0..10);
+
+        let head = layout.index_for_point(Pixels::ZERO, px(45.));
+        assert_eq!(anchor..head, 0..21);
+    }
+
+    #[test]
+    fn vertical_move_steps_through_wrapped_rows() {
+        let layout = wrapped_layout();
+
+        assert_eq!(layout.index_for_vertical_move(0, 1, Pixels::ZERO), Some(10));
+        assert_eq!(
+            layout.index_for_vertical_move(10, 1, Pixels::ZERO),
+            Some(21)
+        );
+        assert_eq!(
+            layout.index_for_vertical_move(10, -1, Pixels::ZERO),
+            Some(0)
+        );
+    }
+
+    /// Every byte is 10px wide.
+    fn monospace(index: usize) -> Pixels {
+        px(10. * index as f32)
+    }
+
+    fn wrap(line: &str, max_width: f32) -> Vec<&str> {
+        soft_wrap_ranges(line, px(max_width), monospace)
+            .into_iter()
+            .map(|row| &line[row])
+            .collect()
+    }
+
+    #[test]
+    fn soft_wrap_breaks_after_whitespace() {
+        assert_eq!(
+            wrap("hello brave new world", 100.),
+            ["hello ", "brave new ", "world"]
+        );
+        assert_eq!(wrap("short", 100.), ["short"]);
+        assert_eq!(wrap("", 100.), [""]);
+    }
+
+    #[test]
+    fn soft_wrap_splits_words_wider_than_a_row() {
+        assert_eq!(wrap("abcdefghij", 40.), ["abcd", "efgh", "ij"]);
+        assert_eq!(wrap("ab abcdefgh", 50.), ["ab ", "abcde", "fgh"]);
+        // Narrower than one grapheme still makes progress.
+        assert_eq!(wrap("abc", 5.), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn soft_wrap_lets_trailing_spaces_hang() {
+        assert_eq!(wrap("abcd    efgh", 40.), ["abcd    ", "efgh"]);
+    }
+
+    #[test]
+    fn soft_wrap_rows_share_boundaries() {
+        let rows = soft_wrap_ranges("one two three", px(40.), monospace);
+        for pair in rows.windows(2) {
+            assert_eq!(pair[0].end, pair[1].start);
+        }
+        assert_eq!(rows.first().map(|row| row.start), Some(0));
+        assert_eq!(rows.last().map(|row| row.end), Some(13));
+    }
+
+    #[test]
+    fn multi_line_placeholder_lays_out_one_row_per_line() {
+        let placeholder = "Name\nEmail\nPhone";
+        let ranges = line_ranges(placeholder);
+
+        assert_eq!(ranges.len(), 3);
+        let lines: Vec<&str> = ranges.iter().map(|r| &placeholder[r.clone()]).collect();
+        assert_eq!(lines, ["Name", "Email", "Phone"]);
+    }
+
+    #[test]
+    fn empty_text_has_a_single_line() {
+        assert_eq!(line_ranges(""), vec![0..0]);
+    }
+
+    #[test]
+    fn trailing_newline_does_not_add_a_row() {
+        assert_eq!(line_ranges("a\n"), vec![0..1]);
+    }
+}
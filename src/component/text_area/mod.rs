@@ -26,6 +26,10 @@ pub(crate) fn init(cx: &mut App) {
         gpui::KeyBinding::new("delete", Delete, Some("UITextArea")),
         gpui::KeyBinding::new("left", Left, Some("UITextArea")),
         gpui::KeyBinding::new("right", Right, Some("UITextArea")),
+        gpui::KeyBinding::new("alt-left", WordLeft, Some("UITextArea")),
+        gpui::KeyBinding::new("alt-right", WordRight, Some("UITextArea")),
+        gpui::KeyBinding::new("alt-backspace", BackspaceWord, Some("UITextArea")),
+        gpui::KeyBinding::new("alt-delete", DeleteWord, Some("UITextArea")),
         gpui::KeyBinding::new("up", Up, Some("UITextArea")),
         gpui::KeyBinding::new("down", Down, Some("UITextArea")),
         gpui::KeyBinding::new("shift-left", SelectLeft, Some("UITextArea")),
@@ -36,9 +40,13 @@ pub(crate) fn init(cx: &mut App) {
         gpui::KeyBinding::new("secondary-v", Paste, Some("UITextArea")),
         gpui::KeyBinding::new("secondary-c", Copy, Some("UITextArea")),
         gpui::KeyBinding::new("secondary-x", Cut, Some("UITextArea")),
+        gpui::KeyBinding::new("secondary-z", Undo, Some("UITextArea")),
+        gpui::KeyBinding::new("secondary-shift-z", Redo, Some("UITextArea")),
         gpui::KeyBinding::new("home", Home, Some("UITextArea")),
         gpui::KeyBinding::new("end", End, Some("UITextArea")),
         gpui::KeyBinding::new("enter", Enter, Some("UITextArea")),
+        gpui::KeyBinding::new("tab", Tab, Some("UITextArea")),
+        gpui::KeyBinding::new("shift-tab", Backtab, Some("UITextArea")),
         gpui::KeyBinding::new(
             "ctrl-secondary-space",
             ShowCharacterPalette,
@@ -5,7 +5,7 @@ use std::sync::Arc;
 
 use gpui::{App, Context, FocusHandle, ParentElement, SharedString, UTF16Selection};
 
-use crate::component::TextEditState;
+use crate::component::{ShapeKey, TextEditState, truncate_graphemes};
 use crate::constants::CURSOR_BLINK_INTERVAL;
 
 pub type TextAreaHandler = Arc<dyn Fn(SharedString, &mut gpui::Window, &mut App)>;
@@ -14,6 +14,7 @@ pub type TextAreaHandler = Arc<dyn Fn(SharedString, &mut gpui::Window, &mut App)
 pub enum WrapMode {
     #[default]
     None,
+    /// Wraps long lines at the field's width instead of scrolling horizontally.
     Soft,
 }
 
@@ -25,6 +26,37 @@ pub enum EnterBehavior {
     Disabled,
 }
 
+/// What Tab does in a text area.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TabBehavior {
+    /// Tab is left to the app, so the text area takes part in focus traversal.
+    #[default]
+    MoveFocus,
+    /// Tab indents with this many spaces; Shift+Tab removes up to as many.
+    Spaces(usize),
+    /// Tab indents with a tab character; Shift+Tab removes one tab or up to four
+    /// spaces.
+    Tab,
+}
+
+impl TabBehavior {
+    /// The text one level of indentation inserts, or `None` when Tab isn't handled.
+    pub fn indent(self) -> Option<String> {
+        match self {
+            TabBehavior::MoveFocus => None,
+            TabBehavior::Spaces(width) => Some(" ".repeat(width)),
+            TabBehavior::Tab => Some("\t".to_string()),
+        }
+    }
+
+    fn outdent_width(self) -> usize {
+        match self {
+            TabBehavior::Spaces(width) => width,
+            _ => 4,
+        }
+    }
+}
+
 pub struct TextAreaState {
     pub focus_handle: FocusHandle,
     pub edit: TextEditState,
@@ -32,6 +64,8 @@ pub struct TextAreaState {
     pub scroll_x: gpui::Pixels,
     pub scroll_y: gpui::Pixels,
     pub last_layout: Option<super::layout::TextAreaLayout>,
+    /// What `last_layout` was shaped from.
+    pub(crate) shape_key: Option<ShapeKey>,
     pub last_bounds: Option<gpui::Bounds<gpui::Pixels>>,
     pub is_selecting: bool,
     pub cursor_visible: bool,
@@ -40,6 +74,8 @@ pub struct TextAreaState {
     pub preferred_x: Option<gpui::Pixels>,
     pub wrap: WrapMode,
     pub enter: EnterBehavior,
+    pub tab: TabBehavior,
+    max_length: Option<usize>,
 }
 
 impl TextAreaState {
@@ -51,6 +87,7 @@ impl TextAreaState {
             scroll_x: gpui::Pixels::ZERO,
             scroll_y: gpui::Pixels::ZERO,
             last_layout: None,
+            shape_key: None,
             last_bounds: None,
             is_selecting: false,
             cursor_visible: true,
@@ -59,6 +96,8 @@ impl TextAreaState {
             preferred_x: None,
             wrap: WrapMode::None,
             enter: EnterBehavior::Newline,
+            tab: TabBehavior::MoveFocus,
+            max_length: None,
         }
     }
 
@@ -67,12 +106,44 @@ impl TextAreaState {
     }
 
     pub fn set_content(&mut self, content: impl Into<SharedString>) {
-        self.edit.set_content(content);
+        let content = content.into();
+        match self.max_length {
+            Some(max) => self
+                .edit
+                .set_content(truncate_graphemes(&content, max).to_owned()),
+            None => self.edit.set_content(content),
+        }
         self.scroll_x = gpui::Pixels::ZERO;
         self.scroll_y = gpui::Pixels::ZERO;
         self.preferred_x = None;
     }
 
+    pub fn max_length(&self) -> Option<usize> {
+        self.max_length
+    }
+
+    /// Caps the content at `max_length` graphemes, cutting any existing excess.
+    pub fn set_max_length(&mut self, max_length: Option<usize>) {
+        self.max_length = max_length;
+        if let Some(max) = max_length
+            && truncate_graphemes(self.edit.content(), max).len() < self.edit.content().len()
+        {
+            self.set_content(self.edit.content().clone());
+        }
+    }
+
+    /// The part of `new_text` that fits under `max_length` when replacing `range_utf16`.
+    fn fit_to_max_length<'a>(
+        &self,
+        range_utf16: Option<&Range<usize>>,
+        new_text: &'a str,
+    ) -> &'a str {
+        match self.max_length {
+            Some(max) => self.edit.fit_replacement(range_utf16, new_text, max),
+            None => new_text,
+        }
+    }
+
     pub fn scroll_x(&self) -> gpui::Pixels {
         self.scroll_x
     }
@@ -126,6 +197,35 @@ impl TextAreaState {
             .detach();
     }
 
+    /// Focuses the field, e.g. to jump to the first invalid field of a form.
+    pub fn focus(&mut self, window: &mut gpui::Window, cx: &mut Context<Self>) {
+        self.focus_in(window, cx);
+        cx.notify();
+    }
+
+    /// Removes focus from the field if it has it.
+    pub fn blur(&mut self, window: &mut gpui::Window, cx: &mut Context<Self>) {
+        if self.focus_handle.is_focused(window) {
+            window.blur();
+            cx.notify();
+        }
+    }
+
+    /// Selects the byte `range` of the content, clamped to its bounds.
+    pub fn select_range(&mut self, range: Range<usize>, cx: &mut Context<Self>) {
+        self.preferred_x = None;
+        self.edit.select_range(range);
+        cx.notify();
+    }
+
+    /// Selects the whole content.
+    ///
+    /// This is the programmatic `select_all`; that name belongs to the handler
+    /// of the `SelectAll` action (Cmd/Ctrl+A), which needs a window.
+    pub fn select_all_content(&mut self, cx: &mut Context<Self>) {
+        self.select_range(0..self.edit.content().len(), cx);
+    }
+
     pub fn focus_in(&mut self, window: &mut gpui::Window, cx: &mut Context<Self>) {
         if self.focus_subscription.is_none() {
             let focus_handle = self.focus_handle.clone();
@@ -207,6 +307,34 @@ impl TextAreaState {
         self.move_vertically(1, false, window, cx);
     }
 
+    pub fn word_left(
+        &mut self,
+        _: &super::actions::WordLeft,
+        window: &mut gpui::Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.preferred_x = None;
+        self.move_to(
+            self.edit.previous_word_boundary(self.edit.cursor_offset()),
+            window,
+            cx,
+        );
+    }
+
+    pub fn word_right(
+        &mut self,
+        _: &super::actions::WordRight,
+        window: &mut gpui::Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.preferred_x = None;
+        self.move_to(
+            self.edit.next_word_boundary(self.edit.cursor_offset()),
+            window,
+            cx,
+        );
+    }
+
     pub fn select_left(
         &mut self,
         _: &super::actions::SelectLeft,
@@ -313,7 +441,8 @@ impl TextAreaState {
         match self.enter {
             EnterBehavior::Newline => {
                 self.reset_cursor_blink(window, cx);
-                self.edit.replace_text_in_range(None, "\n");
+                let new_text = self.fit_to_max_length(None, "\n");
+                self.edit.replace_text_in_range(None, new_text);
                 cx.notify();
             }
             EnterBehavior::Submit => {}
@@ -321,6 +450,74 @@ impl TextAreaState {
         }
     }
 
+    /// Indents every selected line when the selection spans lines, otherwise
+    /// replaces the selection with one level of indentation.
+    pub fn tab(
+        &mut self,
+        _: &super::actions::Tab,
+        window: &mut gpui::Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(indent) = self.tab.indent() else {
+            return;
+        };
+        self.reset_cursor_blink(window, cx);
+        self.preferred_x = None;
+
+        let content = self.edit.content().clone();
+        let selected = self.edit.selected_range().clone();
+        if content[selected.clone()].contains('\n') {
+            let block = line_block(&content, selected);
+            self.replace_block(block.clone(), indent_lines(&content[block], &indent));
+        } else {
+            let new_text = self.fit_to_max_length(None, &indent);
+            self.edit.replace_text_in_range(None, new_text);
+        }
+        cx.notify();
+    }
+
+    /// Outdents the lines the selection (or cursor) is on.
+    pub fn backtab(
+        &mut self,
+        _: &super::actions::Backtab,
+        window: &mut gpui::Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.tab == TabBehavior::MoveFocus {
+            return;
+        }
+        self.reset_cursor_blink(window, cx);
+        self.preferred_x = None;
+
+        let content = self.edit.content().clone();
+        let selected = self.edit.selected_range().clone();
+        let block = line_block(&content, selected.clone());
+        let outdented = outdent_lines(&content[block.clone()], self.tab.outdent_width());
+        let removed = block.len() - outdented.len();
+        if removed == 0 {
+            return;
+        }
+        self.replace_block(block.clone(), outdented);
+        if selected.is_empty() {
+            // Keep the cursor on the same character of the outdented line.
+            self.edit
+                .move_to(selected.start.saturating_sub(removed).max(block.start));
+        }
+        cx.notify();
+    }
+
+    /// Replaces the whole lines in `block` as one undo step and selects them.
+    fn replace_block(&mut self, block: Range<usize>, new_text: String) {
+        let block_utf16 = self.edit.range_to_utf16(&block);
+        if self.fit_to_max_length(Some(&block_utf16), &new_text).len() < new_text.len() {
+            return;
+        }
+        self.edit
+            .replace_text_in_range(Some(block_utf16), &new_text);
+        self.edit
+            .select_range(block.start..block.start + new_text.len());
+    }
+
     pub fn backspace(
         &mut self,
         _: &super::actions::Backspace,
@@ -359,6 +556,44 @@ impl TextAreaState {
         cx.notify();
     }
 
+    pub fn backspace_word(
+        &mut self,
+        _: &super::actions::BackspaceWord,
+        window: &mut gpui::Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.preferred_x = None;
+        if self.edit.selected_range().is_empty() {
+            self.select_to(
+                self.edit.previous_word_boundary(self.edit.cursor_offset()),
+                window,
+                cx,
+            )
+        }
+        self.reset_cursor_blink(window, cx);
+        self.edit.replace_text_in_range(None, "");
+        cx.notify();
+    }
+
+    pub fn delete_word(
+        &mut self,
+        _: &super::actions::DeleteWord,
+        window: &mut gpui::Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.preferred_x = None;
+        if self.edit.selected_range().is_empty() {
+            self.select_to(
+                self.edit.next_word_boundary(self.edit.cursor_offset()),
+                window,
+                cx,
+            )
+        }
+        self.reset_cursor_blink(window, cx);
+        self.edit.replace_text_in_range(None, "");
+        cx.notify();
+    }
+
     pub fn show_character_palette(
         &mut self,
         _: &super::actions::ShowCharacterPalette,
@@ -377,7 +612,8 @@ impl TextAreaState {
         self.preferred_x = None;
         if let Some(text) = cx.read_from_clipboard().and_then(|item| item.text()) {
             self.reset_cursor_blink(window, cx);
-            self.edit.replace_text_in_range(None, &text);
+            let new_text = self.fit_to_max_length(None, &text);
+            self.edit.replace_text_in_range(None, new_text);
             cx.notify();
         }
     }
@@ -407,6 +643,32 @@ impl TextAreaState {
         }
     }
 
+    pub fn undo(
+        &mut self,
+        _: &super::actions::Undo,
+        window: &mut gpui::Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.preferred_x = None;
+        if self.edit.undo() {
+            self.reset_cursor_blink(window, cx);
+            cx.notify();
+        }
+    }
+
+    pub fn redo(
+        &mut self,
+        _: &super::actions::Redo,
+        window: &mut gpui::Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.preferred_x = None;
+        if self.edit.redo() {
+            self.reset_cursor_blink(window, cx);
+            cx.notify();
+        }
+    }
+
     pub fn on_mouse_down(
         &mut self,
         event: &gpui::MouseDownEvent,
@@ -454,17 +716,16 @@ impl TextAreaState {
         let Some(layout) = self.last_layout.as_ref() else {
             return;
         };
-        let Some((row, x)) = layout.position_for_index(self.edit.cursor_offset()) else {
+        let Some((_, x)) = layout.position_for_index(self.edit.cursor_offset()) else {
             return;
         };
 
-        let target_x = self.preferred_x.get_or_insert(x);
-        let target_row = (row as isize + row_delta)
-            .clamp(0, layout.lines.len().saturating_sub(1) as isize)
-            as usize;
-        let line = &layout.lines[target_row];
-        let idx_in_line = line.shaped.closest_index_for_x(*target_x);
-        let target = line.range.start + idx_in_line;
+        let target_x = *self.preferred_x.get_or_insert(x);
+        let Some(target) =
+            layout.index_for_vertical_move(self.edit.cursor_offset(), row_delta, target_x)
+        else {
+            return;
+        };
 
         if selecting {
             self.select_to(target, window, cx);
@@ -481,20 +742,9 @@ impl TextAreaState {
         else {
             return 0;
         };
-        let mut local_x = position.x - bounds.left() + self.scroll_x;
-        let mut local_y = position.y - bounds.top() + self.scroll_y;
-        if local_y < gpui::Pixels::ZERO {
-            local_y = gpui::Pixels::ZERO;
-        }
-        if local_x < gpui::Pixels::ZERO {
-            local_x = gpui::Pixels::ZERO;
-        }
-        let row = layout
-            .row_for_y(local_y)
-            .unwrap_or_else(|| layout.lines.len().saturating_sub(1));
-        let line = &layout.lines[row];
-        let idx_in_line = line.shaped.closest_index_for_x(local_x);
-        line.range.start + idx_in_line
+        let local_x = position.x - bounds.left() + self.scroll_x;
+        let local_y = position.y - bounds.top() + self.scroll_y;
+        layout.index_for_point(local_x, local_y)
     }
 }
 
@@ -547,6 +797,7 @@ impl gpui::EntityInputHandler for TextAreaState {
     ) {
         self.preferred_x = None;
         self.reset_cursor_blink(window, cx);
+        let new_text = self.fit_to_max_length(range_utf16.as_ref(), new_text);
         self.edit.replace_text_in_range(range_utf16, new_text);
         cx.notify();
     }
@@ -561,6 +812,7 @@ impl gpui::EntityInputHandler for TextAreaState {
     ) {
         self.preferred_x = None;
         self.reset_cursor_blink(window, cx);
+        let new_text = self.fit_to_max_length(range_utf16.as_ref(), new_text);
         self.edit
             .replace_and_mark_text_in_range(range_utf16, new_text, new_selected_range_utf16);
         cx.notify();
@@ -617,3 +869,72 @@ impl gpui::Focusable for TextAreaState {
         self.focus_handle.clone()
     }
 }
+
+/// Byte range of the whole lines `range` touches. A selection that ends right at
+/// the start of a line leaves that line out.
+fn line_block(text: &str, range: Range<usize>) -> Range<usize> {
+    let start = text[..range.start].rfind('\n').map_or(0, |i| i + 1);
+    let mut last = range.end;
+    if range.end > range.start && text[..range.end].ends_with('\n') {
+        last -= 1;
+    }
+    let end = text[last..].find('\n').map_or(text.len(), |i| last + i);
+    start..end
+}
+
+/// Prefixes every non-empty line of `block` with `indent`.
+fn indent_lines(block: &str, indent: &str) -> String {
+    block
+        .split('\n')
+        .map(|line| {
+            if line.is_empty() {
+                line.to_string()
+            } else {
+                format!("{indent}{line}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Removes one leading tab, or up to `width` leading spaces, from every line.
+fn outdent_lines(block: &str, width: usize) -> String {
+    block
+        .split('\n')
+        .map(|line| match line.strip_prefix('\t') {
+            Some(rest) => rest,
+            None => {
+                let spaces = line.len() - line.trim_start_matches(' ').len();
+                &line[spaces.min(width)..]
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_block_covers_whole_touched_lines() {
+        let text = "one\ntwo\nthree";
+        assert_eq!(line_block(text, 5..5), 4..7);
+        assert_eq!(line_block(text, 1..6), 0..7);
+        // Ending at the start of "three" doesn't pull that line in.
+        assert_eq!(line_block(text, 0..8), 0..7);
+        assert_eq!(line_block(text, 9..13), 8..13);
+    }
+
+    #[test]
+    fn indent_skips_empty_lines() {
+        assert_eq!(indent_lines("a\n\nb", "  "), "  a\n\n  b");
+        assert_eq!(indent_lines("a", "\t"), "\ta");
+    }
+
+    #[test]
+    fn outdent_removes_one_level() {
+        assert_eq!(outdent_lines("      a\n  b\nc", 4), "  a\nb\nc");
+        assert_eq!(outdent_lines("\t\ta\n    b", 4), "\ta\nb");
+    }
+}
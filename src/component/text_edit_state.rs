@@ -1,14 +1,111 @@
+use std::collections::VecDeque;
 use std::ops::Range;
 
 use gpui::{SharedString, UTF16Selection};
 use unicode_segmentation::UnicodeSegmentation;
 
+/// Default number of undo steps kept by [`EditHistory`].
+pub const DEFAULT_UNDO_LIMIT: usize = 200;
+
+/// Text and selection captured before an edit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EditSnapshot {
+    pub content: SharedString,
+    pub selected_range: Range<usize>,
+    pub selection_reversed: bool,
+}
+
+/// Bounded undo/redo stacks of [`EditSnapshot`]s.
+///
+/// Consecutive single-character insertions at the cursor are coalesced into one
+/// undo step, so undo removes a typed word rather than a single letter.
+#[derive(Clone, Debug)]
+pub struct EditHistory {
+    undo: VecDeque<EditSnapshot>,
+    redo: Vec<EditSnapshot>,
+    limit: usize,
+    /// Offset just after the last typed character while typing continues there.
+    typing_at: Option<usize>,
+}
+
+impl Default for EditHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_UNDO_LIMIT)
+    }
+}
+
+impl EditHistory {
+    pub fn new(limit: usize) -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            limit: limit.max(1),
+            typing_at: None,
+        }
+    }
+
+    /// Sets the maximum number of undo steps, dropping the oldest beyond it.
+    pub fn set_limit(&mut self, limit: usize) {
+        self.limit = limit.max(1);
+        self.trim();
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Records the state before replacing `range` with `inserted`.
+    pub fn record(&mut self, before: EditSnapshot, range: &Range<usize>, inserted: &str) {
+        let typing = range.is_empty() && inserted.chars().count() == 1;
+        if !(typing && self.typing_at == Some(range.start)) {
+            self.undo.push_back(before);
+            self.trim();
+        }
+        self.redo.clear();
+        self.typing_at = typing.then(|| range.start + inserted.len());
+    }
+
+    /// Returns the state to restore for undo, remembering `current` for redo.
+    pub fn undo(&mut self, current: EditSnapshot) -> Option<EditSnapshot> {
+        let previous = self.undo.pop_back()?;
+        self.redo.push(current);
+        self.typing_at = None;
+        Some(previous)
+    }
+
+    /// Returns the state to restore for redo, remembering `current` for undo.
+    pub fn redo(&mut self, current: EditSnapshot) -> Option<EditSnapshot> {
+        let next = self.redo.pop()?;
+        self.undo.push_back(current);
+        self.trim();
+        self.typing_at = None;
+        Some(next)
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.typing_at = None;
+    }
+
+    fn trim(&mut self) {
+        while self.undo.len() > self.limit {
+            self.undo.pop_front();
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct TextEditState {
     content: SharedString,
     selected_range: Range<usize>,
     selection_reversed: bool,
     marked_range: Option<Range<usize>>,
+    history: EditHistory,
 }
 
 impl TextEditState {
@@ -24,6 +121,7 @@ impl Default for TextEditState {
             selected_range: 0..0,
             selection_reversed: false,
             marked_range: None,
+            history: EditHistory::default(),
         }
     }
 }
@@ -37,6 +135,11 @@ impl TextEditState {
         &self.content
     }
 
+    /// Length of the content in grapheme clusters, as counted by `max_length`.
+    pub fn grapheme_count(&self) -> usize {
+        self.content.graphemes(true).count()
+    }
+
     pub fn selected_range(&self) -> &Range<usize> {
         &self.selected_range
     }
@@ -57,6 +160,7 @@ impl TextEditState {
         }
     }
 
+    /// Replaces the whole content, moving the cursor to the end and clearing undo history.
     pub fn set_content(&mut self, content: impl Into<SharedString>) {
         let content = content.into();
         let end = content.len();
@@ -64,6 +168,68 @@ impl TextEditState {
         self.selected_range = end..end;
         self.selection_reversed = false;
         self.marked_range = None;
+        self.history.clear();
+    }
+
+    /// Sets how many undo steps are kept (default: [`DEFAULT_UNDO_LIMIT`]).
+    pub fn set_undo_limit(&mut self, limit: usize) {
+        self.history.set_limit(limit);
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.history.can_undo()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.history.can_redo()
+    }
+
+    /// Reverts the last edit, restoring its text and selection. Returns `false` if
+    /// there is nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        match self.history.undo(self.snapshot()) {
+            Some(snapshot) => {
+                self.restore(snapshot);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Re-applies the last undone edit. Returns `false` if there is nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        match self.history.redo(self.snapshot()) {
+            Some(snapshot) => {
+                self.restore(snapshot);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn snapshot(&self) -> EditSnapshot {
+        EditSnapshot {
+            content: self.content.clone(),
+            selected_range: self.selected_range.clone(),
+            selection_reversed: self.selection_reversed,
+        }
+    }
+
+    fn restore(&mut self, snapshot: EditSnapshot) {
+        self.content = snapshot.content;
+        self.selected_range = clamp_range(&snapshot.selected_range, self.content.len());
+        self.selection_reversed = snapshot.selection_reversed;
+        self.marked_range = None;
+    }
+
+    /// Records an undo step for replacing `range`, unless an IME composition is
+    /// in progress (the step was recorded when the composition started).
+    fn record_edit(&mut self, range: (usize, usize), new_text: &str) {
+        if self.marked_range.is_some() || (range.0 == range.1 && new_text.is_empty()) {
+            return;
+        }
+        self.history
+            .record(self.snapshot(), &(range.0..range.1), new_text);
     }
 
     pub fn move_to(&mut self, offset: usize) {
@@ -72,6 +238,15 @@ impl TextEditState {
         self.selection_reversed = false;
     }
 
+    /// Selects `range`, clamped to the content and snapped back to character
+    /// boundaries.
+    pub fn select_range(&mut self, range: Range<usize>) {
+        let start = floor_char_boundary(&self.content, range.start);
+        let end = floor_char_boundary(&self.content, range.end).max(start);
+        self.selected_range = start..end;
+        self.selection_reversed = false;
+    }
+
     pub fn select_to(&mut self, offset: usize) {
         let offset = offset.clamp(0, self.content.len());
         if self.selection_reversed {
@@ -100,6 +275,31 @@ impl TextEditState {
             .unwrap_or(self.content.len())
     }
 
+    /// Start of the word before `offset`, skipping any whitespace in between.
+    ///
+    /// Words follow Unicode word boundaries, so punctuation runs are their own
+    /// stops and CJK ideographs, which aren't space-delimited, step one at a time.
+    pub fn previous_word_boundary(&self, offset: usize) -> usize {
+        self.content
+            .split_word_bound_indices()
+            .rev()
+            .filter(|(idx, _)| *idx < offset)
+            .find(|(_, word)| !word.trim().is_empty())
+            .map(|(idx, _)| idx)
+            .unwrap_or(0)
+    }
+
+    /// End of the word after `offset`, skipping any whitespace in between.
+    pub fn next_word_boundary(&self, offset: usize) -> usize {
+        self.content
+            .split_word_bound_indices()
+            .map(|(idx, word)| (idx + word.len(), word))
+            .filter(|(end, _)| *end > offset)
+            .find(|(_, word)| !word.trim().is_empty())
+            .map(|(end, _)| end)
+            .unwrap_or(self.content.len())
+    }
+
     pub fn selected_text_range(&self) -> UTF16Selection {
         UTF16Selection {
             range: self.range_to_utf16(&self.selected_range),
@@ -131,6 +331,7 @@ impl TextEditState {
             .range_for_replacement_utf8(range_utf16.as_ref())
             .unwrap_or_else(|| self.selected_range.clone());
         let (range_start, range_end) = clamp_range_bounds(&range, self.content.len());
+        self.record_edit((range_start, range_end), new_text);
 
         self.content =
             (self.content[0..range_start].to_owned() + new_text + &self.content[range_end..])
@@ -150,6 +351,7 @@ impl TextEditState {
             .range_for_replacement_utf8(range_utf16.as_ref())
             .unwrap_or_else(|| self.selected_range.clone());
         let (range_start, range_end) = clamp_range_bounds(&range, self.content.len());
+        self.record_edit((range_start, range_end), new_text);
 
         self.content =
             (self.content[0..range_start].to_owned() + new_text + &self.content[range_end..])
@@ -207,6 +409,34 @@ impl TextEditState {
         self.offset_from_utf16(range_utf16.start)..self.offset_from_utf16(range_utf16.end)
     }
 
+    /// The longest prefix of `new_text` that can replace `range_utf16` (or the
+    /// marked text / selection, as in [`Self::replace_text_in_range`]) without the
+    /// content exceeding `max_graphemes` grapheme clusters.
+    pub fn fit_replacement<'a>(
+        &self,
+        range_utf16: Option<&Range<usize>>,
+        new_text: &'a str,
+        max_graphemes: usize,
+    ) -> &'a str {
+        let range = self
+            .range_for_replacement_utf8(range_utf16)
+            .unwrap_or_else(|| self.selected_range.clone());
+        let (range_start, range_end) = clamp_range_bounds(&range, self.content.len());
+        let kept = self.content[..range_start].graphemes(true).count()
+            + self.content[range_end..].graphemes(true).count();
+        truncate_graphemes(new_text, max_graphemes.saturating_sub(kept))
+    }
+
+    /// The byte range an edit with `range_utf16` replaces: the given range, else
+    /// the marked text, else the selection.
+    pub fn replacement_range(&self, range_utf16: Option<&Range<usize>>) -> Range<usize> {
+        let range = self
+            .range_for_replacement_utf8(range_utf16)
+            .unwrap_or_else(|| self.selected_range.clone());
+        let (start, end) = clamp_range_bounds(&range, self.content.len());
+        start..end
+    }
+
     fn range_for_replacement_utf8(
         &self,
         range_utf16: Option<&Range<usize>>,
@@ -217,6 +447,23 @@ impl TextEditState {
     }
 }
 
+/// The largest char boundary of `text` at or before `offset`.
+pub fn floor_char_boundary(text: &str, offset: usize) -> usize {
+    let mut offset = offset.min(text.len());
+    while !text.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    offset
+}
+
+/// The first `max` grapheme clusters of `text`.
+pub fn truncate_graphemes(text: &str, max: usize) -> &str {
+    match text.grapheme_indices(true).nth(max) {
+        Some((idx, _)) => &text[..idx],
+        None => text,
+    }
+}
+
 fn clamp_range(range: &Range<usize>, len: usize) -> Range<usize> {
     let start = range.start.min(len);
     let end = range.end.min(len).max(start);
@@ -228,3 +475,173 @@ fn clamp_range_bounds(range: &Range<usize>, len: usize) -> (usize, usize) {
     let end = range.end.min(len).max(start);
     (start, end)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_text(state: &mut TextEditState, text: &str) {
+        for ch in text.chars() {
+            state.replace_text_in_range(None, ch.encode_utf8(&mut [0; 4]));
+        }
+    }
+
+    #[test]
+    fn replacement_is_cut_to_fit_max_length() {
+        let mut state = TextEditState::new();
+        state.set_content("abc");
+
+        assert_eq!(state.fit_replacement(None, "defgh", 5), "de");
+        assert_eq!(state.fit_replacement(None, "x", 3), "");
+        // Replacing a selection frees up its graphemes.
+        state.move_to(0);
+        state.select_to(2);
+        assert_eq!(state.fit_replacement(None, "wxyz", 4), "wxy");
+        assert_eq!(state.fit_replacement(Some(&(0..3)), "wxyz", 4), "wxyz");
+    }
+
+    #[test]
+    fn max_length_counts_graphemes_not_bytes() {
+        let mut state = TextEditState::new();
+        state.set_content("é");
+
+        assert_eq!(state.fit_replacement(None, "👍🏽e\u{301}x", 3), "👍🏽e\u{301}");
+        assert_eq!(truncate_graphemes("日本語", 2), "日本");
+        assert_eq!(truncate_graphemes("ab", 5), "ab");
+    }
+
+    #[test]
+    fn select_range_clamps_to_content_and_char_boundaries() {
+        let mut state = TextEditState::new();
+        state.set_content("héllo");
+
+        state.select_range(1..99);
+        assert_eq!(state.selected_range(), &(1..6));
+
+        // Offset 2 falls inside "é" and snaps back to its start.
+        state.select_range(2..4);
+        assert_eq!(state.selected_range(), &(1..4));
+
+        // A backwards range collapses at its start.
+        let (start, end) = (5, 3);
+        state.select_range(start..end);
+        assert_eq!(state.selected_range(), &(5..5));
+    }
+
+    fn word_stops(text: &'static str) -> (Vec<usize>, Vec<usize>) {
+        let mut state = TextEditState::new();
+        state.set_content(text);
+
+        let mut backward = vec![text.len()];
+        while let Some(&offset) = backward.last().filter(|offset| **offset > 0) {
+            backward.push(state.previous_word_boundary(offset));
+        }
+        let mut forward = vec![0];
+        while let Some(&offset) = forward.last().filter(|offset| **offset < text.len()) {
+            forward.push(state.next_word_boundary(offset));
+        }
+        (backward, forward)
+    }
+
+    #[test]
+    fn word_boundaries_skip_whitespace() {
+        let (backward, forward) = word_stops("  hello   world  ");
+
+        assert_eq!(backward, [17, 10, 2, 0]);
+        assert_eq!(forward, [0, 7, 15, 17]);
+    }
+
+    #[test]
+    fn word_boundaries_stop_at_punctuation() {
+        let mut state = TextEditState::new();
+        state.set_content("foo(bar) baz");
+
+        assert_eq!(state.next_word_boundary(0), 3);
+        assert_eq!(state.next_word_boundary(3), 4);
+        assert_eq!(state.previous_word_boundary(9), 7);
+        assert_eq!(state.previous_word_boundary(7), 4);
+        // From inside a word, the boundary is that word's edge.
+        assert_eq!(state.previous_word_boundary(11), 9);
+        assert_eq!(state.next_word_boundary(10), 12);
+    }
+
+    #[test]
+    fn cjk_words_step_per_ideograph() {
+        let text = "你好 世界";
+        let (backward, forward) = word_stops(text);
+
+        assert_eq!(backward, [13, 10, 7, 3, 0]);
+        assert_eq!(forward, [0, 3, 6, 10, 13]);
+    }
+
+    #[test]
+    fn typed_characters_undo_as_one_group() {
+        let mut state = TextEditState::new();
+        type_text(&mut state, "hello");
+
+        assert!(state.undo());
+        assert_eq!(state.content().as_str(), "");
+        assert!(!state.undo());
+
+        assert!(state.redo());
+        assert_eq!(state.content().as_str(), "hello");
+        assert_eq!(state.selected_range(), &(5..5));
+    }
+
+    #[test]
+    fn undo_restores_selection_after_select_all_delete() {
+        let mut state = TextEditState::new();
+        state.set_content("draft");
+        state.move_to(0);
+        state.select_to(5);
+        state.replace_text_in_range(None, "");
+
+        assert!(state.undo());
+        assert_eq!(state.content().as_str(), "draft");
+        assert_eq!(state.selected_range(), &(0..5));
+
+        assert!(state.redo());
+        assert_eq!(state.content().as_str(), "");
+        assert_eq!(state.selected_range(), &(0..0));
+    }
+
+    #[test]
+    fn moving_the_cursor_starts_a_new_group() {
+        let mut state = TextEditState::new();
+        type_text(&mut state, "ab");
+        state.move_to(0);
+        type_text(&mut state, "x");
+
+        assert!(state.undo());
+        assert_eq!(state.content().as_str(), "ab");
+        assert!(state.undo());
+        assert_eq!(state.content().as_str(), "");
+    }
+
+    #[test]
+    fn new_edit_clears_redo_and_set_content_clears_history() {
+        let mut state = TextEditState::new();
+        type_text(&mut state, "a");
+        state.undo();
+        type_text(&mut state, "b");
+        assert!(!state.redo());
+
+        state.set_content("reset");
+        assert!(!state.can_undo());
+        assert!(!state.can_redo());
+    }
+
+    #[test]
+    fn history_is_capped() {
+        let mut state = TextEditState::new();
+        state.set_undo_limit(2);
+        for word in ["one ", "two ", "three "] {
+            state.replace_text_in_range(None, word);
+        }
+
+        assert!(state.undo());
+        assert!(state.undo());
+        assert!(!state.undo());
+        assert_eq!(state.content().as_str(), "one ");
+    }
+}
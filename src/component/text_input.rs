@@ -3,8 +3,13 @@ use std::sync::Arc;
 
 use super::TextEditState;
 use super::input::action_handler;
-use crate::component::{ChangeCallback, compute_input_style};
-use crate::theme::ActiveTheme;
+use crate::component::{
+    ChangeCallback, DEFAULT_SELECTION_OPACITY, IconName, InputMask, ShapeKey, ValidateCallback,
+    WindowCallback, compute_input_style, generate_element_id, icon, icon_button, inline_error,
+    notify_focus_change, register_input, registered_state, selection_color, sync_controlled_value,
+    truncate_graphemes, warn_generated_id,
+};
+use crate::theme::{ActiveTheme, Scale};
 use gpui::{
     AnyElement, App, Bounds, Context, CursorStyle, Div, Element, ElementId, ElementInputHandler,
     Entity, EntityInputHandler, FocusHandle, Focusable, GlobalElementId, Hsla, InteractiveElement,
@@ -31,6 +36,13 @@ actions!(
         Paste,
         Cut,
         Copy,
+        Undo,
+        Redo,
+        WordLeft,
+        WordRight,
+        BackspaceWord,
+        DeleteWord,
+        Clear,
     ]
 );
 
@@ -47,14 +59,21 @@ pub(crate) fn init(cx: &mut App) {
         gpui::KeyBinding::new("enter", Enter, Some("UITextInput")),
         gpui::KeyBinding::new("left", Left, Some("UITextInput")),
         gpui::KeyBinding::new("right", Right, Some("UITextInput")),
+        gpui::KeyBinding::new("alt-left", WordLeft, Some("UITextInput")),
+        gpui::KeyBinding::new("alt-right", WordRight, Some("UITextInput")),
+        gpui::KeyBinding::new("alt-backspace", BackspaceWord, Some("UITextInput")),
+        gpui::KeyBinding::new("alt-delete", DeleteWord, Some("UITextInput")),
         gpui::KeyBinding::new("shift-left", SelectLeft, Some("UITextInput")),
         gpui::KeyBinding::new("shift-right", SelectRight, Some("UITextInput")),
         gpui::KeyBinding::new("secondary-a", SelectAll, Some("UITextInput")),
         gpui::KeyBinding::new("secondary-v", Paste, Some("UITextInput")),
         gpui::KeyBinding::new("secondary-c", Copy, Some("UITextInput")),
         gpui::KeyBinding::new("secondary-x", Cut, Some("UITextInput")),
+        gpui::KeyBinding::new("secondary-z", Undo, Some("UITextInput")),
+        gpui::KeyBinding::new("secondary-shift-z", Redo, Some("UITextInput")),
         gpui::KeyBinding::new("home", Home, Some("UITextInput")),
         gpui::KeyBinding::new("end", End, Some("UITextInput")),
+        gpui::KeyBinding::new("escape", Clear, Some("UITextInput")),
         gpui::KeyBinding::new(
             "ctrl-secondary-space",
             ShowCharacterPalette,
@@ -69,8 +88,12 @@ pub struct TextInputState {
     placeholder: SharedString,
     scroll_x: Pixels,
     last_layout: Option<ShapedLine>,
+    /// What `last_layout` was shaped from.
+    shape_key: Option<ShapeKey>,
     last_bounds: Option<Bounds<Pixels>>,
     is_selecting: bool,
+    max_length: Option<usize>,
+    mask: Option<InputMask>,
 
     cursor_visible: bool,
     cursor_blink_epoch: usize,
@@ -86,8 +109,11 @@ impl TextInputState {
             placeholder: "".into(),
             scroll_x: Pixels::ZERO,
             last_layout: None,
+            shape_key: None,
             last_bounds: None,
             is_selecting: false,
+            max_length: None,
+            mask: None,
 
             cursor_visible: true,
             cursor_blink_epoch: 0,
@@ -151,10 +177,141 @@ impl TextInputState {
     }
 
     pub fn set_content(&mut self, content: impl Into<SharedString>) {
-        self.edit.set_content(content);
+        let mut content = content.into();
+        if let Some(mask) = &self.mask {
+            content = mask.apply(&content).into();
+        }
+        match self.max_length {
+            Some(max) => self
+                .edit
+                .set_content(truncate_graphemes(&content, max).to_owned()),
+            None => self.edit.set_content(content),
+        }
         self.scroll_x = Pixels::ZERO;
     }
 
+    /// Caps the content at `max_length` graphemes, cutting any existing excess.
+    pub fn set_max_length(&mut self, max_length: Option<usize>) {
+        self.max_length = max_length;
+        if let Some(max) = max_length
+            && truncate_graphemes(self.edit.content(), max).len() < self.edit.content().len()
+        {
+            self.set_content(self.edit.content().clone());
+        }
+    }
+
+    /// The characters the user entered, without the mask's literals.
+    ///
+    /// Same as [`Self::content`] when no mask is set.
+    pub fn unmasked(&self) -> SharedString {
+        match &self.mask {
+            Some(mask) => mask.unmask(self.edit.content()).into(),
+            None => self.edit.content().clone(),
+        }
+    }
+
+    pub fn mask(&self) -> Option<&InputMask> {
+        self.mask.as_ref()
+    }
+
+    /// Formats input with `mask`, re-laying out the current content to match.
+    pub fn set_mask(&mut self, mask: Option<InputMask>) {
+        let unmasked = self.unmasked();
+        self.mask = mask;
+        if self.mask.is_some() {
+            self.set_content(unmasked);
+        }
+    }
+
+    /// Applies an edit through the mask, if one is set. Returns `false` when the
+    /// edit should go through unchanged.
+    fn replace_masked(&mut self, range_utf16: Option<&Range<usize>>, new_text: &str) -> bool {
+        let Some(mask) = &self.mask else {
+            return false;
+        };
+        let content = self.edit.content();
+        let range = self.edit.replacement_range(range_utf16);
+        let (masked, cursor) = mask.edit(content, range, new_text);
+
+        // Replace only from the first changed character so undo grouping still
+        // sees single-character typing.
+        let common = content
+            .char_indices()
+            .zip(masked.chars())
+            .find(|((_, a), b)| a != b)
+            .map_or_else(|| content.len().min(masked.len()), |((i, _), _)| i);
+        let replaced = self.edit.range_to_utf16(&(common..content.len()));
+        self.edit
+            .replace_text_in_range(Some(replaced), &masked[common..]);
+        self.edit.move_to(cursor);
+        true
+    }
+
+    /// `offset` moved back over literals, so deleting up to it removes an entered
+    /// character rather than only a separator.
+    fn skip_literals_back(&self, mut offset: usize) -> usize {
+        if let Some(mask) = &self.mask {
+            let content = self.edit.content();
+            while offset > 0 && mask.is_literal_at(content[..offset].chars().count()) {
+                offset = self.edit.previous_boundary(offset);
+            }
+        }
+        offset
+    }
+
+    /// Like [`Self::skip_literals_back`], moving forward.
+    fn skip_literals_forward(&self, mut offset: usize) -> usize {
+        if let Some(mask) = &self.mask {
+            let content = self.edit.content();
+            while offset < content.len()
+                && mask.is_literal_at(content[..offset].chars().count() - 1)
+            {
+                offset = self.edit.next_boundary(offset);
+            }
+        }
+        offset
+    }
+
+    /// The part of `new_text` that fits under `max_length` when replacing `range_utf16`.
+    fn fit_to_max_length<'a>(
+        &self,
+        range_utf16: Option<&Range<usize>>,
+        new_text: &'a str,
+    ) -> &'a str {
+        match self.max_length {
+            Some(max) => self.edit.fit_replacement(range_utf16, new_text, max),
+            None => new_text,
+        }
+    }
+
+    /// Focuses the field, e.g. to jump to the first invalid field of a form.
+    pub fn focus(&mut self, window: &mut gpui::Window, cx: &mut Context<Self>) {
+        self.focus_in(window, cx);
+        cx.notify();
+    }
+
+    /// Removes focus from the field if it has it.
+    pub fn blur(&mut self, window: &mut gpui::Window, cx: &mut Context<Self>) {
+        if self.focus_handle.is_focused(window) {
+            window.blur();
+            cx.notify();
+        }
+    }
+
+    /// Selects the byte `range` of the content, clamped to its bounds.
+    pub fn select_range(&mut self, range: Range<usize>, cx: &mut Context<Self>) {
+        self.edit.select_range(range);
+        cx.notify();
+    }
+
+    /// Selects the whole content.
+    ///
+    /// This is the programmatic `select_all`; that name belongs to the handler
+    /// of the `SelectAll` action (Cmd/Ctrl+A), which needs a window.
+    pub fn select_all_content(&mut self, cx: &mut Context<Self>) {
+        self.select_range(0..self.edit.content().len(), cx);
+    }
+
     fn focus_in(&mut self, window: &mut gpui::Window, cx: &mut Context<Self>) {
         if self.focus_subscription.is_none() {
             let focus_handle = self.focus_handle.clone();
@@ -194,6 +351,22 @@ impl TextInputState {
         }
     }
 
+    fn word_left(&mut self, _: &WordLeft, window: &mut gpui::Window, cx: &mut Context<Self>) {
+        self.move_to(
+            self.edit.previous_word_boundary(self.edit.cursor_offset()),
+            window,
+            cx,
+        );
+    }
+
+    fn word_right(&mut self, _: &WordRight, window: &mut gpui::Window, cx: &mut Context<Self>) {
+        self.move_to(
+            self.edit.next_word_boundary(self.edit.cursor_offset()),
+            window,
+            cx,
+        );
+    }
+
     fn select_left(&mut self, _: &SelectLeft, window: &mut gpui::Window, cx: &mut Context<Self>) {
         self.select_to(
             self.edit.previous_boundary(self.edit.cursor_offset()),
@@ -226,7 +399,7 @@ impl TextInputState {
     fn backspace(&mut self, _: &Backspace, window: &mut gpui::Window, cx: &mut Context<Self>) {
         if self.edit.selected_range().is_empty() {
             self.select_to(
-                self.edit.previous_boundary(self.edit.cursor_offset()),
+                self.skip_literals_back(self.edit.previous_boundary(self.edit.cursor_offset())),
                 window,
                 cx,
             )
@@ -238,7 +411,7 @@ impl TextInputState {
     fn delete(&mut self, _: &Delete, window: &mut gpui::Window, cx: &mut Context<Self>) {
         if self.edit.selected_range().is_empty() {
             self.select_to(
-                self.edit.next_boundary(self.edit.cursor_offset()),
+                self.skip_literals_forward(self.edit.next_boundary(self.edit.cursor_offset())),
                 window,
                 cx,
             )
@@ -247,6 +420,33 @@ impl TextInputState {
         self.replace_text_in_range(None, "", window, cx)
     }
 
+    fn backspace_word(
+        &mut self,
+        _: &BackspaceWord,
+        window: &mut gpui::Window,
+        cx: &mut Context<Self>,
+    ) {
+        if self.edit.selected_range().is_empty() {
+            self.select_to(
+                self.edit.previous_word_boundary(self.edit.cursor_offset()),
+                window,
+                cx,
+            )
+        }
+        self.replace_text_in_range(None, "", window, cx)
+    }
+
+    fn delete_word(&mut self, _: &DeleteWord, window: &mut gpui::Window, cx: &mut Context<Self>) {
+        if self.edit.selected_range().is_empty() {
+            self.select_to(
+                self.edit.next_word_boundary(self.edit.cursor_offset()),
+                window,
+                cx,
+            )
+        }
+        self.replace_text_in_range(None, "", window, cx)
+    }
+
     fn on_mouse_down(
         &mut self,
         event: &MouseDownEvent,
@@ -312,6 +512,20 @@ impl TextInputState {
         }
     }
 
+    fn undo(&mut self, _: &Undo, window: &mut gpui::Window, cx: &mut Context<Self>) {
+        if self.edit.undo() {
+            self.reset_cursor_blink(window, cx);
+            cx.notify();
+        }
+    }
+
+    fn redo(&mut self, _: &Redo, window: &mut gpui::Window, cx: &mut Context<Self>) {
+        if self.edit.redo() {
+            self.reset_cursor_blink(window, cx);
+            cx.notify();
+        }
+    }
+
     fn move_to(&mut self, offset: usize, window: &mut gpui::Window, cx: &mut Context<Self>) {
         self.edit.move_to(offset);
         self.reset_cursor_blink(window, cx);
@@ -391,7 +605,10 @@ impl EntityInputHandler for TextInputState {
         cx: &mut Context<Self>,
     ) {
         self.reset_cursor_blink(window, cx);
-        self.edit.replace_text_in_range(range_utf16, new_text);
+        let new_text = self.fit_to_max_length(range_utf16.as_ref(), new_text);
+        if !self.replace_masked(range_utf16.as_ref(), new_text) {
+            self.edit.replace_text_in_range(range_utf16, new_text);
+        }
         cx.notify();
     }
 
@@ -404,8 +621,15 @@ impl EntityInputHandler for TextInputState {
         cx: &mut Context<Self>,
     ) {
         self.reset_cursor_blink(window, cx);
-        self.edit
-            .replace_and_mark_text_in_range(range_utf16, new_text, new_selected_range_utf16);
+        let new_text = self.fit_to_max_length(range_utf16.as_ref(), new_text);
+        // Masked fields take composed text as-is rather than marking it.
+        if !self.replace_masked(range_utf16.as_ref(), new_text) {
+            self.edit.replace_and_mark_text_in_range(
+                range_utf16,
+                new_text,
+                new_selected_range_utf16,
+            );
+        }
 
         cx.notify();
     }
@@ -461,10 +685,41 @@ impl Focusable for TextInputState {
 struct TextLineElement {
     input: Entity<TextInputState>,
     disabled: bool,
+    read_only: bool,
+    selection_opacity: f32,
+}
+
+/// Horizontal scroll that keeps `cursor_x` within `max_cursor_x` of a line
+/// `line_width` wide, starting from the `previous` frame's scroll.
+///
+/// In RTL, text that fits is right-aligned by a negative scroll. The offset is
+/// stored with the scroll, so hit testing follows it, and clamped away again on
+/// the next call.
+fn line_scroll_x(
+    previous: Pixels,
+    cursor_x: Pixels,
+    line_width: Pixels,
+    max_cursor_x: Pixels,
+    rtl: bool,
+) -> Pixels {
+    let max_scroll_x = (line_width - max_cursor_x).max(Pixels::ZERO);
+    let mut scroll_x = previous.clamp(Pixels::ZERO, max_scroll_x);
+
+    if cursor_x < scroll_x {
+        scroll_x = cursor_x;
+    } else if cursor_x > scroll_x + max_cursor_x {
+        scroll_x = cursor_x - max_cursor_x;
+    }
+    scroll_x = scroll_x.clamp(Pixels::ZERO, max_scroll_x);
+    if rtl {
+        scroll_x -= (max_cursor_x - line_width).max(Pixels::ZERO);
+    }
+    scroll_x
 }
 
 struct PrepaintState {
     line: Option<ShapedLine>,
+    shape_key: Option<ShapeKey>,
     cursor: Option<PaintQuad>,
     selection: Option<PaintQuad>,
     scroll_x: Pixels,
@@ -562,23 +817,32 @@ impl Element for TextLineElement {
         };
 
         let font_size = style.font_size.to_pixels(window.rem_size());
-        let line = window
-            .text_system()
-            .shape_line(display_text, font_size, &runs, None);
+        let shape_key = ShapeKey {
+            text: display_text.clone(),
+            runs: runs.clone(),
+            marked_range,
+            font_size,
+            line_height: window.line_height(),
+            wrap_width: None,
+        };
+        let line = match &input.last_layout {
+            Some(line) if input.shape_key.as_ref() == Some(&shape_key) => line.clone(),
+            _ => window
+                .text_system()
+                .shape_line(display_text, font_size, &runs, None),
+        };
 
         let cursor_pos = line.x_for_index(cursor);
 
         let cursor_width = px(2.);
         let max_cursor_x = (bounds.size.width - cursor_width).max(Pixels::ZERO);
-        let max_scroll_x = (line.width - max_cursor_x).max(Pixels::ZERO);
-        let mut scroll_x = input.scroll_x.clamp(Pixels::ZERO, max_scroll_x);
-
-        if cursor_pos < scroll_x {
-            scroll_x = cursor_pos;
-        } else if cursor_pos > scroll_x + max_cursor_x {
-            scroll_x = cursor_pos - max_cursor_x;
-        }
-        scroll_x = scroll_x.clamp(Pixels::ZERO, max_scroll_x);
+        let scroll_x = line_scroll_x(
+            input.scroll_x,
+            cursor_pos,
+            line.width,
+            max_cursor_x,
+            cx.text_direction().is_rtl(),
+        );
 
         let (selection, cursor) = if selected_range.is_empty() {
             (
@@ -606,7 +870,7 @@ impl Element for TextLineElement {
                             bounds.bottom(),
                         ),
                     ),
-                    cx.theme().border.focus.alpha(0.25),
+                    selection_color(cx.theme(), self.selection_opacity, self.read_only),
                 )),
                 None,
             )
@@ -614,6 +878,7 @@ impl Element for TextLineElement {
 
         PrepaintState {
             line: Some(line),
+            shape_key: Some(shape_key),
             cursor,
             selection,
             scroll_x,
@@ -631,7 +896,7 @@ impl Element for TextLineElement {
         cx: &mut App,
     ) {
         let focus_handle = self.input.read(cx).focus_handle.clone();
-        if !self.disabled {
+        if !self.disabled && !self.read_only {
             window.handle_input(
                 &focus_handle,
                 ElementInputHandler::new(bounds, self.input.clone()),
@@ -660,6 +925,7 @@ impl Element for TextLineElement {
 
         self.input.update(cx, |input, _cx| {
             input.last_layout = Some(line);
+            input.shape_key = prepaint.shape_key.take();
             input.last_bounds = Some(bounds);
             input.scroll_x = prepaint.scroll_x;
         });
@@ -669,14 +935,19 @@ impl Element for TextLineElement {
 #[derive(IntoElement)]
 pub struct TextInput {
     element_id: ElementId,
+    generated_id: bool,
     base: Div,
     placeholder: SharedString,
 
     disabled: bool,
+    read_only: bool,
+    selection_opacity: f32,
+    undo_limit: Option<usize>,
 
     bg: Option<Hsla>,
     border: Option<Hsla>,
     focus_border: Option<Hsla>,
+    error_border: Option<Hsla>,
     text_color: Option<Hsla>,
     height: Option<gpui::AbsoluteLength>,
 
@@ -684,38 +955,83 @@ pub struct TextInput {
     set_content_once: Option<SharedString>,
 
     max_length: Option<usize>,
+    mask: Option<InputMask>,
+    invalid: bool,
+    clearable: bool,
+    prefix: Option<AnyElement>,
+    suffix: Option<AnyElement>,
 
     on_change: Option<ChangeCallback<SharedString>>,
 
+    on_validate: Option<ValidateCallback>,
+
     on_submit: Option<ChangeCallback<SharedString>>,
 
-    on_focus: Option<ChangeCallback<SharedString>>,
+    on_focus: Option<WindowCallback>,
+    on_blur: Option<ChangeCallback<SharedString>>,
 }
 
 impl TextInput {
+    /// Creates a new TextInput without an explicit id.
+    ///
+    /// The id falls back to one derived from the caller's source location; prefer `.id()`.
+    #[track_caller]
     pub fn new() -> Self {
         Self {
-            element_id: "ui:text-input".into(),
-            base: div().h(px(36.)).px_3(),
+            element_id: generate_element_id("ui:text-input"),
+            generated_id: true,
+            base: div().h(px(36.)),
             placeholder: "".into(),
 
             disabled: false,
+            read_only: false,
+            selection_opacity: DEFAULT_SELECTION_OPACITY,
+            undo_limit: None,
             bg: None,
             border: None,
             focus_border: None,
+            error_border: None,
             text_color: None,
             height: None,
             content: None,
             set_content_once: None,
             max_length: None,
+            mask: None,
+            invalid: false,
+            clearable: false,
+            prefix: None,
+            suffix: None,
             on_change: None,
+            on_validate: None,
             on_submit: None,
             on_focus: None,
+            on_blur: None,
         }
     }
 
+    /// The state of the text input with `id`, for driving focus and selection from
+    /// outside the component:
+    ///
+    /// ```rust,ignore
+    /// TextInput::state_handle("email", window, cx).update(cx, |state, cx| state.focus(window, cx));
+    /// ```
+    ///
+    /// A text input that has rendered is found wherever it sits in the tree. Before its
+    /// first render this returns the keyed state it will pick up, which only lines
+    /// up when called from the same element-id scope it renders in.
+    pub fn state_handle(
+        id: impl Into<ElementId>,
+        window: &mut gpui::Window,
+        cx: &mut App,
+    ) -> Entity<TextInputState> {
+        let id = id.into();
+        registered_state(window, cx, &id)
+            .unwrap_or_else(|| window.use_keyed_state(id, cx, |_, cx| TextInputState::new(cx)))
+    }
+
     pub fn id(mut self, id: impl Into<ElementId>) -> Self {
         self.element_id = id.into();
+        self.generated_id = false;
         self
     }
 
@@ -734,11 +1050,40 @@ impl TextInput {
         self
     }
 
+    /// Keeps the field focusable and selectable but rejects edits.
+    ///
+    /// The selection is drawn in a muted color while read-only.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Opacity of the selection fill (default: 0.25).
+    pub fn selection_opacity(mut self, opacity: f32) -> Self {
+        self.selection_opacity = opacity;
+        self
+    }
+
+    /// Maximum number of undo steps kept (default: 200).
+    pub fn undo_limit(mut self, limit: usize) -> Self {
+        self.undo_limit = Some(limit);
+        self
+    }
+
+    /// Controlled content; shorthand for `.value(Some(content))`.
     pub fn content(mut self, content: impl Into<SharedString>) -> Self {
         self.content = Some(content.into());
         self
     }
 
+    /// Controlled value. `Some("")` forces the field empty; `None` leaves it uncontrolled.
+    ///
+    /// The value is written into the field whenever it changes between renders.
+    pub fn value(mut self, value: Option<SharedString>) -> Self {
+        self.content = value;
+        self
+    }
+
     /// Set content once programmatically (e.g., clear button, initial value, loading saved data).
     /// Unlike `.content()`, this only applies once and doesn't create a sync loop.
     /// Use this when you need to:
@@ -758,6 +1103,24 @@ impl TextInput {
         self
     }
 
+    /// Validates the content after every change, once `on_change` has run.
+    ///
+    /// An `Err(message)` marks the field invalid and shows `message` below it until
+    /// a later change validates.
+    pub fn on_validate<F>(mut self, validate: F) -> Self
+    where
+        F: 'static + Fn(&str) -> Result<(), SharedString>,
+    {
+        self.on_validate = Some(Arc::new(validate));
+        self
+    }
+
+    /// Forces the invalid (error border) state, e.g. for server-side errors.
+    pub fn invalid(mut self, invalid: bool) -> Self {
+        self.invalid = invalid;
+        self
+    }
+
     pub fn on_submit<F>(mut self, handler: F) -> Self
     where
         F: 'static + Fn(SharedString, &mut gpui::Window, &mut App),
@@ -766,20 +1129,66 @@ impl TextInput {
         self
     }
 
+    /// Called once each time the field gains focus.
     pub fn on_focus<F>(mut self, handler: F) -> Self
     where
-        F: 'static + Fn(SharedString, &mut gpui::Window, &mut App),
+        F: 'static + Fn(&mut gpui::Window, &mut App),
     {
         self.on_focus = Some(Arc::new(handler));
         self
     }
 
-    /// Set the maximum number of characters allowed in the input.
+    /// Called once each time the field loses focus, with its current content.
+    pub fn on_blur<F>(mut self, handler: F) -> Self
+    where
+        F: 'static + Fn(SharedString, &mut gpui::Window, &mut App),
+    {
+        self.on_blur = Some(Arc::new(handler));
+        self
+    }
+
+    /// Set the maximum number of characters (grapheme clusters) allowed in the input.
+    ///
+    /// Typed or pasted text past the limit is dropped; a paste inserts as much as fits.
     pub fn max_length(mut self, max_length: usize) -> Self {
         self.max_length = Some(max_length);
         self
     }
 
+    /// Formats input with a mask such as `"(###) ###-####"`.
+    ///
+    /// `#` accepts a digit, `A` a letter and `*` any character; other characters
+    /// are literals inserted as the user types (escape a placeholder with `\`).
+    /// `on_change` receives the masked text; read [`TextInputState::unmasked`] for
+    /// just the entered characters.
+    pub fn mask(mut self, pattern: impl Into<SharedString>) -> Self {
+        self.mask = Some(InputMask::new(pattern));
+        self
+    }
+
+    /// Shows a clear ("x") button while the field is non-empty and hovered or
+    /// focused. Escape also clears the field.
+    pub fn clearable(mut self, clearable: bool) -> Self {
+        self.clearable = clearable;
+        self
+    }
+
+    /// Content shown inside the border before the text, e.g. a search icon.
+    ///
+    /// Clicking it focuses the field.
+    pub fn prefix(mut self, prefix: impl IntoElement) -> Self {
+        self.prefix = Some(prefix.into_any_element());
+        self
+    }
+
+    /// Content shown inside the border after the text (and clear button), e.g. a unit.
+    ///
+    /// Clicking it focuses the field.
+    pub fn suffix(mut self, suffix: impl IntoElement) -> Self {
+        self.suffix = Some(suffix.into_any_element());
+        self
+    }
+
     pub fn bg(mut self, color: impl Into<Hsla>) -> Self {
         self.bg = Some(color.into());
         self
@@ -795,6 +1204,12 @@ impl TextInput {
         self
     }
 
+    /// Border color while invalid (default: the theme's error color).
+    pub fn error_border(mut self, color: impl Into<Hsla>) -> Self {
+        self.error_border = Some(color.into());
+        self
+    }
+
     pub fn text_color(mut self, color: impl Into<Hsla>) -> Self {
         self.text_color = Some(color.into());
         self
@@ -807,6 +1222,7 @@ impl TextInput {
 }
 
 impl Default for TextInput {
+    #[track_caller]
     fn default() -> Self {
         Self::new()
     }
@@ -835,17 +1251,34 @@ impl StatefulInteractiveElement for TextInput {}
 impl RenderOnce for TextInput {
     fn render(self, window: &mut gpui::Window, cx: &mut App) -> impl IntoElement {
         // TextInput requires an element ID for keyed state management.
-        // Use `.id()` to provide a stable ID, or a unique ID will be generated automatically.
+        // Use `.id()` to provide a stable ID, or one is derived from the call site.
         let id = self.element_id;
+        if self.generated_id {
+            warn_generated_id(window, cx, &id);
+        }
 
         let disabled = self.disabled;
+        let read_only = disabled || self.read_only;
+        let selection_opacity = self.selection_opacity;
 
         let state = window.use_keyed_state(id.clone(), cx, |_, cx| TextInputState::new(cx));
         let focus_handle = state.read(cx).focus_handle.clone();
         let placeholder = self.placeholder;
+        let undo_limit = self.undo_limit;
+        let max_length = self.max_length;
+        let mask = self.mask;
 
         state.update(cx, |state, _cx| {
             state.placeholder = placeholder;
+            if state.max_length != max_length {
+                state.set_max_length(max_length);
+            }
+            if state.mask != mask {
+                state.set_mask(mask);
+            }
+            if let Some(limit) = undo_limit {
+                state.edit.set_undo_limit(limit);
+            }
         });
 
         let content = self.content;
@@ -858,44 +1291,69 @@ impl RenderOnce for TextInput {
             });
         }
 
-        let last_prop_content = window.use_keyed_state(
-            (id.clone(), format!("{}:last-prop-content", id)),
+        sync_controlled_value(
+            window,
             cx,
-            |_, _cx| None::<SharedString>,
+            &id,
+            content,
+            &state,
+            |state| state.edit.content(),
+            |state, content| state.set_content(content),
+        );
+        register_input(
+            window,
+            cx,
+            &id,
+            &state,
+            |state| state.edit.content(),
+            |state, content| state.set_content(content),
         );
-
-        // Only sync content when it's explicitly provided and different from current state.
-        // Compare by value content, not by reference, to avoid unnecessary updates.
-        if let Some(prop_content) = content.clone() {
-            let current_stored = last_prop_content.read(cx).clone();
-            let needs_sync = match current_stored.as_ref() {
-                Some(stored) => stored != &prop_content,
-                None => true,
-            };
-
-            if needs_sync {
-                last_prop_content.update(cx, |state, _cx| {
-                    *state = Some(prop_content.clone());
-                });
-
-                if state.read(cx).edit.content() != &prop_content {
-                    state.update(cx, |state, _cx| {
-                        state.set_content(prop_content);
-                    });
-                }
-            }
-        } else if last_prop_content.read(cx).is_some() {
-            last_prop_content.update(cx, |state, _cx| {
-                *state = None;
-            });
-        }
 
         let on_change = self.on_change;
+        let on_validate = self.on_validate;
         let last_content = window.use_keyed_state(
             (id.clone(), format!("{}:last-content", id)),
             cx,
             |_, _cx| SharedString::new_static(""),
         );
+        let validation_error =
+            window.use_keyed_state((id.clone(), "ui:text-input:validation"), cx, |_, _| {
+                None::<SharedString>
+            });
+
+        let current = state.read(cx).edit.content().clone();
+        if *last_content.read(cx) != current {
+            last_content.update(cx, |value, _cx| *value = current.clone());
+            if let Some(on_change) = &on_change {
+                on_change(current.clone(), window, cx);
+            }
+            if let Some(validate) = &on_validate {
+                let error = validate(&current).err();
+                validation_error.update(cx, |value, _cx| *value = error);
+            }
+        }
+        let on_focus = self.on_focus;
+        let on_blur = self.on_blur;
+        let was_focused =
+            window.use_keyed_state((id.clone(), "ui:text-input:focused"), cx, |_, _| false);
+        let content_of = {
+            let state = state.clone();
+            move |cx: &App| state.read(cx).edit.content().clone()
+        };
+        notify_focus_change(
+            &was_focused,
+            focus_handle.is_focused(window),
+            &content_of,
+            on_focus.as_ref(),
+            on_blur.as_ref(),
+            window,
+            cx,
+        );
+        if on_validate.is_none() && validation_error.read(cx).is_some() {
+            validation_error.update(cx, |value, _cx| *value = None);
+        }
+        let error_message = validation_error.read(cx).clone().filter(|_| !disabled);
+        let invalid = self.invalid || error_message.is_some();
 
         let theme = cx.theme();
 
@@ -906,14 +1364,25 @@ impl RenderOnce for TextInput {
             self.border,
             self.focus_border,
             self.text_color,
-        );
+        )
+        .with_invalid(theme, disabled, invalid, self.error_border);
+        let clear_button = theme.action.neutral.clone();
 
         let height = self.height.unwrap_or_else(|| px(36.).into());
         let inset = if disabled { px(6.) } else { px(5.) };
+        let clearable = self.clearable && !read_only;
+        let prefix = self.prefix;
+        let suffix = self.suffix;
+        let show_clear = clearable && !current.is_empty();
+        let clear_group = SharedString::from(format!("{}:clear-group", id));
 
         let on_submit = self.on_submit;
-        let mut base = self
-            .base
+        let mut base = self.base;
+        // The theme's padding, unless set through `Styled`.
+        if base.style().padding.left.is_none() {
+            base = base.px(theme.spacing(Scale::Md));
+        }
+        let mut base = base
             .id(id.clone())
             .flex()
             .items_center()
@@ -944,26 +1413,56 @@ impl RenderOnce for TextInput {
                     }
                 }
             })
-            .on_action(action_handler!(state, disabled, Backspace, backspace))
-            .on_action(action_handler!(state, disabled, Delete, delete))
+            .on_action(action_handler!(state, read_only, Backspace, backspace))
+            .on_action(action_handler!(state, read_only, Delete, delete))
+            .on_action(action_handler!(
+                state,
+                read_only,
+                BackspaceWord,
+                backspace_word
+            ))
+            .on_action(action_handler!(state, read_only, DeleteWord, delete_word))
             .on_action(action_handler!(state, disabled, Left, left))
             .on_action(action_handler!(state, disabled, Right, right))
             .on_action(action_handler!(state, disabled, SelectLeft, select_left))
             .on_action(action_handler!(state, disabled, SelectRight, select_right))
+            .on_action(action_handler!(state, disabled, WordLeft, word_left))
+            .on_action(action_handler!(state, disabled, WordRight, word_right))
             .on_action(action_handler!(state, disabled, SelectAll, select_all))
             .on_action(action_handler!(state, disabled, Home, home))
             .on_action(action_handler!(state, disabled, End, end))
             .on_action(action_handler!(
                 state,
-                disabled,
+                read_only,
                 ShowCharacterPalette,
                 show_character_palette
             ))
-            .on_action(action_handler!(state, disabled, Paste, paste))
-            .on_action(action_handler!(state, disabled, Cut, cut))
+            .on_action(action_handler!(state, read_only, Paste, paste))
+            .on_action(action_handler!(state, read_only, Cut, cut))
+            .on_action(action_handler!(state, read_only, Undo, undo))
+            .on_action(action_handler!(state, read_only, Redo, redo))
             .on_action(action_handler!(state, disabled, Copy, copy))
+            .when(clearable, |this| {
+                this.on_action({
+                    let state = state.clone();
+                    move |_: &Clear, window, cx| {
+                        if state.read(cx).edit.content().is_empty() {
+                            cx.propagate();
+                            return;
+                        }
+                        state.update(cx, |state, cx| {
+                            state.set_content(SharedString::new_static(""));
+                            state.reset_cursor_blink(window, cx);
+                            cx.notify();
+                        });
+                    }
+                })
+            })
             .on_mouse_down(MouseButton::Left, {
                 let state = state.clone();
+                let was_focused = was_focused.clone();
+                let content_of = content_of.clone();
+                let on_focus = on_focus.clone();
                 move |event, window, cx| {
                     if disabled {
                         return;
@@ -972,6 +1471,15 @@ impl RenderOnce for TextInput {
                         state.focus_in(window, cx);
                         state.on_mouse_down(event, window, cx);
                     });
+                    notify_focus_change(
+                        &was_focused,
+                        true,
+                        &content_of,
+                        on_focus.as_ref(),
+                        None,
+                        window,
+                        cx,
+                    );
                 }
             })
             .on_mouse_up(MouseButton::Left, {
@@ -1002,11 +1510,16 @@ impl RenderOnce for TextInput {
                 }
             });
 
+        let focused = focus_handle.is_focused(window);
         base =
             base.text_color(input_style.text_color)
+                .when_some(prefix, |this, prefix| {
+                    this.child(div().flex_none().flex().items_center().child(prefix))
+                })
                 .child(
                     div()
-                        .w_full()
+                        .flex_1()
+                        .min_w(px(0.))
                         .h_full()
                         .flex()
                         .items_center()
@@ -1015,31 +1528,209 @@ impl RenderOnce for TextInput {
                             TextLineElement {
                                 input: state.clone(),
                                 disabled,
+                                read_only,
+                                selection_opacity,
                             },
                         )),
                 )
-                .on_mouse_down_out(move |_event, window, _cx| {
+                .when(clearable, |this| {
+                    // The slot keeps its width while hidden so the text doesn't reflow.
+                    this.group(clear_group.clone()).child(
+                        div()
+                            .flex_none()
+                            .flex()
+                            .items_center()
+                            .when(!show_clear, |this| this.invisible())
+                            .when(show_clear && !focused, |this| {
+                                this.invisible()
+                                    .group_hover(clear_group, |style| style.visible())
+                            })
+                            // Don't let the press reach the field and move the cursor.
+                            .on_mouse_down(MouseButton::Left, |_, _, cx| cx.stop_propagation())
+                            .child(
+                                icon_button((id.clone(), "ui:text-input:clear"))
+                                    .icon(icon(IconName::Close))
+                                    .icon_size(px(14.))
+                                    .w(px(24.))
+                                    .h(px(24.))
+                                    .rounded_md()
+                                    .bg(clear_button.bg.alpha(0.0))
+                                    .hover_bg(clear_button.hover_bg)
+                                    .on_click({
+                                        let state = state.clone();
+                                        move |_ev, window, cx| {
+                                            // `on_change("")` fires from the next render.
+                                            state.update(cx, |state, cx| {
+                                                state.set_content(SharedString::new_static(""));
+                                                state.focus_in(window, cx);
+                                                cx.notify();
+                                            });
+                                        }
+                                    }),
+                            ),
+                    )
+                })
+                .when_some(suffix, |this, suffix| {
+                    this.child(div().flex_none().flex().items_center().child(suffix))
+                })
+                .on_mouse_down_out(move |_event, window, cx| {
                     if disabled {
                         return;
                     }
                     if focus_handle.is_focused(window) {
                         window.blur();
+                        notify_focus_change(
+                            &was_focused,
+                            false,
+                            &content_of,
+                            None,
+                            on_blur.as_ref(),
+                            window,
+                            cx,
+                        );
                     }
                 });
 
-        base.map(move |this| {
-            if on_change.is_none() {
-                return this;
-            }
+        match error_message {
+            Some(message) => div()
+                .flex()
+                .flex_col()
+                .gap_1()
+                .w_full()
+                .child(base)
+                .child(inline_error(message).id((id, "ui:text-input:error")))
+                .into_any_element(),
+            None => base.into_any_element(),
+        }
+    }
+}
 
-            let on_change = on_change.expect("checked");
-            let current = state.read(cx).edit.content().clone();
-            let prev = last_content.read(cx).clone();
-            if current != prev {
-                last_content.update(cx, |value, _cx| *value = current.clone());
-                on_change(current, window, cx);
-            }
-            this
-        })
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use gpui::{Render, TestAppContext, WindowAppearance};
+
+    use super::*;
+    use crate::theme::GlobalTheme;
+
+    #[test]
+    fn rtl_right_aligns_text_that_fits() {
+        // A 60px line in a 100px field, cursor at its end.
+        let scroll = line_scroll_x(px(0.), px(60.), px(60.), px(100.), true);
+        assert_eq!(scroll, px(-40.));
+        // The line's start and the cursor land at the field's right side, and a
+        // click there maps back to the end of the line.
+        assert_eq!(px(0.) - scroll, px(40.));
+        assert_eq!(px(60.) - scroll, px(100.));
+        assert_eq!(px(100.) + scroll, px(60.));
+        assert_eq!(
+            line_scroll_x(px(0.), px(60.), px(60.), px(100.), false),
+            px(0.)
+        );
+
+        // The stored negative scroll is clamped on the next frame, not compounded.
+        assert_eq!(
+            line_scroll_x(scroll, px(0.), px(60.), px(100.), true),
+            scroll
+        );
+    }
+
+    #[test]
+    fn rtl_scrolls_long_text_like_ltr() {
+        // A 300px line in a 100px field.
+        for rtl in [false, true] {
+            assert_eq!(
+                line_scroll_x(px(0.), px(250.), px(300.), px(100.), rtl),
+                px(150.)
+            );
+            assert_eq!(
+                line_scroll_x(px(150.), px(20.), px(300.), px(100.), rtl),
+                px(20.)
+            );
+            assert_eq!(
+                line_scroll_x(px(-40.), px(50.), px(300.), px(100.), rtl),
+                px(0.)
+            );
+            assert_eq!(
+                line_scroll_x(px(900.), px(300.), px(300.), px(100.), rtl),
+                px(200.)
+            );
+        }
+    }
+
+    #[test]
+    fn un_ided_inputs_get_distinct_ids_per_call_site() {
+        let first = TextInput::new();
+        let second = TextInput::new();
+        assert!(first.generated_id && second.generated_id);
+        assert_ne!(first.element_id, second.element_id);
+
+        let same_site = || TextInput::new().element_id;
+        assert_eq!(same_site(), same_site());
+    }
+
+    #[test]
+    fn explicit_id_replaces_generated_id() {
+        let input = TextInput::new().id("name");
+        assert!(!input.generated_id);
+        assert_eq!(input.element_id, ElementId::from("name"));
+    }
+
+    /// Two inputs without `.id()`, recording the ids they were given.
+    struct TwoInputs {
+        ids: Rc<RefCell<Vec<ElementId>>>,
+    }
+
+    impl Render for TwoInputs {
+        fn render(
+            &mut self,
+            _window: &mut gpui::Window,
+            _cx: &mut Context<Self>,
+        ) -> impl IntoElement {
+            let first = TextInput::new();
+            let second = TextInput::new();
+            *self.ids.borrow_mut() = vec![first.element_id.clone(), second.element_id.clone()];
+            div().child(first).child(second)
+        }
+    }
+
+    #[gpui::test]
+    fn un_ided_inputs_keep_independent_state(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            cx.set_global(GlobalTheme::new(WindowAppearance::Light));
+            crate::component::init(cx);
+        });
+        let ids = Rc::new(RefCell::new(Vec::new()));
+        let (_, cx) = cx.add_window_view({
+            let ids = ids.clone();
+            |_, _| TwoInputs { ids }
+        });
+        cx.run_until_parked();
+
+        let (first, second) = {
+            let ids = ids.borrow();
+            (ids[0].clone(), ids[1].clone())
+        };
+        assert_ne!(first, second);
+        cx.update(|window, cx| {
+            TextInput::state_handle(first.clone(), window, cx).update(cx, |state, cx| {
+                state.set_content("Ada");
+                cx.notify();
+            });
+        });
+        cx.run_until_parked();
+
+        let content = |cx: &mut gpui::VisualTestContext, id: &ElementId| {
+            cx.update(|window, cx| {
+                TextInput::state_handle(id.clone(), window, cx)
+                    .read(cx)
+                    .content()
+                    .clone()
+            })
+        };
+        assert_eq!(content(cx, &first), "Ada");
+        assert_eq!(content(cx, &second), "");
     }
 }
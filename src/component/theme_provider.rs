@@ -0,0 +1,130 @@
+//! Scoped theme override.
+//!
+//! A theme provider makes `cx.theme()` resolve to a different theme for its
+//! subtree, e.g. a darker sidebar, without touching the global theme. The
+//! override is active while the subtree lays out, prepaints and paints; event
+//! handlers run later and see the global theme.
+
+use std::sync::Arc;
+
+use gpui::{
+    AnyElement, App, Bounds, Div, Element, ElementId, GlobalElementId, InspectorElementId,
+    IntoElement, LayoutId, ParentElement, Pixels, Styled, Window, div,
+};
+
+use crate::theme::{Theme, ThemeStack};
+
+/// Renders its children with `theme` as the active theme.
+///
+/// # Example
+/// ```rust,ignore
+/// let mut sidebar = (**cx.theme()).clone();
+/// sidebar.surface.base = sidebar.surface.sunken;
+///
+/// theme_provider(sidebar).size_full().child(sidebar_content)
+/// ```
+pub fn theme_provider(theme: impl Into<Arc<Theme>>) -> ThemeProvider {
+    ThemeProvider {
+        theme: theme.into(),
+        base: div(),
+    }
+}
+
+pub struct ThemeProvider {
+    theme: Arc<Theme>,
+    base: Div,
+}
+
+impl ParentElement for ThemeProvider {
+    fn extend(&mut self, elements: impl IntoIterator<Item = AnyElement>) {
+        self.base.extend(elements);
+    }
+}
+
+impl Styled for ThemeProvider {
+    fn style(&mut self) -> &mut gpui::StyleRefinement {
+        self.base.style()
+    }
+}
+
+impl IntoElement for ThemeProvider {
+    type Element = ThemeProviderElement;
+
+    fn into_element(self) -> Self::Element {
+        ThemeProviderElement {
+            theme: self.theme,
+            inner: self.base.into_any_element(),
+        }
+    }
+}
+
+pub struct ThemeProviderElement {
+    theme: Arc<Theme>,
+    inner: AnyElement,
+}
+
+impl ThemeProviderElement {
+    fn scoped<R>(&mut self, cx: &mut App, f: impl FnOnce(&mut AnyElement, &mut App) -> R) -> R {
+        cx.default_global::<ThemeStack>().push(self.theme.clone());
+        let result = f(&mut self.inner, cx);
+        cx.default_global::<ThemeStack>().pop();
+        result
+    }
+}
+
+impl IntoElement for ThemeProviderElement {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+impl Element for ThemeProviderElement {
+    type RequestLayoutState = ();
+    type PrepaintState = ();
+
+    fn id(&self) -> Option<ElementId> {
+        None
+    }
+
+    fn source_location(&self) -> Option<&'static core::panic::Location<'static>> {
+        None
+    }
+
+    fn request_layout(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _inspector_id: Option<&InspectorElementId>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        let layout_id = self.scoped(cx, |inner, cx| inner.request_layout(window, cx));
+        (layout_id, ())
+    }
+
+    fn prepaint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _inspector_id: Option<&InspectorElementId>,
+        _bounds: Bounds<Pixels>,
+        _request_layout: &mut Self::RequestLayoutState,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Self::PrepaintState {
+        self.scoped(cx, |inner, cx| inner.prepaint(window, cx));
+    }
+
+    fn paint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _inspector_id: Option<&InspectorElementId>,
+        _bounds: Bounds<Pixels>,
+        _request_layout: &mut Self::RequestLayoutState,
+        _prepaint: &mut Self::PrepaintState,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        self.scoped(cx, |inner, cx| inner.paint(window, cx));
+    }
+}
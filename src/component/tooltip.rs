@@ -1,13 +1,38 @@
+use std::rc::Rc;
+use std::time::Duration;
+
 use gpui::{
-    AnyView, AppContext, ElementId, Hsla, InteractiveElement, IntoElement, ParentElement, Render,
-    RenderOnce, Styled, div,
+    AnyElement, AnyTooltip, AnyView, App, AppContext, AvailableSpace, Bounds, Element, ElementId,
+    Entity, Global, GlobalElementId, Hsla, InspectorElementId, InteractiveElement, IntoElement,
+    LayoutId, ParentElement, Pixels, Point, Render, RenderOnce, Size, StatefulInteractiveElement,
+    Styled, Svg, Transformation, Window, div, percentage, point, prelude::FluentBuilder, px, svg,
 };
 
+use crate::component::{generate_element_id, warn_generated_id};
 use crate::theme::ActiveTheme;
 
+/// How long the pointer rests on a trigger before its tooltip shows.
+const DEFAULT_SHOW_DELAY: Duration = Duration::from_millis(500);
+
+/// How long a tooltip stays after the pointer leaves its trigger.
+const DEFAULT_HIDE_DELAY: Duration = Duration::from_millis(100);
+
+/// Space between the trigger and the tooltip, which the caret sits in.
+const GAP: Pixels = px(7.);
+
+/// Size of the caret's square box; the visible triangle fills half of it.
+const CARET_BOX: Pixels = px(10.);
+
+/// Closest the caret gets to the tooltip's corners.
+const CARET_INSET: Pixels = px(10.);
+
+const CARET_PATH: &str = "icons/ui-tooltip-caret.svg";
+
 /// Defines the placement position of a tooltip relative to its trigger element.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum TooltipPlacement {
     /// Automatically determines the best placement based on available space.
+    #[default]
     Auto,
     /// Positions the tooltip above the trigger element.
     Top,
@@ -19,19 +44,40 @@ pub enum TooltipPlacement {
     Left,
 }
 
+impl TooltipPlacement {
+    fn opposite(self) -> Self {
+        match self {
+            TooltipPlacement::Auto => TooltipPlacement::Auto,
+            TooltipPlacement::Top => TooltipPlacement::Bottom,
+            TooltipPlacement::Right => TooltipPlacement::Left,
+            TooltipPlacement::Bottom => TooltipPlacement::Top,
+            TooltipPlacement::Left => TooltipPlacement::Right,
+        }
+    }
+
+    fn is_vertical(self) -> bool {
+        matches!(self, TooltipPlacement::Top | TooltipPlacement::Bottom)
+    }
+}
+
 /// Creates a new tooltip with text content.
 ///
 /// Use `.placement()` to control positioning and `.bg()`/`.text_color()` for customization.
 /// The tooltip is typically used with `.with_tooltip()` on interactive elements.
 ///
+/// Each trigger keeps its own show/hide state, keyed on the tooltip's `.id()`,
+/// else on the trigger's own id (e.g. a `div().id(...)`), else on the call
+/// site. Give tooltips created in a loop an `.id()` so they stay independent.
+///
 /// # Example
 /// ```rust,ignore
-/// use yororen_ui::component::{button, tooltip, TooltipPlacement};
+/// use yororen_ui::component::{button, tooltip, TooltipExt, TooltipPlacement};
 ///
 /// let btn = button("my-button")
 ///     .child("Hover me")
 ///     .with_tooltip(tooltip("Helpful information").placement(TooltipPlacement::Bottom));
 /// ```
+#[track_caller]
 pub fn tooltip(content: impl Into<String>) -> Tooltip {
     Tooltip::text(content)
 }
@@ -41,11 +87,19 @@ pub fn tooltip(content: impl Into<String>) -> Tooltip {
 /// Tooltips are typically used with `.with_tooltip()` on interactive elements like buttons or icons.
 /// The tooltip will automatically position itself based on available space, or you can specify
 /// a fixed placement using `.placement()`.
+///
+/// `.build()` instead hands the tooltip to gpui's `.tooltip(...)`, which shows it
+/// next to the pointer after gpui's own delay, without a caret; placement and
+/// `.delay()` only apply to `.with_tooltip()`.
 #[derive(IntoElement)]
 pub struct Tooltip {
     element_id: ElementId,
+    generated_id: bool,
     content: String,
     placement: TooltipPlacement,
+    show_delay: Duration,
+    hide_delay: Duration,
+    show_on_disabled: bool,
     bg: Option<Hsla>,
     text_color: Option<Hsla>,
 }
@@ -55,14 +109,21 @@ struct TooltipView {
     content: String,
     bg: Option<Hsla>,
     text_color: Option<Hsla>,
+    /// The side the tooltip ended up on and where along it the caret points.
+    caret: Option<(TooltipPlacement, Pixels)>,
 }
 
 impl Tooltip {
+    #[track_caller]
     pub fn text(content: impl Into<String>) -> Self {
         Self {
-            element_id: "ui:tooltip".into(),
+            element_id: generate_element_id("ui:tooltip"),
+            generated_id: true,
             content: content.into(),
             placement: TooltipPlacement::Auto,
+            show_delay: DEFAULT_SHOW_DELAY,
+            hide_delay: DEFAULT_HIDE_DELAY,
+            show_on_disabled: false,
             bg: None,
             text_color: None,
         }
@@ -70,6 +131,7 @@ impl Tooltip {
 
     pub fn id(mut self, id: impl Into<ElementId>) -> Self {
         self.element_id = id.into();
+        self.generated_id = false;
         self
     }
 
@@ -78,11 +140,36 @@ impl Tooltip {
         self.id(key)
     }
 
+    /// The side of the trigger to show the tooltip on. A tooltip that would
+    /// overflow the window flips to the opposite side; `Auto` picks the side
+    /// with the most room.
     pub fn placement(mut self, placement: TooltipPlacement) -> Self {
         self.placement = placement;
         self
     }
 
+    /// How long, in milliseconds, the pointer rests on the trigger before the
+    /// tooltip shows (default: 500), and how long the tooltip stays after the
+    /// pointer leaves (default: 100). Passing over the trigger faster than
+    /// `show_ms` shows nothing.
+    pub fn delay(mut self, show_ms: u64, hide_ms: u64) -> Self {
+        self.show_delay = Duration::from_millis(show_ms);
+        self.hide_delay = Duration::from_millis(hide_ms);
+        self
+    }
+
+    /// Covers the trigger with a transparent layer that detects hover in its
+    /// place (default: false), so the tooltip can explain why a disabled
+    /// trigger is disabled.
+    ///
+    /// The layer swallows clicks and shows a not-allowed cursor, so set this
+    /// only while the trigger is disabled, e.g. `.show_on_disabled(!can_save)`.
+    /// Only applies to `.with_tooltip()`.
+    pub fn show_on_disabled(mut self, show: bool) -> Self {
+        self.show_on_disabled = show;
+        self
+    }
+
     pub fn bg(mut self, color: impl Into<Hsla>) -> Self {
         self.bg = Some(color.into());
         self
@@ -96,7 +183,6 @@ impl Tooltip {
     pub fn build(self) -> impl Fn(&mut gpui::Window, &mut gpui::App) -> AnyView {
         let element_id = self.element_id;
         let content = self.content;
-        let _placement = self.placement;
         let bg = self.bg;
         let text_color = self.text_color;
         move |_, cx| {
@@ -105,6 +191,7 @@ impl Tooltip {
                 content: content.clone(),
                 bg,
                 text_color,
+                caret: None,
             })
             .into()
         }
@@ -118,20 +205,503 @@ impl Render for TooltipView {
         cx: &mut gpui::Context<Self>,
     ) -> impl IntoElement {
         let theme = cx.theme();
+        let bg = self.bg.unwrap_or_else(|| theme.action.neutral.bg);
         div()
             .id(self.element_id.clone())
+            .relative()
             .px_3()
             .py_2()
             .rounded_sm()
             .text_sm()
-            .bg(self.bg.unwrap_or_else(|| theme.action.neutral.bg))
+            .bg(bg)
             .text_color(self.text_color.unwrap_or_else(|| theme.action.neutral.fg))
             .child(self.content.clone())
+            .when_some(self.caret, |this, (side, offset)| {
+                this.child(caret(side, offset, bg))
+            })
     }
 }
 
+/// The triangle outside the `side` edge of an overlay placed on that side of
+/// its trigger, pointing back at the trigger from `offset` along that edge.
+pub(crate) fn caret(side: TooltipPlacement, offset: Pixels, color: Hsla) -> Svg {
+    // The caret asset points down from the top half of its box.
+    let turns = match side {
+        TooltipPlacement::Top | TooltipPlacement::Auto => 0.,
+        TooltipPlacement::Right => 0.25,
+        TooltipPlacement::Bottom => 0.5,
+        TooltipPlacement::Left => 0.75,
+    };
+    let along = offset - CARET_BOX / 2.;
+    svg()
+        .path(CARET_PATH)
+        .absolute()
+        .size(CARET_BOX)
+        .text_color(color)
+        .map(|this| match side {
+            TooltipPlacement::Top | TooltipPlacement::Auto => this.top_full().left(along),
+            TooltipPlacement::Bottom => this.bottom_full().left(along),
+            TooltipPlacement::Left => this.left_full().top(along),
+            TooltipPlacement::Right => this.right_full().top(along),
+        })
+        .with_transformation(Transformation::rotate(percentage(turns)))
+}
+
 impl RenderOnce for Tooltip {
     fn render(self, _window: &mut gpui::Window, _cx: &mut gpui::App) -> impl IntoElement {
         div().id(self.element_id).child(self.content)
     }
 }
+
+/// Attaches a [`Tooltip`] to any element.
+pub trait TooltipExt: IntoElement + Sized {
+    /// Shows `tooltip` while the pointer rests on this element, on the side
+    /// chosen with [`Tooltip::placement`] and after [`Tooltip::delay`].
+    fn with_tooltip(self, tooltip: Tooltip) -> TooltipTrigger {
+        let trigger = self.into_element();
+        TooltipTrigger {
+            trigger_id: trigger.id(),
+            trigger: trigger.into_any_element(),
+            tooltip,
+        }
+    }
+}
+
+impl<E: IntoElement> TooltipExt for E {}
+
+/// An element with a tooltip attached by [`TooltipExt::with_tooltip`].
+#[derive(IntoElement)]
+pub struct TooltipTrigger {
+    trigger: AnyElement,
+    /// The trigger element's own id, when it has one.
+    trigger_id: Option<ElementId>,
+    tooltip: Tooltip,
+}
+
+/// The id a trigger's tooltip state is keyed on: the tooltip's explicit id,
+/// else the trigger's own id, else the call-site id. The flag is `true` when
+/// the call-site id is used, which trigger instances from a loop share.
+fn state_id(tooltip: &Tooltip, trigger_id: Option<ElementId>) -> (ElementId, bool) {
+    match trigger_id {
+        Some(trigger_id) if tooltip.generated_id => ((trigger_id, "ui:tooltip").into(), false),
+        _ => (tooltip.element_id.clone(), tooltip.generated_id),
+    }
+}
+
+/// Whether a trigger's tooltip is shown, and the pending change to it.
+struct TooltipState {
+    visible: bool,
+    /// Bumped on every hover change, so a timer from an older one is ignored.
+    epoch: usize,
+    view: Option<Entity<TooltipView>>,
+}
+
+impl RenderOnce for TooltipTrigger {
+    fn render(self, window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let tooltip = self.tooltip;
+        let (id, generated) = state_id(&tooltip, self.trigger_id);
+        if generated {
+            warn_generated_id(window, cx, &id);
+        }
+        let state =
+            window.use_keyed_state((id.clone(), "ui:tooltip:state"), cx, |_, _| TooltipState {
+                visible: false,
+                epoch: 0,
+                view: None,
+            });
+
+        let view = if state.read(cx).visible {
+            let view = state.read(cx).view.clone().unwrap_or_else(|| {
+                cx.new(|_| TooltipView {
+                    element_id: (id.clone(), "ui:tooltip:view").into(),
+                    content: String::new(),
+                    bg: None,
+                    text_color: None,
+                    caret: None,
+                })
+            });
+            view.update(cx, |view, _| {
+                view.content = tooltip.content.clone();
+                view.bg = tooltip.bg;
+                view.text_color = tooltip.text_color;
+            });
+            state.update(cx, |state, _| state.view = Some(view.clone()));
+            Some(view)
+        } else {
+            None
+        };
+
+        let show_delay = tooltip.show_delay;
+        let hide_delay = tooltip.hide_delay;
+        let on_hover = {
+            let state = state.clone();
+            move |hovered: &bool, window: &mut Window, cx: &mut App| {
+                let hovered = *hovered;
+                let (epoch, settled) = state.update(cx, |state, _| {
+                    state.epoch = state.epoch.wrapping_add(1);
+                    (state.epoch, state.visible == hovered)
+                });
+                // Already where it is heading: a pending change was just cancelled.
+                if settled {
+                    return;
+                }
+                let delay = if hovered { show_delay } else { hide_delay };
+                let state = state.clone();
+                window
+                    .spawn(cx, async move |cx| {
+                        cx.background_executor().timer(delay).await;
+                        cx.update(|_window, cx| {
+                            state.update(cx, |state, cx| {
+                                if state.epoch == epoch {
+                                    state.visible = hovered;
+                                    cx.notify();
+                                }
+                            });
+                        })
+                        .ok();
+                    })
+                    .detach();
+            }
+        };
+
+        let show_on_disabled = tooltip.show_on_disabled;
+        div()
+            .id(id.clone())
+            .when(!show_on_disabled, |this| this.on_hover(on_hover.clone()))
+            .child(TooltipAnchorElement {
+                trigger: self.trigger,
+                tooltip: view.map(|view| (view, tooltip.placement, state)),
+            })
+            .when(show_on_disabled, |this| {
+                // Painted above the trigger, which keeps it from ever seeing the pointer.
+                this.relative().child(
+                    div()
+                        .id((id, "ui:tooltip:disabled-overlay"))
+                        .absolute()
+                        .inset_0()
+                        .occlude()
+                        .cursor_not_allowed()
+                        .on_hover(on_hover),
+                )
+            })
+    }
+}
+
+/// Lays out a trigger and, while its tooltip is visible, places the tooltip
+/// against the trigger's bounds.
+struct TooltipAnchorElement {
+    trigger: AnyElement,
+    tooltip: Option<(Entity<TooltipView>, TooltipPlacement, Entity<TooltipState>)>,
+}
+
+impl IntoElement for TooltipAnchorElement {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+impl Element for TooltipAnchorElement {
+    type RequestLayoutState = ();
+    type PrepaintState = ();
+
+    fn id(&self) -> Option<ElementId> {
+        None
+    }
+
+    fn source_location(&self) -> Option<&'static core::panic::Location<'static>> {
+        None
+    }
+
+    fn request_layout(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _inspector_id: Option<&InspectorElementId>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        (self.trigger.request_layout(window, cx), ())
+    }
+
+    fn prepaint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _inspector_id: Option<&InspectorElementId>,
+        bounds: Bounds<Pixels>,
+        _request_layout: &mut Self::RequestLayoutState,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Self::PrepaintState {
+        self.trigger.prepaint(window, cx);
+
+        let Some((view, placement, state)) = self.tooltip.take() else {
+            return;
+        };
+        if cx
+            .try_global::<SuppressedTooltips>()
+            .is_some_and(|suppressed| suppressed.0 > 0)
+        {
+            return;
+        }
+        let size = AnyView::from(view.clone())
+            .into_any_element()
+            .layout_as_root(AvailableSpace::min_size(), window, cx);
+        let position = place_tooltip(placement, bounds, size, window.viewport_size());
+        view.update(cx, |view, _| {
+            view.caret = Some((position.side, position.caret));
+        });
+        // gpui shows tooltips 1px below and right of the position it is given.
+        window.set_tooltip(AnyTooltip {
+            view: view.into(),
+            mouse_position: position.origin - point(px(1.), px(1.)),
+            check_visible_and_update: Rc::new(move |_, _, cx| state.read(cx).visible),
+        });
+    }
+
+    fn paint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _inspector_id: Option<&InspectorElementId>,
+        _bounds: Bounds<Pixels>,
+        _request_layout: &mut Self::RequestLayoutState,
+        _prepaint: &mut Self::PrepaintState,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        self.trigger.paint(window, cx);
+    }
+}
+
+/// How many active [`TooltipSuppressor`]s enclose the element being prepainted.
+#[derive(Default)]
+struct SuppressedTooltips(usize);
+
+impl Global for SuppressedTooltips {}
+
+/// Keeps the `.with_tooltip()` tooltips inside `inner` hidden while `active`,
+/// e.g. while a hover card on the same trigger is open.
+///
+/// gpui shows `.tooltip(...)` tooltips (see [`Tooltip::build`]) on its own, so
+/// those are not suppressed.
+pub(crate) struct TooltipSuppressor {
+    pub(crate) active: bool,
+    pub(crate) inner: AnyElement,
+}
+
+impl IntoElement for TooltipSuppressor {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+impl Element for TooltipSuppressor {
+    type RequestLayoutState = ();
+    type PrepaintState = ();
+
+    fn id(&self) -> Option<ElementId> {
+        None
+    }
+
+    fn source_location(&self) -> Option<&'static core::panic::Location<'static>> {
+        None
+    }
+
+    fn request_layout(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _inspector_id: Option<&InspectorElementId>,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        (self.inner.request_layout(window, cx), ())
+    }
+
+    fn prepaint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _inspector_id: Option<&InspectorElementId>,
+        _bounds: Bounds<Pixels>,
+        _request_layout: &mut Self::RequestLayoutState,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Self::PrepaintState {
+        if !self.active {
+            self.inner.prepaint(window, cx);
+            return;
+        }
+        cx.default_global::<SuppressedTooltips>().0 += 1;
+        self.inner.prepaint(window, cx);
+        cx.default_global::<SuppressedTooltips>().0 -= 1;
+    }
+
+    fn paint(
+        &mut self,
+        _id: Option<&GlobalElementId>,
+        _inspector_id: Option<&InspectorElementId>,
+        _bounds: Bounds<Pixels>,
+        _request_layout: &mut Self::RequestLayoutState,
+        _prepaint: &mut Self::PrepaintState,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        self.inner.paint(window, cx);
+    }
+}
+
+/// Where a tooltip goes next to its trigger.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct TooltipPosition {
+    /// The side of the trigger the tooltip is on; never `Auto`.
+    side: TooltipPlacement,
+    /// The tooltip's top-left corner in window coordinates.
+    origin: Point<Pixels>,
+    /// How far along the tooltip's edge the caret points at the trigger's center.
+    caret: Pixels,
+}
+
+/// Places a tooltip of `size` on the `placement` side of `trigger`, flipping to
+/// the opposite side when it doesn't fit there, and keeps it inside the viewport.
+fn place_tooltip(
+    placement: TooltipPlacement,
+    trigger: Bounds<Pixels>,
+    size: Size<Pixels>,
+    viewport: Size<Pixels>,
+) -> TooltipPosition {
+    let room = |side: TooltipPlacement| match side {
+        TooltipPlacement::Top => trigger.top(),
+        TooltipPlacement::Bottom => viewport.height - trigger.bottom(),
+        TooltipPlacement::Left => trigger.left(),
+        TooltipPlacement::Right | TooltipPlacement::Auto => viewport.width - trigger.right(),
+    };
+    let fits = |side: TooltipPlacement| {
+        let needed = if side.is_vertical() {
+            size.height
+        } else {
+            size.width
+        };
+        room(side) >= needed + GAP
+    };
+
+    let side = match placement {
+        TooltipPlacement::Auto => [
+            TooltipPlacement::Top,
+            TooltipPlacement::Bottom,
+            TooltipPlacement::Right,
+            TooltipPlacement::Left,
+        ]
+        .into_iter()
+        .fold(TooltipPlacement::Top, |best, side| {
+            if room(side) > room(best) { side } else { best }
+        }),
+        side if !fits(side) && fits(side.opposite()) => side.opposite(),
+        side => side,
+    };
+
+    let center = trigger.center();
+    let origin = match side {
+        TooltipPlacement::Top => point(
+            center.x - size.width / 2.,
+            trigger.top() - GAP - size.height,
+        ),
+        TooltipPlacement::Bottom => point(center.x - size.width / 2., trigger.bottom() + GAP),
+        TooltipPlacement::Left => point(
+            trigger.left() - GAP - size.width,
+            center.y - size.height / 2.,
+        ),
+        TooltipPlacement::Right | TooltipPlacement::Auto => {
+            point(trigger.right() + GAP, center.y - size.height / 2.)
+        }
+    };
+    let origin = point(
+        origin.x.min(viewport.width - size.width).max(Pixels::ZERO),
+        origin
+            .y
+            .min(viewport.height - size.height)
+            .max(Pixels::ZERO),
+    );
+    let caret = if side.is_vertical() {
+        (center.x - origin.x)
+            .min(size.width - CARET_INSET)
+            .max(CARET_INSET)
+    } else {
+        (center.y - origin.y)
+            .min(size.height - CARET_INSET)
+            .max(CARET_INSET)
+    };
+
+    TooltipPosition {
+        side,
+        origin,
+        caret,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::size;
+
+    fn trigger_at(x: f32, y: f32) -> Bounds<Pixels> {
+        Bounds::new(point(px(x), px(y)), size(px(40.), px(20.)))
+    }
+
+    #[test]
+    fn place_tooltip_flips_when_it_would_overflow() {
+        let viewport = size(px(800.), px(600.));
+        let tip = size(px(100.), px(30.));
+
+        let above = place_tooltip(TooltipPlacement::Top, trigger_at(300., 200.), tip, viewport);
+        assert_eq!(above.side, TooltipPlacement::Top);
+        assert_eq!(above.origin, point(px(270.), px(163.)));
+        assert_eq!(above.caret, px(50.));
+
+        // Too close to the top edge: shown below instead.
+        let flipped = place_tooltip(TooltipPlacement::Top, trigger_at(300., 10.), tip, viewport);
+        assert_eq!(flipped.side, TooltipPlacement::Bottom);
+        assert_eq!(flipped.origin.y, px(37.));
+
+        // Kept inside the window, with the caret still over the trigger.
+        let edge = place_tooltip(
+            TooltipPlacement::Bottom,
+            trigger_at(0., 200.),
+            tip,
+            viewport,
+        );
+        assert_eq!(edge.origin.x, px(0.));
+        assert_eq!(edge.caret, px(20.));
+    }
+
+    #[test]
+    fn auto_placement_picks_the_side_with_most_room() {
+        let viewport = size(px(800.), px(600.));
+        let tip = size(px(100.), px(30.));
+        let side =
+            |x, y| place_tooltip(TooltipPlacement::Auto, trigger_at(x, y), tip, viewport).side;
+
+        assert_eq!(side(380., 500.), TooltipPlacement::Top);
+        assert_eq!(side(380., 20.), TooltipPlacement::Bottom);
+        assert_eq!(side(10., 290.), TooltipPlacement::Right);
+        assert_eq!(side(750., 290.), TooltipPlacement::Left);
+    }
+
+    #[test]
+    fn tooltip_state_is_keyed_per_trigger() {
+        let (id, generated) = state_id(&tooltip("Delete"), Some("row-1".into()));
+        assert_eq!(
+            id,
+            ElementId::from((ElementId::from("row-1"), "ui:tooltip"))
+        );
+        assert!(!generated);
+
+        let explicit = tooltip("Delete").id("delete-tip");
+        let (id, generated) = state_id(&explicit, Some("row-1".into()));
+        assert_eq!(id, ElementId::from("delete-tip"));
+        assert!(!generated);
+
+        let (_, generated) = state_id(&tooltip("Delete"), None);
+        assert!(generated);
+    }
+}
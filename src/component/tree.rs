@@ -3,7 +3,8 @@
 //! # Design Rationale
 //!
 //! The Tree component uses a different construction pattern from other components:
-//! `tree(state, nodes)` requires both `TreeState` and `&[TreeNode]` as constructor parameters.
+//! `tree(state, nodes)` requires both a `TreeState` and the nodes (a slice, `Vec`, or a
+//! shared `Rc<[TreeNode]>`) as constructor parameters.
 //!
 //! This design choice was made because:
 //! - **State and data are fundamental**: Unlike styling properties that can be added later,
@@ -17,20 +18,63 @@
 //! If you need to create a tree dynamically, consider passing an empty slice initially and
 //! populating it later through the state management.
 
+use std::cell::OnceCell;
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::ops::Range;
+use std::rc::Rc;
 use std::sync::Arc;
 
 use gpui::{
-    ClickEvent, Div, ElementId, IntoElement, ListAlignment, ListSizingBehavior, ListState,
-    ParentElement, Pixels, RenderOnce, StatefulInteractiveElement, Styled, Window, div, list, px,
+    AnyElement, App, AppContext, Bounds, ClickEvent, Context, Div, DragMoveEvent, ElementId,
+    Entity, EntityId, FocusHandle, FontWeight, HighlightStyle, Hsla, InteractiveElement,
+    IntoElement, KeyBinding, KeyDownEvent, KeyboardButton, KeyboardClickEvent, ListAlignment,
+    ListSizingBehavior, ListState, ParentElement, Pixels, Render, RenderOnce, SharedString,
+    StatefulInteractiveElement, Styled, StyledText, Window, actions, div, list,
+    prelude::FluentBuilder, px,
 };
 
+use crate::component::BoundsTrackerElement;
 use crate::component::ElementMouseDownCallback;
 use crate::component::{ClickCallback, ElementCallback, ElementClickCallback};
+use crate::i18n::{I18nContext, defaults::DefaultPlaceholders};
+use crate::theme::{ActiveTheme, Scale};
+use crate::widget::{HeightIndex, ScrollAlign, VirtualListController};
 
 use super::tree_data::{
-    ArcTreeNode, FlatTreeNode, SelectionMode, TreeCheckedState, TreeNode, TreeNodeData, TreeState,
-    flatten_tree,
+    ArcTreeNode, DropPosition, FlatTreeNode, IndentGuide, SelectionMode, TreeCheckedState,
+    TreeNavigation, TreeNavigationTarget, TreeNode, TreeNodeData, TreeState, flatten_filtered,
+    flatten_tree, indent_guides, is_same_or_descendant, match_range, navigate, wants_children,
 };
+use super::tree_drag::{DropTarget, TreeDragPreview, drop_position};
+use super::tree_item::TreeItem;
+
+actions!(
+    ui_tree,
+    [
+        FocusPrevious,
+        FocusNext,
+        FocusFirst,
+        FocusLast,
+        ExpandFocused,
+        CollapseFocused,
+        SelectFocused,
+    ]
+);
+
+pub(crate) fn init(cx: &mut App) {
+    cx.bind_keys([
+        KeyBinding::new("up", FocusPrevious, Some("UITree")),
+        KeyBinding::new("down", FocusNext, Some("UITree")),
+        KeyBinding::new("home", FocusFirst, Some("UITree")),
+        KeyBinding::new("end", FocusLast, Some("UITree")),
+        KeyBinding::new("right", ExpandFocused, Some("UITree")),
+        KeyBinding::new("left", CollapseFocused, Some("UITree")),
+        KeyBinding::new("enter", SelectFocused, Some("UITree")),
+        KeyBinding::new("space", SelectFocused, Some("UITree")),
+    ]);
+}
 
 /// Creates a new tree component.
 ///
@@ -43,15 +87,34 @@ use super::tree_data::{
 ///         .children(vec![TreeNode::new("child")])
 /// ];
 ///
-/// tree(state, &nodes)
+/// tree(state, nodes)
 ///     .selection_mode(SelectionMode::Single)
 /// ```
-pub fn tree(state: TreeState, nodes: &[TreeNode]) -> Tree {
+///
+/// `nodes` can be a slice, a `Vec`, or an `Rc<[TreeNode]>` kept between renders,
+/// which is shared rather than copied.
+pub fn tree(state: TreeState, nodes: impl Into<Rc<[TreeNode]>>) -> Tree {
     Tree::new(state, nodes)
 }
 
 /// Callback type for tree check handler.
-type TreeCheckCallback = Arc<dyn Fn(&ElementId, TreeCheckedState)>;
+type TreeCheckCallback = Arc<dyn Fn(&ElementId, TreeCheckedState, &[ElementId])>;
+
+/// Predicate type for custom tree filtering.
+type TreeFilterFn = Arc<dyn Fn(&TreeNode) -> bool>;
+
+/// Callback type for tree lazy-expand handler.
+type TreeExpandCallback = Arc<dyn Fn(&ElementId, &mut Window, &mut App)>;
+
+/// Height of a visible row, by index, for virtualized trees with varying row heights.
+type TreeRowHeightFn = Arc<dyn Fn(usize) -> Pixels>;
+
+/// Height of the loading row shown under a node waiting for its children; the
+/// minimum height of a [`TreeItem`].
+const LOADING_ROW_HEIGHT: Pixels = px(32.);
+
+/// Callback type for tree move handler.
+type TreeMoveCallback = Arc<dyn Fn(&ElementId, &ElementId, DropPosition)>;
 
 /// The main tree view component.
 #[derive(IntoElement)]
@@ -59,53 +122,79 @@ pub struct Tree {
     element_id: ElementId,
     base: Div,
     state: TreeState,
-    nodes: Vec<TreeNode>,
-    flattened: Vec<FlatTreeNode>,
+    nodes: Rc<[TreeNode]>,
+    revision: Option<u64>,
+    flattened: OnceCell<Vec<FlatTreeNode>>,
     selection_mode: SelectionMode,
     show_checkbox: bool,
     draggable: bool,
-    indent: Pixels,
+    lazy: bool,
+    reload_on_expand: bool,
+    indent: Option<Pixels>,
     row_height: Pixels,
     virtualized: bool,
+    measured_heights: Option<TreeRowHeightFn>,
     list_state: Option<ListState>,
+    folder_icons: Option<(SharedString, SharedString)>,
+    row_tooltip_on_overflow: bool,
+    filter: SharedString,
+    filter_fn: Option<TreeFilterFn>,
+    highlight_matches: bool,
+    indent_guides: bool,
+    indent_guide_elbows: bool,
+    scroll_target: Option<ElementId>,
     on_click: Option<ClickCallback>,
     on_item_click: Option<ElementClickCallback>,
     on_item_context_menu: Option<ElementMouseDownCallback>,
     on_toggle_expand: Option<ElementCallback>,
     on_select: Option<ElementCallback>,
     on_check: Option<TreeCheckCallback>,
+    on_move: Option<TreeMoveCallback>,
+    on_expand: Option<TreeExpandCallback>,
 }
 
 impl Default for Tree {
     fn default() -> Self {
-        Self::new(TreeState::new(), &[])
+        Self::new(TreeState::new(), Vec::new())
     }
 }
 
 impl Tree {
-    pub fn new(state: TreeState, nodes: &[TreeNode]) -> Self {
-        let mut tree = Self {
+    pub fn new(state: TreeState, nodes: impl Into<Rc<[TreeNode]>>) -> Self {
+        Self {
             element_id: "ui:tree".into(),
             base: div(),
             state,
-            nodes: nodes.to_vec(),
-            flattened: Vec::new(),
+            nodes: nodes.into(),
+            revision: None,
+            flattened: OnceCell::new(),
             selection_mode: SelectionMode::Multiple,
             show_checkbox: false,
             draggable: false,
-            indent: px(20.),
+            lazy: false,
+            reload_on_expand: false,
+            indent: None,
             row_height: px(32.),
             virtualized: false,
+            measured_heights: None,
             list_state: None,
+            folder_icons: None,
+            row_tooltip_on_overflow: false,
+            filter: SharedString::default(),
+            filter_fn: None,
+            highlight_matches: false,
+            indent_guides: false,
+            indent_guide_elbows: false,
+            scroll_target: None,
             on_click: None,
             on_item_click: None,
             on_item_context_menu: None,
             on_toggle_expand: None,
             on_select: None,
             on_check: None,
-        };
-        tree.rebuild_flattened();
-        tree
+            on_move: None,
+            on_expand: None,
+        }
     }
 
     /// Set a stable element ID for internal keyed state.
@@ -116,26 +205,29 @@ impl Tree {
         self
     }
 
-    fn rebuild_flattened(&mut self) {
-        let mut expanded_ids = std::collections::HashMap::new();
-        for (id, expanded) in &self.state.expanded_nodes {
-            expanded_ids.insert(id.clone(), *expanded);
-        }
+    /// Mark the current `nodes` with a revision the caller bumps whenever they change.
+    ///
+    /// While the revision stays the same, the rows from the last render are reused
+    /// without walking or hashing the nodes. Without one, the nodes are hashed on
+    /// every render to detect changes.
+    pub fn revision(mut self, revision: u64) -> Self {
+        self.revision = Some(revision);
+        self
+    }
 
-        fn collect_expanded(
-            nodes: &[TreeNode],
-            expanded: &mut std::collections::HashMap<ElementId, bool>,
-        ) {
-            for node in nodes {
-                if node.expanded {
-                    expanded.insert(node.id.clone(), true);
-                }
-                collect_expanded(&node.children, expanded);
-            }
-        }
-        collect_expanded(&self.nodes, &mut expanded_ids);
+    /// Drops the rows flattened for [`Self::flattened_nodes`] after an input changed.
+    fn rebuild_flattened(&mut self) {
+        self.flattened.take();
+    }
 
-        self.flattened = flatten_tree(&self.nodes, &expanded_ids, false);
+    fn flatten(&self) -> Vec<FlatTreeNode> {
+        let expanded_ids = expanded_ids(&self.nodes, &self.state.expanded_nodes);
+        visible_rows(
+            &self.nodes,
+            &expanded_ids,
+            &self.filter,
+            self.filter_fn.as_ref(),
+        )
     }
 
     pub fn selection_mode(mut self, mode: SelectionMode) -> Self {
@@ -148,16 +240,59 @@ impl Tree {
         self
     }
 
+    /// Let rows be dragged onto other rows to reorder or re-parent them.
+    ///
+    /// The tree only reports the move through [`on_move`](Self::on_move); the host
+    /// updates its nodes. Escape cancels a drag in progress.
     pub fn draggable(mut self, draggable: bool) -> Self {
         self.draggable = draggable;
         self
     }
 
+    /// Load children on demand.
+    ///
+    /// Expanding a node that has `has_children` set but no `children` fires
+    /// [`on_expand`](Self::on_expand) and shows a loading row until the host
+    /// passes the node's children in. Hosts report an empty node by clearing its
+    /// `has_children`.
+    pub fn lazy(mut self, lazy: bool) -> Self {
+        self.lazy = lazy;
+        self
+    }
+
+    /// With [`lazy`](Self::lazy), fire `on_expand` on every expand, even when the
+    /// node's children are already present.
+    ///
+    /// The current children stay visible meanwhile; set [`TreeNode::loading`] to
+    /// show the loading row instead.
+    pub fn reload_on_expand(mut self, reload: bool) -> Self {
+        self.reload_on_expand = reload;
+        self
+    }
+
+    /// Width of one nesting level (default: the theme's `Scale::Xl` spacing).
     pub fn indent(mut self, indent: Pixels) -> Self {
-        self.indent = indent;
+        self.indent = Some(indent);
+        self
+    }
+
+    /// Draw a thin vertical line at each ancestor indent level of every row.
+    pub fn indent_guides(mut self, enabled: bool) -> Self {
+        self.indent_guides = enabled;
+        self
+    }
+
+    /// With [`indent_guides`](Self::indent_guides), end each line at the last
+    /// child with an elbow instead of running it through every row.
+    ///
+    /// This needs a pass over all visible rows on each render.
+    pub fn indent_guide_elbows(mut self, enabled: bool) -> Self {
+        self.indent_guide_elbows = enabled;
         self
     }
 
+    /// The height virtualized mode assumes for each row when scrolling to a
+    /// node; see [`measured_heights`](Self::measured_heights) for rows that vary.
     pub fn row_height(mut self, height: Pixels) -> Self {
         self.row_height = height;
         self
@@ -172,6 +307,18 @@ impl Tree {
         self
     }
 
+    /// In virtualized mode, give each visible row the height returned for its
+    /// index, for trees whose rows aren't all the same height.
+    ///
+    /// Rows are placed from the running total of these heights, so scrolling to
+    /// a node lands correctly even before the rows above it have been laid out.
+    /// Heights should be at least 32px, the minimum row height; a loading row
+    /// adds 32px more below its node.
+    pub fn measured_heights(mut self, height: impl Fn(usize) -> Pixels + 'static) -> Self {
+        self.measured_heights = Some(Arc::new(height));
+        self
+    }
+
     /// Set the list state for virtualized rendering.
     /// This should be called when virtualized() is enabled.
     pub fn list_state(mut self, state: ListState) -> Self {
@@ -179,6 +326,20 @@ impl Tree {
         self
     }
 
+    /// Reveal a node, expanding its collapsed ancestors, and in virtualized mode
+    /// scroll it to the top of the list with a small margin above.
+    ///
+    /// The tree reveals and scrolls once per target, so it can be passed on every
+    /// render: the user can still scroll away or collapse the target's ancestors
+    /// until a different target is set.
+    pub fn scroll_to(mut self, id: impl Into<ElementId>) -> Self {
+        let id = id.into();
+        self.state.reveal(&self.nodes, &id);
+        self.scroll_target = Some(id);
+        self.rebuild_flattened();
+        self
+    }
+
     /// Set a fixed height for the virtualized tree.
     /// This is needed for the virtualized list to calculate scroll bounds.
     pub fn height(mut self, height: Pixels) -> Self {
@@ -186,6 +347,55 @@ impl Tree {
         self
     }
 
+    /// Set the icons shown for nodes with children, chosen by expansion state.
+    ///
+    /// A node's own icon (e.g. [`ArcTreeNode::with_icon`]) takes precedence.
+    pub fn folder_icons(
+        mut self,
+        open: impl Into<SharedString>,
+        closed: impl Into<SharedString>,
+    ) -> Self {
+        self.folder_icons = Some((open.into(), closed.into()));
+        self
+    }
+
+    /// Show the full label in a tooltip when a row's label is truncated.
+    ///
+    /// Useful for deep hierarchies (e.g. file browsers) where names are cut off.
+    pub fn row_tooltip_on_overflow(mut self, enabled: bool) -> Self {
+        self.row_tooltip_on_overflow = enabled;
+        self
+    }
+
+    /// Show only nodes whose label contains `query`, ignoring case, plus their
+    /// ancestors, which are expanded while the filter is active.
+    ///
+    /// Expansion and selection state are kept, so clearing the filter restores
+    /// the previous view.
+    pub fn filter(mut self, query: impl Into<SharedString>) -> Self {
+        self.filter = query.into();
+        self.rebuild_flattened();
+        self
+    }
+
+    /// Replace the label match of [`filter`](Self::filter) with a custom predicate.
+    ///
+    /// The tree is filtered while a predicate is set, even with an empty query.
+    pub fn filter_fn<F>(mut self, matches: F) -> Self
+    where
+        F: 'static + Fn(&TreeNode) -> bool,
+    {
+        self.filter_fn = Some(Arc::new(matches));
+        self.rebuild_flattened();
+        self
+    }
+
+    /// Emphasize the part of each label that matches the [`filter`](Self::filter) query.
+    pub fn highlight_matches(mut self, highlight: bool) -> Self {
+        self.highlight_matches = highlight;
+        self
+    }
+
     /// Set a click handler for the tree.
     /// The handler receives only the click event (without element ID).
     pub fn on_click<F>(mut self, handler: F) -> Self
@@ -233,14 +443,41 @@ impl Tree {
         self
     }
 
+    /// Called when a row's checkbox is toggled, with the toggled node, its new
+    /// state and every node whose state changed as the toggle cascaded.
+    ///
+    /// Checking a node checks all its descendants; each ancestor becomes checked,
+    /// unchecked or indeterminate depending on its children. See
+    /// [`TreeState::toggle_checked`].
     pub fn on_check<F>(mut self, handler: F) -> Self
     where
-        F: 'static + Fn(&ElementId, TreeCheckedState),
+        F: 'static + Fn(&ElementId, TreeCheckedState, &[ElementId]),
     {
         self.on_check = Some(Arc::new(handler));
         self
     }
 
+    /// Called when a [`lazy`](Self::lazy) tree needs a node's children.
+    pub fn on_expand<F>(mut self, handler: F) -> Self
+    where
+        F: 'static + Fn(&ElementId, &mut Window, &mut App),
+    {
+        self.on_expand = Some(Arc::new(handler));
+        self
+    }
+
+    /// Called when a dragged row is dropped, with the dragged node, the target
+    /// node and where it landed relative to the target.
+    ///
+    /// Drops onto the dragged node itself or into its own subtree are rejected.
+    pub fn on_move<F>(mut self, handler: F) -> Self
+    where
+        F: 'static + Fn(&ElementId, &ElementId, DropPosition),
+    {
+        self.on_move = Some(Arc::new(handler));
+        self
+    }
+
     pub fn toggle_expand(&mut self, id: &ElementId) {
         self.state.toggle_expanded(id);
         self.rebuild_flattened();
@@ -273,16 +510,7 @@ impl Tree {
     }
 
     pub fn select(&mut self, id: &ElementId) {
-        match self.selection_mode {
-            SelectionMode::Single => {
-                self.state.clear_selection();
-                self.state.set_selected(id, true);
-            }
-            SelectionMode::Multiple => {
-                self.state.set_selected(id, !self.state.is_selected(id));
-            }
-            SelectionMode::None => {}
-        }
+        self.state.select(id, self.selection_mode);
         self.rebuild_flattened();
     }
 
@@ -295,7 +523,7 @@ impl Tree {
     }
 
     pub fn flattened_nodes(&self) -> &[FlatTreeNode] {
-        &self.flattened
+        self.flattened.get_or_init(|| self.flatten())
     }
 }
 
@@ -327,10 +555,25 @@ impl Tree {
     /// Render the tree using virtualized list (gpui::list).
     fn render_virtualized(self, window: &mut Window, cx: &mut gpui::App) -> impl IntoElement {
         let show_checkbox = self.show_checkbox;
-        let indent = self.indent;
+        let indent = self.indent.unwrap_or_else(|| cx.theme().spacing(Scale::Xl));
+        let folder_icons = self.folder_icons.clone();
+        let tooltip_on_overflow = self.row_tooltip_on_overflow;
         let base = self.base;
 
         let id = self.element_id.clone();
+        let focus_handle = tree_focus_handle(&id, window, cx);
+        let tree_focused = focus_handle.is_focused(window);
+        let nodes = self.nodes;
+        let drag = self.draggable.then(|| {
+            RowDrag::new(
+                &id,
+                nodes.clone(),
+                focus_handle.clone(),
+                self.on_move.clone(),
+                window,
+                cx,
+            )
+        });
 
         // Use keyed state to persist list state across renders
         let list_state = window.use_keyed_state((id.clone(), "ui:tree:list-state"), cx, |_, _| {
@@ -339,34 +582,33 @@ impl Tree {
                 .unwrap_or_else(|| ListState::new(0, ListAlignment::Top, px(32.)))
         });
 
-        // Recalculate flattened nodes
-        let mut expanded_ids = std::collections::HashMap::new();
-
-        // Collect expanded IDs from the persisted state
         let state_entity =
             window.use_keyed_state((id.clone(), "ui:tree:state"), cx, |_, _| self.state.clone());
-
-        for (id, expanded) in state_entity.read(cx).expanded_nodes() {
-            expanded_ids.insert(id.clone(), *expanded);
-        }
-
-        // Also check the nodes' own expanded field
-        fn collect_expanded<T: super::tree_data::TreeNodeData>(
-            nodes: &[super::tree_data::TreeNode<T>],
-            expanded: &mut std::collections::HashMap<ElementId, bool>,
-        ) {
-            for node in nodes {
-                if node.expanded {
-                    expanded.insert(node.id.clone(), true);
-                }
-                collect_expanded(&node.children, expanded);
-            }
+        let scroll_target = new_scroll_target(&id, self.scroll_target.clone(), window, cx);
+        if let Some(target) = &scroll_target {
+            state_entity.update(cx, |state, _cx| state.reveal(&nodes, target));
         }
-        collect_expanded(&self.nodes, &mut expanded_ids);
 
-        // Recalculate flattened using current nodes
-        let flattened = flatten_tree(&self.nodes, &expanded_ids, false);
+        // Reuse the last render's rows unless the nodes or expansion changed.
+        let state_expanded = state_entity.read(cx).expanded_nodes.clone();
+        let flattened = cached_rows(
+            &id,
+            &nodes,
+            self.revision,
+            &state_expanded,
+            &self.filter,
+            self.filter_fn.as_ref(),
+            window,
+            cx,
+        );
+        let highlight = self.highlight_matches.then(|| self.filter.clone());
+        let guides = RowGuides::new(self.indent_guides, self.indent_guide_elbows, &flattened);
         let item_count = flattened.len();
+        state_entity.update(cx, |state, _cx| state.finish_loading(&flattened));
+        let lazy = self.lazy.then(|| LazyLoad {
+            on_expand: self.on_expand.clone(),
+            reload: self.reload_on_expand,
+        });
 
         // Update list state with item count.
         //
@@ -381,57 +623,124 @@ impl Tree {
         });
 
         let state_snapshot: TreeState = state_entity.read(cx).clone();
+        let row_height = self.measured_heights.clone();
+        if let Some(ix) = scroll_target
+            .as_ref()
+            .and_then(|target| flattened.iter().position(|row| &row.id == target))
+        {
+            let controller = VirtualListController::new(list_state.read(cx).clone());
+            let controller = match &row_height {
+                // Built only when the scroll target changes, not on every render.
+                Some(height) => {
+                    controller.with_heights(Rc::new(HeightIndex::new(item_count, |ix| {
+                        let loading = shows_loading_row(&flattened[ix], &state_snapshot);
+                        height(ix) + if loading { LOADING_ROW_HEIGHT } else { px(0.) }
+                    })))
+                }
+                None => controller.with_row_height(self.row_height),
+            };
+            controller.scroll_to_item(ix, ScrollAlign::Top, window);
+        }
+
         let on_item_click = self.on_item_click;
         let on_item_context_menu = self.on_item_context_menu;
         let selection_mode = self.selection_mode;
-        let _on_toggle_expand = self.on_toggle_expand;
-        let _on_select = self.on_select;
+        let on_check = self.on_check;
+        let keyboard = RowKeyboard {
+            rows: flattened.clone(),
+            state: state_entity.clone(),
+            list_state: Some(list_state.read(cx).clone()),
+            selection_mode,
+            on_item_click: on_item_click.clone(),
+            on_select: self.on_select,
+            on_toggle_expand: self.on_toggle_expand,
+            lazy: lazy.clone(),
+        };
 
         // Clone for use in closures that may be called multiple times
         let state_entity_for_toggle = state_entity.clone();
         let state_entity_for_select = state_entity.clone();
         let on_item_click_clone = on_item_click.clone();
         let on_item_context_menu_clone = on_item_context_menu.clone();
+        let drag_for_container = drag.clone();
 
         // Create the virtualized list
         let _node_id = self.element_id.clone();
-        let list = list(list_state.read(cx).clone(), move |ix, _window, _cx| {
+        let list = list(list_state.read(cx).clone(), move |ix, window, cx| {
             let node = &flattened[ix];
             let node_id = node.id.clone();
             let is_selected = state_snapshot.is_selected(&node_id);
 
-            let label_text = node.data.label().to_string();
+            let (label, label_tooltip) = row_label(
+                &node_id,
+                node.data.label().to_string().into(),
+                highlight.as_ref().map(SharedString::as_ref),
+                tooltip_on_overflow,
+                window,
+                cx,
+            );
             let disabled = node.data.disabled;
             let has_children = node.has_children;
-            let expanded = state_snapshot.is_expanded(&node_id);
+            let expanded = node.expanded;
+            let focused = tree_focused && state_snapshot.focused() == Some(&node_id);
 
-            let icon_path = node.data.icon.clone().map(super::Icon::new);
+            let icon_path = resolve_node_icon(
+                node.data.icon.as_deref(),
+                has_children,
+                expanded,
+                folder_icons.as_ref(),
+            )
+            .map(super::Icon::new);
 
             let row_id: ElementId = (node_id.clone(), "ui:tree:row").into();
 
             let mut row = super::tree_item::tree_item(row_id)
                 .depth(node.depth)
+                .indent_guides(guides.for_row(node))
                 .indent(indent)
                 .selected(is_selected)
+                .focused(focused)
                 .disabled(disabled)
                 .has_children(has_children)
                 .expanded(expanded)
                 .show_checkbox(show_checkbox)
-                .label(super::label(label_text).ellipsis(true));
+                .checked(state_snapshot.checked_or(&node_id, node.checked))
+                .when(show_checkbox && !disabled, |this| {
+                    this.on_check(toggle_check(
+                        nodes.clone(),
+                        state_entity_for_select.clone(),
+                        on_check.clone(),
+                        node_id.clone(),
+                    ))
+                })
+                .label(label)
+                .when_some(label_tooltip, |this, text| {
+                    this.tooltip(super::tooltip(text).build())
+                });
 
             if let Some(icon) = icon_path {
                 row = row.icon(icon);
             }
 
+            if let Some(drag) = &drag {
+                row = drag.apply(row, &node_id, node.data.label(), has_children, cx);
+            }
+
             if has_children && !disabled {
                 let state_entity = state_entity_for_toggle.clone();
                 row = row.on_click({
-                    let node_id = node_id.clone();
+                    let node = node.clone();
+                    let lazy = lazy.clone();
                     move |_ev, window, cx| {
-                        state_entity.update(cx, |state, _cx| {
-                            let expanded_now = state.is_expanded(&node_id);
-                            state.set_expanded(&node_id, !expanded_now);
-                        });
+                        let expanded_now = state_entity.read(cx).is_expanded(&node.id);
+                        set_row_expanded(
+                            &state_entity,
+                            &node,
+                            !expanded_now,
+                            lazy.as_ref(),
+                            window,
+                            cx,
+                        );
                         window.refresh();
                     }
                 });
@@ -442,17 +751,11 @@ impl Tree {
                 let on_item_click = on_item_click_clone.clone();
                 row = row.on_click({
                     let node_id = node_id.clone();
+                    let rows = flattened.clone();
                     move |ev, window, cx| {
-                        state_entity.update(cx, |state, _cx| match selection_mode {
-                            SelectionMode::Single => {
-                                state.clear_selection();
-                                state.set_selected(&node_id, true);
-                            }
-                            SelectionMode::Multiple => {
-                                let selected = state.is_selected(&node_id);
-                                state.set_selected(&node_id, !selected);
-                            }
-                            SelectionMode::None => {}
+                        state_entity.update(cx, |state, _cx| {
+                            state.select_row(&rows, &node_id, selection_mode, ev.modifiers());
+                            state.set_focused(Some(node_id.clone()));
                         });
 
                         if let Some(handler) = &on_item_click {
@@ -476,8 +779,20 @@ impl Tree {
                 });
             }
 
+            if let Some(height) = &row_height {
+                row = row.h(height(ix));
+            }
+
+            let loading = shows_loading_row(node, &state_snapshot);
             super::virtual_row(node_id.clone())
+                .when_some(row_height.as_ref(), |this, height| {
+                    let loading_height = if loading { LOADING_ROW_HEIGHT } else { px(0.) };
+                    this.h(height(ix) + loading_height).overflow_hidden()
+                })
                 .child(row)
+                .when(loading, |this| {
+                    this.child(loading_row(&node_id, node.depth, indent, cx))
+                })
                 .into_any_element()
         })
         // NOTE: For scrollable lists we want the list to size itself from the
@@ -500,177 +815,1068 @@ impl Tree {
             .h_full()
             .min_h_0()
             .flex_grow()
+            .key_context("UITree")
+            .track_focus(&focus_handle)
+            .map(|this| keyboard.listen(this))
+            .when_some(drag_for_container, |this, drag| drag.listen(this))
             .child(list)
     }
 
     /// Render the tree using normal flex layout.
     fn render_normal(self, window: &mut Window, cx: &mut gpui::App) -> impl IntoElement {
         let show_checkbox = self.show_checkbox;
-        let indent = self.indent;
-        let draggable = self.draggable;
+        let indent = self.indent.unwrap_or_else(|| cx.theme().spacing(Scale::Xl));
+        let folder_icons = self.folder_icons.clone();
+        let tooltip_on_overflow = self.row_tooltip_on_overflow;
 
         // Recalculate flattened nodes using the current nodes.
         // This is necessary because the Tree may be reconstructed with new nodes
         // on each render (e.g., when the underlying data changes), but the expansion
         // state is persisted via keyed state.
-        let mut expanded_ids = std::collections::HashMap::new();
         let id = self.element_id;
+        let focus_handle = tree_focus_handle(&id, window, cx);
+        let tree_focused = focus_handle.is_focused(window);
+        let nodes = self.nodes;
+        let drag = self.draggable.then(|| {
+            RowDrag::new(
+                &id,
+                nodes.clone(),
+                focus_handle.clone(),
+                self.on_move.clone(),
+                window,
+                cx,
+            )
+        });
 
         // Tree needs internal state to be updatable from click handlers.
         // Store TreeState in a keyed entity so closures can call `update`.
         let state_entity =
             window.use_keyed_state((id.clone(), "ui:tree:state"), cx, |_, _| self.state.clone());
-
-        // Collect expanded IDs from the persisted state
-        for (id, expanded) in state_entity.read(cx).expanded_nodes() {
-            expanded_ids.insert(id.clone(), *expanded);
+        if let Some(target) = new_scroll_target(&id, self.scroll_target.clone(), window, cx) {
+            state_entity.update(cx, |state, _cx| state.reveal(&nodes, &target));
         }
 
-        // Also check the nodes' own expanded field
-        fn collect_expanded<T: super::tree_data::TreeNodeData>(
-            nodes: &[super::tree_data::TreeNode<T>],
-            expanded: &mut std::collections::HashMap<ElementId, bool>,
-        ) {
-            for node in nodes {
-                if node.expanded {
-                    expanded.insert(node.id.clone(), true);
-                }
-                collect_expanded(&node.children, expanded);
-            }
-        }
-        collect_expanded(&self.nodes, &mut expanded_ids);
-
-        // Recalculate flattened using current nodes
-        let flattened = flatten_tree(&self.nodes, &expanded_ids, false);
+        // Reuse the last render's rows unless the nodes or expansion changed.
+        let state_expanded = state_entity.read(cx).expanded_nodes.clone();
+        let flattened = cached_rows(
+            &id,
+            &nodes,
+            self.revision,
+            &state_expanded,
+            &self.filter,
+            self.filter_fn.as_ref(),
+            window,
+            cx,
+        );
+        let highlight = self.highlight_matches.then(|| self.filter.clone());
+        let guides = RowGuides::new(self.indent_guides, self.indent_guide_elbows, &flattened);
+        state_entity.update(cx, |state, _cx| state.finish_loading(&flattened));
+        let lazy = self.lazy.then(|| LazyLoad {
+            on_expand: self.on_expand.clone(),
+            reload: self.reload_on_expand,
+        });
+        let rows = flattened.clone();
+        let keyboard = RowKeyboard {
+            rows: rows.clone(),
+            state: state_entity.clone(),
+            list_state: None,
+            selection_mode: self.selection_mode,
+            on_item_click: self.on_item_click.clone(),
+            on_select: self.on_select.clone(),
+            on_toggle_expand: self.on_toggle_expand.clone(),
+            lazy: lazy.clone(),
+        };
 
         // Get the current state snapshot for rendering
         let state_snapshot: TreeState = state_entity.read(cx).clone();
 
+        let labels: Vec<_> = flattened
+            .iter()
+            .map(|node| {
+                let row_label = row_label(
+                    &node.id,
+                    node.data.label().to_string().into(),
+                    highlight.as_ref().map(SharedString::as_ref),
+                    tooltip_on_overflow,
+                    window,
+                    cx,
+                );
+                let drop_target = drag.as_ref().and_then(|drag| drag.target_for(&node.id, cx));
+                (row_label, drop_target)
+            })
+            .collect();
+
         let on_item_click = self.on_item_click;
         let on_click = self.on_click;
         let on_item_context_menu = self.on_item_context_menu;
         let on_toggle_expand = self.on_toggle_expand;
         let selection_mode = self.selection_mode;
         let on_select = self.on_select;
+        let on_check = self.on_check;
 
         // NOTE: `Tree` is the stateful container (expanded + selection).
         // `tree_item` is the presentational row (indent + disclosure + icon + label).
         // Wiring click handling here keeps `TreeItem` generic and reusable.
 
+        let drag_for_container = drag.clone();
+
         self.base
             .flex()
             .flex_col()
             .gap_1()
-            .children(flattened.into_iter().map(move |node| {
-                let node_id = node.id.clone();
-                let is_selected = state_snapshot.is_selected(&node_id);
-
-                let label_text = node.data.label().to_string();
-                let disabled = node.data.disabled;
-                let has_children = node.has_children;
-                let expanded = state_snapshot.is_expanded(&node_id);
-
-                let on_item_click = on_item_click.clone();
-                let on_click = on_click.clone();
-                let on_item_context_menu = on_item_context_menu.clone();
-                let on_toggle_expand = on_toggle_expand.clone();
-                let on_select = on_select.clone();
-
-                // Only support file icon paths for the default ArcTreeNode for now.
-                let icon_path = node.data.icon.clone().map(super::Icon::new);
-
-                let row_id: ElementId = (node_id.clone(), "ui:tree:row").into();
-
-                let mut row = super::tree_item::tree_item(row_id)
-                    .depth(node.depth)
-                    .indent(indent)
-                    .selected(is_selected)
-                    .disabled(disabled)
-                    .has_children(has_children)
-                    .expanded(expanded)
-                    .show_checkbox(show_checkbox)
-                    .label(super::label(label_text).ellipsis(true));
-
-                if let Some(icon) = icon_path {
-                    row = row.icon(icon);
-                }
+            .key_context("UITree")
+            .track_focus(&focus_handle)
+            .map(|this| keyboard.listen(this))
+            .when_some(drag_for_container, |this, drag| drag.listen(this))
+            .children(flattened.iter().cloned().zip(labels).map(
+                move |(node, ((label, label_tooltip), drop_target))| {
+                    let node_id = node.id.clone();
+                    let is_selected = state_snapshot.is_selected(&node_id);
+
+                    let disabled = node.data.disabled;
+                    let has_children = node.has_children;
+                    let expanded = node.expanded;
+                    let focused = tree_focused && state_snapshot.focused() == Some(&node_id);
+
+                    let on_item_click = on_item_click.clone();
+                    let on_click = on_click.clone();
+                    let on_item_context_menu = on_item_context_menu.clone();
+                    let on_toggle_expand = on_toggle_expand.clone();
+                    let on_select = on_select.clone();
+
+                    // Only support file icon paths for the default ArcTreeNode for now.
+                    let icon_path = resolve_node_icon(
+                        node.data.icon.as_deref(),
+                        has_children,
+                        expanded,
+                        folder_icons.as_ref(),
+                    )
+                    .map(super::Icon::new);
+
+                    let row_id: ElementId = (node_id.clone(), "ui:tree:row").into();
+
+                    let mut row = super::tree_item::tree_item(row_id)
+                        .depth(node.depth)
+                        .indent_guides(guides.for_row(&node))
+                        .indent(indent)
+                        .selected(is_selected)
+                        .focused(focused)
+                        .disabled(disabled)
+                        .has_children(has_children)
+                        .expanded(expanded)
+                        .show_checkbox(show_checkbox)
+                        .checked(state_snapshot.checked_or(&node_id, node.checked))
+                        .when(show_checkbox && !disabled, |this| {
+                            this.on_check(toggle_check(
+                                nodes.clone(),
+                                state_entity.clone(),
+                                on_check.clone(),
+                                node_id.clone(),
+                            ))
+                        })
+                        .label(label)
+                        .when_some(label_tooltip, |this, text| {
+                            this.tooltip(super::tooltip(text).build())
+                        });
 
-                // Expand/collapse toggle: currently handled by treating the disclosure area
-                // as a normal click target. TreeItem does not have a dedicated handler API.
-                if has_children && !disabled {
-                    row = row.on_click({
-                        let node_id = node_id.clone();
-                        let state_entity = state_entity.clone();
-                        move |_ev, window, cx| {
-                            state_entity.update(cx, |state, _cx| {
-                                let expanded_now = state.is_expanded(&node_id);
-                                state.set_expanded(&node_id, !expanded_now);
-                            });
-
-                            if let Some(handler) = &on_toggle_expand {
-                                handler(&node_id);
-                            }
+                    if let Some(icon) = icon_path {
+                        row = row.icon(icon);
+                    }
 
-                            window.refresh();
-                        }
-                    });
-                }
+                    if let Some(drag) = &drag {
+                        row = drag.apply_with_target(
+                            row,
+                            &node_id,
+                            node.data.label(),
+                            has_children,
+                            drop_target,
+                        );
+                    }
 
-                // Selection click on the whole row.
-                if !disabled {
-                    row = row.on_click({
-                        let node_id = node_id.clone();
-                        let state_entity = state_entity.clone();
-                        move |ev, window, cx| {
-                            state_entity.update(cx, |state, _cx| match selection_mode {
-                                SelectionMode::Single => {
-                                    state.clear_selection();
-                                    state.set_selected(&node_id, true);
-                                }
-                                SelectionMode::Multiple => {
-                                    let selected = state.is_selected(&node_id);
-                                    state.set_selected(&node_id, !selected);
+                    // Expand/collapse toggle: currently handled by treating the disclosure area
+                    // as a normal click target. TreeItem does not have a dedicated handler API.
+                    if has_children && !disabled {
+                        row = row.on_click({
+                            let node = node.clone();
+                            let node_id = node_id.clone();
+                            let state_entity = state_entity.clone();
+                            let lazy = lazy.clone();
+                            move |_ev, window, cx| {
+                                let expanded_now = state_entity.read(cx).is_expanded(&node_id);
+                                set_row_expanded(
+                                    &state_entity,
+                                    &node,
+                                    !expanded_now,
+                                    lazy.as_ref(),
+                                    window,
+                                    cx,
+                                );
+
+                                if let Some(handler) = &on_toggle_expand {
+                                    handler(&node_id);
                                 }
-                                SelectionMode::None => {}
-                            });
 
-                            if let Some(handler) = &on_item_click {
-                                handler(&node_id, ev, window, cx);
+                                window.refresh();
                             }
-                            if let Some(handler) = &on_click {
-                                handler(ev, window, cx);
+                        });
+                    }
+
+                    // Selection click on the whole row.
+                    if !disabled {
+                        row = row.on_click({
+                            let node_id = node_id.clone();
+                            let state_entity = state_entity.clone();
+                            let rows = rows.clone();
+                            move |ev, window, cx| {
+                                state_entity.update(cx, |state, _cx| {
+                                    state.select_row(
+                                        &rows,
+                                        &node_id,
+                                        selection_mode,
+                                        ev.modifiers(),
+                                    );
+                                    state.set_focused(Some(node_id.clone()));
+                                });
+
+                                if let Some(handler) = &on_item_click {
+                                    handler(&node_id, ev, window, cx);
+                                }
+                                if let Some(handler) = &on_click {
+                                    handler(ev, window, cx);
+                                }
+
+                                if let Some(handler) = &on_select {
+                                    handler(&node_id);
+                                }
+
+                                window.refresh();
                             }
+                        });
+                    }
 
-                            if let Some(handler) = &on_select {
-                                handler(&node_id);
+                    if !disabled {
+                        row = row.on_context_menu({
+                            let node_id = node_id.clone();
+                            move |ev, window, cx| {
+                                if let Some(handler) = &on_item_context_menu {
+                                    handler(&node_id, ev, window, cx);
+                                }
                             }
+                        });
+                    }
 
-                            window.refresh();
-                        }
-                    });
+                    let loading = shows_loading_row(&node, &state_snapshot);
+                    super::virtual_row(node_id.clone())
+                        .child(row)
+                        .when(loading, |this| {
+                            this.child(loading_row(&node_id, node.depth, indent, cx))
+                        })
+                },
+            ))
+    }
+}
+
+/// Lazy-loading settings shared by the click and keyboard expand handlers.
+#[derive(Clone)]
+struct LazyLoad {
+    on_expand: Option<TreeExpandCallback>,
+    reload: bool,
+}
+
+/// Expands or collapses `row`, asking the host for its children first when
+/// lazy loading applies and no load is already pending.
+fn set_row_expanded(
+    state: &Entity<TreeState>,
+    row: &FlatTreeNode,
+    expanded: bool,
+    lazy: Option<&LazyLoad>,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let load = expanded
+        && lazy.is_some_and(|lazy| wants_children(row, lazy.reload))
+        && !state.read(cx).is_loading(&row.id);
+    state.update(cx, |state, _cx| {
+        state.set_expanded(&row.id, expanded);
+        if load {
+            state.set_loading(&row.id, true);
+        }
+    });
+
+    if load && let Some(handler) = lazy.and_then(|lazy| lazy.on_expand.as_ref()) {
+        handler(&row.id, window, cx);
+    }
+}
+
+/// Placeholder row shown under an expanded node while its children load.
+fn loading_row(node_id: &ElementId, depth: usize, indent: Pixels, cx: &App) -> TreeItem {
+    super::tree_item::tree_item((node_id.clone(), "ui:tree:loading"))
+        .depth(depth + 1)
+        .indent(indent)
+        .icon(
+            super::spinner()
+                .id((node_id.clone(), "ui:tree:loading-spinner"))
+                .size(super::SpinnerSize::Sm),
+        )
+        .label(super::label(DefaultPlaceholders::loading(cx.i18n().locale())).muted(true))
+}
+
+/// Whether `row` is expanded and waiting for its children.
+fn shows_loading_row(row: &FlatTreeNode, state: &TreeState) -> bool {
+    row.expanded && (row.loading || state.is_loading(&row.id))
+}
+
+/// Toggles a row's checkbox, cascading the change through `nodes`, and reports it.
+fn toggle_check(
+    nodes: Rc<[TreeNode]>,
+    state: Entity<TreeState>,
+    on_check: Option<TreeCheckCallback>,
+    id: ElementId,
+) -> impl Fn(&ClickEvent, &mut Window, &mut App) + 'static {
+    move |_, window, cx| {
+        let (checked, affected) = state.update(cx, |state, _cx| state.toggle_checked(&nodes, &id));
+        if let Some(handler) = &on_check {
+            handler(&id, checked, &affected);
+        }
+        window.refresh();
+    }
+}
+
+/// The focus handle of the tree container, kept across renders.
+fn tree_focus_handle(id: &ElementId, window: &mut Window, cx: &mut App) -> FocusHandle {
+    window
+        .use_keyed_state((id.clone(), "ui:tree:focus"), cx, |_, cx| cx.focus_handle())
+        .read(cx)
+        .clone()
+}
+
+/// Keyboard navigation shared by the normal and virtualized renderers.
+struct RowKeyboard {
+    rows: Rc<Vec<FlatTreeNode>>,
+    state: Entity<TreeState>,
+    /// Scrolled to reveal the focused row in virtualized mode.
+    list_state: Option<ListState>,
+    selection_mode: SelectionMode,
+    on_item_click: Option<ElementClickCallback>,
+    on_select: Option<ElementCallback>,
+    on_toggle_expand: Option<ElementCallback>,
+    lazy: Option<LazyLoad>,
+}
+
+impl RowKeyboard {
+    fn listen(self, container: Div) -> Div {
+        let this = Rc::new(self);
+        let step = |step: TreeNavigation| {
+            let this = this.clone();
+            move |window: &mut Window, cx: &mut App| this.navigate(step, window, cx)
+        };
+        // The arrow keys follow the layout: in RTL, Left expands and Right collapses.
+        let arrow = |ltr: TreeNavigation, rtl: TreeNavigation| {
+            let this = this.clone();
+            move |window: &mut Window, cx: &mut App| {
+                let step = if cx.text_direction().is_rtl() {
+                    rtl
+                } else {
+                    ltr
+                };
+                this.navigate(step, window, cx)
+            }
+        };
+        let (previous, next, first, last, right, left) = (
+            step(TreeNavigation::Previous),
+            step(TreeNavigation::Next),
+            step(TreeNavigation::First),
+            step(TreeNavigation::Last),
+            arrow(TreeNavigation::Expand, TreeNavigation::Collapse),
+            arrow(TreeNavigation::Collapse, TreeNavigation::Expand),
+        );
+
+        container
+            .on_action(move |_: &FocusPrevious, window, cx| previous(window, cx))
+            .on_action(move |_: &FocusNext, window, cx| next(window, cx))
+            .on_action(move |_: &FocusFirst, window, cx| first(window, cx))
+            .on_action(move |_: &FocusLast, window, cx| last(window, cx))
+            .on_action(move |_: &ExpandFocused, window, cx| right(window, cx))
+            .on_action(move |_: &CollapseFocused, window, cx| left(window, cx))
+            .on_action(move |_: &SelectFocused, window, cx| this.select_focused(window, cx))
+    }
+
+    fn navigate(&self, step: TreeNavigation, window: &mut Window, cx: &mut App) {
+        let focused = self.state.read(cx).focused().cloned();
+        match navigate(&self.rows, focused.as_ref(), step) {
+            Some(TreeNavigationTarget::Focus(id)) => {
+                if let (Some(list_state), Some(ix)) = (
+                    &self.list_state,
+                    self.rows.iter().position(|row| row.id == id),
+                ) {
+                    list_state.scroll_to_reveal_item(ix);
                 }
+                self.state
+                    .update(cx, |state, _cx| state.set_focused(Some(id)));
+            }
+            Some(TreeNavigationTarget::SetExpanded(id, expanded)) => {
+                let Some(row) = self.rows.iter().find(|row| row.id == id) else {
+                    return;
+                };
+                set_row_expanded(&self.state, row, expanded, self.lazy.as_ref(), window, cx);
+                if let Some(handler) = &self.on_toggle_expand {
+                    handler(&id);
+                }
+            }
+            None => return,
+        }
+        window.refresh();
+    }
 
-                if !disabled {
-                    row = row.on_context_menu({
-                        let node_id = node_id.clone();
-                        move |ev, window, cx| {
-                            if let Some(handler) = &on_item_context_menu {
-                                handler(&node_id, ev, window, cx);
-                            }
-                        }
+    fn select_focused(&self, window: &mut Window, cx: &mut App) {
+        let Some(id) = self.state.read(cx).focused().cloned() else {
+            return;
+        };
+        if self
+            .rows
+            .iter()
+            .find(|row| row.id == id)
+            .is_none_or(|row| row.data.disabled)
+        {
+            return;
+        }
+
+        self.state
+            .update(cx, |state, _cx| state.select(&id, self.selection_mode));
+
+        if let Some(handler) = &self.on_item_click {
+            let event = ClickEvent::Keyboard(KeyboardClickEvent {
+                button: KeyboardButton::Enter,
+                ..Default::default()
+            });
+            handler(&id, &event, window, cx);
+        }
+        if let Some(handler) = &self.on_select {
+            handler(&id);
+        }
+        window.refresh();
+    }
+}
+
+/// Value carried by a dragged tree row.
+#[derive(Clone)]
+struct DraggedTreeNode {
+    /// Identifies the tree the row came from, so other trees ignore it.
+    tree: EntityId,
+    id: ElementId,
+    label: SharedString,
+}
+
+/// Drag preview that follows the cursor.
+struct DraggedTreeNodeView {
+    label: SharedString,
+}
+
+impl Render for DraggedTreeNodeView {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        TreeDragPreview::new(self.label.to_string())
+    }
+}
+
+/// Drag-and-drop wiring shared by the normal and virtualized row renderers.
+#[derive(Clone)]
+struct RowDrag {
+    nodes: Rc<[TreeNode]>,
+    drop_target: Entity<Option<DropTarget>>,
+    focus_handle: FocusHandle,
+    on_move: Option<TreeMoveCallback>,
+    indicator: Hsla,
+}
+
+impl RowDrag {
+    fn new(
+        id: &ElementId,
+        nodes: Rc<[TreeNode]>,
+        focus_handle: FocusHandle,
+        on_move: Option<TreeMoveCallback>,
+        window: &mut Window,
+        cx: &mut gpui::App,
+    ) -> Self {
+        let drop_target =
+            window.use_keyed_state((id.clone(), "ui:tree:drop-target"), cx, |_, _| {
+                None::<DropTarget>
+            });
+        // A drag that ended outside any row leaves its last target behind.
+        if !cx.has_active_drag() && drop_target.read(cx).is_some() {
+            drop_target.update(cx, |target, _| *target = None);
+        }
+
+        Self {
+            nodes,
+            drop_target,
+            focus_handle,
+            on_move,
+            indicator: cx.theme().border.focus,
+        }
+    }
+
+    /// Cancels a drag in progress on Escape; the tree takes focus when a drag starts.
+    fn listen(&self, container: Div) -> Div {
+        let drop_target = self.drop_target.clone();
+        container.on_key_down(move |ev: &KeyDownEvent, window, cx| {
+            if ev.keystroke.key != "escape" || !cx.has_active_drag() {
+                return;
+            }
+            cx.stop_active_drag(window);
+            drop_target.update(cx, |target, cx| {
+                *target = None;
+                cx.notify();
+            });
+            cx.stop_propagation();
+        })
+    }
+
+    /// Where a drop onto `id` would currently land, if it is the drop target.
+    fn target_for(&self, id: &ElementId, cx: &gpui::App) -> Option<DropPosition> {
+        self.drop_target
+            .read(cx)
+            .as_ref()
+            .filter(|target| &target.id == id)
+            .map(|target| target.position)
+    }
+
+    fn apply(
+        &self,
+        row: TreeItem,
+        id: &ElementId,
+        label: &str,
+        has_children: bool,
+        cx: &gpui::App,
+    ) -> TreeItem {
+        let target = self.target_for(id, cx);
+        self.apply_with_target(row, id, label, has_children, target)
+    }
+
+    fn apply_with_target(
+        &self,
+        row: TreeItem,
+        id: &ElementId,
+        label: &str,
+        has_children: bool,
+        target: Option<DropPosition>,
+    ) -> TreeItem {
+        let tree = self.drop_target.entity_id();
+        let dragged = DraggedTreeNode {
+            tree,
+            id: id.clone(),
+            label: label.to_string().into(),
+        };
+        let indicator = self.indicator;
+        let line = || div().absolute().left_0().right_0().h(px(2.)).bg(indicator);
+
+        row.relative()
+            .when_some(target, |this, position| match position {
+                DropPosition::Before => this.child(line().top_0()),
+                DropPosition::After => this.child(line().bottom_0()),
+                DropPosition::On => this.child(
+                    div()
+                        .absolute()
+                        .inset_0()
+                        .rounded_md()
+                        .border_1()
+                        .border_color(indicator),
+                ),
+            })
+            .on_drag(dragged, {
+                let focus_handle = self.focus_handle.clone();
+                move |dragged: &DraggedTreeNode, _offset, window, cx| {
+                    window.focus(&focus_handle);
+                    let label = dragged.label.clone();
+                    cx.new(|_| DraggedTreeNodeView { label })
+                }
+            })
+            .on_drag_move::<DraggedTreeNode>({
+                let nodes = self.nodes.clone();
+                let drop_target = self.drop_target.clone();
+                let id = id.clone();
+                move |ev: &DragMoveEvent<DraggedTreeNode>, _window, cx| {
+                    if !ev.bounds.contains(&ev.event.position) {
+                        return;
+                    }
+                    let dragged = ev.drag(cx);
+                    if dragged.tree != tree {
+                        return;
+                    }
+
+                    let y_offset = ev.event.position.y - ev.bounds.top();
+                    let position = drop_position(y_offset, ev.bounds.size.height, has_children);
+                    let next =
+                        (!is_same_or_descendant(&nodes, &dragged.id, &id)).then(|| DropTarget {
+                            id: id.clone(),
+                            position,
+                            y_offset,
+                        });
+
+                    let changed = {
+                        let current = drop_target.read(cx).as_ref();
+                        current.map(|t| (&t.id, t.position))
+                            != next.as_ref().map(|t| (&t.id, t.position))
+                    };
+                    if changed {
+                        drop_target.update(cx, |target, cx| {
+                            *target = next;
+                            cx.notify();
+                        });
+                    }
+                }
+            })
+            .on_drop::<DraggedTreeNode>({
+                let drop_target = self.drop_target.clone();
+                let on_move = self.on_move.clone();
+                let id = id.clone();
+                move |dragged, _window, cx| {
+                    let target = drop_target.update(cx, |target, cx| {
+                        cx.notify();
+                        target.take()
                     });
+                    let Some(target) = target.filter(|target| target.id == id) else {
+                        return;
+                    };
+                    if dragged.tree != tree {
+                        return;
+                    }
+                    if let Some(handler) = &on_move {
+                        handler(&dragged.id, &target.id, target.position);
+                    }
                 }
+            })
+    }
+}
+
+/// Picks the leading icon path for a row.
+///
+/// The node's own icon wins; otherwise nodes with children use the open or closed
+/// folder icon depending on `expanded`.
+fn resolve_node_icon(
+    node_icon: Option<&str>,
+    has_children: bool,
+    expanded: bool,
+    folder_icons: Option<&(SharedString, SharedString)>,
+) -> Option<SharedString> {
+    if let Some(icon) = node_icon {
+        return Some(SharedString::from(icon.to_string()));
+    }
+    if !has_children {
+        return None;
+    }
+    folder_icons.map(|(open, closed)| {
+        if expanded {
+            open.clone()
+        } else {
+            closed.clone()
+        }
+    })
+}
+
+/// Indent guides for the rows of one render.
+#[derive(Clone)]
+enum RowGuides {
+    Off,
+    /// A line at every ancestor level.
+    Lines,
+    /// Precomputed guides with elbows, indexed like the flattened rows.
+    Elbows(Rc<Vec<Vec<IndentGuide>>>),
+}
+
+impl RowGuides {
+    fn new(enabled: bool, elbows: bool, rows: &[FlatTreeNode]) -> Self {
+        match (enabled, elbows) {
+            (false, _) => Self::Off,
+            (true, false) => Self::Lines,
+            (true, true) => Self::Elbows(Rc::new(indent_guides(rows))),
+        }
+    }
+
+    fn for_row(&self, row: &FlatTreeNode) -> Vec<IndentGuide> {
+        match self {
+            Self::Off => Vec::new(),
+            Self::Lines => vec![IndentGuide::Line; row.depth],
+            Self::Elbows(guides) => guides.get(row.index).cloned().unwrap_or_default(),
+        }
+    }
+}
+
+/// Flattens the rows to show, applying the filter when one is active.
+fn visible_rows(
+    nodes: &[TreeNode],
+    expanded_ids: &HashMap<ElementId, bool>,
+    query: &str,
+    filter_fn: Option<&TreeFilterFn>,
+) -> Vec<FlatTreeNode> {
+    let query = query.trim();
+    match filter_fn {
+        Some(matches) => flatten_filtered(nodes, &|node| matches(node)),
+        None if !query.is_empty() => flatten_filtered(nodes, &|node| {
+            match_range(node.data.label(), query).is_some()
+        }),
+        None => flatten_tree(nodes, expanded_ids, false),
+    }
+}
+
+/// Returns `target` when it differs from the target applied on an earlier
+/// render, recording it as applied.
+fn new_scroll_target(
+    id: &ElementId,
+    target: Option<ElementId>,
+    window: &mut Window,
+    cx: &mut App,
+) -> Option<ElementId> {
+    let applied = window.use_keyed_state((id.clone(), "ui:tree:scroll-target"), cx, |_, _| None);
+    applied.update(cx, |applied, _| replace_scroll_target(applied, target))
+}
+
+/// Stores `target` in `applied`, returning it only if it changed.
+fn replace_scroll_target(
+    applied: &mut Option<ElementId>,
+    target: Option<ElementId>,
+) -> Option<ElementId> {
+    if *applied == target {
+        return None;
+    }
+    applied.clone_from(&target);
+    target
+}
+
+/// Returns the rows to show, reusing the rows cached in keyed state while the
+/// inputs hash the same as on the last render.
+///
+/// With a `revision`, the nodes are trusted to be unchanged while it stays the
+/// same and only it is hashed in their place. A custom `filter_fn` can't be
+/// hashed, so trees with one flatten every render.
+#[allow(clippy::too_many_arguments)]
+fn cached_rows(
+    id: &ElementId,
+    nodes: &[TreeNode],
+    revision: Option<u64>,
+    state_expanded: &HashMap<ElementId, bool>,
+    query: &str,
+    filter_fn: Option<&TreeFilterFn>,
+    window: &mut Window,
+    cx: &mut App,
+) -> Rc<Vec<FlatTreeNode>> {
+    if filter_fn.is_some() {
+        let expanded_ids = expanded_ids(nodes, state_expanded);
+        return Rc::new(visible_rows(nodes, &expanded_ids, query, filter_fn));
+    }
+
+    let key = match revision {
+        Some(revision) => revision_key(revision, state_expanded, query),
+        None => rows_key(nodes, &expanded_ids(nodes, state_expanded), query),
+    };
+    let cache = window.use_keyed_state((id.clone(), "ui:tree:rows"), cx, |_, _| {
+        None::<(u64, Rc<Vec<FlatTreeNode>>)>
+    });
+    if let Some((_, rows)) = cache.read(cx).as_ref().filter(|(cached, _)| *cached == key) {
+        return rows.clone();
+    }
+
+    let expanded_ids = expanded_ids(nodes, state_expanded);
+    let rows = Rc::new(visible_rows(nodes, &expanded_ids, query, None));
+    cache.update(cx, |cache, _| *cache = Some((key, rows.clone())));
+    rows
+}
+
+/// Merges the expansion recorded in `state_expanded` with the nodes' own
+/// `expanded` flags.
+fn expanded_ids(
+    nodes: &[TreeNode],
+    state_expanded: &HashMap<ElementId, bool>,
+) -> HashMap<ElementId, bool> {
+    fn collect_expanded(nodes: &[TreeNode], expanded: &mut HashMap<ElementId, bool>) {
+        for node in nodes {
+            if node.expanded {
+                expanded.insert(node.id.clone(), true);
+            }
+            collect_expanded(&node.children, expanded);
+        }
+    }
+
+    let mut expanded = state_expanded.clone();
+    collect_expanded(nodes, &mut expanded);
+    expanded
+}
+
+/// Hashes a caller-supplied `revision` with the state [`visible_rows`] reads
+/// besides the nodes themselves.
+///
+/// Expansion entries are combined order-independently, since `HashMap` iteration
+/// order isn't stable across maps.
+fn revision_key(revision: u64, state_expanded: &HashMap<ElementId, bool>, query: &str) -> u64 {
+    let expanded = state_expanded.iter().fold(0u64, |acc, entry| {
+        let mut hasher = DefaultHasher::new();
+        entry.hash(&mut hasher);
+        acc.wrapping_add(hasher.finish())
+    });
+    let mut hasher = DefaultHasher::new();
+    "revision".hash(&mut hasher);
+    revision.hash(&mut hasher);
+    state_expanded.len().hash(&mut hasher);
+    expanded.hash(&mut hasher);
+    query.trim().hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Hashes everything [`visible_rows`] reads without a `filter_fn`.
+///
+/// Node contents are hashed rather than compared by identity, so nodes edited in
+/// place still invalidate the cache. Without a query, the children of collapsed
+/// nodes are hidden and only their count is hashed.
+fn rows_key(nodes: &[TreeNode], expanded_ids: &HashMap<ElementId, bool>, query: &str) -> u64 {
+    fn hash_nodes(
+        nodes: &[TreeNode],
+        expanded_ids: &HashMap<ElementId, bool>,
+        all: bool,
+        hasher: &mut DefaultHasher,
+    ) {
+        nodes.len().hash(hasher);
+        for node in nodes {
+            node.id.hash(hasher);
+            node.data.label.hash(hasher);
+            node.data.icon.hash(hasher);
+            node.data.disabled.hash(hasher);
+            node.expanded.hash(hasher);
+            node.selected.hash(hasher);
+            node.checked.hash(hasher);
+            node.has_children.hash(hasher);
+            node.loading.hash(hasher);
+
+            let expanded = expanded_ids.get(&node.id).copied().unwrap_or(node.expanded);
+            expanded.hash(hasher);
+            if all || expanded {
+                hash_nodes(&node.children, expanded_ids, all, hasher);
+            } else {
+                node.children.len().hash(hasher);
+            }
+        }
+    }
+
+    let query = query.trim();
+    let mut hasher = DefaultHasher::new();
+    query.hash(&mut hasher);
+    hash_nodes(nodes, expanded_ids, !query.is_empty(), &mut hasher);
+    hasher.finish()
+}
 
-                // Draggable is currently a Tree-level capability; keep it for future work.
-                // This field is retained for API stability.
-                let _ = draggable;
+/// Builds a row label, optionally tracking its width for `row_tooltip_on_overflow`.
+///
+/// When `highlight` is a filter query, its first match in the label is emphasized.
+/// Returns the label element and, when the label was truncated on the last layout,
+/// the full text to show in a tooltip. The width from the previous frame is used;
+/// hovering a row re-renders the tree, so the tooltip is in place before it shows.
+/// Labels are only shaped when `measure` is set, and then once per text.
+fn row_label(
+    node_id: &ElementId,
+    text: SharedString,
+    highlight: Option<&str>,
+    measure: bool,
+    window: &mut Window,
+    cx: &mut gpui::App,
+) -> (AnyElement, Option<SharedString>) {
+    let label = match highlight.and_then(|query| match_range(&text, query.trim())) {
+        Some(range) => highlighted_label(text.clone(), range, cx),
+        None => super::label(text.clone()).ellipsis(true).into_any_element(),
+    };
+    if !measure {
+        return (label, None);
+    }
+
+    let bounds_state =
+        window.use_keyed_state((node_id.clone(), "ui:tree:label-bounds"), cx, |_, _| {
+            Bounds::<Pixels>::default()
+        });
+    let available_width = bounds_state.read(cx).size.width;
+    let text_width = if available_width > Pixels::ZERO {
+        label_width(node_id, &text, window, cx)
+    } else {
+        Pixels::ZERO
+    };
+
+    let element = BoundsTrackerElement {
+        bounds_state,
+        inner: div().w_full().min_w_0().child(label).into_any_element(),
+    };
+    (
+        element.into_any_element(),
+        overflow_tooltip(text, text_width, available_width),
+    )
+}
 
-                super::virtual_row(node_id.clone()).child(row)
-            }))
+/// The shaped width of a row's label text, reshaped only when the text or the
+/// font size changed since the last render.
+fn label_width(
+    node_id: &ElementId,
+    text: &SharedString,
+    window: &mut Window,
+    cx: &mut App,
+) -> Pixels {
+    let style = window.text_style();
+    let font_size = style.font_size.to_pixels(window.rem_size());
+    let measured = window.use_keyed_state((node_id.clone(), "ui:tree:label-width"), cx, |_, _| {
+        None::<(SharedString, Pixels, Pixels)>
+    });
+    if let Some((_, _, width)) = measured
+        .read(cx)
+        .as_ref()
+        .filter(|(measured, size, _)| measured == text && *size == font_size)
+    {
+        return *width;
     }
+
+    let run = style.to_run(text.len());
+    let width = window
+        .text_system()
+        .shape_line(text.clone(), font_size, &[run], None)
+        .width;
+    measured.update(cx, |measured, _| {
+        *measured = Some((text.clone(), font_size, width))
+    });
+    width
+}
+
+/// A truncating label with `range` emphasized.
+fn highlighted_label(text: SharedString, range: Range<usize>, cx: &App) -> AnyElement {
+    let theme = cx.theme();
+    let emphasis = HighlightStyle {
+        color: Some(theme.action.primary.bg),
+        font_weight: Some(FontWeight::BOLD),
+        ..Default::default()
+    };
+    div()
+        .truncate()
+        .text_color(theme.content.primary)
+        .child(StyledText::new(text).with_highlights([(range, emphasis)]))
+        .into_any_element()
+}
+
+/// Returns the tooltip text for a label that does not fit its available width.
+///
+/// An unmeasured (zero) width is treated as "fits" so rows don't flash a tooltip
+/// before their first layout.
+fn overflow_tooltip(
+    text: SharedString,
+    text_width: Pixels,
+    available_width: Pixels,
+) -> Option<SharedString> {
+    (available_width > Pixels::ZERO && text_width > available_width + px(0.5)).then_some(text)
 }
 
 /// Builder function for creating tree nodes with ArcTreeNode data.
 pub fn tree_node_data(label: impl Into<String>) -> ArcTreeNode {
     ArcTreeNode::new(label)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::super::tree_data::TreeNodeBuilder;
+    use super::*;
+
+    fn folders() -> (SharedString, SharedString) {
+        ("icons/folder-open.svg".into(), "icons/folder.svg".into())
+    }
+
+    #[test]
+    fn folder_icon_follows_expansion_state() {
+        let icons = folders();
+
+        let open = resolve_node_icon(None, true, true, Some(&icons));
+        let closed = resolve_node_icon(None, true, false, Some(&icons));
+
+        assert_eq!(
+            open,
+            Some(SharedString::new_static("icons/folder-open.svg"))
+        );
+        assert_eq!(closed, Some(SharedString::new_static("icons/folder.svg")));
+    }
+
+    #[test]
+    fn node_icon_overrides_folder_icons() {
+        let icons = folders();
+
+        let icon = resolve_node_icon(Some("icons/repo.svg"), true, true, Some(&icons));
+        assert_eq!(icon, Some(SharedString::new_static("icons/repo.svg")));
+    }
+
+    #[test]
+    fn truncated_label_gets_tooltip() {
+        let text = SharedString::new_static("a-very-long-file-name.txt");
+
+        assert_eq!(
+            overflow_tooltip(text.clone(), px(180.), px(120.)),
+            Some(text.clone())
+        );
+        assert_eq!(overflow_tooltip(text.clone(), px(80.), px(120.)), None);
+    }
+
+    #[test]
+    fn unmeasured_label_has_no_tooltip() {
+        let text = SharedString::new_static("name.txt");
+
+        assert_eq!(overflow_tooltip(text, px(80.), Pixels::ZERO), None);
+    }
+
+    #[test]
+    fn leaves_without_icon_have_none() {
+        let icons = folders();
+
+        assert_eq!(resolve_node_icon(None, false, false, Some(&icons)), None);
+        assert_eq!(resolve_node_icon(None, true, true, None), None);
+    }
+
+    #[test]
+    fn scroll_targets_apply_once_until_they_change() {
+        let mut applied = None;
+        let target = || Some(ElementId::from("b"));
+
+        assert_eq!(replace_scroll_target(&mut applied, target()), target());
+        assert_eq!(replace_scroll_target(&mut applied, target()), None);
+        assert_eq!(replace_scroll_target(&mut applied, None), None);
+        assert_eq!(replace_scroll_target(&mut applied, target()), target());
+    }
+
+    #[test]
+    fn rows_key_tracks_visible_inputs() {
+        let node =
+            |label: &'static str| TreeNodeBuilder::new(label, ArcTreeNode::new(label)).build();
+        let mut nodes = vec![node("root")];
+        nodes[0].children = vec![node("child")];
+        nodes[0].has_children = true;
+        let mut expanded = HashMap::new();
+        let key = rows_key(&nodes, &expanded, "");
+
+        assert_eq!(rows_key(&nodes, &expanded, ""), key);
+        assert_ne!(rows_key(&nodes, &expanded, "child"), key);
+
+        // Children of collapsed nodes are hidden, except from a filter query.
+        nodes[0].children[0].data.label = "renamed".into();
+        assert_eq!(rows_key(&nodes, &expanded, ""), key);
+
+        expanded.insert(nodes[0].id.clone(), true);
+        let expanded_key = rows_key(&nodes, &expanded, "");
+        assert_ne!(expanded_key, key);
+
+        nodes[0].children[0].data.label = "child".into();
+        assert_ne!(rows_key(&nodes, &expanded, ""), expanded_key);
+    }
+
+    #[test]
+    fn revision_key_stands_in_for_the_nodes() {
+        let root = ElementId::from("root");
+        let mut expanded = HashMap::new();
+        let key = revision_key(1, &expanded, "");
+
+        assert_eq!(revision_key(1, &expanded, " "), key);
+        assert_ne!(revision_key(2, &expanded, ""), key);
+        assert_ne!(revision_key(1, &expanded, "child"), key);
+
+        expanded.insert(root.clone(), true);
+        let expanded_key = revision_key(1, &expanded, "");
+        assert_ne!(expanded_key, key);
+        expanded.insert(root, false);
+        assert_ne!(revision_key(1, &expanded, ""), expanded_key);
+    }
+}
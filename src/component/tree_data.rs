@@ -5,8 +5,10 @@
 //!
 //! See the [Tree component documentation](https://github.com/MeowLynxSea/yororen-ui/wiki/Component-Tree) for usage examples.
 
-use gpui::ElementId;
+use gpui::{ElementId, Modifiers};
+use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::ops::Range;
 
 /// Selection mode for tree nodes.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -55,6 +57,8 @@ pub struct TreeNode<T: TreeNodeData = ArcTreeNode> {
     pub depth: usize,
     /// Whether this node has children.
     pub has_children: bool,
+    /// Whether the host is still loading this node's children.
+    pub loading: bool,
 }
 
 /// Type alias for a tree node with default [`ArcTreeNode`] data.
@@ -121,7 +125,7 @@ impl TreeNodeData for ArcTreeNode {
 }
 
 /// Checked state for tree nodes with checkboxes.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum TreeCheckedState {
     /// Node is unchecked.
     #[default]
@@ -149,6 +153,7 @@ impl<T: TreeNodeData> TreeNodeBuilder<T> {
                 checked: TreeCheckedState::Unchecked,
                 depth: 0,
                 has_children: false,
+                loading: false,
             },
         }
     }
@@ -168,6 +173,19 @@ impl<T: TreeNodeData> TreeNodeBuilder<T> {
         self
     }
 
+    /// Mark the node as loading its children, showing a loading row while it
+    /// is expanded.
+    pub fn loading(mut self, loading: bool) -> Self {
+        self.node.loading = loading;
+        self
+    }
+
+    /// Mark the node as having children that are not loaded yet, for lazy trees.
+    pub fn lazy_children(mut self) -> Self {
+        self.node.has_children = true;
+        self
+    }
+
     pub fn child(mut self, child: TreeNode<T>) -> Self {
         self.node.has_children = true;
         self.node.children.push(child);
@@ -185,6 +203,15 @@ pub struct TreeState {
     pub expanded_nodes: HashMap<ElementId, bool>,
     pub selected_nodes: HashMap<ElementId, bool>,
     pub checked_nodes: HashMap<ElementId, TreeCheckedState>,
+    /// The row keyboard navigation starts from.
+    pub focused: Option<ElementId>,
+    /// The row Shift+click ranges extend from.
+    ///
+    /// Kept as an id and resolved against the visible rows on each click, so it
+    /// follows the row when expanding, collapsing or filtering shifts its index.
+    pub anchor: Option<ElementId>,
+    /// Nodes whose children were requested and haven't arrived yet.
+    pub loading_nodes: HashMap<ElementId, bool>,
 }
 
 impl TreeState {
@@ -225,12 +252,224 @@ impl TreeState {
             .unwrap_or(TreeCheckedState::Unchecked)
     }
 
+    /// Select a node the way `mode` selects on click.
+    ///
+    /// `Single` replaces the selection, `Multiple` toggles the node, and `None`
+    /// leaves the selection alone.
+    pub fn select(&mut self, id: &ElementId, mode: SelectionMode) {
+        match mode {
+            SelectionMode::Single => {
+                self.clear_selection();
+                self.set_selected(id, true);
+            }
+            SelectionMode::Multiple => {
+                let selected = self.is_selected(id);
+                self.set_selected(id, !selected);
+            }
+            SelectionMode::None => {}
+        }
+    }
+
+    /// Select a clicked row, honoring Shift and Ctrl/Cmd in `Multiple` mode.
+    ///
+    /// Shift selects the rows between the anchor and `id` in `rows` order,
+    /// replacing the selection unless Ctrl/Cmd is also held. Other clicks select
+    /// as [`select`](Self::select) does and move the anchor to `id`. A Shift+click
+    /// whose anchor is no longer visible selects just `id`.
+    pub fn select_row<T: TreeNodeData>(
+        &mut self,
+        rows: &[FlatTreeNode<T>],
+        id: &ElementId,
+        mode: SelectionMode,
+        modifiers: Modifiers,
+    ) {
+        if mode != SelectionMode::Multiple || !modifiers.shift {
+            self.select(id, mode);
+            self.anchor = Some(id.clone());
+            return;
+        }
+
+        let position = |id: &ElementId| rows.iter().position(|row| &row.id == id);
+        let range = self
+            .anchor
+            .as_ref()
+            .and_then(position)
+            .zip(position(id))
+            .map(|(anchor, target)| anchor.min(target)..=anchor.max(target));
+
+        if !modifiers.secondary() {
+            self.clear_selection();
+        }
+        let Some(range) = range else {
+            self.set_selected(id, true);
+            self.anchor = Some(id.clone());
+            return;
+        };
+        for row in &rows[range] {
+            if !row.data.disabled() {
+                self.set_selected(&row.id, true);
+            }
+        }
+    }
+
+    /// Set the focused row.
+    pub fn set_focused(&mut self, id: Option<ElementId>) {
+        self.focused = id;
+    }
+
+    /// Get the focused row.
+    pub fn focused(&self) -> Option<&ElementId> {
+        self.focused.as_ref()
+    }
+
+    /// Get the checked state of a node, falling back to its own `checked` field
+    /// when the state has no entry for it.
+    pub fn checked_or(&self, id: &ElementId, fallback: TreeCheckedState) -> TreeCheckedState {
+        self.checked_nodes.get(id).copied().unwrap_or(fallback)
+    }
+
+    /// Toggle a node's checkbox and cascade the change; see [`Self::set_checked_cascade`].
+    ///
+    /// A checked node becomes unchecked; an unchecked or indeterminate one becomes checked.
+    pub fn toggle_checked<T: TreeNodeData>(
+        &mut self,
+        nodes: &[TreeNode<T>],
+        id: &ElementId,
+    ) -> (TreeCheckedState, Vec<ElementId>) {
+        let Some(path) = node_path(nodes, id) else {
+            return (TreeCheckedState::Unchecked, Vec::new());
+        };
+        let node = path[path.len() - 1];
+        let checked = match self.checked_or(&node.id, node.checked) {
+            TreeCheckedState::Checked => TreeCheckedState::Unchecked,
+            _ => TreeCheckedState::Checked,
+        };
+        (checked, self.cascade(&path, checked))
+    }
+
+    /// Set a node's checked state, giving every descendant the same state and
+    /// recomputing each ancestor from its children.
+    ///
+    /// An ancestor is `Checked` or `Unchecked` when all its children agree and
+    /// `Indeterminate` otherwise. Returns the ids whose state changed. Past the
+    /// search for `id`, only its subtree and ancestor chain are visited.
+    pub fn set_checked_cascade<T: TreeNodeData>(
+        &mut self,
+        nodes: &[TreeNode<T>],
+        id: &ElementId,
+        checked: TreeCheckedState,
+    ) -> Vec<ElementId> {
+        match node_path(nodes, id) {
+            Some(path) => self.cascade(&path, checked),
+            None => Vec::new(),
+        }
+    }
+
+    fn cascade<T: TreeNodeData>(
+        &mut self,
+        path: &[&TreeNode<T>],
+        checked: TreeCheckedState,
+    ) -> Vec<ElementId> {
+        let Some((node, ancestors)) = path.split_last() else {
+            return Vec::new();
+        };
+
+        let mut affected = Vec::new();
+        self.check_subtree(node, checked, &mut affected);
+
+        for ancestor in ancestors.iter().rev() {
+            let derived = self.derived_checked(ancestor);
+            if self.checked_or(&ancestor.id, ancestor.checked) == derived {
+                // Ancestors further up only depend on this one.
+                break;
+            }
+            self.set_checked(&ancestor.id, derived);
+            affected.push(ancestor.id.clone());
+        }
+        affected
+    }
+
+    fn check_subtree<T: TreeNodeData>(
+        &mut self,
+        node: &TreeNode<T>,
+        checked: TreeCheckedState,
+        affected: &mut Vec<ElementId>,
+    ) {
+        if self.checked_or(&node.id, node.checked) != checked {
+            self.set_checked(&node.id, checked);
+            affected.push(node.id.clone());
+        }
+        for child in &node.children {
+            self.check_subtree(child, checked, affected);
+        }
+    }
+
+    /// The state a parent shows given its children's states.
+    fn derived_checked<T: TreeNodeData>(&self, node: &TreeNode<T>) -> TreeCheckedState {
+        let mut states = node
+            .children
+            .iter()
+            .map(|child| self.checked_or(&child.id, child.checked));
+        let Some(first) = states.next() else {
+            return self.checked_or(&node.id, node.checked);
+        };
+        if first != TreeCheckedState::Indeterminate && states.all(|state| state == first) {
+            first
+        } else {
+            TreeCheckedState::Indeterminate
+        }
+    }
+
+    /// Set whether a node is waiting for its children.
+    pub fn set_loading(&mut self, id: &ElementId, loading: bool) {
+        if loading {
+            self.loading_nodes.insert(id.clone(), true);
+        } else {
+            self.loading_nodes.remove(id);
+        }
+    }
+
+    /// Check if a node is waiting for its children.
+    pub fn is_loading(&self, id: &ElementId) -> bool {
+        self.loading_nodes.get(id).copied().unwrap_or(false)
+    }
+
+    /// Clear the loading flag of rows whose children have arrived, or that the
+    /// host has marked as having none.
+    ///
+    /// Returns whether any flag was cleared.
+    pub fn finish_loading<T: TreeNodeData>(&mut self, rows: &[FlatTreeNode<T>]) -> bool {
+        let mut changed = false;
+        for row in rows {
+            if (row.child_count > 0 || !row.has_children)
+                && self.loading_nodes.remove(&row.id).is_some()
+            {
+                changed = true;
+            }
+        }
+        changed
+    }
+
     /// Toggle the expanded state of a node.
     pub fn toggle_expanded(&mut self, id: &ElementId) {
         let current = self.is_expanded(id);
         self.set_expanded(id, !current);
     }
 
+    /// Expand every ancestor of a node so it becomes visible.
+    ///
+    /// The node itself keeps its expansion state. Returns `false` if `id` isn't
+    /// in `nodes`.
+    pub fn reveal<T: TreeNodeData>(&mut self, nodes: &[TreeNode<T>], id: &ElementId) -> bool {
+        let Some(path) = node_path(nodes, id) else {
+            return false;
+        };
+        for ancestor in &path[..path.len() - 1] {
+            self.set_expanded(&ancestor.id, true);
+        }
+        true
+    }
+
     /// Clear all selected nodes.
     pub fn clear_selection(&mut self) {
         self.selected_nodes.clear();
@@ -315,6 +554,10 @@ pub struct FlatTreeNode<T: TreeNodeData = ArcTreeNode> {
     pub checked: TreeCheckedState,
     /// Whether this node has children.
     pub has_children: bool,
+    /// Number of children currently present; zero for unloaded lazy nodes.
+    pub child_count: usize,
+    /// Whether the host is still loading this node's children.
+    pub loading: bool,
     /// Index in the flattened list.
     pub index: usize,
 }
@@ -357,6 +600,8 @@ fn flatten_tree_recursive<T: TreeNodeData>(
                 selected: node.selected,
                 checked: node.checked,
                 has_children: node.has_children,
+                child_count: node.children.len(),
+                loading: node.loading,
                 index,
             });
             index += 1;
@@ -371,6 +616,8 @@ fn flatten_tree_recursive<T: TreeNodeData>(
             selected: node.selected,
             checked: node.checked,
             has_children: node.has_children,
+            child_count: node.children.len(),
+            loading: node.loading,
             index,
         });
         index += 1;
@@ -390,3 +637,633 @@ fn flatten_tree_recursive<T: TreeNodeData>(
 
     index
 }
+
+/// A keyboard step through the visible rows of a tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TreeNavigation {
+    /// Move to the row above.
+    Previous,
+    /// Move to the row below.
+    Next,
+    /// Move to the first row.
+    First,
+    /// Move to the last row.
+    Last,
+    /// Expand a collapsed node, or move to the first child of an expanded one.
+    Expand,
+    /// Collapse an expanded node, or move to the parent of any other.
+    Collapse,
+}
+
+/// What a [`TreeNavigation`] step does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TreeNavigationTarget {
+    /// Move focus to the row.
+    Focus(ElementId),
+    /// Expand or collapse the node, keeping focus on it.
+    SetExpanded(ElementId, bool),
+}
+
+/// Resolves a keyboard step against the flattened rows.
+///
+/// Without a focused row (or when it has scrolled out of the rows by a collapse),
+/// `Previous` and `Last` land on the last row and every other step on the first.
+/// Returns `None` when the step has nowhere to go.
+pub fn navigate<T: TreeNodeData>(
+    rows: &[FlatTreeNode<T>],
+    focused: Option<&ElementId>,
+    step: TreeNavigation,
+) -> Option<TreeNavigationTarget> {
+    let focus = |ix: usize| {
+        rows.get(ix)
+            .map(|row| TreeNavigationTarget::Focus(row.id.clone()))
+    };
+    let last = rows.len().checked_sub(1)?;
+    let Some(ix) = focused.and_then(|id| rows.iter().position(|row| &row.id == id)) else {
+        return match step {
+            TreeNavigation::Previous | TreeNavigation::Last => focus(last),
+            _ => focus(0),
+        };
+    };
+
+    let row = &rows[ix];
+    let can_toggle = row.has_children && !row.data.disabled();
+    match step {
+        TreeNavigation::Previous => focus(ix.saturating_sub(1)),
+        TreeNavigation::Next => focus((ix + 1).min(last)),
+        TreeNavigation::First => focus(0),
+        TreeNavigation::Last => focus(last),
+        TreeNavigation::Expand if can_toggle && !row.expanded => {
+            Some(TreeNavigationTarget::SetExpanded(row.id.clone(), true))
+        }
+        TreeNavigation::Expand => rows
+            .get(ix + 1)
+            .filter(|next| next.depth > row.depth)
+            .and_then(|_| focus(ix + 1)),
+        TreeNavigation::Collapse if can_toggle && row.expanded => {
+            Some(TreeNavigationTarget::SetExpanded(row.id.clone(), false))
+        }
+        TreeNavigation::Collapse => rows[..ix]
+            .iter()
+            .rposition(|parent| parent.depth < row.depth)
+            .and_then(focus),
+    }
+}
+
+/// The nodes from a root down to `id`, inclusive.
+fn node_path<'a, T: TreeNodeData>(
+    nodes: &'a [TreeNode<T>],
+    id: &ElementId,
+) -> Option<Vec<&'a TreeNode<T>>> {
+    fn walk<'a, T: TreeNodeData>(
+        nodes: &'a [TreeNode<T>],
+        id: &ElementId,
+        path: &mut Vec<&'a TreeNode<T>>,
+    ) -> bool {
+        for node in nodes {
+            path.push(node);
+            if &node.id == id || walk(&node.children, id, path) {
+                return true;
+            }
+            path.pop();
+        }
+        false
+    }
+
+    let mut path = Vec::new();
+    walk(nodes, id, &mut path).then_some(path)
+}
+
+/// Flattens the nodes that satisfy `matches`, together with their ancestors.
+///
+/// Ancestors of a match are shown expanded regardless of their expansion state,
+/// and descendants that don't match are hidden. The source nodes are untouched,
+/// so clearing the filter restores the regular view.
+pub fn flatten_filtered<T: TreeNodeData>(
+    nodes: &[TreeNode<T>],
+    matches: &dyn Fn(&TreeNode<T>) -> bool,
+) -> Vec<FlatTreeNode<T>> {
+    fn walk<T: TreeNodeData>(
+        nodes: &[TreeNode<T>],
+        depth: usize,
+        matches: &dyn Fn(&TreeNode<T>) -> bool,
+        result: &mut Vec<FlatTreeNode<T>>,
+    ) {
+        for node in nodes {
+            let at = result.len();
+            result.push(FlatTreeNode {
+                id: node.id.clone(),
+                data: node.data.clone(),
+                depth,
+                expanded: false,
+                selected: node.selected,
+                checked: node.checked,
+                has_children: node.has_children,
+                child_count: node.children.len(),
+                loading: node.loading,
+                index: at,
+            });
+            walk(&node.children, depth + 1, matches, result);
+
+            if result.len() > at + 1 {
+                result[at].expanded = true;
+            } else if !matches(node) {
+                result.truncate(at);
+            }
+        }
+    }
+
+    let mut result = Vec::new();
+    walk(nodes, 0, matches, &mut result);
+    for (index, row) in result.iter_mut().enumerate() {
+        row.index = index;
+    }
+    result
+}
+
+/// Byte range of the first case-insensitive occurrence of `query` in `label`.
+///
+/// Returns `None` for an empty query.
+pub fn match_range(label: &str, query: &str) -> Option<Range<usize>> {
+    let query: Vec<char> = query.chars().flat_map(char::to_lowercase).collect();
+    if query.is_empty() {
+        return None;
+    }
+
+    // Lowercased characters alongside the byte span of the source character.
+    let lowered: Vec<(char, Range<usize>)> = label
+        .char_indices()
+        .flat_map(|(at, c)| {
+            c.to_lowercase()
+                .map(move |lower| (lower, at..at + c.len_utf8()))
+        })
+        .collect();
+
+    lowered
+        .windows(query.len())
+        .find(|window| window.iter().map(|(c, _)| c).eq(query.iter()))
+        .map(|window| window[0].1.start..window[window.len() - 1].1.end)
+}
+
+/// How the indent guide at one ancestor level of a row is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndentGuide {
+    /// A line through the full height of the row.
+    Line,
+    /// The last child's elbow: a line down to the middle of the row, turning
+    /// toward it.
+    Elbow,
+    /// No line; the ancestor at this level has no children below this row.
+    Blank,
+}
+
+/// Computes the indent guides of each row, one per ancestor level, with elbows
+/// on last children.
+///
+/// A level's line continues through a row when a later row sits at the next
+/// depth before anything shallower. This takes a single backward pass over `rows`.
+pub fn indent_guides<T: TreeNodeData>(rows: &[FlatTreeNode<T>]) -> Vec<Vec<IndentGuide>> {
+    // `seen[depth]`: a later row at `depth` is reachable without crossing a
+    // shallower row.
+    let mut seen: Vec<bool> = Vec::new();
+    let mut guides = vec![Vec::new(); rows.len()];
+
+    for (ix, row) in rows.iter().enumerate().rev() {
+        let depth = row.depth;
+        seen.resize(seen.len().max(depth + 1), false);
+        guides[ix] = (0..depth)
+            .map(|level| match (seen[level + 1], level + 1 == depth) {
+                (true, _) => IndentGuide::Line,
+                (false, true) => IndentGuide::Elbow,
+                (false, false) => IndentGuide::Blank,
+            })
+            .collect();
+        seen[depth] = true;
+        seen.truncate(depth + 1);
+    }
+    guides
+}
+
+/// Whether expanding `row` should ask the host for its children: it has
+/// children that aren't loaded yet, or `reload` asks for a fresh load each time.
+pub fn wants_children<T: TreeNodeData>(row: &FlatTreeNode<T>, reload: bool) -> bool {
+    row.has_children && (row.child_count == 0 || reload)
+}
+
+/// Whether `id` is `ancestor` itself or sits anywhere below it in `nodes`.
+///
+/// Used to reject drops that would move a node into its own subtree.
+pub fn is_same_or_descendant<T: TreeNodeData>(
+    nodes: &[TreeNode<T>],
+    ancestor: &ElementId,
+    id: &ElementId,
+) -> bool {
+    fn find<'a, T: TreeNodeData>(
+        nodes: &'a [TreeNode<T>],
+        id: &ElementId,
+    ) -> Option<&'a TreeNode<T>> {
+        nodes.iter().find_map(|node| {
+            (&node.id == id)
+                .then_some(node)
+                .or_else(|| find(&node.children, id))
+        })
+    }
+
+    ancestor == id || find(nodes, ancestor).is_some_and(|node| find(&node.children, id).is_some())
+}
+
+/// Orders nodes by label, ignoring case.
+///
+/// Labels that differ only in case fall back to a case-sensitive comparison so
+/// the order stays total.
+pub fn by_label<T: TreeNodeData>(a: &TreeNode<T>, b: &TreeNode<T>) -> Ordering {
+    caseless_cmp(a.data.label(), b.data.label())
+}
+
+/// Orders nodes with children before leaves, then by label ignoring case.
+pub fn folders_first<T: TreeNodeData>(a: &TreeNode<T>, b: &TreeNode<T>) -> Ordering {
+    b.has_children
+        .cmp(&a.has_children)
+        .then_with(|| by_label(a, b))
+}
+
+/// Orders nodes by label, comparing digit runs numerically ("file2" < "file10").
+pub fn natural<T: TreeNodeData>(a: &TreeNode<T>, b: &TreeNode<T>) -> Ordering {
+    natural_cmp(a.data.label(), b.data.label())
+}
+
+/// Sorts `nodes` and all their descendants with `compare`.
+///
+/// ```rust,ignore
+/// sort_tree(&mut nodes, &folders_first);
+/// ```
+pub fn sort_tree<T: TreeNodeData>(
+    nodes: &mut [TreeNode<T>],
+    compare: &impl Fn(&TreeNode<T>, &TreeNode<T>) -> Ordering,
+) {
+    nodes.sort_by(compare);
+    for node in nodes {
+        sort_tree(&mut node.children, compare);
+    }
+}
+
+fn caseless_cmp(a: &str, b: &str) -> Ordering {
+    a.chars()
+        .flat_map(char::to_lowercase)
+        .cmp(b.chars().flat_map(char::to_lowercase))
+        .then_with(|| a.cmp(b))
+}
+
+/// Case-insensitive string comparison that orders digit runs by numeric value.
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let mut left = a.chars().peekable();
+    let mut right = b.chars().peekable();
+
+    loop {
+        let ordering = match (left.peek().copied(), right.peek().copied()) {
+            (None, None) => return caseless_cmp(a, b),
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(l), Some(r)) if l.is_ascii_digit() && r.is_ascii_digit() => {
+                let l = take_digits(&mut left);
+                let r = take_digits(&mut right);
+                let (l, r) = (l.trim_start_matches('0'), r.trim_start_matches('0'));
+                l.len().cmp(&r.len()).then_with(|| l.cmp(r))
+            }
+            (Some(l), Some(r)) => {
+                left.next();
+                right.next();
+                l.to_lowercase().cmp(r.to_lowercase())
+            }
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
+fn take_digits(chars: &mut std::iter::Peekable<std::str::Chars<'_>>) -> String {
+    let mut digits = String::new();
+    while let Some(c) = chars.next_if(char::is_ascii_digit) {
+        digits.push(c);
+    }
+    digits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(label: &str, folder: bool) -> SimpleTreeNode {
+        let mut node = TreeNodeBuilder::new(label.to_string(), ArcTreeNode::new(label)).build();
+        node.has_children = folder;
+        node
+    }
+
+    fn labels(nodes: &[SimpleTreeNode]) -> Vec<&str> {
+        nodes.iter().map(|n| n.data.label()).collect()
+    }
+
+    fn id(label: &'static str) -> ElementId {
+        ElementId::from(label)
+    }
+
+    /// root
+    /// ├ docs
+    /// │ ├ a
+    /// │ └ b
+    /// └ c
+    /// other (an empty folder)
+    fn sample_tree() -> Vec<SimpleTreeNode> {
+        let mut docs = node("docs", true);
+        docs.children = vec![node("a", false), node("b", false)];
+        let mut root = node("root", true);
+        root.children = vec![docs, node("c", false)];
+        vec![root, node("other", true)]
+    }
+
+    #[test]
+    fn natural_orders_numbers_by_value() {
+        let mut nodes = vec![
+            node("file10", false),
+            node("File2", false),
+            node("file1", false),
+            node("file02", false),
+        ];
+        nodes.sort_by(natural);
+
+        assert_eq!(labels(&nodes), ["file1", "file02", "File2", "file10"]);
+        assert_eq!(natural_cmp("a9b", "a10a"), Ordering::Less);
+        assert_eq!(natural_cmp("v1.10", "v1.9"), Ordering::Greater);
+    }
+
+    #[test]
+    fn folders_first_groups_then_sorts_by_name() {
+        let mut nodes = vec![
+            node("zeta.txt", false),
+            node("src", true),
+            node("Alpha.txt", false),
+            node("Docs", true),
+        ];
+        nodes.sort_by(folders_first);
+
+        assert_eq!(labels(&nodes), ["Docs", "src", "Alpha.txt", "zeta.txt"]);
+    }
+
+    #[test]
+    fn descendants_are_found_at_any_depth() {
+        let nodes = sample_tree();
+
+        assert!(is_same_or_descendant(&nodes, &id("root"), &id("root")));
+        assert!(is_same_or_descendant(&nodes, &id("root"), &id("a")));
+        assert!(is_same_or_descendant(&nodes, &id("docs"), &id("a")));
+        assert!(!is_same_or_descendant(&nodes, &id("docs"), &id("root")));
+        assert!(!is_same_or_descendant(&nodes, &id("root"), &id("other")));
+    }
+
+    #[test]
+    fn arrows_walk_visible_rows() {
+        let nodes = sample_tree();
+        let focus = |s: &'static str| Some(TreeNavigationTarget::Focus(id(s)));
+
+        let mut expanded = HashMap::new();
+        expanded.insert(id("root"), true);
+        let rows = flatten_tree(&nodes, &expanded, false);
+        let step = |from: Option<&'static str>, step| navigate(&rows, from.map(id).as_ref(), step);
+
+        assert_eq!(step(None, TreeNavigation::Next), focus("root"));
+        assert_eq!(step(None, TreeNavigation::Previous), focus("other"));
+        assert_eq!(step(Some("root"), TreeNavigation::Next), focus("docs"));
+        assert_eq!(step(Some("root"), TreeNavigation::Previous), focus("root"));
+        assert_eq!(step(Some("docs"), TreeNavigation::Last), focus("other"));
+        assert_eq!(step(Some("other"), TreeNavigation::Next), focus("other"));
+
+        // Right expands a collapsed node, then descends into it.
+        assert_eq!(
+            step(Some("docs"), TreeNavigation::Expand),
+            Some(TreeNavigationTarget::SetExpanded(id("docs"), true))
+        );
+        assert_eq!(step(Some("root"), TreeNavigation::Expand), focus("docs"));
+        assert_eq!(step(Some("c"), TreeNavigation::Expand), None);
+
+        // Left collapses an expanded node, otherwise climbs to the parent.
+        assert_eq!(
+            step(Some("root"), TreeNavigation::Collapse),
+            Some(TreeNavigationTarget::SetExpanded(id("root"), false))
+        );
+        assert_eq!(step(Some("c"), TreeNavigation::Collapse), focus("root"));
+        assert_eq!(step(Some("other"), TreeNavigation::Collapse), None);
+    }
+
+    #[test]
+    fn checking_cascades_down_and_up() {
+        let nodes = sample_tree();
+        let mut state = TreeState::new();
+
+        let (checked, affected) = state.toggle_checked(&nodes, &id("a"));
+        assert_eq!(checked, TreeCheckedState::Checked);
+        assert_eq!(affected, [id("a"), id("docs"), id("root")]);
+        assert_eq!(
+            state.get_checked(&id("docs")),
+            TreeCheckedState::Indeterminate
+        );
+        assert_eq!(
+            state.get_checked(&id("root")),
+            TreeCheckedState::Indeterminate
+        );
+
+        // Checking the last unchecked sibling completes the parent; the root
+        // stays indeterminate, so the walk stops there.
+        let (_, affected) = state.toggle_checked(&nodes, &id("b"));
+        assert_eq!(affected, [id("b"), id("docs")]);
+        assert_eq!(state.get_checked(&id("docs")), TreeCheckedState::Checked);
+
+        // Checking a partial parent checks its whole subtree.
+        let (checked, affected) = state.toggle_checked(&nodes, &id("root"));
+        assert_eq!(checked, TreeCheckedState::Checked);
+        assert_eq!(affected, [id("root"), id("c")]);
+
+        let affected = state.set_checked_cascade(&nodes, &id("docs"), TreeCheckedState::Unchecked);
+        assert_eq!(affected, [id("docs"), id("a"), id("b"), id("root")]);
+        assert_eq!(
+            state.get_checked(&id("root")),
+            TreeCheckedState::Indeterminate
+        );
+        assert!(state.toggle_checked(&nodes, &id("missing")).1.is_empty());
+    }
+
+    #[test]
+    fn lazy_nodes_load_until_children_arrive() {
+        let lazy = TreeNodeBuilder::new("lazy", ArcTreeNode::new("lazy"))
+            .lazy_children()
+            .build();
+        let mut loaded = node("loaded", true);
+        loaded.children = vec![node("child", false)];
+        let nodes = vec![lazy, loaded];
+        let rows = flatten_tree(&nodes, &HashMap::new(), false);
+
+        assert!(wants_children(&rows[0], false));
+        assert!(!wants_children(&rows[1], false));
+        assert!(wants_children(&rows[1], true));
+
+        let mut state = TreeState::new();
+        state.set_loading(&rows[0].id, true);
+        state.set_loading(&rows[1].id, true);
+        assert!(state.finish_loading(&rows));
+        assert!(state.is_loading(&rows[0].id));
+        assert!(!state.is_loading(&rows[1].id));
+
+        // An empty folder is reported by dropping `has_children`.
+        let mut empty = nodes[0].clone();
+        empty.has_children = false;
+        assert!(state.finish_loading(&flatten_tree(&[empty], &HashMap::new(), false)));
+        assert!(!state.finish_loading(&rows));
+        assert!(state.loading_nodes.is_empty());
+    }
+
+    #[test]
+    fn reveal_expands_ancestors_only() {
+        let nodes = sample_tree();
+        let mut state = TreeState::new();
+
+        assert!(state.reveal(&nodes, &id("b")));
+        assert!(state.is_expanded(&id("root")));
+        assert!(state.is_expanded(&id("docs")));
+        assert!(!state.is_expanded(&id("other")));
+
+        let rows = flatten_tree(&nodes, &state.expanded_nodes, false);
+        assert_eq!(rows.iter().position(|row| row.id == id("b")), Some(3));
+
+        assert!(state.reveal(&nodes, &id("other")));
+        assert!(!state.is_expanded(&id("other")));
+        assert!(!state.reveal(&nodes, &id("missing")));
+    }
+
+    #[test]
+    fn filtering_keeps_ancestors_of_matches() {
+        let mut src = node("src", true);
+        src.children = vec![node("main.rs", false), node("lib.rs", false)];
+        let mut docs = node("docs", true);
+        docs.children = vec![node("guide.md", false)];
+        let nodes = vec![src, docs, node("Cargo.toml", false)];
+
+        let rows = flatten_filtered(&nodes, &|node| {
+            match_range(node.data.label(), "MAIN").is_some()
+        });
+        let visible: Vec<_> = rows.iter().map(|row| row.data.label()).collect();
+        assert_eq!(visible, ["src", "main.rs"]);
+        assert!(rows[0].expanded);
+        assert_eq!((rows[1].depth, rows[1].index), (1, 1));
+
+        // A matching parent is shown without its non-matching children.
+        let rows = flatten_filtered(&nodes, &|node| node.data.label() == "docs");
+        assert_eq!(rows.len(), 1);
+        assert!(!rows[0].expanded);
+    }
+
+    #[test]
+    fn match_range_ignores_case() {
+        assert_eq!(match_range("Cargo.toml", "TOML"), Some(6..10));
+        assert_eq!(match_range("Straße", "SSE"), None);
+        assert_eq!(match_range("Über.rs", "ber"), Some(2..5));
+        assert_eq!(match_range("main.rs", ""), None);
+        assert_eq!(match_range("main.rs", "lib"), None);
+    }
+
+    #[test]
+    fn shift_click_selects_visible_range() {
+        let nodes = sample_tree();
+        let selected = |state: &TreeState| {
+            let mut ids: Vec<_> = state
+                .selected_nodes
+                .iter()
+                .filter(|(_, selected)| **selected)
+                .map(|(id, _)| id.to_string())
+                .collect();
+            ids.sort();
+            ids
+        };
+        let multiple = SelectionMode::Multiple;
+        let mut expanded = HashMap::new();
+        expanded.insert(id("root"), true);
+        let collapsed = flatten_tree(&nodes, &expanded, false);
+        let mut state = TreeState::new();
+
+        state.select_row(&collapsed, &id("c"), multiple, Modifiers::none());
+        state.select_row(&collapsed, &id("root"), multiple, Modifiers::shift());
+        assert_eq!(selected(&state), ["c", "docs", "root"]);
+
+        // Expanding shifts indices; the anchor still resolves to "c".
+        expanded.insert(id("docs"), true);
+        let rows = flatten_tree(&nodes, &expanded, false);
+        state.select_row(&rows, &id("b"), multiple, Modifiers::shift());
+        assert_eq!(selected(&state), ["b", "c"]);
+
+        // Ctrl/Cmd toggles one row and moves the anchor.
+        state.select_row(&rows, &id("other"), multiple, Modifiers::secondary_key());
+        assert_eq!(selected(&state), ["b", "c", "other"]);
+        let add_range = Modifiers {
+            shift: true,
+            ..Modifiers::secondary_key()
+        };
+        state.select_row(&rows, &id("root"), multiple, add_range);
+        assert_eq!(selected(&state), ["a", "b", "c", "docs", "other", "root"]);
+
+        // An anchor hidden by a collapse selects just the clicked row.
+        state.anchor = Some(id("a"));
+        state.select_row(&collapsed, &id("c"), multiple, Modifiers::shift());
+        assert_eq!(selected(&state), ["c"]);
+
+        state.select_row(
+            &rows,
+            &id("root"),
+            SelectionMode::Single,
+            Modifiers::shift(),
+        );
+        assert_eq!(selected(&state), ["root"]);
+    }
+
+    #[test]
+    fn indent_guides_end_at_last_children() {
+        // root
+        // ├ a
+        // │ └ a1
+        // └ b
+        //   └ b1
+        let mut a = node("a", true);
+        a.children = vec![node("a1", false)];
+        let mut b = node("b", true);
+        b.children = vec![node("b1", false)];
+        let mut root = node("root", true);
+        root.children = vec![a, b];
+        let expanded: HashMap<_, _> = ["root", "a", "b"]
+            .into_iter()
+            .map(|label| (id(label), true))
+            .collect();
+        let rows = flatten_tree(&[root], &expanded, false);
+
+        use IndentGuide::*;
+        assert_eq!(
+            indent_guides(&rows),
+            [
+                vec![],
+                vec![Line],
+                vec![Line, Elbow],
+                vec![Elbow],
+                vec![Blank, Elbow],
+            ]
+        );
+    }
+
+    #[test]
+    fn sort_tree_sorts_children() {
+        let mut root = node("root", true);
+        root.children = vec![node("b", false), node("a", false)];
+        let mut nodes = vec![root, node("A", true)];
+
+        sort_tree(&mut nodes, &by_label);
+
+        assert_eq!(labels(&nodes), ["A", "root"]);
+        assert_eq!(labels(&nodes[1].children), ["a", "b"]);
+    }
+}
@@ -43,6 +43,32 @@ pub struct DropTarget {
     pub y_offset: Pixels,
 }
 
+/// Where a drop at `y_offset` into a row of `height` lands.
+///
+/// The top and bottom quarters insert before and after the row; the middle drops
+/// into it. Rows that can't take children split at the midpoint instead.
+pub fn drop_position(y_offset: Pixels, height: Pixels, can_nest: bool) -> DropPosition {
+    let fraction = if height > Pixels::ZERO {
+        y_offset / height
+    } else {
+        0.5
+    };
+    if !can_nest {
+        return if fraction < 0.5 {
+            DropPosition::Before
+        } else {
+            DropPosition::After
+        };
+    }
+    if fraction < 0.25 {
+        DropPosition::Before
+    } else if fraction > 0.75 {
+        DropPosition::After
+    } else {
+        DropPosition::On
+    }
+}
+
 /// Callback type for tree drag drop handler.
 type TreeDropCallback = Box<dyn Fn(&ElementId, &ElementId, DropPosition)>;
 
@@ -180,3 +206,19 @@ impl RenderOnce for TreeDragPreview {
             .child(self.text)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drop_position_splits_rows_into_zones() {
+        let height = px(32.);
+        assert_eq!(drop_position(px(4.), height, true), DropPosition::Before);
+        assert_eq!(drop_position(px(16.), height, true), DropPosition::On);
+        assert_eq!(drop_position(px(30.), height, true), DropPosition::After);
+
+        assert_eq!(drop_position(px(12.), height, false), DropPosition::Before);
+        assert_eq!(drop_position(px(20.), height, false), DropPosition::After);
+    }
+}
@@ -3,16 +3,24 @@
 //! This component provides the visual representation of a tree node,
 //! including indentation, expand/collapse toggle, icons, and selection states.
 
+use std::sync::Arc;
+
 use gpui::{
-    AnyElement, Div, ElementId, Hsla, InteractiveElement, IntoElement, MouseButton, MouseDownEvent,
-    ParentElement, Pixels, RenderOnce, StatefulInteractiveElement, Styled, div,
-    prelude::FluentBuilder, px,
+    AnyElement, ClickEvent, Div, ElementId, Hsla, InteractiveElement, IntoElement, MouseButton,
+    MouseDownEvent, ParentElement, Pixels, RenderOnce, StatefulInteractiveElement, Styled, div,
+    prelude::FluentBuilder, px, relative,
 };
 
 use crate::component::{checkbox, disclosure};
-use crate::theme::ActiveTheme;
+use crate::theme::{ActiveTheme, Scale};
+
+use super::tree_data::{IndentGuide, TreeCheckedState};
 
-use super::tree_data::TreeCheckedState;
+/// Horizontal offset of the indent guide for `level`, centered under the
+/// disclosure of the ancestor at that level.
+fn guide_x(level: usize, indent: Pixels) -> Pixels {
+    indent * level as f32 + px(7.)
+}
 
 /// Creates a new tree item element.
 pub fn tree_item(id: impl Into<ElementId>) -> TreeItem {
@@ -22,6 +30,9 @@ pub fn tree_item(id: impl Into<ElementId>) -> TreeItem {
 /// Callback type for tree item context menu handler.
 type TreeItemContextMenuCallback = Box<dyn Fn(&MouseDownEvent, &mut gpui::Window, &mut gpui::App)>;
 
+/// Callback type for tree item checkbox handler.
+type TreeItemCheckCallback = Arc<dyn Fn(&ClickEvent, &mut gpui::Window, &mut gpui::App)>;
+
 /// A row in a tree view, representing a single node.
 #[derive(IntoElement)]
 pub struct TreeItem {
@@ -31,6 +42,7 @@ pub struct TreeItem {
     expanded: bool,
     has_children: bool,
     selected: bool,
+    focused: bool,
     disabled: bool,
     checked: TreeCheckedState,
     show_checkbox: bool,
@@ -38,10 +50,12 @@ pub struct TreeItem {
     label_element: Option<AnyElement>,
     secondary: Option<AnyElement>,
     trailing: Option<AnyElement>,
-    indent: Pixels,
+    indent: Option<Pixels>,
+    indent_guides: Vec<IndentGuide>,
     hover_bg: Option<Hsla>,
     selected_bg: Option<Hsla>,
     on_context_menu: Option<TreeItemContextMenuCallback>,
+    on_check: Option<TreeItemCheckCallback>,
 }
 
 impl Default for TreeItem {
@@ -59,6 +73,7 @@ impl TreeItem {
             expanded: false,
             has_children: false,
             selected: false,
+            focused: false,
             disabled: false,
             checked: TreeCheckedState::Unchecked,
             show_checkbox: false,
@@ -66,10 +81,12 @@ impl TreeItem {
             label_element: None,
             secondary: None,
             trailing: None,
-            indent: px(20.),
+            indent: None,
+            indent_guides: Vec::new(),
             hover_bg: None,
             selected_bg: None,
             on_context_menu: None,
+            on_check: None,
         }
     }
 
@@ -102,6 +119,12 @@ impl TreeItem {
         self
     }
 
+    /// Mark this row as the keyboard focus, drawing a focus ring around it.
+    pub fn focused(mut self, focused: bool) -> Self {
+        self.focused = focused;
+        self
+    }
+
     pub fn disabled(mut self, disabled: bool) -> Self {
         self.disabled = disabled;
         self
@@ -137,8 +160,16 @@ impl TreeItem {
         self
     }
 
+    /// Width of one nesting level (default: the theme's `Scale::Xl` spacing).
     pub fn indent(mut self, indent: Pixels) -> Self {
-        self.indent = indent;
+        self.indent = Some(indent);
+        self
+    }
+
+    /// Draw vertical guide lines at each ancestor level, one entry per level
+    /// from the root down to this row's parent.
+    pub fn indent_guides(mut self, guides: Vec<IndentGuide>) -> Self {
+        self.indent_guides = guides;
         self
     }
 
@@ -161,6 +192,17 @@ impl TreeItem {
         self
     }
 
+    /// Attach a handler for clicks on this row's checkbox.
+    ///
+    /// The click does not reach the row's own click handlers.
+    pub fn on_check<F>(mut self, listener: F) -> Self
+    where
+        F: 'static + Fn(&ClickEvent, &mut gpui::Window, &mut gpui::App),
+    {
+        self.on_check = Some(Arc::new(listener));
+        self
+    }
+
     /// Generate a child element ID by combining this component's element ID with a suffix.
     pub fn child_id(&self, suffix: &str) -> ElementId {
         (self.element_id.clone(), suffix.to_string()).into()
@@ -197,6 +239,7 @@ impl RenderOnce for TreeItem {
         let expanded = self.expanded;
         let has_children = self.has_children;
         let selected = self.selected;
+        let focused = self.focused;
         let disabled = self.disabled;
         let checked = self.checked;
         let show_checkbox = self.show_checkbox;
@@ -204,12 +247,16 @@ impl RenderOnce for TreeItem {
         let label_element = self.label_element;
         let secondary = self.secondary;
         let trailing = self.trailing;
-        let indent = self.indent;
+        let indent = self.indent.unwrap_or_else(|| theme.spacing(Scale::Xl));
+        let indent_guides = self.indent_guides;
+        let guide_color = theme.border.divider;
         let hover_bg = self.hover_bg.unwrap_or(theme.surface.hover);
         let selected_bg = self.selected_bg.unwrap_or(theme.action.neutral.active_bg);
         let on_context_menu = self.on_context_menu;
+        let on_check = self.on_check;
 
         let is_checked = checked == TreeCheckedState::Checked;
+        let rtl = cx.text_direction().is_rtl();
 
         let disclosure_id: ElementId = (element_id.clone(), "ui:tree-item:disclosure").into();
         let checkbox_id: ElementId = (element_id.clone(), "ui:tree-item:checkbox").into();
@@ -218,8 +265,14 @@ impl RenderOnce for TreeItem {
             .id(element_id.to_string())
             .w_full()
             .min_h(px(32.))
-            .pl(indent * depth as f32)
-            .pr_3()
+            .map(|this| {
+                // Indent from the start edge, which is the right one in RTL.
+                if rtl {
+                    this.pr(indent * depth as f32).pl_3().flex_row_reverse()
+                } else {
+                    this.pl(indent * depth as f32).pr_3()
+                }
+            })
             .py_1()
             .rounded_md()
             .flex()
@@ -228,6 +281,36 @@ impl RenderOnce for TreeItem {
             .when(selected, |this| this.bg(selected_bg))
             .when(!selected, |this| this.hover(|s| s.bg(hover_bg)))
             .when(disabled, |this| this.opacity(0.5))
+            .when(!indent_guides.is_empty(), |this| {
+                this.relative()
+                    .children(indent_guides.iter().enumerate().flat_map(|(level, guide)| {
+                        let x = guide_x(level, indent);
+                        let at_start = |el: Div| if rtl { el.right(x) } else { el.left(x) };
+                        let line = at_start(div().absolute().top_0().w(px(1.)).bg(guide_color));
+                        match guide {
+                            IndentGuide::Line => vec![line.bottom_0()],
+                            IndentGuide::Elbow => vec![
+                                line.h(relative(0.5)),
+                                at_start(div().absolute())
+                                    .top(relative(0.5))
+                                    .w(indent - px(7.))
+                                    .h(px(1.))
+                                    .bg(guide_color),
+                            ],
+                            IndentGuide::Blank => Vec::new(),
+                        }
+                    }))
+            })
+            .when(focused, |this| {
+                this.relative().child(
+                    div()
+                        .absolute()
+                        .inset_0()
+                        .rounded_md()
+                        .border_1()
+                        .border_color(theme.border.focus),
+                )
+            })
             .when_some(on_context_menu, |this, handler| {
                 this.on_mouse_down(MouseButton::Right, move |ev, window, cx| {
                     cx.stop_propagation();
@@ -238,7 +321,20 @@ impl RenderOnce for TreeItem {
                 this.child(disclosure(disclosure_id).expanded(expanded))
             })
             .when(show_checkbox, |this| {
-                this.child(checkbox(checkbox_id).checked(is_checked))
+                this.child(
+                    checkbox(checkbox_id)
+                        .checked(is_checked)
+                        .indeterminate(checked == TreeCheckedState::Indeterminate)
+                        .disabled(disabled)
+                        .when_some(on_check, |this, handler| {
+                            this.on_toggle(move |_, ev, window, cx| {
+                                cx.stop_propagation();
+                                if let Some(ev) = ev {
+                                    handler(ev, window, cx);
+                                }
+                            })
+                        }),
+                )
             })
             .children(icon_element)
             .child(
@@ -246,7 +342,8 @@ impl RenderOnce for TreeItem {
                     .flex()
                     .flex_col()
                     .gap_1()
-                    .items_start()
+                    .when(rtl, |this| this.items_end())
+                    .when(!rtl, |this| this.items_start())
                     .flex_grow()
                     .children(label_element)
                     .children(secondary.map(|el| {
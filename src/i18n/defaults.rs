@@ -38,6 +38,68 @@ impl DefaultPlaceholders {
         }
     }
 
+    /// Get the default empty-results message for a ComboBox.
+    pub fn combobox_no_results(locale: &Locale) -> &'static str {
+        match locale.language() {
+            "zh" => "无匹配结果",
+            "ja" => "該当なし",
+            "ko" => "결과 없음",
+            "ar" => "لا توجد نتائج",
+            "he" => "אין תוצאות",
+            "fr" => "Aucun résultat",
+            "de" => "Keine Ergebnisse",
+            "es" => "Sin resultados",
+            _ => "No results",
+        }
+    }
+
+    /// Get the default message for a ComboBox that has no options at all.
+    pub fn combobox_no_options(locale: &Locale) -> &'static str {
+        match locale.language() {
+            "zh" => "暂无可选项",
+            "ja" => "選択肢がありません",
+            "ko" => "사용 가능한 옵션 없음",
+            "ar" => "لا توجد خيارات متاحة",
+            "he" => "אין אפשרויות זמינות",
+            "fr" => "Aucune option disponible",
+            "de" => "Keine Optionen verfügbar",
+            "es" => "No hay opciones disponibles",
+            _ => "No options available",
+        }
+    }
+
+    /// Get the default label for a ComboBox row that creates a new option.
+    ///
+    /// `{query}` is replaced with the search text.
+    pub fn combobox_create(locale: &Locale) -> &'static str {
+        match locale.language() {
+            "zh" => "创建“{query}”",
+            "ja" => "「{query}」を作成",
+            "ko" => "\"{query}\" 만들기",
+            "ar" => "إنشاء \"{query}\"",
+            "he" => "צור \"{query}\"",
+            "fr" => "Créer « {query} »",
+            "de" => "„{query}“ erstellen",
+            "es" => "Crear \"{query}\"",
+            _ => "Create \"{query}\"",
+        }
+    }
+
+    /// Get the default text shown while content is loading.
+    pub fn loading(locale: &Locale) -> &'static str {
+        match locale.language() {
+            "zh" => "加载中…",
+            "ja" => "読み込み中…",
+            "ko" => "로드 중…",
+            "ar" => "جارٍ التحميل…",
+            "he" => "טוען…",
+            "fr" => "Chargement…",
+            "de" => "Wird geladen…",
+            "es" => "Cargando…",
+            _ => "Loading…",
+        }
+    }
+
     /// Get the default label for a DropdownMenu.
     pub fn dropdown_menu_label(locale: &Locale) -> &'static str {
         match locale.language() {
@@ -53,6 +115,96 @@ impl DefaultPlaceholders {
         }
     }
 
+    /// Get the label of the menu holding tabs that don't fit a Tabs strip.
+    pub fn tabs_more(locale: &Locale) -> &'static str {
+        match locale.language() {
+            "zh" => "更多",
+            "ja" => "その他",
+            "ko" => "더 보기",
+            "ar" => "المزيد",
+            "he" => "עוד",
+            "fr" => "Plus",
+            "de" => "Mehr",
+            "es" => "Más",
+            _ => "More",
+        }
+    }
+
+    /// Get the default placeholder for a DatePicker with no date.
+    pub fn date_picker_placeholder(locale: &Locale) -> &'static str {
+        match locale.language() {
+            "zh" => "选择日期",
+            "ja" => "日付を選択",
+            "ko" => "날짜 선택",
+            "ar" => "اختر تاريخًا",
+            "he" => "בחר תאריך",
+            "fr" => "Choisir une date",
+            "de" => "Datum auswählen",
+            "es" => "Seleccionar fecha",
+            _ => "Select date",
+        }
+    }
+
+    /// Get the label of a DatePicker's previous-month button.
+    pub fn date_picker_previous_month(locale: &Locale) -> &'static str {
+        match locale.language() {
+            "zh" => "上个月",
+            "ja" => "前の月",
+            "ko" => "이전 달",
+            "ar" => "الشهر السابق",
+            "he" => "החודש הקודם",
+            "fr" => "Mois précédent",
+            "de" => "Vorheriger Monat",
+            "es" => "Mes anterior",
+            _ => "Previous month",
+        }
+    }
+
+    /// Get the label of a DatePicker's next-month button.
+    pub fn date_picker_next_month(locale: &Locale) -> &'static str {
+        match locale.language() {
+            "zh" => "下个月",
+            "ja" => "次の月",
+            "ko" => "다음 달",
+            "ar" => "الشهر التالي",
+            "he" => "החודש הבא",
+            "fr" => "Mois suivant",
+            "de" => "Nächster Monat",
+            "es" => "Mes siguiente",
+            _ => "Next month",
+        }
+    }
+
+    /// Get the label of a DatePicker's previous-year button.
+    pub fn date_picker_previous_year(locale: &Locale) -> &'static str {
+        match locale.language() {
+            "zh" => "上一年",
+            "ja" => "前の年",
+            "ko" => "이전 해",
+            "ar" => "السنة السابقة",
+            "he" => "השנה הקודמת",
+            "fr" => "Année précédente",
+            "de" => "Vorheriges Jahr",
+            "es" => "Año anterior",
+            _ => "Previous year",
+        }
+    }
+
+    /// Get the label of a DatePicker's next-year button.
+    pub fn date_picker_next_year(locale: &Locale) -> &'static str {
+        match locale.language() {
+            "zh" => "下一年",
+            "ja" => "次の年",
+            "ko" => "다음 해",
+            "ar" => "السنة التالية",
+            "he" => "השנה הבאה",
+            "fr" => "Année suivante",
+            "de" => "Nächstes Jahr",
+            "es" => "Año siguiente",
+            _ => "Next year",
+        }
+    }
+
     /// Get the default placeholder for a FilePathInput.
     pub fn file_path_placeholder(locale: &Locale) -> &'static str {
         match locale.language() {
@@ -97,6 +249,51 @@ impl DefaultPlaceholders {
             _ => "Waiting for keys…",
         }
     }
+
+    /// Get the Caps Lock warning shown by a PasswordInput.
+    pub fn caps_lock_on(locale: &Locale) -> &'static str {
+        match locale.language() {
+            "zh" => "大写锁定已开启",
+            "ja" => "Caps Lock がオンです",
+            "ko" => "Caps Lock이 켜져 있습니다",
+            "ar" => "مفتاح Caps Lock مفعّل",
+            "he" => "Caps Lock פועל",
+            "fr" => "Verr. Maj activé",
+            "de" => "Feststelltaste ist aktiv",
+            "es" => "Bloq Mayús activado",
+            _ => "Caps Lock is on",
+        }
+    }
+
+    /// Get the confirmation announced by a CopyButton.
+    pub fn copied(locale: &Locale) -> &'static str {
+        match locale.language() {
+            "zh" => "已复制",
+            "ja" => "コピーしました",
+            "ko" => "복사됨",
+            "ar" => "تم النسخ",
+            "he" => "הועתק",
+            "fr" => "Copié",
+            "de" => "Kopiert",
+            "es" => "Copiado",
+            _ => "Copied",
+        }
+    }
+
+    /// Get the "+N more" label standing in for `count` items that aren't shown.
+    pub fn more_count(locale: &Locale, count: usize) -> String {
+        match locale.language() {
+            "zh" => format!("+{count} 条更多"),
+            "ja" => format!("他 {count} 件"),
+            "ko" => format!("+{count}개 더"),
+            "ar" => format!("+{count} أخرى"),
+            "he" => format!("+{count} נוספים"),
+            "fr" => format!("+{count} de plus"),
+            "de" => format!("+{count} weitere"),
+            "es" => format!("+{count} más"),
+            _ => format!("+{count} more"),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -111,4 +308,13 @@ mod tests {
         assert_eq!(DefaultPlaceholders::select_placeholder(&en), "Select…");
         assert_eq!(DefaultPlaceholders::select_placeholder(&zh), "请选择…");
     }
+
+    #[test]
+    fn test_more_count() {
+        let en = Locale::new("en").unwrap();
+        let de = Locale::new("de").unwrap();
+
+        assert_eq!(DefaultPlaceholders::more_count(&en, 3), "+3 more");
+        assert_eq!(DefaultPlaceholders::more_count(&de, 3), "+3 weitere");
+    }
 }
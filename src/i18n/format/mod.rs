@@ -6,6 +6,9 @@
 use super::locale::Locale;
 
 use std::borrow::Cow;
+use std::fmt;
+
+use chrono::Timelike;
 
 /// Locale-specific separators and digit shape.
 #[derive(Clone, Copy, Debug)]
@@ -15,10 +18,45 @@ struct NumberSymbols {
     minus: char,
     /// Whether to use Arabic-Indic digits (٠١٢٣٤٥٦٧٨٩).
     use_arabic_indic_digits: bool,
+    grouping: GroupingStyle,
+}
+
+/// How integer digits are split into groups.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum GroupingStyle {
+    /// Groups of three: 1,000,000.
+    Thousands,
+    /// Indian lakh/crore grouping: three digits, then twos (1,00,00,000).
+    Indian,
+}
+
+impl GroupingStyle {
+    fn for_locale(locale: &Locale) -> Self {
+        match (locale.language(), locale.region()) {
+            ("hi" | "bn", _) => Self::Indian,
+            ("en", Some(region)) if region.eq_ignore_ascii_case("IN") => Self::Indian,
+            _ => Self::Thousands,
+        }
+    }
+
+    /// Size of every group left of the rightmost one.
+    fn secondary_size(self) -> usize {
+        match self {
+            Self::Thousands => 3,
+            Self::Indian => 2,
+        }
+    }
 }
 
 impl NumberSymbols {
+    /// Whether `c` separates digit groups. Space-grouping locales also accept
+    /// the no-break spaces other tools use in that role.
+    fn is_group(&self, c: char) -> bool {
+        c == self.group || (self.group == ' ' && matches!(c, '\u{a0}' | '\u{202f}'))
+    }
+
     fn for_locale(locale: &Locale) -> Self {
+        let grouping = GroupingStyle::for_locale(locale);
         match locale.language() {
             // Arabic: Arabic-Indic digits + Arabic separators.
             // Note: Decimal separator in Arabic locales is typically "٫" (U+066B)
@@ -28,6 +66,7 @@ impl NumberSymbols {
                 group: '٬',
                 minus: '−',
                 use_arabic_indic_digits: true,
+                grouping,
             },
             // French family commonly uses comma for decimals and space (or NBSP) for grouping.
             "fr" | "de" | "es" | "it" | "ru" => Self {
@@ -35,12 +74,14 @@ impl NumberSymbols {
                 group: ' ',
                 minus: '−',
                 use_arabic_indic_digits: false,
+                grouping,
             },
             _ => Self {
                 decimal: '.',
                 group: ',',
                 minus: '-',
                 use_arabic_indic_digits: false,
+                grouping,
             },
         }
     }
@@ -87,6 +128,19 @@ pub enum CurrencyDisplay {
     Name,
 }
 
+/// Error returned by [`NumberFormatter::parse_decimal`] for input that isn't a
+/// number in the formatter's locale.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NumberParseError;
+
+impl fmt::Display for NumberParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Failed to parse number for locale")
+    }
+}
+
+impl std::error::Error for NumberParseError {}
+
 /// Number formatter.
 pub struct NumberFormatter {
     locale: Locale,
@@ -184,7 +238,7 @@ impl NumberFormatter {
             .unwrap_or((formatted.as_str(), None));
 
         let int_part = if use_grouping {
-            add_grouping_separators(int_part, symbols.group)
+            add_grouping_separators(int_part, symbols.group, symbols.grouping)
         } else {
             int_part.to_string()
         };
@@ -206,6 +260,68 @@ impl NumberFormatter {
         out
     }
 
+    /// Parse a number written the way [`format_decimal`](Self::format_decimal)
+    /// writes it for this locale.
+    ///
+    /// Accepts surrounding whitespace, a leading minus (the locale's or `-`),
+    /// Latin or Arabic-Indic digits, and group separators placed the way the
+    /// locale groups integer digits (threes, or lakh/crore for `en-IN`, `hi` and
+    /// `bn`). Anything else, such as a misplaced group separator or the other
+    /// convention's decimal point (`1.000` in `de`), is rejected rather than
+    /// guessed at.
+    pub fn parse_decimal(&self, input: &str) -> Result<f64, NumberParseError> {
+        let symbols = NumberSymbols::for_locale(&self.locale);
+        let input = input.trim();
+        let (negative, body) = match input
+            .strip_prefix(symbols.minus)
+            .or_else(|| input.strip_prefix('-'))
+        {
+            Some(rest) => (true, rest),
+            None => (false, input),
+        };
+        let sign = if negative { -1.0 } else { 1.0 };
+
+        match body {
+            "∞" => return Ok(sign * f64::INFINITY),
+            "NaN" if !negative => return Ok(f64::NAN),
+            _ => {}
+        }
+
+        let (int_part, frac_part) = match body.split_once(symbols.decimal) {
+            Some((int_part, frac_part)) => (int_part, Some(frac_part)),
+            None => (body, None),
+        };
+        if int_part.is_empty() && frac_part.is_none_or(str::is_empty) {
+            return Err(NumberParseError);
+        }
+
+        let groups: Vec<&str> = int_part.split(|c| symbols.is_group(c)).collect();
+        let secondary = symbols.grouping.secondary_size();
+        if let [first, middle @ .., last] = groups.as_slice()
+            && (first.is_empty()
+                || first.chars().count() > secondary
+                || middle
+                    .iter()
+                    .any(|group| group.chars().count() != secondary)
+                || last.chars().count() != 3)
+        {
+            return Err(NumberParseError);
+        }
+
+        let mut digits = String::new();
+        for group in groups {
+            push_latin_digits(group, &mut digits)?;
+        }
+        if let Some(frac_part) = frac_part {
+            digits.push('.');
+            push_latin_digits(frac_part, &mut digits)?;
+        }
+        digits
+            .parse::<f64>()
+            .map(|value| sign * value)
+            .map_err(|_| NumberParseError)
+    }
+
     /// Format a number with options.
     pub fn format_with_options(&self, value: f64, options: &NumberFormatOptions) -> String {
         let result = self.format_decimal_with_options(value, options);
@@ -219,14 +335,15 @@ impl NumberFormatter {
             CurrencyDisplay::Name => get_currency_name(currency, &self.locale),
         };
 
-        let as_suffix = options
-            .currency_as_suffix
-            .unwrap_or_else(|| currency_should_be_suffix(&self.locale));
+        let pattern = CurrencyPattern::for_locale(&self.locale);
+        let as_suffix = options.currency_as_suffix.unwrap_or(pattern.suffix);
 
         if as_suffix {
-            format!("{result} {symbol}")
+            let space = pattern.spacing(pattern.suffix_space, symbol.chars().next());
+            format!("{result}{space}{symbol}")
         } else {
-            format!("{symbol} {result}")
+            let space = pattern.spacing(pattern.prefix_space, symbol.chars().last());
+            format!("{symbol}{space}{result}")
         }
     }
 
@@ -252,9 +369,9 @@ impl NumberFormatter {
     }
 }
 
-/// Add thousand separators based on locale.
-fn add_grouping_separators(s: &str, separator: char) -> String {
-    let group_size = 3;
+/// Add group separators in the locale's grouping style.
+fn add_grouping_separators(s: &str, separator: char, grouping: GroupingStyle) -> String {
+    let mut group_size = 3;
 
     let chars: Vec<char> = s.chars().collect();
     let len = chars.len();
@@ -278,6 +395,7 @@ fn add_grouping_separators(s: &str, separator: char) -> String {
         }
 
         remaining = start;
+        group_size = grouping.secondary_size();
     }
 
     result
@@ -301,13 +419,63 @@ fn latin_to_arabic_indic_digits(s: &str) -> String {
         .collect()
 }
 
-fn currency_should_be_suffix(locale: &Locale) -> bool {
-    // A small pragmatic default: English often prefixes, many European languages suffix.
-    match locale.language() {
-        "fr" | "de" | "es" | "it" | "ru" => true,
-        // Arabic commonly uses suffix in many contexts (e.g. ١٠٠ ر.س), but varies.
-        "ar" => true,
-        _ => false,
+/// Append `s` to `out` as Latin digits, failing on anything but Latin or
+/// Arabic-Indic digits.
+fn push_latin_digits(s: &str, out: &mut String) -> Result<(), NumberParseError> {
+    for c in s.chars() {
+        let digit = match c {
+            '0'..='9' => c,
+            '٠'..='٩' => char::from(b'0' + (c as u32 - '٠' as u32) as u8),
+            _ => return Err(NumberParseError),
+        };
+        out.push(digit);
+    }
+    Ok(())
+}
+
+/// Where a locale puts the currency symbol and what separates it from the number.
+#[derive(Clone, Copy, Debug)]
+struct CurrencyPattern {
+    /// Put the symbol after the number by default.
+    suffix: bool,
+    /// Between a prefixed symbol and the number.
+    prefix_space: &'static str,
+    /// Between the number and a suffixed symbol.
+    suffix_space: &'static str,
+}
+
+impl CurrencyPattern {
+    fn for_locale(locale: &Locale) -> Self {
+        match locale.language() {
+            // 1 234,50 €
+            "fr" | "de" | "es" | "it" | "ru" => Self {
+                suffix: true,
+                prefix_space: "\u{a0}",
+                suffix_space: "\u{a0}",
+            },
+            // ١٠٠٫٥٠ ر.س
+            "ar" => Self {
+                suffix: true,
+                prefix_space: "\u{a0}",
+                suffix_space: "\u{a0}",
+            },
+            // $100.50
+            _ => Self {
+                suffix: false,
+                prefix_space: "",
+                suffix_space: "\u{a0}",
+            },
+        }
+    }
+
+    /// The separator to use next to a symbol whose adjacent character is `edge`.
+    /// Letters (codes like `CHF`, or currency names) never touch the digits.
+    fn spacing(&self, space: &'static str, edge: Option<char>) -> &'static str {
+        if space.is_empty() && edge.is_some_and(char::is_alphabetic) {
+            "\u{a0}"
+        } else {
+            space
+        }
     }
 }
 
@@ -392,8 +560,12 @@ fn get_currency_name(currency: &str, locale: &Locale) -> String {
 pub struct DateTimeFormatOptions {
     /// Date length.
     pub date_length: DateTimeLength,
-    /// Time length.
+    /// Time length. `Short` omits seconds; longer lengths include them.
     pub time_length: DateTimeLength,
+    /// Use a 12-hour clock with an AM/PM marker.
+    ///
+    /// If `None`, a locale-aware default is used.
+    pub hour12: Option<bool>,
 }
 
 /// Date/time length.
@@ -406,15 +578,43 @@ pub enum DateTimeLength {
     Full,
 }
 
+/// Whether `locale` conventionally writes times on a 12-hour clock.
+fn uses_12_hour_clock(locale: &Locale) -> bool {
+    match locale.language() {
+        "en" => !locale
+            .region()
+            .is_some_and(|region| ["GB", "IE"].iter().any(|r| region.eq_ignore_ascii_case(r))),
+        "ko" => true,
+        _ => false,
+    }
+}
+
+/// The AM/PM marker for a language, and whether it precedes the time.
+fn day_period(lang: &str, pm: bool) -> (&'static str, bool) {
+    match lang {
+        "ko" => (if pm { "오후" } else { "오전" }, true),
+        "zh" => (if pm { "下午" } else { "上午" }, true),
+        "ja" => (if pm { "午後" } else { "午前" }, true),
+        "ar" => (if pm { "م" } else { "ص" }, false),
+        _ => (if pm { "PM" } else { "AM" }, false),
+    }
+}
+
 /// Date/time formatter.
 pub struct DateTimeFormatter {
     locale: Locale,
+    options: DateTimeFormatOptions,
 }
 
 impl DateTimeFormatter {
     /// Create a new date/time formatter for a locale.
     pub fn new(locale: Locale) -> Self {
-        Self { locale }
+        Self::with_options(locale, DateTimeFormatOptions::default())
+    }
+
+    /// Create a new date/time formatter for a locale with explicit options.
+    pub fn with_options(locale: Locale, options: DateTimeFormatOptions) -> Self {
+        Self { locale, options }
     }
 
     /// Format a date (timestamp in seconds).
@@ -460,17 +660,31 @@ impl DateTimeFormatter {
     }
 
     /// Format a time (timestamp in seconds).
+    ///
+    /// On a 12-hour clock the hour is zero-padded (`%I`) in every locale, like
+    /// the 24-hour `%H`, so times line up in columns; only the AM/PM marker and
+    /// its position vary by language.
     pub fn format_time(&self, timestamp: i64) -> String {
         use chrono::{TimeZone, Utc};
 
         let datetime = Utc.timestamp_opt(timestamp, 0).single();
         if let Some(dt) = datetime {
-            let lang = self.locale.language();
+            let seconds = !matches!(self.options.time_length, DateTimeLength::Short);
+            let hour12 = self
+                .options
+                .hour12
+                .unwrap_or_else(|| uses_12_hour_clock(&self.locale));
+
+            if !hour12 {
+                let pattern = if seconds { "%H:%M:%S" } else { "%H:%M" };
+                return dt.format(pattern).to_string();
+            }
 
-            // Some locales use 12-hour format
-            match lang {
-                "en" | "ko" | "zh" | "ja" => dt.format("%H:%M").to_string(),
-                _ => dt.format("%H:%M").to_string(),
+            let pattern = if seconds { "%I:%M:%S" } else { "%I:%M" };
+            let time = dt.format(pattern);
+            match day_period(self.locale.language(), dt.hour() >= 12) {
+                (marker, true) => format!("{marker} {time}"),
+                (marker, false) => format!("{time} {marker}"),
             }
         } else {
             "Invalid time".to_string()
@@ -485,6 +699,250 @@ impl DateTimeFormatter {
             self.format_time(timestamp)
         )
     }
+
+    /// The day calendar weeks start on.
+    pub fn first_weekday(&self) -> chrono::Weekday {
+        use chrono::Weekday;
+
+        let region = self.locale.region().map(str::to_ascii_uppercase);
+        match (self.locale.language(), region.as_deref()) {
+            ("en", None | Some("US" | "CA" | "PH")) => Weekday::Sun,
+            ("zh", Some("TW" | "HK" | "MO")) => Weekday::Sun,
+            ("ja" | "ko" | "he", _) => Weekday::Sun,
+            ("pt", Some("BR")) => Weekday::Sun,
+            ("ar", _) => Weekday::Sat,
+            _ => Weekday::Mon,
+        }
+    }
+
+    /// The short weekday name used in calendar headers, e.g. "Mo" or "一".
+    pub fn weekday_short(&self, weekday: chrono::Weekday) -> &'static str {
+        let names: [&str; 7] = match self.locale.language() {
+            "zh" => ["一", "二", "三", "四", "五", "六", "日"],
+            "ja" => ["月", "火", "水", "木", "金", "土", "日"],
+            "ko" => ["월", "화", "수", "목", "금", "토", "일"],
+            "de" => ["Mo", "Di", "Mi", "Do", "Fr", "Sa", "So"],
+            "fr" => ["lu", "ma", "me", "je", "ve", "sa", "di"],
+            "es" => ["lu", "ma", "mi", "ju", "vi", "sá", "do"],
+            "ru" => ["пн", "вт", "ср", "чт", "пт", "сб", "вс"],
+            "ar" => ["ن", "ث", "ر", "خ", "ج", "س", "ح"],
+            "he" => ["ב׳", "ג׳", "ד׳", "ה׳", "ו׳", "ש׳", "א׳"],
+            _ => ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"],
+        };
+        names[weekday.num_days_from_monday() as usize]
+    }
+
+    /// Format a month heading (`month` is 1-based), e.g. "March 2024" or "2024年3月".
+    pub fn format_month(&self, year: i32, month: u32) -> String {
+        let names: [&str; 12] = match self.locale.language() {
+            "zh" | "ja" => return format!("{year}年{month}月"),
+            "ko" => return format!("{year}년 {month}월"),
+            "de" => [
+                "Januar",
+                "Februar",
+                "März",
+                "April",
+                "Mai",
+                "Juni",
+                "Juli",
+                "August",
+                "September",
+                "Oktober",
+                "November",
+                "Dezember",
+            ],
+            "fr" => [
+                "janvier",
+                "février",
+                "mars",
+                "avril",
+                "mai",
+                "juin",
+                "juillet",
+                "août",
+                "septembre",
+                "octobre",
+                "novembre",
+                "décembre",
+            ],
+            "es" => [
+                "enero",
+                "febrero",
+                "marzo",
+                "abril",
+                "mayo",
+                "junio",
+                "julio",
+                "agosto",
+                "septiembre",
+                "octubre",
+                "noviembre",
+                "diciembre",
+            ],
+            "ru" => [
+                "январь",
+                "февраль",
+                "март",
+                "апрель",
+                "май",
+                "июнь",
+                "июль",
+                "август",
+                "сентябрь",
+                "октябрь",
+                "ноябрь",
+                "декабрь",
+            ],
+            "ar" => [
+                "يناير",
+                "فبراير",
+                "مارس",
+                "أبريل",
+                "مايو",
+                "يونيو",
+                "يوليو",
+                "أغسطس",
+                "سبتمبر",
+                "أكتوبر",
+                "نوفمبر",
+                "ديسمبر",
+            ],
+            "he" => [
+                "ינואר",
+                "פברואר",
+                "מרץ",
+                "אפריל",
+                "מאי",
+                "יוני",
+                "יולי",
+                "אוגוסט",
+                "ספטמבר",
+                "אוקטובר",
+                "נובמבר",
+                "דצמבר",
+            ],
+            _ => [
+                "January",
+                "February",
+                "March",
+                "April",
+                "May",
+                "June",
+                "July",
+                "August",
+                "September",
+                "October",
+                "November",
+                "December",
+            ],
+        };
+        match names.get(month.wrapping_sub(1) as usize) {
+            Some(name) => format!("{name} {year}"),
+            None => "Invalid month".to_string(),
+        }
+    }
+}
+
+/// Kind of list being joined.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ListStyle {
+    /// All of the items: "A, B, and C".
+    #[default]
+    And,
+    /// Any of the items: "A, B, or C".
+    Or,
+    /// Parts of a single quantity: "3 ft, 7 in".
+    Unit,
+}
+
+/// Separators used to join a list.
+#[derive(Clone, Copy, Debug)]
+struct ListPattern {
+    /// Between the items of a two-item list.
+    pair: &'static str,
+    /// Between items, except before the last one.
+    middle: &'static str,
+    /// Before the last item of a list of three or more.
+    end: &'static str,
+}
+
+impl ListPattern {
+    const fn new(pair: &'static str, middle: &'static str, end: &'static str) -> Self {
+        Self { pair, middle, end }
+    }
+
+    /// A list joined with `middle`, and with `word` before the last item.
+    const fn with_word(middle: &'static str, word: &'static str) -> Self {
+        Self::new(word, middle, word)
+    }
+
+    fn for_locale(locale: &Locale, style: ListStyle) -> Self {
+        match (locale.language(), style) {
+            ("zh", ListStyle::And) => Self::with_word("、", "和"),
+            ("zh", ListStyle::Or) => Self::with_word("、", "或"),
+            ("zh", ListStyle::Unit) => Self::with_word("", ""),
+            ("ja", ListStyle::And) => Self::with_word("、", "、"),
+            ("ja", ListStyle::Or) => Self::with_word("、", "、または"),
+            ("ko", ListStyle::And) => Self::with_word(", ", " 및 "),
+            ("ko", ListStyle::Or) => Self::with_word(", ", " 또는 "),
+            ("fr", ListStyle::And | ListStyle::Unit) => Self::with_word(", ", " et "),
+            ("fr", ListStyle::Or) => Self::with_word(", ", " ou "),
+            ("de", ListStyle::And | ListStyle::Unit) => Self::with_word(", ", " und "),
+            ("de", ListStyle::Or) => Self::with_word(", ", " oder "),
+            ("es", ListStyle::And) => Self::with_word(", ", " y "),
+            ("es", ListStyle::Or) => Self::with_word(", ", " o "),
+            ("it", ListStyle::And) => Self::with_word(", ", " e "),
+            ("it", ListStyle::Or) => Self::with_word(", ", " o "),
+            ("pt", ListStyle::And) => Self::with_word(", ", " e "),
+            ("pt", ListStyle::Or) => Self::with_word(", ", " ou "),
+            ("ru", ListStyle::And) => Self::with_word(", ", " и "),
+            ("ru", ListStyle::Or) => Self::with_word(", ", " или "),
+            // Arabic attaches "و" to the following item and uses the Arabic comma.
+            ("ar", ListStyle::And) => Self::with_word(" و", " و"),
+            ("ar", ListStyle::Or) => Self::with_word(" أو ", " أو "),
+            ("ar", ListStyle::Unit) => Self::with_word("، ", "، "),
+            // English and default
+            (_, ListStyle::Unit) => Self::with_word(", ", ", "),
+            (_, ListStyle::And) => Self::new(" and ", ", ", ", and "),
+            (_, ListStyle::Or) => Self::new(" or ", ", ", ", or "),
+        }
+    }
+}
+
+/// List formatter ("A, B, and C").
+pub struct ListFormatter {
+    locale: Locale,
+}
+
+impl ListFormatter {
+    /// Create a new list formatter for a locale.
+    pub fn new(locale: Locale) -> Self {
+        Self { locale }
+    }
+
+    /// Join `items` with the locale's separators and conjunction for `style`.
+    ///
+    /// An empty list gives an empty string and a single item is returned as is.
+    pub fn format<S: AsRef<str>>(&self, items: &[S], style: ListStyle) -> String {
+        let pattern = ListPattern::for_locale(&self.locale, style);
+        match items {
+            [] => String::new(),
+            [item] => item.as_ref().to_string(),
+            [first, second] => format!("{}{}{}", first.as_ref(), pattern.pair, second.as_ref()),
+            [init @ .., last] => {
+                let mut out = String::new();
+                for (ix, item) in init.iter().enumerate() {
+                    if ix > 0 {
+                        out.push_str(pattern.middle);
+                    }
+                    out.push_str(item.as_ref());
+                }
+                out.push_str(pattern.end);
+                out.push_str(last.as_ref());
+                out
+            }
+        }
+    }
 }
 
 /// Combined formatter for both numbers and date/time.
@@ -492,6 +950,7 @@ pub struct Formatter {
     _locale: Locale,
     number: NumberFormatter,
     datetime: DateTimeFormatter,
+    list: ListFormatter,
 }
 
 impl Formatter {
@@ -500,7 +959,8 @@ impl Formatter {
         Self {
             _locale: locale.clone(),
             number: NumberFormatter::new(locale.clone()),
-            datetime: DateTimeFormatter::new(locale),
+            datetime: DateTimeFormatter::new(locale.clone()),
+            list: ListFormatter::new(locale),
         }
     }
 
@@ -514,6 +974,11 @@ impl Formatter {
         &self.datetime
     }
 
+    /// Get the list formatter.
+    pub fn list(&self) -> &ListFormatter {
+        &self.list
+    }
+
     /// Format a number.
     pub fn format_number(&self, value: f64) -> String {
         self.number.format_decimal(value)
@@ -543,6 +1008,11 @@ impl Formatter {
     pub fn format_datetime(&self, timestamp: i64) -> String {
         self.datetime.format_datetime(timestamp)
     }
+
+    /// Join a list of items, e.g. "A, B, and C".
+    pub fn format_list<S: AsRef<str>>(&self, items: &[S], style: ListStyle) -> String {
+        self.list.format(items, style)
+    }
 }
 
 /// Helper to add formatting to I18n.
@@ -563,12 +1033,131 @@ mod tests {
         assert_eq!(formatter.format_decimal(100.5), "100.5");
     }
 
+    #[test]
+    fn test_indian_grouping() {
+        let formatter = NumberFormatter::new(Locale::new("en-IN").unwrap());
+        assert_eq!(formatter.format_decimal(1000.0), "1,000");
+        assert_eq!(formatter.format_decimal(100000.0), "1,00,000");
+        assert_eq!(formatter.format_decimal(10000000.0), "1,00,00,000");
+        assert_eq!(formatter.format_decimal(12345678.5), "1,23,45,678.5");
+        assert_eq!(formatter.format_currency(100000.0, "INR"), "₹1,00,000.00");
+
+        let hi = NumberFormatter::new(Locale::new("hi").unwrap());
+        assert_eq!(hi.format_decimal(100000.0), "1,00,000");
+        let bn = NumberFormatter::new(Locale::new("bn").unwrap());
+        assert_eq!(bn.format_decimal(100000.0), "1,00,000");
+        let lowercase = NumberFormatter::new(Locale::new("en-in").unwrap());
+        assert_eq!(lowercase.format_decimal(100000.0), "1,00,000");
+
+        assert_eq!(formatter.parse_decimal("1,00,00,000"), Ok(10000000.0));
+        assert_eq!(formatter.parse_decimal("10,000"), Ok(10000.0));
+        assert_eq!(formatter.parse_decimal("100,000"), Err(NumberParseError));
+        assert_eq!(formatter.parse_decimal("1,000,000"), Err(NumberParseError));
+    }
+
+    #[test]
+    fn test_number_parse() {
+        let formatter = NumberFormatter::new(Locale::new("en").unwrap());
+
+        assert_eq!(formatter.parse_decimal("1,000"), Ok(1000.0));
+        assert_eq!(formatter.parse_decimal("1,000,000"), Ok(1000000.0));
+        assert_eq!(formatter.parse_decimal("100.5"), Ok(100.5));
+        assert_eq!(formatter.parse_decimal("  -2.25 "), Ok(-2.25));
+        assert_eq!(formatter.parse_decimal(".5"), Ok(0.5));
+        assert_eq!(formatter.parse_decimal("1000"), Ok(1000.0));
+
+        for input in [
+            "", "-", ".", "1,00", ",100", "1,,000", "1.000,5", "1e3", "- 1",
+        ] {
+            assert_eq!(
+                formatter.parse_decimal(input),
+                Err(NumberParseError),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_number_parse_locales() {
+        let parse = |tag: &str, input: &str| {
+            NumberFormatter::new(Locale::new(tag).unwrap()).parse_decimal(input)
+        };
+
+        assert_eq!(parse("de", "1 000,5"), Ok(1000.5));
+        assert_eq!(parse("fr", "−2,5"), Ok(-2.5));
+        assert_eq!(parse("fr", "1\u{a0}234"), Ok(1234.0));
+        assert_eq!(parse("de", "1.000"), Err(NumberParseError));
+        assert_eq!(parse("ar", "١٬٠٠٠٫٥"), Ok(1000.5));
+        assert_eq!(parse("ar", "12.5"), Err(NumberParseError));
+
+        for tag in ["en", "en-IN", "de", "ar"] {
+            let formatter = NumberFormatter::new(Locale::new(tag).unwrap());
+            for value in [-1234567.25, 0.5, 42.0] {
+                let text = formatter.format_decimal(value);
+                assert_eq!(formatter.parse_decimal(&text), Ok(value), "{tag}: {text}");
+            }
+        }
+    }
+
     #[test]
     fn test_currency_format() {
         let formatter = NumberFormatter::new(Locale::new("en").unwrap());
 
-        assert_eq!(formatter.format_currency(100.50, "USD"), "$ 100.50");
-        assert_eq!(formatter.format_currency(1000.0, "EUR"), "€ 1,000.00");
+        assert_eq!(formatter.format_currency(100.50, "USD"), "$100.50");
+        assert_eq!(formatter.format_currency(1000.0, "EUR"), "€1,000.00");
+        assert_eq!(formatter.format_currency(5.0, "CHF"), "CHF\u{a0}5.00");
+    }
+
+    #[test]
+    fn test_currency_spacing_per_locale() {
+        let fr = NumberFormatter::new(Locale::new("fr").unwrap());
+        assert_eq!(fr.format_currency(100.5, "EUR"), "100,50\u{a0}€");
+
+        let de = NumberFormatter::new(Locale::new("de").unwrap());
+        assert_eq!(de.format_currency(1000.0, "EUR"), "1 000,00\u{a0}€");
+
+        let ar = NumberFormatter::new(Locale::new("ar").unwrap());
+        assert_eq!(ar.format_currency(100.5, "SAR"), "١٠٠٫٥٠\u{a0}ر.س");
+
+        let en = NumberFormatter::new(Locale::new("en").unwrap());
+        let options = NumberFormatOptions {
+            currency: Some("USD"),
+            currency_as_suffix: Some(true),
+            min_fraction_digits: Some(2),
+            max_fraction_digits: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(en.format_with_options(100.5, &options), "100.50\u{a0}$");
+
+        let options = NumberFormatOptions {
+            currency_display: CurrencyDisplay::Code,
+            currency_as_suffix: None,
+            ..options
+        };
+        assert_eq!(en.format_with_options(100.5, &options), "USD\u{a0}100.50");
+    }
+
+    #[test]
+    fn test_list_format() {
+        let format = |tag: &str, items: &[&str], style| {
+            ListFormatter::new(Locale::new(tag).unwrap()).format(items, style)
+        };
+
+        assert_eq!(format("en", &[], ListStyle::And), "");
+        assert_eq!(format("en", &["A"], ListStyle::And), "A");
+        assert_eq!(format("en", &["A", "B"], ListStyle::And), "A and B");
+        assert_eq!(
+            format("en", &["A", "B", "C"], ListStyle::And),
+            "A, B, and C"
+        );
+        assert_eq!(format("en", &["A", "B", "C"], ListStyle::Or), "A, B, or C");
+        assert_eq!(format("en", &["A", "B", "C"], ListStyle::Unit), "A, B, C");
+        assert_eq!(format("zh-CN", &["A", "B", "C"], ListStyle::And), "A、B和C");
+        assert_eq!(format("zh-CN", &["A", "B"], ListStyle::Or), "A或B");
+        assert_eq!(format("fr", &["A", "B", "C"], ListStyle::And), "A, B et C");
+        assert_eq!(format("de", &["A", "B", "C"], ListStyle::Or), "A, B oder C");
+        assert_eq!(format("ar", &["أ", "ب", "ج"], ListStyle::And), "أ وب وج");
+        assert_eq!(format("ar", &["أ", "ب", "ج"], ListStyle::Unit), "أ، ب، ج");
     }
 
     #[test]
@@ -579,4 +1168,66 @@ mod tests {
         let date = formatter.format_date(timestamp);
         assert!(date.contains("2024"));
     }
+
+    #[test]
+    fn test_time_format() {
+        // 2024-01-01 15:04:05 UTC
+        let timestamp = 1704121445;
+        let time = |tag: &str, options: DateTimeFormatOptions| {
+            DateTimeFormatter::with_options(Locale::new(tag).unwrap(), options)
+                .format_time(timestamp)
+        };
+        let short = DateTimeFormatOptions::default;
+
+        assert_eq!(time("en", short()), "03:04 PM");
+        assert_eq!(time("en-US", short()), "03:04 PM");
+        assert_eq!(time("en-GB", short()), "15:04");
+        assert_eq!(time("de", short()), "15:04");
+        assert_eq!(time("ja", short()), "15:04");
+        assert_eq!(time("ko", short()), "오후 03:04");
+
+        let medium = DateTimeFormatOptions {
+            time_length: DateTimeLength::Medium,
+            ..short()
+        };
+        assert_eq!(time("fr", medium.clone()), "15:04:05");
+        assert_eq!(time("en", medium), "03:04:05 PM");
+
+        let forced = |hour12| DateTimeFormatOptions {
+            hour12: Some(hour12),
+            ..short()
+        };
+        assert_eq!(time("en", forced(false)), "15:04");
+        assert_eq!(time("de", forced(true)), "03:04 PM");
+        assert_eq!(time("zh", forced(true)), "下午 03:04");
+
+        // The 12-hour clock is zero-padded whatever the marker's position.
+        let morning = |tag: &str| {
+            DateTimeFormatter::with_options(Locale::new(tag).unwrap(), forced(true))
+                .format_time(timestamp - 6 * 3600)
+        };
+        assert_eq!(morning("en"), "09:04 AM");
+        assert_eq!(morning("ko"), "오전 09:04");
+        assert_eq!(morning("ar"), "09:04 ص");
+    }
+
+    #[test]
+    fn test_calendar_names() {
+        use chrono::Weekday;
+
+        let formatter = |tag: &str| DateTimeFormatter::new(Locale::new(tag).unwrap());
+
+        assert_eq!(formatter("en").first_weekday(), Weekday::Sun);
+        assert_eq!(formatter("en-GB").first_weekday(), Weekday::Mon);
+        assert_eq!(formatter("de").first_weekday(), Weekday::Mon);
+        assert_eq!(formatter("ar").first_weekday(), Weekday::Sat);
+        assert_eq!(formatter("zh-TW").first_weekday(), Weekday::Sun);
+
+        assert_eq!(formatter("en").weekday_short(Weekday::Sun), "Su");
+        assert_eq!(formatter("zh").weekday_short(Weekday::Mon), "一");
+        assert_eq!(formatter("en").format_month(2024, 3), "March 2024");
+        assert_eq!(formatter("ja").format_month(2024, 3), "2024年3月");
+        assert_eq!(formatter("fr").format_month(2024, 12), "décembre 2024");
+        assert_eq!(formatter("en").format_month(2024, 13), "Invalid month");
+    }
 }
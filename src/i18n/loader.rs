@@ -147,6 +147,7 @@ fn parse_json_object_to_map(
 }
 
 /// Loader that loads translations from external files.
+#[derive(Clone)]
 pub struct FileLoader {
     base_path: String,
 }
@@ -181,6 +182,235 @@ impl TranslationLoader for FileLoader {
     }
 }
 
+#[cfg(feature = "hot-reload")]
+mod hot_reload {
+    use std::collections::HashMap;
+    use std::path::{Path, PathBuf};
+    use std::time::{Duration, SystemTime};
+
+    use gpui::{App, BorrowAppContext, Task};
+
+    use super::{EmbeddedLoader, FileLoader, TranslationLoader};
+    use crate::i18n::locale::Locale;
+    use crate::i18n::runtime::{I18n, TranslationMap};
+
+    /// How often the watched directory is checked for changes.
+    const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+    impl FileLoader {
+        /// Watch the base directory and push edited `<locale>.json` files into the
+        /// global [`I18n`], re-rendering every window.
+        ///
+        /// A file is reloaded once it stops changing for a poll interval, so an
+        /// editor's burst of writes triggers a single reload. Files that fail to
+        /// parse are reported and the previous translations are kept. Reloaded maps
+        /// are merged, so keys deleted from a file stay until restart. Only files
+        /// named after a locale, such as `en.json` or `zh-CN.json`, are watched.
+        ///
+        /// The directory is polled and files are parsed on the background
+        /// executor; only the reloaded maps are applied on the main thread.
+        ///
+        /// Drop the returned task to stop watching.
+        pub fn watch(self, cx: &mut App) -> Task<()> {
+            cx.spawn(async move |cx| {
+                let base_path = PathBuf::from(&self.base_path);
+                let mut debouncer = cx
+                    .background_executor()
+                    .spawn(async move { Debouncer::new(modified_times(&base_path)) })
+                    .await;
+                loop {
+                    cx.background_executor().timer(POLL_INTERVAL).await;
+                    let loader = self.clone();
+                    let (polled, reloaded) = cx
+                        .background_executor()
+                        .spawn(async move {
+                            let reloaded = reload_changed(&mut debouncer, &loader);
+                            (debouncer, reloaded)
+                        })
+                        .await;
+                    debouncer = polled;
+                    if reloaded.is_empty() {
+                        continue;
+                    }
+                    let updated = cx.update(|cx| {
+                        if !cx.has_global::<I18n>() {
+                            return;
+                        }
+                        cx.update_global::<I18n, _>(|i18n, _| {
+                            for (locale, map) in reloaded {
+                                i18n.merge_translations(locale, map);
+                            }
+                        });
+                        cx.refresh_windows();
+                    });
+                    if updated.is_err() {
+                        return;
+                    }
+                }
+            })
+        }
+    }
+
+    /// Loads the locale files that settled since the last poll, reporting and
+    /// skipping those that fail to parse.
+    fn reload_changed(
+        debouncer: &mut Debouncer,
+        loader: &FileLoader,
+    ) -> Vec<(Locale, TranslationMap)> {
+        let changed = debouncer.poll(modified_times(Path::new(&loader.base_path)));
+        changed
+            .into_iter()
+            .filter_map(|path| {
+                let locale = locale_for_path(&path)?;
+                match loader.load(&locale) {
+                    Ok(map) => Some((locale, map)),
+                    Err(err) => {
+                        eprintln!("yororen_ui: failed to reload {}: {err}", path.display());
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// Modification times of the locale files directly inside `dir`.
+    fn modified_times(dir: &Path) -> HashMap<PathBuf, SystemTime> {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return HashMap::new();
+        };
+        entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| locale_for_path(path).is_some())
+            .filter_map(|path| {
+                let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+                Some((path, modified))
+            })
+            .collect()
+    }
+
+    /// The locale a file holds, if it is named `<language>.json` or
+    /// `<language>-<region>.json` with a two- or three-letter language code.
+    fn locale_for_path(path: &Path) -> Option<Locale> {
+        let stem = path.file_stem()?.to_str()?;
+        let language = stem.split('-').next()?;
+        if !(2..=3).contains(&language.len()) || !language.bytes().all(|b| b.is_ascii_lowercase()) {
+            return None;
+        }
+        let locale = Locale::new(stem).ok()?;
+        // The loader reads `EmbeddedLoader::filename_for_locale`, so the name must round-trip.
+        let file_name = path.file_name()?.to_str()?;
+        (EmbeddedLoader::filename_for_locale(&locale) == file_name).then_some(locale)
+    }
+
+    /// Reports a file as changed once its modification time holds still for a
+    /// whole poll.
+    struct Debouncer {
+        seen: HashMap<PathBuf, SystemTime>,
+        pending: HashMap<PathBuf, SystemTime>,
+    }
+
+    impl Debouncer {
+        fn new(seen: HashMap<PathBuf, SystemTime>) -> Self {
+            Self {
+                seen,
+                pending: HashMap::new(),
+            }
+        }
+
+        fn poll(&mut self, current: HashMap<PathBuf, SystemTime>) -> Vec<PathBuf> {
+            let mut settled = Vec::new();
+            self.pending.retain(|path, _| current.contains_key(path));
+            for (path, modified) in current {
+                if self.seen.get(&path) == Some(&modified) {
+                    self.pending.remove(&path);
+                } else if self.pending.get(&path) == Some(&modified) {
+                    self.pending.remove(&path);
+                    self.seen.insert(path.clone(), modified);
+                    settled.push(path);
+                } else {
+                    self.pending.insert(path, modified);
+                }
+            }
+            settled.sort();
+            settled
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn times(entries: &[(&str, u64)]) -> HashMap<PathBuf, SystemTime> {
+            entries
+                .iter()
+                .map(|(path, secs)| {
+                    (
+                        PathBuf::from(path),
+                        SystemTime::UNIX_EPOCH + Duration::from_secs(*secs),
+                    )
+                })
+                .collect()
+        }
+
+        #[test]
+        fn changes_settle_before_reloading() {
+            let mut debouncer = Debouncer::new(times(&[("en.json", 1), ("ar.json", 1)]));
+
+            assert!(
+                debouncer
+                    .poll(times(&[("en.json", 1), ("ar.json", 1)]))
+                    .is_empty()
+            );
+            assert!(
+                debouncer
+                    .poll(times(&[("en.json", 2), ("ar.json", 1)]))
+                    .is_empty()
+            );
+            assert!(
+                debouncer
+                    .poll(times(&[("en.json", 3), ("ar.json", 1)]))
+                    .is_empty()
+            );
+            assert_eq!(
+                debouncer.poll(times(&[("en.json", 3), ("ar.json", 1)])),
+                vec![PathBuf::from("en.json")]
+            );
+            assert!(
+                debouncer
+                    .poll(times(&[("en.json", 3), ("ar.json", 1)]))
+                    .is_empty()
+            );
+        }
+
+        #[test]
+        fn new_files_are_reported() {
+            let mut debouncer = Debouncer::new(HashMap::new());
+
+            assert!(debouncer.poll(times(&[("fr.json", 1)])).is_empty());
+            assert_eq!(
+                debouncer.poll(times(&[("fr.json", 1)])),
+                vec![PathBuf::from("fr.json")]
+            );
+        }
+
+        #[test]
+        fn only_locale_files_are_watched() {
+            assert_eq!(
+                locale_for_path(Path::new("locales/zh-CN.json")),
+                Locale::new("zh-CN").ok()
+            );
+            assert_eq!(
+                locale_for_path(Path::new("locales/en.json")),
+                Locale::new("en").ok()
+            );
+            assert_eq!(locale_for_path(Path::new("locales/package.json")), None);
+            assert_eq!(locale_for_path(Path::new("locales/en.yaml")), None);
+            assert_eq!(locale_for_path(Path::new("locales/zh-Hans-CN.json")), None);
+        }
+    }
+}
+
 /// Create a loader that tries embedded first, then falls back to file system.
 pub struct FallbackLoader {
     embedded: EmbeddedLoader,
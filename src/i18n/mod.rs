@@ -34,21 +34,35 @@
 //!
 //! ## Plural Forms
 //!
-//! In your translation JSON file:
+//! In your translation JSON file, key each form by its CLDR plural category
+//! (`zero`, `one`, `two`, `few`, `many`, `other`). Only the categories the
+//! language uses are needed; `other` is the fallback:
 //! ```json
 //! {
 //!   "items": {
-//!     "one": "{count} item",
-//!     "other": "{count} items"
+//!     "one": "{count} предмет",
+//!     "few": "{count} предмета",
+//!     "many": "{count} предметов",
+//!     "other": "{count} предмета"
 //!   }
 //! }
 //! ```
 //!
 //! ```ignore
 //! use gpui::App;
+//! use yororen_ui::i18n::Translate;
+//!
+//! let text = cx.tn("items", 5);
+//! ```
+//!
+//! ## Number Formatting
+//!
+//! ```ignore
+//! use gpui::App;
 //! use yororen_ui::i18n::I18nContext;
 //!
-//! let text = cx.tn("items", n = 5);
+//! // Uses the active locale and follows `I18n::switch_locale`.
+//! let total = cx.format_currency(1234.5, "USD");
 //! ```
 
 pub mod defaults;
@@ -60,7 +74,8 @@ pub mod translate;
 
 pub use format::{
     CurrencyDisplay, DateTimeFormatOptions, DateTimeFormatter, DateTimeLength, Formatter,
-    I18nFormatter, NumberFormatOptions, NumberFormatter,
+    I18nFormatter, ListFormatter, ListStyle, NumberFormatOptions, NumberFormatter,
+    NumberParseError,
 };
 pub use loader::{
     EmbeddedLoader, FallbackLoader, FileLoader, LoadError, LocaleFiles, TranslationLoader,
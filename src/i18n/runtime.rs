@@ -1,12 +1,15 @@
 //! Internationalization runtime state and management.
 
-use std::collections::HashMap;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
-use gpui::{App, Global, SharedString};
+use gpui::{App, BorrowAppContext, Global, SharedString};
 
+use super::format::{Formatter, I18nFormatter, ListStyle};
+use super::loader::{EmbeddedLoader, FallbackLoader, TranslationLoader};
 use super::locale::{Locale, SupportedLocale, TextDirection};
-use super::loader::{EmbeddedLoader, TranslationLoader};
+use super::translate::plural_form;
 
 /// Global i18n state that stores the current locale and available translations.
 pub struct I18n {
@@ -16,6 +19,14 @@ pub struct I18n {
     pub available_locales: Vec<SupportedLocale>,
     /// Translation strings indexed by locale.
     translations: HashMap<Locale, Arc<TranslationMap>>,
+    /// Locale consulted after the current locale and its base language.
+    fallback_locale: Option<Locale>,
+    /// Formats keys that no locale in the chain translates.
+    missing_key_format: Option<fn(&str) -> String>,
+    /// Report keys that fell through to a fallback or were missing.
+    log_missing_keys: bool,
+    /// Keys already reported, so each gap is logged once per locale.
+    reported_keys: RefCell<HashSet<(Locale, String)>>,
 }
 
 impl Global for I18n {}
@@ -54,14 +65,66 @@ impl I18n {
             current_locale: locale,
             available_locales: SupportedLocale::all().to_vec(),
             translations: HashMap::new(),
+            fallback_locale: None,
+            missing_key_format: None,
+            log_missing_keys: false,
+            reported_keys: RefCell::default(),
         }
     }
 
+    /// Look keys up in `locale` when neither the current locale nor its base
+    /// language (`pt` for `pt-BR`) has them.
+    ///
+    /// The fallback's translations are loaded through [`FallbackLoader`] unless
+    /// they already are.
+    pub fn with_fallback_locale(mut self, locale: Locale) -> Self {
+        if !self.translations.contains_key(&locale)
+            && let Ok(map) = FallbackLoader::new(None::<String>).load(&locale)
+        {
+            self.load_translations(locale.clone(), map);
+        }
+        self.fallback_locale = Some(locale);
+        self
+    }
+
+    /// Format keys missing from every locale in the lookup chain, e.g. to wrap
+    /// them as `⟦key⟧` so untranslated text stands out. By default the key is
+    /// shown as is.
+    pub fn with_missing_key_format(mut self, format: fn(&str) -> String) -> Self {
+        self.missing_key_format = Some(format);
+        self
+    }
+
+    /// Log keys that fell through to a fallback locale or were missing, once per
+    /// key and locale. Off by default.
+    pub fn log_missing_keys(mut self, log: bool) -> Self {
+        self.log_missing_keys = log;
+        self
+    }
+
+    /// The locale consulted after the current locale and its base language.
+    pub fn fallback_locale(&self) -> Option<&Locale> {
+        self.fallback_locale.as_ref()
+    }
+
     /// Set the current locale.
+    ///
+    /// Loaded translations are kept, so switching back and forth does not drop
+    /// runtime-loaded overrides. Use [`I18n::switch_locale`] from app code so
+    /// observers are notified and windows re-render.
     pub fn set_locale(&mut self, locale: Locale) {
         self.current_locale = locale;
     }
 
+    /// Switch the active locale on the global `I18n` and re-render all windows.
+    ///
+    /// Unlike replacing the global with `cx.set_global`, this keeps every loaded
+    /// translation bundle. Global observers of `I18n` are notified.
+    pub fn switch_locale(locale: &Locale, cx: &mut App) {
+        cx.update_global::<Self, _>(|i18n, _| i18n.set_locale(locale.clone()));
+        cx.refresh_windows();
+    }
+
     /// Get the current locale.
     pub fn locale(&self) -> &Locale {
         &self.current_locale
@@ -103,8 +166,87 @@ impl I18n {
     }
 
     /// Get a translation by key.
+    ///
+    /// Tries the current locale, then its base language, then the
+    /// [fallback locale](Self::with_fallback_locale).
     pub fn t(&self, key: &str) -> Option<&str> {
-        self.translations()?.get(key)
+        self.resolve(key, |map, _| map.get(key))
+    }
+
+    /// Get the plural form of `key` for `n`, using the current locale's rules.
+    ///
+    /// Looks up `key.zero`, `key.one`, `key.two`, `key.few` or `key.many` as the
+    /// count requires, falling back to `key.other`. Missing keys follow the same
+    /// chain as [`t`](Self::t), with each locale's own plural rules.
+    pub fn tn(&self, key: &str, n: usize) -> Option<&str> {
+        self.resolve(key, |map, locale| plural_form(map, key, n as u64, locale))
+    }
+
+    /// Text to show for a key no locale translates.
+    pub fn missing_key(&self, key: &str) -> String {
+        match self.missing_key_format {
+            Some(format) => format(key),
+            None => key.to_string(),
+        }
+    }
+
+    /// The loaded locales to search, in order.
+    fn lookup_chain(&self) -> impl Iterator<Item = (&Locale, &TranslationMap)> {
+        let current = &self.current_locale;
+        let base = self.translations.keys().find(|locale| {
+            locale.language() == current.language()
+                && locale.region().is_none()
+                && locale.variant().is_none()
+                && *locale != current
+        });
+        [Some(current), base, self.fallback_locale.as_ref()]
+            .into_iter()
+            .flatten()
+            .filter_map(|locale| self.translations.get_key_value(locale))
+            .map(|(locale, map)| (locale, map.as_ref()))
+    }
+
+    fn resolve<'a>(
+        &'a self,
+        key: &str,
+        get: impl Fn(&'a TranslationMap, &Locale) -> Option<&'a str>,
+    ) -> Option<&'a str> {
+        let found = self
+            .lookup_chain()
+            .find_map(|(locale, map)| get(map, locale).map(|value| (locale, value)));
+        match found {
+            Some((locale, value)) => {
+                if locale != &self.current_locale {
+                    self.report_missing(key, Some(locale));
+                }
+                Some(value)
+            }
+            None => {
+                self.report_missing(key, None);
+                None
+            }
+        }
+    }
+
+    fn report_missing(&self, key: &str, used: Option<&Locale>) {
+        if !self.log_missing_keys
+            || !self
+                .reported_keys
+                .borrow_mut()
+                .insert((self.current_locale.clone(), key.to_string()))
+        {
+            return;
+        }
+        match used {
+            Some(used) => eprintln!(
+                "yororen_ui: no `{}` translation for `{key}`, using `{used}`",
+                self.current_locale
+            ),
+            None => eprintln!(
+                "yororen_ui: no translation for `{key}` in `{}` or its fallbacks",
+                self.current_locale
+            ),
+        }
     }
 }
 
@@ -195,9 +337,41 @@ impl TranslationMap {
     }
 }
 
+impl I18nFormatter for I18n {
+    /// A formatter bound to the current locale.
+    fn formatter(&self) -> Formatter {
+        Formatter::new(self.current_locale.clone())
+    }
+}
+
 /// Helper to access i18n from app context.
 pub trait I18nContext {
     fn i18n(&self) -> &I18n;
+
+    /// Format a number in the active locale.
+    fn format_number(&self, value: f64) -> String {
+        self.i18n().formatter().format_number(value)
+    }
+
+    /// Format a currency value in the active locale.
+    fn format_currency(&self, value: f64, currency: &'static str) -> String {
+        self.i18n().formatter().format_currency(value, currency)
+    }
+
+    /// Format a percentage in the active locale.
+    fn format_percent(&self, value: f64) -> String {
+        self.i18n().formatter().format_percent(value)
+    }
+
+    /// Format a date (Unix timestamp in seconds) in the active locale.
+    fn format_date(&self, timestamp: i64) -> String {
+        self.i18n().formatter().format_date(timestamp)
+    }
+
+    /// Join a list of items in the active locale, e.g. "A, B, and C".
+    fn format_list<S: AsRef<str>>(&self, items: &[S], style: ListStyle) -> String {
+        self.i18n().formatter().format_list(items, style)
+    }
 }
 
 impl I18nContext for App {
@@ -213,6 +387,9 @@ pub trait Translate {
 
     /// Translate with placeholders.
     fn t_with_args(&self, key: &str, args: &HashMap<&str, &str>) -> SharedString;
+
+    /// Translate the plural form for `n`, replacing `{count}` with `n`.
+    fn tn(&self, key: &str, n: usize) -> SharedString;
 }
 
 impl Translate for App {
@@ -220,7 +397,7 @@ impl Translate for App {
         let i18n = self.i18n();
         match i18n.t(key) {
             Some(s) => s.to_string().into(),
-            None => key.to_string().into(),
+            None => i18n.missing_key(key).into(),
         }
     }
 
@@ -228,11 +405,21 @@ impl Translate for App {
         let i18n = self.i18n();
         let base = match i18n.t(key) {
             Some(s) => s.to_string(),
-            None => key.to_string(),
+            None => i18n.missing_key(key),
         };
 
         replace_placeholders(&base, args).into()
     }
+
+    fn tn(&self, key: &str, n: usize) -> SharedString {
+        let i18n = self.i18n();
+        let base = match i18n.tn(key, n) {
+            Some(s) => s.to_string(),
+            None => i18n.missing_key(key),
+        };
+        let count = n.to_string();
+        replace_placeholders(&base, &HashMap::from([("count", count.as_str())])).into()
+    }
 }
 
 /// Replace placeholders in a string with values from the args map.
@@ -247,6 +434,10 @@ fn replace_placeholders(template: &str, args: &HashMap<&str, &str>) -> String {
 
 #[cfg(test)]
 mod tests {
+    use std::rc::Rc;
+
+    use gpui::{IntoElement, ParentElement, Render, TestAppContext, div};
+
     use super::*;
 
     #[test]
@@ -262,6 +453,87 @@ mod tests {
         assert_eq!(map.get("select.placeholder"), Some("Select…"));
     }
 
+    #[test]
+    fn test_set_locale_keeps_translations() {
+        let en = Locale::new("en").unwrap();
+        let zh = Locale::new("zh-CN").unwrap();
+
+        let mut en_map = TranslationMap::new();
+        en_map.insert("hello", "Hello");
+        let mut zh_map = TranslationMap::new();
+        zh_map.insert("hello", "你好");
+
+        let mut i18n = I18n::with_locale(en.clone());
+        i18n.load_translations(en.clone(), en_map);
+        i18n.load_translations(zh.clone(), zh_map);
+        assert_eq!(i18n.t("hello"), Some("Hello"));
+
+        i18n.set_locale(zh.clone());
+        assert_eq!(i18n.locale(), &zh);
+        assert_eq!(i18n.t("hello"), Some("你好"));
+
+        i18n.set_locale(en);
+        assert_eq!(i18n.t("hello"), Some("Hello"));
+    }
+
+    #[test]
+    fn test_tn_follows_current_locale() {
+        let mut items = TranslationMap::new();
+        items.insert("one", "one");
+        items.insert("few", "few");
+        items.insert("many", "many");
+        items.insert("other", "other");
+        let mut map = TranslationMap::new();
+        map.insert_nested("items", items);
+
+        let ru = Locale::new("ru").unwrap();
+        let mut i18n = I18n::with_locale(ru.clone());
+        i18n.load_translations(ru, map);
+        assert_eq!(i18n.tn("items", 21), Some("one"));
+        assert_eq!(i18n.tn("items", 22), Some("few"));
+        assert_eq!(i18n.tn("items", 25), Some("many"));
+        assert_eq!(i18n.tn("missing", 1), None);
+    }
+
+    #[test]
+    fn test_missing_keys_follow_fallback_chain() {
+        let pt_br = Locale::new("pt-BR").unwrap();
+        let pt = Locale::new("pt").unwrap();
+        let en = Locale::new("en").unwrap();
+
+        let mut br_map = TranslationMap::new();
+        br_map.insert("save", "Salvar");
+        let mut pt_map = TranslationMap::new();
+        pt_map.insert("save", "Guardar");
+        pt_map.insert("cancel", "Cancelar");
+        let mut en_map = TranslationMap::new();
+        en_map.insert("delete", "Delete");
+
+        let mut i18n = I18n::with_locale(pt_br.clone())
+            .with_fallback_locale(en.clone())
+            .log_missing_keys(false);
+        i18n.load_translations(pt_br, br_map);
+        i18n.load_translations(pt, pt_map);
+        i18n.load_translations(en, en_map);
+
+        assert_eq!(i18n.t("save"), Some("Salvar"));
+        assert_eq!(i18n.t("cancel"), Some("Cancelar"));
+        assert_eq!(i18n.t("delete"), Some("Delete"));
+        assert_eq!(i18n.t("missing"), None);
+        assert_eq!(i18n.missing_key("missing"), "missing");
+
+        let i18n = i18n.with_missing_key_format(|key| format!("⟦{key}⟧"));
+        assert_eq!(i18n.missing_key("missing"), "⟦missing⟧");
+    }
+
+    #[test]
+    fn test_fallback_locale_loads_embedded_translations() {
+        let i18n = I18n::with_locale(Locale::new("fr").unwrap())
+            .with_fallback_locale(Locale::new("en").unwrap())
+            .log_missing_keys(false);
+        assert!(i18n.t("items.other").is_some());
+    }
+
     #[test]
     fn test_replace_placeholders() {
         let template = "Hello {name}, you have {count} items";
@@ -272,4 +544,80 @@ mod tests {
         let result = replace_placeholders(template, &args);
         assert_eq!(result, "Hello World, you have 5 items");
     }
+
+    struct TestContext(I18n);
+
+    impl I18nContext for TestContext {
+        fn i18n(&self) -> &I18n {
+            &self.0
+        }
+    }
+
+    #[test]
+    fn test_context_formatter_uses_current_locale() {
+        let cx = TestContext(I18n::with_locale(Locale::new("en").unwrap()));
+        assert_eq!(cx.format_number(1234.5), "1,234.5");
+
+        let formatter = cx.i18n().formatter();
+        assert_eq!(formatter.format_number(1234.5), "1,234.5");
+    }
+
+    #[test]
+    fn test_context_formatter_follows_locale_change() {
+        let mut cx = TestContext(I18n::with_locale(Locale::new("en").unwrap()));
+        assert_eq!(cx.format_number(1234.5), "1,234.5");
+
+        cx.0.set_locale(Locale::new("fr").unwrap());
+        assert_eq!(cx.format_number(1234.5), "1 234,5");
+    }
+
+    struct Greeting {
+        rendered: Rc<RefCell<Vec<SharedString>>>,
+    }
+
+    impl Render for Greeting {
+        fn render(
+            &mut self,
+            _window: &mut gpui::Window,
+            cx: &mut gpui::Context<Self>,
+        ) -> impl IntoElement {
+            let text = cx.t("hello");
+            self.rendered.borrow_mut().push(text.clone());
+            div().child(text)
+        }
+    }
+
+    #[gpui::test]
+    fn test_switch_locale_keeps_loaded_bundles(cx: &mut TestAppContext) {
+        let en = Locale::new("en").unwrap();
+        let zh = Locale::new("zh-CN").unwrap();
+        cx.update(|cx| {
+            cx.set_global(I18n::with_locale(en.clone()));
+            // Loaded at runtime, after the global was installed.
+            cx.update_global::<I18n, _>(|i18n, _| {
+                let mut en_map = TranslationMap::new();
+                en_map.insert("hello", "Hello");
+                i18n.load_translations(en.clone(), en_map);
+                let mut zh_map = TranslationMap::new();
+                zh_map.insert("hello", "你好");
+                i18n.load_translations(zh.clone(), zh_map);
+            });
+        });
+        let rendered = Rc::new(RefCell::new(Vec::new()));
+        let (_, cx) = cx.add_window_view({
+            let rendered = rendered.clone();
+            |_, _| Greeting { rendered }
+        });
+        cx.run_until_parked();
+        assert_eq!(rendered.borrow().last().unwrap(), "Hello");
+
+        cx.update(|_, cx| I18n::switch_locale(&zh, cx));
+        cx.run_until_parked();
+        assert_eq!(rendered.borrow().last().unwrap(), "你好");
+
+        cx.update(|_, cx| I18n::switch_locale(&en, cx));
+        cx.run_until_parked();
+        assert_eq!(rendered.borrow().last().unwrap(), "Hello");
+        assert_eq!(cx.update(|_, cx| cx.i18n().locale().clone()), en);
+    }
 }
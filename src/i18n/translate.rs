@@ -27,28 +27,22 @@ pub enum PluralCategory {
 
 impl PluralCategory {
     /// Get the plural category for a number in a specific locale.
-    /// Uses simple rules based on language family.
+    /// Follows the CLDR rules for integer counts.
     pub fn for_number(n: u64, locale: &Locale) -> Self {
         let lang = locale.language();
+        let n10 = n % 10;
+        let n100 = n % 100;
 
-        // Use language-specific plural rules (simplified CLDR rules)
         match lang {
-            // Arabic: 0=zero, 1=one, 2=two, 3-10=few, 11-99=many, rest=other
-            "ar" => {
-                if n == 0 {
-                    PluralCategory::Zero
-                } else if n == 1 {
-                    PluralCategory::One
-                } else if n == 2 {
-                    PluralCategory::Two
-                } else if (3..=10).contains(&n) {
-                    PluralCategory::Few
-                } else if (11..=99).contains(&n) {
-                    PluralCategory::Many
-                } else {
-                    PluralCategory::Other
-                }
-            }
+            // Arabic: 0=zero, 1=one, 2=two, n%100 in 3..10=few, n%100 in 11..99=many
+            "ar" => match n {
+                0 => PluralCategory::Zero,
+                1 => PluralCategory::One,
+                2 => PluralCategory::Two,
+                _ if (3..=10).contains(&n100) => PluralCategory::Few,
+                _ if (11..=99).contains(&n100) => PluralCategory::Many,
+                _ => PluralCategory::Other,
+            },
 
             // Chinese, Japanese, Korean, Vietnamese: no plural
             "zh" | "ja" | "ko" | "vi" | "th" => PluralCategory::Other,
@@ -63,7 +57,7 @@ impl PluralCategory {
             }
 
             // German, Dutch: 1=one, rest=other
-            "de" | "nl" | "sv" | "da" | "no" | "fi" | "et" | "el" => {
+            "de" | "nl" | "sv" | "da" | "no" | "fi" | "et" | "el" | "bg" => {
                 if n == 1 {
                     PluralCategory::One
                 } else {
@@ -71,38 +65,66 @@ impl PluralCategory {
                 }
             }
 
-            // Polish: 1=one, 2-4=few, 5-21=other (simplified)
-            "pl" | "cs" | "sk" | "sl" | "uk" | "ru" | "bg" | "sr" | "hr" => {
-                if n == 1 {
+            // Russian, Ukrainian: 1, 21, 31=one; 2-4, 22-24=few; rest=many
+            "ru" | "uk" | "be" => {
+                if n10 == 1 && n100 != 11 {
                     PluralCategory::One
-                } else if (2..=4).contains(&n) {
+                } else if (2..=4).contains(&n10) && !(12..=14).contains(&n100) {
                     PluralCategory::Few
                 } else {
-                    PluralCategory::Other
+                    PluralCategory::Many
                 }
             }
 
-            // Romanian: 1=one, 2-19=few if not 1 (simplified)
-            "ro" | "hu" => {
+            // Polish: 1=one; 2-4, 22-24=few; rest=many
+            "pl" => {
                 if n == 1 {
                     PluralCategory::One
-                } else if (2..=19).contains(&n) {
+                } else if (2..=4).contains(&n10) && !(12..=14).contains(&n100) {
+                    PluralCategory::Few
+                } else {
+                    PluralCategory::Many
+                }
+            }
+
+            // Croatian, Serbian, Bosnian: like Russian, but the rest is other
+            "hr" | "sr" | "bs" => {
+                if n10 == 1 && n100 != 11 {
+                    PluralCategory::One
+                } else if (2..=4).contains(&n10) && !(12..=14).contains(&n100) {
                     PluralCategory::Few
                 } else {
                     PluralCategory::Other
                 }
             }
 
-            // English: 1=one, rest=other
-            "en" | "en-US" | "en-GB" => {
+            // Czech, Slovak: 1=one, 2-4=few, rest=other
+            "cs" | "sk" => match n {
+                1 => PluralCategory::One,
+                2..=4 => PluralCategory::Few,
+                _ => PluralCategory::Other,
+            },
+
+            // Slovenian: n%100 of 1=one, 2=two, 3-4=few, rest=other
+            "sl" => match n100 {
+                1 => PluralCategory::One,
+                2 => PluralCategory::Two,
+                3 | 4 => PluralCategory::Few,
+                _ => PluralCategory::Other,
+            },
+
+            // Romanian: 1=one, 0 and n%100 in 2..19=few, rest=other
+            "ro" => {
                 if n == 1 {
                     PluralCategory::One
+                } else if n == 0 || (2..=19).contains(&n100) {
+                    PluralCategory::Few
                 } else {
                     PluralCategory::Other
                 }
             }
 
-            // Default fallback to English rules
+            // English, Hungarian and default: 1=one, rest=other
             _ => {
                 if n == 1 {
                     PluralCategory::One
@@ -114,6 +136,20 @@ impl PluralCategory {
     }
 }
 
+/// Look up the plural form of `key` for `n` in `locale`.
+///
+/// Tries `key.<category>` first and falls back to `key.other`.
+pub(crate) fn plural_form<'a>(
+    map: &'a TranslationMap,
+    key: &str,
+    n: u64,
+    locale: &Locale,
+) -> Option<&'a str> {
+    let category = PluralCategory::for_number(n, locale);
+    map.get(&format!("{}.{}", key, category))
+        .or_else(|| map.get(&format!("{}.other", key)))
+}
+
 impl fmt::Display for PluralCategory {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -184,19 +220,10 @@ impl Translator for TranslationMap {
         self.get(key)
     }
 
+    /// A bare map doesn't know its locale, so this uses the default locale's
+    /// rules. Use [`I18n::tn`](super::I18n::tn) to follow the active locale.
     fn tn(&self, key: &str, n: usize) -> Option<&str> {
-        // Try plural form first: key.one, key.few, key.many, key.other
-        let category = PluralCategory::for_number(n as u64, &Locale::default());
-
-        // Try specific category
-        let plural_key = format!("{}.{}", key, category);
-        if let Some(value) = self.get(&plural_key) {
-            return Some(value);
-        }
-
-        // Fall back to "other" category
-        let other_key = format!("{}.other", key);
-        self.get(&other_key)
+        plural_form(self, key, n as u64, &Locale::default())
     }
 
     fn tf(&self, key: &str, args: &HashMap<&str, impl fmt::Display>) -> String {
@@ -292,6 +319,50 @@ mod tests {
         assert_eq!(PluralCategory::for_number(5, &locale), PluralCategory::Few);
     }
 
+    fn categories(lang: &str) -> Vec<PluralCategory> {
+        let locale = Locale::new(lang).unwrap();
+        [1, 2, 5, 21, 22, 25, 0, 11, 102, 111]
+            .into_iter()
+            .map(|n| PluralCategory::for_number(n, &locale))
+            .collect()
+    }
+
+    #[test]
+    fn test_plural_matrix() {
+        use PluralCategory::*;
+
+        // Counts: 1, 2, 5, 21, 22, 25, 0, 11, 102, 111
+        assert_eq!(
+            categories("ru"),
+            [One, Few, Many, One, Few, Many, Many, Many, Few, Many]
+        );
+        assert_eq!(
+            categories("uk"),
+            [One, Few, Many, One, Few, Many, Many, Many, Few, Many]
+        );
+        assert_eq!(
+            categories("pl"),
+            [One, Few, Many, Many, Few, Many, Many, Many, Few, Many]
+        );
+        assert_eq!(
+            categories("ar"),
+            [One, Two, Few, Many, Many, Many, Zero, Many, Other, Many]
+        );
+    }
+
+    #[test]
+    fn test_tn_falls_back_to_other() {
+        let mut items = TranslationMap::new();
+        items.insert("one", "{count} item");
+        items.insert("other", "{count} items");
+        let mut map = TranslationMap::new();
+        map.insert_nested("items", items);
+
+        assert_eq!(map.tn("items", 1), Some("{count} item"));
+        assert_eq!(map.tn("items", 5), Some("{count} items"));
+        assert_eq!(map.tn("missing", 5), None);
+    }
+
     #[test]
     fn test_translated_string_args() {
         let mut args: HashMap<&str, Box<dyn fmt::Display>> = HashMap::new();
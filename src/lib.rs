@@ -7,5 +7,6 @@ pub mod hover_refresh;
 pub mod i18n;
 pub mod notification;
 pub mod rtl;
+pub mod shortcuts;
 pub mod theme;
 pub mod widget;
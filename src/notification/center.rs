@@ -1,16 +1,21 @@
 use std::{
     collections::{HashMap, HashSet, VecDeque},
     sync::{Arc, Mutex},
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use chrono::{DateTime, Utc};
-use gpui::{AnyWindowHandle, AppContext, ClickEvent, Global, SharedString, Window};
+use gpui::{
+    AnyWindowHandle, AppContext, ClickEvent, Global, Pixels, Point, SharedString, Window, point,
+};
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use uuid::Uuid;
 
+use crate::a11y::{Politeness, announce};
 use crate::component::ToastKind;
+use crate::i18n::{I18nContext, Locale, defaults::DefaultPlaceholders};
+use crate::theme::ActionVariantKind;
 
 /// How a notification should be dismissed.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -27,8 +32,128 @@ impl Default for DismissStrategy {
     }
 }
 
+/// When the notification host moves keyboard focus to a new actionable notification.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NotificationFocus {
+    /// Never move focus; the action stays reachable with Tab.
+    Never,
+    /// Move focus only when nothing else in the window is focused, so typing
+    /// into a field is never interrupted.
+    #[default]
+    WhenIdle,
+    /// Always move focus to the newest actionable notification.
+    Always,
+}
+
+impl NotificationFocus {
+    /// Returns whether a newly shown notification should take focus.
+    pub fn should_focus(self, window_has_focus: bool) -> bool {
+        match self {
+            Self::Never => false,
+            Self::WhenIdle => !window_has_focus,
+            Self::Always => true,
+        }
+    }
+}
+
+/// Where the notification host places its toast stack.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ToastPosition {
+    TopLeft,
+    TopCenter,
+    #[default]
+    TopRight,
+    BottomLeft,
+    BottomCenter,
+    BottomRight,
+}
+
+impl ToastPosition {
+    /// Whether the stack is anchored to the top edge. New toasts are added at
+    /// the anchored edge: on top for top positions, at the bottom otherwise.
+    pub fn is_top(self) -> bool {
+        matches!(self, Self::TopLeft | Self::TopCenter | Self::TopRight)
+    }
+
+    /// Offset a new toast slides in from, `distance` away towards the nearest
+    /// window edge: the side for corner positions, the top or bottom for
+    /// centered ones.
+    pub fn slide_offset(self, distance: Pixels) -> Point<Pixels> {
+        let zero = Pixels::ZERO;
+        match self {
+            Self::TopLeft | Self::BottomLeft => point(-distance, zero),
+            Self::TopRight | Self::BottomRight => point(distance, zero),
+            Self::TopCenter => point(zero, -distance),
+            Self::BottomCenter => point(zero, distance),
+        }
+    }
+}
+
+/// A button on a notification.
+///
+/// The label and variant are persisted with sticky notifications; the callback
+/// is not.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct NotificationAction {
+    pub label: SharedString,
+    pub variant: ActionVariantKind,
+
+    /// Whether activating the action dismisses the notification. `None` dismisses
+    /// unless the notification waits for a manual dismiss.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dismiss: Option<bool>,
+
+    #[serde(skip)]
+    on_click: Option<ClickCb>,
+}
+
+impl std::fmt::Debug for NotificationAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NotificationAction")
+            .field("label", &self.label)
+            .field("variant", &self.variant)
+            .field("dismiss", &self.dismiss)
+            .finish_non_exhaustive()
+    }
+}
+
+impl NotificationAction {
+    pub fn new(label: impl Into<SharedString>) -> Self {
+        Self {
+            label: label.into(),
+            variant: ActionVariantKind::Neutral,
+            dismiss: None,
+            on_click: None,
+        }
+    }
+
+    /// Sets the button style. The first [`ActionVariantKind::Primary`] action is
+    /// emphasized and takes focus when the notification does.
+    pub fn variant(mut self, variant: ActionVariantKind) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    /// Sets whether activating the action dismisses the notification.
+    pub fn dismiss(mut self, dismiss: bool) -> Self {
+        self.dismiss = Some(dismiss);
+        self
+    }
+
+    /// Runs `f` when the action is activated. Without one, the notification's
+    /// click callback from [`NotificationCenter::notify_with_callbacks`] runs.
+    pub fn on_click<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&Notification, &ClickEvent, &mut Window, &mut gpui::App) + 'static,
+    {
+        self.on_click = Some(Arc::new(f));
+        self
+    }
+}
+
 /// A single notification payload.
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(from = "StoredNotification")]
 pub struct Notification {
     pub id: Uuid,
     pub created_at: DateTime<Utc>,
@@ -45,12 +170,71 @@ pub struct Notification {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub payload: Option<JsonValue>,
 
-    /// Optional label for the action that occurs on click.
-    pub action_label: Option<SharedString>,
+    /// Buttons rendered below the message, in order.
+    ///
+    /// This replaces the former `action_label: Option<SharedString>` field. A
+    /// label becomes a primary action through [`Notification::action_label`], and
+    /// persisted notifications that still carry `action_label` load with it as
+    /// their only action.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub actions: Vec<NotificationAction>,
 
     /// If true, the notification is retained across persistence loads.
     /// Useful for long-running tasks or important messages.
     pub sticky: bool,
+
+    /// Whether the host shows a bar depleting until the auto-dismiss.
+    #[serde(default = "default_progress_bar")]
+    pub progress_bar: bool,
+}
+
+fn default_progress_bar() -> bool {
+    true
+}
+
+/// The persisted form of a [`Notification`], which also accepts the
+/// `action_label` field written before notifications had several actions.
+#[derive(Deserialize)]
+struct StoredNotification {
+    id: Uuid,
+    created_at: DateTime<Utc>,
+    title: Option<SharedString>,
+    message: SharedString,
+    kind: ToastKind,
+    dismiss: DismissStrategy,
+    #[serde(default)]
+    payload: Option<JsonValue>,
+    #[serde(default)]
+    actions: Vec<NotificationAction>,
+    #[serde(default)]
+    action_label: Option<SharedString>,
+    sticky: bool,
+    #[serde(default = "default_progress_bar")]
+    progress_bar: bool,
+}
+
+impl From<StoredNotification> for Notification {
+    fn from(stored: StoredNotification) -> Self {
+        let mut notification = Self {
+            id: stored.id,
+            created_at: stored.created_at,
+            title: stored.title,
+            message: stored.message,
+            kind: stored.kind,
+            dismiss: stored.dismiss,
+            payload: stored.payload,
+            actions: stored.actions,
+            sticky: stored.sticky,
+            progress_bar: stored.progress_bar,
+        };
+        if let Some(label) = stored
+            .action_label
+            .filter(|_| notification.actions.is_empty())
+        {
+            notification = notification.action_label(label);
+        }
+        notification
+    }
 }
 
 impl Notification {
@@ -63,8 +247,9 @@ impl Notification {
             kind: ToastKind::Neutral,
             dismiss: DismissStrategy::default(),
             payload: None,
-            action_label: None,
+            actions: Vec::new(),
             sticky: false,
+            progress_bar: true,
         }
     }
 
@@ -83,8 +268,16 @@ impl Notification {
         self
     }
 
-    pub fn action_label(mut self, label: impl Into<SharedString>) -> Self {
-        self.action_label = Some(label.into());
+    /// Adds a primary action that runs the notification's click callback.
+    ///
+    /// Shorthand for `.action(NotificationAction::new(label).variant(ActionVariantKind::Primary))`.
+    pub fn action_label(self, label: impl Into<SharedString>) -> Self {
+        self.action(NotificationAction::new(label).variant(ActionVariantKind::Primary))
+    }
+
+    /// Adds an action button.
+    pub fn action(mut self, action: NotificationAction) -> Self {
+        self.actions.push(action);
         self
     }
 
@@ -97,6 +290,120 @@ impl Notification {
         self.sticky = sticky;
         self
     }
+
+    /// Shows a bar along the bottom edge that depletes until the notification
+    /// is dismissed automatically (default: true). Only non-sticky notifications
+    /// with [`DismissStrategy::After`] have one.
+    pub fn progress_bar(mut self, show: bool) -> Self {
+        self.progress_bar = show;
+        self
+    }
+
+    /// Whether the host should render the auto-dismiss progress bar.
+    pub fn shows_progress_bar(&self) -> bool {
+        self.progress_bar && !self.sticky && matches!(self.dismiss, DismissStrategy::After { .. })
+    }
+
+    /// Whether the notification renders focusable action buttons.
+    pub fn is_actionable(&self) -> bool {
+        !self.actions.is_empty()
+    }
+
+    /// Index of the emphasized action: the first primary one, else the first.
+    pub fn primary_action(&self) -> Option<usize> {
+        self.actions
+            .iter()
+            .position(|action| action.variant == ActionVariantKind::Primary)
+            .or_else(|| (!self.actions.is_empty()).then_some(0))
+    }
+
+    /// ARIA attributes announcing this notification.
+    ///
+    /// Errors and warnings interrupt as alerts; everything else is announced politely.
+    pub fn aria_attrs(&self) -> Vec<(String, String)> {
+        crate::a11y::notification::attrs(self.politeness() == Politeness::Assertive)
+    }
+
+    /// How screen readers announce this notification when it is shown.
+    pub fn politeness(&self) -> Politeness {
+        match self.kind {
+            ToastKind::Error | ToastKind::Warning => Politeness::Assertive,
+            _ => Politeness::Polite,
+        }
+    }
+
+    /// The text announced to screen readers: the title, if any, then the message.
+    pub fn announcement(&self) -> SharedString {
+        match &self.title {
+            Some(title) => format!("{}: {}", title, self.message).into(),
+            None => self.message.clone(),
+        }
+    }
+}
+
+/// Limits how many notifications are shown per time interval.
+///
+/// Notifications beyond the limit are dropped, or collapsed into a single
+/// localized "+N more" summary notification, of the kind of the first one held
+/// back, when `summarize` is set (the default).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RateLimit {
+    pub max_per_interval: usize,
+    pub interval: Duration,
+    pub summarize: bool,
+}
+
+impl RateLimit {
+    pub fn new(max_per_interval: usize, interval: Duration) -> Self {
+        Self {
+            max_per_interval: max_per_interval.max(1),
+            interval,
+            summarize: true,
+        }
+    }
+
+    pub fn summarize(mut self, summarize: bool) -> Self {
+        self.summarize = summarize;
+        self
+    }
+}
+
+enum Admission {
+    Show,
+    Suppress { suppressed: usize },
+}
+
+/// The current rate-limit interval: what was shown and what was held back.
+#[derive(Default)]
+struct RateWindow {
+    started: Option<Instant>,
+    shown: usize,
+    suppressed: usize,
+    summary: Option<Uuid>,
+}
+
+impl RateWindow {
+    fn admit(&mut self, limit: &RateLimit, now: Instant) -> Admission {
+        let expired = self
+            .started
+            .is_none_or(|started| now.saturating_duration_since(started) >= limit.interval);
+        if expired {
+            *self = Self {
+                started: Some(now),
+                ..Self::default()
+            };
+        }
+
+        if self.shown < limit.max_per_interval {
+            self.shown += 1;
+            Admission::Show
+        } else {
+            self.suppressed += 1;
+            Admission::Suppress {
+                suppressed: self.suppressed,
+            }
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
@@ -119,8 +426,14 @@ struct State {
 
     // config
     max_queue_len: usize,
+    max_visible: Option<usize>,
+    overflow_summary: bool,
     persist_enabled: bool,
     persist_key: SharedString,
+    focus_policy: NotificationFocus,
+    position: ToastPosition,
+    rate_limit: Option<RateLimit>,
+    rate_window: RateWindow,
 
     // host registration
     host_window: Option<AnyWindowHandle>,
@@ -133,8 +446,10 @@ struct State {
     on_click: HashMap<Uuid, ClickCb>,
     on_dismiss: HashMap<Uuid, DismissCb>,
 
-    // used to avoid re-scheduling auto-dismiss for the same notification
-    scheduled_auto_dismiss: HashSet<Uuid>,
+    // auto-dismiss timers; also used to avoid re-scheduling the same notification
+    timers: HashMap<Uuid, DismissTimer>,
+    // visible notifications already announced to screen readers
+    announced: HashSet<Uuid>,
 }
 
 impl Global for NotificationCenter {}
@@ -145,6 +460,7 @@ impl NotificationCenter {
         Self {
             state: Arc::new(Mutex::new(State {
                 max_queue_len: 5,
+                overflow_summary: true,
                 persist_enabled: true,
                 persist_key: "yororen_ui:notifications".into(),
                 persisted_state: None,
@@ -160,6 +476,37 @@ impl NotificationCenter {
         Self::trim_queue_locked(&mut state);
     }
 
+    /// Shows at most `max` toasts at a time; `None` shows every queued one.
+    ///
+    /// The rest wait, oldest first, and are promoted as visible ones are
+    /// dismissed. Their auto-dismiss timers only start once they are visible.
+    /// Sticky notifications count against the cap, while notifications waiting
+    /// for a manual dismiss take at most `max - 1` slots so timed ones keep
+    /// flowing. [`set_max_queue_len`](Self::set_max_queue_len) still caps
+    /// visible and waiting notifications together.
+    pub fn set_max_visible(&self, max: Option<usize>, cx: &mut gpui::App) {
+        self.state.lock().unwrap().max_visible = max.map(|max| max.max(1));
+        self.refresh_host(cx);
+        self.schedule_visible(cx);
+        self.announce_visible(cx);
+    }
+
+    pub fn max_visible(&self) -> Option<usize> {
+        self.state.lock().unwrap().max_visible
+    }
+
+    /// Sets whether the host shows a "+N more" chip for waiting notifications.
+    ///
+    /// Default: `true`.
+    pub fn set_overflow_summary(&self, enabled: bool, cx: &mut gpui::App) {
+        self.state.lock().unwrap().overflow_summary = enabled;
+        self.refresh_host(cx);
+    }
+
+    pub fn overflow_summary(&self) -> bool {
+        self.state.lock().unwrap().overflow_summary
+    }
+
     pub fn set_persistence(&self, enabled: bool, key: impl Into<SharedString>) {
         let mut state = self.state.lock().unwrap();
         state.persist_enabled = enabled;
@@ -171,6 +518,37 @@ impl NotificationCenter {
         (state.persist_enabled, state.persist_key.clone())
     }
 
+    /// Sets when the host moves focus to new actionable notifications.
+    ///
+    /// Default: [`NotificationFocus::WhenIdle`].
+    pub fn set_focus_policy(&self, policy: NotificationFocus) {
+        self.state.lock().unwrap().focus_policy = policy;
+    }
+
+    pub fn focus_policy(&self) -> NotificationFocus {
+        self.state.lock().unwrap().focus_policy
+    }
+
+    /// Sets where hosts place the toast stack.
+    ///
+    /// Default: [`ToastPosition::TopRight`]. A host's own
+    /// [`position`](crate::notification::NotificationHost::position) takes precedence.
+    pub fn set_position(&self, position: ToastPosition, cx: &mut gpui::App) {
+        self.state.lock().unwrap().position = position;
+        self.refresh_host(cx);
+    }
+
+    pub fn position(&self) -> ToastPosition {
+        self.state.lock().unwrap().position
+    }
+
+    /// Limits how many notifications are shown per interval; `None` disables limiting.
+    pub fn set_rate_limit(&self, limit: Option<RateLimit>) {
+        let mut state = self.state.lock().unwrap();
+        state.rate_limit = limit;
+        state.rate_window = RateWindow::default();
+    }
+
     pub fn register_host_window(&self, window: AnyWindowHandle) {
         let mut state = self.state.lock().unwrap();
         state.host_window = Some(window);
@@ -183,21 +561,55 @@ impl NotificationCenter {
         }
     }
 
+    /// Shows a notification and returns its id.
+    ///
+    /// With a [`RateLimit`] set, a notification over the limit is not shown; the
+    /// id is still returned but no longer refers to a queued item.
     pub fn notify(&self, n: Notification, cx: &mut gpui::App) -> Uuid {
         let id = n.id;
+        self.notify_batched([n], cx);
+        id
+    }
 
-        {
+    /// Shows several notifications at once, refreshing the host a single time.
+    ///
+    /// Returns the ids of the notifications that were shown, in order.
+    pub fn notify_batched(
+        &self,
+        items: impl IntoIterator<Item = Notification>,
+        cx: &mut gpui::App,
+    ) -> Vec<Uuid> {
+        let now = Instant::now();
+        let locale = cx.i18n().locale().clone();
+        let (shown, to_schedule) = {
             let mut state = self.state.lock().unwrap();
-            state.queue.push_back(n);
-            Self::trim_queue_locked(&mut state);
-        }
+            let mut shown = Vec::new();
+            let mut to_schedule = Vec::new();
+            for n in items {
+                let id = n.id;
+                if let Some(queued) = Self::enqueue_locked(&mut state, n, now, &locale) {
+                    if queued == id {
+                        shown.push(id);
+                    }
+                    to_schedule.push(queued);
+                }
+            }
+            (shown, to_schedule)
+        };
 
         self.persist(cx);
         self.refresh_host(cx);
-        self.maybe_schedule_auto_dismiss(id, cx);
-        id
+        self.announce_visible(cx);
+        if !to_schedule.is_empty() {
+            self.schedule_visible(cx);
+        }
+        shown
     }
 
+    /// Shows a notification with callbacks and returns its id.
+    ///
+    /// `on_click` runs when the notification is clicked, and for actions without
+    /// a callback of their own; `on_dismiss` runs when the user dismisses it.
     pub fn notify_with_callbacks(
         &self,
         n: Notification,
@@ -207,6 +619,9 @@ impl NotificationCenter {
     ) -> Uuid {
         let id = self.notify(n, cx);
         let mut state = self.state.lock().unwrap();
+        if !state.queue.iter().any(|n| n.id == id) {
+            return id;
+        }
         if let Some(cb) = on_click {
             state.on_click.insert(id, cb);
         }
@@ -222,11 +637,13 @@ impl NotificationCenter {
             state.queue.retain(|n| n.id != id);
             state.on_click.remove(&id);
             state.on_dismiss.remove(&id);
-            state.scheduled_auto_dismiss.remove(&id);
+            state.timers.remove(&id);
         }
 
         self.persist(cx);
         self.refresh_host(cx);
+        self.schedule_visible(cx);
+        self.announce_visible(cx);
     }
 
     pub fn clear(&self, cx: &mut gpui::App) {
@@ -235,28 +652,120 @@ impl NotificationCenter {
             state.queue.clear();
             state.on_click.clear();
             state.on_dismiss.clear();
-            state.scheduled_auto_dismiss.clear();
+            state.timers.clear();
         }
         self.persist(cx);
         self.refresh_host(cx);
     }
 
+    /// All queued notifications, visible and waiting, oldest first.
     pub fn items(&self) -> Vec<Notification> {
         let state = self.state.lock().unwrap();
         state.queue.iter().cloned().collect()
     }
 
+    /// The notifications the host shows, oldest first. See
+    /// [`set_max_visible`](Self::set_max_visible).
+    pub fn visible_items(&self) -> Vec<Notification> {
+        let state = self.state.lock().unwrap();
+        visible_indices(&state.queue, state.max_visible)
+            .into_iter()
+            .map(|ix| state.queue[ix].clone())
+            .collect()
+    }
+
+    /// How many notifications are waiting for a visible slot.
+    pub fn pending_count(&self) -> usize {
+        let state = self.state.lock().unwrap();
+        state.queue.len() - visible_indices(&state.queue, state.max_visible).len()
+    }
+
+    /// Returns the most recent visible notification with an action button.
+    pub fn newest_actionable(&self) -> Option<Uuid> {
+        self.visible_items()
+            .into_iter()
+            .rev()
+            .find(|n| n.is_actionable())
+            .map(|n| n.id)
+    }
+
+    fn click_target(&self, id: Uuid) -> Option<(Notification, ClickCb)> {
+        let state = self.state.lock().unwrap();
+        let n = state.queue.iter().find(|n| n.id == id).cloned()?;
+        let cb = state.on_click.get(&id).cloned()?;
+        Some((n, cb))
+    }
+
     pub(crate) fn click(&self, id: Uuid, ev: &ClickEvent, window: &mut Window, cx: &mut gpui::App) {
-        let (n, cb) = {
+        if let Some((n, cb)) = self.click_target(id) {
+            cb(&n, ev, window, cx);
+        }
+    }
+
+    /// Runs the notification's action, then dismisses it unless it waits for a manual dismiss.
+    ///
+    /// Used for both pointer clicks and Enter on a focused action button.
+    pub(crate) fn activate(
+        &self,
+        id: Uuid,
+        ev: &ClickEvent,
+        window: &mut Window,
+        cx: &mut gpui::App,
+    ) {
+        let dismiss = {
             let state = self.state.lock().unwrap();
-            let n = state.queue.iter().find(|n| n.id == id).cloned();
-            let cb = state.on_click.get(&id).cloned();
-            (n, cb)
+            state
+                .queue
+                .iter()
+                .find(|n| n.id == id)
+                .map(|n| n.dismiss.clone())
         };
 
-        if let (Some(n), Some(cb)) = (n, cb) {
+        self.click(id, ev, window, cx);
+        if matches!(dismiss, Some(DismissStrategy::After { .. })) {
+            self.dismiss_from_ui(id, window, cx);
+        }
+    }
+
+    /// The notification, the callback to run and whether to dismiss afterwards
+    /// for action `index` of `id`.
+    fn action_target(
+        &self,
+        id: Uuid,
+        index: usize,
+    ) -> Option<(Notification, Option<ClickCb>, bool)> {
+        let state = self.state.lock().unwrap();
+        let n = state.queue.iter().find(|n| n.id == id).cloned()?;
+        let action = n.actions.get(index)?;
+        let cb = action
+            .on_click
+            .clone()
+            .or_else(|| state.on_click.get(&id).cloned());
+        let dismiss = action
+            .dismiss
+            .unwrap_or(n.dismiss != DismissStrategy::Manual);
+        Some((n, cb, dismiss))
+    }
+
+    /// Runs action `index` of the notification, then dismisses it through the
+    /// same path as the close button if the action dismisses.
+    pub(crate) fn activate_action(
+        &self,
+        id: Uuid,
+        index: usize,
+        ev: &ClickEvent,
+        window: &mut Window,
+        cx: &mut gpui::App,
+    ) {
+        let Some((n, cb, dismiss)) = self.action_target(id, index) else {
+            return;
+        };
+        if let Some(cb) = cb {
             cb(&n, ev, window, cx);
         }
+        if dismiss {
+            self.dismiss_from_ui(id, window, cx);
+        }
     }
 
     pub(crate) fn dismiss_from_ui(&self, id: Uuid, window: &mut Window, cx: &mut gpui::App) {
@@ -304,16 +813,15 @@ impl NotificationCenter {
         };
 
         if should_load {
-            let ids_to_schedule = {
+            {
                 let mut state = self.state.lock().unwrap();
                 state.queue = loaded_snapshot.items.into_iter().collect();
                 Self::trim_queue_locked(&mut state);
-                state.queue.iter().map(|n| n.id).collect::<Vec<_>>()
-            };
-
-            for id in ids_to_schedule {
-                self.maybe_schedule_auto_dismiss(id, cx);
+                // Restored notifications were announced when first shown.
+                Self::newly_visible_locked(&mut state);
             }
+
+            self.schedule_visible(cx);
         }
     }
 
@@ -322,40 +830,79 @@ impl NotificationCenter {
         state.persisted_state = None;
     }
 
+    /// Starts auto-dismiss timers for visible notifications that don't have one yet.
+    fn schedule_visible(&self, cx: &mut gpui::App) {
+        for n in self.visible_items() {
+            self.maybe_schedule_auto_dismiss(n.id, cx);
+        }
+    }
+
+    /// Announces notifications that became visible since the last call, so
+    /// waiting ones are announced when promoted rather than when queued.
+    fn announce_visible(&self, cx: &mut gpui::App) {
+        let announcements = {
+            let mut state = self.state.lock().unwrap();
+            Self::newly_visible_locked(&mut state)
+        };
+        for (message, politeness) in announcements {
+            announce(cx, message, politeness);
+        }
+    }
+
+    /// Marks the visible notifications as announced, returning the
+    /// announcements of those that weren't yet.
+    fn newly_visible_locked(state: &mut State) -> Vec<(SharedString, Politeness)> {
+        let visible = visible_indices(&state.queue, state.max_visible);
+        let queue = &state.queue;
+        state
+            .announced
+            .retain(|id| queue.iter().any(|n| n.id == *id));
+        visible
+            .into_iter()
+            .map(|ix| &queue[ix])
+            .filter(|n| state.announced.insert(n.id))
+            .map(|n| (n.announcement(), n.politeness()))
+            .collect()
+    }
+
     fn maybe_schedule_auto_dismiss(&self, id: Uuid, cx: &mut gpui::App) {
-        let (dismiss, host_window, already_scheduled) = {
+        {
             let mut state = self.state.lock().unwrap();
+            if state.timers.contains_key(&id) {
+                return;
+            }
             let Some(n) = state.queue.iter().find(|n| n.id == id) else {
                 return;
             };
-            let dismiss = n.dismiss.clone();
-            let host = state.host_window;
-            let already = state.scheduled_auto_dismiss.contains(&id);
-            if !already {
-                state.scheduled_auto_dismiss.insert(id);
+            let DismissStrategy::After { duration_ms } = n.dismiss else {
+                return;
+            };
+            // Require a host window for correctness: we don't want to spawn tasks in a context
+            // where there is no window rendering to reflect the changes.
+            if state.host_window.is_none() {
+                return;
             }
-            (dismiss, host, already)
-        };
-
-        if already_scheduled {
-            return;
-        }
-
-        let DismissStrategy::After { duration_ms } = dismiss else {
-            return;
-        };
-
-        // Require a host window for correctness: we don't want to spawn tasks in a context
-        // where there is no window rendering to reflect the changes.
-        if host_window.is_none() {
-            return;
+            let timer = DismissTimer::new(Duration::from_millis(duration_ms), Instant::now());
+            state.timers.insert(id, timer);
         }
 
         let this = self.clone();
         cx.spawn(async move |cx| {
-            cx.background_executor()
-                .timer(Duration::from_millis(duration_ms))
-                .await;
+            loop {
+                let wait = {
+                    let state = this.state.lock().unwrap();
+                    match state.timers.get(&id) {
+                        Some(timer) => timer.remaining(Instant::now()),
+                        None => return,
+                    }
+                };
+                match wait {
+                    Some(remaining) if remaining.is_zero() => break,
+                    Some(remaining) => cx.background_executor().timer(remaining).await,
+                    // Paused: check again shortly.
+                    None => cx.background_executor().timer(PAUSED_POLL).await,
+                }
+            }
             cx.update(|cx| {
                 this.dismiss(id, cx);
             })
@@ -364,6 +911,28 @@ impl NotificationCenter {
         .detach();
     }
 
+    /// Pauses the auto-dismiss timer of `id`, e.g. while the pointer is over it.
+    pub fn pause_auto_dismiss(&self, id: Uuid) {
+        if let Some(timer) = self.state.lock().unwrap().timers.get_mut(&id) {
+            timer.pause(Instant::now());
+        }
+    }
+
+    /// Resumes a timer paused with [`pause_auto_dismiss`](Self::pause_auto_dismiss).
+    pub fn resume_auto_dismiss(&self, id: Uuid) {
+        if let Some(timer) = self.state.lock().unwrap().timers.get_mut(&id) {
+            timer.resume(Instant::now());
+        }
+    }
+
+    /// Fraction of the auto-dismiss time left for `id`, from `1.0` down to `0.0`,
+    /// and whether the timer is running. `None` if it has no timer.
+    pub fn auto_dismiss_progress(&self, id: Uuid) -> Option<(f32, bool)> {
+        let state = self.state.lock().unwrap();
+        let timer = state.timers.get(&id)?;
+        Some((timer.fraction_left(Instant::now()), timer.is_running()))
+    }
+
     fn refresh_host(&self, cx: &mut gpui::App) {
         let host = { self.state.lock().unwrap().host_window };
         if let Some(host) = host {
@@ -406,12 +975,53 @@ impl NotificationCenter {
         });
     }
 
+    /// Queues `n`, applying the rate limit.
+    ///
+    /// Returns the id of a newly queued notification: `n` itself, or a fresh summary
+    /// standing in for suppressed items. Returns `None` if nothing new was queued.
+    fn enqueue_locked(
+        state: &mut State,
+        n: Notification,
+        now: Instant,
+        locale: &Locale,
+    ) -> Option<Uuid> {
+        let admission = match &state.rate_limit {
+            Some(limit) => {
+                let limit = limit.clone();
+                let admission = state.rate_window.admit(&limit, now);
+                (limit.summarize, admission)
+            }
+            None => (false, Admission::Show),
+        };
+
+        let n = match admission {
+            (_, Admission::Show) => n,
+            (false, Admission::Suppress { .. }) => return None,
+            (true, Admission::Suppress { suppressed }) => {
+                let summary_id = state.rate_window.summary;
+                if let Some(summary) = state.queue.iter_mut().find(|q| Some(q.id) == summary_id) {
+                    summary.message = DefaultPlaceholders::more_count(locale, suppressed).into();
+                    return None;
+                }
+                let message = DefaultPlaceholders::more_count(locale, suppressed);
+                let summary = Notification::new(message).kind(n.kind);
+                state.rate_window.summary = Some(summary.id);
+                summary
+            }
+        };
+
+        let id = n.id;
+        state.queue.push_back(n);
+        Self::trim_queue_locked(state);
+        Some(id)
+    }
+
     fn trim_queue_locked(state: &mut State) {
         while state.queue.len() > state.max_queue_len {
             if let Some(removed) = state.queue.pop_front() {
                 state.on_click.remove(&removed.id);
                 state.on_dismiss.remove(&removed.id);
-                state.scheduled_auto_dismiss.remove(&removed.id);
+                state.timers.remove(&removed.id);
             }
         }
     }
@@ -423,10 +1033,204 @@ impl Default for NotificationCenter {
     }
 }
 
+/// How often a paused auto-dismiss timer checks whether it was resumed.
+const PAUSED_POLL: Duration = Duration::from_millis(100);
+
+/// Time left until a notification is dismissed automatically; it can be paused.
+#[derive(Clone, Copy, Debug)]
+struct DismissTimer {
+    total: Duration,
+    /// Time run before the current stretch.
+    elapsed: Duration,
+    /// When the current stretch started; `None` while paused.
+    resumed_at: Option<Instant>,
+}
+
+impl DismissTimer {
+    fn new(total: Duration, now: Instant) -> Self {
+        Self {
+            total,
+            elapsed: Duration::ZERO,
+            resumed_at: Some(now),
+        }
+    }
+
+    fn is_running(&self) -> bool {
+        self.resumed_at.is_some()
+    }
+
+    fn elapsed(&self, now: Instant) -> Duration {
+        let running = self
+            .resumed_at
+            .map(|at| now.saturating_duration_since(at))
+            .unwrap_or_default();
+        (self.elapsed + running).min(self.total)
+    }
+
+    /// Time left, or `None` while paused.
+    fn remaining(&self, now: Instant) -> Option<Duration> {
+        self.resumed_at?;
+        Some(self.total - self.elapsed(now))
+    }
+
+    fn fraction_left(&self, now: Instant) -> f32 {
+        if self.total.is_zero() {
+            return 0.0;
+        }
+        1.0 - self.elapsed(now).as_secs_f32() / self.total.as_secs_f32()
+    }
+
+    fn pause(&mut self, now: Instant) {
+        if self.resumed_at.is_some() {
+            self.elapsed = self.elapsed(now);
+            self.resumed_at = None;
+        }
+    }
+
+    fn resume(&mut self, now: Instant) {
+        if self.resumed_at.is_none() {
+            self.resumed_at = Some(now);
+        }
+    }
+}
+
+/// Indices into `queue` of the notifications to show, oldest first.
+///
+/// While a timed notification is queued, manual-dismiss notifications fill at
+/// most `max - 1` slots, so a stack of them cannot hold it back forever.
+fn visible_indices(queue: &VecDeque<Notification>, max_visible: Option<usize>) -> Vec<usize> {
+    let Some(max) = max_visible else {
+        return (0..queue.len()).collect();
+    };
+    let timed_queued = queue.iter().any(|n| n.dismiss != DismissStrategy::Manual);
+    let max_manual = if timed_queued {
+        max.saturating_sub(1)
+    } else {
+        max
+    };
+    let mut visible = Vec::new();
+    let mut manual = 0;
+    for (ix, n) in queue.iter().enumerate() {
+        if visible.len() == max {
+            break;
+        }
+        if n.dismiss == DismissStrategy::Manual {
+            if manual == max_manual {
+                continue;
+            }
+            manual += 1;
+        }
+        visible.push(ix);
+    }
+    visible
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn toast_position_anchors_and_slides_from_nearest_edge() {
+        assert!(ToastPosition::TopCenter.is_top());
+        assert!(!ToastPosition::BottomLeft.is_top());
+        let d = gpui::px(24.);
+        assert_eq!(
+            ToastPosition::TopRight.slide_offset(d),
+            point(d, Pixels::ZERO)
+        );
+        assert_eq!(
+            ToastPosition::BottomLeft.slide_offset(d),
+            point(-d, Pixels::ZERO)
+        );
+        assert_eq!(
+            ToastPosition::BottomCenter.slide_offset(d),
+            point(Pixels::ZERO, d)
+        );
+        assert_eq!(
+            ToastPosition::TopCenter.slide_offset(d),
+            point(Pixels::ZERO, -d)
+        );
+    }
+
+    #[test]
+    fn max_visible_queues_the_rest() {
+        let timed = |m: &str| Notification::new(m.to_string());
+        let manual = |m: &str| Notification::new(m.to_string()).dismiss(DismissStrategy::Manual);
+        let queue: VecDeque<_> = [timed("1"), timed("2"), timed("3")].into();
+        assert_eq!(visible_indices(&queue, None), [0, 1, 2]);
+        assert_eq!(visible_indices(&queue, Some(2)), [0, 1]);
+
+        // Manual toasts leave one slot for the timed ones behind them.
+        let queue: VecDeque<_> = [manual("a"), manual("b"), manual("c"), timed("4")].into();
+        assert_eq!(visible_indices(&queue, Some(3)), [0, 1, 3]);
+        assert_eq!(visible_indices(&queue, Some(1)), [3]);
+
+        // With nothing timed waiting, manual toasts may take every slot.
+        let manual_only: VecDeque<_> = [manual("a"), manual("b")].into();
+        assert_eq!(visible_indices(&manual_only, Some(1)), [0]);
+
+        let center = NotificationCenter::new();
+        {
+            let mut state = center.state.lock().unwrap();
+            state.max_visible = Some(2);
+            state.queue = queue;
+        }
+        assert_eq!(center.visible_items().len(), 2);
+        assert_eq!(center.pending_count(), 2);
+    }
+
+    #[test]
+    fn queued_notifications_are_announced_when_promoted() {
+        let first = Notification::new("first");
+        let second = Notification::new("second");
+        let first_id = first.id;
+        let mut state = State {
+            max_visible: Some(1),
+            queue: [first, second].into(),
+            ..State::default()
+        };
+
+        let announced = NotificationCenter::newly_visible_locked(&mut state);
+        assert_eq!(announced.len(), 1);
+        assert_eq!(announced[0].0.as_str(), "first");
+        assert!(NotificationCenter::newly_visible_locked(&mut state).is_empty());
+
+        state.queue.retain(|n| n.id != first_id);
+        let announced = NotificationCenter::newly_visible_locked(&mut state);
+        assert_eq!(announced.len(), 1);
+        assert_eq!(announced[0].0.as_str(), "second");
+    }
+
+    #[test]
+    fn dismiss_timer_pauses() {
+        let start = Instant::now();
+        let mut timer = DismissTimer::new(Duration::from_secs(4), start);
+        let at = |secs| start + Duration::from_secs(secs);
+
+        assert_eq!(timer.remaining(at(1)), Some(Duration::from_secs(3)));
+        timer.pause(at(1));
+        assert_eq!(timer.remaining(at(3)), None);
+        assert_eq!(timer.fraction_left(at(3)), 0.75);
+
+        timer.resume(at(3));
+        assert_eq!(timer.remaining(at(5)), Some(Duration::from_secs(1)));
+        assert_eq!(timer.remaining(at(9)), Some(Duration::ZERO));
+        assert_eq!(timer.fraction_left(at(9)), 0.0);
+    }
+
+    #[test]
+    fn progress_bar_only_for_timed_notifications() {
+        assert!(Notification::new("Saved").shows_progress_bar());
+        assert!(
+            !Notification::new("Saved")
+                .progress_bar(false)
+                .shows_progress_bar()
+        );
+        assert!(!Notification::new("Saved").sticky(true).shows_progress_bar());
+        let manual = Notification::new("Saved").dismiss(DismissStrategy::Manual);
+        assert!(!manual.shows_progress_bar());
+    }
+
     #[test]
     fn max_queue_len_trims_oldest() {
         let center = NotificationCenter::new();
@@ -447,4 +1251,132 @@ mod tests {
         assert_eq!(items[0].message.as_str(), "2");
         assert_eq!(items[1].message.as_str(), "3");
     }
+
+    #[test]
+    fn newest_actionable_notification_resolves_its_click_callback() {
+        let center = NotificationCenter::new();
+        let n = Notification::new("Upload failed")
+            .action_label("Retry")
+            .dismiss(DismissStrategy::Manual);
+        let id = n.id;
+        let cb: ClickCb = Arc::new(|_, _, _, _| {});
+        {
+            let mut state = center.state.lock().unwrap();
+            state.queue.push_back(Notification::new("Saved"));
+            state.queue.push_back(n);
+            state.on_click.insert(id, cb.clone());
+        }
+
+        assert_eq!(center.newest_actionable(), Some(id));
+        let (target, action) = center.click_target(id).expect("action callback");
+        assert_eq!(target.id, id);
+        assert!(Arc::ptr_eq(&action, &cb));
+    }
+
+    #[test]
+    fn actions_fall_back_to_the_click_callback() {
+        let center = NotificationCenter::new();
+        let install: ClickCb = Arc::new(|_, _, _, _| {});
+        let on_click: ClickCb = Arc::new(|_, _, _, _| {});
+        let n = Notification::new("Update available")
+            .dismiss(DismissStrategy::Manual)
+            .action(NotificationAction::new("Later").dismiss(true))
+            .action(NotificationAction {
+                on_click: Some(install.clone()),
+                ..NotificationAction::new("Install").variant(ActionVariantKind::Primary)
+            })
+            .action_label("Details");
+        let id = n.id;
+        assert_eq!(n.primary_action(), Some(1));
+        {
+            let mut state = center.state.lock().unwrap();
+            state.queue.push_back(n);
+            state.on_click.insert(id, on_click.clone());
+        }
+
+        let (_, cb, dismiss) = center.action_target(id, 0).unwrap();
+        assert!(Arc::ptr_eq(&cb.unwrap(), &on_click));
+        assert!(dismiss);
+        let (_, cb, dismiss) = center.action_target(id, 1).unwrap();
+        assert!(Arc::ptr_eq(&cb.unwrap(), &install));
+        // Manual notifications stay open unless the action says otherwise.
+        assert!(!dismiss);
+        assert!(center.action_target(id, 3).is_none());
+    }
+
+    #[test]
+    fn focus_policy_never_interrupts_typing_by_default() {
+        assert_eq!(NotificationFocus::default(), NotificationFocus::WhenIdle);
+        assert!(NotificationFocus::WhenIdle.should_focus(false));
+        assert!(!NotificationFocus::WhenIdle.should_focus(true));
+        assert!(NotificationFocus::Always.should_focus(true));
+        assert!(!NotificationFocus::Never.should_focus(false));
+    }
+
+    #[test]
+    fn burst_beyond_rate_limit_collapses_into_summary() {
+        let center = NotificationCenter::new();
+        center.set_max_queue_len(10);
+        center.set_rate_limit(Some(RateLimit::new(2, Duration::from_secs(1))));
+        let start = Instant::now();
+        let en = Locale::new("en").unwrap();
+
+        let mut state = center.state.lock().unwrap();
+        let mut queued = Vec::new();
+        for i in 0..7 {
+            let n = Notification::new(format!("copy failed: {i}")).kind(ToastKind::Error);
+            queued.extend(NotificationCenter::enqueue_locked(
+                &mut state, n, start, &en,
+            ));
+        }
+
+        let messages: Vec<_> = state.queue.iter().map(|n| n.message.to_string()).collect();
+        assert_eq!(messages, ["copy failed: 0", "copy failed: 1", "+5 more"]);
+        // Two shown items plus the summary, created once and then updated in place.
+        assert_eq!(queued.len(), 3);
+
+        let later = start + Duration::from_secs(1);
+        let n = Notification::new("copy done");
+        let id = n.id;
+        assert_eq!(
+            NotificationCenter::enqueue_locked(&mut state, n, later, &en),
+            Some(id)
+        );
+        assert_eq!(state.queue.len(), 4);
+    }
+
+    #[test]
+    fn rate_limit_without_summary_drops_excess() {
+        let center = NotificationCenter::new();
+        center.set_rate_limit(Some(
+            RateLimit::new(1, Duration::from_secs(1)).summarize(false),
+        ));
+        let now = Instant::now();
+        let en = Locale::new("en").unwrap();
+
+        let mut state = center.state.lock().unwrap();
+        for i in 0..3 {
+            let n = Notification::new(format!("{i}"));
+            NotificationCenter::enqueue_locked(&mut state, n, now, &en);
+        }
+
+        assert_eq!(state.queue.len(), 1);
+        assert_eq!(state.queue[0].message.as_str(), "0");
+    }
+
+    #[test]
+    fn legacy_action_label_loads_as_an_action() {
+        let mut stored = serde_json::to_value(Notification::new("Upload failed")).unwrap();
+        stored["action_label"] = "Retry".into();
+
+        let loaded: Notification = serde_json::from_value(stored).unwrap();
+        assert_eq!(loaded.actions.len(), 1);
+        assert_eq!(loaded.actions[0].label.as_str(), "Retry");
+        assert_eq!(loaded.actions[0].variant, ActionVariantKind::Primary);
+
+        let saved = Notification::new("Saved").action_label("Undo");
+        let reloaded: Notification =
+            serde_json::from_value(serde_json::to_value(&saved).unwrap()).unwrap();
+        assert_eq!(reloaded.actions.len(), 1);
+    }
 }
@@ -1,16 +1,37 @@
+use std::time::Duration;
+
 use gpui::{
-    App, ClickEvent, Hsla, InteractiveElement, IntoElement, ParentElement, RenderOnce,
-    StatefulInteractiveElement, Styled, Window, div, px,
+    Animation, AnimationExt, App, AppContext, ClickEvent, Empty, Entity, FocusHandle, Hsla,
+    InteractiveElement, IntoElement, MouseButton, MouseDownEvent, ParentElement, RenderOnce,
+    StatefulInteractiveElement, Styled, Window, actions, div, px, relative,
 };
 
 use gpui::prelude::FluentBuilder;
+use uuid::Uuid;
 
 use crate::{
-    component::{Icon, IconName, label, toast},
-    notification::{DismissStrategy, NotificationCenter},
-    theme::ActiveTheme,
+    animation::ease_out_quint_clamped,
+    component::{Icon, IconName, button, label, toast},
+    i18n::{I18nContext, defaults::DefaultPlaceholders},
+    notification::{Notification, NotificationCenter, ToastPosition},
+    theme::{ActionVariantKind, ActiveTheme},
 };
 
+actions!(ui_notification, [ActivateNotification, DismissNotification]);
+
+/// Horizontal drag distance past which a released toast is dismissed.
+const SWIPE_DISMISS_DISTANCE: f32 = 80.;
+/// How far a swiped toast travels while sliding out.
+const SWIPE_EXIT_DISTANCE: f32 = 320.;
+const SWIPE_EXIT_DURATION: Duration = Duration::from_millis(150);
+
+pub(crate) fn init(cx: &mut App) {
+    cx.bind_keys([
+        gpui::KeyBinding::new("enter", ActivateNotification, Some("UINotification")),
+        gpui::KeyBinding::new("escape", DismissNotification, Some("UINotification")),
+    ]);
+}
+
 /// A host element that renders the global [`NotificationCenter`] as a toast stack.
 ///
 /// Render this once near the root of your window (e.g. as the last child of your app root)
@@ -24,6 +45,7 @@ pub struct NotificationHost {
     base: gpui::Div,
     max_width: gpui::Pixels,
     offset: gpui::Pixels,
+    position: Option<ToastPosition>,
 }
 
 impl Default for NotificationHost {
@@ -38,6 +60,7 @@ impl NotificationHost {
             base: div(),
             max_width: px(420.),
             offset: px(16.),
+            position: None,
         }
     }
 
@@ -47,11 +70,18 @@ impl NotificationHost {
         self
     }
 
-    /// Offset from the top-right corner.
+    /// Offset from the window edges the stack is anchored to.
     pub fn offset(mut self, offset: gpui::Pixels) -> Self {
         self.offset = offset;
         self
     }
+
+    /// Where to place the toast stack, overriding
+    /// [`NotificationCenter::set_position`].
+    pub fn position(mut self, position: ToastPosition) -> Self {
+        self.position = Some(position);
+        self
+    }
 }
 
 impl ParentElement for NotificationHost {
@@ -92,26 +122,121 @@ impl RenderOnce for NotificationHost {
         let sub = window.observe_global::<NotificationCenter>(cx, |_window, _cx| {});
         sub.detach();
 
-        let items = center.items();
+        let items = center.visible_items();
         let theme = cx.theme().clone();
 
-        self.base
+        let focus_handles = items
+            .iter()
+            .map(|n| {
+                if !n.is_actionable() {
+                    return Vec::new();
+                }
+                let count = n.actions.len();
+                window
+                    .use_keyed_state(
+                        ("ui:notification:action-focus", n.id.as_u128() as u64),
+                        cx,
+                        move |_, cx| {
+                            (0..count)
+                                .map(|_| cx.focus_handle().tab_stop(true))
+                                .collect::<Vec<_>>()
+                        },
+                    )
+                    .read(cx)
+                    .clone()
+            })
+            .collect::<Vec<_>>();
+
+        // Move focus to a newly shown actionable notification once, if the policy allows.
+        let newest = center.newest_actionable();
+        let last_focused =
+            window.use_keyed_state("ui:notification-host:autofocus", cx, |_, _| None::<Uuid>);
+        if newest.is_some() && newest != *last_focused.read(cx) {
+            last_focused.update(cx, |last, _| *last = newest);
+            let window_has_focus = window.focused(cx).is_some();
+            if center.focus_policy().should_focus(window_has_focus)
+                && let Some(handle) = items
+                    .iter()
+                    .zip(&focus_handles)
+                    .find(|(n, _)| Some(n.id) == newest)
+                    .and_then(|(n, handles)| handles.get(n.primary_action()?))
+            {
+                handle.focus(window);
+            }
+        }
+        let position = self.position.unwrap_or_else(|| center.position());
+        // The newest toast sits at the anchored edge.
+        let mut rows = items.into_iter().zip(focus_handles).collect::<Vec<_>>();
+        if position.is_top() {
+            rows.reverse();
+        }
+        let slide_from = position.slide_offset(px(24.));
+        let offset = self.offset;
+
+        let host = self
+            .base
             .id("ui:notification-host")
             .absolute()
-            .top_0()
-            .right_0()
-            .mt(self.offset)
-            .mr(self.offset)
             .flex()
             .flex_col()
-            .gap_2()
-            .items_end()
-            .children(items.into_iter().rev().map(move |n| {
+            .gap_2();
+        let host = if position.is_top() {
+            host.top_0().mt(offset)
+        } else {
+            host.bottom_0().mb(offset)
+        };
+        let host = match position {
+            ToastPosition::TopLeft | ToastPosition::BottomLeft => {
+                host.left_0().ml(offset).items_start()
+            }
+            ToastPosition::TopCenter | ToastPosition::BottomCenter => {
+                host.left_0().right_0().items_center()
+            }
+            ToastPosition::TopRight | ToastPosition::BottomRight => {
+                host.right_0().mr(offset).items_end()
+            }
+        };
+
+        // Waiting notifications collapse into a chip at the far end of the stack.
+        let pending = center.pending_count();
+        let overflow = (pending > 0 && center.overflow_summary()).then(|| {
+            div()
+                .id("ui:notification-host:overflow")
+                .px_2()
+                .py_0p5()
+                .rounded_full()
+                .text_xs()
+                .bg(theme.surface.raised)
+                .text_color(theme.content.secondary)
+                .border_1()
+                .border_color(theme.border.default)
+                .child(DefaultPlaceholders::more_count(cx.i18n().locale(), pending))
+        });
+        let (overflow_before, overflow_after) = if position.is_top() {
+            (None, overflow)
+        } else {
+            (overflow, None)
+        };
+
+        host.children(overflow_before)
+            .children(rows.into_iter().map(move |(n, focus)| {
                 let id = n.id;
-                let dismiss = n.dismiss.clone();
 
                 let center_for_click = center.clone();
                 let center_for_dismiss = center.clone();
+                let center_for_hover = center.clone();
+                let center_for_release = center.clone();
+                let center_for_release_out = center.clone();
+
+                let swipe = window.use_keyed_state(
+                    ("ui:notification:swipe", id.as_u128() as u64),
+                    cx,
+                    |_, _| Swipe::default(),
+                );
+                let (swipe_offset, swipe_opacity, leaving) = {
+                    let swipe = swipe.read(cx);
+                    (swipe.offset, swipe.opacity(), swipe.leaving)
+                };
 
                 fn adjust_hover(bg: Hsla) -> Hsla {
                     let delta = if bg.l > 0.5 { -0.06 } else { 0.06 };
@@ -173,13 +298,11 @@ impl RenderOnce for NotificationHost {
                     })
                     .child(label(n.message.clone()).inherit_color(true).ellipsis(false));
 
-                if let Some(action) = n.action_label.clone() {
-                    body = body.child(
-                        div()
-                            .text_xs()
-                            .opacity(0.85)
-                            .child(label(action).inherit_color(true)),
-                    );
+                if !focus.is_empty() {
+                    let actions = focus.into_iter().enumerate().map(|(index, focus)| {
+                        action_button(&n, index, focus, fg, close_hover_bg, center.clone(), window)
+                    });
+                    body = body.child(div().flex().flex_wrap().gap_2().children(actions));
                 }
 
                 let toast_el = toast()
@@ -189,21 +312,331 @@ impl RenderOnce for NotificationHost {
                     .content(body)
                     .trailing(close);
 
+                // A bar along the bottom edge depletes until the auto-dismiss.
+                let progress = n
+                    .shows_progress_bar()
+                    .then(|| center.auto_dismiss_progress(id))
+                    .flatten();
+                if matches!(progress, Some((_, true))) {
+                    window.request_animation_frame();
+                }
+                let progress_color = match n.kind {
+                    crate::component::ToastKind::Neutral => theme.border.focus,
+                    _ => {
+                        let delta = if bg.l > 0.5 { -0.3 } else { 0.3 };
+                        Hsla {
+                            l: (bg.l + delta).clamp(0.0, 1.0),
+                            ..bg
+                        }
+                    }
+                };
+                let toast_el = div()
+                    .relative()
+                    .left(px(swipe_offset))
+                    .opacity(swipe_opacity)
+                    .child(toast_el)
+                    .when_some(progress, |this, (left, _)| {
+                        this.child(
+                            div()
+                                .absolute()
+                                .bottom_0()
+                                .left_0()
+                                .right_0()
+                                .px(px(4.))
+                                .child(
+                                    div()
+                                        .h(px(3.))
+                                        .w(relative(left))
+                                        .rounded_full()
+                                        .bg(progress_color),
+                                ),
+                        )
+                    });
+                // A toast swiped past the threshold slides out before it is dismissed.
+                let toast_el = match leaving {
+                    Some(direction) => toast_el
+                        .with_animation(
+                            ("ui:notification:leave", id.as_u128() as u64),
+                            Animation::new(SWIPE_EXIT_DURATION).with_easing(ease_out_quint_clamped),
+                            move |this, delta| {
+                                this.left(
+                                    px(swipe_offset + direction * SWIPE_EXIT_DISTANCE * delta),
+                                )
+                                .opacity(swipe_opacity * (1. - delta))
+                            },
+                        )
+                        .into_any_element(),
+                    None => toast_el.into_any_element(),
+                };
+
                 div()
                     .id(("ui:notification", id.as_u128() as u64))
                     .cursor_pointer()
+                    .on_mouse_down(MouseButton::Left, {
+                        let swipe = swipe.clone();
+                        move |ev: &MouseDownEvent, _window, cx| {
+                            swipe.update(cx, |swipe, _| swipe.begin(f32::from(ev.position.x)));
+                        }
+                    })
+                    .on_drag(SwipeDrag(id), |_drag: &SwipeDrag, _pos, _window, cx| {
+                        cx.new(|_| Empty)
+                    })
+                    .on_drag_move::<SwipeDrag>({
+                        let swipe = swipe.clone();
+                        move |ev, window, cx| {
+                            if ev.drag(cx).0 != id {
+                                return;
+                            }
+                            let x = f32::from(ev.event.position.x);
+                            swipe.update(cx, |swipe, _| swipe.drag(x));
+                            window.refresh();
+                        }
+                    })
+                    .on_mouse_up(MouseButton::Left, {
+                        let swipe = swipe.clone();
+                        move |_ev, window, cx| {
+                            finish_swipe(&swipe, &center_for_release, id, window, cx);
+                        }
+                    })
+                    .on_mouse_up_out(MouseButton::Left, move |_ev, window, cx| {
+                        finish_swipe(&swipe, &center_for_release_out, id, window, cx);
+                    })
                     .on_click(move |ev: &ClickEvent, window, cx| {
-                        center_for_click.click(id, ev, window, cx);
-                        if matches!(dismiss, DismissStrategy::After { .. }) {
-                            center_for_click.dismiss_from_ui(id, window, cx);
+                        center_for_click.activate(id, ev, window, cx);
+                        window.refresh();
+                    })
+                    .on_hover(move |hovered, window, _cx| {
+                        if *hovered {
+                            center_for_hover.pause_auto_dismiss(id);
+                        } else {
+                            center_for_hover.resume_auto_dismiss(id);
                         }
                         window.refresh();
                     })
                     .flex()
                     .flex_col()
-                    .items_end()
                     .gap_1()
                     .child(toast_el)
+                    .with_animation(
+                        ("ui:notification:enter", id.as_u128() as u64),
+                        Animation::new(Duration::from_millis(200))
+                            .with_easing(ease_out_quint_clamped),
+                        move |this, delta| {
+                            this.left(slide_from.x * (1. - delta))
+                                .top(slide_from.y * (1. - delta))
+                                .opacity(delta)
+                        },
+                    )
             }))
+            .children(overflow_after)
+    }
+}
+
+/// Drag payload of a toast being swiped.
+struct SwipeDrag(Uuid);
+
+/// Horizontal swipe state of one toast.
+#[derive(Default)]
+struct Swipe {
+    /// Pointer x where the press started, while the button is held.
+    origin: Option<f32>,
+    offset: f32,
+    /// Direction (`-1.0` or `1.0`) the toast slides out in after a dismissing swipe.
+    leaving: Option<f32>,
+}
+
+impl Swipe {
+    fn begin(&mut self, x: f32) {
+        if self.leaving.is_none() {
+            self.origin = Some(x);
+            self.offset = 0.;
+        }
+    }
+
+    fn drag(&mut self, x: f32) {
+        if let Some(origin) = self.origin {
+            self.offset = x - origin;
+        }
+    }
+
+    /// Ends the swipe. Returns `true` if it went far enough to dismiss; otherwise
+    /// the toast snaps back.
+    fn release(&mut self) -> bool {
+        if self.origin.take().is_none() {
+            return false;
+        }
+        if self.offset.abs() >= SWIPE_DISMISS_DISTANCE {
+            self.leaving = Some(self.offset.signum());
+            true
+        } else {
+            self.offset = 0.;
+            false
+        }
+    }
+
+    /// The toast fades towards half opacity as it nears the dismiss distance.
+    fn opacity(&self) -> f32 {
+        1. - (self.offset.abs() / SWIPE_DISMISS_DISTANCE).min(1.) * 0.5
+    }
+}
+
+fn finish_swipe(
+    swipe: &Entity<Swipe>,
+    center: &NotificationCenter,
+    id: Uuid,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let dismissed = swipe.update(cx, |swipe, _| swipe.release());
+    if dismissed {
+        let center = center.clone();
+        window
+            .spawn(cx, async move |cx| {
+                cx.background_executor().timer(SWIPE_EXIT_DURATION).await;
+                cx.update(|window, cx| center.dismiss_from_ui(id, window, cx))
+                    .ok();
+            })
+            .detach();
+    }
+    window.refresh();
+}
+
+/// A focusable action button of a notification.
+///
+/// Primary and danger actions are filled with their theme colors; neutral ones
+/// are outlined in the toast's text color. Clicks run the action without
+/// activating the notification itself. When focused, Enter runs the action and
+/// Escape dismisses the notification.
+fn action_button(
+    n: &Notification,
+    index: usize,
+    focus: FocusHandle,
+    fg: Hsla,
+    hover_bg: Hsla,
+    center: NotificationCenter,
+    window: &Window,
+) -> impl IntoElement {
+    let id = n.id;
+    let action = &n.actions[index];
+    let focused = focus.is_focused(window);
+    let outlined = action.variant == ActionVariantKind::Neutral;
+    let center_for_click = center.clone();
+    let center_for_dismiss = center.clone();
+
+    button(("ui:notification:action", index))
+        .variant(action.variant)
+        .when(outlined, |this| {
+            this.bg(gpui::transparent_black()).hover_bg(hover_bg)
+        })
+        .key_context("UINotification")
+        .track_focus(&focus)
+        .h(px(26.))
+        .px_2()
+        .py_0p5()
+        .rounded_sm()
+        .text_xs()
+        .border_1()
+        .border_color(Hsla {
+            a: match (focused, outlined) {
+                (true, _) => 0.9,
+                (false, true) => 0.35,
+                (false, false) => 0.,
+            },
+            ..fg
+        })
+        .on_click(move |ev, window, cx| {
+            cx.stop_propagation();
+            center_for_click.activate_action(id, index, ev, window, cx);
+            window.refresh();
+        })
+        .on_action(move |_: &ActivateNotification, window, cx| {
+            center.activate_action(id, index, &ClickEvent::default(), window, cx);
+            window.refresh();
+        })
+        .on_action(move |_: &DismissNotification, window, cx| {
+            center_for_dismiss.dismiss_from_ui(id, window, cx);
+            window.refresh();
+        })
+        .child(
+            div()
+                .when(outlined, |this| this.text_color(fg))
+                .child(label(action.label.clone()).inherit_color(true)),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    use gpui::{Render, TestAppContext, WindowAppearance};
+
+    use super::*;
+    use crate::notification::{NotificationAction, NotificationFocus};
+    use crate::theme::GlobalTheme;
+
+    #[test]
+    fn swipe_dismisses_past_the_threshold() {
+        let mut swipe = Swipe::default();
+        swipe.begin(100.);
+        swipe.drag(140.);
+        assert!(swipe.opacity() < 1.);
+        assert!(!swipe.release());
+        assert_eq!(swipe.offset, 0.);
+        assert_eq!(swipe.leaving, None);
+
+        swipe.begin(100.);
+        swipe.drag(100. - SWIPE_DISMISS_DISTANCE);
+        assert!(swipe.release());
+        assert_eq!(swipe.leaving, Some(-1.));
+
+        // A leaving toast ignores further presses, and a release without a
+        // press does nothing.
+        swipe.begin(0.);
+        assert!(!swipe.release());
+    }
+
+    struct Host;
+
+    impl Render for Host {
+        fn render(
+            &mut self,
+            _window: &mut Window,
+            _cx: &mut gpui::Context<Self>,
+        ) -> impl IntoElement {
+            div().size_full().child(notification_host())
+        }
+    }
+
+    #[gpui::test]
+    fn enter_runs_the_focused_action(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            cx.set_global(GlobalTheme::new(WindowAppearance::Light));
+            crate::component::init(cx);
+            cx.set_global(crate::i18n::I18n::new());
+            cx.set_global(NotificationCenter::new());
+        });
+        let (_, cx) = cx.add_window_view(|_, _| Host);
+        cx.run_until_parked();
+
+        let ran = Rc::new(Cell::new(0));
+        cx.update(|_, cx| {
+            let center = cx.global::<NotificationCenter>().clone();
+            center.set_focus_policy(NotificationFocus::Always);
+            let ran = ran.clone();
+            center.notify(
+                Notification::new("File deleted").action(
+                    NotificationAction::new("Undo")
+                        .variant(ActionVariantKind::Primary)
+                        .on_click(move |_, _, _, _| ran.set(ran.get() + 1)),
+                ),
+                cx,
+            );
+        });
+        cx.run_until_parked();
+        assert!(cx.update(|window, cx| window.focused(cx).is_some()));
+
+        cx.simulate_keystrokes("enter");
+        assert_eq!(ran.get(), 1);
     }
 }
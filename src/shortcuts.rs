@@ -0,0 +1,171 @@
+//! App-level keyboard shortcuts.
+//!
+//! Shortcuts registered here are bound with gpui's `bind_keys` and handled by a
+//! global action listener, so they work wherever focus is, as long as no focused
+//! element handles the action first. The registry also remembers each shortcut,
+//! letting a "keyboard shortcuts" help sheet list them with
+//! [`registered_shortcuts`].
+//!
+//! ```rust,ignore
+//! use gpui::actions;
+//! use yororen_ui::shortcuts::{Shortcut, register_shortcut};
+//!
+//! actions!(app, [OpenCommandPalette]);
+//!
+//! register_shortcut(
+//!     cx,
+//!     Shortcut::new("secondary-k", "Open command palette"),
+//!     OpenCommandPalette,
+//!     |_, cx| palette.update(cx, |palette, cx| palette.open(cx)),
+//! );
+//! ```
+
+use gpui::{Action, App, Global, KeyBinding, Keystroke, SharedString};
+
+use crate::component::format_keybinding_ui;
+
+/// A keyboard shortcut and what it does, as listed by [`registered_shortcuts`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Shortcut {
+    keystrokes: SharedString,
+    description: SharedString,
+    context: Option<SharedString>,
+}
+
+impl Shortcut {
+    /// Creates a shortcut from gpui keystroke syntax, e.g. `"cmd-s"` or
+    /// `"ctrl-k ctrl-t"` for a sequence. `secondary` means Cmd on macOS and Ctrl
+    /// elsewhere.
+    pub fn new(keystrokes: impl Into<SharedString>, description: impl Into<SharedString>) -> Self {
+        Self {
+            keystrokes: keystrokes.into(),
+            description: description.into(),
+            context: None,
+        }
+    }
+
+    /// Limits the shortcut to focus inside elements with a matching
+    /// `key_context`, using gpui's context predicate syntax.
+    pub fn context(mut self, context: impl Into<SharedString>) -> Self {
+        self.context = Some(context.into());
+        self
+    }
+
+    /// The keystrokes in gpui syntax, as registered.
+    pub fn keystrokes(&self) -> &SharedString {
+        &self.keystrokes
+    }
+
+    pub fn description(&self) -> &SharedString {
+        &self.description
+    }
+
+    pub fn context_predicate(&self) -> Option<&SharedString> {
+        self.context.as_ref()
+    }
+
+    /// The keystrokes formatted for this platform: `⇧⌘K` on macOS, `Ctrl+Shift+K`
+    /// elsewhere.
+    pub fn display_keys(&self) -> SharedString {
+        format_keystrokes(&self.keystrokes, cfg!(target_os = "macos"))
+    }
+}
+
+#[derive(Default)]
+struct ShortcutRegistry {
+    shortcuts: Vec<Shortcut>,
+}
+
+impl Global for ShortcutRegistry {}
+
+/// Binds `shortcut` to `action` and runs `handler` whenever the action reaches
+/// the app unhandled.
+///
+/// Register each shortcut once, e.g. at startup. An action type should have a
+/// single handler; bind more keystrokes to it by registering it again with the
+/// same handler.
+pub fn register_shortcut<A: Action>(
+    cx: &mut App,
+    shortcut: Shortcut,
+    action: A,
+    handler: impl Fn(&A, &mut App) + 'static,
+) {
+    cx.bind_keys([KeyBinding::new(
+        &shortcut.keystrokes,
+        action,
+        shortcut.context.as_ref().map(|context| context.as_ref()),
+    )]);
+    cx.on_action(handler);
+    cx.default_global::<ShortcutRegistry>()
+        .shortcuts
+        .push(shortcut);
+}
+
+/// Every shortcut registered with [`register_shortcut`], in registration order.
+pub fn registered_shortcuts(cx: &App) -> Vec<Shortcut> {
+    cx.try_global::<ShortcutRegistry>()
+        .map(|registry| registry.shortcuts.clone())
+        .unwrap_or_default()
+}
+
+/// Formats gpui keystroke syntax with macOS symbols or as `Ctrl+Shift+K`.
+/// Keystrokes that do not parse are shown as written.
+fn format_keystrokes(source: &str, mac: bool) -> SharedString {
+    source
+        .split_whitespace()
+        .map(|chord| match Keystroke::parse(chord) {
+            Ok(keystroke) if mac => format_keybinding_ui(&keystroke).to_string(),
+            Ok(keystroke) => format_chord(&keystroke),
+            Err(_) => chord.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+        .into()
+}
+
+fn format_chord(keystroke: &Keystroke) -> String {
+    let m = &keystroke.modifiers;
+    let platform = if cfg!(target_os = "windows") {
+        "Win"
+    } else {
+        "Super"
+    };
+    let mut parts = Vec::new();
+    for (held, name) in [
+        (m.control, "Ctrl"),
+        (m.alt, "Alt"),
+        (m.shift, "Shift"),
+        (m.platform, platform),
+        (m.function, "Fn"),
+    ] {
+        if held {
+            parts.push(name.to_string());
+        }
+    }
+    parts.push(match keystroke.key.as_str() {
+        "escape" => "Esc".to_string(),
+        key if key.chars().count() == 1 => key.to_uppercase(),
+        key => {
+            let mut chars = key.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect())
+                .unwrap_or_default()
+        }
+    });
+    parts.join("+")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keystrokes_format_per_platform() {
+        assert_eq!(format_keystrokes("ctrl-shift-k", true), "⌃⇧K");
+        assert_eq!(format_keystrokes("ctrl-shift-k", false), "Ctrl+Shift+K");
+        assert_eq!(format_keystrokes("alt-escape", false), "Alt+Esc");
+        assert_eq!(format_keystrokes("ctrl-k ctrl-t", false), "Ctrl+K Ctrl+T");
+        assert_eq!(format_keystrokes("f5", false), "F5");
+    }
+}
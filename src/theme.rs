@@ -1,10 +1,18 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
 use std::sync::Arc;
 
-use gpui::{App, Global, Hsla, WindowAppearance, hsla, rgb};
+use gpui::{
+    App, BoxShadow, FontWeight, Global, Hsla, Pixels, Rgba, SharedString, Styled, Subscription,
+    Window, WindowAppearance, hsla, point, px, rgb,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
-use crate::i18n::TextDirection;
+use crate::i18n::{I18n, TextDirection};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Theme {
     pub surface: SurfaceTheme,
     pub content: ContentTheme,
@@ -12,11 +20,18 @@ pub struct Theme {
     pub action: ActionTheme,
     pub status: StatusTheme,
     pub shadow: ShadowTheme,
-    /// Text direction (LTR or RTL)
+    /// Spacing for padding, gaps and indents, per [`Scale`] step.
+    #[serde(default = "ScaleTheme::default_spacing")]
+    pub spacing: ScaleTheme,
+    /// Corner radii, per [`Scale`] step.
+    #[serde(default = "ScaleTheme::default_radius")]
+    pub radius: ScaleTheme,
+    /// Text direction (LTR or RTL). Not part of the JSON form.
+    #[serde(skip, default = "Theme::default_text_direction")]
     pub text_direction: TextDirection,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SurfaceTheme {
     pub canvas: Hsla,
     pub base: Hsla,
@@ -25,7 +40,7 @@ pub struct SurfaceTheme {
     pub hover: Hsla,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ContentTheme {
     pub primary: Hsla,
     pub secondary: Hsla,
@@ -35,7 +50,7 @@ pub struct ContentTheme {
     pub on_status: Hsla,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct BorderTheme {
     pub default: Hsla,
     pub muted: Hsla,
@@ -43,14 +58,14 @@ pub struct BorderTheme {
     pub divider: Hsla,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ActionTheme {
     pub neutral: ActionVariant,
     pub primary: ActionVariant,
     pub danger: ActionVariant,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ActionVariant {
     pub bg: Hsla,
     pub hover_bg: Hsla,
@@ -60,14 +75,14 @@ pub struct ActionVariant {
     pub disabled_fg: Hsla,
 }
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum ActionVariantKind {
     Neutral,
     Primary,
     Danger,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct StatusTheme {
     pub success: StatusVariant,
     pub warning: StatusVariant,
@@ -75,16 +90,64 @@ pub struct StatusTheme {
     pub info: StatusVariant,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct StatusVariant {
     pub bg: Hsla,
     pub fg: Hsla,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ShadowTheme {
     pub elevation_1: Hsla,
     pub elevation_2: Hsla,
+    /// Laid over the view behind drawers and other modal overlays.
+    pub scrim: Hsla,
+}
+
+/// A size for each [`Scale`] step.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ScaleTheme {
+    pub xs: Pixels,
+    pub sm: Pixels,
+    pub md: Pixels,
+    pub lg: Pixels,
+    pub xl: Pixels,
+    pub xxl: Pixels,
+}
+
+impl ScaleTheme {
+    /// The built-in spacing scale. See [`Scale::spacing`].
+    pub fn default_spacing() -> Self {
+        Self::from_fn(Scale::spacing)
+    }
+
+    /// The built-in radius scale. See [`Scale::radius`].
+    pub fn default_radius() -> Self {
+        Self::from_fn(Scale::radius)
+    }
+
+    /// The size for `scale`.
+    pub fn get(&self, scale: Scale) -> Pixels {
+        match scale {
+            Scale::Xs => self.xs,
+            Scale::Sm => self.sm,
+            Scale::Md => self.md,
+            Scale::Lg => self.lg,
+            Scale::Xl => self.xl,
+            Scale::Xxl => self.xxl,
+        }
+    }
+
+    fn from_fn(size: fn(Scale) -> Pixels) -> Self {
+        Self {
+            xs: size(Scale::Xs),
+            sm: size(Scale::Sm),
+            md: size(Scale::Md),
+            lg: size(Scale::Lg),
+            xl: size(Scale::Xl),
+            xxl: size(Scale::Xxl),
+        }
+    }
 }
 
 impl Theme {
@@ -160,7 +223,10 @@ impl Theme {
             shadow: ShadowTheme {
                 elevation_1: hsla(0.0, 0.0, 0.0, 0.3),
                 elevation_2: hsla(0.0, 0.0, 0.0, 0.45),
+                scrim: hsla(0.0, 0.0, 0.0, 0.55),
             },
+            spacing: ScaleTheme::default_spacing(),
+            radius: ScaleTheme::default_radius(),
             text_direction: TextDirection::Ltr,
         }
     }
@@ -237,7 +303,92 @@ impl Theme {
             shadow: ShadowTheme {
                 elevation_1: hsla(0.0, 0.0, 0.0, 0.18),
                 elevation_2: hsla(0.0, 0.0, 0.0, 0.3),
+                scrim: hsla(0.0, 0.0, 0.0, 0.35),
+            },
+            spacing: ScaleTheme::default_spacing(),
+            radius: ScaleTheme::default_radius(),
+            text_direction: TextDirection::Ltr,
+        }
+    }
+
+    /// A black-and-white theme for low vision, with maximum contrast between
+    /// `content`, `border` and `surface`.
+    pub fn high_contrast() -> Self {
+        let content = ContentTheme {
+            primary: rgb(0xFFFFFF).into(),
+            secondary: rgb(0xF0F0F0).into(),
+            tertiary: rgb(0xD6D6D6).into(),
+            disabled: rgb(0x8C8C8C).into(),
+            on_primary: rgb(0x000000).into(),
+            on_status: rgb(0x000000).into(),
+        };
+
+        Self {
+            surface: SurfaceTheme {
+                canvas: rgb(0x000000).into(),
+                base: rgb(0x000000).into(),
+                raised: rgb(0x000000).into(),
+                sunken: rgb(0x000000).into(),
+                hover: rgb(0x1F1F1F).into(),
+            },
+            content: content.clone(),
+            border: BorderTheme {
+                default: rgb(0xFFFFFF).into(),
+                muted: rgb(0xD6D6D6).into(),
+                focus: rgb(0xFFD400).into(),
+                divider: rgb(0xFFFFFF).into(),
+            },
+            action: ActionTheme {
+                neutral: ActionVariant {
+                    bg: rgb(0x000000).into(),
+                    hover_bg: rgb(0x1F1F1F).into(),
+                    active_bg: rgb(0x333333).into(),
+                    fg: content.primary,
+                    disabled_bg: rgb(0x000000).into(),
+                    disabled_fg: content.disabled,
+                },
+                primary: ActionVariant {
+                    bg: rgb(0xFFFFFF).into(),
+                    hover_bg: rgb(0xFFD400).into(),
+                    active_bg: rgb(0xE6E6E6).into(),
+                    fg: content.on_primary,
+                    disabled_bg: rgb(0x262626).into(),
+                    disabled_fg: content.disabled,
+                },
+                danger: ActionVariant {
+                    bg: rgb(0xFF9C94).into(),
+                    hover_bg: rgb(0xFFB4AE).into(),
+                    active_bg: rgb(0xFF8A82).into(),
+                    fg: content.on_status,
+                    disabled_bg: rgb(0x262626).into(),
+                    disabled_fg: content.disabled,
+                },
+            },
+            status: StatusTheme {
+                success: StatusVariant {
+                    bg: rgb(0x8CF5A8).into(),
+                    fg: content.on_status,
+                },
+                warning: StatusVariant {
+                    bg: rgb(0xFFD400).into(),
+                    fg: content.on_status,
+                },
+                error: StatusVariant {
+                    bg: rgb(0xFF9C94).into(),
+                    fg: content.on_status,
+                },
+                info: StatusVariant {
+                    bg: rgb(0x9CCBFF).into(),
+                    fg: content.on_status,
+                },
             },
+            shadow: ShadowTheme {
+                elevation_1: hsla(0.0, 0.0, 0.0, 0.5),
+                elevation_2: hsla(0.0, 0.0, 0.0, 0.7),
+                scrim: hsla(0.0, 0.0, 0.0, 0.7),
+            },
+            spacing: ScaleTheme::default_spacing(),
+            radius: ScaleTheme::default_radius(),
             text_direction: TextDirection::Ltr,
         }
     }
@@ -252,6 +403,71 @@ impl Theme {
         TextDirection::Ltr
     }
 
+    /// Parse a theme from JSON, using [`Theme::default_light`] for every token
+    /// the JSON leaves out.
+    ///
+    /// The JSON mirrors the token tree, with colors as `#rgb`, `#rgba`,
+    /// `#rrggbb` or `#rrggbbaa` strings and sizes as pixel numbers:
+    ///
+    /// ```json
+    /// {
+    ///   "surface": { "canvas": "#f4f4f6", "base": "#ffffff", "raised": "…", "sunken": "…", "hover": "…" },
+    ///   "content": {
+    ///     "primary": "#141416", "secondary": "…", "tertiary": "…", "disabled": "…",
+    ///     "on_primary": "…", "on_status": "…"
+    ///   },
+    ///   "border": { "default": "…", "muted": "…", "focus": "…", "divider": "…" },
+    ///   "action": {
+    ///     "neutral": {
+    ///       "bg": "…", "hover_bg": "…", "active_bg": "…", "fg": "…",
+    ///       "disabled_bg": "…", "disabled_fg": "…"
+    ///     },
+    ///     "primary": { "…": "same keys as neutral" },
+    ///     "danger": { "…": "same keys as neutral" }
+    ///   },
+    ///   "status": {
+    ///     "success": { "bg": "…", "fg": "…" },
+    ///     "warning": { "bg": "…", "fg": "…" },
+    ///     "error": { "bg": "…", "fg": "…" },
+    ///     "info": { "bg": "…", "fg": "…" }
+    ///   },
+    ///   "shadow": { "elevation_1": "#0000002e", "elevation_2": "#0000004d", "scrim": "#00000059" },
+    ///   "spacing": { "xs": 4, "sm": 8, "md": 12, "lg": 16, "xl": 20, "xxl": 32 },
+    ///   "radius": { "xs": 2, "sm": 4, "md": 6, "lg": 8, "xl": 12, "xxl": 16 }
+    /// }
+    /// ```
+    ///
+    /// Missing tokens, unknown keys and values of the wrong kind are ignored;
+    /// only malformed JSON is an error.
+    pub fn from_json(json: &str) -> Result<Self, ThemeLoadError> {
+        Self::from_json_with_base(json, &Self::default_light())
+    }
+
+    /// Like [`from_json`](Self::from_json), falling back to `base` for tokens
+    /// the JSON leaves out.
+    pub fn from_json_with_base(json: &str, base: &Theme) -> Result<Self, ThemeLoadError> {
+        let overrides: Value =
+            serde_json::from_str(json).map_err(|e| ThemeLoadError::ParseError(e.to_string()))?;
+        if !overrides.is_object() {
+            return Err(ThemeLoadError::ParseError(
+                "expected a JSON object of theme tokens".into(),
+            ));
+        }
+        let mut tokens =
+            serde_json::to_value(base).map_err(|e| ThemeLoadError::ParseError(e.to_string()))?;
+        overlay_tokens(&mut tokens, &overrides);
+        let mut theme: Theme = serde_json::from_value(tokens)
+            .map_err(|e| ThemeLoadError::ParseError(e.to_string()))?;
+        theme.text_direction = base.text_direction;
+        Ok(theme)
+    }
+
+    /// Serialize every color token to pretty-printed JSON, in the format
+    /// [`from_json`](Self::from_json) reads.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("theme tokens are always serializable")
+    }
+
     pub fn action_variant(&self, variant: ActionVariantKind) -> &ActionVariant {
         match variant {
             ActionVariantKind::Neutral => &self.action.neutral,
@@ -259,10 +475,245 @@ impl Theme {
             ActionVariantKind::Danger => &self.action.danger,
         }
     }
+
+    /// Spacing for padding, gaps and indents, from this theme's `spacing` scale.
+    pub fn spacing(&self, scale: Scale) -> Pixels {
+        self.spacing.get(scale)
+    }
+
+    /// Corner radius, from this theme's `radius` scale.
+    pub fn radius(&self, scale: Scale) -> Pixels {
+        self.radius.get(scale)
+    }
+
+    /// Box shadows for `level`, tinted with this theme's `shadow` colors.
+    pub fn elevation(&self, level: ElevationLevel) -> Vec<BoxShadow> {
+        let (color, y, blur) = match level {
+            ElevationLevel::Flat => return Vec::new(),
+            ElevationLevel::Raised => (self.shadow.elevation_1, 1., 3.),
+            ElevationLevel::Overlay => (self.shadow.elevation_2, 4., 12.),
+        };
+        vec![BoxShadow {
+            color,
+            offset: point(px(0.), px(y)),
+            blur_radius: px(blur),
+            spread_radius: px(0.),
+        }]
+    }
+
+    /// Size, line height and weight for a kind of text.
+    pub fn text_style(&self, role: TextRole) -> TextStyleToken {
+        let (size, line_height, weight) = match role {
+            TextRole::Caption => (12., 16., FontWeight::NORMAL),
+            TextRole::Body => (14., 20., FontWeight::NORMAL),
+            TextRole::Heading => (18., 24., FontWeight::SEMIBOLD),
+        };
+        TextStyleToken {
+            size: px(size),
+            line_height: px(line_height),
+            weight,
+        }
+    }
+}
+
+/// Steps of the spacing and radius scales, so components reference tokens
+/// instead of raw pixel values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Scale {
+    /// Extra small: hairline gaps and the tightest corners.
+    Xs,
+    /// Small: gaps between related items.
+    Sm,
+    /// Medium: control padding.
+    Md,
+    /// Large: padding inside panels and cards.
+    Lg,
+    /// Extra large: tree indents and section gaps.
+    Xl,
+    /// Twice extra large: page margins.
+    Xxl,
+}
+
+impl Scale {
+    /// Default spacing: 4, 8, 12, 16, 20 or 32 px. Components read
+    /// [`Theme::spacing`], which a theme can override.
+    pub fn spacing(self) -> Pixels {
+        px(match self {
+            Scale::Xs => 4.,
+            Scale::Sm => 8.,
+            Scale::Md => 12.,
+            Scale::Lg => 16.,
+            Scale::Xl => 20.,
+            Scale::Xxl => 32.,
+        })
+    }
+
+    /// Default radius: 2, 4, 6, 8, 12 or 16 px; `Sm`, `Md` and `Lg` match
+    /// gpui's `rounded_*`. Components read [`Theme::radius`].
+    pub fn radius(self) -> Pixels {
+        px(match self {
+            Scale::Xs => 2.,
+            Scale::Sm => 4.,
+            Scale::Md => 6.,
+            Scale::Lg => 8.,
+            Scale::Xl => 12.,
+            Scale::Xxl => 16.,
+        })
+    }
+}
+
+/// How far a surface sits above the canvas.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ElevationLevel {
+    /// No shadow.
+    Flat,
+    /// Cards and raised controls.
+    Raised,
+    /// Popovers, menus and modals.
+    Overlay,
+}
+
+/// Named text styles.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TextRole {
+    /// Running text and control labels.
+    Body,
+    /// Secondary text such as hints, counters and timestamps.
+    Caption,
+    /// Titles of panels, dialogs and sections.
+    Heading,
+}
+
+/// A resolved [`TextRole`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TextStyleToken {
+    pub size: Pixels,
+    pub line_height: Pixels,
+    pub weight: FontWeight,
+}
+
+impl TextStyleToken {
+    /// Apply the size, line height and weight to `element`.
+    pub fn apply<E: Styled>(self, element: E) -> E {
+        element
+            .text_size(self.size)
+            .line_height(self.line_height)
+            .font_weight(self.weight)
+    }
+}
+
+/// Whether the theme follows the OS appearance or is pinned to light or dark.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ThemeMode {
+    /// Follow the OS appearance.
+    #[default]
+    System,
+    /// Always use the light palette.
+    Light,
+    /// Always use the dark palette.
+    Dark,
+}
+
+impl ThemeMode {
+    /// The appearance to pick a palette for, given the OS appearance.
+    pub fn resolve(self, system: WindowAppearance) -> WindowAppearance {
+        match self {
+            ThemeMode::System => system,
+            ThemeMode::Light => WindowAppearance::Light,
+            ThemeMode::Dark => WindowAppearance::Dark,
+        }
+    }
+}
+
+/// Copy each color or size in `overrides` onto the matching token in `base`,
+/// leaving tokens that are missing, unknown or of the wrong kind untouched.
+fn overlay_tokens(base: &mut Value, overrides: &Value) {
+    match (base, overrides) {
+        (Value::Object(base), Value::Object(overrides)) => {
+            for (key, token) in base.iter_mut() {
+                if let Some(value) = overrides.get(key) {
+                    overlay_tokens(token, value);
+                }
+            }
+        }
+        (token @ Value::String(_), Value::String(color))
+            if Rgba::try_from(color.as_str()).is_ok() =>
+        {
+            *token = Value::String(color.clone());
+        }
+        (token @ Value::Number(_), Value::Number(size)) => {
+            *token = Value::Number(size.clone());
+        }
+        _ => {}
+    }
+}
+
+/// Error type for theme loading.
+#[derive(Debug)]
+pub enum ThemeLoadError {
+    /// Failed to read the theme file.
+    ReadError(String),
+    /// Failed to parse JSON.
+    ParseError(String),
+}
+
+impl fmt::Display for ThemeLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThemeLoadError::ReadError(msg) => write!(f, "Read error: {}", msg),
+            ThemeLoadError::ParseError(msg) => write!(f, "Parse error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for ThemeLoadError {}
+
+/// Loads a theme from a JSON file, the way [`FileLoader`](crate::i18n::FileLoader)
+/// loads translations.
+///
+/// ```ignore
+/// let theme = ThemeFileLoader::new("themes/solarized.json").load()?;
+/// cx.default_global::<ThemeRegistry>().register("solarized", theme);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ThemeFileLoader {
+    path: PathBuf,
+    base: Option<Arc<Theme>>,
+}
+
+impl ThemeFileLoader {
+    /// Create a loader for the theme file at `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            base: None,
+        }
+    }
+
+    /// Fill tokens the file leaves out from `base` instead of the light theme.
+    pub fn with_base(mut self, base: impl Into<Arc<Theme>>) -> Self {
+        self.base = Some(base.into());
+        self
+    }
+
+    /// Read and parse the theme file.
+    pub fn load(&self) -> Result<Theme, ThemeLoadError> {
+        let content = std::fs::read_to_string(&self.path)
+            .map_err(|e| ThemeLoadError::ReadError(format!("{}: {}", self.path.display(), e)))?;
+        match &self.base {
+            Some(base) => Theme::from_json_with_base(&content, base),
+            None => Theme::from_json(&content),
+        }
+    }
 }
 
 pub struct GlobalTheme {
     theme: Arc<Theme>,
+    themes: ThemeSet,
+    mode: ThemeMode,
+    system_appearance: WindowAppearance,
+    /// Name of the [`ThemeRegistry`] theme in use, overriding `theme`.
+    active: Option<SharedString>,
 }
 
 impl Global for GlobalTheme {}
@@ -278,11 +729,169 @@ impl GlobalTheme {
     pub fn new_with_themes(appearance: WindowAppearance, themes: ThemeSet) -> Self {
         Self {
             theme: themes.resolve(appearance),
+            themes,
+            mode: ThemeMode::System,
+            system_appearance: appearance,
+            active: None,
         }
     }
 
+    /// Start pinned to `mode` instead of following the OS appearance.
+    pub fn with_mode(mut self, mode: ThemeMode) -> Self {
+        self.mode = mode;
+        self.resolve();
+        self
+    }
+
+    /// The current mode.
+    pub fn mode(&self) -> ThemeMode {
+        self.mode
+    }
+
+    /// Name of the activated [`ThemeRegistry`] theme, if any.
+    pub fn active(&self) -> Option<&SharedString> {
+        self.active.as_ref()
+    }
+
+    /// Switch to `mode` on the global theme and re-render all windows.
+    ///
+    /// Only the palette changes: component state, focus and running animations
+    /// are kept. This also leaves any [activated](Self::activate) registry theme.
+    pub fn set_mode(mode: ThemeMode, cx: &mut App) {
+        let system = cx.window_appearance();
+        Self::update(cx, |theme| {
+            theme.system_appearance = system;
+            theme.mode = mode;
+            theme.active = None;
+        });
+    }
+
+    /// Switch to the [`ThemeRegistry`] theme registered as `name` and re-render
+    /// all windows. Returns `false`, changing nothing, if there is no such theme.
+    ///
+    /// The theme stays in use, whatever the mode or OS appearance, until another
+    /// one is activated or [`set_mode`](Self::set_mode) is called.
+    pub fn activate(name: impl Into<SharedString>, cx: &mut App) -> bool {
+        let name = name.into();
+        if !cx.default_global::<ThemeRegistry>().contains(&name) {
+            return false;
+        }
+        Self::update(cx, |theme| theme.active = Some(name));
+        true
+    }
+
+    /// Replace the global theme with a single custom palette, used in every
+    /// mode, and re-render all windows.
+    pub fn set_theme(theme: impl Into<Arc<Theme>>, cx: &mut App) {
+        Self::set_themes(ThemeSet::new(theme), cx);
+    }
+
+    /// Replace the global light/dark palettes and re-render all windows.
+    pub fn set_themes(themes: ThemeSet, cx: &mut App) {
+        Self::update(cx, |theme| {
+            theme.themes = themes;
+            theme.active = None;
+        });
+    }
+
+    /// Keep the global theme in sync with `window`'s OS appearance while the
+    /// mode is [`ThemeMode::System`]. Keep the subscription alive (or
+    /// `detach` it) for as long as the theme should follow the OS.
+    pub fn observe_system_appearance(window: &Window) -> Subscription {
+        window.observe_window_appearance(|window, cx| {
+            let system = window.appearance();
+            if cx.global::<Self>().system_appearance != system {
+                Self::update(cx, |theme| theme.system_appearance = system);
+            }
+        })
+    }
+
+    fn update(cx: &mut App, f: impl FnOnce(&mut Self)) {
+        let theme = cx.global_mut::<Self>();
+        f(theme);
+        theme.resolve();
+        cx.refresh_windows();
+    }
+
+    fn resolve(&mut self) {
+        self.theme = self
+            .themes
+            .resolve(self.mode.resolve(self.system_appearance));
+    }
+
     fn theme(cx: &App) -> &Arc<Theme> {
-        &cx.global::<Self>().theme
+        let global = cx.global::<Self>();
+        global
+            .active
+            .as_ref()
+            .and_then(|name| cx.try_global::<ThemeRegistry>()?.get(name))
+            .unwrap_or(&global.theme)
+    }
+}
+
+/// Named themes that [`GlobalTheme::activate`] can switch to.
+///
+/// The default registry holds the built-in [`LIGHT`](Self::LIGHT),
+/// [`DARK`](Self::DARK) and [`HIGH_CONTRAST`](Self::HIGH_CONTRAST) themes.
+/// Register more with `cx.default_global::<ThemeRegistry>().register(..)`;
+/// re-registering the active theme's name updates it on the next render.
+#[derive(Clone, Debug)]
+pub struct ThemeRegistry {
+    themes: HashMap<SharedString, Arc<Theme>>,
+}
+
+impl Global for ThemeRegistry {}
+
+impl Default for ThemeRegistry {
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry.register(Self::LIGHT, Theme::default_light());
+        registry.register(Self::DARK, Theme::default_dark());
+        registry.register(Self::HIGH_CONTRAST, Theme::high_contrast());
+        registry
+    }
+}
+
+impl ThemeRegistry {
+    pub const LIGHT: &'static str = "light";
+    pub const DARK: &'static str = "dark";
+    pub const HIGH_CONTRAST: &'static str = "high_contrast";
+
+    /// A registry with the built-in themes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A registry without any themes.
+    pub fn empty() -> Self {
+        Self {
+            themes: HashMap::new(),
+        }
+    }
+
+    /// Register `theme` as `name`, replacing any theme with that name.
+    pub fn register(&mut self, name: impl Into<SharedString>, theme: impl Into<Arc<Theme>>) {
+        self.themes.insert(name.into(), theme.into());
+    }
+
+    /// Remove the theme registered as `name`.
+    pub fn unregister(&mut self, name: &str) -> Option<Arc<Theme>> {
+        self.themes.remove(name)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Arc<Theme>> {
+        self.themes.get(name)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.themes.contains_key(name)
+    }
+
+    /// Registered theme names, sorted.
+    pub fn names(&self) -> Vec<SharedString> {
+        let mut names: Vec<_> = self.themes.keys().cloned().collect();
+        names.sort();
+        names
     }
 }
 
@@ -320,6 +929,11 @@ impl ThemeSet {
 
 pub trait ActiveTheme {
     fn theme(&self) -> &Arc<Theme>;
+
+    /// The direction components lay themselves out in.
+    fn text_direction(&self) -> TextDirection {
+        self.theme().text_direction
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -344,9 +958,46 @@ pub fn interactive_colors(theme: &Theme) -> InteractiveColors {
     }
 }
 
+/// Themes pushed by enclosing [`theme_provider`](crate::component::theme_provider)s.
+///
+/// The innermost override wins; with an empty stack the global theme applies.
+#[derive(Default)]
+pub(crate) struct ThemeStack {
+    stack: Vec<Arc<Theme>>,
+}
+
+impl Global for ThemeStack {}
+
+impl ThemeStack {
+    pub(crate) fn push(&mut self, theme: Arc<Theme>) {
+        self.stack.push(theme);
+    }
+
+    pub(crate) fn pop(&mut self) {
+        self.stack.pop();
+    }
+
+    fn resolve<'a>(&'a self, global: &'a Arc<Theme>) -> &'a Arc<Theme> {
+        self.stack.last().unwrap_or(global)
+    }
+}
+
 impl ActiveTheme for App {
     fn theme(&self) -> &Arc<Theme> {
-        GlobalTheme::theme(self)
+        let global = GlobalTheme::theme(self);
+        match self.try_global::<ThemeStack>() {
+            Some(stack) => stack.resolve(global),
+            None => global,
+        }
+    }
+
+    /// The active locale's direction when an [`I18n`] global is set, otherwise
+    /// the theme's `text_direction`.
+    fn text_direction(&self) -> TextDirection {
+        match self.try_global::<I18n>() {
+            Some(i18n) => i18n.text_direction(),
+            None => self.theme().text_direction,
+        }
     }
 }
 
@@ -382,11 +1033,44 @@ mod tests {
         assert!(ratio >= min, "{label} contrast {ratio:.2} below {min:.2}");
     }
 
+    #[test]
+    fn theme_stack_scopes_overrides() {
+        let global = Arc::new(Theme::default_light());
+        let mut sidebar = Theme::default_light();
+        sidebar.surface.base = hsla(0.0, 0.0, 0.1, 1.0);
+        let mut stack = ThemeStack::default();
+
+        stack.push(Arc::new(sidebar));
+        let inside = stack.resolve(&global).surface.base;
+        stack.pop();
+        let sibling = stack.resolve(&global).surface.base;
+
+        assert_eq!(inside, hsla(0.0, 0.0, 0.1, 1.0));
+        assert_eq!(sibling, global.surface.base);
+    }
+
+    #[test]
+    fn nested_theme_overrides_use_innermost() {
+        let global = Arc::new(Theme::default_light());
+        let outer = Arc::new(Theme::default_dark());
+        let inner = Arc::new(Theme::default_light());
+        let mut stack = ThemeStack::default();
+
+        stack.push(outer.clone());
+        stack.push(inner.clone());
+        assert!(Arc::ptr_eq(stack.resolve(&global), &inner));
+        stack.pop();
+        assert!(Arc::ptr_eq(stack.resolve(&global), &outer));
+        stack.pop();
+        assert!(Arc::ptr_eq(stack.resolve(&global), &global));
+    }
+
     #[test]
     fn theme_contrast_requirements() {
         for (name, theme) in [
             ("dark", Theme::default_dark()),
             ("light", Theme::default_light()),
+            ("high_contrast", Theme::high_contrast()),
         ] {
             assert_contrast_at_least(
                 &format!("{name}: surface.base/content.primary"),
@@ -438,4 +1122,143 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn mode_pins_appearance() {
+        let dark = Arc::new(Theme::default_dark());
+        let themes = ThemeSet::new(Theme::default_light()).dark(dark.clone());
+        let mut theme = GlobalTheme::new_with_themes(WindowAppearance::Light, themes);
+        assert!(!Arc::ptr_eq(&theme.theme, &dark));
+
+        theme.system_appearance = WindowAppearance::Dark;
+        theme.resolve();
+        assert!(Arc::ptr_eq(&theme.theme, &dark));
+
+        theme = theme.with_mode(ThemeMode::Light);
+        assert!(!Arc::ptr_eq(&theme.theme, &dark));
+        assert_eq!(
+            ThemeMode::Dark.resolve(WindowAppearance::VibrantLight),
+            WindowAppearance::Dark
+        );
+    }
+
+    #[test]
+    fn high_contrast_meets_aaa() {
+        let theme = Theme::high_contrast();
+        for (label, fg) in [
+            ("content.primary", theme.content.primary),
+            ("content.secondary", theme.content.secondary),
+            ("content.tertiary", theme.content.tertiary),
+            ("border.default", theme.border.default),
+            ("border.focus", theme.border.focus),
+        ] {
+            for bg in [
+                theme.surface.base,
+                theme.surface.raised,
+                theme.surface.hover,
+            ] {
+                assert_contrast_at_least(label, bg, fg, 7.0);
+            }
+        }
+    }
+
+    #[test]
+    fn registry_holds_builtins_and_custom_themes() {
+        let mut registry = ThemeRegistry::new();
+        assert_eq!(
+            registry.names(),
+            vec![
+                SharedString::from(ThemeRegistry::DARK),
+                SharedString::from(ThemeRegistry::HIGH_CONTRAST),
+                SharedString::from(ThemeRegistry::LIGHT),
+            ]
+        );
+
+        let brand = Arc::new(Theme::default_dark());
+        registry.register("brand", brand.clone());
+        assert!(Arc::ptr_eq(registry.get("brand").unwrap(), &brand));
+        assert!(registry.unregister("brand").is_some());
+        assert!(!registry.contains("brand"));
+        assert!(ThemeRegistry::empty().names().is_empty());
+    }
+
+    #[test]
+    fn json_round_trip() {
+        for theme in [
+            Theme::default_light(),
+            Theme::default_dark(),
+            Theme::high_contrast(),
+        ] {
+            let json = theme.to_json();
+            let parsed = Theme::from_json(&json).unwrap();
+            assert_eq!(parsed.to_json(), json);
+        }
+    }
+
+    #[test]
+    fn json_falls_back_per_token() {
+        let dark = Theme::default_dark();
+        let theme = Theme::from_json_with_base(
+            r##"{
+                "surface": { "base": "#102030", "hover": "not a color" },
+                "action": { "primary": { "bg": "#ff0000" } },
+                "unknown": { "token": "#ffffff" }
+            }"##,
+            &dark,
+        )
+        .unwrap();
+        assert_eq!(theme.surface.base, Hsla::from(rgb(0x102030)));
+        assert_eq!(theme.action.primary.bg, Hsla::from(rgb(0xff0000)));
+        assert_eq!(theme.surface.hover.to_rgb(), dark.surface.hover.to_rgb());
+        assert_eq!(
+            theme.content.primary.to_rgb(),
+            dark.content.primary.to_rgb()
+        );
+
+        assert!(Theme::from_json("{ not json").is_err());
+        assert!(Theme::from_json("[]").is_err());
+    }
+
+    #[test]
+    fn token_scales_grow() {
+        let theme = Theme::default_light();
+        let scales = [
+            Scale::Xs,
+            Scale::Sm,
+            Scale::Md,
+            Scale::Lg,
+            Scale::Xl,
+            Scale::Xxl,
+        ];
+        for pair in scales.windows(2) {
+            assert!(theme.spacing(pair[0]) < theme.spacing(pair[1]));
+            assert!(theme.radius(pair[0]) < theme.radius(pair[1]));
+        }
+        assert_eq!(theme.spacing(Scale::Md), px(12.));
+        assert_eq!(theme.radius(Scale::Md), Scale::Md.radius());
+
+        assert!(theme.elevation(ElevationLevel::Flat).is_empty());
+        let raised = theme.elevation(ElevationLevel::Raised);
+        let overlay = theme.elevation(ElevationLevel::Overlay);
+        assert_eq!(raised[0].color, theme.shadow.elevation_1);
+        assert!(overlay[0].blur_radius > raised[0].blur_radius);
+
+        assert!(theme.text_style(TextRole::Caption).size < theme.text_style(TextRole::Body).size);
+    }
+
+    #[test]
+    fn json_overrides_spacing_and_radius() {
+        let theme =
+            Theme::from_json(r#"{ "spacing": { "md": 10, "lg": "wide" }, "radius": { "sm": 3 } }"#)
+                .unwrap();
+        assert_eq!(theme.spacing(Scale::Md), px(10.));
+        assert_eq!(theme.spacing(Scale::Lg), Scale::Lg.spacing());
+        assert_eq!(theme.radius(Scale::Sm), px(3.));
+
+        // Themes saved before the scales existed still load.
+        let mut tokens = serde_json::to_value(Theme::default_dark()).unwrap();
+        tokens.as_object_mut().unwrap().remove("spacing");
+        let theme: Theme = serde_json::from_value(tokens).unwrap();
+        assert_eq!(theme.spacing(Scale::Xl), Scale::Xl.spacing());
+    }
 }
@@ -1,22 +1,247 @@
+use std::ops::Range;
+use std::rc::Rc;
+
 use gpui::{
-    AnyElement, IntoElement, ListAlignment, ListSizingBehavior, Pixels, RenderOnce, Styled, list,
+    AnyElement, App, Div, ElementId, FontWeight, IntoElement, ListAlignment, ListOffset,
+    ListSizingBehavior, ParentElement, Pixels, Refineable, RenderOnce, SharedString, Styled,
+    Window, div, list, px,
 };
 
+use crate::theme::ActiveTheme;
+
+/// Space left above an item scrolled to with [`ScrollAlign::Top`].
+const SCROLL_TO_ITEM_MARGIN: Pixels = px(8.);
+
 #[allow(clippy::type_complexity)]
 type RenderRowFn = Box<dyn FnMut(usize, &mut gpui::Window, &mut gpui::App) -> AnyElement + 'static>;
 
+/// Callback type for the height of an item, by index.
+type ItemHeightFn = Rc<dyn Fn(usize) -> Pixels>;
+
+/// Callback type for reaching either end of a list.
+type ReachCallback = Rc<dyn Fn(&mut Window, &mut App)>;
+
+/// Section grouping for [`VirtualList::sticky_sections`].
+#[derive(Clone)]
+struct Sections {
+    section_of: Rc<dyn Fn(usize) -> SharedString>,
+    header_height: Pixels,
+}
+
+impl Sections {
+    /// Whether item `ix` is the first of its section.
+    fn starts_at(&self, ix: usize) -> bool {
+        ix == 0 || (self.section_of)(ix) != (self.section_of)(ix - 1)
+    }
+
+    /// The header of item `ix`'s section.
+    fn header(&self, ix: usize, cx: &App) -> Div {
+        let theme = cx.theme();
+        div()
+            .flex()
+            .items_center()
+            .w_full()
+            .h(self.header_height)
+            .px_3()
+            .bg(theme.surface.raised)
+            .text_sm()
+            .font_weight(FontWeight::SEMIBOLD)
+            .text_color(theme.content.secondary)
+            .child((self.section_of)(ix))
+    }
+}
+
+/// How far the pinned header is pushed above the viewport when the top item,
+/// `row_height` tall and scrolled `offset_in_item` out of view, is the last of
+/// its section.
+fn pinned_header_offset(
+    row_height: Pixels,
+    offset_in_item: Pixels,
+    header_height: Pixels,
+) -> Pixels {
+    (row_height - offset_in_item - header_height).min(px(0.))
+}
+
+/// Running totals of item heights, for lists whose items have known heights
+/// that differ from one another.
+///
+/// `gpui::ListState` only learns an item's height by laying it out, so offsets
+/// past the rendered items are unknown. With the heights known up front, this
+/// index maps between item indices and content offsets in `O(log n)`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HeightIndex {
+    /// The bottom edge of each item, measured from the top of the content.
+    ends: Vec<Pixels>,
+}
+
+impl HeightIndex {
+    pub fn new(count: usize, height: impl Fn(usize) -> Pixels) -> Self {
+        let mut end = px(0.);
+        let ends = (0..count)
+            .map(|ix| {
+                end += height(ix);
+                end
+            })
+            .collect();
+        Self { ends }
+    }
+
+    pub fn len(&self) -> usize {
+        self.ends.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ends.is_empty()
+    }
+
+    /// The height of all items together.
+    pub fn total_height(&self) -> Pixels {
+        self.ends.last().copied().unwrap_or(px(0.))
+    }
+
+    /// The offset of item `ix`'s top edge; past the end, the total height.
+    pub fn item_top(&self, ix: usize) -> Pixels {
+        match ix.checked_sub(1) {
+            Some(prev) => self.ends.get(prev).copied().unwrap_or(self.total_height()),
+            None => px(0.),
+        }
+    }
+
+    pub fn item_height(&self, ix: usize) -> Pixels {
+        self.ends
+            .get(ix)
+            .map_or(px(0.), |end| *end - self.item_top(ix))
+    }
+
+    /// The scroll position that puts content offset `y` at the top of the viewport.
+    pub fn offset_at(&self, y: Pixels) -> ListOffset {
+        let y = y.max(px(0.));
+        let item_ix = self.ends.partition_point(|end| *end <= y);
+        ListOffset {
+            item_ix,
+            offset_in_item: if item_ix < self.len() {
+                y - self.item_top(item_ix)
+            } else {
+                px(0.)
+            },
+        }
+    }
+}
+
+/// Where [`VirtualListController::scroll_to_item`] places an item in the viewport.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ScrollAlign {
+    /// Near the top edge, with a small margin showing the end of the item above.
+    #[default]
+    Top,
+    Center,
+    Bottom,
+    /// Scroll as little as needed to show the whole item, or not at all if it
+    /// is already fully visible.
+    Nearest,
+}
+
+/// The item heights a controller scrolls by.
+#[derive(Clone, Debug)]
+enum RowHeights {
+    Fixed(Pixels),
+    Index(Rc<HeightIndex>),
+}
+
+impl RowHeights {
+    /// The top edge and height of item `ix`.
+    fn item(&self, ix: usize) -> (Pixels, Pixels) {
+        match self {
+            RowHeights::Fixed(height) => (*height * ix as f32, *height),
+            RowHeights::Index(index) => (index.item_top(ix), index.item_height(ix)),
+        }
+    }
+
+    fn total(&self, count: usize) -> Pixels {
+        match self {
+            RowHeights::Fixed(height) => *height * count as f32,
+            RowHeights::Index(index) => index.total_height(),
+        }
+    }
+
+    fn offset_at(&self, y: Pixels) -> ListOffset {
+        match self {
+            RowHeights::Fixed(height) if *height > px(0.) => {
+                let item_ix = (y / *height).floor() as usize;
+                ListOffset {
+                    item_ix,
+                    offset_in_item: y - *height * item_ix as f32,
+                }
+            }
+            RowHeights::Fixed(_) => ListOffset {
+                item_ix: 0,
+                offset_in_item: px(0.),
+            },
+            RowHeights::Index(index) => index.offset_at(y),
+        }
+    }
+}
+
+/// The scroll offset that places `item` at `align` in a `viewport`-tall window
+/// onto `content`-tall content currently scrolled to `scroll_top`, clamped to the
+/// scrollable range. `None` means no scroll is needed.
+fn aligned_scroll_top(
+    align: ScrollAlign,
+    item: Range<Pixels>,
+    scroll_top: Pixels,
+    viewport: Pixels,
+    content: Pixels,
+) -> Option<Pixels> {
+    let height = item.end - item.start;
+    let goal = match align {
+        ScrollAlign::Top => item.start - SCROLL_TO_ITEM_MARGIN,
+        ScrollAlign::Center => item.start - (viewport - height) / 2.,
+        ScrollAlign::Bottom => item.end - viewport,
+        ScrollAlign::Nearest => {
+            if item.start < scroll_top {
+                item.start
+            } else if item.end > scroll_top + viewport {
+                item.end - viewport
+            } else {
+                return None;
+            }
+        }
+    };
+    Some(goal.min(content - viewport).max(px(0.)))
+}
+
 /// Controller for a [`VirtualList`].
 ///
 /// This is intentionally a thin wrapper over `gpui::ListState` so Yororen UI users
 /// don't have to call `reset/splice/scroll_to_reveal_item` directly.
+///
+/// [`scroll_to_item`](Self::scroll_to_item) positions items from their heights:
+/// a fixed row height (32px unless set with
+/// [`with_row_height`](Self::with_row_height)) or a [`HeightIndex`].
 #[derive(Clone, Debug)]
 pub struct VirtualListController {
     state: gpui::ListState,
+    heights: RowHeights,
 }
 
 impl VirtualListController {
     pub fn new(state: gpui::ListState) -> Self {
-        Self { state }
+        Self {
+            state,
+            heights: RowHeights::Fixed(px(32.)),
+        }
+    }
+
+    /// Scroll by a fixed height shared by every item.
+    pub fn with_row_height(mut self, height: Pixels) -> Self {
+        self.heights = RowHeights::Fixed(height);
+        self
+    }
+
+    /// Scroll by per-item heights, for lists whose rows differ in height.
+    pub fn with_heights(mut self, heights: Rc<HeightIndex>) -> Self {
+        self.heights = RowHeights::Index(heights);
+        self
     }
 
     pub fn state(&self) -> gpui::ListState {
@@ -34,6 +259,39 @@ impl VirtualListController {
     pub fn scroll_to_reveal_item(&self, ix: usize) {
         self.state.scroll_to_reveal_item(ix);
     }
+
+    /// Scroll item `ix` to `align` within the viewport and repaint.
+    ///
+    /// The offset is computed from the item heights, so it is exact even for
+    /// items that haven't been laid out, and clamped so the list never scrolls
+    /// past either end. An `ix` past the end scrolls to the last item. Before the
+    /// list's first layout the viewport size is unknown, so every alignment
+    /// behaves like `Top`.
+    pub fn scroll_to_item(&self, ix: usize, align: ScrollAlign, window: &mut gpui::Window) {
+        let count = self.state.item_count();
+        if count == 0 {
+            return;
+        }
+        let ix = ix.min(count - 1);
+
+        let (top, height) = self.heights.item(ix);
+        let current = self.state.logical_scroll_top();
+        let scroll_top = self.heights.item(current.item_ix).0 + current.offset_in_item;
+        let viewport = self.state.viewport_bounds().size.height;
+        let (align, viewport) = if viewport > px(0.) {
+            (align, viewport)
+        } else {
+            (ScrollAlign::Top, px(0.))
+        };
+        let content = self.heights.total(count).max(viewport);
+
+        if let Some(goal) =
+            aligned_scroll_top(align, top..top + height, scroll_top, viewport, content)
+        {
+            self.state.scroll_to(self.heights.offset_at(goal));
+            window.refresh();
+        }
+    }
 }
 
 /// Widget: a virtualized list based on `gpui::list`.
@@ -49,9 +307,14 @@ impl VirtualListController {
 ///   [`VirtualListController::splice`] or [`VirtualListController::reset`].
 #[derive(IntoElement)]
 pub struct VirtualList {
+    element_id: ElementId,
     state: gpui::ListState,
     sizing_behavior: ListSizingBehavior,
     render_row: RenderRowFn,
+    measured_heights: Option<ItemHeightFn>,
+    sections: Option<Sections>,
+    on_reach_start: Option<(usize, ReachCallback)>,
+    on_reach_end: Option<(usize, ReachCallback)>,
     style: gpui::StyleRefinement,
 }
 
@@ -61,17 +324,122 @@ impl VirtualList {
         render_row: impl FnMut(usize, &mut gpui::Window, &mut gpui::App) -> AnyElement + 'static,
     ) -> Self {
         Self {
+            element_id: "ui:virtual-list".into(),
             state,
             sizing_behavior: ListSizingBehavior::default(),
             render_row: Box::new(render_row),
+            measured_heights: None,
+            sections: None,
+            on_reach_start: None,
+            on_reach_end: None,
             style: gpui::StyleRefinement::default(),
         }
     }
 
+    /// Set a stable element ID for internal keyed state.
+    ///
+    /// If multiple lists in the same window use [`on_reach_end`](Self::on_reach_end)
+    /// or [`on_reach_start`](Self::on_reach_start), give each a unique ID.
+    pub fn id(mut self, id: impl Into<ElementId>) -> Self {
+        self.element_id = id.into();
+        self
+    }
+
     pub fn with_sizing_behavior(mut self, behavior: ListSizingBehavior) -> Self {
         self.sizing_behavior = behavior;
         self
     }
+
+    /// Give each item the height returned for its index instead of its measured one.
+    ///
+    /// Rows are clipped to their height. With `ListSizingBehavior::Infer`, the
+    /// list takes the total of the heights rather than laying out every item to
+    /// find it. Lists whose rows share one height don't need this.
+    pub fn measured_heights(mut self, height: impl Fn(usize) -> Pixels + 'static) -> Self {
+        self.measured_heights = Some(Rc::new(height));
+        self
+    }
+
+    /// Group items into sections and pin the header of the section at the top
+    /// of the viewport until the next section's header pushes it up.
+    ///
+    /// `section_of` returns an item's section key, shown as its header; items of
+    /// a section must be contiguous. Each header is drawn above the first item of
+    /// its section, inside that item's row, so item indices don't change. With
+    /// [`measured_heights`](Self::measured_heights), give heights without the header.
+    pub fn sticky_sections(
+        mut self,
+        header_height: Pixels,
+        section_of: impl Fn(usize) -> SharedString + 'static,
+    ) -> Self {
+        self.sections = Some(Sections {
+            section_of: Rc::new(section_of),
+            header_height,
+        });
+        self
+    }
+
+    /// Call `handler` when the user scrolls within `threshold_rows` of the last
+    /// item, e.g. to fetch the next page of an infinite feed.
+    ///
+    /// It fires once per approach: again only after scrolling back out of the
+    /// threshold, or once the item count grows. This installs the list state's
+    /// scroll handler, replacing any set with `ListState::set_scroll_handler`.
+    pub fn on_reach_end(
+        mut self,
+        threshold_rows: usize,
+        handler: impl Fn(&mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_reach_end = Some((threshold_rows, Rc::new(handler)));
+        self
+    }
+
+    /// Like [`on_reach_end`](Self::on_reach_end), for the first item, e.g. to
+    /// load older messages at the top of a chat.
+    pub fn on_reach_start(
+        mut self,
+        threshold_rows: usize,
+        handler: impl Fn(&mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_reach_start = Some((threshold_rows, Rc::new(handler)));
+        self
+    }
+}
+
+/// Tracks which ends of a list have already fired their reach callback.
+#[derive(Debug, Default)]
+struct ReachGuard {
+    count: usize,
+    near_start: bool,
+    near_end: bool,
+}
+
+impl ReachGuard {
+    /// Records a scroll that left `visible` of `count` items in view, returning
+    /// whether the start and end callbacks should fire.
+    ///
+    /// An end fires when it comes within its threshold, and then not again until
+    /// it leaves the threshold or the item count grows.
+    fn scrolled(
+        &mut self,
+        visible: Range<usize>,
+        count: usize,
+        start_threshold: Option<usize>,
+        end_threshold: Option<usize>,
+    ) -> (bool, bool) {
+        if count > self.count {
+            self.near_start = false;
+            self.near_end = false;
+        }
+        self.count = count;
+
+        let near_start = start_threshold.is_some_and(|rows| visible.start <= rows);
+        let near_end = end_threshold.is_some_and(|rows| visible.end + rows >= count);
+        let fire = (near_start && !self.near_start, near_end && !self.near_end);
+        self.near_start = near_start;
+        self.near_end = near_end;
+        fire
+    }
 }
 
 impl Styled for VirtualList {
@@ -81,14 +449,131 @@ impl Styled for VirtualList {
 }
 
 impl RenderOnce for VirtualList {
-    fn render(self, _window: &mut gpui::Window, _cx: &mut gpui::App) -> impl IntoElement {
-        // We must preserve styling that callers applied to `VirtualList`.
-        // `gpui::List` is `Styled`, so we can transfer our style refinement onto it.
-        let mut inner =
-            list(self.state.clone(), self.render_row).with_sizing_behavior(self.sizing_behavior);
-        *inner.style() = self.style;
+    fn render(self, window: &mut gpui::Window, cx: &mut gpui::App) -> impl IntoElement {
+        if self.on_reach_start.is_some() || self.on_reach_end.is_some() {
+            let guard = window.use_keyed_state(
+                (self.element_id.clone(), "ui:virtual-list:reach"),
+                cx,
+                |_, _| ReachGuard::default(),
+            );
+            let on_reach_start = self.on_reach_start;
+            let on_reach_end = self.on_reach_end;
+            self.state.set_scroll_handler(move |event, window, cx| {
+                let (start, end) = guard.update(cx, |guard, _cx| {
+                    guard.scrolled(
+                        event.visible_range.clone(),
+                        event.count,
+                        on_reach_start.as_ref().map(|(rows, _)| *rows),
+                        on_reach_end.as_ref().map(|(rows, _)| *rows),
+                    )
+                });
+                if let Some((_, handler)) = on_reach_start.as_ref().filter(|_| start) {
+                    handler(window, cx);
+                }
+                if let Some((_, handler)) = on_reach_end.as_ref().filter(|_| end) {
+                    handler(window, cx);
+                }
+            });
+        }
 
-        inner
+        let sections = self.sections;
+        let mut render_row = self.render_row;
+        if let Some(sections) = sections.clone() {
+            render_row = Box::new(move |ix, window, cx| {
+                let row = render_row(ix, window, cx);
+                if !sections.starts_at(ix) {
+                    return row;
+                }
+                div()
+                    .flex()
+                    .flex_col()
+                    .w_full()
+                    .child(sections.header(ix, cx))
+                    .child(row)
+                    .into_any_element()
+            });
+        }
+        let measured_heights = self.measured_heights.map(|height| match sections.clone() {
+            Some(sections) => Rc::new(move |ix| {
+                let header = if sections.starts_at(ix) {
+                    sections.header_height
+                } else {
+                    px(0.)
+                };
+                height(ix) + header
+            }) as ItemHeightFn,
+            None => height,
+        });
+
+        let mut inner = match measured_heights {
+            // We must preserve styling that callers applied to `VirtualList`.
+            // `gpui::List` is `Styled`, so we can transfer our style refinement onto it.
+            None => list(self.state.clone(), render_row).with_sizing_behavior(self.sizing_behavior),
+            Some(height) => {
+                let infer = self.sizing_behavior == ListSizingBehavior::Infer;
+                let total = infer.then(|| {
+                    (0..self.state.item_count()).fold(px(0.), |total, ix| total + height(ix))
+                });
+                let mut inner = list(self.state.clone(), move |ix, window, cx| {
+                    div()
+                        .w_full()
+                        .h(height(ix))
+                        .overflow_hidden()
+                        .child(render_row(ix, window, cx))
+                        .into_any_element()
+                })
+                .with_sizing_behavior(if infer {
+                    ListSizingBehavior::Auto
+                } else {
+                    self.sizing_behavior
+                });
+                if let Some(total) = total
+                    && self.style.size.height.is_none()
+                {
+                    inner = inner.h(total);
+                }
+                inner
+            }
+        };
+
+        let Some(sections) = sections else {
+            inner.style().refine(&self.style);
+            return inner.into_any_element();
+        };
+
+        // The header of the section at the top of the viewport, pushed up by the
+        // next section's header as it scrolls in.
+        let count = self.state.item_count();
+        let pinned = (count > 0).then(|| {
+            let top = self.state.logical_scroll_top();
+            let ix = top.item_ix.min(count - 1);
+            let offset = if ix + 1 < count && sections.starts_at(ix + 1) {
+                self.state.bounds_for_item(ix).map_or(px(0.), |bounds| {
+                    pinned_header_offset(
+                        bounds.size.height,
+                        top.offset_in_item,
+                        sections.header_height,
+                    )
+                })
+            } else {
+                px(0.)
+            };
+            div()
+                .absolute()
+                .left_0()
+                .top(offset)
+                .w_full()
+                .child(sections.header(ix, cx))
+        });
+
+        let mut container = div();
+        *container.style() = self.style;
+        container
+            .relative()
+            .overflow_hidden()
+            .child(inner.size_full())
+            .children(pinned)
+            .into_any_element()
     }
 }
 
@@ -138,3 +623,91 @@ pub fn virtual_list_state(
 ) -> gpui::ListState {
     gpui::ListState::new(item_count, alignment, overdraw)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn heights() -> HeightIndex {
+        HeightIndex::new(4, |ix| px([10., 20., 30., 40.][ix]))
+    }
+
+    #[test]
+    fn offsets_follow_running_totals() {
+        let index = heights();
+        assert_eq!(index.total_height(), px(100.));
+        assert_eq!(index.item_top(2), px(30.));
+        assert_eq!(index.item_height(3), px(40.));
+        assert_eq!(index.item_top(9), px(100.));
+
+        let offset = index.offset_at(px(35.));
+        assert_eq!((offset.item_ix, offset.offset_in_item), (2, px(5.)));
+        let offset = index.offset_at(px(-5.));
+        assert_eq!((offset.item_ix, offset.offset_in_item), (0, px(0.)));
+        assert_eq!(index.offset_at(px(100.)).item_ix, 4);
+    }
+
+    #[test]
+    fn reach_fires_once_per_approach() {
+        let mut guard = ReachGuard::default();
+        let end = |guard: &mut ReachGuard, visible, count| {
+            guard.scrolled(visible, count, None, Some(3)).1
+        };
+
+        assert!(!end(&mut guard, 0..10, 50));
+        assert!(end(&mut guard, 37..47, 50));
+        assert!(!end(&mut guard, 38..48, 50));
+        // Scrolling back up re-arms it.
+        assert!(!end(&mut guard, 20..30, 50));
+        assert!(end(&mut guard, 40..50, 50));
+        // So does a new page arriving, even while still near the end.
+        assert!(end(&mut guard, 40..50, 52));
+        assert!(!end(&mut guard, 40..50, 60));
+
+        let mut guard = ReachGuard::default();
+        assert_eq!(guard.scrolled(1..10, 50, Some(2), None), (true, false));
+        assert_eq!(guard.scrolled(0..9, 50, Some(2), None), (false, false));
+    }
+
+    #[test]
+    fn pinned_header_is_pushed_by_the_next_one() {
+        let header = px(24.);
+        assert_eq!(pinned_header_offset(px(40.), px(0.), header), px(0.));
+        assert_eq!(pinned_header_offset(px(40.), px(16.), header), px(0.));
+        assert_eq!(pinned_header_offset(px(40.), px(26.), header), px(-10.));
+        assert_eq!(pinned_header_offset(px(40.), px(40.), header), px(-24.));
+    }
+
+    #[test]
+    fn alignment_offsets_are_clamped() {
+        let item = px(200.)..px(232.);
+        let top = |align, scroll_top| {
+            aligned_scroll_top(align, item.clone(), scroll_top, px(100.), px(1000.))
+        };
+        assert_eq!(top(ScrollAlign::Top, px(0.)), Some(px(192.)));
+        assert_eq!(top(ScrollAlign::Center, px(0.)), Some(px(166.)));
+        assert_eq!(top(ScrollAlign::Bottom, px(0.)), Some(px(132.)));
+
+        // Nearest moves the closer edge into view, or leaves a visible item be.
+        assert_eq!(top(ScrollAlign::Nearest, px(0.)), Some(px(132.)));
+        assert_eq!(top(ScrollAlign::Nearest, px(500.)), Some(px(200.)));
+        assert_eq!(top(ScrollAlign::Nearest, px(150.)), None);
+
+        let first = aligned_scroll_top(
+            ScrollAlign::Bottom,
+            px(0.)..px(32.),
+            px(50.),
+            px(100.),
+            px(1000.),
+        );
+        assert_eq!(first, Some(px(0.)));
+        let last = aligned_scroll_top(
+            ScrollAlign::Top,
+            px(968.)..px(1000.),
+            px(0.),
+            px(100.),
+            px(1000.),
+        );
+        assert_eq!(last, Some(px(900.)));
+    }
+}